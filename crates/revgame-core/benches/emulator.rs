@@ -0,0 +1,87 @@
+//! Emulator hot-path benchmarks.
+//!
+//! Run with `cargo bench -p revgame-core`. Criterion keeps earlier results
+//! under `target/criterion` and reports how each benchmark changed since.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use revgame_core::debugger::{CapturePolicy, Debugger, MemorySearch};
+use revgame_core::emulator::{CpuState, ExecutionResult, Executor, Memory};
+
+const CODE_START: u32 = 0x1000;
+const DATA_START: u32 = 0x2000;
+const STACK_START: u32 = 0x8000;
+
+/// `mov ecx, count; xor eax, eax; add eax, ecx; dec ecx; jnz -5; hlt`
+fn loop_program(count: u32) -> Vec<u8> {
+    let mut code = vec![0xB9];
+    code.extend_from_slice(&count.to_le_bytes());
+    code.extend_from_slice(&[0x31, 0xC0, 0x01, 0xC8, 0x49, 0x75, 0xFB, 0xF4]);
+    code
+}
+
+fn bench_execute_one(c: &mut Criterion) {
+    let mut memory = Memory::with_puzzle_layout(CODE_START, DATA_START, STACK_START);
+    // add eax, ecx; jmp -4 (tight two-instruction loop)
+    memory.load(CODE_START, &[0x01, 0xC8, 0xEB, 0xFC]).unwrap();
+    let mut cpu = CpuState::new(CODE_START, STACK_START);
+    let mut executor = Executor::new();
+
+    c.bench_function("execute_one", |b| {
+        b.iter(|| {
+            let result = executor.execute_one(&mut cpu, &mut memory).unwrap();
+            if let ExecutionResult::Continue { next_eip } = result {
+                cpu.eip = next_eip;
+            }
+            black_box(&cpu);
+        })
+    });
+}
+
+fn bench_debugger_run(c: &mut Criterion) {
+    let iterations = 100_000;
+    let mut group = c.benchmark_group("Debugger::run 300k instructions");
+    group.sample_size(20);
+    for (name, policy) in [
+        ("history", CapturePolicy::Always),
+        ("no history", CapturePolicy::StepOnly),
+    ] {
+        let mut debugger =
+            Debugger::with_puzzle_layout(CODE_START, CODE_START, DATA_START, STACK_START);
+        debugger
+            .load_code(CODE_START, &loop_program(iterations))
+            .unwrap();
+        debugger.max_instructions = u64::from(iterations) * 3 + 10;
+        debugger.set_capture_policy(policy);
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                debugger.reset();
+                black_box(debugger.run().unwrap());
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_memory_search(c: &mut Criterion) {
+    let mut memory = Memory::new(0x10000);
+    let needle = [0xDE, 0xAD, 0xBE, 0xEF];
+    for address in (0..0x10000u32).step_by(0x1000) {
+        memory.write_bytes(address + 0x800, &needle).unwrap();
+    }
+
+    c.bench_function("memory search (64 KiB, 4-byte pattern)", |b| {
+        b.iter(|| black_box(MemorySearch::search_bytes(&memory, &needle, 0, 0x10000).unwrap()))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_execute_one,
+    bench_debugger_run,
+    bench_memory_search
+);
+criterion_main!(benches);
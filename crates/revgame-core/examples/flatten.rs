@@ -0,0 +1,109 @@
+//! Control-flow flattening for puzzle authors.
+//!
+//! Takes machine code for a simple instruction sequence and prints a
+//! flattened version (dispatcher + state variable) as a `[setup.code]`
+//! snippet with its disassembly, plus `[annotations]` marking the
+//! dispatcher:
+//!
+//! ```text
+//! cargo run -p revgame-core --example flatten -- "B8 00 00 00 00 ... F4" \
+//!     [--origin 0x1000] [--code-start 0x1000] [--state 0x2000] [--seed 1]
+//! ```
+
+use std::process::ExitCode;
+
+use revgame_core::emulator::Disassembler;
+use revgame_core::puzzle::{flatten, FlattenOptions, Puzzle};
+
+fn parse_number(value: &str) -> Result<u32, String> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => value.parse(),
+    }
+    .map_err(|_| format!("Not a number: {}", value))
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    let mut options = FlattenOptions {
+        origin: 0x1000,
+        code_start: 0x1000,
+        state_address: 0x2000,
+        seed: 1,
+    };
+    let mut source = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("{} needs a value", arg))
+                .and_then(|v| parse_number(v))
+        };
+        match arg.as_str() {
+            "--origin" => options.origin = value()?,
+            "--code-start" => options.code_start = value()?,
+            "--state" => options.state_address = value()?,
+            "--seed" => options.seed = value()?,
+            hex => source = Some(Puzzle::parse_hex(hex)?),
+        }
+    }
+    let source = source
+        .ok_or("Usage: flatten <hex bytes> [--origin A] [--code-start A] [--state A] [--seed N]")?;
+
+    let flattened = flatten(&source, &options)?;
+    let mut out = format!(
+        "[setup.code]\n# Flattened: {} blocks behind a dispatcher on dword [0x{:X}] (seed {})\n#\n# Assembly:\n",
+        flattened.blocks, options.state_address, options.seed
+    );
+    let mut disassembler = Disassembler::new();
+    let lines =
+        disassembler.disassemble(&flattened.bytes, options.code_start, flattened.bytes.len());
+    for line in &lines {
+        let bytes: Vec<String> = line.bytes.iter().map(|b| format!("{:02X}", b)).collect();
+        out.push_str(&format!(
+            "#   0x{:04X}: {:<36} ; {}\n",
+            line.address,
+            line.text,
+            bytes.join(" ")
+        ));
+    }
+    let bytes: Vec<String> = flattened
+        .bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect();
+    out.push_str(&format!("bytes = \"{}\"\n", bytes.join(" ")));
+
+    // The state store, one CMP/JE pair per block, then the HLT for no match
+    out.push_str("\n[annotations]\n");
+    let notes = [
+        (0, "initial state".to_string()),
+        (
+            1,
+            format!(
+                "dispatcher: jump to the block for dword [0x{:X}]",
+                options.state_address
+            ),
+        ),
+        (1 + 2 * flattened.blocks, "no state matched".to_string()),
+    ];
+    for (index, note) in notes {
+        if let Some(line) = lines.get(index) {
+            out.push_str(&format!("\"0x{:X}\" = \"{}\"\n", line.address, note));
+        }
+    }
+    Ok(out)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(snippet) => {
+            print!("{}", snippet);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
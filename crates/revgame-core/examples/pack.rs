@@ -0,0 +1,131 @@
+//! XOR packer for puzzle authors.
+//!
+//! Takes machine code for a payload and prints a starter packer puzzle:
+//! the payload encrypted in place, followed by a stub that decrypts it and
+//! jumps to it, with both disassembled:
+//!
+//! ```text
+//! cargo run -p revgame-core --example pack -- "B8 01 00 00 00 F4" \
+//!     [--code-start 0x1000] [--key 0x5A]
+//! ```
+
+use std::process::ExitCode;
+
+use revgame_core::emulator::Disassembler;
+use revgame_core::puzzle::{pack, PackOptions, Puzzle};
+
+fn parse_number(value: &str) -> Result<u32, String> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => value.parse(),
+    }
+    .map_err(|_| format!("Not a number: {}", value))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn run(args: &[String]) -> Result<String, String> {
+    let mut options = PackOptions {
+        code_start: 0x1000,
+        key: 0x5A,
+    };
+    let mut payload = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("{} needs a value", arg))
+                .and_then(|v| parse_number(v))
+        };
+        match arg.as_str() {
+            "--code-start" => options.code_start = value()?,
+            "--key" => {
+                options.key =
+                    u8::try_from(value()?).map_err(|_| "The key is a single byte".to_string())?
+            }
+            bytes => payload = Some(Puzzle::parse_hex(bytes)?),
+        }
+    }
+    let payload = payload.ok_or("Usage: pack <hex bytes> [--code-start A] [--key N]")?;
+
+    let packed = pack(&payload, &options)?;
+    let stub_start = options.code_start + packed.entry_point;
+    let mut disassembler = Disassembler::new();
+    let mut listing = |bytes: &[u8], address: u32, out: &mut String| {
+        for line in disassembler.disassemble(bytes, address, bytes.len()) {
+            out.push_str(&format!(
+                "#   0x{:04X}: {:<28} ; {}\n",
+                line.address,
+                line.text,
+                hex(&line.bytes)
+            ));
+        }
+    };
+
+    let mut out = format!(
+        r#"[metadata]
+id = "packers-000"
+title = "TODO"
+difficulty = 3
+category = "packer"
+tags = ["packer", "self-modifying", "xor"]
+concepts = ["obfuscation", "xor"]
+
+[description]
+brief = "TODO"
+detailed = """
+TODO
+"""
+
+[setup]
+code_start = 0x{code_start:X}
+enforce_permissions = true
+writable_code = true
+
+[setup.code]
+# Packed with key 0x{key:02X}: the stub at 0x{stub_start:X} decrypts 0x{len:X} bytes at
+# 0x{code_start:X} in place and jumps there
+#
+# Payload, once unpacked:
+"#,
+        code_start = options.code_start,
+        key = options.key,
+        stub_start = stub_start,
+        len = payload.len(),
+    );
+    listing(&payload, options.code_start, &mut out);
+    out.push_str("#\n# Stub:\n");
+    listing(
+        &packed.bytes[packed.entry_point as usize..],
+        stub_start,
+        &mut out,
+    );
+    out.push_str(&format!(
+        "bytes = \"{}\"\nentry_point = 0x{:X}\n\n[annotations]\n\"0x{:X}\" = \"payload, encrypted until the stub runs\"\n\"0x{:X}\" = \"stub: decrypt the payload in place\"\n\n[validation]\ntype = \"register_value\"\nregister = \"eax\"\nexpected = 1\n",
+        hex(&packed.bytes),
+        packed.entry_point,
+        options.code_start,
+        stub_start
+    ));
+    Ok(out)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(puzzle) => {
+            print!("{}", puzzle);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,12 +1,69 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+/// What a bookmarked address is, shown as a colored gutter marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BookmarkCategory {
+    /// Start of a function
+    Function,
+    /// A comparison or branch that decides the outcome
+    Check,
+    /// A key, buffer or string the code uses
+    Data,
+    /// Body of a loop
+    Loop,
+    /// Something to come back to
+    Todo,
+}
+
+impl BookmarkCategory {
+    pub const ALL: [BookmarkCategory; 5] = [
+        BookmarkCategory::Function,
+        BookmarkCategory::Check,
+        BookmarkCategory::Data,
+        BookmarkCategory::Loop,
+        BookmarkCategory::Todo,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BookmarkCategory::Function => "function",
+            BookmarkCategory::Check => "check",
+            BookmarkCategory::Data => "data",
+            BookmarkCategory::Loop => "loop",
+            BookmarkCategory::Todo => "todo",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The category after `category` in [`ALL`](Self::ALL), cycling
+    /// through no category after the last
+    pub fn cycle(category: Option<Self>) -> Option<Self> {
+        match category {
+            None => Some(Self::ALL[0]),
+            Some(current) => Self::ALL
+                .iter()
+                .position(|c| *c == current)
+                .and_then(|i| Self::ALL.get(i + 1))
+                .copied(),
+        }
+    }
+}
+
 /// A bookmark at a specific memory address with optional note
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Bookmark {
     pub address: u32,
     pub note: String,
     pub created_at: u64, // Unix timestamp
+    #[serde(default)]
+    pub category: Option<BookmarkCategory>,
 }
 
 impl Bookmark {
@@ -18,6 +75,7 @@ impl Bookmark {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs(),
+            category: None,
         }
     }
 }
@@ -59,6 +117,14 @@ impl BookmarkManager {
         self.bookmarks.get_mut(&address)
     }
 
+    /// Find the bookmark whose note is `note` (ignoring case), so notes
+    /// can serve as labels
+    pub fn find_note(&self, note: &str) -> Option<&Bookmark> {
+        self.bookmarks
+            .values()
+            .find(|b| b.note.eq_ignore_ascii_case(note))
+    }
+
     /// Update the note for a bookmark
     pub fn update_note(&mut self, address: u32, note: impl Into<String>) -> bool {
         if let Some(bookmark) = self.bookmarks.get_mut(&address) {
@@ -69,11 +135,37 @@ impl BookmarkManager {
         }
     }
 
+    /// Set or clear the category of a bookmark
+    pub fn set_category(&mut self, address: u32, category: Option<BookmarkCategory>) -> bool {
+        if let Some(bookmark) = self.bookmarks.get_mut(&address) {
+            bookmark.category = category;
+            true
+        } else {
+            false
+        }
+    }
+
     /// Get all bookmarks sorted by address
     pub fn list(&self) -> Vec<&Bookmark> {
         self.bookmarks.values().collect()
     }
 
+    /// Bookmarks sorted by address, only those in `category` when given
+    pub fn filtered(&self, category: Option<BookmarkCategory>) -> Vec<&Bookmark> {
+        self.bookmarks
+            .values()
+            .filter(|b| category.is_none() || b.category == category)
+            .collect()
+    }
+
+    /// First bookmark in `start..start + len`, for marking a row of memory
+    pub fn first_in(&self, start: u32, len: u32) -> Option<&Bookmark> {
+        self.bookmarks
+            .range(start..start.saturating_add(len))
+            .next()
+            .map(|(_, b)| b)
+    }
+
     /// Get all bookmark addresses sorted
     pub fn addresses(&self) -> Vec<u32> {
         self.bookmarks.keys().copied().collect()
@@ -188,4 +280,30 @@ mod tests {
         // Update non-existent returns false
         assert!(!manager.update_note(0x2000, "Test"));
     }
+
+    #[test]
+    fn test_categories_filter_and_cycle() {
+        let mut manager = BookmarkManager::new();
+        manager.add(0x1000, "main");
+        manager.add(0x1010, "cmp");
+        manager.add(0x2000, "key");
+        assert!(manager.set_category(0x1000, Some(BookmarkCategory::Function)));
+        assert!(manager.set_category(0x2000, BookmarkCategory::from_name("DATA")));
+        assert!(!manager.set_category(0x3000, None));
+
+        let notes = |list: Vec<&Bookmark>| list.iter().map(|b| b.note.clone()).collect::<Vec<_>>();
+        assert_eq!(notes(manager.filtered(None)), vec!["main", "cmp", "key"]);
+        assert_eq!(
+            notes(manager.filtered(Some(BookmarkCategory::Data))),
+            vec!["key"]
+        );
+        assert_eq!(manager.first_in(0x1001, 0x10).unwrap().note, "cmp");
+        assert!(manager.first_in(0x1011, 0x10).is_none());
+
+        assert_eq!(
+            BookmarkCategory::cycle(None),
+            Some(BookmarkCategory::Function)
+        );
+        assert_eq!(BookmarkCategory::cycle(Some(BookmarkCategory::Todo)), None);
+    }
 }
@@ -0,0 +1,145 @@
+use iced_x86::OpKind;
+
+use crate::emulator::DisassemblyLine;
+
+/// A well-known constant and the algorithm it gives away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MagicConstant {
+    pub value: u32,
+    pub name: &'static str,
+    /// What seeing it suggests about the surrounding code
+    pub hint: &'static str,
+}
+
+/// Constants worth recognizing on sight. Values below 0x10000 are left out:
+/// they turn up too often by accident to mean anything.
+pub const MAGIC_CONSTANTS: &[MagicConstant] = &[
+    MagicConstant { value: 0xEDB8_8320, name: "CRC32 polynomial (reflected)", hint: "CRC32 as used by zip and PNG: a loop of shifts and XORs with this value, or a 256-entry table built from it" },
+    MagicConstant { value: 0x04C1_1DB7, name: "CRC32 polynomial", hint: "CRC32 computed MSB-first (Ethernet, MPEG-2)" },
+    MagicConstant { value: 0x82F6_3B78, name: "CRC32C polynomial (reflected)", hint: "CRC32C (Castagnoli), used by iSCSI, ext4 and SSE4.2's CRC32 instruction" },
+    MagicConstant { value: 0x811C_9DC5, name: "FNV-1 offset basis", hint: "32-bit FNV-1 or FNV-1a hash: start here, then XOR and multiply by 0x01000193 for each byte" },
+    MagicConstant { value: 0x0100_0193, name: "FNV prime", hint: "32-bit FNV hash: each byte is mixed in by multiplying with this prime" },
+    MagicConstant { value: 0x6745_2301, name: "MD5/SHA-1 state A", hint: "MD5 or SHA-1 initialization (the first of four or five state words)" },
+    MagicConstant { value: 0xEFCD_AB89, name: "MD5/SHA-1 state B", hint: "MD5 or SHA-1 initialization" },
+    MagicConstant { value: 0x98BA_DCFE, name: "MD5/SHA-1 state C", hint: "MD5 or SHA-1 initialization" },
+    MagicConstant { value: 0x1032_5476, name: "MD5/SHA-1 state D", hint: "MD5 or SHA-1 initialization" },
+    MagicConstant { value: 0xC3D2_E1F0, name: "SHA-1 state E", hint: "SHA-1 initialization: MD5 only has four state words, so this one means SHA-1" },
+    MagicConstant { value: 0xD76A_A478, name: "MD5 round constant T[1]", hint: "MD5 compression function: the first of 64 sine-derived round constants" },
+    MagicConstant { value: 0x6A09_E667, name: "SHA-256 state H0", hint: "SHA-256 initialization (fractional part of the square root of 2)" },
+    MagicConstant { value: 0xBB67_AE85, name: "SHA-256 state H1", hint: "SHA-256 initialization" },
+    MagicConstant { value: 0x428A_2F98, name: "SHA-256 round constant K[0]", hint: "SHA-256 compression function: the first of 64 round constants" },
+    MagicConstant { value: 0x9E37_79B9, name: "Golden ratio (TEA delta)", hint: "TEA/XTEA encryption adds this each round; also used by multiplicative hashing" },
+    MagicConstant { value: 0xCC9E_2D51, name: "MurmurHash3 c1", hint: "MurmurHash3 (32-bit): each block is multiplied by this" },
+    MagicConstant { value: 0x1B87_3593, name: "MurmurHash3 c2", hint: "MurmurHash3 (32-bit): second block multiplier" },
+    MagicConstant { value: 0x41C6_4E6D, name: "LCG multiplier 1103515245", hint: "C library rand(): seed = seed * 1103515245 + 12345, a predictable PRNG" },
+    MagicConstant { value: 0x0003_43FD, name: "LCG multiplier 214013", hint: "MSVC rand(): seed = seed * 214013 + 2531011, a predictable PRNG" },
+    MagicConstant { value: 0x0026_9EC3, name: "LCG increment 2531011", hint: "MSVC rand(): seed = seed * 214013 + 2531011, a predictable PRNG" },
+    MagicConstant { value: 0x464C_457F, name: "ELF magic", hint: "\\x7FELF: the start of an ELF executable, being checked for or embedded" },
+    MagicConstant { value: 0xCAFE_BABE, name: "Java class / Mach-O fat magic", hint: "File format check for a Java class file or a universal Mach-O binary" },
+    MagicConstant { value: 0xFEED_FACE, name: "Mach-O magic", hint: "File format check for a 32-bit Mach-O binary" },
+    MagicConstant { value: 0xDEAD_BEEF, name: "DEADBEEF", hint: "A debugging marker: often fills uninitialized or freed memory, or acts as a canary" },
+];
+
+/// Look up a value in the constant database
+pub fn lookup_constant(value: u32) -> Option<&'static MagicConstant> {
+    MAGIC_CONSTANTS
+        .iter()
+        .find(|constant| constant.value == value)
+}
+
+/// Where a constant was spotted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstantSource {
+    /// An immediate operand of the instruction at the address
+    Immediate,
+    /// A little-endian dword stored at the address
+    Memory,
+}
+
+/// A known constant found in code or data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstantMatch {
+    pub address: u32,
+    pub constant: &'static MagicConstant,
+    pub source: ConstantSource,
+}
+
+/// Known constants used as immediate operands, in address order
+pub fn constants_in_code(lines: &[DisassemblyLine]) -> Vec<ConstantMatch> {
+    lines
+        .iter()
+        .filter(|line| line.is_code())
+        .flat_map(|line| {
+            let instr = &line.instruction;
+            (0..instr.op_count())
+                .filter(move |&i| {
+                    matches!(
+                        instr.op_kind(i),
+                        OpKind::Immediate32 | OpKind::Immediate8to32 | OpKind::Immediate32to64
+                    )
+                })
+                .filter_map(move |i| lookup_constant(instr.immediate(i) as u32))
+                .map(move |constant| ConstantMatch {
+                    address: line.address,
+                    constant,
+                    source: ConstantSource::Immediate,
+                })
+        })
+        .collect()
+}
+
+/// Known constants stored as little-endian dwords in `bytes` loaded at `base`.
+/// Every offset is checked, since data need not be aligned.
+pub fn constants_in_memory(bytes: &[u8], base: u32) -> Vec<ConstantMatch> {
+    bytes
+        .windows(4)
+        .enumerate()
+        .filter_map(|(offset, window)| {
+            let value = u32::from_le_bytes([window[0], window[1], window[2], window[3]]);
+            let constant = lookup_constant(value)?;
+            Some(ConstantMatch {
+                address: base.wrapping_add(offset as u32),
+                constant,
+                source: ConstantSource::Memory,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::Disassembler;
+
+    #[test]
+    fn test_constants_in_code() {
+        // MOV EAX, 0xEDB88320; XOR EDX, 0x12345678; IMUL ECX, ECX, 0x01000193
+        let lines = Disassembler::new().disassemble(
+            &[
+                0xB8, 0x20, 0x83, 0xB8, 0xED, 0x81, 0xF2, 0x78, 0x56, 0x34, 0x12, 0x69, 0xC9, 0x93,
+                0x01, 0x00, 0x01,
+            ],
+            0x1000,
+            8,
+        );
+        let found = constants_in_code(&lines);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].address, 0x1000);
+        assert!(found[0].constant.name.starts_with("CRC32"));
+        assert_eq!(found[1].address, 0x100B);
+        assert_eq!(found[1].constant.name, "FNV prime");
+    }
+
+    #[test]
+    fn test_constants_in_memory() {
+        let mut data = vec![0u8; 3];
+        data.extend_from_slice(&0x6745_2301u32.to_le_bytes());
+        data.extend_from_slice(&0xEFCD_AB89u32.to_le_bytes());
+
+        let found = constants_in_memory(&data, 0x2000);
+        let addresses: Vec<u32> = found.iter().map(|found| found.address).collect();
+        assert_eq!(addresses, [0x2003, 0x2007]);
+        assert_eq!(found[0].source, ConstantSource::Memory);
+        assert!(lookup_constant(0x1234).is_none());
+    }
+}
@@ -0,0 +1,127 @@
+/// Byte-value counts for a stretch of memory
+#[derive(Debug, Clone)]
+pub struct ByteStats {
+    counts: [u32; 256],
+    total: usize,
+}
+
+impl ByteStats {
+    pub fn of(bytes: &[u8]) -> Self {
+        let mut counts = [0u32; 256];
+        for &byte in bytes {
+            counts[byte as usize] += 1;
+        }
+        Self {
+            counts,
+            total: bytes.len(),
+        }
+    }
+
+    pub fn count(&self, byte: u8) -> u32 {
+        self.counts[byte as usize]
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Number of different byte values present
+    pub fn distinct(&self) -> usize {
+        self.counts.iter().filter(|&&count| count > 0).count()
+    }
+
+    /// Shannon entropy in bits per byte: 0 for a single repeated value,
+    /// 8 when all 256 values are equally likely
+    pub fn entropy(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        let total = self.total as f64;
+        self.counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Counts grouped into `bins` equal ranges of byte values
+    pub fn buckets(&self, bins: usize) -> Vec<u64> {
+        let bins = bins.clamp(1, 256);
+        let mut buckets = vec![0u64; bins];
+        for (value, &count) in self.counts.iter().enumerate() {
+            buckets[value * bins / 256] += count as u64;
+        }
+        buckets
+    }
+
+    /// The most frequent byte values, most frequent first
+    pub fn most_common(&self, n: usize) -> Vec<(u8, u32)> {
+        let mut values: Vec<(u8, u32)> = (0..=255u8)
+            .map(|byte| (byte, self.count(byte)))
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        values.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        values.truncate(n);
+        values
+    }
+}
+
+/// What data with this entropy usually is
+pub fn describe_entropy(entropy: f64) -> &'static str {
+    match entropy {
+        e if e < 1.0 => "empty or padding",
+        e if e < 4.5 => "text or sparse data",
+        e if e < 6.5 => "code or structured data",
+        e if e < 7.5 => "compressed or dense data",
+        _ => "packed or encrypted",
+    }
+}
+
+/// Entropy of consecutive windows of `size` bytes (the last may be shorter)
+pub fn window_entropy(bytes: &[u8], size: usize) -> Vec<f64> {
+    bytes
+        .chunks(size.max(1))
+        .map(|window| ByteStats::of(window).entropy())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entropy_extremes() {
+        assert_eq!(ByteStats::of(&[]).entropy(), 0.0);
+        assert_eq!(ByteStats::of(&[0; 64]).entropy(), 0.0);
+
+        let all: Vec<u8> = (0..=255).collect();
+        let stats = ByteStats::of(&all);
+        assert!((stats.entropy() - 8.0).abs() < 1e-9);
+        assert_eq!(stats.distinct(), 256);
+        assert_eq!(describe_entropy(stats.entropy()), "packed or encrypted");
+
+        // Two values, equally often: one bit per byte
+        assert!((ByteStats::of(b"abab").entropy() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let stats = ByteStats::of(b"hello\0\0\0");
+        assert_eq!(stats.count(b'l'), 2);
+        assert_eq!(stats.most_common(2), [(0, 3), (b'l', 2)]);
+
+        let buckets = stats.buckets(16);
+        assert_eq!(buckets.len(), 16);
+        assert_eq!(buckets[0], 3);
+        assert_eq!(buckets[6], 5);
+        assert_eq!(buckets.iter().sum::<u64>(), 8);
+
+        let windows = window_entropy(&[0, 0, 0, 0, 1, 2, 3, 4, 9], 4);
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[0], 0.0);
+        assert!((windows[1] - 2.0).abs() < 1e-9);
+    }
+}
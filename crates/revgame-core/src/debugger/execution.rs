@@ -1,8 +1,17 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
-use crate::emulator::{CpuState, DisassemblyLine, ExecutionResult, Executor, Memory};
+use iced_x86::Mnemonic;
 
-use super::{DebuggerError, History, MemoryPatch, BookmarkManager};
+use crate::emulator::{
+    CpuState, CycleModel, Disassembler, DisassemblyLine, DisassemblyMode, EmulatorError,
+    ExecutionResult, Executor, Flag, Memory, MemorySnapshot, Prng, Register, MAX_INSTRUCTION_LEN,
+};
+
+use super::{
+    BookmarkManager, DebuggerError, FlagHit, Frame, FrameTracker, History, InputPlacement,
+    InterruptHandler, InterruptOutcome, MemoryPatch, ModifiedLine, PatchFit, ProgramInput,
+    RunTrace, StateCapture, Timeline, TimelineMark, Watch, WatchHit,
+};
 
 /// Debugger execution state
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -13,6 +22,10 @@ pub enum DebuggerState {
     Running,
     /// Stopped at a breakpoint
     AtBreakpoint(u32),
+    /// Stopped because a watched value changed
+    AtWatch,
+    /// Stopped because a flag being broken on changed
+    AtFlagChange(Flag),
     /// Program has halted normally
     Halted,
     /// Hit execution limit
@@ -39,6 +52,10 @@ pub struct StepResult {
 pub enum RunResult {
     /// Stopped at breakpoint
     Breakpoint(u32),
+    /// Stopped because a watched value changed
+    Watch(WatchHit),
+    /// Stopped because a flag being broken on changed
+    FlagChange(FlagHit),
     /// Program halted
     Halted,
     /// Hit execution limit
@@ -47,6 +64,30 @@ pub enum RunResult {
     Error(String),
 }
 
+/// When executed instructions are captured into history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CapturePolicy {
+    /// Record every instruction, whether stepped or run
+    #[default]
+    Always,
+    /// Record single steps only; `run` executes without history (fast run)
+    StepOnly,
+    /// Never record history
+    Never,
+}
+
+impl CapturePolicy {
+    /// Whether single steps are recorded
+    pub fn captures_steps(&self) -> bool {
+        !matches!(self, CapturePolicy::Never)
+    }
+
+    /// Whether instructions executed by `run` are recorded
+    pub fn captures_runs(&self) -> bool {
+        matches!(self, CapturePolicy::Always)
+    }
+}
+
 /// Entry in execution history for reverse debugging
 #[derive(Debug, Clone)]
 pub struct HistoryEntry {
@@ -55,6 +96,17 @@ pub struct HistoryEntry {
     pub instruction_text: String,
 }
 
+/// A register's value right after one recorded instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterSample {
+    /// Address of the instruction
+    pub eip: u32,
+    /// Disassembly of the instruction
+    pub instruction_text: String,
+    /// Register value once the instruction had executed
+    pub value: u32,
+}
+
 /// The main debugger combining CPU, memory, and execution control
 pub struct Debugger {
     /// CPU state
@@ -72,6 +124,10 @@ pub struct Debugger {
     /// Breakpoint addresses
     pub breakpoints: HashSet<u32>,
 
+    /// Addresses where runs stop for the puzzle (where its goal is checked),
+    /// without showing as breakpoints
+    stop_points: HashSet<u32>,
+
     /// Maximum instructions before stopping (prevents infinite loops)
     pub max_instructions: u64,
 
@@ -81,21 +137,63 @@ pub struct Debugger {
     /// Total instructions executed across all runs
     pub total_instructions: u64,
 
+    /// Instructions executed since the program started
+    position: u64,
+
+    /// Where the program has been since it started, for scrubbing back and forth
+    timeline: Timeline,
+
     /// Execution history for debugging
     history: VecDeque<HistoryEntry>,
 
     /// Maximum history entries to keep
     max_history: usize,
 
+    /// When executed instructions are recorded for step-back
+    capture_policy: CapturePolicy,
+
+    /// Control flow of the last run (when runs are captured)
+    last_trace: Option<RunTrace>,
+
     /// Patch history for undo/redo
     patch_history: History,
 
     /// Bookmarks for memory addresses
     pub bookmarks: BookmarkManager,
 
+    /// Handlers for `INT n`, by vector
+    interrupt_handlers: HashMap<u8, InterruptHandler>,
+
+    /// Arguments and stdin for the program
+    input: ProgramInput,
+
+    /// The session's random generator, restarted from its seed on reset
+    rng: Prng,
+
+    /// Values checked for changes after every instruction
+    watches: Vec<Watch>,
+
+    /// Whether a change to a watched value stops execution
+    break_on_watch: bool,
+
+    /// The most recent change to a watched value, until taken
+    watch_hit: Option<WatchHit>,
+
+    /// Flags whose every change stops execution
+    flag_breaks: BTreeSet<Flag>,
+
+    /// The most recent change to a flag being broken on, until taken
+    flag_hit: Option<FlagHit>,
+
+    /// Times each mnemonic was executed, until taken; replays by `seek` don't count
+    mnemonic_counts: HashMap<Mnemonic, u64>,
+
+    /// Stack frames opened by CALL and not yet returned from
+    frames: FrameTracker,
+
     /// Initial state for reset
     initial_cpu: CpuState,
-    initial_memory: Vec<u8>,
+    initial_memory: MemorySnapshot,
 }
 
 impl Debugger {
@@ -108,15 +206,30 @@ impl Debugger {
             executor: Executor::new(),
             state: DebuggerState::Ready,
             breakpoints: HashSet::new(),
-            max_instructions: 100_000,
+            stop_points: HashSet::new(),
+            max_instructions: 1_000_000,
             instructions_executed: 0,
             total_instructions: 0,
+            position: 0,
+            timeline: Timeline::new(),
             history: VecDeque::new(),
             max_history: 1000,
+            capture_policy: CapturePolicy::Always,
+            last_trace: None,
             patch_history: History::new(100),
             bookmarks: BookmarkManager::new(),
+            interrupt_handlers: HashMap::new(),
+            input: ProgramInput::default(),
+            rng: Prng::new(1),
+            watches: Vec::new(),
+            break_on_watch: false,
+            watch_hit: None,
+            flag_breaks: BTreeSet::new(),
+            flag_hit: None,
+            mnemonic_counts: HashMap::new(),
+            frames: FrameTracker::new(),
             initial_cpu: CpuState::default(),
-            initial_memory: vec![0; memory_size],
+            initial_memory: memory.snapshot(),
         }
     }
 
@@ -136,15 +249,30 @@ impl Debugger {
             executor: Executor::new(),
             state: DebuggerState::Ready,
             breakpoints: HashSet::new(),
-            max_instructions: 100_000,
+            stop_points: HashSet::new(),
+            max_instructions: 1_000_000,
             instructions_executed: 0,
             total_instructions: 0,
+            position: 0,
+            timeline: Timeline::new(),
             history: VecDeque::new(),
             max_history: 1000,
+            capture_policy: CapturePolicy::Always,
+            last_trace: None,
             patch_history: History::new(100),
             bookmarks: BookmarkManager::new(),
+            interrupt_handlers: HashMap::new(),
+            input: ProgramInput::default(),
+            rng: Prng::new(1),
+            watches: Vec::new(),
+            break_on_watch: false,
+            watch_hit: None,
+            flag_breaks: BTreeSet::new(),
+            flag_hit: None,
+            mnemonic_counts: HashMap::new(),
+            frames: FrameTracker::new(),
             initial_cpu: cpu,
-            initial_memory: memory.raw().to_vec(),
+            initial_memory: memory.snapshot(),
         }
     }
 
@@ -152,14 +280,14 @@ impl Debugger {
     pub fn load_code(&mut self, address: u32, code: &[u8]) -> Result<(), DebuggerError> {
         self.memory.load(address, code)?;
         // Update initial state
-        self.initial_memory = self.memory.raw().to_vec();
+        self.initial_memory = self.memory.snapshot();
         Ok(())
     }
 
     /// Load data into memory
     pub fn load_data(&mut self, address: u32, data: &[u8]) -> Result<(), DebuggerError> {
         self.memory.load(address, data)?;
-        self.initial_memory = self.memory.raw().to_vec();
+        self.initial_memory = self.memory.snapshot();
         Ok(())
     }
 
@@ -178,17 +306,37 @@ impl Debugger {
     /// Save current state as initial state (for reset)
     pub fn save_initial_state(&mut self) {
         self.initial_cpu = self.cpu.clone();
-        self.initial_memory = self.memory.raw().to_vec();
+        self.initial_memory = self.memory.snapshot();
     }
 
     /// Reset to initial state
     pub fn reset(&mut self) {
+        self.patch_history.clear();
+        self.timeline.clear();
+        self.soft_reset();
+    }
+
+    /// Restart from the initial state but keep the player's patches and
+    /// their undo history. Whatever the program itself wrote is restored.
+    /// The timeline is kept, so the run can be replayed back to where it was.
+    pub fn soft_reset(&mut self) {
+        self.position = 0;
         self.cpu = self.initial_cpu.clone();
-        self.memory.load(0, &self.initial_memory).ok();
+        self.memory.restore(&self.initial_memory);
+        for patch in self.patch_history.net_changes() {
+            let _ = self.memory.poke(patch.address, &patch.new_bytes);
+        }
+        self.memory.reset_devices();
+        self.rng.restart();
         self.state = DebuggerState::Ready;
         self.instructions_executed = 0;
         self.history.clear();
-        self.patch_history.clear();
+        self.last_trace = None;
+        self.watch_hit = None;
+        self.flag_hit = None;
+        self.frames.clear();
+        // The input buffer lies in memory that was just restored
+        let _ = self.input.apply(&mut self.cpu, &mut self.memory);
     }
 
     /// Set a breakpoint at the given address
@@ -217,70 +365,32 @@ impl Debugger {
         self.breakpoints.contains(&address)
     }
 
+    /// Stop runs at `address` like a breakpoint, without it being one the
+    /// player can see or remove
+    pub fn set_stop_point(&mut self, address: u32) {
+        self.stop_points.insert(address);
+    }
+
     /// Execute a single instruction
     pub fn step(&mut self) -> Result<StepResult, DebuggerError> {
-        if matches!(self.state, DebuggerState::Halted | DebuggerState::LimitExceeded) {
+        if matches!(
+            self.state,
+            DebuggerState::Halted | DebuggerState::LimitExceeded
+        ) {
             return Err(DebuggerError::AlreadyHalted);
         }
 
-        // Save state before execution for history
+        // Save state before execution to report changed registers
         let old_cpu = self.cpu.clone();
-        let old_eip = self.cpu.eip;
 
         // Get the instruction we're about to execute
-        let disasm = self.executor.disassemble(&self.memory, self.cpu.eip, 1);
-        let instruction = disasm.into_iter().next();
-
-        // Execute the instruction
-        let result = self.executor.execute_one(&mut self.cpu, &mut self.memory)?;
+        let instruction = self.current_instruction();
 
-        // Update history
-        if let Some(ref instr) = instruction {
-            self.history.push_back(HistoryEntry {
-                eip: old_eip,
-                cpu_snapshot: old_cpu.clone(),
-                instruction_text: instr.text.clone(),
-            });
-
-            if self.history.len() > self.max_history {
-                self.history.pop_front();
-            }
-        }
-
-        // Update counters
-        self.instructions_executed += 1;
-        self.total_instructions += 1;
-
-        // Determine new state and apply result
-        let new_state = match result {
-            ExecutionResult::Continue { next_eip } => {
-                self.cpu.eip = next_eip;
-
-                // Check for breakpoint at new address
-                if self.breakpoints.contains(&next_eip) {
-                    DebuggerState::AtBreakpoint(next_eip)
-                } else if self.instructions_executed >= self.max_instructions {
-                    DebuggerState::LimitExceeded
-                } else {
-                    DebuggerState::Ready
-                }
-            }
-            ExecutionResult::Halt => {
-                self.cpu.halted = true;
-                DebuggerState::Halted
-            }
-            ExecutionResult::Breakpoint => {
-                // INT 3 hit - stop at current location
-                DebuggerState::AtBreakpoint(self.cpu.eip)
-            }
-            ExecutionResult::Interrupt(vector) => {
-                // For now, treat other interrupts as a halt
-                log::debug!("Interrupt {} at 0x{:08X}", vector, self.cpu.eip);
-                DebuggerState::Ready
-            }
-        };
-
-        self.state = new_state.clone();
+        let text = instruction
+            .as_ref()
+            .filter(|_| self.capture_policy.captures_steps())
+            .map(|instr| instr.text.as_str());
+        let new_state = self.execute(text)?;
 
         // Detect changed registers
         let changed_registers = self.detect_register_changes(&old_cpu);
@@ -298,13 +408,52 @@ impl Debugger {
         self.instructions_executed = 0;
         self.state = DebuggerState::Running;
 
+        let capture = self.capture_policy.captures_runs();
+        let mut trace = capture.then(RunTrace::new);
+
+        let result = self.run_loop(trace.as_mut());
+
+        if let Some(ref mut trace) = trace {
+            trace.instructions = self.instructions_executed;
+        }
+        self.last_trace = trace;
+        result
+    }
+
+    fn run_loop(&mut self, mut trace: Option<&mut RunTrace>) -> Result<RunResult, DebuggerError> {
         loop {
-            let result = self.step()?;
+            // Only format the instruction when it will be kept in history
+            let instruction = if trace.is_some() {
+                self.current_instruction()
+            } else {
+                None
+            };
+
+            let from = self.cpu.eip;
+            let state = self.execute(instruction.as_ref().map(|instr| instr.text.as_str()))?;
+            if let Some(trace) = trace.as_deref_mut() {
+                if let Some(ref instruction) = instruction {
+                    trace.record_step(from, &instruction.text);
+                }
+                if !matches!(state, DebuggerState::Halted | DebuggerState::Error(_)) {
+                    trace.record(from, self.cpu.eip);
+                }
+            }
 
-            match result.state {
+            match state {
                 DebuggerState::AtBreakpoint(addr) => {
                     return Ok(RunResult::Breakpoint(addr));
                 }
+                DebuggerState::AtWatch => {
+                    if let Some(ref hit) = self.watch_hit {
+                        return Ok(RunResult::Watch(hit.clone()));
+                    }
+                }
+                DebuggerState::AtFlagChange(_) => {
+                    if let Some(ref hit) = self.flag_hit {
+                        return Ok(RunResult::FlagChange(hit.clone()));
+                    }
+                }
                 DebuggerState::Halted => {
                     return Ok(RunResult::Halted);
                 }
@@ -321,6 +470,173 @@ impl Debugger {
         }
     }
 
+    /// Disassemble the instruction at EIP
+    fn current_instruction(&mut self) -> Option<DisassemblyLine> {
+        self.executor
+            .disassemble(&self.memory, self.cpu.eip, 1)
+            .into_iter()
+            .next()
+    }
+
+    /// Execute the instruction at EIP and update counters and state.
+    /// A history entry is recorded when the instruction text is given.
+    fn execute(&mut self, instruction_text: Option<&str>) -> Result<DebuggerState, DebuggerError> {
+        // Snapshot the CPU only when the entry will be kept
+        let entry = instruction_text.map(|text| HistoryEntry {
+            eip: self.cpu.eip,
+            cpu_snapshot: self.cpu.clone(),
+            instruction_text: text.to_string(),
+        });
+
+        // Remember watched values to spot the instruction that changes them
+        let eip = self.cpu.eip;
+        let watched: Vec<Vec<u8>> = self
+            .watches
+            .iter()
+            .map(|watch| watch.read(&self.cpu, &self.memory))
+            .collect();
+
+        // Flags before, and the instruction to blame if one being broken on changes
+        let flags_before = self.cpu.eflags.clone();
+        let flag_instruction = if self.flag_breaks.is_empty() {
+            None
+        } else {
+            instruction_text
+                .map(str::to_string)
+                .or_else(|| self.current_instruction().map(|line| line.text))
+        };
+
+        // Opcode bytes and stack pointer for frame tracking
+        let mut opcode = [0; 2];
+        let opcode_len = self.memory.peek_into(eip, &mut opcode);
+        let esp = self.cpu.regs.esp;
+
+        // Execute the instruction; one the emulator can't run stops here,
+        // like a fault, rather than failing the whole run
+        let result = match self.executor.execute_one(&mut self.cpu, &mut self.memory) {
+            Ok(result) => result,
+            Err(EmulatorError::UnsupportedInstruction(what)) => {
+                log::debug!("Unsupported instruction: {}", what);
+                self.state = DebuggerState::Error(format!("Unsupported instruction: {}", what));
+                return Ok(self.state.clone());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        // Update history
+        if let Some(entry) = entry {
+            self.history.push_back(entry);
+
+            if self.history.len() > self.max_history {
+                self.history.pop_front();
+            }
+        }
+
+        // Update counters
+        if let Some(mnemonic) = self.executor.last_mnemonic() {
+            *self.mnemonic_counts.entry(mnemonic).or_insert(0) += 1;
+        }
+        self.instructions_executed += 1;
+        self.total_instructions += 1;
+        self.position += 1;
+        self.timeline.advance_to(self.position);
+
+        // Determine new state and apply result
+        let new_state = match result {
+            ExecutionResult::Continue { next_eip } => self.continue_at(next_eip),
+            ExecutionResult::Halt => {
+                self.cpu.halted = true;
+                DebuggerState::Halted
+            }
+            ExecutionResult::Breakpoint => {
+                // INT 3 hit - stop at current location
+                DebuggerState::AtBreakpoint(self.cpu.eip)
+            }
+            ExecutionResult::Interrupt { vector, next_eip } => {
+                match self.interrupt_handlers.get(&vector) {
+                    Some(handler) => {
+                        let outcome = handler.apply(
+                            &mut self.cpu,
+                            &mut self.memory,
+                            next_eip,
+                            self.position,
+                            &mut self.input,
+                            &mut self.rng,
+                        )?;
+                        match outcome {
+                            InterruptOutcome::Continue(target) => self.continue_at(target),
+                            InterruptOutcome::Halt => DebuggerState::Halted,
+                        }
+                    }
+                    None => {
+                        // Stay on the INT so the player can see what stopped
+                        log::debug!("Unhandled interrupt {} at 0x{:08X}", vector, self.cpu.eip);
+                        DebuggerState::Error(format!("Unhandled interrupt 0x{:02X}", vector))
+                    }
+                }
+            }
+        };
+
+        self.frames
+            .observe(&opcode[..opcode_len], eip, esp, &self.cpu);
+
+        let mut new_state = new_state;
+        for (watch, old) in self.watches.iter().zip(watched) {
+            let new = watch.read(&self.cpu, &self.memory);
+            if new != old {
+                self.watch_hit = Some(WatchHit {
+                    watch: watch.clone(),
+                    eip,
+                    old,
+                    new,
+                });
+                if self.break_on_watch && new_state == DebuggerState::Ready {
+                    new_state = DebuggerState::AtWatch;
+                }
+                break;
+            }
+        }
+
+        let changed_flag = self
+            .flag_breaks
+            .iter()
+            .find(|flag| flag.get(&flags_before) != flag.get(&self.cpu.eflags))
+            .copied();
+        if let Some(flag) = changed_flag {
+            self.flag_hit = Some(FlagHit {
+                flag,
+                eip,
+                instruction: flag_instruction.unwrap_or_default(),
+                set: flag.get(&self.cpu.eflags),
+            });
+            if new_state == DebuggerState::Ready {
+                new_state = DebuggerState::AtFlagChange(flag);
+            }
+        }
+
+        if let DebuggerState::AtBreakpoint(_) = new_state {
+            self.timeline
+                .record(self.position, TimelineMark::Breakpoint);
+        }
+
+        self.state = new_state.clone();
+        Ok(new_state)
+    }
+
+    /// Move EIP to `next_eip` and work out whether execution should stop there
+    fn continue_at(&mut self, next_eip: u32) -> DebuggerState {
+        self.cpu.eip = next_eip;
+
+        // Check for breakpoint at new address
+        if self.breakpoints.contains(&next_eip) || self.stop_points.contains(&next_eip) {
+            DebuggerState::AtBreakpoint(next_eip)
+        } else if self.instructions_executed >= self.max_instructions {
+            DebuggerState::LimitExceeded
+        } else {
+            DebuggerState::Ready
+        }
+    }
+
     /// Run for N instructions
     pub fn run_n(&mut self, count: u64) -> Result<RunResult, DebuggerError> {
         let original_limit = self.max_instructions;
@@ -338,12 +654,108 @@ impl Debugger {
         if let Some(entry) = self.history.pop_back() {
             self.cpu = entry.cpu_snapshot.clone();
             self.state = DebuggerState::Ready;
+            self.frames.unwind_to(self.cpu.regs.esp);
             Some(entry)
         } else {
             None
         }
     }
 
+    /// Instructions executed since the program started
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Where the program has been since it started
+    pub fn timeline(&self) -> &Timeline {
+        &self.timeline
+    }
+
+    /// Mark the current point on the timeline to come back to
+    pub fn mark_snapshot(&mut self) {
+        self.timeline.record(self.position, TimelineMark::Snapshot);
+    }
+
+    /// Positions marked as snapshots, in timeline order
+    pub fn snapshots(&self) -> Vec<u64> {
+        self.timeline
+            .markers()
+            .iter()
+            .filter(|marker| marker.mark == TimelineMark::Snapshot)
+            .map(|marker| marker.position)
+            .collect()
+    }
+
+    /// CPU and memory at instruction `position` of the timeline, replayed
+    /// there and back so the debugger ends up where it was
+    pub fn capture_at(&mut self, position: u64) -> Result<StateCapture, DebuggerError> {
+        let here = self.position;
+        self.seek(position)?;
+        let capture = StateCapture {
+            position: self.position,
+            cpu: self.cpu.clone(),
+            memory: self.memory.snapshot(),
+        };
+        self.seek(here)?;
+        Ok(capture)
+    }
+
+    /// Go to instruction `target` of the timeline (clamped to how far the
+    /// program has got). Going back replays from the start with the current
+    /// patches; breakpoints and watches don't stop the replay.
+    pub fn seek(&mut self, target: u64) -> Result<(), DebuggerError> {
+        let target = target.min(self.timeline.length());
+        if target < self.position {
+            self.soft_reset();
+        }
+
+        // Only the last stretch is kept for step-back history
+        let capture_from = target.saturating_sub(self.max_history as u64);
+        let counted = std::mem::take(&mut self.mnemonic_counts);
+        self.instructions_executed = 0;
+        while self.position < target {
+            let instruction =
+                if self.capture_policy.captures_steps() && self.position >= capture_from {
+                    self.current_instruction()
+                } else {
+                    None
+                };
+            let state = self.execute(instruction.as_ref().map(|instr| instr.text.as_str()));
+            if let Err(e) = state {
+                self.mnemonic_counts = counted;
+                return Err(e);
+            }
+            if matches!(state, Ok(DebuggerState::Halted | DebuggerState::Error(_))) {
+                break;
+            }
+        }
+
+        self.mnemonic_counts = counted;
+        self.instructions_executed = 0;
+        self.watch_hit = None;
+        self.flag_hit = None;
+        if matches!(
+            self.state,
+            DebuggerState::AtWatch
+                | DebuggerState::AtFlagChange(_)
+                | DebuggerState::LimitExceeded
+                | DebuggerState::Running
+        ) {
+            self.state = DebuggerState::Ready;
+        }
+        Ok(())
+    }
+
+    /// Stack frames opened by CALL, outermost first
+    pub fn frames(&self) -> &FrameTracker {
+        &self.frames
+    }
+
+    /// The frame closed by the last RET, if not taken yet
+    pub fn take_collapsed_frame(&mut self) -> Option<Frame> {
+        self.frames.take_collapsed()
+    }
+
     /// Get disassembly around current EIP
     pub fn disassemble(&mut self, count: usize) -> Vec<DisassemblyLine> {
         self.executor.disassemble(&self.memory, self.cpu.eip, count)
@@ -354,26 +766,119 @@ impl Debugger {
         self.executor.disassemble(&self.memory, address, count)
     }
 
-    /// Patch memory (for puzzle modifications)
+    /// Get disassembly at an address using the given strategy
+    ///
+    /// Recursive traversal walks the whole code region from the entry point,
+    /// EIP and any `INT` handlers, then returns the lines from `address` on.
+    pub fn disassemble_in(
+        &mut self,
+        mode: DisassemblyMode,
+        address: u32,
+        count: usize,
+    ) -> Vec<DisassemblyLine> {
+        let region = self
+            .memory
+            .get_region(address)
+            .filter(|region| region.permissions.execute)
+            .map(|region| (region.start, region.end));
+        let code = region.and_then(|(start, end)| {
+            self.memory
+                .slice(start, end.saturating_sub(start) as usize)
+                .map(|bytes| (start, bytes))
+        });
+
+        match (mode, code) {
+            (DisassemblyMode::Recursive, Some((start, bytes))) => {
+                let mut roots = vec![self.initial_cpu.eip, self.cpu.eip];
+                roots.extend(self.interrupt_handlers.values().filter_map(
+                    |handler| match handler {
+                        InterruptHandler::Jump(target) => Some(*target),
+                        _ => None,
+                    },
+                ));
+
+                self.executor
+                    .disassemble_recursive(&bytes, start, &roots)
+                    .into_iter()
+                    .skip_while(|line| line.address + line.length as u32 <= address)
+                    .take(count)
+                    .collect()
+            }
+            _ => self.disassemble_at(address, count),
+        }
+    }
+
+    /// Disassemble bytes that aren't in memory, such as a proposed patch
+    pub fn disassemble_bytes(
+        &mut self,
+        bytes: &[u8],
+        address: u32,
+        count: usize,
+    ) -> Vec<DisassemblyLine> {
+        self.executor.disassemble_bytes(bytes, address, count)
+    }
+
+    /// Show `length` bytes at `start` as `db` lines instead of instructions
+    pub fn mark_data(&mut self, start: u32, length: u32) {
+        self.executor.mark_data(start, length);
+    }
+
+    /// Patch memory (for puzzle modifications). A code patch must leave the
+    /// instructions after it decoding as before (see `check_patch`);
+    /// `force_patch` writes it anyway.
     pub fn patch(&mut self, address: u32, bytes: &[u8]) -> Result<(), DebuggerError> {
-        // Read old bytes before patching
-        let old_bytes = self.memory.read_bytes(address, bytes.len())?;
+        match self.check_patch(address, bytes) {
+            PatchFit::Clean => self.force_patch(address, bytes),
+            fit => Err(DebuggerError::PatchMisfit(fit)),
+        }
+    }
+
+    /// Patch memory as is, wherever the bytes land
+    pub fn force_patch(&mut self, address: u32, bytes: &[u8]) -> Result<(), DebuggerError> {
+        // Old bytes come from the program image (as loaded, plus earlier
+        // patches) rather than live memory, which a run may have changed:
+        // undoing restores the image no matter when the patch was made
+        let old_bytes = self.patched_image(address, bytes.len());
 
-        // Apply the patch
-        self.memory.write_bytes(address, bytes)?;
+        // Apply the patch (the player can write anywhere, even read-only code)
+        self.memory.poke(address, bytes)?;
 
         // Record in history
         let patch = MemoryPatch::new(address, old_bytes, bytes.to_vec());
         self.patch_history.record(patch);
+        self.mark_patch();
 
         Ok(())
     }
 
+    /// Mark a patch on the timeline; what the program did after it may no
+    /// longer happen the same way
+    fn mark_patch(&mut self) {
+        self.timeline.truncate(self.position);
+        self.timeline.record(self.position, TimelineMark::Patch);
+    }
+
+    /// Bytes at `address` in the initial memory with the applied patches on top
+    fn patched_image(&self, address: u32, count: usize) -> Vec<u8> {
+        let mut bytes = self.initial_memory.read_bytes(address, count);
+        let end = address as u64 + count as u64;
+        for patch in self.patch_history.net_changes() {
+            for (i, &byte) in patch.new_bytes.iter().enumerate() {
+                let at = patch.address as u64 + i as u64;
+                if (address as u64..end).contains(&at) {
+                    bytes[(at - address as u64) as usize] = byte;
+                }
+            }
+        }
+        bytes
+    }
+
     /// Undo the last patch
     pub fn undo_patch(&mut self) -> Result<(), DebuggerError> {
         if let Some(patch) = self.patch_history.undo() {
-            // Apply the inverse patch without recording it
-            self.memory.write_bytes(patch.address, &patch.new_bytes)?;
+            // Put back what the patch replaced, without recording it
+            self.memory.poke(patch.address, &patch.old_bytes)?;
+            self.mark_patch();
             Ok(())
         } else {
             Err(DebuggerError::NothingToUndo)
@@ -384,7 +889,8 @@ impl Debugger {
     pub fn redo_patch(&mut self) -> Result<(), DebuggerError> {
         if let Some(patch) = self.patch_history.redo() {
             // Apply the forward patch without recording it
-            self.memory.write_bytes(patch.address, &patch.new_bytes)?;
+            self.memory.poke(patch.address, &patch.new_bytes)?;
+            self.mark_patch();
             Ok(())
         } else {
             Err(DebuggerError::NothingToRedo)
@@ -411,11 +917,208 @@ impl Debugger {
         self.patch_history.redo_count()
     }
 
+    /// The most recent patch still applied
+    pub fn last_patch(&self) -> Option<&MemoryPatch> {
+        self.patch_history.last()
+    }
+
+    /// Bytes the player's patches have changed since the last hard reset
+    pub fn net_patches(&self) -> Vec<MemoryPatch> {
+        self.patch_history.net_changes()
+    }
+
+    /// Bytes at `address` as the program was loaded, before any patch or
+    /// self-modification
+    pub fn original_bytes(&self, address: u32, count: usize) -> Vec<u8> {
+        self.initial_memory.read_bytes(address, count)
+    }
+
+    /// Lines of `lines` whose bytes differ from the program as loaded, with
+    /// the instruction that was there before
+    pub fn modified_lines(&self, lines: &[DisassemblyLine]) -> Vec<ModifiedLine> {
+        let mut disassembler = Disassembler::new();
+        // End of the last original instruction decoded, so the lines a
+        // longer instruction was replaced with only name it once
+        let mut covered_until = 0u64;
+        let mut modified = Vec::new();
+        for line in lines {
+            if self.original_bytes(line.address, line.bytes.len()) == line.bytes {
+                continue;
+            }
+            let mut original = None;
+            if u64::from(line.address) >= covered_until {
+                let bytes = self.original_bytes(line.address, MAX_INSTRUCTION_LEN);
+                if let Some(decoded) = disassembler
+                    .disassemble(&bytes, line.address, 1)
+                    .into_iter()
+                    .next()
+                {
+                    covered_until = u64::from(line.address) + decoded.length as u64;
+                    original = Some(decoded.text);
+                }
+            }
+            modified.push(ModifiedLine {
+                address: line.address,
+                original,
+            });
+        }
+        modified
+    }
+
+    /// Install a handler for `INT vector` (replacing any existing one)
+    pub fn set_interrupt_handler(&mut self, vector: u8, handler: InterruptHandler) {
+        self.interrupt_handlers.insert(vector, handler);
+    }
+
+    /// Replace the cycle costs behind RDTSC
+    pub fn set_cycle_model(&mut self, model: CycleModel) {
+        self.executor.set_cycle_model(model);
+    }
+
+    pub fn cycle_model(&self) -> &CycleModel {
+        self.executor.cycle_model()
+    }
+
+    /// Seed of the session's random numbers (`INT` "random" handlers and
+    /// RNG devices without a seed of their own)
+    pub fn rng_seed(&self) -> u32 {
+        self.rng.seed()
+    }
+
+    /// Reseed the session's random numbers; every run from here on, and
+    /// every replay with the same seed, sees the same sequence
+    pub fn set_rng_seed(&mut self, seed: u32) {
+        self.rng.reseed(seed);
+        self.memory.reseed_devices(seed);
+        self.timeline.clear();
+    }
+
+    /// Copy the program's input to `placement` before every run
+    pub fn set_input_placement(&mut self, placement: InputPlacement) -> Result<(), DebuggerError> {
+        self.input.set_placement(placement);
+        self.input.apply(&mut self.cpu, &mut self.memory)?;
+        Ok(())
+    }
+
+    /// Replace the program's input. It takes effect from the start of the
+    /// next run: the input buffer is refilled and stdin rewound on reset.
+    pub fn set_input(&mut self, data: &[u8]) {
+        self.input.set_data(data);
+        self.timeline.clear();
+    }
+
+    /// The program's current input
+    pub fn input(&self) -> &[u8] {
+        self.input.data()
+    }
+
+    /// Where the input is copied before each run, if anywhere
+    pub fn input_placement(&self) -> Option<&InputPlacement> {
+        self.input.placement()
+    }
+
+    /// Check these values for changes after every instruction
+    pub fn set_watches(&mut self, watches: Vec<Watch>) {
+        self.watches = watches;
+    }
+
+    pub fn watches(&self) -> &[Watch] {
+        &self.watches
+    }
+
+    /// Stop running when a watched value changes
+    pub fn set_break_on_watch(&mut self, enabled: bool) {
+        self.break_on_watch = enabled;
+    }
+
+    pub fn breaks_on_watch(&self) -> bool {
+        self.break_on_watch
+    }
+
+    /// The most recent change to a watched value, if not yet taken
+    pub fn take_watch_hit(&mut self) -> Option<WatchHit> {
+        self.watch_hit.take()
+    }
+
+    /// Stop whenever `flag` changes, or stop stopping
+    pub fn set_flag_break(&mut self, flag: Flag, enabled: bool) {
+        if enabled {
+            self.flag_breaks.insert(flag);
+        } else {
+            self.flag_breaks.remove(&flag);
+        }
+    }
+
+    /// Flags whose changes stop execution
+    pub fn flag_breaks(&self) -> &BTreeSet<Flag> {
+        &self.flag_breaks
+    }
+
+    /// The most recent change to a flag being broken on, if not yet taken
+    pub fn take_flag_hit(&mut self) -> Option<FlagHit> {
+        self.flag_hit.take()
+    }
+
+    /// Times each mnemonic was executed since last taken, by name
+    pub fn take_mnemonic_counts(&mut self) -> BTreeMap<String, u64> {
+        self.mnemonic_counts
+            .drain()
+            .map(|(mnemonic, count)| (format!("{:?}", mnemonic).to_uppercase(), count))
+            .collect()
+    }
+
+    /// Get the handler installed for a vector
+    pub fn interrupt_handler(&self, vector: u8) -> Option<&InterruptHandler> {
+        self.interrupt_handlers.get(&vector)
+    }
+
+    /// Choose when execution history is recorded. Long runs are considerably
+    /// faster without it, but `step_back` cannot undo uncaptured instructions.
+    pub fn set_capture_policy(&mut self, policy: CapturePolicy) {
+        self.capture_policy = policy;
+        if policy == CapturePolicy::Never {
+            self.history.clear();
+        }
+    }
+
+    /// When execution history is recorded
+    pub fn capture_policy(&self) -> CapturePolicy {
+        self.capture_policy
+    }
+
+    /// Control flow of the last run, if it was captured
+    pub fn last_trace(&self) -> Option<&RunTrace> {
+        self.last_trace.as_ref()
+    }
+
     /// Get execution history
     pub fn history(&self) -> &VecDeque<HistoryEntry> {
         &self.history
     }
 
+    /// How `reg` evolved over the last `count` recorded instructions, oldest first
+    pub fn register_timeline(&self, reg: Register, count: usize) -> Vec<RegisterSample> {
+        let skip = self.history.len().saturating_sub(count);
+        self.history
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(i, entry)| {
+                // The state after an instruction is the snapshot before the next one
+                let after = self
+                    .history
+                    .get(i + 1)
+                    .map(|next| &next.cpu_snapshot)
+                    .unwrap_or(&self.cpu);
+                RegisterSample {
+                    eip: entry.eip,
+                    instruction_text: entry.instruction_text.clone(),
+                    value: after.get_register(reg),
+                }
+            })
+            .collect()
+    }
+
     /// Detect which registers changed between two states
     fn detect_register_changes(&self, old: &CpuState) -> Vec<String> {
         let mut changes = Vec::new();
@@ -463,13 +1166,477 @@ mod tests {
         dbg.cpu.regs.esp = 0x3000;
 
         // MOV EAX, 0x42; HLT
-        dbg.memory.load(0x1000, &[0xB8, 0x42, 0x00, 0x00, 0x00, 0xF4]).unwrap();
+        dbg.memory
+            .load(0x1000, &[0xB8, 0x42, 0x00, 0x00, 0x00, 0xF4])
+            .unwrap();
 
         let result = dbg.run().unwrap();
         assert!(matches!(result, RunResult::Halted));
         assert_eq!(dbg.cpu.regs.eax, 0x42);
     }
 
+    #[test]
+    fn test_run_without_history() {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+        dbg.max_instructions = 1_000_000;
+
+        // MOV ECX, 1000; XOR EAX, EAX; ADD EAX, ECX; DEC ECX; JNZ -5; HLT
+        dbg.memory
+            .load(
+                0x1000,
+                &[
+                    0xB9, 0xE8, 0x03, 0x00, 0x00, 0x31, 0xC0, 0x01, 0xC8, 0x49, 0x75, 0xFB, 0xF4,
+                ],
+            )
+            .unwrap();
+        dbg.save_initial_state();
+
+        let result = dbg.run().unwrap();
+        assert!(matches!(result, RunResult::Halted));
+        assert_eq!(dbg.cpu.regs.eax, 500_500);
+        assert_eq!(dbg.history().len(), 1000);
+
+        dbg.reset();
+        dbg.set_capture_policy(CapturePolicy::Never);
+        let result = dbg.run().unwrap();
+        assert!(matches!(result, RunResult::Halted));
+        assert_eq!(dbg.cpu.regs.eax, 500_500);
+        assert!(dbg.history().is_empty());
+        assert!(dbg.step_back().is_none());
+    }
+
+    #[test]
+    fn test_register_timeline() {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+
+        // MOV ECX, 3; XOR EAX, EAX; ADD EAX, ECX; DEC ECX; JNZ -5; HLT
+        dbg.memory
+            .load(
+                0x1000,
+                &[
+                    0xB9, 0x03, 0x00, 0x00, 0x00, 0x31, 0xC0, 0x01, 0xC8, 0x49, 0x75, 0xFB, 0xF4,
+                ],
+            )
+            .unwrap();
+        dbg.run().unwrap();
+
+        let ecx: Vec<u32> = dbg
+            .register_timeline(Register::Ecx, 100)
+            .iter()
+            .map(|sample| sample.value)
+            .collect();
+        assert_eq!(ecx, vec![3, 3, 3, 2, 2, 2, 1, 1, 1, 0, 0, 0]);
+
+        let eax = dbg.register_timeline(Register::Eax, 3);
+        assert_eq!(eax.len(), 3);
+        assert_eq!(eax[0].eip, 0x1009);
+        assert_eq!(eax[2].instruction_text, "hlt");
+        assert_eq!(eax[2].value, 6);
+    }
+
+    #[test]
+    fn test_mmio_devices() {
+        use crate::emulator::{RandomDevice, SerialPort};
+
+        let mut dbg = Debugger::new(0x10000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+        let serial = dbg
+            .memory
+            .map_device(0xF000, SerialPort::SIZE, SerialPort::new());
+        dbg.memory
+            .map_device(0xF010, RandomDevice::SIZE, RandomDevice::new(7));
+
+        // MOV BYTE [0xF000], 'H'; MOV BYTE [0xF000], 'i'; MOV EAX, [0xF010]; HLT
+        dbg.memory
+            .load(
+                0x1000,
+                &[
+                    0xC6, 0x05, 0x00, 0xF0, 0x00, 0x00, b'H', 0xC6, 0x05, 0x00, 0xF0, 0x00, 0x00,
+                    b'i', 0xA1, 0x10, 0xF0, 0x00, 0x00, 0xF4,
+                ],
+            )
+            .unwrap();
+        dbg.save_initial_state();
+
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+        assert_eq!(serial.lock().unwrap().output().as_deref(), Some("Hi"));
+        let random = dbg.cpu.regs.eax;
+        assert_ne!(random, 0);
+
+        // Device writes never reach page storage; reset restores power-on state
+        dbg.reset();
+        assert_eq!(serial.lock().unwrap().output().as_deref(), Some(""));
+        dbg.run().unwrap();
+        assert_eq!(dbg.cpu.regs.eax, random);
+    }
+
+    #[test]
+    fn test_looking_leaves_devices_alone() {
+        use crate::emulator::{MmioDevice, RandomDevice};
+
+        let mut dbg = Debugger::new(0x10000);
+        dbg.cpu.eip = 0x10F9;
+        dbg.cpu.regs.esp = 0x3000;
+        dbg.memory
+            .map_device(0x1100, RandomDevice::SIZE, RandomDevice::new(7));
+
+        // MOV EAX, [0x1100]; HLT, ending right before the device so that
+        // fetching the MOV prefetches into it
+        dbg.memory
+            .load(0x10F9, &[0xA1, 0x00, 0x11, 0x00, 0x00, 0xF4])
+            .unwrap();
+
+        // Drawing the disassembly reads across the device too
+        dbg.disassemble(20);
+        dbg.disassemble_at(0x10F0, 20);
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+
+        // One dword read draws exactly one number
+        let mut fresh = RandomDevice::new(7);
+        let expected =
+            u32::from_le_bytes([fresh.read(0), fresh.read(1), fresh.read(2), fresh.read(3)]);
+        assert_eq!(dbg.cpu.regs.eax, expected);
+        assert_eq!(fresh.read(1), expected.to_le_bytes()[1]);
+        assert_ne!(fresh.read(0), expected.to_le_bytes()[0]);
+    }
+
+    #[test]
+    fn test_interrupt_handlers() {
+        use crate::emulator::Register;
+
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+        dbg.set_interrupt_handler(0x21, InterruptHandler::Jump(0x1100));
+        dbg.set_interrupt_handler(0x1A, InterruptHandler::Ticks(Register::Edx));
+        dbg.set_interrupt_handler(0x80, InterruptHandler::Halt);
+
+        // INT 0x21; INT 0x1A; INT 0x80; HLT
+        dbg.memory
+            .load(0x1000, &[0xCD, 0x21, 0xCD, 0x1A, 0xCD, 0x80, 0xF4])
+            .unwrap();
+        // Handler: MOV EAX, 5; IRETD
+        dbg.memory
+            .load(0x1100, &[0xB8, 0x05, 0x00, 0x00, 0x00, 0xCF])
+            .unwrap();
+
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+        assert_eq!(dbg.cpu.regs.eax, 5);
+        assert_eq!(dbg.cpu.regs.edx, 4);
+        assert_eq!(dbg.cpu.regs.esp, 0x3000);
+        assert_eq!(dbg.cpu.eip, 0x1004);
+    }
+
+    #[test]
+    fn test_ticks_follow_the_timeline() {
+        use crate::emulator::Register;
+
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.set_interrupt_handler(0x1A, InterruptHandler::Ticks(Register::Edx));
+        // NOP; NOP; INT 0x1A; HLT
+        dbg.memory
+            .load(0x1000, &[0x90, 0x90, 0xCD, 0x1A, 0xF4])
+            .unwrap();
+        dbg.save_initial_state();
+
+        dbg.run().unwrap();
+        assert_eq!(dbg.cpu.regs.edx, 3);
+
+        // Restarting and replaying read the same time as the first run
+        dbg.soft_reset();
+        dbg.run().unwrap();
+        assert_eq!(dbg.cpu.regs.edx, 3);
+        dbg.seek(1).unwrap();
+        dbg.seek(4).unwrap();
+        assert_eq!(dbg.cpu.regs.edx, 3);
+        assert_eq!(dbg.total_instructions, 12);
+    }
+
+    #[test]
+    fn test_session_rng() {
+        use crate::emulator::{RandomDevice, Register};
+
+        let mut dbg = Debugger::new(0x10000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+        dbg.memory
+            .map_device(0xF010, RandomDevice::SIZE, RandomDevice::session());
+        dbg.set_interrupt_handler(0x1B, InterruptHandler::Random(Register::Ebx));
+
+        // INT 0x1B; MOV EAX, [0xF010]; HLT
+        dbg.memory
+            .load(0x1000, &[0xCD, 0x1B, 0xA1, 0x10, 0xF0, 0x00, 0x00, 0xF4])
+            .unwrap();
+        dbg.save_initial_state();
+        dbg.set_rng_seed(1234);
+        assert_eq!(dbg.rng_seed(), 1234);
+
+        let run = |dbg: &mut Debugger| {
+            dbg.reset();
+            dbg.run().unwrap();
+            (dbg.cpu.regs.ebx, dbg.cpu.regs.eax)
+        };
+        let first = run(&mut dbg);
+        assert_ne!(first.0, 0);
+        assert_eq!(run(&mut dbg), first);
+
+        // Another seed gives other numbers, and the first seed brings them back
+        dbg.set_rng_seed(99);
+        assert_ne!(run(&mut dbg), first);
+        dbg.set_rng_seed(1234);
+        assert_eq!(run(&mut dbg), first);
+    }
+
+    #[test]
+    fn test_program_input() {
+        use crate::emulator::Register;
+
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+        dbg.save_initial_state();
+        dbg.set_interrupt_handler(0x80, InterruptHandler::Read);
+        dbg.set_input(b"KEY");
+        dbg.set_input_placement(InputPlacement {
+            address: 0x2000,
+            max_length: 8,
+            length_register: Some(Register::Esi),
+        })
+        .unwrap();
+        assert_eq!(dbg.cpu.regs.esi, 3);
+
+        // MOV ECX, 0x2100; MOV EDX, 2; INT 0x80; HLT
+        dbg.memory
+            .load(
+                0x1000,
+                &[
+                    0xB9, 0x00, 0x21, 0x00, 0x00, 0xBA, 0x02, 0x00, 0x00, 0x00, 0xCD, 0x80, 0xF4,
+                ],
+            )
+            .unwrap();
+        dbg.save_initial_state();
+
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+        assert_eq!(dbg.cpu.regs.eax, 2);
+        assert_eq!(dbg.memory.slice(0x2100, 2).unwrap(), b"KE");
+
+        // New input is placed and read from the start after a reset
+        dbg.set_input(b"LOCK");
+        dbg.reset();
+        assert_eq!(dbg.memory.slice(0x2000, 5).unwrap(), b"LOCK\0");
+        assert_eq!(dbg.cpu.regs.esi, 4);
+        dbg.run().unwrap();
+        assert_eq!(dbg.memory.slice(0x2100, 2).unwrap(), b"LO");
+    }
+
+    #[test]
+    fn test_break_on_watch() {
+        use crate::emulator::Register;
+
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+        dbg.set_watches(vec![Watch::Register(Register::Eax)]);
+
+        // MOV ECX, 2; MOV EAX, 1; HLT
+        dbg.memory
+            .load(
+                0x1000,
+                &[
+                    0xB9, 0x02, 0x00, 0x00, 0x00, 0xB8, 0x01, 0x00, 0x00, 0x00, 0xF4,
+                ],
+            )
+            .unwrap();
+        dbg.save_initial_state();
+
+        // Without breaking, the change is only noted
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+        assert_eq!(dbg.take_watch_hit().unwrap().eip, 0x1005);
+        assert!(dbg.take_watch_hit().is_none());
+
+        dbg.reset();
+        dbg.set_break_on_watch(true);
+        match dbg.run().unwrap() {
+            RunResult::Watch(hit) => {
+                assert_eq!(hit.eip, 0x1005);
+                assert_eq!(hit.new, vec![1, 0, 0, 0]);
+            }
+            other => panic!("expected a watch stop, got {:?}", other),
+        }
+        assert_eq!(dbg.cpu.eip, 0x100A);
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+    }
+
+    #[test]
+    fn test_break_on_flag_change() {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+
+        // MOV EAX, 2; DEC EAX; DEC EAX; HLT
+        dbg.memory
+            .load(0x1000, &[0xB8, 0x02, 0x00, 0x00, 0x00, 0x48, 0x48, 0xF4])
+            .unwrap();
+        dbg.save_initial_state();
+        dbg.set_flag_break(Flag::Zf, true);
+
+        match dbg.run().unwrap() {
+            RunResult::FlagChange(hit) => {
+                assert_eq!((hit.flag, hit.eip, hit.set), (Flag::Zf, 0x1006, true));
+                assert!(hit.instruction.to_lowercase().starts_with("dec"));
+            }
+            other => panic!("expected a flag stop, got {:?}", other),
+        }
+        assert_eq!(dbg.state, DebuggerState::AtFlagChange(Flag::Zf));
+        assert_eq!(dbg.cpu.eip, 0x1007);
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+
+        dbg.reset();
+        dbg.set_flag_break(Flag::Zf, false);
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+        assert!(dbg.take_flag_hit().is_none());
+    }
+
+    #[test]
+    fn test_mnemonic_counts() {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+
+        // MOV EAX, 2; DEC EAX; DEC EAX; HLT
+        dbg.memory
+            .load(0x1000, &[0xB8, 0x02, 0x00, 0x00, 0x00, 0x48, 0x48, 0xF4])
+            .unwrap();
+        dbg.save_initial_state();
+        dbg.run().unwrap();
+
+        let counts = dbg.take_mnemonic_counts();
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            [
+                ("DEC".to_string(), 2),
+                ("HLT".to_string(), 1),
+                ("MOV".to_string(), 1)
+            ]
+        );
+        assert!(dbg.take_mnemonic_counts().is_empty());
+
+        // Replaying to a position isn't running anything new, stepping on is
+        dbg.seek(1).unwrap();
+        assert!(dbg.take_mnemonic_counts().is_empty());
+        dbg.step().unwrap();
+        assert_eq!(
+            dbg.take_mnemonic_counts().into_iter().collect::<Vec<_>>(),
+            [("DEC".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_unhandled_interrupt_stops() {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+
+        // INT 0x21; HLT
+        dbg.memory.load(0x1000, &[0xCD, 0x21, 0xF4]).unwrap();
+
+        let result = dbg.run().unwrap();
+        assert!(matches!(result, RunResult::Error(ref msg) if msg.contains("0x21")));
+        assert_eq!(dbg.cpu.eip, 0x1000);
+    }
+
+    #[test]
+    fn test_unsupported_instruction_stops() {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+
+        // NOP; DIV ECX; HLT
+        dbg.memory.load(0x1000, &[0x90, 0xF7, 0xF1, 0xF4]).unwrap();
+
+        let result = dbg.run().unwrap();
+        assert!(
+            matches!(result, RunResult::Error(ref msg) if msg == "Unsupported instruction: Div at 0x00001001")
+        );
+        assert_eq!(dbg.cpu.eip, 0x1001);
+        assert!(matches!(dbg.state, DebuggerState::Error(_)));
+        assert_eq!(dbg.history().len(), 1);
+
+        // Stepping stays put instead of failing
+        let step = dbg.step().unwrap();
+        assert!(matches!(step.state, DebuggerState::Error(_)));
+        assert_eq!(dbg.cpu.eip, 0x1001);
+    }
+
+    #[test]
+    fn test_seek_replays_to_timeline_position() {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+
+        // MOV EAX, 1; INC EAX; INC EAX; HLT
+        dbg.memory
+            .load(0x1000, &[0xB8, 0x01, 0x00, 0x00, 0x00, 0x40, 0x40, 0xF4])
+            .unwrap();
+        dbg.save_initial_state();
+        dbg.set_breakpoint(0x1006);
+
+        assert!(matches!(dbg.run().unwrap(), RunResult::Breakpoint(0x1006)));
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+        assert_eq!(dbg.timeline().length(), 4);
+        assert_eq!(dbg.timeline().markers()[0].mark, TimelineMark::Breakpoint);
+        assert_eq!(dbg.timeline().markers()[0].position, 2);
+
+        dbg.seek(1).unwrap();
+        assert_eq!(dbg.position(), 1);
+        assert_eq!(dbg.cpu.regs.eax, 1);
+        assert_eq!(dbg.cpu.eip, 0x1005);
+        assert_eq!(dbg.state, DebuggerState::Ready);
+        assert_eq!(dbg.history().len(), 1);
+
+        dbg.seek(3).unwrap();
+        assert_eq!(dbg.cpu.regs.eax, 3);
+        assert_eq!(dbg.timeline().length(), 4);
+
+        // A patch forgets what came after it
+        dbg.seek(2).unwrap();
+        dbg.patch(0x1006, &[0x90]).unwrap();
+        assert_eq!(dbg.timeline().length(), 2);
+        assert_eq!(
+            dbg.timeline().markers().last().unwrap().mark,
+            TimelineMark::Patch
+        );
+    }
+
+    #[test]
+    fn test_step_only_capture_policy() {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+        dbg.set_capture_policy(CapturePolicy::StepOnly);
+
+        // NOP; NOP; NOP; HLT
+        dbg.memory.load(0x1000, &[0x90, 0x90, 0x90, 0xF4]).unwrap();
+        dbg.set_breakpoint(0x1002);
+
+        // Running to the breakpoint records nothing
+        assert!(matches!(dbg.run().unwrap(), RunResult::Breakpoint(0x1002)));
+        assert!(dbg.history().is_empty());
+
+        // Stepping is still recorded and can be undone
+        dbg.step().unwrap();
+        assert_eq!(dbg.history().len(), 1);
+        let entry = dbg.step_back().unwrap();
+        assert_eq!(entry.eip, 0x1002);
+        assert_eq!(dbg.cpu.eip, 0x1002);
+    }
+
     #[test]
     fn test_breakpoint() {
         let mut dbg = Debugger::new(0x4000);
@@ -514,7 +1681,9 @@ mod tests {
         dbg.cpu.regs.esp = 0x3000;
 
         // MOV EAX, 0x42; HLT
-        dbg.memory.load(0x1000, &[0xB8, 0x42, 0x00, 0x00, 0x00, 0xF4]).unwrap();
+        dbg.memory
+            .load(0x1000, &[0xB8, 0x42, 0x00, 0x00, 0x00, 0xF4])
+            .unwrap();
         dbg.save_initial_state();
 
         dbg.run().unwrap();
@@ -524,4 +1693,149 @@ mod tests {
         assert_eq!(dbg.cpu.regs.eax, 0);
         assert_eq!(dbg.cpu.eip, 0x1000);
     }
+
+    #[test]
+    fn test_soft_reset_keeps_patches() {
+        let mut dbg = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        // MOV EAX, 0x42; MOV [0x2000], EAX; HLT
+        dbg.load_code(
+            0x1000,
+            &[
+                0xB8, 0x42, 0x00, 0x00, 0x00, 0xA3, 0x00, 0x20, 0x00, 0x00, 0xF4,
+            ],
+        )
+        .unwrap();
+        dbg.save_initial_state();
+
+        dbg.patch(0x1001, &[0x99]).unwrap();
+        dbg.run().unwrap();
+        assert_eq!(dbg.memory.read_u32(0x2000).unwrap(), 0x99);
+
+        // The patch and its history survive; the program's own write doesn't
+        dbg.soft_reset();
+        assert_eq!(dbg.cpu.eip, 0x1000);
+        assert_eq!(dbg.memory.read_u8(0x1001).unwrap(), 0x99);
+        assert_eq!(dbg.memory.read_u32(0x2000).unwrap(), 0);
+        assert!(dbg.can_undo());
+        dbg.undo_patch().unwrap();
+        assert_eq!(dbg.memory.read_u8(0x1001).unwrap(), 0x42);
+
+        // Undone patches stay undone
+        dbg.soft_reset();
+        assert_eq!(dbg.memory.read_u8(0x1001).unwrap(), 0x42);
+
+        // A hard reset drops them
+        dbg.redo_patch().unwrap();
+        dbg.reset();
+        assert_eq!(dbg.memory.read_u8(0x1001).unwrap(), 0x42);
+        assert!(!dbg.can_undo());
+    }
+
+    /// MOV EAX, [0x2000]; INC EAX; MOV [0x2000], EAX; HLT, with 0x10 at 0x2000
+    fn counter() -> Debugger {
+        let mut dbg = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        dbg.load_code(
+            0x1000,
+            &[
+                0xA1, 0x00, 0x20, 0x00, 0x00, 0x40, 0xA3, 0x00, 0x20, 0x00, 0x00, 0xF4,
+            ],
+        )
+        .unwrap();
+        dbg.load_data(0x2000, &[0x10]).unwrap();
+        dbg.save_initial_state();
+        dbg
+    }
+
+    #[test]
+    fn test_undo_after_run_restores_the_image() {
+        // Run, patch what the program wrote, restart, undo
+        let mut dbg = counter();
+        dbg.run().unwrap();
+        assert_eq!(dbg.memory.read_u8(0x2000).unwrap(), 0x11);
+        dbg.patch(0x2000, &[0x40]).unwrap();
+        dbg.soft_reset();
+        assert_eq!(dbg.memory.read_u8(0x2000).unwrap(), 0x40);
+        dbg.undo_patch().unwrap();
+        assert_eq!(dbg.memory.read_u8(0x2000).unwrap(), 0x10);
+
+        // Patch, run, undo: the patch goes, not what the run made of it
+        let mut dbg = counter();
+        dbg.patch(0x2000, &[0x40]).unwrap();
+        dbg.run().unwrap();
+        assert_eq!(dbg.memory.read_u8(0x2000).unwrap(), 0x41);
+        dbg.undo_patch().unwrap();
+        assert_eq!(dbg.memory.read_u8(0x2000).unwrap(), 0x10);
+        dbg.redo_patch().unwrap();
+        assert_eq!(dbg.memory.read_u8(0x2000).unwrap(), 0x40);
+        dbg.soft_reset();
+        dbg.run().unwrap();
+        assert_eq!(dbg.memory.read_u8(0x2000).unwrap(), 0x41);
+    }
+
+    #[test]
+    fn test_overlapping_patches_undo_in_order() {
+        let mut dbg = counter();
+        // INC EAX -> NOP, then NOP + first byte of the store -> two INCs
+        dbg.patch(0x1005, &[0x90]).unwrap();
+        dbg.run().unwrap();
+        dbg.force_patch(0x1005, &[0x40, 0x40]).unwrap();
+
+        dbg.undo_patch().unwrap();
+        assert_eq!(dbg.memory.slice(0x1005, 2).unwrap(), [0x90, 0xA3]);
+        dbg.undo_patch().unwrap();
+        assert_eq!(dbg.memory.slice(0x1005, 2).unwrap(), [0x40, 0xA3]);
+        assert!(dbg.net_patches().is_empty());
+
+        dbg.redo_patch().unwrap();
+        dbg.redo_patch().unwrap();
+        assert_eq!(dbg.memory.slice(0x1005, 2).unwrap(), [0x40, 0x40]);
+        assert_eq!(
+            dbg.net_patches(),
+            [MemoryPatch::new(0x1006, vec![0xA3], vec![0x40])]
+        );
+        assert!(dbg.undo_patch().is_ok() && dbg.undo_patch().is_ok() && dbg.undo_patch().is_err());
+    }
+
+    #[test]
+    fn test_patch_keeps_instruction_boundaries() {
+        let mut dbg = counter();
+        // Half of the store's opcode would run as something else
+        let err = dbg.patch(0x1005, &[0x40, 0x40]).unwrap_err();
+        assert!(matches!(
+            err,
+            DebuggerError::PatchMisfit(PatchFit::EndsMidInstruction { start: 0x1006, .. })
+        ));
+        assert!(err.to_string().contains("stray bytes"));
+        assert_eq!(dbg.memory.slice(0x1005, 2).unwrap(), [0x40, 0xA3]);
+        assert_eq!(dbg.undo_count(), 0);
+
+        dbg.force_patch(0x1005, &[0x40, 0x40]).unwrap();
+        assert_eq!(dbg.memory.slice(0x1005, 2).unwrap(), [0x40, 0x40]);
+    }
+
+    #[test]
+    fn test_modified_lines_name_the_original() {
+        let mut dbg = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        // CMP EAX, 0x1337; JNE +7; MOV EAX, 1; HLT
+        dbg.load_code(
+            0x1000,
+            &[
+                0x3D, 0x37, 0x13, 0x00, 0x00, 0x75, 0x07, 0xB8, 0x01, 0x00, 0x00, 0x00, 0xF4,
+            ],
+        )
+        .unwrap();
+        dbg.save_initial_state();
+        let lines = dbg.disassemble_at(0x1000, 6);
+        assert!(dbg.modified_lines(&lines).is_empty());
+
+        // The JNE becomes two NOPs: both are marked, the first names the JNE
+        dbg.patch(0x1005, &[0x90, 0x90]).unwrap();
+        let lines = dbg.disassemble_at(0x1000, 6);
+        let modified = dbg.modified_lines(&lines);
+        let addresses: Vec<u32> = modified.iter().map(|line| line.address).collect();
+        assert_eq!(addresses, [0x1005, 0x1006]);
+        assert!(modified[0].original.as_deref().unwrap().starts_with("jne"));
+        assert_eq!(modified[1].original, None);
+        assert_eq!(dbg.original_bytes(0x1005, 2), [0x75, 0x07]);
+    }
 }
@@ -0,0 +1,172 @@
+use iced_x86::{Instruction, Mnemonic};
+
+use crate::puzzle::ValidationResult;
+
+use super::{Debugger, DebuggerState};
+
+/// Describe the last run in plain sentences: where it stopped, which
+/// branches went which way, how often loops ran, where EAX came from and
+/// what the validator said.
+pub fn explain_run(debugger: &mut Debugger, validation: Option<&ValidationResult>) -> Vec<String> {
+    let Some(trace) = debugger.last_trace().cloned() else {
+        return vec!["No run recorded yet. Press F5 to run the program.".to_string()];
+    };
+
+    let mut lines = Vec::new();
+
+    let stop = match debugger.state {
+        DebuggerState::Halted => format!("halted at 0x{:08X}", debugger.cpu.eip),
+        DebuggerState::AtBreakpoint(addr) => format!("stopped at the breakpoint at 0x{:08X}", addr),
+        DebuggerState::AtWatch => format!(
+            "stopped at 0x{:08X} when a watched value changed",
+            debugger.cpu.eip
+        ),
+        DebuggerState::AtFlagChange(flag) => format!(
+            "stopped at 0x{:08X} when {} changed",
+            debugger.cpu.eip,
+            flag.name()
+        ),
+        DebuggerState::LimitExceeded => "hit the instruction limit (an endless loop?)".to_string(),
+        DebuggerState::Error(ref e) => format!("stopped with an error: {}", e),
+        _ => format!("stopped at 0x{:08X}", debugger.cpu.eip),
+    };
+    lines.push(format!(
+        "The program ran {} instruction{} and {}.",
+        trace.instructions,
+        if trace.instructions == 1 { "" } else { "s" },
+        stop
+    ));
+
+    // Conditional branches
+    for from in trace.sources() {
+        let Some(line) = debugger.disassemble_at(from, 1).into_iter().next() else {
+            continue;
+        };
+        if !is_conditional_branch(&line.instruction) {
+            continue;
+        }
+
+        let target = line.instruction.near_branch_target() as u32;
+        let fallthrough = from.wrapping_add(line.length as u32);
+        let taken = trace.count(from, target);
+        let not_taken = trace.count(from, fallthrough);
+
+        let outcome = match (taken, not_taken) {
+            (_, 0) => format!("was taken (jumped to 0x{:08X})", target),
+            (0, _) => "was not taken (fell through)".to_string(),
+            _ => format!(
+                "was taken {} and not taken {}",
+                times(taken),
+                times(not_taken)
+            ),
+        };
+        lines.push(format!(
+            "The `{}` at 0x{:08X} {}.",
+            line.text, from, outcome
+        ));
+    }
+
+    // Loops
+    for (from, to, count) in trace.back_edges() {
+        lines.push(format!(
+            "A loop jumped back from 0x{:08X} to 0x{:08X} {}.",
+            from,
+            to,
+            times(count)
+        ));
+    }
+
+    // Where EAX came from
+    let eax = debugger.cpu.regs.eax;
+    let mut eax_line = format!("EAX ended as 0x{:08X} ({}).", eax, eax);
+    let history = debugger.history();
+    let mut next_eax = eax;
+    for entry in history.iter().rev() {
+        if entry.cpu_snapshot.regs.eax != next_eax {
+            eax_line.push_str(&format!(
+                " It was last set by `{}` at 0x{:08X}.",
+                entry.instruction_text, entry.eip
+            ));
+            break;
+        }
+        next_eax = entry.cpu_snapshot.regs.eax;
+    }
+    lines.push(eax_line);
+
+    match validation {
+        Some(ValidationResult::Success) => lines.push("Validation passed.".to_string()),
+        Some(ValidationResult::Failure(msg)) => lines.push(format!("Validation failed: {}.", msg)),
+        Some(ValidationResult::Error(e)) => lines.push(format!("Validation error: {}.", e)),
+        None => {}
+    }
+
+    lines
+}
+
+fn is_conditional_branch(instruction: &Instruction) -> bool {
+    matches!(
+        instruction.mnemonic(),
+        Mnemonic::Jo
+            | Mnemonic::Jno
+            | Mnemonic::Jb
+            | Mnemonic::Jae
+            | Mnemonic::Je
+            | Mnemonic::Jne
+            | Mnemonic::Jbe
+            | Mnemonic::Ja
+            | Mnemonic::Js
+            | Mnemonic::Jns
+            | Mnemonic::Jp
+            | Mnemonic::Jnp
+            | Mnemonic::Jl
+            | Mnemonic::Jge
+            | Mnemonic::Jle
+            | Mnemonic::Jg
+    )
+}
+
+fn times(count: u64) -> String {
+    match count {
+        1 => "once".to_string(),
+        2 => "twice".to_string(),
+        n => format!("{} times", n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explains_branches_loops_and_eax() {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.cpu.eip = 0x1000;
+        dbg.cpu.regs.esp = 0x3000;
+
+        // MOV ECX, 3; XOR EAX, EAX; ADD EAX, ECX; DEC ECX; JNZ -5; CMP EAX, 6; JE +1; NOP; HLT
+        dbg.memory
+            .load(
+                0x1000,
+                &[
+                    0xB9, 0x03, 0x00, 0x00, 0x00, 0x31, 0xC0, 0x01, 0xC8, 0x49, 0x75, 0xFB, 0x83,
+                    0xF8, 0x06, 0x74, 0x01, 0x90, 0xF4,
+                ],
+            )
+            .unwrap();
+        dbg.run().unwrap();
+
+        let failure =
+            ValidationResult::Failure("EAX = 0x00000006, expected 0x00000007".to_string());
+        let lines = explain_run(&mut dbg, Some(&failure));
+        let text = lines.join("\n");
+
+        assert!(lines[0].starts_with("The program ran 14 instructions and halted at 0x00001012."));
+        assert!(text.contains("at 0x0000100A was taken twice and not taken once"));
+        assert!(text.contains("at 0x0000100F was taken (jumped to 0x00001012)"));
+        assert!(text.contains("A loop jumped back from 0x0000100A to 0x00001007 twice."));
+        assert!(text.contains(
+            "EAX ended as 0x00000006 (6). It was last set by `add eax, ecx` at 0x00001007."
+        ));
+        assert!(text.ends_with("Validation failed: EAX = 0x00000006, expected 0x00000007."));
+    }
+}
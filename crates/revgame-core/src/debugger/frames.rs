@@ -0,0 +1,259 @@
+use crate::emulator::CpuState;
+
+/// Stack arguments shown for a cdecl call
+pub const CDECL_ARGS: usize = 4;
+
+/// A function's stack frame, opened by a CALL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// Address of the CALL instruction
+    pub call_site: u32,
+    /// Start of the called function
+    pub entry: u32,
+    /// Stack slot holding the return address
+    pub return_slot: u32,
+    /// Stack slot holding the caller's EBP, once the function pushes it
+    pub saved_ebp_slot: Option<u32>,
+    /// Lowest ESP seen while this was the innermost frame
+    pub lowest: u32,
+    /// EAX after the RET that closed this frame
+    pub return_value: Option<u32>,
+}
+
+impl Frame {
+    /// Stack slot of a cdecl argument (0 is the first)
+    pub fn argument_slot(&self, index: usize) -> u32 {
+        self.return_slot.wrapping_add(4 * (index as u32 + 1))
+    }
+}
+
+/// What a stack slot holds within a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotRole {
+    ReturnAddress,
+    SavedEbp,
+    Local,
+    /// A cdecl argument (0 is the first), pushed by the caller
+    Argument(usize),
+}
+
+impl SlotRole {
+    pub fn label(&self) -> String {
+        match self {
+            SlotRole::ReturnAddress => "return".to_string(),
+            SlotRole::SavedEbp => "saved EBP".to_string(),
+            SlotRole::Local => "local".to_string(),
+            SlotRole::Argument(index) => format!("arg {}", index + 1),
+        }
+    }
+}
+
+/// Follows CALL, PUSH EBP and RET to delimit the frames on the stack
+#[derive(Debug, Clone, Default)]
+pub struct FrameTracker {
+    /// Open frames, outermost first
+    frames: Vec<Frame>,
+    /// The frame the last RET closed, until taken
+    collapsed: Option<Frame>,
+    /// The frame the last RET closed
+    last_return: Option<Frame>,
+}
+
+impl FrameTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the frames after one instruction. `opcode` holds the first
+    /// bytes of the instruction and `before` the CPU state it started from.
+    pub fn observe(&mut self, opcode: &[u8], before_eip: u32, before_esp: u32, after: &CpuState) {
+        let esp = after.regs.esp;
+        let is_call = match opcode {
+            [0xE8, ..] => true,
+            [0xFF, modrm, ..] => (modrm >> 3) & 7 == 2,
+            _ => false,
+        };
+        let is_ret = matches!(opcode.first(), Some(0xC3 | 0xC2));
+
+        if is_call && esp == before_esp.wrapping_sub(4) {
+            self.frames.push(Frame {
+                call_site: before_eip,
+                entry: after.eip,
+                return_slot: esp,
+                saved_ebp_slot: None,
+                lowest: esp,
+                return_value: None,
+            });
+            return;
+        }
+
+        // A frame is gone once ESP has moved above its return address
+        while let Some(frame) = self.frames.last() {
+            if frame.return_slot >= esp {
+                break;
+            }
+            let mut frame = self.frames.pop().unwrap();
+            if is_ret && frame.return_slot == before_esp {
+                frame.return_value = Some(after.regs.eax);
+                self.last_return = Some(frame.clone());
+                self.collapsed = Some(frame);
+            }
+        }
+
+        if let Some(frame) = self.frames.last_mut() {
+            if opcode.first() == Some(&0x55)
+                && frame.saved_ebp_slot.is_none()
+                && esp == frame.return_slot.wrapping_sub(4)
+            {
+                frame.saved_ebp_slot = Some(esp);
+            }
+            frame.lowest = frame.lowest.min(esp);
+        }
+    }
+
+    /// Open frames, outermost first
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// The frame closed by the last RET, if not taken yet
+    pub fn take_collapsed(&mut self) -> Option<Frame> {
+        self.collapsed.take()
+    }
+
+    /// The frame closed by the most recent RET, with its return value
+    pub fn last_return(&self) -> Option<&Frame> {
+        self.last_return.as_ref()
+    }
+
+    /// Forget all frames (on reset)
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.collapsed = None;
+        self.last_return = None;
+    }
+
+    /// Drop frames the stack pointer has moved past (after stepping back)
+    pub fn unwind_to(&mut self, esp: u32) {
+        self.frames.retain(|frame| frame.return_slot >= esp);
+    }
+
+    /// Which frame a stack slot belongs to (0 is the outermost) and what it
+    /// holds. Slots between ESP and the innermost return address belong to
+    /// the innermost frame; the slots just above a return address are its
+    /// arguments, up to the caller's saved EBP.
+    pub fn classify(&self, address: u32, esp: u32) -> Option<(usize, SlotRole)> {
+        let mut low = esp;
+        for (depth, frame) in self.frames.iter().enumerate().rev() {
+            if address >= low && address <= frame.return_slot {
+                let role = if address == frame.return_slot {
+                    SlotRole::ReturnAddress
+                } else if Some(address) == frame.saved_ebp_slot {
+                    SlotRole::SavedEbp
+                } else {
+                    SlotRole::Local
+                };
+                return Some((depth, role));
+            }
+
+            let caller_limit = depth
+                .checked_sub(1)
+                .map(|outer| {
+                    let outer = &self.frames[outer];
+                    outer.saved_ebp_slot.unwrap_or(outer.return_slot)
+                })
+                .unwrap_or(u32::MAX);
+            if let Some(index) = (0..CDECL_ARGS).find(|&i| frame.argument_slot(i) == address) {
+                if address < caller_limit {
+                    return Some((depth, SlotRole::Argument(index)));
+                }
+            }
+            low = frame.return_slot.wrapping_add(4);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cpu(eip: u32, esp: u32) -> CpuState {
+        let mut cpu = CpuState::new(eip, esp);
+        cpu.regs.esp = esp;
+        cpu
+    }
+
+    #[test]
+    fn test_call_push_ret() {
+        let mut tracker = FrameTracker::new();
+
+        // CALL 0x1100 from 0x1000 with ESP 0x3000
+        tracker.observe(&[0xE8, 0xFB], 0x1000, 0x3000, &cpu(0x1100, 0x2FFC));
+        // PUSH EBP, then SUB ESP, 8
+        tracker.observe(&[0x55], 0x1100, 0x2FFC, &cpu(0x1101, 0x2FF8));
+        tracker.observe(&[0x83, 0xEC], 0x1101, 0x2FF8, &cpu(0x1104, 0x2FF0));
+
+        let frame = &tracker.frames()[0];
+        assert_eq!(frame.return_slot, 0x2FFC);
+        assert_eq!(frame.saved_ebp_slot, Some(0x2FF8));
+        assert_eq!(frame.lowest, 0x2FF0);
+
+        assert_eq!(
+            tracker.classify(0x2FFC, 0x2FF0),
+            Some((0, SlotRole::ReturnAddress))
+        );
+        assert_eq!(
+            tracker.classify(0x2FF8, 0x2FF0),
+            Some((0, SlotRole::SavedEbp))
+        );
+        assert_eq!(tracker.classify(0x2FF4, 0x2FF0), Some((0, SlotRole::Local)));
+        assert_eq!(
+            tracker.classify(0x3000, 0x2FF0),
+            Some((0, SlotRole::Argument(0)))
+        );
+        assert_eq!(tracker.classify(0x3010, 0x2FF0), None);
+
+        // LEAVE, then RET
+        tracker.observe(&[0xC9], 0x1104, 0x2FF0, &cpu(0x1105, 0x2FFC));
+        assert_eq!(tracker.frames().len(), 1);
+        let mut after = cpu(0x1005, 0x3000);
+        after.regs.eax = 7;
+        tracker.observe(&[0xC3], 0x1105, 0x2FFC, &after);
+        assert!(tracker.frames().is_empty());
+        assert_eq!(tracker.last_return().unwrap().return_value, Some(7));
+        assert_eq!(tracker.take_collapsed().unwrap().entry, 0x1100);
+        assert!(tracker.take_collapsed().is_none());
+    }
+
+    #[test]
+    fn test_nested_frames() {
+        let mut tracker = FrameTracker::new();
+        tracker.observe(&[0xE8], 0x1000, 0x3000, &cpu(0x1100, 0x2FFC));
+        tracker.observe(&[0x6A], 0x1100, 0x2FFC, &cpu(0x1102, 0x2FF8));
+        // CALL EAX (FF D0)
+        tracker.observe(&[0xFF, 0xD0], 0x1102, 0x2FF8, &cpu(0x1200, 0x2FF4));
+
+        assert_eq!(tracker.frames().len(), 2);
+        assert_eq!(
+            tracker.classify(0x2FF4, 0x2FF4),
+            Some((1, SlotRole::ReturnAddress))
+        );
+        assert_eq!(
+            tracker.classify(0x2FF8, 0x2FF4),
+            Some((1, SlotRole::Argument(0)))
+        );
+        // The caller's return address bounds the callee's arguments
+        assert_eq!(
+            tracker.classify(0x2FFC, 0x2FF4),
+            Some((0, SlotRole::ReturnAddress))
+        );
+
+        // JMP [EAX] (FF 20) is not a call
+        tracker.observe(&[0xFF, 0x20], 0x1200, 0x2FF4, &cpu(0x1300, 0x2FF4));
+        assert_eq!(tracker.frames().len(), 2);
+
+        tracker.unwind_to(0x2FFC);
+        assert_eq!(tracker.frames().len(), 1);
+    }
+}
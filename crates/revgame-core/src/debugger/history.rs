@@ -1,8 +1,8 @@
 /// History tracking for undo/redo functionality
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 
 /// A single memory modification action
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MemoryPatch {
     pub address: u32,
     pub old_bytes: Vec<u8>,
@@ -28,11 +28,22 @@ impl MemoryPatch {
     }
 }
 
+/// A disassembled line whose bytes differ from the program as loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModifiedLine {
+    pub address: u32,
+    /// The instruction that started at `address` originally, if one did
+    /// (not when the line sits inside an instruction that was replaced)
+    pub original: Option<String>,
+}
+
 /// Manages undo/redo history
 #[derive(Debug)]
 pub struct History {
     undo_stack: VecDeque<MemoryPatch>,
     redo_stack: VecDeque<MemoryPatch>,
+    /// Patches pushed off the undo stack: no longer undoable, but still applied
+    settled: Vec<MemoryPatch>,
     max_history: usize,
 }
 
@@ -41,6 +52,7 @@ impl History {
         Self {
             undo_stack: VecDeque::new(),
             redo_stack: VecDeque::new(),
+            settled: Vec::new(),
             max_history,
         }
     }
@@ -52,30 +64,27 @@ impl History {
 
         // Limit history size
         if self.undo_stack.len() > self.max_history {
-            self.undo_stack.pop_front();
+            self.settled.extend(self.undo_stack.pop_front());
         }
     }
 
-    /// Get the next undo action
+    /// Take back the last patch; the caller writes its `old_bytes`
     pub fn undo(&mut self) -> Option<MemoryPatch> {
-        if let Some(patch) = self.undo_stack.pop_back() {
-            let inverse = patch.inverse();
-            self.redo_stack.push_back(patch);
-            Some(inverse)
-        } else {
-            None
-        }
+        let patch = self.undo_stack.pop_back()?;
+        self.redo_stack.push_back(patch.clone());
+        Some(patch)
     }
 
-    /// Get the next redo action
+    /// Reapply the last undone patch; the caller writes its `new_bytes`
     pub fn redo(&mut self) -> Option<MemoryPatch> {
-        if let Some(patch) = self.redo_stack.pop_back() {
-            let forward = patch.clone();
-            self.undo_stack.push_back(patch);
-            Some(forward)
-        } else {
-            None
-        }
+        let patch = self.redo_stack.pop_back()?;
+        self.undo_stack.push_back(patch.clone());
+        Some(patch)
+    }
+
+    /// The patch undo would take back
+    pub fn last(&self) -> Option<&MemoryPatch> {
+        self.undo_stack.back()
     }
 
     /// Check if undo is available
@@ -102,6 +111,31 @@ impl History {
     pub fn clear(&mut self) {
         self.undo_stack.clear();
         self.redo_stack.clear();
+        self.settled.clear();
+    }
+
+    /// What the applied patches add up to: one patch per run of bytes
+    /// that now differ from before the first patch touched them
+    pub fn net_changes(&self) -> Vec<MemoryPatch> {
+        let mut bytes: BTreeMap<u32, (u8, u8)> = BTreeMap::new();
+        for patch in self.settled.iter().chain(&self.undo_stack) {
+            for (i, (&old, &new)) in patch.old_bytes.iter().zip(&patch.new_bytes).enumerate() {
+                let address = patch.address.wrapping_add(i as u32);
+                bytes.entry(address).or_insert((old, new)).1 = new;
+            }
+        }
+
+        let mut changes: Vec<MemoryPatch> = Vec::new();
+        for (address, (old, new)) in bytes.into_iter().filter(|(_, (old, new))| old != new) {
+            match changes.last_mut() {
+                Some(last) if last.address.wrapping_add(last.new_bytes.len() as u32) == address => {
+                    last.old_bytes.push(old);
+                    last.new_bytes.push(new);
+                }
+                _ => changes.push(MemoryPatch::new(address, vec![old], vec![new])),
+            }
+        }
+        changes
     }
 }
 
@@ -123,7 +157,7 @@ mod tests {
         // Undo
         let undo = history.undo().unwrap();
         assert_eq!(undo.address, 0x1000);
-        assert_eq!(undo.new_bytes, vec![0x90]); // Restore old value
+        assert_eq!(undo.old_bytes, vec![0x90]); // Restore old value
 
         assert!(!history.can_undo());
         assert!(history.can_redo());
@@ -165,5 +199,29 @@ mod tests {
 
         // Should only keep last 3
         assert_eq!(history.undo_count(), 3);
+
+        // The oldest can't be undone but still counts as applied
+        assert_eq!(history.net_changes().len(), 1);
+        assert_eq!(history.net_changes()[0].new_bytes, vec![0xFF; 4]);
+        history.clear();
+        assert!(history.net_changes().is_empty());
+    }
+
+    #[test]
+    fn test_net_changes() {
+        let mut history = History::new(10);
+        history.record(MemoryPatch::new(0x1000, vec![0x75, 0x07], vec![0x90, 0x90]));
+        history.record(MemoryPatch::new(0x1001, vec![0x90, 0xB8], vec![0x07, 0xB9]));
+        history.record(MemoryPatch::new(0x1010, vec![0x00], vec![0xFF]));
+        history.undo();
+
+        // 0x1001 is back to its original byte, splitting the run
+        assert_eq!(
+            history.net_changes(),
+            [
+                MemoryPatch::new(0x1000, vec![0x75], vec![0x90]),
+                MemoryPatch::new(0x1002, vec![0xB8], vec![0xB9]),
+            ]
+        );
     }
 }
@@ -0,0 +1,213 @@
+use iced_x86::{Instruction, Mnemonic, OpKind, Register};
+
+use crate::emulator::DisassemblyLine;
+
+/// A common instruction pattern compilers and hand-written code use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idiom {
+    /// XOR or SUB of a register with itself
+    ZeroRegister,
+    /// TEST of a register with itself, then JE or JNE
+    NullCheck,
+    /// SHL by a constant
+    ShiftMultiply,
+    /// SHR or SAR by a constant
+    ShiftDivide,
+    /// LEA used for arithmetic rather than to reach memory
+    LeaArithmetic,
+}
+
+impl Idiom {
+    pub const ALL: [Idiom; 5] = [
+        Idiom::ZeroRegister,
+        Idiom::NullCheck,
+        Idiom::ShiftMultiply,
+        Idiom::ShiftDivide,
+        Idiom::LeaArithmetic,
+    ];
+
+    /// Short name used by the `learn` command and in settings
+    pub fn name(&self) -> &'static str {
+        match self {
+            Idiom::ZeroRegister => "zeroing",
+            Idiom::NullCheck => "null-check",
+            Idiom::ShiftMultiply => "shift-multiply",
+            Idiom::ShiftDivide => "shift-divide",
+            Idiom::LeaArithmetic => "lea-math",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|idiom| idiom.name() == name)
+    }
+}
+
+/// An idiom found at an address, with an explanation of this occurrence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdiomMatch {
+    /// Address of the first instruction of the pattern
+    pub address: u32,
+    pub idiom: Idiom,
+    pub note: String,
+}
+
+/// Every idiom in `lines`, in address order
+pub fn recognize_idioms(lines: &[DisassemblyLine]) -> Vec<IdiomMatch> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.is_code())
+        .filter_map(|(i, line)| {
+            let (idiom, note) = match_idiom(
+                &line.instruction,
+                lines.get(i + 1).map(|next| &next.instruction),
+            )?;
+            Some(IdiomMatch {
+                address: line.address,
+                idiom,
+                note,
+            })
+        })
+        .collect()
+}
+
+/// The first occurrence of each idiom not yet learned
+pub fn first_idioms(lines: &[DisassemblyLine], learned: &[String]) -> Vec<IdiomMatch> {
+    let mut shown: Vec<Idiom> = Vec::new();
+    recognize_idioms(lines)
+        .into_iter()
+        .filter(|found| {
+            if shown.contains(&found.idiom) || learned.iter().any(|name| name == found.idiom.name())
+            {
+                return false;
+            }
+            shown.push(found.idiom);
+            true
+        })
+        .collect()
+}
+
+fn match_idiom(instr: &Instruction, next: Option<&Instruction>) -> Option<(Idiom, String)> {
+    let same_register = instr.op_count() == 2
+        && instr.op0_kind() == OpKind::Register
+        && instr.op1_kind() == OpKind::Register
+        && instr.op0_register() == instr.op1_register();
+    let reg = || register(instr.op0_register());
+    let shift = || match instr.op1_kind() {
+        OpKind::Immediate8 | OpKind::Immediate8_2nd => Some(instr.immediate(1) as u32 & 31),
+        _ => None,
+    };
+
+    match instr.mnemonic() {
+        Mnemonic::Xor | Mnemonic::Sub if same_register => Some((
+            Idiom::ZeroRegister,
+            format!(
+                "{} = 0: a register combined with itself cancels out (shorter than MOV {}, 0)",
+                reg(),
+                reg()
+            ),
+        )),
+        Mnemonic::Test if same_register => {
+            let outcome = match next?.mnemonic() {
+                Mnemonic::Je => "jumps if",
+                Mnemonic::Jne => "jumps unless",
+                _ => return None,
+            };
+            Some((
+                Idiom::NullCheck,
+                format!(
+                    "zero/null check: the jump {} {} is 0 (TEST sets ZF without changing it)",
+                    outcome,
+                    reg()
+                ),
+            ))
+        }
+        Mnemonic::Shl | Mnemonic::Sal if instr.op0_kind() == OpKind::Register => {
+            let n = shift().filter(|&n| n > 0)?;
+            Some((
+                Idiom::ShiftMultiply,
+                format!(
+                    "{} *= {}: shifting left by {} multiplies by 2^{}",
+                    reg(),
+                    1u64 << n,
+                    n,
+                    n
+                ),
+            ))
+        }
+        Mnemonic::Shr | Mnemonic::Sar if instr.op0_kind() == OpKind::Register => {
+            let n = shift().filter(|&n| n > 0)?;
+            let kind = if instr.mnemonic() == Mnemonic::Sar {
+                "signed, rounding down"
+            } else {
+                "unsigned"
+            };
+            Some((
+                Idiom::ShiftDivide,
+                format!(
+                    "{} /= {} ({}): shifting right by {} divides by 2^{}",
+                    reg(),
+                    1u64 << n,
+                    kind,
+                    n,
+                    n
+                ),
+            ))
+        }
+        Mnemonic::Lea if instr.memory_index() != Register::None => Some((
+            Idiom::LeaArithmetic,
+            format!(
+                "LEA only computes the address, so this is arithmetic: no memory is read into {}",
+                reg()
+            ),
+        )),
+        _ => None,
+    }
+}
+
+fn register(reg: Register) -> String {
+    format!("{:?}", reg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::Disassembler;
+
+    #[test]
+    fn test_recognize() {
+        // XOR EAX, EAX; TEST EBX, EBX; JE +0; SHL ECX, 3; SAR EDX, 1; LEA ESI, [EAX+ECX*4]; XOR EAX, EBX
+        let lines = Disassembler::new().disassemble(
+            &[
+                0x31, 0xC0, 0x85, 0xDB, 0x74, 0x00, 0xC1, 0xE1, 0x03, 0xD1, 0xFA, 0x8D, 0x34, 0x88,
+                0x31, 0xD8,
+            ],
+            0x1000,
+            16,
+        );
+        let found = recognize_idioms(&lines);
+        let idioms: Vec<_> = found.iter().map(|found| found.idiom).collect();
+        assert_eq!(idioms, Idiom::ALL);
+
+        assert_eq!(found[1].address, 0x1002);
+        assert!(found[1].note.contains("jumps if EBX is 0"));
+        assert!(found[2].note.starts_with("ECX *= 8"));
+        assert!(found[3].note.contains("signed"));
+    }
+
+    #[test]
+    fn test_first_idioms_skip_learned() {
+        // XOR EAX, EAX; XOR ECX, ECX; SHL EAX, 2
+        let lines =
+            Disassembler::new().disassemble(&[0x31, 0xC0, 0x31, 0xC9, 0xC1, 0xE0, 0x02], 0x1000, 8);
+
+        let shown = first_idioms(&lines, &[]);
+        assert_eq!(shown.len(), 2);
+        assert_eq!(shown[0].address, 0x1000);
+
+        let shown = first_idioms(&lines, &["zeroing".to_string()]);
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].idiom, Idiom::ShiftMultiply);
+        assert_eq!(Idiom::from_name("lea-math"), Some(Idiom::LeaArithmetic));
+    }
+}
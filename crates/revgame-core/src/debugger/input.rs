@@ -0,0 +1,153 @@
+use crate::emulator::{CpuState, Memory, MemoryError, Register};
+
+/// Where a program's input is copied before each run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputPlacement {
+    /// Buffer the input is copied into, NUL-terminated
+    pub address: u32,
+    /// Buffer size; longer input is cut short
+    pub max_length: u32,
+    /// Register loaded with the input length
+    pub length_register: Option<Register>,
+}
+
+/// The input a program runs with: its "arguments", placed in memory, and
+/// its stdin, consumed by `read` interrupts
+#[derive(Debug, Clone, Default)]
+pub struct ProgramInput {
+    data: Vec<u8>,
+    /// Bytes already consumed by `read`
+    position: usize,
+    placement: Option<InputPlacement>,
+}
+
+impl ProgramInput {
+    /// The whole input
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Replace the input and rewind stdin
+    pub fn set_data(&mut self, data: &[u8]) {
+        self.data = data.to_vec();
+        self.position = 0;
+    }
+
+    pub fn placement(&self) -> Option<&InputPlacement> {
+        self.placement.as_ref()
+    }
+
+    pub fn set_placement(&mut self, placement: InputPlacement) {
+        self.placement = Some(placement);
+    }
+
+    /// Rewind stdin and copy the input into memory, ready for a fresh run
+    pub fn apply(&mut self, cpu: &mut CpuState, memory: &mut Memory) -> Result<(), MemoryError> {
+        self.position = 0;
+        let Some(ref placement) = self.placement else {
+            return Ok(());
+        };
+
+        let length = self.data.len().min(placement.max_length as usize);
+        let mut buffer = vec![0; placement.max_length as usize + 1];
+        buffer[..length].copy_from_slice(&self.data[..length]);
+        memory.load(placement.address, &buffer)?;
+
+        if let Some(register) = placement.length_register {
+            cpu.set_register(register, length as u32);
+        }
+        Ok(())
+    }
+
+    /// Parse input typed by the player. `\n`, `\t`, `\0`, `\\` and `\xNN`
+    /// escapes stand for bytes that can't be typed directly.
+    pub fn unescape(text: &str) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                let mut buffer = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                continue;
+            }
+            match chars.next() {
+                Some('n') => bytes.push(b'\n'),
+                Some('t') => bytes.push(b'\t'),
+                Some('0') => bytes.push(0),
+                Some('\\') => bytes.push(b'\\'),
+                Some('x') => {
+                    let hex: String = chars.by_ref().take(2).collect();
+                    let byte = u8::from_str_radix(&hex, 16)
+                        .map_err(|_| format!("Invalid escape: \\x{}", hex))?;
+                    bytes.push(byte);
+                }
+                Some(other) => return Err(format!("Invalid escape: \\{}", other)),
+                None => return Err("Input ends with a lone backslash".to_string()),
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Show input the way the player would type it
+    pub fn escape(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .map(|&b| match b {
+                b'\n' => "\\n".to_string(),
+                b'\t' => "\\t".to_string(),
+                0 => "\\0".to_string(),
+                b'\\' => "\\\\".to_string(),
+                0x20..=0x7E => (b as char).to_string(),
+                _ => format!("\\x{:02X}", b),
+            })
+            .collect()
+    }
+
+    /// Consume up to `count` bytes of stdin
+    pub fn read(&mut self, count: usize) -> &[u8] {
+        let start = self.position;
+        self.position = (start + count).min(self.data.len());
+        &self.data[start..self.position]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placement_and_read() {
+        let mut cpu = CpuState::default();
+        let mut memory = Memory::new(0x1000);
+        let mut input = ProgramInput::default();
+        input.set_placement(InputPlacement {
+            address: 0x100,
+            max_length: 4,
+            length_register: Some(Register::Ecx),
+        });
+
+        input.set_data(b"SECRET");
+        input.apply(&mut cpu, &mut memory).unwrap();
+        assert_eq!(memory.slice(0x100, 5).unwrap(), b"SECR\0");
+        assert_eq!(cpu.regs.ecx, 4);
+
+        assert_eq!(input.read(4), b"SECR");
+        assert_eq!(input.read(4), b"ET");
+        assert_eq!(input.read(4), b"");
+
+        // A fresh run starts reading from the beginning again
+        input.apply(&mut cpu, &mut memory).unwrap();
+        assert_eq!(input.read(2), b"SE");
+    }
+
+    #[test]
+    fn test_escapes() {
+        let bytes = ProgramInput::unescape(r"AB\n\x7F\0\\").unwrap();
+        assert_eq!(bytes, b"AB\n\x7F\0\\");
+        assert_eq!(ProgramInput::escape(&bytes), r"AB\n\x7F\0\\");
+
+        assert!(ProgramInput::unescape(r"\q").is_err());
+        assert!(ProgramInput::unescape(r"\xZZ").is_err());
+        assert!(ProgramInput::unescape("end\\").is_err());
+    }
+}
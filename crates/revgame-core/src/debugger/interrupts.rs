@@ -0,0 +1,80 @@
+use crate::emulator::{CpuState, Memory, MemoryError, Prng, Register};
+
+use super::ProgramInput;
+
+/// What happens when the program executes `INT n` for a given vector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterruptHandler {
+    /// Transfer control to a handler routine. Like a flat-mode interrupt
+    /// gate, EFLAGS and then the return address are pushed; the routine
+    /// returns with IRET.
+    Jump(u32),
+    /// Set a register to a fixed value and continue
+    SetRegister(Register, u32),
+    /// Set a register to the number of instructions executed since the
+    /// program started (a deterministic stand-in for the system time that
+    /// restarts and replays read the same)
+    Ticks(Register),
+    /// Set a register to the next number from the session's seeded
+    /// generator, so runs and replays see the same sequence
+    Random(Register),
+    /// Read stdin like a read syscall: up to EDX bytes into the buffer at
+    /// ECX, with the number of bytes read returned in EAX
+    Read,
+    /// Stop the program, like an exit syscall
+    Halt,
+}
+
+/// Where execution continues after an interrupt was handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptOutcome {
+    /// Continue at the given address
+    Continue(u32),
+    /// The program halted
+    Halt,
+}
+
+impl InterruptHandler {
+    /// Apply the handler for an `INT` whose next instruction is at `next_eip`
+    pub fn apply(
+        &self,
+        cpu: &mut CpuState,
+        memory: &mut Memory,
+        next_eip: u32,
+        ticks: u64,
+        input: &mut ProgramInput,
+        rng: &mut Prng,
+    ) -> Result<InterruptOutcome, MemoryError> {
+        match self {
+            InterruptHandler::Jump(target) => {
+                cpu.regs.esp = cpu.regs.esp.wrapping_sub(4);
+                memory.write_u32(cpu.regs.esp, cpu.eflags.as_u32())?;
+                cpu.regs.esp = cpu.regs.esp.wrapping_sub(4);
+                memory.write_u32(cpu.regs.esp, next_eip)?;
+                Ok(InterruptOutcome::Continue(*target))
+            }
+            InterruptHandler::SetRegister(register, value) => {
+                cpu.set_register(*register, *value);
+                Ok(InterruptOutcome::Continue(next_eip))
+            }
+            InterruptHandler::Ticks(register) => {
+                cpu.set_register(*register, ticks as u32);
+                Ok(InterruptOutcome::Continue(next_eip))
+            }
+            InterruptHandler::Random(register) => {
+                cpu.set_register(*register, rng.next_u32());
+                Ok(InterruptOutcome::Continue(next_eip))
+            }
+            InterruptHandler::Read => {
+                let bytes = input.read(cpu.regs.edx as usize);
+                memory.write_bytes(cpu.regs.ecx, bytes)?;
+                cpu.regs.eax = bytes.len() as u32;
+                Ok(InterruptOutcome::Continue(next_eip))
+            }
+            InterruptHandler::Halt => {
+                cpu.halted = true;
+                Ok(InterruptOutcome::Halt)
+            }
+        }
+    }
+}
@@ -1,12 +1,55 @@
+mod bookmarks;
+mod constants;
+mod entropy;
 mod execution;
+mod explain;
+mod frames;
 mod history;
+mod idioms;
+mod input;
+mod interrupts;
+mod patch_check;
+mod pins;
+mod pointers;
+mod predict;
+mod pseudo;
 mod search;
-mod bookmarks;
+mod state_diff;
+mod timeline;
+mod trace;
+mod trace_diff;
+mod transform;
+mod watch;
 
-pub use execution::{Debugger, DebuggerState, StepResult, RunResult};
-pub use history::{History, MemoryPatch};
-pub use search::{MemorySearch, SearchResult};
-pub use bookmarks::{Bookmark, BookmarkManager};
+pub use bookmarks::{Bookmark, BookmarkCategory, BookmarkManager};
+pub use constants::{
+    constants_in_code, constants_in_memory, lookup_constant, ConstantMatch, ConstantSource,
+    MagicConstant, MAGIC_CONSTANTS,
+};
+pub use entropy::{describe_entropy, window_entropy, ByteStats};
+pub use execution::{
+    CapturePolicy, Debugger, DebuggerState, RegisterSample, RunResult, StepResult,
+};
+pub use explain::explain_run;
+pub use frames::{Frame, FrameTracker, SlotRole, CDECL_ARGS};
+pub use history::{History, MemoryPatch, ModifiedLine};
+pub use idioms::{first_idioms, recognize_idioms, Idiom, IdiomMatch};
+pub use input::{InputPlacement, ProgramInput};
+pub use interrupts::{InterruptHandler, InterruptOutcome};
+pub use patch_check::{pad_with_nops, PatchFit, NOP};
+pub use pins::{Pin, PinList};
+pub use pointers::{chase_pointer, is_address, ChainEnd, PointerChain, MAX_CHAIN_DEPTH};
+pub use predict::{describe_changes, PredictMode};
+pub use pseudo::{basic_block, pseudo_c, PseudoLine};
+pub use search::{
+    MemorySearch, SearchQuery, SearchResult, SearchScan, ValueHit, ValueLocation, SCAN_CHUNK,
+};
+pub use state_diff::{ByteChange, StateCapture, StateDiff};
+pub use timeline::{Timeline, TimelineMark, TimelineMarker};
+pub use trace::{RunTrace, TraceStep};
+pub use trace_diff::TraceDiff;
+pub use transform::Transform;
+pub use watch::{FlagHit, Watch, WatchHit};
 
 use thiserror::Error;
 
@@ -35,4 +78,7 @@ pub enum DebuggerError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    #[error("{}", .0.warning().unwrap_or_default())]
+    PatchMisfit(PatchFit),
 }
@@ -0,0 +1,202 @@
+use super::Debugger;
+
+/// NOP opcode used for padding
+pub const NOP: u8 = 0x90;
+
+/// How a byte patch lines up with the instructions it overwrites
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchFit {
+    /// Outside code, or the patched code still lines up with the
+    /// instructions that follow it
+    Clean,
+    /// The new instructions stop short of the end of the one at `start`,
+    /// leaving `leftover` of its bytes to run as something else
+    EndsMidInstruction {
+        start: u32,
+        text: String,
+        leftover: usize,
+    },
+    /// The last new instruction is longer than the patch and swallows the
+    /// start of the instruction at `start`
+    Overruns { start: u32, text: String },
+    /// The patched bytes do not decode as instructions
+    Undecodable,
+}
+
+impl PatchFit {
+    /// Guidance for the player, or None when the patch is clean
+    pub fn warning(&self) -> Option<String> {
+        match self {
+            PatchFit::Clean => None,
+            PatchFit::EndsMidInstruction { start, text, leftover } => Some(format!(
+                "Patch leaves {} stray byte{} of `{}` at 0x{:08X}, which would run as a different instruction",
+                leftover,
+                if *leftover == 1 { "" } else { "s" },
+                text,
+                start
+            )),
+            PatchFit::Overruns { start, text } => Some(format!(
+                "The last patched instruction is longer than the patch and swallows part of `{}` at 0x{:08X}",
+                text, start
+            )),
+            PatchFit::Undecodable => {
+                Some("The patched bytes do not decode as valid instructions".to_string())
+            }
+        }
+    }
+
+    /// NOPs needed after the patch to finish the last overwritten instruction
+    pub fn padding(&self) -> usize {
+        match self {
+            PatchFit::EndsMidInstruction { leftover, .. } => *leftover,
+            _ => 0,
+        }
+    }
+}
+
+/// `bytes` followed by `count` NOPs
+pub fn pad_with_nops(bytes: &[u8], count: usize) -> Vec<u8> {
+    let mut padded = bytes.to_vec();
+    padded.resize(bytes.len() + count, NOP);
+    padded
+}
+
+impl Debugger {
+    /// Check whether writing `bytes` at `address` keeps the instructions
+    /// after the patch decoding the way they did before.
+    ///
+    /// Opcode swaps (JE -> JMP) and operand edits are clean; replacing a
+    /// 2-byte JNE with a single NOP is not.
+    pub fn check_patch(&mut self, address: u32, bytes: &[u8]) -> PatchFit {
+        // Only executable regions have instruction boundaries to respect
+        let Some((start, region_end)) = self
+            .memory
+            .get_region(address)
+            .filter(|region| region.permissions.execute)
+            .map(|region| (region.start, region.end))
+        else {
+            return PatchFit::Clean;
+        };
+
+        let end = address.saturating_add(bytes.len() as u32);
+        let span = (end.saturating_sub(start) as usize + 15)
+            .min(region_end.saturating_sub(start) as usize);
+        let Some(code) = self.memory.slice(start, span) else {
+            return PatchFit::Clean;
+        };
+
+        // Original instructions, walking linearly from the start of the region
+        let original = self.disassemble_bytes(&code, start, code.len());
+        let Some(first) = original.iter().position(|line| {
+            line.address <= address && address < line.address + line.length as u32
+        }) else {
+            return PatchFit::Clean;
+        };
+
+        // Decode the patched code from the first overwritten instruction
+        let from = original[first].address;
+        let mut patched = code[(from - start) as usize..].to_vec();
+        let offset = (address - from) as usize;
+        let overlap = bytes.len().min(patched.len().saturating_sub(offset));
+        patched[offset..offset + overlap].copy_from_slice(&bytes[..overlap]);
+
+        let mut boundary = from;
+        for line in self.disassemble_bytes(&patched, from, patched.len()) {
+            if boundary >= end || !line.is_code() {
+                break;
+            }
+            boundary = line.address + line.length as u32;
+        }
+        if boundary < end {
+            return PatchFit::Undecodable;
+        }
+
+        // Back in step with the original code?
+        let Some(line) = original[first..]
+            .iter()
+            .find(|line| line.address < boundary && boundary < line.address + line.length as u32)
+        else {
+            return PatchFit::Clean;
+        };
+
+        if boundary == end {
+            PatchFit::EndsMidInstruction {
+                start: line.address,
+                text: line.text.clone(),
+                leftover: (line.address + line.length as u32 - boundary) as usize,
+            }
+        } else {
+            PatchFit::Overruns {
+                start: line.address,
+                text: line.text.clone(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::Memory;
+
+    fn debugger() -> Debugger {
+        let mut dbg = Debugger::new(0x4000);
+        dbg.memory = Memory::with_puzzle_layout(0x1000, 0x2000, 0x3000);
+        // CMP EAX, 0x1337; JNE +7; MOV EAX, 1; HLT
+        dbg.memory
+            .load(
+                0x1000,
+                &[
+                    0x3D, 0x37, 0x13, 0x00, 0x00, 0x75, 0x07, 0xB8, 0x01, 0x00, 0x00, 0x00, 0xF4,
+                ],
+            )
+            .unwrap();
+        dbg
+    }
+
+    #[test]
+    fn test_clean_patches() {
+        let mut dbg = debugger();
+        assert_eq!(dbg.check_patch(0x1005, &[0x90, 0x90]), PatchFit::Clean);
+        assert_eq!(dbg.check_patch(0x1000, &[0x90; 7]), PatchFit::Clean);
+        // Opcode swap and operand edits keep the original lengths
+        assert_eq!(dbg.check_patch(0x1005, &[0xEB]), PatchFit::Clean);
+        assert_eq!(dbg.check_patch(0x1001, &[0x10]), PatchFit::Clean);
+        // Data is free-form
+        assert_eq!(dbg.check_patch(0x2001, &[0x90]), PatchFit::Clean);
+    }
+
+    #[test]
+    fn test_short_patch_leaves_stray_bytes() {
+        let mut dbg = debugger();
+        let fit = dbg.check_patch(0x1005, &[0x90]);
+        assert_eq!(
+            fit,
+            PatchFit::EndsMidInstruction {
+                start: 0x1005,
+                text: "jne short 0x0000100E".to_string(),
+                leftover: 1,
+            }
+        );
+        assert_eq!(pad_with_nops(&[0x90], fit.padding()), vec![0x90, 0x90]);
+
+        // A longer encoding spills into the next instruction
+        let fit = dbg.check_patch(0x1005, &[0xB8, 0x01, 0x00, 0x00, 0x00]);
+        assert_eq!(fit.padding(), 2);
+        assert_eq!(
+            dbg.check_patch(0x1005, &[0xB8, 0x01, 0x00, 0x00, 0x00, 0x90, 0x90]),
+            PatchFit::Clean
+        );
+    }
+
+    #[test]
+    fn test_overrunning_patch() {
+        let mut dbg = debugger();
+        // MOV EAX, imm32 takes its operand from the bytes after the patch
+        assert!(matches!(
+            dbg.check_patch(0x1005, &[0xB8]),
+            PatchFit::Overruns { start: 0x1007, .. }
+        ));
+        assert_eq!(dbg.check_patch(0x1005, &[0xB8]).padding(), 0);
+    }
+}
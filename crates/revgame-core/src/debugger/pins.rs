@@ -0,0 +1,106 @@
+use crate::puzzle::{Condition, ScriptContext};
+
+/// An expression pinned to the watch list, with its latest value
+#[derive(Debug, Clone)]
+pub struct Pin {
+    /// Expression as the player typed it
+    pub text: String,
+    condition: Condition,
+    /// Value at the last update, or why it couldn't be evaluated
+    pub value: Result<u32, String>,
+    /// Whether the last update changed the value
+    pub changed: bool,
+}
+
+/// Expressions (registers, memory reads, bookmark labels) whose values are
+/// shown after every step. Unlike watches, they never stop execution.
+#[derive(Debug, Clone, Default)]
+pub struct PinList {
+    pins: Vec<Pin>,
+}
+
+impl PinList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pin an expression, evaluated right away
+    pub fn add(&mut self, text: &str, ctx: &ScriptContext) -> Result<(), String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Enter an expression to pin".to_string());
+        }
+        let condition = Condition::parse(text)?;
+        let value = condition.value(ctx).map(|v| v as u32);
+        self.pins.push(Pin {
+            text: text.to_string(),
+            condition,
+            value,
+            changed: false,
+        });
+        Ok(())
+    }
+
+    /// Unpin the expression at `index` (0-based)
+    pub fn remove(&mut self, index: usize) -> Option<Pin> {
+        (index < self.pins.len()).then(|| self.pins.remove(index))
+    }
+
+    pub fn clear(&mut self) {
+        self.pins.clear();
+    }
+
+    pub fn list(&self) -> &[Pin] {
+        &self.pins
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pins.is_empty()
+    }
+
+    /// Re-evaluate every pin, marking the ones whose value changed
+    pub fn update(&mut self, ctx: &ScriptContext) {
+        for pin in &mut self.pins {
+            let value = pin.condition.value(ctx).map(|v| v as u32);
+            pin.changed = value != pin.value;
+            pin.value = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::Debugger;
+
+    #[test]
+    fn test_pins_track_changes() {
+        let mut dbg = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        // INC EAX; HLT
+        dbg.load_code(0x1000, &[0x40, 0xF4]).unwrap();
+        dbg.memory.write_u32(0x2000, 0x1337).unwrap();
+        dbg.bookmarks.add(0x2000, "key");
+
+        let mut pins = PinList::new();
+        pins.add("eax", &ScriptContext::from_debugger(&dbg))
+            .unwrap();
+        pins.add("dword[key] ^ 0xFF", &ScriptContext::from_debugger(&dbg))
+            .unwrap();
+        pins.add("nothing", &ScriptContext::from_debugger(&dbg))
+            .unwrap();
+        assert!(pins
+            .add("eax +", &ScriptContext::from_debugger(&dbg))
+            .is_err());
+        assert_eq!(pins.list()[1].value, Ok(0x13C8));
+        assert!(pins.list()[2].value.is_err());
+
+        dbg.step().unwrap();
+        pins.update(&ScriptContext::from_debugger(&dbg));
+        assert_eq!(pins.list()[0].value, Ok(1));
+        assert!(pins.list()[0].changed);
+        assert!(!pins.list()[1].changed);
+
+        assert_eq!(pins.remove(2).unwrap().text, "nothing");
+        assert!(pins.remove(2).is_none());
+    }
+}
@@ -0,0 +1,142 @@
+use crate::emulator::Memory;
+
+/// Pointers followed before giving up on a chain
+pub const MAX_CHAIN_DEPTH: usize = 8;
+
+/// Printable characters needed before the target counts as a string
+const MIN_STRING_LEN: usize = 3;
+
+/// Longest string shown at the end of a chain
+const MAX_STRING_LEN: usize = 48;
+
+/// What a pointer chain ends on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainEnd {
+    /// A printable string at the last address
+    String(String),
+    /// The dword at the last address, which isn't itself an address
+    Value(u32),
+    /// The last address holds a pointer back into the chain
+    Cycle(u32),
+    /// Still pointing somewhere after `MAX_CHAIN_DEPTH` hops
+    TooDeep,
+}
+
+/// Addresses reached by dereferencing a value over and over, like
+/// `EAX → [0x2000] → "SECRET"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointerChain {
+    /// Every address dereferenced, starting with the original value
+    pub addresses: Vec<u32>,
+    pub end: ChainEnd,
+}
+
+impl PointerChain {
+    /// Breadcrumbs after `origin`: one `[address]` per hop, then the end
+    pub fn breadcrumbs(&self, origin: &str) -> Vec<String> {
+        let mut crumbs = vec![origin.to_string()];
+        crumbs.extend(
+            self.addresses
+                .iter()
+                .map(|address| format!("[0x{:08X}]", address)),
+        );
+        crumbs.push(match &self.end {
+            ChainEnd::String(text) => format!("{:?}", text),
+            ChainEnd::Value(value) => format!("0x{:08X}", value),
+            ChainEnd::Cycle(address) => format!("back to 0x{:08X}", address),
+            ChainEnd::TooDeep => "...".to_string(),
+        });
+        crumbs
+    }
+}
+
+/// Whether `value` points into mapped memory. Null never does.
+pub fn is_address(memory: &Memory, value: u32) -> bool {
+    if value == 0 || value as u64 >= memory.size() {
+        return false;
+    }
+    let regions = memory.regions();
+    regions.is_empty() || regions.iter().any(|r| (r.start..r.end).contains(&value))
+}
+
+/// Printable ASCII at `address`, if there's enough of it to be a string
+fn string_at(memory: &Memory, address: u32) -> Option<String> {
+    let available = (memory.size() - address as u64).min(MAX_STRING_LEN as u64) as usize;
+    let bytes = memory.slice(address, available)?;
+    let text: String = bytes
+        .iter()
+        .take_while(|b| b.is_ascii_graphic() || **b == b' ')
+        .map(|&b| b as char)
+        .collect();
+    (text.len() >= MIN_STRING_LEN).then_some(text)
+}
+
+/// Follow `value` as a pointer until it reaches a string, a plain value or
+/// a cycle. `None` when `value` isn't an address to begin with.
+pub fn chase_pointer(memory: &Memory, value: u32) -> Option<PointerChain> {
+    if !is_address(memory, value) {
+        return None;
+    }
+
+    let mut addresses = Vec::new();
+    let mut current = value;
+    let end = loop {
+        if addresses.contains(&current) {
+            break ChainEnd::Cycle(current);
+        }
+        if addresses.len() == MAX_CHAIN_DEPTH {
+            break ChainEnd::TooDeep;
+        }
+        addresses.push(current);
+        if let Some(text) = string_at(memory, current) {
+            break ChainEnd::String(text);
+        }
+        let Some(bytes) = memory.slice(current, 4) else {
+            break ChainEnd::Value(0);
+        };
+        let next = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        if !is_address(memory, next) {
+            break ChainEnd::Value(next);
+        }
+        current = next;
+    };
+
+    Some(PointerChain { addresses, end })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::Debugger;
+
+    #[test]
+    fn test_chase_pointer_to_string_value_and_cycle() {
+        let mut dbg = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        dbg.memory.write_bytes(0x2000, b"SECRET\0").unwrap();
+        dbg.memory.write_u32(0x2100, 0x2000).unwrap();
+        dbg.memory.write_u32(0x2104, 0x2100).unwrap();
+        dbg.memory.write_u32(0x2200, 0xC0FFEE).unwrap();
+        dbg.memory.write_u32(0x2300, 0x2304).unwrap();
+        dbg.memory.write_u32(0x2304, 0x2300).unwrap();
+
+        let chain = chase_pointer(&dbg.memory, 0x2104).unwrap();
+        assert_eq!(chain.addresses, vec![0x2104, 0x2100, 0x2000]);
+        assert_eq!(chain.end, ChainEnd::String("SECRET".to_string()));
+        assert_eq!(
+            chain.breadcrumbs("EAX").join(" → "),
+            "EAX → [0x00002104] → [0x00002100] → [0x00002000] → \"SECRET\""
+        );
+
+        let chain = chase_pointer(&dbg.memory, 0x2200).unwrap();
+        assert_eq!(chain.end, ChainEnd::Value(0xC0FFEE));
+
+        let chain = chase_pointer(&dbg.memory, 0x2300).unwrap();
+        assert_eq!(chain.addresses, vec![0x2300, 0x2304]);
+        assert_eq!(chain.end, ChainEnd::Cycle(0x2300));
+
+        // Null and unmapped values aren't pointers
+        assert!(chase_pointer(&dbg.memory, 0).is_none());
+        assert!(chase_pointer(&dbg.memory, 0x7).is_none());
+        assert!(chase_pointer(&dbg.memory, 0xFFFF_FFF0).is_none());
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeSet;
+
+use crate::emulator::{CpuState, Register};
+
+/// Flags a prediction can name
+const FLAGS: [&str; 4] = ["ZF", "SF", "CF", "OF"];
+
+/// Registers a prediction can name; EIP always changes, so it's left out
+const REGISTERS: [Register; 8] = [
+    Register::Eax,
+    Register::Ebx,
+    Register::Ecx,
+    Register::Edx,
+    Register::Esi,
+    Register::Edi,
+    Register::Ebp,
+    Register::Esp,
+];
+
+/// What the player is asked to predict before each step
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PredictMode {
+    #[default]
+    Off,
+    /// Which flags will change
+    Flags,
+    /// Which flags and registers will change
+    Full,
+}
+
+impl PredictMode {
+    pub const ALL: [PredictMode; 3] = [PredictMode::Off, PredictMode::Flags, PredictMode::Full];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PredictMode::Off => "off",
+            PredictMode::Flags => "flags",
+            PredictMode::Full => "full",
+        }
+    }
+
+    /// Unknown names (including the empty default) mean off
+    pub fn from_name(name: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name() == name)
+            .unwrap_or_default()
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            PredictMode::Off => PredictMode::Flags,
+            PredictMode::Flags => PredictMode::Full,
+            PredictMode::Full => PredictMode::Off,
+        }
+    }
+
+    /// The question asked before a step
+    pub fn prompt(&self) -> &'static str {
+        match self {
+            PredictMode::Full => "Which flags (ZF SF CF OF) and registers will change?",
+            _ => "Which flags (ZF SF CF OF) will change?",
+        }
+    }
+
+    /// Flags and registers that differ between two CPU states, limited to
+    /// what this mode asks about
+    pub fn changes(&self, before: &CpuState, after: &CpuState) -> BTreeSet<String> {
+        let mut changes = BTreeSet::new();
+        if *self == PredictMode::Off {
+            return changes;
+        }
+
+        let flags = |cpu: &CpuState| [cpu.eflags.zf, cpu.eflags.sf, cpu.eflags.cf, cpu.eflags.of];
+        for ((name, old), new) in FLAGS.iter().zip(flags(before)).zip(flags(after)) {
+            if old != new {
+                changes.insert(name.to_string());
+            }
+        }
+
+        if *self == PredictMode::Full {
+            for register in REGISTERS {
+                if before.get_register(register) != after.get_register(register) {
+                    changes.insert(register.name().to_string());
+                }
+            }
+        }
+        changes
+    }
+
+    /// Read a guess such as "ZF CF EAX" or "none"
+    pub fn parse(&self, text: &str) -> Result<BTreeSet<String>, String> {
+        let mut guess = BTreeSet::new();
+        for token in text
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|t| !t.is_empty())
+        {
+            let token = token.to_uppercase();
+            if token == "NONE" {
+                continue;
+            }
+            let known = FLAGS.contains(&token.as_str())
+                || (*self == PredictMode::Full && REGISTERS.iter().any(|r| r.name() == token));
+            if !known {
+                return Err(format!("Can't predict {} here; {}", token, self.prompt()));
+            }
+            guess.insert(token);
+        }
+        Ok(guess)
+    }
+}
+
+/// Show a set of changes, e.g. "CF ZF" or "nothing"
+pub fn describe_changes(changes: &BTreeSet<String>) -> String {
+    if changes.is_empty() {
+        "nothing".to_string()
+    } else {
+        changes.iter().cloned().collect::<Vec<_>>().join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_and_guesses() {
+        let before = CpuState::default();
+        let mut after = before.clone();
+        after.regs.eax = 1;
+        after.eflags.zf = !before.eflags.zf;
+        after.eip += 2;
+
+        let flags = PredictMode::Flags.changes(&before, &after);
+        assert_eq!(describe_changes(&flags), "ZF");
+        let full = PredictMode::Full.changes(&before, &after);
+        assert_eq!(describe_changes(&full), "EAX ZF");
+        assert!(PredictMode::Off.changes(&before, &after).is_empty());
+
+        assert_eq!(PredictMode::Full.parse("zf, eax").unwrap(), full);
+        assert!(PredictMode::Flags.parse("EAX").is_err());
+        assert!(PredictMode::Flags.parse("none").unwrap().is_empty());
+        assert!(PredictMode::Full.parse("EIP").is_err());
+    }
+
+    #[test]
+    fn test_mode_names() {
+        assert_eq!(PredictMode::from_name(""), PredictMode::Off);
+        assert_eq!(PredictMode::from_name("full"), PredictMode::Full);
+        assert_eq!(PredictMode::Full.next(), PredictMode::Off);
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::HashSet;
+
+use iced_x86::{Instruction, MemorySize, Mnemonic, OpKind, Register};
+
+use crate::emulator::DisassemblyLine;
+
+/// One line of approximate C, tied to the instruction it came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PseudoLine {
+    /// Address of the first instruction the line covers
+    pub address: u32,
+    pub text: String,
+}
+
+/// The straight-line block of `lines` containing `address`: from the
+/// nearest branch target or instruction after a branch, up to and
+/// including the next jump, RET or HLT. Calls do not end a block.
+pub fn basic_block(lines: &[DisassemblyLine], address: u32) -> &[DisassemblyLine] {
+    let Some(current) = lines.iter().position(|line| line.address == address) else {
+        return &[];
+    };
+
+    let targets: HashSet<u32> = lines
+        .iter()
+        .filter(|line| is_branch(&line.instruction))
+        .map(|line| line.instruction.near_branch_target() as u32)
+        .collect();
+    let is_leader =
+        |i: usize| i == 0 || targets.contains(&lines[i].address) || ends_block(&lines[i - 1]);
+
+    let start = (0..=current).rev().find(|&i| is_leader(i)).unwrap_or(0);
+    let mut end = current;
+    while !ends_block(&lines[end]) && end + 1 < lines.len() && !is_leader(end + 1) {
+        end += 1;
+    }
+    &lines[start..=end]
+}
+
+/// Translate straight-line instructions into pseudo-C. Each assignment
+/// uses register names as variables; CMP or TEST followed by a
+/// conditional jump becomes one `if (...) goto`. Anything without a
+/// simple C reading is kept as an assembly comment.
+pub fn pseudo_c(block: &[DisassemblyLine]) -> Vec<PseudoLine> {
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < block.len() {
+        let line = &block[i];
+        let instr = &line.instruction;
+
+        let next_jcc = block
+            .get(i + 1)
+            .filter(|next| next.is_code() && flag_condition(next.instruction.mnemonic()).is_some());
+        let text = match (instr.mnemonic(), next_jcc) {
+            (Mnemonic::Cmp | Mnemonic::Test, Some(jcc)) => {
+                i += 1;
+                let jcc_mnemonic = jcc.instruction.mnemonic();
+                let condition = compare_condition(instr, jcc_mnemonic)
+                    .or_else(|| flag_condition(jcc_mnemonic).map(str::to_string))
+                    .unwrap_or_default();
+                Some(format!(
+                    "if ({}) goto {};",
+                    condition,
+                    label(jcc.instruction.near_branch_target())
+                ))
+            }
+            _ if !line.is_code() => Some(format!("/* {} */", line.text)),
+            _ => translate(instr).or_else(|| Some(format!("/* {} */", line.text))),
+        };
+
+        if let Some(text) = text.filter(|text| !text.is_empty()) {
+            result.push(PseudoLine {
+                address: line.address,
+                text,
+            });
+        }
+        i += 1;
+    }
+    result
+}
+
+/// Whether a line ends a basic block
+fn ends_block(line: &DisassemblyLine) -> bool {
+    let mnemonic = line.instruction.mnemonic();
+    !line.is_code()
+        || flag_condition(mnemonic).is_some()
+        || matches!(
+            mnemonic,
+            Mnemonic::Jmp
+                | Mnemonic::Ret
+                | Mnemonic::Retf
+                | Mnemonic::Iret
+                | Mnemonic::Iretd
+                | Mnemonic::Hlt
+                | Mnemonic::Ud2
+                | Mnemonic::Loop
+                | Mnemonic::Loope
+                | Mnemonic::Loopne
+        )
+}
+
+/// Jumps with a fixed target (calls lead elsewhere and come back)
+fn is_branch(instr: &Instruction) -> bool {
+    is_direct(instr) && instr.mnemonic() != Mnemonic::Call
+}
+
+/// C for instructions that read well on their own (None for the rest).
+/// An empty string means the instruction has no C equivalent worth showing.
+fn translate(instr: &Instruction) -> Option<String> {
+    let dst = || operand(instr, 0);
+    let src = || operand(instr, 1);
+    let same_register = instr.op_count() == 2
+        && instr.op0_kind() == OpKind::Register
+        && instr.op1_kind() == OpKind::Register
+        && instr.op0_register() == instr.op1_register();
+
+    Some(match instr.mnemonic() {
+        Mnemonic::Nop => String::new(),
+        Mnemonic::Mov | Mnemonic::Movzx => format!("{} = {};", dst(), src()),
+        Mnemonic::Movsx => format!(
+            "{} = ({}){};",
+            dst(),
+            signed_type(operand_width(instr, 1)),
+            src()
+        ),
+        Mnemonic::Lea => format!("{} = {};", dst(), address(instr)),
+        Mnemonic::Xor | Mnemonic::Sub if same_register => format!("{} = 0;", dst()),
+        Mnemonic::Add => format!("{} += {};", dst(), src()),
+        Mnemonic::Sub => format!("{} -= {};", dst(), src()),
+        Mnemonic::And => format!("{} &= {};", dst(), src()),
+        Mnemonic::Or => format!("{} |= {};", dst(), src()),
+        Mnemonic::Xor => format!("{} ^= {};", dst(), src()),
+        Mnemonic::Shl | Mnemonic::Sal => format!("{} <<= {};", dst(), src()),
+        Mnemonic::Shr => format!("{} >>= {};", dst(), src()),
+        Mnemonic::Sar => format!("{} = (int32_t){} >> {};", dst(), dst(), src()),
+        Mnemonic::Imul if instr.op_count() == 2 => format!("{} *= {};", dst(), src()),
+        Mnemonic::Imul if instr.op_count() == 3 => {
+            format!("{} = {} * {};", dst(), src(), operand(instr, 2))
+        }
+        Mnemonic::Inc => format!("{}++;", dst()),
+        Mnemonic::Dec => format!("{}--;", dst()),
+        Mnemonic::Neg => format!("{} = -{};", dst(), dst()),
+        Mnemonic::Not => format!("{} = ~{};", dst(), dst()),
+        Mnemonic::Xchg => format!("swap({}, {});", dst(), src()),
+        Mnemonic::Push => format!("push({});", dst()),
+        Mnemonic::Pop => format!("{} = pop();", dst()),
+        Mnemonic::Leave => "esp = ebp; ebp = pop();".to_string(),
+        Mnemonic::Call if is_direct(instr) => {
+            format!("eax = {}();", function(instr.near_branch_target()))
+        }
+        Mnemonic::Call => format!("eax = (*{})();", dst()),
+        Mnemonic::Jmp if is_direct(instr) => format!("goto {};", label(instr.near_branch_target())),
+        Mnemonic::Jmp => format!("goto *{};", dst()),
+        Mnemonic::Ret => "return eax;".to_string(),
+        Mnemonic::Hlt => "halt();".to_string(),
+        mnemonic => match flag_condition(mnemonic) {
+            Some(condition) => format!(
+                "if ({}) goto {};",
+                condition,
+                label(instr.near_branch_target())
+            ),
+            None => return None,
+        },
+    })
+}
+
+/// The condition a CMP or TEST sets up for a conditional jump
+fn compare_condition(instr: &Instruction, jcc: Mnemonic) -> Option<String> {
+    let a = operand(instr, 0);
+    let b = operand(instr, 1);
+
+    if instr.mnemonic() == Mnemonic::Test {
+        let same = instr.op0_kind() == OpKind::Register
+            && instr.op1_kind() == OpKind::Register
+            && instr.op0_register() == instr.op1_register();
+        let value = if same { a } else { format!("({} & {})", a, b) };
+        return Some(match jcc {
+            Mnemonic::Je => format!("{} == 0", value),
+            Mnemonic::Jne => format!("{} != 0", value),
+            Mnemonic::Js => format!("(int32_t){} < 0", value),
+            Mnemonic::Jns => format!("(int32_t){} >= 0", value),
+            _ => return None,
+        });
+    }
+
+    // Unsigned conditions compare the registers as they are; signed ones cast
+    let (op, signed) = match jcc {
+        Mnemonic::Je => ("==", false),
+        Mnemonic::Jne => ("!=", false),
+        Mnemonic::Jb => ("<", false),
+        Mnemonic::Jae => (">=", false),
+        Mnemonic::Jbe => ("<=", false),
+        Mnemonic::Ja => (">", false),
+        Mnemonic::Jl => ("<", true),
+        Mnemonic::Jge => (">=", true),
+        Mnemonic::Jle => ("<=", true),
+        Mnemonic::Jg => (">", true),
+        _ => return None,
+    };
+    Some(if signed {
+        format!("(int32_t){} {} (int32_t){}", a, op, b)
+    } else {
+        format!("{} {} {}", a, op, b)
+    })
+}
+
+/// The flags a conditional jump tests, as C (None for other instructions)
+fn flag_condition(jcc: Mnemonic) -> Option<&'static str> {
+    Some(match jcc {
+        Mnemonic::Jo => "OF",
+        Mnemonic::Jno => "!OF",
+        Mnemonic::Jb => "CF",
+        Mnemonic::Jae => "!CF",
+        Mnemonic::Je => "ZF",
+        Mnemonic::Jne => "!ZF",
+        Mnemonic::Jbe => "CF || ZF",
+        Mnemonic::Ja => "!CF && !ZF",
+        Mnemonic::Js => "SF",
+        Mnemonic::Jns => "!SF",
+        Mnemonic::Jp => "PF",
+        Mnemonic::Jnp => "!PF",
+        Mnemonic::Jl => "SF != OF",
+        Mnemonic::Jge => "SF == OF",
+        Mnemonic::Jle => "ZF || SF != OF",
+        Mnemonic::Jg => "!ZF && SF == OF",
+        Mnemonic::Jecxz => "ecx == 0",
+        _ => return None,
+    })
+}
+
+fn is_direct(instr: &Instruction) -> bool {
+    matches!(
+        instr.op0_kind(),
+        OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64
+    )
+}
+
+fn label(target: u64) -> String {
+    format!("loc_{:08X}", target as u32)
+}
+
+fn function(target: u64) -> String {
+    format!("sub_{:08X}", target as u32)
+}
+
+fn register(reg: Register) -> String {
+    format!("{:?}", reg).to_lowercase()
+}
+
+/// Size in bytes of operand `index`
+fn operand_width(instr: &Instruction, index: u32) -> u32 {
+    match instr.op_kind(index) {
+        OpKind::Memory => match instr.memory_size() {
+            MemorySize::UInt8 | MemorySize::Int8 => 1,
+            MemorySize::UInt16 | MemorySize::Int16 => 2,
+            _ => 4,
+        },
+        OpKind::Register => match register(instr.op_register(index)).as_str() {
+            name if name.ends_with('l') || name.ends_with('h') => 1,
+            name if name.len() == 2 => 2,
+            _ => 4,
+        },
+        _ => 4,
+    }
+}
+
+fn unsigned_type(size: u32) -> &'static str {
+    match size {
+        1 => "uint8_t",
+        2 => "uint16_t",
+        _ => "uint32_t",
+    }
+}
+
+fn signed_type(size: u32) -> &'static str {
+    match size {
+        1 => "int8_t",
+        2 => "int16_t",
+        _ => "int32_t",
+    }
+}
+
+/// An operand as a C expression
+fn operand(instr: &Instruction, index: u32) -> String {
+    match instr.op_kind(index) {
+        OpKind::Register => register(instr.op_register(index)),
+        OpKind::Memory => format!(
+            "*({}*){}",
+            unsigned_type(operand_width(instr, index)),
+            match address(instr) {
+                addr if addr.starts_with("0x") => addr,
+                addr => format!("({})", addr),
+            }
+        ),
+        OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64 => {
+            label(instr.near_branch_target())
+        }
+        _ => number(instr.immediate(index) as u32),
+    }
+}
+
+/// The memory operand's effective address, e.g. "ebx + ecx * 4 + 8"
+fn address(instr: &Instruction) -> String {
+    let mut parts = Vec::new();
+    if instr.memory_base() != Register::None {
+        parts.push(register(instr.memory_base()));
+    }
+    if instr.memory_index() != Register::None {
+        match instr.memory_index_scale() {
+            1 => parts.push(register(instr.memory_index())),
+            scale => parts.push(format!("{} * {}", register(instr.memory_index()), scale)),
+        }
+    }
+
+    let displacement = instr.memory_displacement32();
+    if parts.is_empty() {
+        return format!("0x{:X}", displacement);
+    }
+    let mut text = parts.join(" + ");
+    match displacement as i32 {
+        0 => {}
+        d if d < 0 => text.push_str(&format!(" - {}", number(d.unsigned_abs()))),
+        _ => text.push_str(&format!(" + {}", number(displacement))),
+    }
+    text
+}
+
+/// Small numbers in decimal, the rest in hex; -1 and friends as negatives
+fn number(value: u32) -> String {
+    match value as i32 {
+        v @ -256..=-1 => v.to_string(),
+        0..=9 => value.to_string(),
+        _ => format!("0x{:X}", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::Disassembler;
+
+    fn disassemble(bytes: &[u8]) -> Vec<DisassemblyLine> {
+        Disassembler::new().disassemble(bytes, 0x1000, 32)
+    }
+
+    fn texts(lines: &[PseudoLine]) -> Vec<&str> {
+        lines.iter().map(|line| line.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_straight_line_assignments() {
+        // XOR EAX, EAX; MOV ECX, [EBX+8]; ADD EAX, ECX; SHL EAX, 2; LEA EDX, [EAX+ECX*4-4]; NOP; RET
+        let lines = disassemble(&[
+            0x31, 0xC0, 0x8B, 0x4B, 0x08, 0x01, 0xC8, 0xC1, 0xE0, 0x02, 0x8D, 0x54, 0x88, 0xFC,
+            0x90, 0xC3,
+        ]);
+        let block = basic_block(&lines, 0x1005);
+        assert_eq!(block.len(), 7);
+
+        assert_eq!(
+            texts(&pseudo_c(block)),
+            [
+                "eax = 0;",
+                "ecx = *(uint32_t*)(ebx + 8);",
+                "eax += ecx;",
+                "eax <<= 2;",
+                "edx = eax + ecx * 4 - 4;",
+                "return eax;",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_and_branch() {
+        // 1000: CMP EAX, 0x10; JL 0x1009; INC EAX; 1008: HLT; 1009: TEST EBX, EBX; JE 0x1000; RET
+        let lines = disassemble(&[
+            0x83, 0xF8, 0x10, 0x7C, 0x04, 0x40, 0x90, 0x90, 0xF4, 0x85, 0xDB, 0x74, 0xF3, 0xC3,
+        ]);
+
+        let first = basic_block(&lines, 0x1000);
+        assert_eq!(
+            texts(&pseudo_c(first)),
+            ["if ((int32_t)eax < (int32_t)0x10) goto loc_00001009;"]
+        );
+
+        // The fallthrough block runs up to the HLT; the jump target starts the next
+        let middle = basic_block(&lines, 0x1005);
+        assert_eq!(texts(&pseudo_c(middle)), ["eax++;", "halt();"]);
+
+        let last = basic_block(&lines, 0x100B);
+        assert_eq!(last[0].address, 0x1009);
+        assert_eq!(texts(&pseudo_c(last)), ["if (ebx == 0) goto loc_00001000;"]);
+        assert!(basic_block(&lines, 0x2000).is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use super::DebuggerError;
-use crate::emulator::Memory;
+use crate::emulator::{CpuState, Memory, Register};
 
 /// Search result containing address and matched data
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -8,6 +8,80 @@ pub struct SearchResult {
     pub data: Vec<u8>,
 }
 
+/// Offsets of every (possibly overlapping) occurrence of `needle` in `haystack`.
+/// Scans for the first byte before comparing the rest of the pattern.
+fn find_all<'a>(haystack: &'a [u8], needle: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+    let first = needle.first().copied();
+    let last_start = haystack.len().checked_sub(needle.len());
+    let mut pos = 0;
+
+    std::iter::from_fn(move || {
+        let (first, last_start) = (first?, last_start?);
+        while pos <= last_start {
+            let offset = haystack[pos..=last_start]
+                .iter()
+                .position(|&b| b == first)?;
+            let start = pos + offset;
+            pos = start + 1;
+            if &haystack[start..start + needle.len()] == needle {
+                return Some(start);
+            }
+        }
+        None
+    })
+}
+
+/// Where a value turned up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueLocation {
+    Register(Register),
+    /// Within the stack window, `offset` bytes above ESP
+    Stack {
+        address: u32,
+        offset: u32,
+    },
+    Memory {
+        address: u32,
+    },
+}
+
+/// One occurrence of a 32-bit value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueHit {
+    pub location: ValueLocation,
+    /// Found with its bytes reversed (stored big-endian)
+    pub big_endian: bool,
+}
+
+impl ValueHit {
+    /// Memory address of the hit, if it isn't a register
+    pub fn address(&self) -> Option<u32> {
+        match self.location {
+            ValueLocation::Register(_) => None,
+            ValueLocation::Stack { address, .. } | ValueLocation::Memory { address } => {
+                Some(address)
+            }
+        }
+    }
+
+    /// "EAX", "[esp+8] 0x2FF8", "0x2000"
+    pub fn describe(&self) -> String {
+        let place = match self.location {
+            ValueLocation::Register(reg) => reg.name().to_string(),
+            ValueLocation::Stack { address, offset: 0 } => format!("[esp] 0x{:08X}", address),
+            ValueLocation::Stack { address, offset } => {
+                format!("[esp+{}] 0x{:08X}", offset, address)
+            }
+            ValueLocation::Memory { address } => format!("0x{:08X}", address),
+        };
+        if self.big_endian {
+            format!("{} (big-endian)", place)
+        } else {
+            place
+        }
+    }
+}
+
 /// Search engine for finding bytes and strings in memory
 pub struct MemorySearch;
 
@@ -43,17 +117,15 @@ impl MemorySearch {
         let mut results = Vec::new();
         let search_len = (end_address - start_address) as usize;
 
-        // Read the entire search range
-        let data = memory.read_bytes(start_address, search_len)?;
+        // Peek the entire search range (devices keep their state)
+        let data = memory.peek_bytes(start_address, search_len)?;
 
         // Search for pattern
-        for i in 0..=data.len().saturating_sub(pattern.len()) {
-            if &data[i..i + pattern.len()] == pattern {
-                results.push(SearchResult {
-                    address: start_address + i as u32,
-                    data: pattern.to_vec(),
-                });
-            }
+        for i in find_all(&data, pattern) {
+            results.push(SearchResult {
+                address: start_address + i as u32,
+                data: pattern.to_vec(),
+            });
         }
 
         Ok(results)
@@ -98,27 +170,31 @@ impl MemorySearch {
         let mut results = Vec::new();
         let search_len = (end_address - start_address) as usize;
 
-        // Read the entire search range
-        let data = memory.read_bytes(start_address, search_len)?;
+        // Peek the entire search range (devices keep their state)
+        let data = memory.peek_bytes(start_address, search_len)?;
 
         // Convert to lowercase if case-insensitive
         let search_data = if case_sensitive {
             data.clone()
         } else {
             data.iter()
-                .map(|&b| if b.is_ascii_alphabetic() { b.to_ascii_lowercase() } else { b })
+                .map(|&b| {
+                    if b.is_ascii_alphabetic() {
+                        b.to_ascii_lowercase()
+                    } else {
+                        b
+                    }
+                })
                 .collect::<Vec<_>>()
         };
 
         // Search for pattern
-        for i in 0..=search_data.len().saturating_sub(pattern_bytes.len()) {
-            if &search_data[i..i + pattern_bytes.len()] == pattern_bytes {
-                // Return the original bytes, not the lowercased ones
-                results.push(SearchResult {
-                    address: start_address + i as u32,
-                    data: data[i..i + pattern_bytes.len()].to_vec(),
-                });
-            }
+        for i in find_all(&search_data, pattern_bytes) {
+            // Return the original bytes, not the lowercased ones
+            results.push(SearchResult {
+                address: start_address + i as u32,
+                data: data[i..i + pattern_bytes.len()].to_vec(),
+            });
         }
 
         Ok(results)
@@ -149,8 +225,8 @@ impl MemorySearch {
         let mut results = Vec::new();
         let search_len = (end_address - start_address) as usize;
 
-        // Read the entire search range
-        let data = memory.read_bytes(start_address, search_len)?;
+        // Peek the entire search range (devices keep their state)
+        let data = memory.peek_bytes(start_address, search_len)?;
 
         let mut current_string = Vec::new();
         let mut string_start: Option<u32> = None;
@@ -191,30 +267,309 @@ impl MemorySearch {
             .replace("0x", "")
             .replace(",", "");
 
-        if cleaned.len() % 2 != 0 {
+        let digits: Vec<char> = cleaned.chars().collect();
+        if !digits.len().is_multiple_of(2) {
             return Err("Hex string must have an even number of characters".to_string());
         }
 
         let mut bytes = Vec::new();
-        for i in (0..cleaned.len()).step_by(2) {
-            let byte_str = &cleaned[i..i + 2];
-            match u8::from_str_radix(byte_str, 16) {
-                Ok(byte) => bytes.push(byte),
-                Err(_) => return Err(format!("Invalid hex byte: {}", byte_str)),
+        for pair in digits.chunks(2) {
+            match (pair[0].to_digit(16), pair[1].to_digit(16)) {
+                (Some(high), Some(low)) => bytes.push((high * 16 + low) as u8),
+                _ => return Err(format!("Invalid hex byte: {}{}", pair[0], pair[1])),
             }
         }
 
         Ok(bytes)
     }
+
+    /// Every place a 32-bit value shows up: registers, then memory in both
+    /// byte orders. Memory hits within `stack_slots` dwords above ESP are
+    /// reported as stack slots. Only mapped regions are scanned, or the
+    /// whole of memory when none are mapped, and within them only memory
+    /// that was written to (untouched memory reads as zeros, so a value of
+    /// zero isn't reported all through it).
+    pub fn find_value(
+        cpu: &CpuState,
+        memory: &Memory,
+        value: u32,
+        stack_slots: u32,
+    ) -> Vec<ValueHit> {
+        let swapped = value.swap_bytes();
+        let mut hits = Vec::new();
+
+        for reg in Register::ALL {
+            let current = cpu.get_register(reg);
+            if current == value || current == swapped {
+                hits.push(ValueHit {
+                    location: ValueLocation::Register(reg),
+                    big_endian: current != value,
+                });
+            }
+        }
+
+        let mut ranges: Vec<(u32, u32)> =
+            memory.regions().iter().map(|r| (r.start, r.end)).collect();
+        if ranges.is_empty() {
+            ranges.push((0, memory.end()));
+        }
+        ranges.sort();
+
+        let esp = cpu.regs.esp;
+        let stack_end = esp.saturating_add(stack_slots.saturating_mul(4));
+        let mut patterns = vec![(value.to_le_bytes(), false)];
+        if swapped != value {
+            patterns.push((value.to_be_bytes(), true));
+        }
+
+        for (start, end) in ranges {
+            let end = end.min(memory.end());
+            // A dword can reach three bytes into untouched memory either side
+            let mut spans: Vec<(u32, u32)> = Vec::new();
+            for (from, to) in memory.populated_ranges(start, end) {
+                let (from, to) = (
+                    from.saturating_sub(3).max(start),
+                    to.saturating_add(3).min(end),
+                );
+                match spans.last_mut() {
+                    Some(last) if from <= last.1 => last.1 = to,
+                    _ => spans.push((from, to)),
+                }
+            }
+
+            let mut found: Vec<(u32, bool)> = Vec::new();
+            for (from, to) in spans {
+                let Some(data) = memory.slice(from, (to - from) as usize) else {
+                    continue;
+                };
+                for (pattern, big_endian) in &patterns {
+                    found.extend(find_all(&data, pattern).map(|i| (from + i as u32, *big_endian)));
+                }
+            }
+            found.sort();
+            hits.extend(found.into_iter().map(|(address, big_endian)| ValueHit {
+                location: if (esp..stack_end).contains(&address) {
+                    ValueLocation::Stack {
+                        address,
+                        offset: address - esp,
+                    }
+                } else {
+                    ValueLocation::Memory { address }
+                },
+                big_endian,
+            }));
+        }
+
+        hits
+    }
 }
 
+/// What a [`SearchScan`] looks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchQuery {
+    /// An exact byte pattern
+    Bytes(Vec<u8>),
+    /// ASCII text, optionally ignoring case
+    Text { text: String, case_sensitive: bool },
+    /// Null-terminated printable strings at least `min_length` bytes long
+    Strings { min_length: usize },
+}
+
+/// A search that runs a chunk at a time, so scanning a large memory never
+/// stalls the UI. Results come back from each [`step`](Self::step) as
+/// they're found, and the scan stops once `limit` have turned up.
+#[derive(Debug, Clone)]
+pub struct SearchScan {
+    query: SearchQuery,
+    /// Bytes compared against memory (lowercased for case-insensitive text)
+    pattern: Vec<u8>,
+    start: u32,
+    end: u32,
+    /// Next address to scan
+    next: u32,
+    limit: usize,
+    found: usize,
+    capped: bool,
+    /// String still being read at the end of the last chunk
+    partial: Option<(u32, Vec<u8>)>,
+}
+
+impl SearchScan {
+    pub fn new(
+        query: SearchQuery,
+        start: u32,
+        end: u32,
+        limit: usize,
+    ) -> Result<Self, DebuggerError> {
+        if start >= end {
+            return Err(DebuggerError::InvalidInput(
+                "Start address must be less than end address".to_string(),
+            ));
+        }
+        let pattern = match &query {
+            SearchQuery::Bytes(bytes) => bytes.clone(),
+            SearchQuery::Text {
+                text,
+                case_sensitive: true,
+            } => text.as_bytes().to_vec(),
+            SearchQuery::Text {
+                text,
+                case_sensitive: false,
+            } => text.to_ascii_lowercase().into_bytes(),
+            SearchQuery::Strings { .. } => Vec::new(),
+        };
+        if pattern.is_empty() && !matches!(query, SearchQuery::Strings { .. }) {
+            return Err(DebuggerError::InvalidInput(
+                "Search pattern cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            query,
+            pattern,
+            start,
+            end,
+            next: start,
+            limit,
+            found: 0,
+            capped: false,
+            partial: None,
+        })
+    }
+
+    /// Scan up to `budget` more bytes, returning the results found in them
+    pub fn step(&mut self, memory: &Memory, budget: usize) -> Vec<SearchResult> {
+        let end = self.end.min(memory.end());
+        if self.is_done() || self.next >= end {
+            self.next = self.end;
+            return Vec::new();
+        }
+
+        let chunk_start = self.next;
+        let chunk_end = end.min(chunk_start.saturating_add(budget.max(1) as u32));
+        // Read far enough past the chunk to catch patterns that straddle it
+        let overlap = self.pattern.len().saturating_sub(1) as u32;
+        let read_end = end.min(chunk_end.saturating_add(overlap));
+        let data = memory
+            .slice(chunk_start, (read_end - chunk_start) as usize)
+            .unwrap_or_default();
+        self.next = if chunk_end == end {
+            self.end
+        } else {
+            chunk_end
+        };
+
+        let mut results = Vec::new();
+        match self.query {
+            SearchQuery::Bytes(_) | SearchQuery::Text { .. } => {
+                let haystack = match self.query {
+                    SearchQuery::Text {
+                        case_sensitive: false,
+                        ..
+                    } => data.to_ascii_lowercase(),
+                    _ => data.clone(),
+                };
+                let offsets: Vec<usize> = find_all(&haystack, &self.pattern)
+                    .take_while(|&i| chunk_start + (i as u32) < chunk_end)
+                    .collect();
+                for i in offsets {
+                    if !self.take() {
+                        break;
+                    }
+                    // Keep the original bytes, not the lowercased ones
+                    results.push(SearchResult {
+                        address: chunk_start + i as u32,
+                        data: data[i..i + self.pattern.len()].to_vec(),
+                    });
+                }
+            }
+            SearchQuery::Strings { min_length } => {
+                for (i, &byte) in data.iter().enumerate() {
+                    if byte == 0 {
+                        match self.partial.take() {
+                            Some((address, bytes)) if bytes.len() >= min_length => {
+                                if !self.take() {
+                                    break;
+                                }
+                                results.push(SearchResult {
+                                    address,
+                                    data: bytes,
+                                });
+                            }
+                            _ => {}
+                        }
+                    } else if byte.is_ascii_graphic() || byte.is_ascii_whitespace() {
+                        self.partial
+                            .get_or_insert_with(|| (chunk_start + i as u32, Vec::new()))
+                            .1
+                            .push(byte);
+                    } else {
+                        self.partial = None;
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    /// Count one more result, or note the cap when it's been reached
+    fn take(&mut self) -> bool {
+        if self.found >= self.limit {
+            self.capped = true;
+            return false;
+        }
+        self.found += 1;
+        true
+    }
+
+    /// Start over from the beginning of the range, as after memory changed
+    pub fn restart(&mut self) {
+        self.next = self.start;
+        self.found = 0;
+        self.capped = false;
+        self.partial = None;
+    }
+
+    /// Run the rest of the scan at once
+    pub fn finish(&mut self, memory: &Memory) -> Vec<SearchResult> {
+        let mut results = Vec::new();
+        while !self.is_done() {
+            results.extend(self.step(memory, SCAN_CHUNK));
+        }
+        results
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.capped || self.next >= self.end
+    }
+
+    /// Whether the scan stopped early because it hit its result limit
+    pub fn is_capped(&self) -> bool {
+        self.capped
+    }
+
+    /// Results found so far
+    pub fn found(&self) -> usize {
+        self.found
+    }
+
+    /// How much of the range has been scanned, 0-100
+    pub fn percent(&self) -> u32 {
+        let total = (self.end - self.start) as u64;
+        ((self.next - self.start) as u64 * 100 / total) as u32
+    }
+}
+
+/// Bytes a [`SearchScan`] covers per step when run to completion
+pub const SCAN_CHUNK: usize = 0x4000;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_search_bytes() {
-        let mut memory = Memory::new(4096);
+        let mut memory = Memory::new(0x4000);
 
         // Write pattern at 0x1000
         memory.write_bytes(0x1000, &[0x90, 0x90, 0x90]).unwrap();
@@ -222,13 +577,7 @@ mod tests {
         // Write pattern at 0x2000
         memory.write_bytes(0x2000, &[0x90, 0x90, 0x90]).unwrap();
 
-        let results = MemorySearch::search_bytes(
-            &memory,
-            &[0x90, 0x90],
-            0x0,
-            0x3000,
-        )
-        .unwrap();
+        let results = MemorySearch::search_bytes(&memory, &[0x90, 0x90], 0x0, 0x3000).unwrap();
 
         // Should find 4 matches: 0x1000, 0x1001, 0x2000, 0x2001
         assert_eq!(results.len(), 4);
@@ -238,9 +587,24 @@ mod tests {
         assert_eq!(results[3].address, 0x2001);
     }
 
+    #[test]
+    fn test_search_bytes_overlapping_and_short_range() {
+        let mut memory = Memory::new(0x4000);
+        memory.load(0x1000, &[0xAA, 0xAA, 0xAA]).unwrap();
+
+        let results = MemorySearch::search_bytes(&memory, &[0xAA, 0xAA], 0x1000, 0x1010).unwrap();
+        let addresses: Vec<u32> = results.iter().map(|r| r.address).collect();
+        assert_eq!(addresses, vec![0x1000, 0x1001]);
+
+        // Pattern longer than the range finds nothing instead of panicking
+        let results =
+            MemorySearch::search_bytes(&memory, &[0xAA, 0xAA, 0xAA], 0x1000, 0x1002).unwrap();
+        assert!(results.is_empty());
+    }
+
     #[test]
     fn test_search_string() {
-        let mut memory = Memory::new(4096);
+        let mut memory = Memory::new(0x4000);
 
         // Write "HELLO" at 0x1000
         memory.write_bytes(0x1000, b"HELLO").unwrap();
@@ -249,27 +613,13 @@ mod tests {
         memory.write_bytes(0x2000, b"hello").unwrap();
 
         // Case-sensitive search
-        let results = MemorySearch::search_string(
-            &memory,
-            "HELLO",
-            0x0,
-            0x3000,
-            true,
-        )
-        .unwrap();
+        let results = MemorySearch::search_string(&memory, "HELLO", 0x0, 0x3000, true).unwrap();
 
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].address, 0x1000);
 
         // Case-insensitive search
-        let results = MemorySearch::search_string(
-            &memory,
-            "hello",
-            0x0,
-            0x3000,
-            false,
-        )
-        .unwrap();
+        let results = MemorySearch::search_string(&memory, "hello", 0x0, 0x3000, false).unwrap();
 
         assert_eq!(results.len(), 2);
         assert_eq!(results[0].address, 0x1000);
@@ -278,7 +628,7 @@ mod tests {
 
     #[test]
     fn test_find_strings() {
-        let mut memory = Memory::new(4096);
+        let mut memory = Memory::new(0x4000);
 
         // Write null-terminated strings
         memory.write_bytes(0x1000, b"Hello\0").unwrap();
@@ -286,10 +636,8 @@ mod tests {
         memory.write_bytes(0x3000, b"Hi\0").unwrap(); // Too short
 
         let results = MemorySearch::find_strings(
-            &memory,
-            4, // min length
-            0x0,
-            0x4000,
+            &memory, 4, // min length
+            0x0, 0x4000,
         )
         .unwrap();
 
@@ -300,6 +648,75 @@ mod tests {
         assert_eq!(results[1].data, b"World!");
     }
 
+    #[test]
+    fn test_search_leaves_devices_alone() {
+        use crate::emulator::RandomDevice;
+
+        let mut memory = Memory::new(0x4000);
+        memory.map_device(0x3000, RandomDevice::SIZE, RandomDevice::new(0x1234));
+        let before = memory.slice(0x3000, 4);
+
+        MemorySearch::search_bytes(&memory, &[0x90], 0x2000, 0x4000).unwrap();
+        MemorySearch::search_string(&memory, "key", 0x2000, 0x4000, false).unwrap();
+        MemorySearch::find_strings(&memory, 4, 0x2000, 0x4000).unwrap();
+
+        // Reading the device would have advanced its generator
+        assert_eq!(memory.slice(0x3000, 4), before);
+        let mut fresh = Memory::new(0x4000);
+        fresh.map_device(0x3000, RandomDevice::SIZE, RandomDevice::new(0x1234));
+        assert_eq!(
+            memory.read_u32(0x3000).unwrap(),
+            fresh.read_u32(0x3000).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_scan_in_small_steps_matches_across_chunks_and_caps() {
+        let mut memory = Memory::new(0x4000);
+        memory.write_bytes(0x0FFE, b"Flag\0").unwrap();
+        memory.write_bytes(0x2000, b"flag{xx}\0").unwrap();
+
+        // Steps of 3 bytes split both matches across chunk boundaries
+        let text = SearchQuery::Text {
+            text: "FLAG".to_string(),
+            case_sensitive: false,
+        };
+        let mut scan = SearchScan::new(text.clone(), 0, 0x4000, 10).unwrap();
+        let mut results = Vec::new();
+        while !scan.is_done() {
+            results.extend(scan.step(&memory, 3));
+        }
+        let addresses: Vec<u32> = results.iter().map(|r| r.address).collect();
+        assert_eq!(addresses, vec![0x0FFE, 0x2000]);
+        assert_eq!(results[1].data, b"flag");
+        assert!(!scan.is_capped());
+        assert_eq!(scan.percent(), 100);
+
+        let mut strings =
+            SearchScan::new(SearchQuery::Strings { min_length: 4 }, 0, 0x4000, 10).unwrap();
+        let mut found = Vec::new();
+        while !strings.is_done() {
+            found.extend(strings.step(&memory, 5));
+        }
+        assert_eq!(
+            found,
+            MemorySearch::find_strings(&memory, 4, 0, 0x4000).unwrap()
+        );
+
+        // One result allowed: the scan stops at the second and says so
+        let mut capped = SearchScan::new(text, 0, 0x4000, 1).unwrap();
+        assert_eq!(capped.finish(&memory).len(), 1);
+        assert!(capped.is_capped() && capped.is_done());
+        capped.restart();
+        assert!(!capped.is_done());
+        assert_eq!(capped.finish(&memory)[0].address, 0x0FFE);
+
+        // The range is clipped to the memory size
+        let mut beyond = SearchScan::new(SearchQuery::Bytes(vec![0x78]), 0, 0x10000, 10).unwrap();
+        assert_eq!(beyond.finish(&memory).len(), 2);
+        assert!(SearchScan::new(SearchQuery::Bytes(Vec::new()), 0, 0x10, 10).is_err());
+    }
+
     #[test]
     fn test_parse_hex_pattern() {
         assert_eq!(
@@ -319,5 +736,73 @@ mod tests {
 
         assert!(MemorySearch::parse_hex_pattern("9").is_err());
         assert!(MemorySearch::parse_hex_pattern("ZZ").is_err());
+        assert_eq!(
+            MemorySearch::parse_hex_pattern("9🦀").unwrap_err(),
+            "Invalid hex byte: 9🦀"
+        );
+    }
+
+    #[test]
+    fn test_find_value_in_registers_stack_and_memory() {
+        let mut dbg = crate::debugger::Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        dbg.cpu.regs.eax = 0xDEADBEEF;
+        dbg.cpu.regs.ebx = 0xEFBEADDE;
+        dbg.memory.write_u32(0x2000, 0xDEADBEEF).unwrap();
+        dbg.memory
+            .write_bytes(0x2010, &[0xDE, 0xAD, 0xBE, 0xEF])
+            .unwrap();
+        let esp = dbg.cpu.regs.esp;
+        dbg.memory.write_u32(esp + 8, 0xDEADBEEF).unwrap();
+
+        let hits = MemorySearch::find_value(&dbg.cpu, &dbg.memory, 0xDEADBEEF, 8);
+        let described: Vec<String> = hits.iter().map(|h| h.describe()).collect();
+        assert_eq!(
+            described,
+            vec![
+                "EAX".to_string(),
+                "EBX (big-endian)".to_string(),
+                "0x00002000".to_string(),
+                "0x00002010 (big-endian)".to_string(),
+                format!("[esp+8] 0x{:08X}", esp + 8),
+            ]
+        );
+        assert_eq!(hits[0].address(), None);
+        assert_eq!(hits[4].address(), Some(esp + 8));
+
+        // A palindromic value isn't reported twice
+        dbg.memory.write_u32(0x2020, 0x11111111).unwrap();
+        let hits = MemorySearch::find_value(&dbg.cpu, &dbg.memory, 0x11111111, 8);
+        assert_eq!(hits.len(), 1);
+        assert!(!hits[0].big_endian);
+    }
+
+    #[test]
+    fn test_find_value_skips_untouched_memory() {
+        // A data region of nearly 2 GB, almost all of it never written
+        let mut dbg =
+            crate::debugger::Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x8000_0000);
+        dbg.cpu.regs.esp = 0x8000_0000;
+        dbg.memory.write_u32(0x4000_0000, 0xCAFEBABE).unwrap();
+        // Straddles the end of a written page into an untouched one
+        dbg.memory.write_u8(0x5000_0FFF, 0x12).unwrap();
+
+        let hits = MemorySearch::find_value(&dbg.cpu, &dbg.memory, 0xCAFEBABE, 8);
+        assert_eq!(
+            hits.iter().map(ValueHit::address).collect::<Vec<_>>(),
+            [Some(0x4000_0000)]
+        );
+        let hits = MemorySearch::find_value(&dbg.cpu, &dbg.memory, 0x12, 8);
+        let found: Vec<(Option<u32>, bool)> = hits
+            .iter()
+            .map(|hit| (hit.address(), hit.big_endian))
+            .collect();
+        assert_eq!(
+            found,
+            [(Some(0x5000_0FFC), true), (Some(0x5000_0FFF), false)]
+        );
+        assert_eq!(
+            dbg.memory.populated_ranges(0x2000, 0x7FFF_F000),
+            [(0x4000_0000, 0x4000_1000), (0x5000_0000, 0x5000_1000)]
+        );
     }
 }
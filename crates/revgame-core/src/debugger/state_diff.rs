@@ -0,0 +1,188 @@
+use crate::emulator::{CpuState, Eflags, MemorySnapshot, Register};
+
+/// CPU and memory as they were at a point on the timeline
+#[derive(Debug, Clone)]
+pub struct StateCapture {
+    /// Instruction index the capture was taken at
+    pub position: u64,
+    pub cpu: CpuState,
+    pub memory: MemorySnapshot,
+}
+
+/// A byte of a compared memory range that differs between the captures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteChange {
+    pub address: u32,
+    pub before: u8,
+    pub after: u8,
+}
+
+/// Registers, flags and chosen memory ranges at two points on the timeline
+#[derive(Debug, Clone)]
+pub struct StateDiff {
+    pub from: u64,
+    pub to: u64,
+    /// Every register with its value at both points
+    pub registers: Vec<(Register, u32, u32)>,
+    /// Every flag with its state at both points
+    pub flags: Vec<(&'static str, bool, bool)>,
+    /// Memory ranges compared, as start and length
+    pub ranges: Vec<(u32, u32)>,
+    /// Bytes in those ranges that changed, in address order
+    pub memory: Vec<ByteChange>,
+}
+
+/// Flags in the order they're shown
+fn flags(eflags: &Eflags) -> [(&'static str, bool); 7] {
+    [
+        ("CF", eflags.cf),
+        ("ZF", eflags.zf),
+        ("SF", eflags.sf),
+        ("OF", eflags.of),
+        ("PF", eflags.pf),
+        ("AF", eflags.af),
+        ("DF", eflags.df),
+    ]
+}
+
+impl StateDiff {
+    /// Compare two captures, along with the memory in `ranges`
+    pub fn new(before: &StateCapture, after: &StateCapture, ranges: &[(u32, u32)]) -> Self {
+        let registers = Register::ALL
+            .iter()
+            .map(|&reg| {
+                (
+                    reg,
+                    before.cpu.get_register(reg),
+                    after.cpu.get_register(reg),
+                )
+            })
+            .collect();
+        let flags = flags(&before.cpu.eflags)
+            .into_iter()
+            .zip(flags(&after.cpu.eflags))
+            .map(|((name, was), (_, now))| (name, was, now))
+            .collect();
+
+        let mut memory: Vec<ByteChange> = ranges
+            .iter()
+            .flat_map(|&(start, length)| {
+                let was = before.memory.read_bytes(start, length as usize);
+                let now = after.memory.read_bytes(start, length as usize);
+                was.into_iter()
+                    .zip(now)
+                    .enumerate()
+                    .filter(|(_, (was, now))| was != now)
+                    .map(move |(offset, (before, after))| ByteChange {
+                        address: start.wrapping_add(offset as u32),
+                        before,
+                        after,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        memory.sort_by_key(|change| change.address);
+        memory.dedup_by_key(|change| change.address);
+
+        Self {
+            from: before.position,
+            to: after.position,
+            registers,
+            flags,
+            ranges: ranges.to_vec(),
+            memory,
+        }
+    }
+
+    /// Registers whose value changed
+    pub fn changed_registers(&self) -> impl Iterator<Item = &(Register, u32, u32)> {
+        self.registers.iter().filter(|(_, was, now)| was != now)
+    }
+
+    /// Flags that flipped
+    pub fn changed_flags(&self) -> impl Iterator<Item = &(&'static str, bool, bool)> {
+        self.flags.iter().filter(|(_, was, now)| was != now)
+    }
+
+    /// One-line count of what changed
+    pub fn summary(&self) -> String {
+        let plural = |count: usize, what: &str| {
+            format!("{} {}{}", count, what, if count == 1 { "" } else { "s" })
+        };
+        let mut parts = vec![
+            plural(self.changed_registers().count(), "register"),
+            plural(self.changed_flags().count(), "flag"),
+        ];
+        if !self.ranges.is_empty() {
+            parts.push(plural(self.memory.len(), "byte"));
+        }
+        format!(
+            "Instruction {} to {}: {} changed",
+            self.from,
+            self.to,
+            parts.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::Debugger;
+
+    #[test]
+    fn test_diff_between_timeline_points() {
+        let mut debugger = Debugger::new(0x10000);
+        // mov eax, 5; mov [0x2000], al; inc eax; hlt
+        debugger
+            .load_code(
+                0x1000,
+                &[
+                    0xB8, 0x05, 0x00, 0x00, 0x00, 0xA2, 0x00, 0x20, 0x00, 0x00, 0x40, 0xF4,
+                ],
+            )
+            .unwrap();
+        debugger.set_entry_point(0x1000);
+        debugger.save_initial_state();
+        debugger.run().unwrap();
+        let end = debugger.position();
+
+        let start = debugger.capture_at(0).unwrap();
+        let middle = debugger.capture_at(2).unwrap();
+        assert_eq!(
+            debugger.position(),
+            end,
+            "capturing leaves the debugger where it was"
+        );
+
+        let diff = StateDiff::new(&start, &middle, &[(0x2000, 4)]);
+        let changed: Vec<Register> = diff.changed_registers().map(|(reg, _, _)| *reg).collect();
+        assert_eq!(changed, [Register::Eax, Register::Eip]);
+        assert_eq!(
+            diff.memory,
+            [ByteChange {
+                address: 0x2000,
+                before: 0,
+                after: 5
+            }]
+        );
+        assert_eq!(
+            diff.summary(),
+            "Instruction 0 to 2: 2 registers, 0 flags, 1 byte changed"
+        );
+
+        // INC sets PF for 6 (two bits set)
+        let last = debugger.capture_at(3).unwrap();
+        let diff = StateDiff::new(&middle, &last, &[]);
+        assert_eq!(
+            diff.changed_flags()
+                .map(|(name, _, _)| *name)
+                .collect::<Vec<_>>(),
+            ["PF"]
+        );
+        assert_eq!(
+            diff.summary(),
+            "Instruction 2 to 3: 2 registers, 1 flag changed"
+        );
+    }
+}
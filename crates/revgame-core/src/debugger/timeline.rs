@@ -0,0 +1,115 @@
+/// Something that happened at a point on the execution timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineMark {
+    /// Execution stopped at a breakpoint
+    Breakpoint,
+    /// The player patched memory
+    Patch,
+    /// The player marked the point to come back to
+    Snapshot,
+}
+
+/// A mark at an instruction index (instructions executed since the program
+/// started)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineMarker {
+    pub position: u64,
+    pub mark: TimelineMark,
+}
+
+/// How far the program has got since it started, with marks for breakpoint
+/// hits, patches and snapshots. Any point up to `length` can be revisited:
+/// execution is deterministic, so the debugger replays its way there.
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    markers: Vec<TimelineMarker>,
+    length: u64,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Furthest instruction index reached
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Marks in order of position
+    pub fn markers(&self) -> &[TimelineMarker] {
+        &self.markers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0 && self.markers.is_empty()
+    }
+
+    /// Note that execution reached `position`
+    pub fn advance_to(&mut self, position: u64) {
+        self.length = self.length.max(position);
+    }
+
+    /// Mark `position`, once per kind of mark
+    pub fn record(&mut self, position: u64, mark: TimelineMark) {
+        let marker = TimelineMarker { position, mark };
+        if !self.markers.contains(&marker) {
+            let at = self.markers.partition_point(|m| m.position <= position);
+            self.markers.insert(at, marker);
+        }
+    }
+
+    /// Forget everything after `position`, which may no longer happen the
+    /// same way (after a patch)
+    pub fn truncate(&mut self, position: u64) {
+        self.markers.retain(|m| m.position <= position);
+        self.length = self.length.min(position);
+    }
+
+    pub fn clear(&mut self) {
+        self.markers.clear();
+        self.length = 0;
+    }
+
+    /// Nearest marked position before `position`
+    pub fn previous_marker(&self, position: u64) -> Option<u64> {
+        self.markers
+            .iter()
+            .rev()
+            .map(|m| m.position)
+            .find(|&p| p < position)
+    }
+
+    /// Nearest marked position after `position`
+    pub fn next_marker(&self, position: u64) -> Option<u64> {
+        self.markers
+            .iter()
+            .map(|m| m.position)
+            .find(|&p| p > position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_markers() {
+        let mut timeline = Timeline::new();
+        timeline.advance_to(10);
+        timeline.record(7, TimelineMark::Patch);
+        timeline.record(3, TimelineMark::Breakpoint);
+        timeline.record(3, TimelineMark::Breakpoint);
+        timeline.record(9, TimelineMark::Snapshot);
+
+        let positions: Vec<u64> = timeline.markers().iter().map(|m| m.position).collect();
+        assert_eq!(positions, [3, 7, 9]);
+        assert_eq!(timeline.previous_marker(7), Some(3));
+        assert_eq!(timeline.next_marker(7), Some(9));
+        assert_eq!(timeline.next_marker(9), None);
+
+        timeline.truncate(7);
+        assert_eq!(timeline.length(), 7);
+        assert_eq!(timeline.markers().len(), 2);
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+/// Most instructions kept in a trace's ordered path
+pub const MAX_PATH: usize = 10_000;
+
+/// One executed instruction in a run's path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    /// Address of the instruction
+    pub address: u32,
+    /// Disassembly of the instruction
+    pub text: String,
+}
+
+/// Control flow recorded during a run: how often execution went from one
+/// instruction to the next, and the order of the first `MAX_PATH` steps
+#[derive(Debug, Clone, Default)]
+pub struct RunTrace {
+    /// Transition counts keyed by (from, to) instruction address
+    edges: HashMap<(u32, u32), u64>,
+    /// Executed instructions in order
+    path: Vec<TraceStep>,
+    /// Whether the path stopped at `MAX_PATH` steps
+    truncated: bool,
+    /// Instructions executed
+    pub instructions: u64,
+}
+
+impl RunTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that execution moved from `from` to `to`
+    pub fn record(&mut self, from: u32, to: u32) {
+        *self.edges.entry((from, to)).or_insert(0) += 1;
+    }
+
+    /// Append an executed instruction to the path
+    pub fn record_step(&mut self, address: u32, text: &str) {
+        if self.path.len() < MAX_PATH {
+            self.path.push(TraceStep {
+                address,
+                text: text.to_string(),
+            });
+        } else {
+            self.truncated = true;
+        }
+    }
+
+    /// Executed instructions in order
+    pub fn path(&self) -> &[TraceStep] {
+        &self.path
+    }
+
+    /// Whether the run was longer than the recorded path
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// How often execution went from `from` to `to`
+    pub fn count(&self, from: u32, to: u32) -> u64 {
+        self.edges.get(&(from, to)).copied().unwrap_or(0)
+    }
+
+    /// Addresses execution left at least once, in ascending order
+    pub fn sources(&self) -> Vec<u32> {
+        let mut sources: Vec<u32> = self.edges.keys().map(|&(from, _)| from).collect();
+        sources.sort_unstable();
+        sources.dedup();
+        sources
+    }
+
+    /// Backward transitions (loops) as (from, to, count), in address order
+    pub fn back_edges(&self) -> Vec<(u32, u32, u64)> {
+        let mut edges: Vec<(u32, u32, u64)> = self
+            .edges
+            .iter()
+            .filter(|&(&(from, to), _)| to <= from)
+            .map(|(&(from, to), &count)| (from, to, count))
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
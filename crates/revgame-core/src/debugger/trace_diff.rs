@@ -0,0 +1,139 @@
+use super::{RunTrace, TraceStep};
+
+/// Two runs' paths lined up against each other
+#[derive(Debug, Clone)]
+pub struct TraceDiff {
+    /// Path of the earlier run
+    pub before: Vec<TraceStep>,
+    /// Path of the later run
+    pub after: Vec<TraceStep>,
+    /// Index of the first step where the paths differ
+    pub divergence: Option<usize>,
+    /// Whether either path was cut short before the runs finished
+    pub truncated: bool,
+}
+
+impl TraceDiff {
+    /// Compare two runs step by step
+    pub fn new(before: &RunTrace, after: &RunTrace) -> Self {
+        let before_path = before.path();
+        let after_path = after.path();
+        let truncated = before.is_truncated() || after.is_truncated();
+
+        let common = before_path
+            .iter()
+            .zip(after_path)
+            .take_while(|(a, b)| a.address == b.address)
+            .count();
+
+        // A run that stopped earlier diverges where it stopped
+        let divergence = if common < before_path.len().max(after_path.len()) {
+            Some(common)
+        } else {
+            None
+        };
+
+        Self {
+            before: before_path.to_vec(),
+            after: after_path.to_vec(),
+            divergence,
+            truncated,
+        }
+    }
+
+    /// The last instruction both runs executed before they split: usually
+    /// the branch that decided the outcome
+    pub fn decisive(&self) -> Option<&TraceStep> {
+        self.divergence
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| self.before.get(index))
+    }
+
+    /// One-line description of where the runs split
+    pub fn summary(&self) -> String {
+        let Some(index) = self.divergence else {
+            let note = if self.truncated {
+                " (as far as recorded)"
+            } else {
+                ""
+            };
+            return format!(
+                "Both runs took the same path: {} instructions{}",
+                self.before.len(),
+                note
+            );
+        };
+
+        let went = |path: &[TraceStep]| match path.get(index) {
+            Some(step) => format!("0x{:08X}", step.address),
+            None => "the end".to_string(),
+        };
+
+        match self.decisive() {
+            Some(step) => format!(
+                "Paths split after step {} at 0x{:08X} ({}): before went to {}, after went to {}",
+                index,
+                step.address,
+                step.text,
+                went(&self.before),
+                went(&self.after)
+            ),
+            None => format!(
+                "Paths differ from the first instruction: before started at {}, after at {}",
+                went(&self.before),
+                went(&self.after)
+            ),
+        }
+    }
+
+    /// Number of rows in the side-by-side view
+    pub fn len(&self) -> usize {
+        self.before.len().max(self.after.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace(addresses: &[u32]) -> RunTrace {
+        let mut trace = RunTrace::new();
+        for &address in addresses {
+            trace.record_step(address, &format!("insn_{:X}", address));
+        }
+        trace
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let before = trace(&[0x1000, 0x1003, 0x1005, 0x1007]);
+        let after = trace(&[0x1000, 0x1003, 0x100A]);
+        let diff = TraceDiff::new(&before, &after);
+
+        assert_eq!(diff.divergence, Some(2));
+        assert_eq!(diff.decisive().unwrap().address, 0x1003);
+        assert_eq!(diff.len(), 4);
+        assert!(diff
+            .summary()
+            .contains("before went to 0x00001005, after went to 0x0000100A"));
+    }
+
+    #[test]
+    fn test_identical_and_prefix_paths() {
+        let full = trace(&[0x1000, 0x1001, 0x1002]);
+        let same = TraceDiff::new(&full, &full);
+        assert_eq!(same.divergence, None);
+        assert!(same
+            .summary()
+            .starts_with("Both runs took the same path: 3"));
+
+        // Stopping early (a breakpoint, say) is a divergence
+        let shorter = TraceDiff::new(&full, &trace(&[0x1000, 0x1001]));
+        assert_eq!(shorter.divergence, Some(2));
+        assert!(shorter.summary().ends_with("after went to the end"));
+    }
+}
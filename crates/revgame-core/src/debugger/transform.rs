@@ -0,0 +1,167 @@
+/// A reversible-looking byte transformation used to hide strings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transform {
+    /// XOR with a repeating key
+    Xor(Vec<u8>),
+    /// Rotate letters by 13 places
+    Rot13,
+    /// Decode base64 text
+    Base64,
+    /// Add a constant to every byte (wrapping)
+    Add(u8),
+    /// Subtract a constant from every byte (wrapping)
+    Sub(u8),
+}
+
+impl Transform {
+    /// Transformation names accepted by `parse`
+    pub const NAMES: [&'static str; 5] = ["xor", "rot13", "base64", "add", "sub"];
+
+    /// Parse "xor 5A", "xor 0x13 0x37", "rot13", "base64", "add 3" or "sub 0x20".
+    /// Key bytes are hex, with or without a 0x prefix.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parts = text.split_whitespace();
+        let name = parts
+            .next()
+            .ok_or("Enter a transformation: xor <key>, rot13, base64, add <n> or sub <n>")?;
+        let bytes = parts.map(parse_byte).collect::<Result<Vec<u8>, String>>()?;
+        let single = || match bytes.as_slice() {
+            [value] => Ok(*value),
+            _ => Err(format!("{} takes one byte, e.g. {} 0x20", name, name)),
+        };
+
+        match name.to_lowercase().as_str() {
+            "xor" if bytes.is_empty() => {
+                Err("xor needs a key, e.g. xor 5A or xor 13 37".to_string())
+            }
+            "xor" => Ok(Transform::Xor(bytes)),
+            "rot13" => Ok(Transform::Rot13),
+            "base64" | "b64" => Ok(Transform::Base64),
+            "add" => Ok(Transform::Add(single()?)),
+            "sub" => Ok(Transform::Sub(single()?)),
+            other => Err(format!(
+                "Unknown transformation: {} (one of {})",
+                other,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+
+    /// Apply the transformation. Only base64 can fail, on invalid input.
+    pub fn apply(&self, bytes: &[u8]) -> Result<Vec<u8>, String> {
+        Ok(match self {
+            Transform::Xor(key) => bytes
+                .iter()
+                .zip(key.iter().cycle())
+                .map(|(byte, key)| byte ^ key)
+                .collect(),
+            Transform::Rot13 => bytes.iter().map(|&byte| rot13(byte)).collect(),
+            Transform::Base64 => decode_base64(bytes)?,
+            Transform::Add(n) => bytes.iter().map(|byte| byte.wrapping_add(*n)).collect(),
+            Transform::Sub(n) => bytes.iter().map(|byte| byte.wrapping_sub(*n)).collect(),
+        })
+    }
+
+    /// What the transformation does, for the overlay title
+    pub fn describe(&self) -> String {
+        match self {
+            Transform::Xor(key) => format!(
+                "XOR with key {}",
+                key.iter()
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Transform::Rot13 => "ROT13 (letters rotated by 13)".to_string(),
+            Transform::Base64 => "Base64 decode".to_string(),
+            Transform::Add(n) => format!("Add 0x{:02X} to each byte", n),
+            Transform::Sub(n) => format!("Subtract 0x{:02X} from each byte", n),
+        }
+    }
+}
+
+fn parse_byte(text: &str) -> Result<u8, String> {
+    let digits = text.trim_start_matches("0x").trim_start_matches("0X");
+    u8::from_str_radix(digits, 16).map_err(|_| format!("Not a hex byte: {}", text))
+}
+
+fn rot13(byte: u8) -> u8 {
+    match byte {
+        b'a'..=b'z' => (byte - b'a' + 13) % 26 + b'a',
+        b'A'..=b'Z' => (byte - b'A' + 13) % 26 + b'A',
+        _ => byte,
+    }
+}
+
+/// Decode standard base64, stopping at padding or a NUL terminator
+fn decode_base64(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let value = |c: u8| -> Result<u32, String> {
+        Ok(match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(format!("Not base64: byte 0x{:02X}", c)),
+        } as u32)
+    };
+
+    let text: Vec<u8> = bytes
+        .iter()
+        .copied()
+        .take_while(|&c| c != b'=' && c != 0)
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    if text.len() % 4 == 1 {
+        return Err("Base64 text is one character too long or short".to_string());
+    }
+
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.chunks(4) {
+        let mut bits = 0u32;
+        for &c in chunk {
+            bits = (bits << 6) | value(c)?;
+        }
+        bits <<= 6 * (4 - chunk.len() as u32);
+        out.extend_from_slice(&bits.to_be_bytes()[1..chunk.len()]);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transforms() {
+        let xor = Transform::parse("xor 0x13 37").unwrap();
+        assert_eq!(xor, Transform::Xor(vec![0x13, 0x37]));
+        assert_eq!(xor.apply(b"AAA").unwrap(), [0x52, 0x76, 0x52]);
+
+        assert_eq!(
+            Transform::Rot13.apply(b"Uryyb, jbeyq!").unwrap(),
+            b"Hello, world!"
+        );
+        assert_eq!(
+            Transform::parse("sub 1").unwrap().apply(b"IBM").unwrap(),
+            b"HAL"
+        );
+        assert_eq!(Transform::parse("add FF").unwrap(), Transform::Add(0xFF));
+
+        assert!(Transform::parse("").is_err());
+        assert!(Transform::parse("xor").is_err());
+        assert!(Transform::parse("add 1 2").is_err());
+        assert!(Transform::parse("xor zz").is_err());
+        assert!(Transform::parse("rot47").is_err());
+    }
+
+    #[test]
+    fn test_base64() {
+        let base64 = Transform::parse("base64").unwrap();
+        assert_eq!(base64.apply(b"SGVsbG8=").unwrap(), b"Hello");
+        assert_eq!(base64.apply(b"Zmxh\0garbage").unwrap(), b"fla");
+        assert_eq!(base64.apply(b"SGk").unwrap(), b"Hi");
+        assert!(base64.apply(b"S").is_err());
+        assert!(base64.apply(b"S$==").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use crate::emulator::{CpuState, Flag, Memory, Register};
+
+/// A value the debugger keeps an eye on while the program runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Watch {
+    /// A register
+    Register(Register),
+    /// A range of memory
+    Memory { address: u32, length: u32 },
+}
+
+impl Watch {
+    /// Current value, as bytes (registers are little-endian)
+    pub fn read(&self, cpu: &CpuState, memory: &Memory) -> Vec<u8> {
+        match *self {
+            Watch::Register(register) => cpu.get_register(register).to_le_bytes().to_vec(),
+            Watch::Memory { address, length } => {
+                memory.slice(address, length as usize).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Short name, e.g. "EAX" or "memory at 0x00002000"
+    pub fn name(&self) -> String {
+        match *self {
+            Watch::Register(register) => register.name().to_string(),
+            Watch::Memory { address, .. } => format!("memory at 0x{:08X}", address),
+        }
+    }
+}
+
+/// A watched value changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchHit {
+    /// What changed
+    pub watch: Watch,
+    /// Address of the instruction that changed it
+    pub eip: u32,
+    /// Value before the instruction
+    pub old: Vec<u8>,
+    /// Value after the instruction
+    pub new: Vec<u8>,
+}
+
+impl WatchHit {
+    /// Plain sentence, e.g. "EAX just became 0x00000001 (was 0x00001337) at 0x0000100A"
+    pub fn describe(&self) -> String {
+        let show = |bytes: &[u8]| match self.watch {
+            Watch::Register(_) => {
+                let mut value = [0; 4];
+                value.copy_from_slice(&bytes[..4]);
+                format!("0x{:08X}", u32::from_le_bytes(value))
+            }
+            Watch::Memory { .. } => bytes
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+
+        format!(
+            "{} just became {} (was {}) at 0x{:08X}",
+            self.watch.name(),
+            show(&self.new),
+            show(&self.old),
+            self.eip
+        )
+    }
+}
+
+/// A flag being broken on changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagHit {
+    pub flag: Flag,
+    /// Address of the instruction that changed it
+    pub eip: u32,
+    /// The instruction that changed it, as disassembled
+    pub instruction: String,
+    /// Whether the flag is now set
+    pub set: bool,
+}
+
+impl FlagHit {
+    /// Which operation changed the flag and why, e.g. "ZF set by cmp eax, ebx
+    /// at 0x00001004: the operands were equal"
+    pub fn describe(&self) -> String {
+        let mnemonic = self
+            .instruction
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_uppercase();
+        format!(
+            "{} {} by {} at 0x{:08X}: {}",
+            self.flag.name(),
+            if self.set { "set" } else { "cleared" },
+            self.instruction,
+            self.eip,
+            flag_reason(self.flag, &mnemonic, self.set)
+        )
+    }
+}
+
+/// Why an instruction leaves a flag set or clear
+fn flag_reason(flag: Flag, mnemonic: &str, set: bool) -> &'static str {
+    let logic = matches!(mnemonic, "AND" | "OR" | "XOR" | "TEST");
+    let shift = matches!(
+        mnemonic,
+        "SHL" | "SAL" | "SHR" | "SAR" | "ROL" | "ROR" | "RCL" | "RCR"
+    );
+    match (flag, set) {
+        (Flag::Zf, true) if mnemonic == "CMP" => "the operands were equal",
+        (Flag::Zf, false) if mnemonic == "CMP" => "the operands were not equal",
+        (Flag::Zf, true) if mnemonic == "TEST" => "the operands have no set bits in common",
+        (Flag::Zf, false) if mnemonic == "TEST" => "the operands share a set bit",
+        (Flag::Zf, true) => "the result was zero",
+        (Flag::Zf, false) => "the result was not zero",
+        (Flag::Cf, false) if logic => "logic instructions always clear CF",
+        (Flag::Cf, true) if shift => "the last bit shifted out was 1",
+        (Flag::Cf, false) if shift => "the last bit shifted out was 0",
+        (Flag::Cf, true) if matches!(mnemonic, "CMP" | "SUB" | "SBB") => {
+            "the first operand was below the second as unsigned numbers, so the subtraction borrowed"
+        }
+        (Flag::Cf, false) if matches!(mnemonic, "CMP" | "SUB" | "SBB") => {
+            "the first operand was not below the second as unsigned numbers, so nothing was borrowed"
+        }
+        (Flag::Cf, true) => "the unsigned result didn't fit: it carried out of the top bit",
+        (Flag::Cf, false) => "the unsigned result fit, with no carry out of the top bit",
+        (Flag::Sf, true) => "the result's top bit is 1, so it's negative as a signed number",
+        (Flag::Sf, false) => "the result's top bit is 0, so it's not negative as a signed number",
+        (Flag::Of, false) if logic => "logic instructions always clear OF",
+        (Flag::Of, true) => "the signed result overflowed: it doesn't fit the signed range",
+        (Flag::Of, false) => "the signed result fit the signed range",
+        (Flag::Pf, true) => "the result's low byte has an even number of 1 bits",
+        (Flag::Pf, false) => "the result's low byte has an odd number of 1 bits",
+        (Flag::Af, true) => "a carry or borrow crossed bit 3 (used by BCD arithmetic)",
+        (Flag::Af, false) => "no carry or borrow crossed bit 3",
+        (Flag::Df, true) => "string instructions now step backwards through memory",
+        (Flag::Df, false) => "string instructions now step forwards through memory",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_and_describe() {
+        let mut cpu = CpuState::default();
+        let mut memory = Memory::new(0x1000);
+        cpu.regs.eax = 1;
+        memory.load(0x100, &[0xAA, 0xBB]).unwrap();
+
+        let eax = Watch::Register(Register::Eax);
+        let buffer = Watch::Memory {
+            address: 0x100,
+            length: 2,
+        };
+        assert_eq!(eax.read(&cpu, &memory), vec![1, 0, 0, 0]);
+        assert_eq!(buffer.read(&cpu, &memory), vec![0xAA, 0xBB]);
+
+        let hit = WatchHit {
+            watch: eax,
+            eip: 0x100A,
+            old: 0x1337u32.to_le_bytes().to_vec(),
+            new: vec![1, 0, 0, 0],
+        };
+        assert_eq!(
+            hit.describe(),
+            "EAX just became 0x00000001 (was 0x00001337) at 0x0000100A"
+        );
+
+        let hit = WatchHit {
+            watch: buffer,
+            eip: 0x1000,
+            old: vec![0, 0],
+            new: vec![0xAA, 0xBB],
+        };
+        assert_eq!(
+            hit.describe(),
+            "memory at 0x00000100 just became AA BB (was 00 00) at 0x00001000"
+        );
+    }
+
+    #[test]
+    fn test_flag_hit_explains_operation() {
+        let hit = FlagHit {
+            flag: Flag::Zf,
+            eip: 0x1004,
+            instruction: "cmp eax, ebx".to_string(),
+            set: true,
+        };
+        assert_eq!(
+            hit.describe(),
+            "ZF set by cmp eax, ebx at 0x00001004: the operands were equal"
+        );
+
+        let hit = FlagHit {
+            flag: Flag::Cf,
+            eip: 0x1000,
+            instruction: "xor eax, eax".to_string(),
+            set: false,
+        };
+        assert!(hit
+            .describe()
+            .ends_with("logic instructions always clear CF"));
+    }
+}
@@ -0,0 +1,564 @@
+//! A small Intel-syntax assembler for 32-bit snippets, built on the iced
+//! encoder. Each line is `[label:] mnemonic op, op ; comment`, where an
+//! operand is a register, a number (`10`, `0x2A`, `2Ah`, `'A'`), a label,
+//! or memory such as `dword [ebx + ecx*4 + 8]`. Of the encodings that fit,
+//! the shortest is kept, so `add eax, 10` comes out as `83 C0 0A`.
+
+use std::collections::HashMap;
+
+use iced_x86::{Code, Encoder, Instruction, MemoryOperand, Mnemonic, OpKind, Register};
+use thiserror::Error;
+
+/// Why a snippet couldn't be assembled
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AsmError {
+    #[error("Line {line}: {message}")]
+    Syntax { line: usize, message: String },
+
+    #[error("Line {line}: no label `{label}`")]
+    UnknownLabel { line: usize, label: String },
+}
+
+/// Passes allowed for jumps to settle on their final sizes
+const MAX_PASSES: usize = 16;
+
+const REGISTERS: [(&str, Register); 24] = [
+    ("eax", Register::EAX),
+    ("ecx", Register::ECX),
+    ("edx", Register::EDX),
+    ("ebx", Register::EBX),
+    ("esp", Register::ESP),
+    ("ebp", Register::EBP),
+    ("esi", Register::ESI),
+    ("edi", Register::EDI),
+    ("ax", Register::AX),
+    ("cx", Register::CX),
+    ("dx", Register::DX),
+    ("bx", Register::BX),
+    ("sp", Register::SP),
+    ("bp", Register::BP),
+    ("si", Register::SI),
+    ("di", Register::DI),
+    ("al", Register::AL),
+    ("cl", Register::CL),
+    ("dl", Register::DL),
+    ("bl", Register::BL),
+    ("ah", Register::AH),
+    ("ch", Register::CH),
+    ("dh", Register::DH),
+    ("bh", Register::BH),
+];
+
+/// Other names for the same mnemonic
+const ALIASES: [(&str, &str); 16] = [
+    ("jz", "je"),
+    ("jnz", "jne"),
+    ("jc", "jb"),
+    ("jnae", "jb"),
+    ("jnc", "jae"),
+    ("jnb", "jae"),
+    ("jna", "jbe"),
+    ("jnbe", "ja"),
+    ("jnge", "jl"),
+    ("jnl", "jge"),
+    ("jng", "jle"),
+    ("jnle", "jg"),
+    ("sal", "shl"),
+    ("loopz", "loope"),
+    ("loopnz", "loopne"),
+    ("retn", "ret"),
+];
+
+/// Assemble `source` to run at `origin`
+pub fn assemble(source: &str, origin: u32) -> Result<Vec<u8>, AsmError> {
+    let lines = parse(source)?;
+
+    // Every label a line refers to must be defined somewhere
+    let defined: Vec<&str> = lines
+        .iter()
+        .flat_map(|line| line.labels.iter().map(String::as_str))
+        .collect();
+    for line in &lines {
+        if let Some(label) = line
+            .operands
+            .iter()
+            .find_map(|op| op.label().filter(|l| !defined.contains(l)))
+        {
+            return Err(AsmError::UnknownLabel {
+                line: line.number,
+                label: label.to_string(),
+            });
+        }
+    }
+
+    // Forward jumps start out assuming the shortest distance and grow
+    // until every label stays put
+    let mut labels: HashMap<&str, u32> = defined.iter().map(|&label| (label, origin)).collect();
+    for _ in 0..MAX_PASSES {
+        let mut code = Vec::new();
+        let mut moved = false;
+        for line in &lines {
+            let address = origin.wrapping_add(code.len() as u32);
+            for label in &line.labels {
+                moved |= labels.insert(label, address) != Some(address);
+            }
+            if let Some(mnemonic) = line.mnemonic {
+                code.extend(encode(line, mnemonic, address, &labels)?);
+            }
+        }
+        if !moved {
+            return Ok(code);
+        }
+    }
+    Err(AsmError::Syntax {
+        line: 0,
+        message: "jumps never settled on their sizes".to_string(),
+    })
+}
+
+/// One source line, parsed
+struct Line {
+    number: usize,
+    labels: Vec<String>,
+    mnemonic: Option<Mnemonic>,
+    operands: Vec<Operand>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Register(Register),
+    Immediate(i64),
+    Label(String),
+    Memory(Memory),
+}
+
+impl Operand {
+    fn label(&self) -> Option<&str> {
+        match self {
+            Operand::Label(label) => Some(label),
+            Operand::Memory(memory) => memory.label.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Memory {
+    /// Operand size in bytes when `byte`, `word` or `dword` says so
+    size: Option<usize>,
+    base: Register,
+    index: Register,
+    scale: u32,
+    displacement: i64,
+    /// Label whose address adds to the displacement
+    label: Option<String>,
+}
+
+/// An operand with labels replaced by their addresses
+enum Resolved {
+    Register(Register),
+    Immediate(i64),
+    Memory(MemoryOperand),
+}
+
+fn parse(source: &str) -> Result<Vec<Line>, AsmError> {
+    let mut lines = Vec::new();
+    let mut labels = Vec::new();
+    for (i, text) in source.lines().enumerate() {
+        let number = i + 1;
+        let error = |message: String| AsmError::Syntax {
+            line: number,
+            message,
+        };
+        let mut text = text.split(';').next().unwrap_or_default().trim();
+
+        while let Some((label, rest)) = text
+            .split_once(':')
+            .filter(|(label, _)| is_identifier(label.trim()))
+        {
+            labels.push(label.trim().to_lowercase());
+            text = rest.trim();
+        }
+        // `...` stands for code left out of an example
+        if text.is_empty() || text == "..." {
+            continue;
+        }
+
+        let (name, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let mnemonic =
+            find_mnemonic(name).ok_or_else(|| error(format!("unknown instruction `{}`", name)))?;
+        let operands = if rest.trim().is_empty() {
+            Vec::new()
+        } else {
+            rest.split(',')
+                .map(|op| parse_operand(op.trim()).map_err(&error))
+                .collect::<Result<_, _>>()?
+        };
+        lines.push(Line {
+            number,
+            labels: std::mem::take(&mut labels),
+            mnemonic: Some(mnemonic),
+            operands,
+        });
+    }
+    // Labels at the very end mark the address after the last instruction
+    if !labels.is_empty() {
+        lines.push(Line {
+            number: source.lines().count(),
+            labels,
+            mnemonic: None,
+            operands: Vec::new(),
+        });
+    }
+    Ok(lines)
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn find_mnemonic(name: &str) -> Option<Mnemonic> {
+    let name = name.to_lowercase();
+    let name = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or(name.as_str(), |(_, to)| to);
+    Mnemonic::values().find(|mnemonic| format!("{:?}", mnemonic).eq_ignore_ascii_case(name))
+}
+
+fn find_register(name: &str) -> Option<Register> {
+    REGISTERS
+        .iter()
+        .find(|(reg, _)| reg.eq_ignore_ascii_case(name))
+        .map(|&(_, reg)| reg)
+}
+
+fn parse_number(text: &str) -> Option<i64> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest.trim()),
+        None => (false, text),
+    };
+    let lower = text.to_lowercase();
+    let value = if let Some(hex) = lower.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if let Some(hex) = lower
+        .strip_suffix('h')
+        .filter(|hex| hex.starts_with(|c: char| c.is_ascii_digit()))
+    {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if let Some(c) = text
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+    {
+        let mut chars = c.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c as i64,
+            _ => return None,
+        }
+    } else {
+        lower.parse().ok()?
+    };
+    Some(if negative { -value } else { value })
+}
+
+fn parse_operand(text: &str) -> Result<Operand, String> {
+    let lower = text.to_lowercase();
+    let (size, rest) = [("byte", 1), ("word", 2), ("dword", 4)]
+        .iter()
+        .find_map(|&(keyword, size)| {
+            let rest = lower.strip_prefix(keyword)?;
+            rest.starts_with(|c: char| c.is_whitespace() || c == '[')
+                .then_some((Some(size), rest.trim_start()))
+        })
+        .unwrap_or((None, lower.as_str()));
+    let rest = rest.strip_prefix("ptr").map_or(rest, str::trim_start);
+
+    if let Some(inner) = rest
+        .strip_prefix('[')
+        .and_then(|inner| inner.strip_suffix(']'))
+    {
+        return parse_memory(inner, size).map(Operand::Memory);
+    }
+    if size.is_some() {
+        return Err(format!("`{}` needs a memory operand like [ebx]", text));
+    }
+    if let Some(reg) = find_register(rest) {
+        return Ok(Operand::Register(reg));
+    }
+    if let Some(value) = parse_number(text) {
+        return Ok(Operand::Immediate(value));
+    }
+    if is_identifier(rest) {
+        return Ok(Operand::Label(rest.to_string()));
+    }
+    Err(format!("can't read operand `{}`", text))
+}
+
+/// `base + index*scale + displacement`, in any order, with `-` before numbers
+fn parse_memory(text: &str, size: Option<usize>) -> Result<Memory, String> {
+    let mut memory = Memory {
+        size,
+        base: Register::None,
+        index: Register::None,
+        scale: 1,
+        displacement: 0,
+        label: None,
+    };
+    let spaced = text.replace('-', "+-");
+    for term in spaced
+        .split('+')
+        .map(str::trim)
+        .filter(|term| !term.is_empty())
+    {
+        let (term, negative) = match term.strip_prefix('-') {
+            Some(rest) => (rest.trim(), true),
+            None => (term, false),
+        };
+        if let Some((reg, scale)) = term.split_once('*') {
+            let reg = find_register(reg.trim())
+                .ok_or_else(|| format!("`{}` isn't a register", reg.trim()))?;
+            let scale = parse_number(scale.trim()).filter(|s| [1, 2, 4, 8].contains(s));
+            let scale = scale.ok_or_else(|| format!("scale in `{}` must be 1, 2, 4 or 8", term))?;
+            if negative || memory.index != Register::None {
+                return Err(format!("can't use `{}` as the index", term));
+            }
+            (memory.index, memory.scale) = (reg, scale as u32);
+        } else if let Some(reg) = find_register(term) {
+            if negative {
+                return Err(format!("can't subtract register `{}`", term));
+            }
+            if memory.base == Register::None {
+                memory.base = reg;
+            } else if memory.index == Register::None {
+                memory.index = reg;
+            } else {
+                return Err(format!("too many registers in `[{}]`", text));
+            }
+        } else if let Some(value) = parse_number(term) {
+            memory.displacement += if negative { -value } else { value };
+        } else if is_identifier(term) && !negative && memory.label.is_none() {
+            memory.label = Some(term.to_string());
+        } else {
+            return Err(format!("can't read `{}` in `[{}]`", term, text));
+        }
+    }
+    Ok(memory)
+}
+
+fn resolve(operand: &Operand, labels: &HashMap<&str, u32>) -> Resolved {
+    match operand {
+        Operand::Register(reg) => Resolved::Register(*reg),
+        Operand::Immediate(value) => Resolved::Immediate(*value),
+        Operand::Label(label) => Resolved::Immediate(labels[label.as_str()] as i64),
+        Operand::Memory(memory) => {
+            let label = memory
+                .label
+                .as_deref()
+                .map_or(0, |label| labels[label] as i64);
+            let displacement = (memory.displacement + label) as u32 as i64;
+            // An absolute address always takes a 32-bit displacement
+            let displ_size = match (memory.base, memory.index, displacement) {
+                (Register::None, _, _) => 4,
+                (_, _, 0) => 0,
+                _ => 1,
+            };
+            Resolved::Memory(MemoryOperand::with_base_index_scale_displ_size(
+                memory.base,
+                memory.index,
+                memory.scale,
+                displacement,
+                displ_size,
+            ))
+        }
+    }
+}
+
+/// Pass `$value` to `$body` as an `i32` when negative, else as a `u32`
+macro_rules! with_imm {
+    ($value:expr, $imm:ident => $body:expr) => {
+        if $value < 0 {
+            let $imm = $value as i32;
+            $body
+        } else {
+            let $imm = $value as u32;
+            $body
+        }
+    };
+}
+
+/// Build `code` with `operands`, failing if they don't fit its forms
+fn build(code: Code, operands: &[Resolved]) -> Option<Instruction> {
+    use Resolved::*;
+    let mut shape = Instruction::default();
+    shape.set_code(code);
+    if shape.op_count() as usize != operands.len() {
+        return None;
+    }
+    let instr = match *operands {
+        [] => Ok(Instruction::with(code)),
+        [Register(a)] => Instruction::with1(code, a),
+        [Memory(m)] => Instruction::with1(code, m),
+        [Immediate(i)] => Instruction::with_branch(code, i as u32 as u64)
+            .or_else(|_| with_imm!(i, v => Instruction::with1(code, v))),
+        [Register(a), Register(b)] => Instruction::with2(code, a, b),
+        [Register(a), Memory(m)] => Instruction::with2(code, a, m),
+        [Memory(m), Register(b)] => Instruction::with2(code, m, b),
+        [Register(a), Immediate(i)] => with_imm!(i, v => Instruction::with2(code, a, v)),
+        [Memory(m), Immediate(i)] => with_imm!(i, v => Instruction::with2(code, m, v)),
+        [Register(a), Register(b), Immediate(i)] => {
+            with_imm!(i, v => Instruction::with3(code, a, b, v))
+        }
+        [Register(a), Memory(m), Immediate(i)] => {
+            with_imm!(i, v => Instruction::with3(code, a, m, v))
+        }
+        _ => return None,
+    };
+    instr.ok()
+}
+
+fn encode(
+    line: &Line,
+    mnemonic: Mnemonic,
+    address: u32,
+    labels: &HashMap<&str, u32>,
+) -> Result<Vec<u8>, AsmError> {
+    let error = |message: String| AsmError::Syntax {
+        line: line.number,
+        message,
+    };
+    let operands: Vec<Resolved> = line.operands.iter().map(|op| resolve(op, labels)).collect();
+    if let Some(Resolved::Immediate(value)) = operands
+        .iter()
+        .find(|op| matches!(op, Resolved::Immediate(v) if !(-(1i64 << 31)..1 << 32).contains(v)))
+    {
+        return Err(error(format!("{} doesn't fit in 32 bits", value)));
+    }
+
+    // Memory without a register beside it is a dword unless it says otherwise
+    let has_register = line
+        .operands
+        .iter()
+        .any(|op| matches!(op, Operand::Register(_)));
+    let size = line.operands.iter().find_map(|op| match op {
+        Operand::Memory(memory) => memory.size.or((!has_register).then_some(4)),
+        _ => None,
+    });
+
+    let mut best: Option<Vec<u8>> = None;
+    for code in Code::values().filter(|code| code.mnemonic() == mnemonic) {
+        let Some(instr) = build(code, &operands) else {
+            continue;
+        };
+        // A 16-bit branch would cut EIP down to IP
+        if size.is_some_and(|size| instr.memory_size().size() != size)
+            || instr.op0_kind() == OpKind::NearBranch16
+        {
+            continue;
+        }
+        let mut encoder = Encoder::new(32);
+        if encoder.encode(&instr, address as u64).is_ok() {
+            let bytes = encoder.take_buffer();
+            if best.as_ref().is_none_or(|best| bytes.len() < best.len()) {
+                best = Some(bytes);
+            }
+        }
+    }
+    best.ok_or_else(|| {
+        error(format!("no encoding of `{:?}` takes these operands", mnemonic).to_lowercase())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asm(source: &str) -> Vec<u8> {
+        assemble(source, 0x1000).unwrap()
+    }
+
+    #[test]
+    fn test_picks_short_encodings() {
+        assert_eq!(asm("mov eax, 1"), [0xB8, 0x01, 0x00, 0x00, 0x00]);
+        assert_eq!(asm("add eax, 10"), [0x83, 0xC0, 0x0A]);
+        assert_eq!(asm("cmp al, 0x33"), [0x3C, 0x33]);
+        assert_eq!(asm("xor eax, eax"), [0x31, 0xC0]);
+        assert_eq!(asm("push 0x42"), [0x6A, 0x42]);
+        assert_eq!(asm("INC EAX ; comment"), [0x40]);
+        assert_eq!(asm("ret"), [0xC3]);
+        assert_eq!(asm("ret 8"), [0xC2, 0x08, 0x00]);
+        assert_eq!(asm("int 0x80"), [0xCD, 0x80]);
+        assert_eq!(asm("pushad\npopad\nhlt"), [0x60, 0x61, 0xF4]);
+        assert_eq!(asm("imul eax, ebx"), [0x0F, 0xAF, 0xC3]);
+    }
+
+    #[test]
+    fn test_memory_operands() {
+        assert_eq!(asm("mov [0x1000], eax"), [0xA3, 0x00, 0x10, 0x00, 0x00]);
+        assert_eq!(asm("movzx edx, byte [ebx]"), [0x0F, 0xB6, 0x13]);
+        assert_eq!(asm("mov eax, [ebx + ecx*4 + 8]"), [0x8B, 0x44, 0x8B, 0x08]);
+        assert_eq!(
+            asm("mov dword ptr [ebp-4], 'A'"),
+            [0xC7, 0x45, 0xFC, 0x41, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            asm("mov byte [0x2000], 5"),
+            [0xC6, 0x05, 0x00, 0x20, 0x00, 0x00, 0x05]
+        );
+    }
+
+    #[test]
+    fn test_labels_and_jumps() {
+        // The same loop array-001 ships as bytes
+        let code = asm(
+            "xor eax, eax\nnext: movzx edx, byte [ebx]\nadd eax, edx\ninc ebx\nloop next\n\
+             cmp eax, 100\nje success\nmov eax, 0\njmp end\nsuccess: mov eax, 1\nend: hlt",
+        );
+        let bytes =
+            "31 C0 0F B6 13 01 D0 43 E2 F8 83 F8 64 74 07 B8 00 00 00 00 EB 05 B8 01 00 00 00 F4";
+        assert_eq!(
+            code,
+            bytes
+                .split(' ')
+                .map(|b| u8::from_str_radix(b, 16).unwrap())
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(asm("jmp 0x1000"), [0xEB, 0xFE]);
+        assert_eq!(asm("jz done\ndone:"), [0x74, 0x00]);
+        // Too far for a short jump
+        let far = asm(&format!(
+            "jmp far_away\n{}far_away: hlt",
+            "nop\n".repeat(200)
+        ));
+        assert_eq!(far[..5], [0xE9, 0xC8, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        assert_eq!(
+            assemble("nop\njne missing", 0x1000),
+            Err(AsmError::UnknownLabel {
+                line: 2,
+                label: "missing".to_string()
+            })
+        );
+        let cases = [
+            "frobnicate eax",
+            "mov eax, [ebx*3]",
+            "mov eax, 0x100000000",
+            "add eax, ebx, ecx",
+            "mov eax, ?",
+        ];
+        for source in cases {
+            assert!(
+                matches!(
+                    assemble(source, 0x1000),
+                    Err(AsmError::Syntax { line: 1, .. })
+                ),
+                "{}",
+                source
+            );
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use serde::{Deserialize, Serialize};
 use super::Eflags;
+use serde::{Deserialize, Serialize};
 
 /// Represents which register to access
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -18,6 +18,19 @@ pub enum Register {
 }
 
 impl Register {
+    /// All registers in display order
+    pub const ALL: [Register; 9] = [
+        Register::Eax,
+        Register::Ebx,
+        Register::Ecx,
+        Register::Edx,
+        Register::Esi,
+        Register::Edi,
+        Register::Ebp,
+        Register::Esp,
+        Register::Eip,
+    ];
+
     /// Parse register name from string (case-insensitive)
     pub fn from_name(name: &str) -> Option<Self> {
         match name.to_lowercase().as_str() {
@@ -64,7 +77,7 @@ pub struct Registers {
 }
 
 /// Complete CPU state for the emulator
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CpuState {
     /// General-purpose registers
     pub regs: Registers,
@@ -78,29 +91,26 @@ pub struct CpuState {
     /// Whether the CPU has halted
     pub halted: bool,
 
+    /// Time-stamp counter: virtual cycles since the program started,
+    /// read by RDTSC
+    #[serde(default)]
+    pub cycles: u64,
+
     /// Optional fault information
     pub fault: Option<CpuFault>,
 }
 
-impl Default for CpuState {
-    fn default() -> Self {
-        Self {
-            regs: Registers::default(),
-            eip: 0,
-            eflags: Eflags::default(),
-            halted: false,
-            fault: None,
-        }
-    }
-}
-
 impl CpuState {
     /// Create a new CPU state with specified entry point and stack pointer
     pub fn new(entry_point: u32, stack_pointer: u32) -> Self {
-        let mut state = Self::default();
-        state.eip = entry_point;
-        state.regs.esp = stack_pointer;
-        state
+        Self {
+            regs: Registers {
+                esp: stack_pointer,
+                ..Default::default()
+            },
+            eip: entry_point,
+            ..Default::default()
+        }
     }
 
     /// Get the value of a register
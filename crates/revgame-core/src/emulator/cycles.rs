@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use iced_x86::{Instruction, Mnemonic, OpKind};
+
+/// Virtual cycle costs behind RDTSC: a price per instruction (tunable per
+/// mnemonic), plus extra for memory operands and taken branches. Nothing
+/// here depends on the host, so a program's timing is the same every run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleModel {
+    /// Cost of an instruction without its own entry
+    pub base: u32,
+    /// Extra cost for an explicit memory operand
+    pub memory: u32,
+    /// Extra cost when a jump, call or return changes EIP
+    pub taken_branch: u32,
+    costs: HashMap<Mnemonic, u32>,
+}
+
+impl Default for CycleModel {
+    fn default() -> Self {
+        let costs = HashMap::from([
+            (Mnemonic::Imul, 3),
+            (Mnemonic::Mul, 3),
+            (Mnemonic::Div, 20),
+            (Mnemonic::Idiv, 20),
+            (Mnemonic::Call, 2),
+            (Mnemonic::Ret, 2),
+            (Mnemonic::Int, 20),
+            (Mnemonic::Iretd, 20),
+            (Mnemonic::Rdtsc, 20),
+        ]);
+        Self {
+            base: 1,
+            memory: 2,
+            taken_branch: 1,
+            costs,
+        }
+    }
+}
+
+impl CycleModel {
+    /// Set the cost of a mnemonic by name ("mul", "rdtsc", ...)
+    pub fn set_cost(&mut self, mnemonic: &str, cost: u32) -> Result<(), String> {
+        let mnemonic = Mnemonic::values()
+            .find(|m| format!("{:?}", m).eq_ignore_ascii_case(mnemonic))
+            .ok_or_else(|| format!("Unknown instruction: {}", mnemonic))?;
+        self.costs.insert(mnemonic, cost);
+        Ok(())
+    }
+
+    /// Cycles `instr` takes; `taken` when it moved EIP somewhere other than
+    /// the next instruction
+    pub(crate) fn cost(&self, instr: &Instruction, taken: bool) -> u32 {
+        let mut cost = self
+            .costs
+            .get(&instr.mnemonic())
+            .copied()
+            .unwrap_or(self.base);
+        if (0..instr.op_count()).any(|i| instr.op_kind(i) == OpKind::Memory) {
+            cost += self.memory;
+        }
+        if taken {
+            cost += self.taken_branch;
+        }
+        cost
+    }
+}
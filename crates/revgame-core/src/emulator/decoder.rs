@@ -1,4 +1,92 @@
-use iced_x86::{Decoder, DecoderOptions, Formatter, Instruction, IntelFormatter};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use iced_x86::{
+    Decoder, DecoderError, DecoderOptions, Formatter, Instruction, IntelFormatter, Mnemonic, OpKind,
+};
+
+/// Maximum number of bytes shown on one `db` line
+const DATA_LINE_BYTES: usize = 8;
+
+/// What a line of disassembly represents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// A decoded instruction
+    Code,
+    /// Bytes inside a region declared as data
+    Data,
+    /// A byte that doesn't decode to a valid instruction
+    Junk,
+}
+
+/// How the disassembler decides which bytes are instructions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisassemblyMode {
+    /// Decode every byte in order, one instruction after another
+    #[default]
+    Linear,
+    /// Follow control flow from known entry points; unreached bytes are data
+    Recursive,
+}
+
+impl DisassemblyMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DisassemblyMode::Linear => "linear sweep",
+            DisassemblyMode::Recursive => "recursive traversal",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "linear" => Some(DisassemblyMode::Linear),
+            "recursive" => Some(DisassemblyMode::Recursive),
+            _ => None,
+        }
+    }
+
+    /// The other mode
+    pub fn toggled(self) -> Self {
+        match self {
+            DisassemblyMode::Linear => DisassemblyMode::Recursive,
+            DisassemblyMode::Recursive => DisassemblyMode::Linear,
+        }
+    }
+}
+
+/// Where execution can go after an instruction
+enum Flow {
+    /// Falls through to the next instruction
+    Next,
+    /// Falls through, and may also branch or call to the target
+    NextAnd(u32),
+    /// Always jumps to the target
+    Jump(u32),
+    /// Doesn't fall through (RET, HLT, indirect jumps)
+    Stop,
+}
+
+impl Flow {
+    fn of(instruction: &Instruction) -> Self {
+        let target = match instruction.op0_kind() {
+            OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64 => {
+                Some(instruction.near_branch_target() as u32)
+            }
+            _ => None,
+        };
+
+        match (instruction.mnemonic(), target) {
+            (Mnemonic::Jmp, Some(target)) => Flow::Jump(target),
+            (
+                Mnemonic::Jmp | Mnemonic::Ret | Mnemonic::Retf | Mnemonic::Iret | Mnemonic::Iretd,
+                _,
+            )
+            | (Mnemonic::Hlt | Mnemonic::Ud2, _) => Flow::Stop,
+            (_, Some(target)) => Flow::NextAnd(target),
+            (_, None) => Flow::Next,
+        }
+    }
+}
 
 /// A single line of disassembly
 #[derive(Debug, Clone)]
@@ -11,14 +99,47 @@ pub struct DisassemblyLine {
     pub text: String,
     /// Length of the instruction in bytes
     pub length: usize,
-    /// The decoded instruction (for analysis)
+    /// The decoded instruction (for analysis; invalid for `db` lines)
     pub instruction: Instruction,
+    /// Whether this line is an instruction or raw bytes
+    pub kind: LineKind,
+}
+
+impl DisassemblyLine {
+    /// Build a `db` line for raw bytes
+    fn data(address: u32, bytes: &[u8], kind: LineKind) -> Self {
+        let values = bytes
+            .iter()
+            .map(|b| format!("0x{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Self {
+            address,
+            bytes: bytes.to_vec(),
+            text: format!("db {}", values),
+            length: bytes.len(),
+            instruction: Instruction::default(),
+            kind,
+        }
+    }
+
+    /// Whether this line is a decoded instruction
+    pub fn is_code(&self) -> bool {
+        self.kind == LineKind::Code
+    }
+
+    /// Whether this line is a CALL instruction
+    pub fn is_call(&self) -> bool {
+        self.is_code() && self.instruction.mnemonic() == Mnemonic::Call
+    }
 }
 
 /// Disassemble code from memory
 pub struct Disassembler {
     bitness: u32,
     formatter: IntelFormatter,
+    /// Address ranges that hold data rather than code
+    data_regions: Vec<Range<u32>>,
 }
 
 impl Disassembler {
@@ -37,9 +158,34 @@ impl Disassembler {
         formatter.options_mut().set_uppercase_hex(true);
         formatter.options_mut().set_hex_prefix("0x");
         formatter.options_mut().set_hex_suffix("");
-        formatter.options_mut().set_space_after_operand_separator(true);
+        formatter
+            .options_mut()
+            .set_space_after_operand_separator(true);
 
-        Self { bitness, formatter }
+        Self {
+            bitness,
+            formatter,
+            data_regions: Vec::new(),
+        }
+    }
+
+    /// Declare `length` bytes at `start` as data, shown as `db` lines
+    pub fn mark_data(&mut self, start: u32, length: u32) {
+        if length > 0 {
+            self.data_regions.push(start..start.saturating_add(length));
+        }
+    }
+
+    /// Forget all declared data regions
+    pub fn clear_data(&mut self) {
+        self.data_regions.clear();
+    }
+
+    /// The declared data region containing `address`, if any
+    fn data_region(&self, address: u32) -> Option<&Range<u32>> {
+        self.data_regions
+            .iter()
+            .find(|region| region.contains(&address))
     }
 
     /// Disassemble a single instruction at the given address
@@ -48,12 +194,8 @@ impl Disassembler {
             return None;
         }
 
-        let mut decoder = Decoder::with_ip(
-            self.bitness,
-            bytes,
-            address as u64,
-            DecoderOptions::NONE,
-        );
+        let mut decoder =
+            Decoder::with_ip(self.bitness, bytes, address as u64, DecoderOptions::NONE);
 
         if !decoder.can_decode() {
             return None;
@@ -74,11 +216,20 @@ impl Disassembler {
             text,
             length,
             instruction,
+            kind: LineKind::Code,
         })
     }
 
     /// Disassemble multiple instructions starting at address
-    pub fn disassemble(&mut self, bytes: &[u8], start_address: u32, count: usize) -> Vec<DisassemblyLine> {
+    ///
+    /// Declared data regions become `db` lines, and bytes that don't decode
+    /// are emitted one at a time as junk so decoding resumes right after them.
+    pub fn disassemble(
+        &mut self,
+        bytes: &[u8],
+        start_address: u32,
+        count: usize,
+    ) -> Vec<DisassemblyLine> {
         let mut result = Vec::with_capacity(count);
         let mut decoder = Decoder::with_ip(
             self.bitness,
@@ -86,16 +237,50 @@ impl Disassembler {
             start_address as u64,
             DecoderOptions::NONE,
         );
+        let mut offset = 0;
+
+        while offset < bytes.len() && result.len() < count {
+            let address = start_address.wrapping_add(offset as u32);
 
-        while decoder.can_decode() && result.len() < count {
+            if let Some(region) = self.data_region(address) {
+                let length = ((region.end - address) as usize)
+                    .min(DATA_LINE_BYTES)
+                    .min(bytes.len() - offset);
+                result.push(DisassemblyLine::data(
+                    address,
+                    &bytes[offset..offset + length],
+                    LineKind::Data,
+                ));
+                offset += length;
+                continue;
+            }
+
+            decoder.set_ip(address as u64);
+            if decoder.set_position(offset).is_err() {
+                break;
+            }
             let instruction = decoder.decode();
-            if instruction.is_invalid() {
+            if decoder.last_error() == DecoderError::NoMoreBytes {
                 break;
             }
-
-            let address = instruction.ip() as u32;
             let length = instruction.len();
-            let offset = (address - start_address) as usize;
+
+            // Instructions running into a data region are junk as well
+            let end = address.wrapping_add(length as u32);
+            let overlaps_data = self
+                .data_regions
+                .iter()
+                .any(|region| region.start > address && region.start < end);
+
+            if instruction.is_invalid() || overlaps_data {
+                result.push(DisassemblyLine::data(
+                    address,
+                    &bytes[offset..offset + 1],
+                    LineKind::Junk,
+                ));
+                offset += 1;
+                continue;
+            }
 
             let mut text = String::new();
             self.formatter.format(&instruction, &mut text);
@@ -106,7 +291,120 @@ impl Disassembler {
                 text,
                 length,
                 instruction,
+                kind: LineKind::Code,
             });
+            offset += length;
+        }
+
+        result
+    }
+
+    /// Disassemble `bytes` by following control flow from `roots`
+    ///
+    /// Conditional branches and calls continue on both paths; unconditional
+    /// jumps and returns end a path. Bytes no path reaches are shown as `db`
+    /// lines. When paths disagree about where an instruction starts, the
+    /// first one to reach it wins.
+    pub fn disassemble_recursive(
+        &mut self,
+        bytes: &[u8],
+        start_address: u32,
+        roots: &[u32],
+    ) -> Vec<DisassemblyLine> {
+        let in_range = |address: u32| {
+            address >= start_address && ((address - start_address) as usize) < bytes.len()
+        };
+        let mut decoder = Decoder::with_ip(
+            self.bitness,
+            bytes,
+            start_address as u64,
+            DecoderOptions::NONE,
+        );
+        let mut covered = vec![false; bytes.len()];
+        let mut instructions = BTreeMap::new();
+        let mut pending: Vec<u32> = roots
+            .iter()
+            .copied()
+            .filter(|&root| in_range(root))
+            .collect();
+
+        while let Some(mut address) = pending.pop() {
+            while in_range(address) && self.data_region(address).is_none() {
+                let offset = (address - start_address) as usize;
+                if covered[offset] {
+                    break;
+                }
+
+                decoder.set_ip(address as u64);
+                if decoder.set_position(offset).is_err() {
+                    break;
+                }
+                let instruction = decoder.decode();
+                let end = offset + instruction.len();
+                if instruction.is_invalid()
+                    || covered[offset..end].iter().any(|&c| c)
+                    || (offset + 1..end)
+                        .any(|o| self.data_region(start_address + o as u32).is_some())
+                {
+                    break;
+                }
+
+                covered[offset..end].fill(true);
+                instructions.insert(address, instruction);
+
+                match Flow::of(&instruction) {
+                    Flow::Next => {}
+                    Flow::NextAnd(target) => pending.push(target),
+                    Flow::Jump(target) => {
+                        pending.push(target);
+                        break;
+                    }
+                    Flow::Stop => break,
+                }
+                address = instruction.next_ip32();
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let address = start_address + offset as u32;
+
+            if let Some(instruction) = instructions.get(&address) {
+                let length = instruction.len();
+                let mut text = String::new();
+                self.formatter.format(instruction, &mut text);
+                result.push(DisassemblyLine {
+                    address,
+                    bytes: bytes[offset..offset + length].to_vec(),
+                    text,
+                    length,
+                    instruction: *instruction,
+                    kind: LineKind::Code,
+                });
+                offset += length;
+                continue;
+            }
+
+            // Unreached bytes, up to the next instruction or region boundary
+            let region_end = self.data_region(address).map(|region| region.end);
+            let mut length = 1;
+            while length < DATA_LINE_BYTES
+                && offset + length < bytes.len()
+                && !covered[offset + length]
+                && self
+                    .data_region(address + length as u32)
+                    .map(|region| region.end)
+                    == region_end
+            {
+                length += 1;
+            }
+            result.push(DisassemblyLine::data(
+                address,
+                &bytes[offset..offset + length],
+                LineKind::Data,
+            ));
+            offset += length;
         }
 
         result
@@ -114,7 +412,8 @@ impl Disassembler {
 
     /// Format bytes as hex string (e.g., "90 90 CC")
     pub fn format_bytes(bytes: &[u8]) -> String {
-        bytes.iter()
+        bytes
+            .iter()
             .map(|b| format!("{:02X}", b))
             .collect::<Vec<_>>()
             .join(" ")
@@ -168,4 +467,77 @@ mod tests {
         assert_eq!(lines[2].address, 0x1002);
         assert!(lines[2].text.contains("ret"));
     }
+
+    #[test]
+    fn test_junk_bytes_are_skipped() {
+        let mut dis = Disassembler::new();
+        // NOP; (invalid FE /7); CLC; NOP
+        let bytes = [0x90, 0xFE, 0xF8, 0x90];
+        let lines = dis.disassemble(&bytes, 0x1000, 10);
+
+        assert_eq!(lines[0].kind, LineKind::Code);
+        assert_eq!(lines[1].kind, LineKind::Junk);
+        assert_eq!(lines[1].text, "db 0xFE");
+        assert!(lines[2].text.contains("clc"));
+        assert_eq!(lines.last().unwrap().address, 0x1003);
+        assert!(lines.last().unwrap().text.contains("nop"));
+    }
+
+    #[test]
+    fn test_data_regions_render_as_db() {
+        let mut dis = Disassembler::new();
+        // JMP +1; (data E8); MOV EAX, 1
+        let bytes = [0xEB, 0x01, 0xE8, 0xB8, 0x01, 0x00, 0x00, 0x00];
+
+        let linear = dis.disassemble(&bytes, 0x1000, 10);
+        assert!(linear[1].text.starts_with("call"));
+
+        dis.mark_data(0x1002, 1);
+        let lines = dis.disassemble(&bytes, 0x1000, 10);
+        assert_eq!(lines[1].kind, LineKind::Data);
+        assert_eq!(lines[1].text, "db 0xE8");
+        assert_eq!(lines[2].address, 0x1003);
+        assert!(lines[2].text.starts_with("mov eax"));
+    }
+
+    #[test]
+    fn test_recursive_traversal_skips_embedded_data() {
+        let mut dis = Disassembler::new();
+        // JMP +1; (junk E8); CMP ECX, 7; JNE +1; HLT; HLT
+        let bytes = [0xEB, 0x01, 0xE8, 0x83, 0xF9, 0x07, 0x75, 0x01, 0xF4, 0xF4];
+
+        let linear = dis.disassemble(&bytes, 0x1000, 10);
+        assert!(linear[1].text.starts_with("call"));
+
+        let lines = dis.disassemble_recursive(&bytes, 0x1000, &[0x1000]);
+        let texts: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts[1], "db 0xE8");
+        assert!(texts[2].starts_with("cmp ecx"));
+        assert!(texts[3].starts_with("jne"));
+        // Both the fallthrough and the branch target are reached
+        assert_eq!(lines[4].address, 0x1008);
+        assert!(lines[4].is_code());
+        assert_eq!(lines[5].address, 0x1009);
+        assert!(lines[5].is_code());
+    }
+
+    #[test]
+    fn test_recursive_traversal_groups_unreached_bytes() {
+        let mut dis = Disassembler::new();
+        // HLT, then bytes nothing jumps to
+        let bytes = [0xF4, 0x90, 0x90, 0x90];
+        let lines = dis.disassemble_recursive(&bytes, 0x1000, &[0x1000]);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].text, "db 0x90, 0x90, 0x90");
+        assert_eq!(lines[1].kind, LineKind::Data);
+    }
+
+    #[test]
+    fn test_truncated_tail_is_not_junk() {
+        let mut dis = Disassembler::new();
+        // NOP; MOV EAX, imm32 cut short
+        let lines = dis.disassemble(&[0x90, 0xB8, 0x01], 0x1000, 10);
+        assert_eq!(lines.len(), 1);
+    }
 }
@@ -0,0 +1,374 @@
+use iced_x86::{Decoder, DecoderOptions, Instruction, OpKind};
+
+use super::Disassembler;
+
+/// Part of an x86 instruction encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Prefix,
+    Opcode,
+    ModRm,
+    Sib,
+    Displacement,
+    Immediate,
+}
+
+impl FieldKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FieldKind::Prefix => "Prefix",
+            FieldKind::Opcode => "Opcode",
+            FieldKind::ModRm => "ModRM",
+            FieldKind::Sib => "SIB",
+            FieldKind::Displacement => "Displacement",
+            FieldKind::Immediate => "Immediate",
+        }
+    }
+}
+
+/// One field of an encoded instruction and what it means
+#[derive(Debug, Clone)]
+pub struct EncodingField {
+    pub kind: FieldKind,
+    /// Offset of the field within the instruction
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+    pub explanation: String,
+}
+
+/// An instruction split into its encoding fields
+#[derive(Debug, Clone)]
+pub struct InstructionEncoding {
+    pub address: u32,
+    /// Formatted instruction text (Intel syntax)
+    pub text: String,
+    pub bytes: Vec<u8>,
+    pub fields: Vec<EncodingField>,
+}
+
+impl InstructionEncoding {
+    /// Decode the instruction at the start of `bytes` and split it into fields
+    pub fn decode(bytes: &[u8], address: u32) -> Option<Self> {
+        let mut decoder = Decoder::with_ip(32, bytes, address as u64, DecoderOptions::NONE);
+        if !decoder.can_decode() {
+            return None;
+        }
+        let instruction = decoder.decode();
+        if instruction.is_invalid() {
+            return None;
+        }
+        let offsets = decoder.get_constant_offsets(&instruction);
+
+        // Same formatting as the disassembly view
+        let text = Disassembler::new().disassemble_one(bytes, address)?.text;
+
+        let bytes = bytes[..instruction.len()].to_vec();
+        let mut fields = Vec::new();
+
+        // Legacy prefixes
+        let mut pos = 0;
+        while pos < bytes.len() {
+            let Some(name) = prefix_name(bytes[pos]) else {
+                break;
+            };
+            fields.push(field(
+                FieldKind::Prefix,
+                pos,
+                &bytes[pos..pos + 1],
+                name.to_string(),
+            ));
+            pos += 1;
+        }
+
+        // Opcode: one byte, or 0F plus one (or 0F 38/3A plus one)
+        let opcode_len = match (bytes.get(pos), bytes.get(pos + 1)) {
+            (Some(0x0F), Some(0x38 | 0x3A)) => 3,
+            (Some(0x0F), _) => 2,
+            _ => 1,
+        };
+        let opcode_end = (pos + opcode_len).min(bytes.len());
+        fields.push(field(
+            FieldKind::Opcode,
+            pos,
+            &bytes[pos..opcode_end],
+            explain_opcode(&instruction, &bytes[pos..opcode_end]),
+        ));
+
+        // Whatever sits between the opcode and the constants is ModRM (+ SIB)
+        let constants_start = [
+            (offsets.has_displacement(), offsets.displacement_offset()),
+            (offsets.has_immediate(), offsets.immediate_offset()),
+        ]
+        .iter()
+        .filter(|(present, _)| *present)
+        .map(|&(_, offset)| offset)
+        .min()
+        .unwrap_or(bytes.len());
+
+        if opcode_end < constants_start {
+            let modrm = bytes[opcode_end];
+            fields.push(field(
+                FieldKind::ModRm,
+                opcode_end,
+                &bytes[opcode_end..opcode_end + 1],
+                explain_modrm(modrm),
+            ));
+            if opcode_end + 1 < constants_start {
+                let sib = bytes[opcode_end + 1];
+                fields.push(field(
+                    FieldKind::Sib,
+                    opcode_end + 1,
+                    &bytes[opcode_end + 1..opcode_end + 2],
+                    explain_sib(sib),
+                ));
+            }
+        }
+
+        if offsets.has_displacement() {
+            let start = offsets.displacement_offset();
+            let raw = &bytes[start..start + offsets.displacement_size()];
+            fields.push(field(
+                FieldKind::Displacement,
+                start,
+                raw,
+                format!(
+                    "{}-bit address offset {} (little-endian)",
+                    raw.len() * 8,
+                    signed_hex(le_value(raw), raw.len())
+                ),
+            ));
+        }
+
+        for (present, start, size) in [
+            (
+                offsets.has_immediate(),
+                offsets.immediate_offset(),
+                offsets.immediate_size(),
+            ),
+            (
+                offsets.has_immediate2(),
+                offsets.immediate_offset2(),
+                offsets.immediate_size2(),
+            ),
+        ] {
+            if !present {
+                continue;
+            }
+            let raw = &bytes[start..start + size];
+            let explanation = if is_relative_branch(&instruction) {
+                format!(
+                    "{}-bit relative offset {}: target = next instruction 0x{:08X} {} = 0x{:08X}",
+                    size * 8,
+                    signed_hex(le_value(raw), size),
+                    instruction.next_ip32(),
+                    signed_hex(le_value(raw), size),
+                    instruction.near_branch32()
+                )
+            } else {
+                format!(
+                    "{}-bit constant 0x{:X} (little-endian)",
+                    size * 8,
+                    le_value(raw)
+                )
+            };
+            fields.push(field(FieldKind::Immediate, start, raw, explanation));
+        }
+
+        fields.sort_by_key(|f| f.offset);
+
+        Some(Self {
+            address,
+            text,
+            bytes,
+            fields,
+        })
+    }
+
+    /// How to patch this instruction out with NOPs
+    pub fn nop_advice(&self) -> String {
+        let len = self.bytes.len();
+        let nops = vec!["90"; len].join(" ");
+        if len == 1 {
+            "1 byte: one NOP (90) replaces it".to_string()
+        } else {
+            format!(
+                "{} bytes: patching it out takes {} NOPs ({}), or the CPU decodes the leftover bytes as a new instruction",
+                len, len, nops
+            )
+        }
+    }
+}
+
+fn field(kind: FieldKind, offset: usize, bytes: &[u8], explanation: String) -> EncodingField {
+    EncodingField {
+        kind,
+        offset,
+        bytes: bytes.to_vec(),
+        explanation,
+    }
+}
+
+fn prefix_name(byte: u8) -> Option<&'static str> {
+    Some(match byte {
+        0xF0 => "LOCK: make the memory access atomic",
+        0xF2 => "REPNE: repeat string operation while not equal",
+        0xF3 => "REP/REPE: repeat string operation",
+        0x2E => "CS segment override",
+        0x36 => "SS segment override",
+        0x3E => "DS segment override",
+        0x26 => "ES segment override",
+        0x64 => "FS segment override",
+        0x65 => "GS segment override",
+        0x66 => "Operand-size override: 16-bit operands instead of 32",
+        0x67 => "Address-size override: 16-bit addressing",
+        _ => return None,
+    })
+}
+
+/// JMP, Jcc, CALL and LOOP with a target relative to the next instruction
+fn is_relative_branch(instruction: &Instruction) -> bool {
+    matches!(
+        instruction.op0_kind(),
+        OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64
+    )
+}
+
+fn explain_opcode(instruction: &Instruction, opcode: &[u8]) -> String {
+    let mnemonic = format!("{:?}", instruction.mnemonic()).to_uppercase();
+    match opcode {
+        [op @ 0x70..=0x7F] => format!(
+            "{}: short conditional jump (70-7F, low nibble {:X} picks the condition)",
+            mnemonic,
+            op & 0x0F
+        ),
+        [0x0F, op @ 0x80..=0x8F] => format!(
+            "{}: near conditional jump (0F 80-8F, low nibble {:X} picks the condition)",
+            mnemonic,
+            op & 0x0F
+        ),
+        [op @ 0xB8..=0xBF] => format!(
+            "{}: move immediate into a register (B8 + register number {})",
+            mnemonic,
+            op - 0xB8
+        ),
+        [op @ 0x50..=0x57] => format!("{}: register number {} is added to 50", mnemonic, op - 0x50),
+        [op @ 0x58..=0x5F] => format!("{}: register number {} is added to 58", mnemonic, op - 0x58),
+        [0x0F, ..] => format!("{}: two-byte opcode (0F escape)", mnemonic),
+        _ => mnemonic,
+    }
+}
+
+const REGISTERS: [&str; 8] = ["EAX", "ECX", "EDX", "EBX", "ESP", "EBP", "ESI", "EDI"];
+
+fn explain_modrm(modrm: u8) -> String {
+    let mode = modrm >> 6;
+    let reg = (modrm >> 3) & 7;
+    let rm = modrm & 7;
+
+    let rm_text = match (mode, rm) {
+        (3, _) => format!("register {}", REGISTERS[rm as usize]),
+        (0, 5) => "memory at a 32-bit address".to_string(),
+        (_, 4) => "memory addressed by the SIB byte".to_string(),
+        (0, _) => format!("memory at [{}]", REGISTERS[rm as usize]),
+        (1, _) => format!("memory at [{} + disp8]", REGISTERS[rm as usize]),
+        _ => format!("memory at [{} + disp32]", REGISTERS[rm as usize]),
+    };
+
+    format!(
+        "mod={:02b} reg={:03b} rm={:03b}: reg is {} (or /{} opcode extension), rm is {}",
+        mode, reg, rm, REGISTERS[reg as usize], reg, rm_text
+    )
+}
+
+fn explain_sib(sib: u8) -> String {
+    let scale = 1 << (sib >> 6);
+    let index = (sib >> 3) & 7;
+    let base = sib & 7;
+
+    let index_text = if index == 4 {
+        "no index".to_string()
+    } else {
+        format!("index {} * {}", REGISTERS[index as usize], scale)
+    };
+    format!(
+        "scale={} index={:03b} base={:03b}: base {}, {}",
+        scale, index, base, REGISTERS[base as usize], index_text
+    )
+}
+
+fn le_value(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0u64, |acc, &b| (acc << 8) | u64::from(b))
+}
+
+/// Format a little-endian value of `size` bytes as a signed hex number
+fn signed_hex(value: u64, size: usize) -> String {
+    let bits = size * 8;
+    let signed = ((value << (64 - bits)) as i64) >> (64 - bits);
+    if signed < 0 {
+        format!("-0x{:X}", signed.unsigned_abs())
+    } else {
+        format!("+0x{:X}", signed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(encoding: &InstructionEncoding) -> Vec<FieldKind> {
+        encoding.fields.iter().map(|f| f.kind).collect()
+    }
+
+    #[test]
+    fn test_short_jump_fields() {
+        let encoding = InstructionEncoding::decode(&[0x75, 0x07], 0x1005).unwrap();
+        assert_eq!(
+            kinds(&encoding),
+            vec![FieldKind::Opcode, FieldKind::Immediate]
+        );
+        assert!(encoding.fields[1]
+            .explanation
+            .contains("target = next instruction 0x00001007 +0x7 = 0x0000100E"));
+        assert!(encoding.nop_advice().contains("2 NOPs (90 90)"));
+    }
+
+    #[test]
+    fn test_modrm_sib_displacement_immediate() {
+        // 66 C7 44 8B 10 34 12: mov word [ebx+ecx*4+0x10], 0x1234
+        let encoding =
+            InstructionEncoding::decode(&[0x66, 0xC7, 0x44, 0x8B, 0x10, 0x34, 0x12], 0x1000)
+                .unwrap();
+        assert_eq!(
+            kinds(&encoding),
+            vec![
+                FieldKind::Prefix,
+                FieldKind::Opcode,
+                FieldKind::ModRm,
+                FieldKind::Sib,
+                FieldKind::Displacement,
+                FieldKind::Immediate,
+            ]
+        );
+        assert!(encoding.fields[3]
+            .explanation
+            .contains("base EBX, index ECX * 4"));
+        assert!(encoding.fields[4].explanation.contains("+0x10"));
+        assert!(encoding.fields[5].explanation.contains("0x1234"));
+    }
+
+    #[test]
+    fn test_register_form_modrm() {
+        // 01 C8: add eax, ecx
+        let encoding = InstructionEncoding::decode(&[0x01, 0xC8], 0x1000).unwrap();
+        assert_eq!(kinds(&encoding), vec![FieldKind::Opcode, FieldKind::ModRm]);
+        assert!(encoding.fields[1]
+            .explanation
+            .starts_with("mod=11 reg=001 rm=000: reg is ECX"));
+        assert!(encoding.fields[1]
+            .explanation
+            .ends_with("rm is register EAX"));
+    }
+}
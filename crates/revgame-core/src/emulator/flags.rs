@@ -19,6 +19,62 @@ pub struct Eflags {
     pub df: bool,
 }
 
+/// A single status flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Flag {
+    Cf,
+    Zf,
+    Sf,
+    Of,
+    Pf,
+    Af,
+    Df,
+}
+
+impl Flag {
+    pub const ALL: [Flag; 7] = [
+        Flag::Cf,
+        Flag::Zf,
+        Flag::Sf,
+        Flag::Of,
+        Flag::Pf,
+        Flag::Af,
+        Flag::Df,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Flag::Cf => "CF",
+            Flag::Zf => "ZF",
+            Flag::Sf => "SF",
+            Flag::Of => "OF",
+            Flag::Pf => "PF",
+            Flag::Af => "AF",
+            Flag::Df => "DF",
+        }
+    }
+
+    /// Parse a flag name such as "zf" or "ZF"
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|flag| flag.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Whether the flag is set in `eflags`
+    pub fn get(&self, eflags: &Eflags) -> bool {
+        match self {
+            Flag::Cf => eflags.cf,
+            Flag::Zf => eflags.zf,
+            Flag::Sf => eflags.sf,
+            Flag::Of => eflags.of,
+            Flag::Pf => eflags.pf,
+            Flag::Af => eflags.af,
+            Flag::Df => eflags.df,
+        }
+    }
+}
+
 impl Eflags {
     /// Create a new Eflags with all flags cleared
     pub fn new() -> Self {
@@ -61,9 +117,9 @@ impl Eflags {
         self.zf = result == 0;
         self.sf = (result as i32) < 0;
         self.pf = Self::compute_parity(result as u8);
-        self.cf = false;  // Always cleared for logical ops
-        self.of = false;  // Always cleared for logical ops
-        self.af = false;  // Undefined, we clear it
+        self.cf = false; // Always cleared for logical ops
+        self.of = false; // Always cleared for logical ops
+        self.af = false; // Undefined, we clear it
     }
 
     /// Update flags for increment operation
@@ -90,19 +146,33 @@ impl Eflags {
 
     /// Compute parity of the low byte (true if even number of 1 bits)
     fn compute_parity(byte: u8) -> bool {
-        byte.count_ones() % 2 == 0
+        byte.count_ones().is_multiple_of(2)
     }
 
     /// Get flags as a formatted string for display
     pub fn display(&self) -> String {
         let mut flags = Vec::new();
-        if self.cf { flags.push("CF"); }
-        if self.zf { flags.push("ZF"); }
-        if self.sf { flags.push("SF"); }
-        if self.of { flags.push("OF"); }
-        if self.pf { flags.push("PF"); }
-        if self.af { flags.push("AF"); }
-        if self.df { flags.push("DF"); }
+        if self.cf {
+            flags.push("CF");
+        }
+        if self.zf {
+            flags.push("ZF");
+        }
+        if self.sf {
+            flags.push("SF");
+        }
+        if self.of {
+            flags.push("OF");
+        }
+        if self.pf {
+            flags.push("PF");
+        }
+        if self.af {
+            flags.push("AF");
+        }
+        if self.df {
+            flags.push("DF");
+        }
 
         if flags.is_empty() {
             "[ ]".to_string()
@@ -114,15 +184,42 @@ impl Eflags {
     /// Get flags as a compact bit representation
     pub fn as_u32(&self) -> u32 {
         let mut value = 0u32;
-        if self.cf { value |= 1 << 0; }  // bit 0
-        if self.pf { value |= 1 << 2; }  // bit 2
-        if self.af { value |= 1 << 4; }  // bit 4
-        if self.zf { value |= 1 << 6; }  // bit 6
-        if self.sf { value |= 1 << 7; }  // bit 7
-        if self.df { value |= 1 << 10; } // bit 10
-        if self.of { value |= 1 << 11; } // bit 11
+        if self.cf {
+            value |= 1 << 0;
+        } // bit 0
+        if self.pf {
+            value |= 1 << 2;
+        } // bit 2
+        if self.af {
+            value |= 1 << 4;
+        } // bit 4
+        if self.zf {
+            value |= 1 << 6;
+        } // bit 6
+        if self.sf {
+            value |= 1 << 7;
+        } // bit 7
+        if self.df {
+            value |= 1 << 10;
+        } // bit 10
+        if self.of {
+            value |= 1 << 11;
+        } // bit 11
         value
     }
+
+    /// Build flags from the bit representation used by `as_u32`
+    pub fn from_u32(value: u32) -> Self {
+        Self {
+            cf: value & (1 << 0) != 0,
+            pf: value & (1 << 2) != 0,
+            af: value & (1 << 4) != 0,
+            zf: value & (1 << 6) != 0,
+            sf: value & (1 << 7) != 0,
+            df: value & (1 << 10) != 0,
+            of: value & (1 << 11) != 0,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -154,6 +251,18 @@ mod tests {
         assert!(flags.cf); // Borrow occurred
     }
 
+    #[test]
+    fn test_flag_names() {
+        assert_eq!(Flag::from_name("zf"), Some(Flag::Zf));
+        assert_eq!(Flag::from_name("XF"), None);
+        let flags = Eflags::from_u32(1 << 11);
+        let set: Vec<Flag> = Flag::ALL
+            .into_iter()
+            .filter(|flag| flag.get(&flags))
+            .collect();
+        assert_eq!(set, [Flag::Of]);
+    }
+
     #[test]
     fn test_logical_flags() {
         let mut flags = Eflags::new();
@@ -0,0 +1,75 @@
+/// A family of instructions a puzzle can declare it needs, so a puzzle
+/// using something the emulator can't run yet is rejected when it loads
+/// rather than failing partway through a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InstructionGroup {
+    /// MOV, MOVZX, MOVSX, PUSH, POP, XCHG, LEA
+    DataMove,
+    /// ADD, SUB, INC, DEC, NEG, CMP
+    Arithmetic,
+    /// MUL, IMUL
+    Mul,
+    /// DIV, IDIV
+    Div,
+    /// AND, OR, XOR, NOT, TEST
+    Logic,
+    /// SHL, SHR, SAR
+    Shifts,
+    /// ROL, ROR, RCL, RCR
+    Rotates,
+    /// BT, BTS, BTR, BSF, BSR
+    BitOps,
+    /// JMP and the conditional jumps
+    Jumps,
+    /// CALL, RET
+    Calls,
+    /// MOVS, STOS, LODS, CMPS, SCAS and their REP forms
+    StringOps,
+    /// INT, INT3, IRETD
+    Interrupts,
+    /// RDTSC
+    Timing,
+}
+
+impl InstructionGroup {
+    pub const ALL: [InstructionGroup; 13] = [
+        InstructionGroup::DataMove,
+        InstructionGroup::Arithmetic,
+        InstructionGroup::Mul,
+        InstructionGroup::Div,
+        InstructionGroup::Logic,
+        InstructionGroup::Shifts,
+        InstructionGroup::Rotates,
+        InstructionGroup::BitOps,
+        InstructionGroup::Jumps,
+        InstructionGroup::Calls,
+        InstructionGroup::StringOps,
+        InstructionGroup::Interrupts,
+        InstructionGroup::Timing,
+    ];
+
+    /// Name used in puzzle files (`requires = ["div"]`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            InstructionGroup::DataMove => "data_move",
+            InstructionGroup::Arithmetic => "arithmetic",
+            InstructionGroup::Mul => "mul",
+            InstructionGroup::Div => "div",
+            InstructionGroup::Logic => "logic",
+            InstructionGroup::Shifts => "shifts",
+            InstructionGroup::Rotates => "rotates",
+            InstructionGroup::BitOps => "bit_ops",
+            InstructionGroup::Jumps => "jumps",
+            InstructionGroup::Calls => "calls",
+            InstructionGroup::StringOps => "string_ops",
+            InstructionGroup::Interrupts => "interrupts",
+            InstructionGroup::Timing => "timing",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|group| group.name().eq_ignore_ascii_case(name))
+    }
+}
@@ -1,8 +1,11 @@
 use iced_x86::{
-    Decoder, DecoderOptions, Instruction, Mnemonic, OpKind, Register as IcedRegister,
+    Decoder, DecoderOptions, Instruction, MemorySize, Mnemonic, OpKind, Register as IcedRegister,
 };
 
-use super::{CpuState, DisassemblyLine, Disassembler, EmulatorError, Memory, Register};
+use super::{
+    CpuState, CycleModel, Disassembler, DisassemblyLine, Eflags, EmulatorError, InstructionGroup,
+    Memory, Register,
+};
 
 /// Result of executing a single instruction
 #[derive(Debug, Clone)]
@@ -13,14 +16,34 @@ pub enum ExecutionResult {
     Halt,
     /// Hit a breakpoint (INT 3)
     Breakpoint,
-    /// Syscall/interrupt (for future I/O)
-    Interrupt(u8),
+    /// Software interrupt (`INT n`); the debugger decides what it does
+    Interrupt { vector: u8, next_eip: u32 },
+}
+
+/// Maximum length of an x86 instruction in bytes
+pub(crate) const MAX_INSTRUCTION_LEN: usize = 15;
+
+/// Number of slots in the direct-mapped decode cache
+const DECODE_CACHE_SIZE: usize = 256;
+
+/// A decoded instruction together with the bytes it was decoded from
+#[derive(Clone)]
+struct DecodedEntry {
+    eip: u32,
+    bytes: [u8; MAX_INSTRUCTION_LEN],
+    instr: Instruction,
 }
 
 /// The instruction executor
 pub struct Executor {
     bitness: u32,
     disassembler: Disassembler,
+    /// Recently decoded instructions, indexed by address
+    decode_cache: Vec<Option<DecodedEntry>>,
+    /// Cycle costs that advance the time-stamp counter
+    cycle_model: CycleModel,
+    /// Mnemonic of the last instruction executed
+    last_mnemonic: Option<Mnemonic>,
 }
 
 impl Executor {
@@ -29,6 +52,43 @@ impl Executor {
         Self {
             bitness: 32,
             disassembler: Disassembler::new(),
+            decode_cache: vec![None; DECODE_CACHE_SIZE],
+            cycle_model: CycleModel::default(),
+            last_mnemonic: None,
+        }
+    }
+
+    /// Replace the cycle costs RDTSC counts in
+    pub fn set_cycle_model(&mut self, model: CycleModel) {
+        self.cycle_model = model;
+    }
+
+    pub fn cycle_model(&self) -> &CycleModel {
+        &self.cycle_model
+    }
+
+    /// Mnemonic of the last instruction `execute_one` ran
+    pub fn last_mnemonic(&self) -> Option<Mnemonic> {
+        self.last_mnemonic
+    }
+
+    /// Whether `execute_one` handles every instruction in `group`; keep in
+    /// step with the mnemonic match below
+    pub fn supports(group: InstructionGroup) -> bool {
+        match group {
+            InstructionGroup::DataMove
+            | InstructionGroup::Arithmetic
+            | InstructionGroup::Mul
+            | InstructionGroup::Logic
+            | InstructionGroup::Shifts
+            | InstructionGroup::Jumps
+            | InstructionGroup::Calls
+            | InstructionGroup::Interrupts
+            | InstructionGroup::Timing => true,
+            InstructionGroup::Div
+            | InstructionGroup::Rotates
+            | InstructionGroup::BitOps
+            | InstructionGroup::StringOps => false,
         }
     }
 
@@ -38,24 +98,7 @@ impl Executor {
         cpu: &mut CpuState,
         memory: &mut Memory,
     ) -> Result<ExecutionResult, EmulatorError> {
-        // Fetch bytes from current EIP
-        let bytes = memory.read_bytes(cpu.eip, 15)?; // Max x86 instruction length
-
-        // Decode instruction
-        let mut decoder = Decoder::with_ip(
-            self.bitness,
-            &bytes,
-            cpu.eip as u64,
-            DecoderOptions::NONE,
-        );
-
-        let instr = decoder.decode();
-        if instr.is_invalid() {
-            return Err(EmulatorError::UnsupportedInstruction(format!(
-                "Invalid instruction at 0x{:08X}",
-                cpu.eip
-            )));
-        }
+        let instr = self.fetch_decode(cpu.eip, memory)?;
 
         let instr_len = instr.len() as u32;
         let next_eip = cpu.eip + instr_len;
@@ -64,6 +107,8 @@ impl Executor {
         let result = match instr.mnemonic() {
             // Data movement
             Mnemonic::Mov => self.exec_mov(cpu, memory, &instr, next_eip),
+            Mnemonic::Movzx => self.exec_mov(cpu, memory, &instr, next_eip),
+            Mnemonic::Movsx => self.exec_movsx(cpu, memory, &instr, next_eip),
             Mnemonic::Push => self.exec_push(cpu, memory, &instr, next_eip),
             Mnemonic::Pop => self.exec_pop(cpu, memory, &instr, next_eip),
             Mnemonic::Xchg => self.exec_xchg(cpu, memory, &instr, next_eip),
@@ -117,12 +162,8 @@ impl Executor {
                 let cond = !cpu.eflags.cf && !cpu.eflags.zf;
                 self.exec_jcc(cpu, &instr, next_eip, cond)
             }
-            Mnemonic::Jae => {
-                self.exec_jcc(cpu, &instr, next_eip, !cpu.eflags.cf)
-            }
-            Mnemonic::Jb => {
-                self.exec_jcc(cpu, &instr, next_eip, cpu.eflags.cf)
-            }
+            Mnemonic::Jae => self.exec_jcc(cpu, &instr, next_eip, !cpu.eflags.cf),
+            Mnemonic::Jb => self.exec_jcc(cpu, &instr, next_eip, cpu.eflags.cf),
             Mnemonic::Jbe => {
                 let cond = cpu.eflags.cf || cpu.eflags.zf;
                 self.exec_jcc(cpu, &instr, next_eip, cond)
@@ -132,9 +173,22 @@ impl Executor {
             Mnemonic::Jo => self.exec_jcc(cpu, &instr, next_eip, cpu.eflags.of),
             Mnemonic::Jno => self.exec_jcc(cpu, &instr, next_eip, !cpu.eflags.of),
 
+            // Counted loops: decrement ECX without touching flags
+            Mnemonic::Loop | Mnemonic::Loope | Mnemonic::Loopne => {
+                cpu.regs.ecx = cpu.regs.ecx.wrapping_sub(1);
+                let cond = cpu.regs.ecx != 0
+                    && match instr.mnemonic() {
+                        Mnemonic::Loope => cpu.eflags.zf,
+                        Mnemonic::Loopne => !cpu.eflags.zf,
+                        _ => true,
+                    };
+                self.exec_jcc(cpu, &instr, next_eip, cond)
+            }
+
             // Call/Return
             Mnemonic::Call => self.exec_call(cpu, memory, &instr, next_eip),
             Mnemonic::Ret => self.exec_ret(cpu, memory, &instr),
+            Mnemonic::Iretd => self.exec_iret(cpu, memory),
 
             // Misc
             Mnemonic::Nop => Ok(ExecutionResult::Continue { next_eip }),
@@ -144,6 +198,11 @@ impl Executor {
             }
             Mnemonic::Int => self.exec_int(cpu, &instr, next_eip),
             Mnemonic::Int3 => Ok(ExecutionResult::Breakpoint),
+            Mnemonic::Rdtsc => {
+                cpu.regs.eax = cpu.cycles as u32;
+                cpu.regs.edx = (cpu.cycles >> 32) as u32;
+                Ok(ExecutionResult::Continue { next_eip })
+            }
 
             _ => Err(EmulatorError::UnsupportedInstruction(format!(
                 "{:?} at 0x{:08X}",
@@ -152,12 +211,102 @@ impl Executor {
             ))),
         };
 
+        if let Ok(ref outcome) = result {
+            let taken = matches!(outcome, ExecutionResult::Continue { next_eip: target } if *target != next_eip);
+            cpu.cycles = cpu
+                .cycles
+                .wrapping_add(self.cycle_model.cost(&instr, taken) as u64);
+            self.last_mnemonic = Some(instr.mnemonic());
+        }
+
         result
     }
 
+    /// Fetch and decode the instruction at `eip` without allocating
+    fn fetch_decode(&mut self, eip: u32, memory: &Memory) -> Result<Instruction, EmulatorError> {
+        // Max x86 instruction length; shorter near the end of memory. The
+        // bytes are peeked so that looking past the instruction never
+        // reaches a device.
+        memory.check_execute(eip)?;
+        let mut bytes = [0u8; MAX_INSTRUCTION_LEN];
+        let len = memory.fetch_into(eip, &mut bytes)?;
+
+        // Reuse the previous decode if the bytes at `eip` are unchanged
+        // (patches and self-modifying code simply miss the cache)
+        let slot = eip as usize % DECODE_CACHE_SIZE;
+        let cached = self.decode_cache[slot]
+            .as_ref()
+            .filter(|entry| {
+                let n = entry.instr.len();
+                entry.eip == eip && n <= len && entry.bytes[..n] == bytes[..n]
+            })
+            .map(|entry| entry.instr);
+        let instr = match cached {
+            Some(instr) => instr,
+            None => {
+                let instr = self.decode_at(eip, &bytes[..len])?;
+                self.decode_cache[slot] = Some(DecodedEntry { eip, bytes, instr });
+                instr
+            }
+        };
+
+        // Code running from a device window really reads its own bytes
+        if memory.maps_device(eip, instr.len()) {
+            let n = instr.len();
+            memory.read_into(eip, &mut bytes[..n])?;
+            return self.decode_at(eip, &bytes[..n]);
+        }
+        Ok(instr)
+    }
+
+    fn decode_at(&self, eip: u32, bytes: &[u8]) -> Result<Instruction, EmulatorError> {
+        let mut decoder = Decoder::with_ip(self.bitness, bytes, eip as u64, DecoderOptions::NONE);
+
+        let instr = decoder.decode();
+        if instr.is_invalid() {
+            return Err(EmulatorError::UnsupportedInstruction(format!(
+                "Invalid instruction at 0x{:08X}",
+                eip
+            )));
+        }
+        Ok(instr)
+    }
+
+    /// Declare `length` bytes at `start` as data for disassembly
+    pub fn mark_data(&mut self, start: u32, length: u32) {
+        self.disassembler.mark_data(start, length);
+    }
+
+    /// Disassemble raw bytes, honouring the declared data regions
+    pub fn disassemble_bytes(
+        &mut self,
+        bytes: &[u8],
+        address: u32,
+        count: usize,
+    ) -> Vec<DisassemblyLine> {
+        self.disassembler.disassemble(bytes, address, count)
+    }
+
+    /// Disassemble raw bytes by following control flow from `roots`
+    pub fn disassemble_recursive(
+        &mut self,
+        bytes: &[u8],
+        address: u32,
+        roots: &[u32],
+    ) -> Vec<DisassemblyLine> {
+        self.disassembler
+            .disassemble_recursive(bytes, address, roots)
+    }
+
     /// Get disassembly around an address
-    pub fn disassemble(&mut self, memory: &Memory, address: u32, count: usize) -> Vec<DisassemblyLine> {
-        if let Ok(bytes) = memory.read_bytes(address, count * 15) {
+    pub fn disassemble(
+        &mut self,
+        memory: &Memory,
+        address: u32,
+        count: usize,
+    ) -> Vec<DisassemblyLine> {
+        // Peeked, so that drawing the disassembly never changes a device
+        if let Ok(bytes) = memory.peek_bytes(address, count * 15) {
             self.disassembler.disassemble(&bytes, address, count)
         } else {
             Vec::new()
@@ -189,7 +338,47 @@ impl Executor {
         }
     }
 
-    /// Read operand value (register or immediate)
+    /// Mask and bit shift selecting a register's part of its full
+    /// 32-bit register (AL is the low byte of EAX, AH the next one)
+    fn register_part(reg: IcedRegister) -> (u32, u32) {
+        match reg {
+            IcedRegister::AL | IcedRegister::BL | IcedRegister::CL | IcedRegister::DL => (0xFF, 0),
+            IcedRegister::AH | IcedRegister::BH | IcedRegister::CH | IcedRegister::DH => (0xFF, 8),
+            IcedRegister::AX
+            | IcedRegister::BX
+            | IcedRegister::CX
+            | IcedRegister::DX
+            | IcedRegister::SI
+            | IcedRegister::DI
+            | IcedRegister::BP
+            | IcedRegister::SP => (0xFFFF, 0),
+            _ => (0xFFFF_FFFF, 0),
+        }
+    }
+
+    /// Size in bytes of the instruction's memory operand
+    fn memory_width(instr: &Instruction) -> u32 {
+        match instr.memory_size() {
+            MemorySize::UInt8 | MemorySize::Int8 => 1,
+            MemorySize::UInt16 | MemorySize::Int16 => 2,
+            _ => 4,
+        }
+    }
+
+    /// Size in bytes of operand `op_idx`
+    fn operand_width(instr: &Instruction, op_idx: u32) -> u32 {
+        match instr.op_kind(op_idx) {
+            OpKind::Register => match Self::register_part(instr.op_register(op_idx)).0 {
+                0xFF => 1,
+                0xFFFF => 2,
+                _ => 4,
+            },
+            OpKind::Memory => Self::memory_width(instr),
+            _ => 4,
+        }
+    }
+
+    /// Read operand value (register or immediate), zero-extended to 32 bits
     fn read_operand(
         &self,
         cpu: &CpuState,
@@ -201,7 +390,8 @@ impl Executor {
             OpKind::Register => {
                 let reg = instr.op_register(op_idx);
                 if let Some(r) = Self::iced_to_register(reg) {
-                    Ok(cpu.get_register(r))
+                    let (mask, shift) = Self::register_part(reg);
+                    Ok((cpu.get_register(r) >> shift) & mask)
                 } else {
                     Err(EmulatorError::InvalidOperand(format!(
                         "Unsupported register: {:?}",
@@ -216,7 +406,12 @@ impl Executor {
             OpKind::Immediate8to16 => Ok(instr.immediate8to16() as u32),
             OpKind::Memory => {
                 let addr = self.calculate_memory_address(cpu, instr)?;
-                memory.read_u32(addr).map_err(EmulatorError::from)
+                let value = match Self::memory_width(instr) {
+                    1 => memory.read_u8(addr).map(u32::from),
+                    2 => memory.read_u16(addr).map(u32::from),
+                    _ => memory.read_u32(addr),
+                };
+                value.map_err(EmulatorError::from)
             }
             _ => Err(EmulatorError::InvalidOperand(format!(
                 "Unsupported operand kind: {:?}",
@@ -238,7 +433,10 @@ impl Executor {
             OpKind::Register => {
                 let reg = instr.op_register(op_idx);
                 if let Some(r) = Self::iced_to_register(reg) {
-                    cpu.set_register(r, value);
+                    // Writing a part leaves the rest of the register alone
+                    let (mask, shift) = Self::register_part(reg);
+                    let full = cpu.get_register(r);
+                    cpu.set_register(r, (full & !(mask << shift)) | ((value & mask) << shift));
                     Ok(())
                 } else {
                     Err(EmulatorError::InvalidOperand(format!(
@@ -249,7 +447,12 @@ impl Executor {
             }
             OpKind::Memory => {
                 let addr = self.calculate_memory_address(cpu, instr)?;
-                memory.write_u32(addr, value).map_err(EmulatorError::from)
+                let result = match Self::memory_width(instr) {
+                    1 => memory.write_u8(addr, value as u8),
+                    2 => memory.write_u16(addr, value as u16),
+                    _ => memory.write_u32(addr, value),
+                };
+                result.map_err(EmulatorError::from)
             }
             _ => Err(EmulatorError::InvalidOperand(format!(
                 "Cannot write to operand kind: {:?}",
@@ -299,6 +502,23 @@ impl Executor {
         Ok(ExecutionResult::Continue { next_eip })
     }
 
+    fn exec_movsx(
+        &self,
+        cpu: &mut CpuState,
+        memory: &mut Memory,
+        instr: &Instruction,
+        next_eip: u32,
+    ) -> Result<ExecutionResult, EmulatorError> {
+        let value = self.read_operand(cpu, memory, instr, 1)?;
+        let extended = match Self::operand_width(instr, 1) {
+            1 => value as u8 as i8 as i32 as u32,
+            2 => value as u16 as i16 as i32 as u32,
+            _ => value,
+        };
+        self.write_operand(cpu, memory, instr, 0, extended)?;
+        Ok(ExecutionResult::Continue { next_eip })
+    }
+
     fn exec_push(
         &self,
         cpu: &mut CpuState,
@@ -424,7 +644,7 @@ impl Executor {
         next_eip: u32,
     ) -> Result<ExecutionResult, EmulatorError> {
         let value = self.read_operand(cpu, memory, instr, 0)?;
-        let result = (-(value as i32)) as u32;
+        let result = value.wrapping_neg();
         cpu.eflags.update_arithmetic(result, 0, value, true);
         cpu.eflags.cf = value != 0; // NEG sets CF if operand is non-zero
         self.write_operand(cpu, memory, instr, 0, result)?;
@@ -570,12 +790,12 @@ impl Executor {
         next_eip: u32,
     ) -> Result<ExecutionResult, EmulatorError> {
         let value = self.read_operand(cpu, memory, instr, 0)?;
-        let count = (self.read_operand(cpu, memory, instr, 1)? & 0x1F) as u32;
+        let count = self.read_operand(cpu, memory, instr, 1)? & 0x1F;
 
         if count > 0 {
             let result = value << count;
-            cpu.eflags.cf = ((value >> (32 - count)) & 1) != 0;
             cpu.eflags.update_logical(result);
+            cpu.eflags.cf = ((value >> (32 - count)) & 1) != 0;
             if count == 1 {
                 cpu.eflags.of = ((result >> 31) & 1) != cpu.eflags.cf as u32;
             }
@@ -593,12 +813,12 @@ impl Executor {
         next_eip: u32,
     ) -> Result<ExecutionResult, EmulatorError> {
         let value = self.read_operand(cpu, memory, instr, 0)?;
-        let count = (self.read_operand(cpu, memory, instr, 1)? & 0x1F) as u32;
+        let count = self.read_operand(cpu, memory, instr, 1)? & 0x1F;
 
         if count > 0 {
             let result = value >> count;
-            cpu.eflags.cf = ((value >> (count - 1)) & 1) != 0;
             cpu.eflags.update_logical(result);
+            cpu.eflags.cf = ((value >> (count - 1)) & 1) != 0;
             if count == 1 {
                 cpu.eflags.of = (value >> 31) != 0;
             }
@@ -616,12 +836,12 @@ impl Executor {
         next_eip: u32,
     ) -> Result<ExecutionResult, EmulatorError> {
         let value = self.read_operand(cpu, memory, instr, 0)? as i32;
-        let count = (self.read_operand(cpu, memory, instr, 1)? & 0x1F) as u32;
+        let count = self.read_operand(cpu, memory, instr, 1)? & 0x1F;
 
         if count > 0 {
             let result = (value >> count) as u32;
-            cpu.eflags.cf = ((value >> (count as i32 - 1)) & 1) != 0;
             cpu.eflags.update_logical(result);
+            cpu.eflags.cf = ((value >> (count as i32 - 1)) & 1) != 0;
             if count == 1 {
                 cpu.eflags.of = false; // SAR always clears OF for count=1
             }
@@ -755,6 +975,22 @@ impl Executor {
         })
     }
 
+    /// IRET in the flat model: pop the return address, then EFLAGS
+    fn exec_iret(
+        &self,
+        cpu: &mut CpuState,
+        memory: &mut Memory,
+    ) -> Result<ExecutionResult, EmulatorError> {
+        let return_addr = memory.read_u32(cpu.regs.esp)?;
+        let flags = memory.read_u32(cpu.regs.esp.wrapping_add(4))?;
+        cpu.regs.esp = cpu.regs.esp.wrapping_add(8);
+        cpu.eflags = Eflags::from_u32(flags);
+
+        Ok(ExecutionResult::Continue {
+            next_eip: return_addr,
+        })
+    }
+
     fn exec_int(
         &self,
         _cpu: &CpuState,
@@ -775,8 +1011,8 @@ impl Executor {
             return Ok(ExecutionResult::Breakpoint);
         }
 
-        // Other interrupts could be used for simulated syscalls
-        Ok(ExecutionResult::Interrupt(vector))
+        // Other interrupts are handled by the debugger (simulated syscalls)
+        Ok(ExecutionResult::Interrupt { vector, next_eip })
     }
 }
 
@@ -792,7 +1028,7 @@ mod tests {
 
     fn setup_test() -> (CpuState, Memory, Executor) {
         let cpu = CpuState::new(0x1000, 0x3000);
-        let mut memory = Memory::new(0x4000);
+        let memory = Memory::new(0x4000);
         let executor = Executor::new();
         (cpu, memory, executor)
     }
@@ -801,13 +1037,99 @@ mod tests {
     fn test_mov_reg_imm() {
         let (mut cpu, mut memory, mut executor) = setup_test();
         // MOV EAX, 0x12345678
-        memory.load(0x1000, &[0xB8, 0x78, 0x56, 0x34, 0x12]).unwrap();
+        memory
+            .load(0x1000, &[0xB8, 0x78, 0x56, 0x34, 0x12])
+            .unwrap();
 
         let result = executor.execute_one(&mut cpu, &mut memory).unwrap();
-        assert!(matches!(result, ExecutionResult::Continue { next_eip: 0x1005 }));
+        assert!(matches!(
+            result,
+            ExecutionResult::Continue { next_eip: 0x1005 }
+        ));
         assert_eq!(cpu.regs.eax, 0x12345678);
     }
 
+    #[test]
+    fn test_shift_carry_survives_result_flags() {
+        let (mut cpu, mut memory, mut executor) = setup_test();
+        // SHL EAX, 1; SHR EBX, 4
+        memory
+            .load(0x1000, &[0xD1, 0xE0, 0xC1, 0xEB, 0x04])
+            .unwrap();
+        cpu.regs.eax = 0x8000_0001;
+        cpu.regs.ebx = 0x18;
+
+        executor.execute_one(&mut cpu, &mut memory).unwrap();
+        assert_eq!(cpu.regs.eax, 2);
+        assert!(cpu.eflags.cf && cpu.eflags.of);
+
+        cpu.eip = 0x1002;
+        executor.execute_one(&mut cpu, &mut memory).unwrap();
+        assert_eq!(cpu.regs.ebx, 1);
+        assert!(cpu.eflags.cf);
+    }
+
+    #[test]
+    fn test_rdtsc_counts_cycles() {
+        let (mut cpu, mut memory, mut executor) = setup_test();
+        // MOV EBX, [0x2000]; RDTSC; JMP +0; RDTSC
+        memory
+            .load(
+                0x1000,
+                &[
+                    0x8B, 0x1D, 0x00, 0x20, 0x00, 0x00, 0x0F, 0x31, 0xEB, 0x00, 0x0F, 0x31,
+                ],
+            )
+            .unwrap();
+        let step = |cpu: &mut CpuState, memory: &mut Memory, executor: &mut Executor| {
+            if let ExecutionResult::Continue { next_eip } =
+                executor.execute_one(cpu, memory).unwrap()
+            {
+                cpu.eip = next_eip;
+            }
+        };
+
+        step(&mut cpu, &mut memory, &mut executor);
+        step(&mut cpu, &mut memory, &mut executor);
+        // The load costs base + memory
+        assert_eq!((cpu.regs.eax, cpu.regs.edx), (3, 0));
+
+        // Tuned costs; a jump to the next instruction isn't taken
+        let mut model = CycleModel::default();
+        model.set_cost("JMP", 5).unwrap();
+        assert!(model.set_cost("frobnicate", 1).is_err());
+        executor.set_cycle_model(model);
+        step(&mut cpu, &mut memory, &mut executor);
+        step(&mut cpu, &mut memory, &mut executor);
+        assert_eq!(cpu.regs.eax, 3 + 20 + 5);
+    }
+
+    #[test]
+    fn test_decode_cache_sees_patched_code() {
+        let (mut cpu, mut memory, mut executor) = setup_test();
+        // INC EAX
+        memory.load(0x1000, &[0x40]).unwrap();
+        executor.execute_one(&mut cpu, &mut memory).unwrap();
+        assert_eq!(cpu.regs.eax, 1);
+
+        // Patch to DEC EAX and run the same address again
+        memory.write_u8(0x1000, 0x48).unwrap();
+        cpu.eip = 0x1000;
+        executor.execute_one(&mut cpu, &mut memory).unwrap();
+        assert_eq!(cpu.regs.eax, 0);
+    }
+
+    #[test]
+    fn test_execute_at_end_of_memory() {
+        let (mut cpu, mut memory, mut executor) = setup_test();
+        // HLT in the very last byte
+        memory.write_u8(0x3FFF, 0xF4).unwrap();
+        cpu.eip = 0x3FFF;
+
+        let result = executor.execute_one(&mut cpu, &mut memory).unwrap();
+        assert!(matches!(result, ExecutionResult::Halt));
+    }
+
     #[test]
     fn test_add_reg_reg() {
         let (mut cpu, mut memory, mut executor) = setup_test();
@@ -840,7 +1162,10 @@ mod tests {
         memory.load(0x1000, &[0x75, 0x05]).unwrap();
 
         let result = executor.execute_one(&mut cpu, &mut memory).unwrap();
-        assert!(matches!(result, ExecutionResult::Continue { next_eip: 0x1002 }));
+        assert!(matches!(
+            result,
+            ExecutionResult::Continue { next_eip: 0x1002 }
+        ));
     }
 
     #[test]
@@ -851,7 +1176,44 @@ mod tests {
         memory.load(0x1000, &[0x75, 0x05]).unwrap();
 
         let result = executor.execute_one(&mut cpu, &mut memory).unwrap();
-        assert!(matches!(result, ExecutionResult::Continue { next_eip: 0x1007 }));
+        assert!(matches!(
+            result,
+            ExecutionResult::Continue { next_eip: 0x1007 }
+        ));
+    }
+
+    #[test]
+    fn test_loop_counts_down_ecx() {
+        let (mut cpu, mut memory, mut executor) = setup_test();
+        cpu.regs.ecx = 2;
+        cpu.eflags.zf = true;
+        // LOOP -2 (to itself)
+        memory.load(0x1000, &[0xE2, 0xFE]).unwrap();
+
+        let result = executor.execute_one(&mut cpu, &mut memory).unwrap();
+        assert!(matches!(
+            result,
+            ExecutionResult::Continue { next_eip: 0x1000 }
+        ));
+        assert_eq!(cpu.regs.ecx, 1);
+        let result = executor.execute_one(&mut cpu, &mut memory).unwrap();
+        assert!(matches!(
+            result,
+            ExecutionResult::Continue { next_eip: 0x1002 }
+        ));
+        assert_eq!(cpu.regs.ecx, 0);
+        assert!(cpu.eflags.zf);
+
+        // LOOPNE stops early on ZF even with ECX left
+        cpu.eip = 0x1000;
+        cpu.regs.ecx = 5;
+        memory.load(0x1000, &[0xE0, 0xFE]).unwrap();
+        let result = executor.execute_one(&mut cpu, &mut memory).unwrap();
+        assert!(matches!(
+            result,
+            ExecutionResult::Continue { next_eip: 0x1002 }
+        ));
+        assert_eq!(cpu.regs.ecx, 4);
     }
 
     #[test]
@@ -874,4 +1236,36 @@ mod tests {
         assert_eq!(cpu.regs.ebx, 0xDEADBEEF);
         assert_eq!(cpu.regs.esp, 0x3000);
     }
+
+    #[test]
+    fn test_byte_sized_operands() {
+        let (mut cpu, mut memory, mut executor) = setup_test();
+        cpu.regs.eax = 0x1122_3344;
+        cpu.regs.esi = 0x2000;
+        memory.load(0x2000, &[0xAB, 0xCD, 0xEF, 0x01]).unwrap();
+
+        // MOV AL, [ESI]; MOV AH, 0x7F; MOV [ESI+2], AL; MOVZX EBX, BYTE [ESI]; MOVSX ECX, BYTE [ESI]
+        memory
+            .load(
+                0x1000,
+                &[
+                    0x8A, 0x06, 0xB4, 0x7F, 0x88, 0x46, 0x02, 0x0F, 0xB6, 0x1E, 0x0F, 0xBE, 0x0E,
+                ],
+            )
+            .unwrap();
+        for _ in 0..5 {
+            match executor.execute_one(&mut cpu, &mut memory).unwrap() {
+                ExecutionResult::Continue { next_eip } => cpu.eip = next_eip,
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+
+        assert_eq!(cpu.regs.eax, 0x1122_7FAB);
+        assert_eq!(
+            memory.read_bytes(0x2000, 4).unwrap(),
+            vec![0xAB, 0xCD, 0xAB, 0x01]
+        );
+        assert_eq!(cpu.regs.ebx, 0xAB);
+        assert_eq!(cpu.regs.ecx, 0xFFFF_FFAB);
+    }
 }
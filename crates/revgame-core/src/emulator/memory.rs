@@ -1,10 +1,14 @@
+use std::sync::{Arc, Mutex};
+
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::mmio::{MmioDevice, MmioMapping, SharedDevice};
+
 #[derive(Error, Debug)]
 pub enum MemoryError {
     #[error("Address 0x{address:08X} out of bounds (size: 0x{size:X})")]
-    OutOfBounds { address: u32, size: usize },
+    OutOfBounds { address: u32, size: u64 },
 
     #[error("Access violation at 0x{address:08X}: {reason}")]
     AccessViolation { address: u32, reason: String },
@@ -23,19 +27,35 @@ pub struct Permissions {
 
 impl Permissions {
     pub fn rwx() -> Self {
-        Self { read: true, write: true, execute: true }
+        Self {
+            read: true,
+            write: true,
+            execute: true,
+        }
     }
 
     pub fn rw() -> Self {
-        Self { read: true, write: true, execute: false }
+        Self {
+            read: true,
+            write: true,
+            execute: false,
+        }
     }
 
     pub fn rx() -> Self {
-        Self { read: true, write: false, execute: true }
+        Self {
+            read: true,
+            write: false,
+            execute: true,
+        }
     }
 
     pub fn ro() -> Self {
-        Self { read: true, write: false, execute: false }
+        Self {
+            read: true,
+            write: false,
+            execute: false,
+        }
     }
 }
 
@@ -58,33 +78,166 @@ impl MemoryRegion {
     }
 }
 
+/// Size of a memory page in bytes
+pub const PAGE_SIZE: usize = 0x1000;
+
+/// Size of the full 32-bit address space
+pub const ADDRESS_SPACE: u64 = 1 << 32;
+
+/// Entries per page table level (two levels cover the 32-bit address space)
+const TABLE_ENTRIES: usize = 1024;
+
+type Page = [u8; PAGE_SIZE];
+type Table = Box<[Option<Box<Page>>]>;
+
+/// Two-level page table over the 32-bit address space. Pages are allocated
+/// on first write; unmapped pages read as zero.
+#[derive(Debug, Clone)]
+struct PageTable {
+    directory: Vec<Option<Table>>,
+}
+
+impl PageTable {
+    fn new() -> Self {
+        Self {
+            directory: vec![None; TABLE_ENTRIES],
+        }
+    }
+
+    /// Split an address into directory index, table index and page offset
+    fn indices(address: u32) -> (usize, usize, usize) {
+        (
+            (address >> 22) as usize,
+            ((address >> 12) & 0x3FF) as usize,
+            (address & 0xFFF) as usize,
+        )
+    }
+
+    fn page(&self, address: u32) -> Option<&Page> {
+        let (dir, table, _) = Self::indices(address);
+        self.directory[dir].as_ref()?[table].as_deref()
+    }
+
+    fn page_mut(&mut self, address: u32) -> &mut Page {
+        let (dir, table, _) = Self::indices(address);
+        let entries =
+            self.directory[dir].get_or_insert_with(|| vec![None; TABLE_ENTRIES].into_boxed_slice());
+        entries[table].get_or_insert_with(|| Box::new([0; PAGE_SIZE]))
+    }
+
+    /// Start addresses of allocated pages, lowest first
+    fn allocated(&self) -> impl Iterator<Item = u32> + '_ {
+        self.directory.iter().enumerate().flat_map(|(dir, table)| {
+            table
+                .iter()
+                .flat_map(|entries| entries.iter().enumerate())
+                .filter_map(move |(index, page)| {
+                    page.as_ref().map(|_| ((dir << 22) | (index << 12)) as u32)
+                })
+        })
+    }
+
+    /// Number of allocated pages
+    fn page_count(&self) -> usize {
+        self.directory
+            .iter()
+            .flatten()
+            .map(|table| table.iter().flatten().count())
+            .sum()
+    }
+
+    /// Copy bytes starting at `address` into `buf` (caller checks bounds)
+    fn read(&self, address: u32, buf: &mut [u8]) {
+        let mut done = 0;
+        while done < buf.len() {
+            let addr = address.wrapping_add(done as u32);
+            let offset = (addr & 0xFFF) as usize;
+            let count = (PAGE_SIZE - offset).min(buf.len() - done);
+            match self.page(addr) {
+                Some(page) => {
+                    buf[done..done + count].copy_from_slice(&page[offset..offset + count])
+                }
+                None => buf[done..done + count].fill(0),
+            }
+            done += count;
+        }
+    }
+
+    /// Copy `bytes` into memory starting at `address` (caller checks bounds)
+    fn write(&mut self, address: u32, bytes: &[u8]) {
+        let mut done = 0;
+        while done < bytes.len() {
+            let addr = address.wrapping_add(done as u32);
+            let offset = (addr & 0xFFF) as usize;
+            let count = (PAGE_SIZE - offset).min(bytes.len() - done);
+            self.page_mut(addr)[offset..offset + count].copy_from_slice(&bytes[done..done + count]);
+            done += count;
+        }
+    }
+}
+
+/// A copy of memory contents (not regions or permissions) for restoring later
+#[derive(Debug, Clone)]
+pub struct MemorySnapshot {
+    pages: PageTable,
+}
+
+impl MemorySnapshot {
+    /// Bytes as they were when the snapshot was taken
+    pub fn read_bytes(&self, address: u32, count: usize) -> Vec<u8> {
+        let mut bytes = vec![0; count];
+        self.pages.read(address, &mut bytes);
+        bytes
+    }
+}
+
 /// Memory subsystem for the emulator
 #[derive(Debug, Clone)]
 pub struct Memory {
-    /// Flat memory array
-    data: Vec<u8>,
+    /// Sparse page-backed storage
+    data: PageTable,
+
+    /// Size of the addressable space in bytes (up to `ADDRESS_SPACE`)
+    size: u64,
 
     /// Named regions with permissions
     regions: Vec<MemoryRegion>,
 
     /// Whether to enforce permissions (can be disabled for puzzles)
     enforce_permissions: bool,
+
+    /// Memory-mapped devices (take precedence over page storage)
+    mmio: Vec<MmioMapping>,
 }
 
 impl Memory {
-    /// Create a new memory instance with the given size
+    /// Create a new memory instance with the given size. Only pages that are
+    /// written to take up space, so large address spaces are cheap.
     pub fn new(size: usize) -> Self {
+        Self::with_size(size as u64)
+    }
+
+    fn with_size(size: u64) -> Self {
         Self {
-            data: vec![0; size],
+            data: PageTable::new(),
+            size: size.min(ADDRESS_SPACE),
             regions: Vec::new(),
             enforce_permissions: false, // Default off for simpler puzzles
+            mmio: Vec::new(),
         }
     }
 
-    /// Create memory with default puzzle layout
+    /// Create memory with default puzzle layout over the whole 32-bit
+    /// address space, so the stack may sit below the image as well as above
     pub fn with_puzzle_layout(code_start: u32, data_start: u32, stack_start: u32) -> Self {
-        let size = (stack_start + 0x1000) as usize; // Stack grows down, give it 4KB
-        let mut mem = Self::new(size);
+        let mut mem = Self::with_size(ADDRESS_SPACE);
+        // Data runs up to the stack, or to the end of memory when the stack
+        // is below it (stack grows down, give it 4KB)
+        let data_end = if stack_start > data_start {
+            stack_start - 0x1000
+        } else {
+            u32::MAX
+        };
 
         mem.regions.push(MemoryRegion {
             name: "code".to_string(),
@@ -96,7 +249,7 @@ impl Memory {
         mem.regions.push(MemoryRegion {
             name: "data".to_string(),
             start: data_start,
-            end: stack_start - 0x1000,
+            end: data_end,
             permissions: Permissions::rw(),
         });
 
@@ -111,8 +264,161 @@ impl Memory {
     }
 
     /// Get the total memory size
-    pub fn size(&self) -> usize {
-        self.data.len()
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Exclusive end of memory as an address, for scans. A full 4 GiB
+    /// space ends at `u32::MAX`, leaving out its very last byte.
+    pub fn end(&self) -> u32 {
+        self.size.min(u32::MAX as u64) as u32
+    }
+
+    /// Bytes from `address` to the end of memory
+    fn available(&self, address: u32) -> usize {
+        usize::try_from(self.size.saturating_sub(address as u64)).unwrap_or(usize::MAX)
+    }
+
+    /// Number of bytes actually allocated for page storage
+    pub fn allocated_bytes(&self) -> usize {
+        self.data.page_count() * PAGE_SIZE
+    }
+
+    /// The parts of `start..end` that can hold anything but zeros: pages
+    /// written to and device windows, merged where they touch. A scan of a
+    /// huge, mostly untouched region only needs to look at these.
+    pub fn populated_ranges(&self, start: u32, end: u32) -> Vec<(u32, u32)> {
+        let pages = self
+            .data
+            .allocated()
+            .map(|page| (page, page.saturating_add(PAGE_SIZE as u32)));
+        let devices = self
+            .mmio
+            .iter()
+            .map(|m| (m.start, m.start.saturating_add(m.size)));
+        let mut spans: Vec<(u32, u32)> = pages
+            .chain(devices)
+            .map(|(from, to)| (from.max(start), to.min(end)))
+            .filter(|(from, to)| from < to)
+            .collect();
+        spans.sort();
+
+        let mut merged: Vec<(u32, u32)> = Vec::new();
+        for (from, to) in spans {
+            match merged.last_mut() {
+                Some(last) if from <= last.1 => last.1 = last.1.max(to),
+                _ => merged.push((from, to)),
+            }
+        }
+        merged
+    }
+
+    /// Map a device at `start..start + size`, adding a named region for it.
+    /// Returns a handle for inspecting the device later.
+    pub fn map_device<D: MmioDevice + 'static>(
+        &mut self,
+        start: u32,
+        size: u32,
+        device: D,
+    ) -> SharedDevice {
+        let device: SharedDevice = Arc::new(Mutex::new(device));
+        let name = device
+            .lock()
+            .map(|d| d.name().to_string())
+            .unwrap_or_default();
+
+        self.regions.push(MemoryRegion {
+            name,
+            start,
+            end: start.saturating_add(size),
+            permissions: Permissions::rw(),
+        });
+        self.mmio.push(MmioMapping {
+            start,
+            size,
+            device: device.clone(),
+        });
+
+        device
+    }
+
+    /// All mapped devices
+    pub fn devices(&self) -> &[MmioMapping] {
+        &self.mmio
+    }
+
+    /// Get the device mapped at an address
+    pub fn device_at(&self, address: u32) -> Option<&MmioMapping> {
+        self.mmio.iter().find(|m| m.contains(address))
+    }
+
+    /// Pass the session's random seed to every mapped device
+    pub fn reseed_devices(&self, seed: u32) {
+        for mapping in &self.mmio {
+            if let Ok(mut device) = mapping.device.lock() {
+                device.reseed(seed);
+            }
+        }
+    }
+
+    /// Reset every mapped device to its power-on state
+    pub fn reset_devices(&self) {
+        for mapping in &self.mmio {
+            if let Ok(mut device) = mapping.device.lock() {
+                device.reset();
+            }
+        }
+    }
+
+    /// Read bytes, routing addresses inside device windows to the device
+    fn read_raw(&self, address: u32, buf: &mut [u8]) {
+        self.data.read(address, buf);
+        if self.mmio.is_empty() {
+            return;
+        }
+        for (i, byte) in buf.iter_mut().enumerate() {
+            let addr = address.wrapping_add(i as u32);
+            if let Some(mapping) = self.device_at(addr) {
+                if let Ok(mut device) = mapping.device.lock() {
+                    *byte = device.read(addr - mapping.start);
+                }
+            }
+        }
+    }
+
+    /// Read bytes like `read_raw`, but peek devices instead of reading them
+    fn peek_raw(&self, address: u32, buf: &mut [u8]) {
+        self.data.read(address, buf);
+        if self.mmio.is_empty() {
+            return;
+        }
+        for (i, byte) in buf.iter_mut().enumerate() {
+            let addr = address.wrapping_add(i as u32);
+            if let Some(mapping) = self.device_at(addr) {
+                if let Ok(device) = mapping.device.lock() {
+                    *byte = device.peek(addr - mapping.start);
+                }
+            }
+        }
+    }
+
+    /// Write bytes, routing addresses inside device windows to the device
+    fn write_raw(&mut self, address: u32, bytes: &[u8]) {
+        if self.mmio.is_empty() {
+            self.data.write(address, bytes);
+            return;
+        }
+        for (i, &byte) in bytes.iter().enumerate() {
+            let addr = address.wrapping_add(i as u32);
+            match self.device_at(addr) {
+                Some(mapping) => {
+                    if let Ok(mut device) = mapping.device.lock() {
+                        device.write(addr - mapping.start, byte);
+                    }
+                }
+                None => self.data.write(addr, &[byte]),
+            }
+        }
     }
 
     /// Enable or disable permission enforcement
@@ -130,13 +436,33 @@ impl Memory {
         self.regions.iter().find(|r| r.contains(address))
     }
 
+    /// Get all named regions
+    pub fn regions(&self) -> &[MemoryRegion] {
+        &self.regions
+    }
+
+    /// Get a region by name, to change its permissions
+    pub fn region_mut(&mut self, name: &str) -> Option<&mut MemoryRegion> {
+        self.regions.iter_mut().find(|r| r.name == name)
+    }
+
+    /// Whether permission enforcement is enabled
+    pub fn enforces_permissions(&self) -> bool {
+        self.enforce_permissions
+    }
+
+    /// Check whether an address would be readable (always true when enforcement is off)
+    pub fn is_readable(&self, address: u32) -> bool {
+        self.check_read(address).is_ok()
+    }
+
     /// Check if an address is within bounds
     fn check_bounds(&self, address: u32, size: usize) -> Result<(), MemoryError> {
-        let end = address as usize + size;
-        if end > self.data.len() {
+        let end = address as u64 + size as u64;
+        if end > self.size {
             return Err(MemoryError::OutOfBounds {
                 address,
-                size: self.data.len()
+                size: self.size,
             });
         }
         Ok(())
@@ -176,47 +502,108 @@ impl Memory {
         Ok(())
     }
 
+    /// Check execute permission (for instruction fetch)
+    pub(crate) fn check_execute(&self, address: u32) -> Result<(), MemoryError> {
+        if !self.enforce_permissions {
+            return Ok(());
+        }
+
+        if let Some(region) = self.get_region(address) {
+            if !region.permissions.execute {
+                return Err(MemoryError::AccessViolation {
+                    address,
+                    reason: format!("Region '{}' is not executable", region.name),
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Read a single byte
     pub fn read_u8(&self, address: u32) -> Result<u8, MemoryError> {
         self.check_bounds(address, 1)?;
         self.check_read(address)?;
-        Ok(self.data[address as usize])
+        let mut byte = [0; 1];
+        self.read_raw(address, &mut byte);
+        Ok(byte[0])
     }
 
     /// Read a 16-bit value (little-endian)
     pub fn read_u16(&self, address: u32) -> Result<u16, MemoryError> {
         self.check_bounds(address, 2)?;
         self.check_read(address)?;
-        let addr = address as usize;
-        Ok(u16::from_le_bytes([self.data[addr], self.data[addr + 1]]))
+        let mut bytes = [0; 2];
+        self.read_raw(address, &mut bytes);
+        Ok(u16::from_le_bytes(bytes))
     }
 
     /// Read a 32-bit value (little-endian)
     pub fn read_u32(&self, address: u32) -> Result<u32, MemoryError> {
         self.check_bounds(address, 4)?;
         self.check_read(address)?;
-        let addr = address as usize;
-        Ok(u32::from_le_bytes([
-            self.data[addr],
-            self.data[addr + 1],
-            self.data[addr + 2],
-            self.data[addr + 3],
-        ]))
+        let mut bytes = [0; 4];
+        self.read_raw(address, &mut bytes);
+        Ok(u32::from_le_bytes(bytes))
     }
 
     /// Read a slice of bytes
     pub fn read_bytes(&self, address: u32, count: usize) -> Result<Vec<u8>, MemoryError> {
         self.check_bounds(address, count)?;
         self.check_read(address)?;
-        let addr = address as usize;
-        Ok(self.data[addr..addr + count].to_vec())
+        let mut bytes = vec![0; count];
+        self.read_raw(address, &mut bytes);
+        Ok(bytes)
+    }
+
+    /// Copy up to `buf.len()` bytes starting at `address` into `buf`, stopping
+    /// at the end of memory. Returns the number of bytes copied.
+    pub fn read_into(&self, address: u32, buf: &mut [u8]) -> Result<usize, MemoryError> {
+        self.check_bounds(address, 1)?;
+        self.check_read(address)?;
+        let count = buf.len().min(self.available(address));
+        self.read_raw(address, &mut buf[..count]);
+        Ok(count)
+    }
+
+    /// Copy up to `buf.len()` bytes starting at `address` into `buf` for the
+    /// decoder, checked like `read_into` but with devices peeked. Returns
+    /// the number of bytes copied.
+    pub fn fetch_into(&self, address: u32, buf: &mut [u8]) -> Result<usize, MemoryError> {
+        self.check_bounds(address, 1)?;
+        self.check_read(address)?;
+        Ok(self.peek_into(address, buf))
+    }
+
+    /// Whether any of the `len` bytes at `address` belong to a device
+    pub fn maps_device(&self, address: u32, len: usize) -> bool {
+        !self.mmio.is_empty()
+            && (0..len as u32).any(|i| self.device_at(address.wrapping_add(i)).is_some())
+    }
+
+    /// Read a 32-bit value the way the debugger inspects it: checked like
+    /// `read_u32`, but devices are peeked so looking has no side effects
+    pub fn peek_u32(&self, address: u32) -> Result<u32, MemoryError> {
+        self.check_bounds(address, 4)?;
+        self.check_read(address)?;
+        let mut bytes = [0; 4];
+        self.peek_raw(address, &mut bytes);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    /// Read a slice of bytes without side effects (see `peek_u32`)
+    pub fn peek_bytes(&self, address: u32, count: usize) -> Result<Vec<u8>, MemoryError> {
+        self.check_bounds(address, count)?;
+        self.check_read(address)?;
+        let mut bytes = vec![0; count];
+        self.peek_raw(address, &mut bytes);
+        Ok(bytes)
     }
 
     /// Write a single byte
     pub fn write_u8(&mut self, address: u32, value: u8) -> Result<(), MemoryError> {
         self.check_bounds(address, 1)?;
         self.check_write(address)?;
-        self.data[address as usize] = value;
+        self.write_raw(address, &[value]);
         Ok(())
     }
 
@@ -224,10 +611,7 @@ impl Memory {
     pub fn write_u16(&mut self, address: u32, value: u16) -> Result<(), MemoryError> {
         self.check_bounds(address, 2)?;
         self.check_write(address)?;
-        let bytes = value.to_le_bytes();
-        let addr = address as usize;
-        self.data[addr] = bytes[0];
-        self.data[addr + 1] = bytes[1];
+        self.write_raw(address, &value.to_le_bytes());
         Ok(())
     }
 
@@ -235,12 +619,7 @@ impl Memory {
     pub fn write_u32(&mut self, address: u32, value: u32) -> Result<(), MemoryError> {
         self.check_bounds(address, 4)?;
         self.check_write(address)?;
-        let bytes = value.to_le_bytes();
-        let addr = address as usize;
-        self.data[addr] = bytes[0];
-        self.data[addr + 1] = bytes[1];
-        self.data[addr + 2] = bytes[2];
-        self.data[addr + 3] = bytes[3];
+        self.write_raw(address, &value.to_le_bytes());
         Ok(())
     }
 
@@ -248,32 +627,55 @@ impl Memory {
     pub fn write_bytes(&mut self, address: u32, bytes: &[u8]) -> Result<(), MemoryError> {
         self.check_bounds(address, bytes.len())?;
         self.check_write(address)?;
-        let addr = address as usize;
-        self.data[addr..addr + bytes.len()].copy_from_slice(bytes);
+        self.write_raw(address, bytes);
+        Ok(())
+    }
+
+    /// Write bytes the way a debugger does: permissions don't apply, but
+    /// devices still see the write
+    pub fn poke(&mut self, address: u32, bytes: &[u8]) -> Result<(), MemoryError> {
+        self.check_bounds(address, bytes.len())?;
+        self.write_raw(address, bytes);
         Ok(())
     }
 
     /// Load binary data at an address (bypasses permission checks for initial setup)
     pub fn load(&mut self, address: u32, data: &[u8]) -> Result<(), MemoryError> {
         self.check_bounds(address, data.len())?;
-        let addr = address as usize;
-        self.data[addr..addr + data.len()].copy_from_slice(data);
+        self.data.write(address, data);
         Ok(())
     }
 
-    /// Get a slice of raw memory for display purposes
-    pub fn slice(&self, address: u32, count: usize) -> Option<&[u8]> {
-        let addr = address as usize;
-        if addr + count <= self.data.len() {
-            Some(&self.data[addr..addr + count])
+    /// Copy a range of memory for display purposes (devices are peeked,
+    /// so displaying them has no side effects)
+    pub fn slice(&self, address: u32, count: usize) -> Option<Vec<u8>> {
+        if address as u64 + count as u64 <= self.size {
+            let mut bytes = vec![0; count];
+            self.peek_raw(address, &mut bytes);
+            Some(bytes)
         } else {
             None
         }
     }
 
-    /// Get raw access to memory (for debugging/display)
-    pub fn raw(&self) -> &[u8] {
-        &self.data
+    /// Like `slice`, but copies into `buf` without allocating, stopping at
+    /// the end of memory. Returns the number of bytes copied.
+    pub fn peek_into(&self, address: u32, buf: &mut [u8]) -> usize {
+        let count = buf.len().min(self.available(address));
+        self.peek_raw(address, &mut buf[..count]);
+        count
+    }
+
+    /// Capture the current contents (allocated pages only)
+    pub fn snapshot(&self) -> MemorySnapshot {
+        MemorySnapshot {
+            pages: self.data.clone(),
+        }
+    }
+
+    /// Restore contents captured by `snapshot`; regions and permissions are kept
+    pub fn restore(&mut self, snapshot: &MemorySnapshot) {
+        self.data = snapshot.pages.clone();
     }
 }
 
@@ -301,6 +703,27 @@ mod tests {
         assert_eq!(mem.read_u8(0x103).unwrap(), 0xDE);
     }
 
+    #[test]
+    fn test_stack_below_image() {
+        // Windows-style: stack around 0x0012_0000, image at 0x400000
+        let mut mem = Memory::with_puzzle_layout(0x400000, 0x410000, 0x130000);
+        mem.write_u32(0x12FFFC, 0x12345678).unwrap();
+        mem.write_u32(0x410000, 0xCAFEBABE).unwrap();
+        mem.write_u32(0xFFFF_FFFC, 1).unwrap();
+        assert_eq!(mem.read_u32(0x12FFFC).unwrap(), 0x12345678);
+        assert_eq!(mem.read_u32(0x410000).unwrap(), 0xCAFEBABE);
+        assert!(mem.read_u32(0xFFFF_FFFE).is_err());
+
+        let names: Vec<&str> = [0x400000, 0x410000, 0x12FFFC, 0x7000_0000]
+            .iter()
+            .map(|&address| {
+                mem.get_region(address)
+                    .map_or("", |region| region.name.as_str())
+            })
+            .collect();
+        assert_eq!(names, ["code", "data", "stack", "data"]);
+    }
+
     #[test]
     fn test_out_of_bounds() {
         let mem = Memory::new(0x100);
@@ -308,6 +731,120 @@ mod tests {
         assert!(mem.read_u32(0xFE).is_err()); // Would read past end
     }
 
+    #[test]
+    fn test_sparse_high_addresses() {
+        // A realistic image base and stack without allocating the whole range
+        let mut mem = Memory::with_puzzle_layout(0x400000, 0x410000, 0x7FFF0000);
+        assert_eq!(mem.size(), ADDRESS_SPACE);
+        assert_eq!(mem.allocated_bytes(), 0);
+
+        mem.write_u32(0x400000, 0xDEADBEEF).unwrap();
+        mem.write_u32(0x7FFEFFFC, 0x12345678).unwrap();
+        assert_eq!(mem.read_u32(0x400000).unwrap(), 0xDEADBEEF);
+        assert_eq!(mem.read_u32(0x7FFEFFFC).unwrap(), 0x12345678);
+        assert_eq!(mem.allocated_bytes(), 2 * PAGE_SIZE);
+
+        // Unwritten memory reads as zero without allocating
+        assert_eq!(mem.read_u32(0x500000).unwrap(), 0);
+        assert_eq!(mem.allocated_bytes(), 2 * PAGE_SIZE);
+    }
+
+    #[test]
+    fn test_access_across_page_boundary() {
+        let mut mem = Memory::new(0x4000);
+        mem.write_u32(0x1FFE, 0xAABBCCDD).unwrap();
+        assert_eq!(mem.read_u32(0x1FFE).unwrap(), 0xAABBCCDD);
+        assert_eq!(mem.read_u16(0x1FFF).unwrap(), 0xBBCC);
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        mem.write_bytes(0x2F80, &bytes).unwrap();
+        assert_eq!(mem.read_bytes(0x2F80, 256).unwrap(), bytes);
+        assert_eq!(mem.slice(0x2F80, 256).unwrap(), bytes);
+        assert!(mem.slice(0x3F80, 256).is_none());
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut mem = Memory::new(0x4000);
+        mem.write_u8(0x100, 1).unwrap();
+        let snapshot = mem.snapshot();
+
+        mem.write_u8(0x100, 2).unwrap();
+        mem.write_u8(0x3000, 3).unwrap();
+        mem.restore(&snapshot);
+
+        assert_eq!(mem.read_u8(0x100).unwrap(), 1);
+        assert_eq!(mem.read_u8(0x3000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_read_into_stops_at_end() {
+        let mut mem = Memory::new(0x100);
+        mem.write_u8(0xFF, 0xF4).unwrap();
+
+        let mut buf = [0u8; 15];
+        assert_eq!(mem.read_into(0xF0, &mut buf).unwrap(), 15);
+        assert_eq!(mem.read_into(0xFF, &mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0xF4);
+        assert!(mem.read_into(0x100, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_peek_into_stops_at_end() {
+        let mut mem = Memory::new(0x100);
+        mem.write_u8(0xFF, 0xC3).unwrap();
+
+        let mut buf = [0u8; 2];
+        assert_eq!(mem.peek_into(0xFF, &mut buf), 1);
+        assert_eq!(buf[0], 0xC3);
+        assert_eq!(mem.peek_into(0x100, &mut buf), 0);
+        assert_eq!(mem.peek_into(u32::MAX, &mut buf), 0);
+    }
+
+    #[test]
+    fn test_is_readable() {
+        let mut mem = Memory::new(0x1000);
+        mem.add_region(MemoryRegion {
+            name: "secret".to_string(),
+            start: 0x800,
+            end: 0x900,
+            permissions: Permissions {
+                read: false,
+                write: false,
+                execute: false,
+            },
+        });
+
+        // Permissions are only consulted when enforcement is on
+        assert!(mem.is_readable(0x800));
+
+        mem.set_enforce_permissions(true);
+        assert!(!mem.is_readable(0x800));
+        assert!(mem.is_readable(0x700));
+        assert_eq!(
+            mem.get_region(0x880).map(|r| r.name.as_str()),
+            Some("secret")
+        );
+    }
+
+    #[test]
+    fn test_write_xor_execute() {
+        let mut mem = Memory::with_puzzle_layout(0x1000, 0x2000, 0x4000);
+        mem.set_enforce_permissions(true);
+        assert!(mem.write_u8(0x1000, 0x90).is_err());
+        assert!(mem.check_execute(0x1000).is_ok());
+        assert!(mem.check_execute(0x2000).is_err());
+
+        // A debugger patch ignores permissions
+        mem.poke(0x1000, &[0x90]).unwrap();
+        assert_eq!(mem.read_u8(0x1000).unwrap(), 0x90);
+
+        // Unpacking stubs need code they can rewrite
+        mem.region_mut("code").unwrap().permissions = Permissions::rwx();
+        mem.write_u8(0x1000, 0xF4).unwrap();
+        assert_eq!(mem.read_u8(0x1000).unwrap(), 0xF4);
+    }
+
     #[test]
     fn test_load() {
         let mut mem = Memory::new(0x1000);
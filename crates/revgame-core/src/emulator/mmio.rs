@@ -0,0 +1,223 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::rng::Prng;
+
+/// A device whose registers are mapped into the emulator's address space.
+///
+/// Accesses are byte-sized; multi-byte reads and writes are split into
+/// little-endian byte accesses at consecutive offsets.
+pub trait MmioDevice: Send + fmt::Debug {
+    /// Short name shown in memory views (e.g. "serial")
+    fn name(&self) -> &str;
+
+    /// Read the byte at `offset` into the device window
+    fn read(&mut self, offset: u32) -> u8;
+
+    /// Write the byte at `offset` into the device window
+    fn write(&mut self, offset: u32, value: u8);
+
+    /// Value to display at `offset` without any side effects
+    fn peek(&self, _offset: u32) -> u8 {
+        0
+    }
+
+    /// Return to the power-on state (called when the puzzle is reset)
+    fn reset(&mut self) {}
+
+    /// Take on the session's random seed (devices with their own seed
+    /// ignore it)
+    fn reseed(&mut self, _seed: u32) {}
+
+    /// Text produced by the device so far, if it produces any
+    fn output(&self) -> Option<String> {
+        None
+    }
+}
+
+/// A device shared between memory clones
+pub type SharedDevice = Arc<Mutex<dyn MmioDevice>>;
+
+/// A device mapped at a fixed address range
+#[derive(Debug, Clone)]
+pub struct MmioMapping {
+    pub start: u32,
+    pub size: u32,
+    pub device: SharedDevice,
+}
+
+impl MmioMapping {
+    pub fn contains(&self, address: u32) -> bool {
+        address >= self.start && address - self.start < self.size
+    }
+
+    /// Name of the mapped device
+    pub fn name(&self) -> String {
+        self.device
+            .lock()
+            .map(|d| d.name().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Output produced by the mapped device
+    pub fn output(&self) -> Option<String> {
+        self.device.lock().ok().and_then(|d| d.output())
+    }
+}
+
+/// Serial port: writing to offset 0 transmits a byte, offset 1 reads as
+/// the "ready" status (always 1)
+#[derive(Debug, Default)]
+pub struct SerialPort {
+    output: Vec<u8>,
+}
+
+impl SerialPort {
+    /// Size of the register window
+    pub const SIZE: u32 = 2;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bytes transmitted so far
+    pub fn bytes(&self) -> &[u8] {
+        &self.output
+    }
+}
+
+impl MmioDevice for SerialPort {
+    fn name(&self) -> &str {
+        "serial"
+    }
+
+    fn read(&mut self, offset: u32) -> u8 {
+        self.peek(offset)
+    }
+
+    fn write(&mut self, offset: u32, value: u8) {
+        if offset == 0 {
+            self.output.push(value);
+        }
+    }
+
+    fn peek(&self, offset: u32) -> u8 {
+        u8::from(offset == 1)
+    }
+
+    fn reset(&mut self) {
+        self.output.clear();
+    }
+
+    fn output(&self) -> Option<String> {
+        Some(String::from_utf8_lossy(&self.output).into_owned())
+    }
+}
+
+/// Deterministic random number register (xorshift32). Reading offset 0
+/// draws the next number and latches it, so offsets 1-3 (the rest of a
+/// dword read) return the same number; writing any byte restarts the
+/// generator from the seed.
+#[derive(Debug)]
+pub struct RandomDevice {
+    prng: Prng,
+    /// The number the last read of offset 0 drew
+    latched: u32,
+    /// Whether the seed comes from the session rather than the puzzle
+    follows_session: bool,
+}
+
+impl RandomDevice {
+    /// Size of the register window
+    pub const SIZE: u32 = 4;
+
+    /// A generator with a fixed seed
+    pub fn new(seed: u32) -> Self {
+        Self {
+            prng: Prng::new(seed),
+            latched: 0,
+            follows_session: false,
+        }
+    }
+
+    /// A generator seeded by the session (see `Debugger::set_rng_seed`)
+    pub fn session() -> Self {
+        Self {
+            prng: Prng::new(1),
+            latched: 0,
+            follows_session: true,
+        }
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.prng.seed()
+    }
+}
+
+impl MmioDevice for RandomDevice {
+    fn name(&self) -> &str {
+        "rng"
+    }
+
+    fn read(&mut self, offset: u32) -> u8 {
+        if offset.is_multiple_of(4) {
+            self.latched = self.prng.next_u32();
+        }
+        self.peek(offset)
+    }
+
+    fn write(&mut self, _offset: u32, _value: u8) {
+        self.prng.restart();
+        self.latched = 0;
+    }
+
+    fn peek(&self, offset: u32) -> u8 {
+        (self.latched >> ((offset % 4) * 8)) as u8
+    }
+
+    fn reset(&mut self) {
+        self.prng.restart();
+        self.latched = 0;
+    }
+
+    fn reseed(&mut self, seed: u32) {
+        if self.follows_session {
+            self.prng.reseed(seed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_device_is_deterministic() {
+        let mut a = RandomDevice::new(42);
+        let mut b = RandomDevice::new(42);
+        let first: Vec<u8> = (0..8).map(|i| a.read(i % 4)).collect();
+        let second: Vec<u8> = (0..8).map(|i| b.read(i % 4)).collect();
+        assert_eq!(first, second);
+
+        a.reset();
+        let again: Vec<u8> = (0..8).map(|i| a.read(i % 4)).collect();
+        assert_eq!(first, again);
+    }
+
+    #[test]
+    fn test_random_device_reseed() {
+        let mut fixed = RandomDevice::new(42);
+        let mut session = RandomDevice::session();
+        fixed.reseed(42);
+        session.reseed(42);
+        let first: Vec<u8> = (0..4).map(|i| fixed.read(i)).collect();
+        let second: Vec<u8> = (0..4).map(|i| session.read(i)).collect();
+        assert_eq!(first, second);
+
+        // A fixed seed stays put
+        fixed.reseed(7);
+        assert_eq!(fixed.seed(), 42);
+        session.reseed(7);
+        assert_eq!(session.seed(), 7);
+    }
+}
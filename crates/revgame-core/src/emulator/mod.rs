@@ -1,14 +1,29 @@
+mod assembler;
 mod cpu;
-mod memory;
+mod cycles;
+mod decoder;
+mod encoding;
 mod flags;
+mod groups;
 mod instructions;
-mod decoder;
-
-pub use cpu::{CpuState, Registers, Register};
-pub use memory::{Memory, MemoryRegion, Permissions, MemoryError};
-pub use flags::Eflags;
-pub use instructions::{Executor, ExecutionResult};
-pub use decoder::{DisassemblyLine, Disassembler};
+mod memory;
+mod mmio;
+mod rng;
+
+pub use assembler::{assemble, AsmError};
+pub use cpu::{CpuState, Register, Registers};
+pub use cycles::CycleModel;
+pub use decoder::{Disassembler, DisassemblyLine, DisassemblyMode, LineKind};
+pub use encoding::{EncodingField, FieldKind, InstructionEncoding};
+pub use flags::{Eflags, Flag};
+pub use groups::InstructionGroup;
+pub(crate) use instructions::MAX_INSTRUCTION_LEN;
+pub use instructions::{ExecutionResult, Executor};
+pub use memory::{
+    Memory, MemoryError, MemoryRegion, MemorySnapshot, Permissions, ADDRESS_SPACE, PAGE_SIZE,
+};
+pub use mmio::{MmioDevice, MmioMapping, RandomDevice, SerialPort, SharedDevice};
+pub use rng::Prng;
 
 use thiserror::Error;
 
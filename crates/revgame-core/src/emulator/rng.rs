@@ -0,0 +1,46 @@
+/// Deterministic xorshift32 generator behind the puzzle RNG. Restarting it
+/// from the seed replays the same sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prng {
+    seed: u32,
+    state: u32,
+}
+
+impl Prng {
+    pub fn new(seed: u32) -> Self {
+        let mut prng = Self { seed: 0, state: 0 };
+        prng.reseed(seed);
+        prng
+    }
+
+    /// The seed the sequence starts from
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// The value the next call to `next_u32` builds on
+    pub fn state(&self) -> u32 {
+        self.state
+    }
+
+    /// Start a new sequence from `seed`
+    pub fn reseed(&mut self, seed: u32) {
+        // xorshift gets stuck at zero
+        self.seed = if seed == 0 { 0x2545_F491 } else { seed };
+        self.state = self.seed;
+    }
+
+    /// Go back to the start of the sequence
+    pub fn restart(&mut self) {
+        self.state = self.seed;
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+}
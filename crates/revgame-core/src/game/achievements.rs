@@ -11,19 +11,20 @@ pub enum AchievementId {
 
     // Skill-based
     NoHintsUsed,
-    SpeedRunner,      // Complete puzzle in under par time
-    Minimalist,       // Minimal number of patches
-    OneShot,          // Solve with single patch
+    SpeedRunner, // Complete puzzle in under par time
+    Minimalist,  // Minimal number of patches
+    OneShot,     // Solve with single patch
 
     // Categories
-    BasicMaster,      // Complete all basic puzzles
-    FlowMaster,       // Complete all control-flow puzzles
-    CrackmeMaster,    // Complete all crackmes
+    BasicMaster,   // Complete all basic puzzles
+    FlowMaster,    // Complete all control-flow puzzles
+    CrackmeMaster, // Complete all crackmes
 
     // Special
-    UndoMaster,       // Use undo 10 times
-    Perfectionist,    // All achievements in a category
-    Experimenter,     // Try 5+ different approaches (undo/redo)
+    UndoMaster,    // Use undo 10 times
+    Perfectionist, // All achievements in a category
+    Experimenter,  // Try 5+ different approaches (undo/redo)
+    Clairvoyant,   // Predict 10 steps in a row
 
     // Streaks
     WinStreak3,
@@ -46,6 +47,7 @@ impl AchievementId {
             Self::UndoMaster => "Time Traveler",
             Self::Perfectionist => "Perfectionist",
             Self::Experimenter => "Mad Scientist",
+            Self::Clairvoyant => "Clairvoyant",
             Self::WinStreak3 => "On a Roll",
             Self::WinStreak5 => "Unstoppable",
             Self::WinStreak10 => "Legendary",
@@ -66,6 +68,7 @@ impl AchievementId {
             Self::UndoMaster => "Use undo 10 times",
             Self::Perfectionist => "Earn all achievements in a category",
             Self::Experimenter => "Try 5+ different approaches on one puzzle",
+            Self::Clairvoyant => "Predict what 10 steps in a row will change",
             Self::WinStreak3 => "Solve 3 puzzles in a row",
             Self::WinStreak5 => "Solve 5 puzzles in a row",
             Self::WinStreak10 => "Solve 10 puzzles in a row",
@@ -86,6 +89,7 @@ impl AchievementId {
             Self::UndoMaster => "⏪",
             Self::Perfectionist => "💎",
             Self::Experimenter => "🔬",
+            Self::Clairvoyant => "🔮",
             Self::WinStreak3 => "🔥",
             Self::WinStreak5 => "🔥🔥",
             Self::WinStreak10 => "🔥🔥🔥",
@@ -106,6 +110,7 @@ impl AchievementId {
             Self::UndoMaster => 25,
             Self::Perfectionist => 1000,
             Self::Experimenter => 50,
+            Self::Clairvoyant => 100,
             Self::WinStreak3 => 50,
             Self::WinStreak5 => 150,
             Self::WinStreak10 => 500,
@@ -122,6 +127,14 @@ pub struct PuzzleStats {
     pub patches_made: usize,
     pub time_seconds: Option<u64>,
     pub attempts: usize,
+
+    /// The player gave up and looked at the official solution
+    #[serde(default)]
+    pub revealed: bool,
+
+    /// Validation checks failed on the way
+    #[serde(default)]
+    pub failed_checks: usize,
 }
 
 impl PuzzleStats {
@@ -133,6 +146,8 @@ impl PuzzleStats {
             patches_made: 0,
             time_seconds: None,
             attempts: 0,
+            revealed: false,
+            failed_checks: 0,
         }
     }
 }
@@ -163,6 +178,10 @@ pub struct AchievementTracker {
 
     /// Total undos used
     pub total_undos: u32,
+
+    /// Puzzles given up on to see the solution
+    #[serde(default)]
+    pub total_revealed: u32,
 }
 
 impl Default for AchievementTracker {
@@ -182,6 +201,7 @@ impl AchievementTracker {
             total_completed: 0,
             total_patches: 0,
             total_undos: 0,
+            total_revealed: 0,
         }
     }
 
@@ -211,7 +231,8 @@ impl AchievementTracker {
         let mut newly_unlocked = Vec::new();
 
         // Update stats
-        let stats = self.puzzle_stats
+        let stats = self
+            .puzzle_stats
             .entry(puzzle_id.to_string())
             .or_insert_with(|| PuzzleStats::new(puzzle_id.to_string()));
 
@@ -266,6 +287,22 @@ impl AchievementTracker {
         self.current_streak = 0;
     }
 
+    /// Record giving up on a puzzle to see its solution. It counts as an
+    /// attempt but not a completion, and breaks the streak.
+    pub fn record_reveal(&mut self, puzzle_id: &str, hints_used: usize) {
+        let stats = self
+            .puzzle_stats
+            .entry(puzzle_id.to_string())
+            .or_insert_with(|| PuzzleStats::new(puzzle_id.to_string()));
+
+        stats.revealed = true;
+        stats.hints_used += hints_used;
+        stats.attempts += 1;
+
+        self.total_revealed += 1;
+        self.record_failure();
+    }
+
     /// Record an undo action
     pub fn record_undo(&mut self) -> Option<AchievementId> {
         self.total_undos += 1;
@@ -277,43 +314,67 @@ impl AchievementTracker {
         }
     }
 
+    /// Record a run of correct step predictions
+    pub fn record_prediction_streak(&mut self, streak: u32) -> Option<AchievementId> {
+        if streak >= 10 && self.unlock(AchievementId::Clairvoyant) {
+            Some(AchievementId::Clairvoyant)
+        } else {
+            None
+        }
+    }
+
     /// Check for category completion achievements
     fn check_category_completions(&mut self, newly_unlocked: &mut Vec<AchievementId>) {
         // Check if all puzzles in a category are completed
-        let basic_puzzles: Vec<_> = self.puzzle_stats.keys()
+        let basic_puzzles: Vec<_> = self
+            .puzzle_stats
+            .keys()
             .filter(|id| id.starts_with("basic-"))
             .collect();
 
-        if !basic_puzzles.is_empty() &&
-           basic_puzzles.iter().all(|id| self.puzzle_stats[*id].completed) &&
-           self.unlock(AchievementId::BasicMaster) {
+        if !basic_puzzles.is_empty()
+            && basic_puzzles
+                .iter()
+                .all(|id| self.puzzle_stats[*id].completed)
+            && self.unlock(AchievementId::BasicMaster)
+        {
             newly_unlocked.push(AchievementId::BasicMaster);
         }
 
-        let flow_puzzles: Vec<_> = self.puzzle_stats.keys()
+        let flow_puzzles: Vec<_> = self
+            .puzzle_stats
+            .keys()
             .filter(|id| id.starts_with("flow-"))
             .collect();
 
-        if !flow_puzzles.is_empty() &&
-           flow_puzzles.iter().all(|id| self.puzzle_stats[*id].completed) &&
-           self.unlock(AchievementId::FlowMaster) {
+        if !flow_puzzles.is_empty()
+            && flow_puzzles
+                .iter()
+                .all(|id| self.puzzle_stats[*id].completed)
+            && self.unlock(AchievementId::FlowMaster)
+        {
             newly_unlocked.push(AchievementId::FlowMaster);
         }
 
-        let crackme_puzzles: Vec<_> = self.puzzle_stats.keys()
+        let crackme_puzzles: Vec<_> = self
+            .puzzle_stats
+            .keys()
             .filter(|id| id.starts_with("crackme-"))
             .collect();
 
-        if !crackme_puzzles.is_empty() &&
-           crackme_puzzles.iter().all(|id| self.puzzle_stats[*id].completed) &&
-           self.unlock(AchievementId::CrackmeMaster) {
+        if !crackme_puzzles.is_empty()
+            && crackme_puzzles
+                .iter()
+                .all(|id| self.puzzle_stats[*id].completed)
+            && self.unlock(AchievementId::CrackmeMaster)
+        {
             newly_unlocked.push(AchievementId::CrackmeMaster);
         }
     }
 
     /// Get progress percentage
     pub fn progress_percentage(&self) -> f32 {
-        let total_achievements = 15; // Total number of achievements
+        let total_achievements = 16; // Total number of achievements
         (self.unlocked.len() as f32 / total_achievements as f32) * 100.0
     }
 
@@ -370,6 +431,23 @@ mod tests {
         assert_eq!(tracker.best_streak, 3);
     }
 
+    #[test]
+    fn test_reveal_tracking() {
+        let mut tracker = AchievementTracker::new();
+        tracker.record_completion("flow-001", 0, 1, None);
+        tracker.record_reveal("basic-002", 3);
+
+        assert_eq!(tracker.current_streak, 0);
+        assert_eq!(tracker.total_revealed, 1);
+        assert_eq!(tracker.total_completed, 1);
+        let stats = &tracker.puzzle_stats["basic-002"];
+        assert!(stats.revealed && !stats.completed);
+
+        // An unsolved, revealed puzzle holds back the category achievement
+        tracker.record_completion("basic-001", 0, 1, None);
+        assert!(!tracker.is_unlocked(AchievementId::BasicMaster));
+    }
+
     #[test]
     fn test_undo_tracking() {
         let mut tracker = AchievementTracker::new();
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+/// How much help the game gives, chosen by the player. Less help scores more.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AssistLevel {
+    /// Everything available, and hints are offered after failed attempts
+    Relaxed,
+    /// Everything available on request
+    #[default]
+    Standard,
+    /// No hints, undo, snapshots or run explanations
+    Hardcore,
+}
+
+impl AssistLevel {
+    /// All levels, most help first
+    pub const ALL: [AssistLevel; 3] = [
+        AssistLevel::Relaxed,
+        AssistLevel::Standard,
+        AssistLevel::Hardcore,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AssistLevel::Relaxed => "relaxed",
+            AssistLevel::Standard => "standard",
+            AssistLevel::Hardcore => "hardcore",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AssistLevel::Relaxed => "Relaxed",
+            AssistLevel::Standard => "Standard",
+            AssistLevel::Hardcore => "Hardcore",
+        }
+    }
+
+    pub fn allows_hints(&self) -> bool {
+        *self != AssistLevel::Hardcore
+    }
+
+    /// Whether hints are offered after a failed attempt regardless of settings
+    pub fn forces_eager_hints(&self) -> bool {
+        *self == AssistLevel::Relaxed
+    }
+
+    pub fn allows_undo(&self) -> bool {
+        *self != AssistLevel::Hardcore
+    }
+
+    /// Quick save and quick load
+    pub fn allows_snapshots(&self) -> bool {
+        *self != AssistLevel::Hardcore
+    }
+
+    /// The overlay explaining branches, loops and flags of the last run
+    pub fn allows_explainer(&self) -> bool {
+        *self != AssistLevel::Hardcore
+    }
+
+    /// Score multiplier, in percent
+    pub fn score_percent(&self) -> u32 {
+        match self {
+            AssistLevel::Relaxed => 50,
+            AssistLevel::Standard => 100,
+            AssistLevel::Hardcore => 200,
+        }
+    }
+
+    /// Points for solving a puzzle of the given difficulty at this level
+    pub fn score(&self, difficulty: u8) -> u32 {
+        100 * difficulty.max(1) as u32 * self.score_percent() / 100
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(AssistLevel::default(), AssistLevel::Standard);
+        assert_eq!(
+            AssistLevel::from_name("hardcore"),
+            Some(AssistLevel::Hardcore)
+        );
+        assert_eq!(AssistLevel::from_name("easy"), None);
+
+        assert!(AssistLevel::Relaxed < AssistLevel::Hardcore);
+        assert!(!AssistLevel::Hardcore.allows_undo());
+        assert!(AssistLevel::Standard.allows_hints());
+
+        assert_eq!(AssistLevel::Relaxed.score(2), 100);
+        assert_eq!(AssistLevel::Standard.score(2), 200);
+        assert_eq!(AssistLevel::Hardcore.score(2), 400);
+    }
+}
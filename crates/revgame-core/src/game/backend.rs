@@ -0,0 +1,172 @@
+/// Where saves are kept: files on native builds, an in-memory store on the
+/// web (not yet persisted anywhere)
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+/// Key-value storage for saves and settings. Keys are plain names
+/// (`save_quick`, `settings`), values JSON text.
+pub trait SaveBackend {
+    fn read(&self, key: &str) -> Result<Option<String>, String>;
+
+    fn write(&self, key: &str, value: &str) -> Result<(), String>;
+
+    /// Remove `key`, returning whether it existed
+    fn remove(&self, key: &str) -> Result<bool, String>;
+
+    fn keys(&self) -> Result<Vec<String>, String>;
+
+    /// When `key` was last written, if the backend knows
+    fn modified(&self, _key: &str) -> Option<SystemTime> {
+        None
+    }
+
+    /// The directory the backend writes to, if it keeps files at all
+    fn directory(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// One JSON file per key in a directory
+pub struct FileBackend {
+    dir: PathBuf,
+}
+
+impl FileBackend {
+    /// Use `dir`, creating it if needed
+    pub fn create(dir: PathBuf) -> Result<Self, String> {
+        if !dir.exists() {
+            fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create save directory: {}", e))?;
+        }
+        Ok(Self { dir })
+    }
+
+    /// Use `dir` as is
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+impl SaveBackend for FileBackend {
+    fn read(&self, key: &str) -> Result<Option<String>, String> {
+        let path = self.path(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        fs::read_to_string(&path)
+            .map(Some)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<(), String> {
+        fs::write(self.path(key), value).map_err(|e| format!("Failed to write save file: {}", e))
+    }
+
+    fn remove(&self, key: &str) -> Result<bool, String> {
+        let path = self.path(key);
+        if !path.exists() {
+            return Ok(false);
+        }
+        fs::remove_file(&path)
+            .map(|()| true)
+            .map_err(|e| format!("Failed to delete save file: {}", e))
+    }
+
+    fn keys(&self) -> Result<Vec<String>, String> {
+        let entries =
+            fs::read_dir(&self.dir).map_err(|e| format!("Failed to read save directory: {}", e))?;
+
+        let mut keys = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+            if let Some(key) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_suffix(".json"))
+            {
+                keys.push(key.to_string());
+            }
+        }
+        Ok(keys)
+    }
+
+    fn modified(&self, key: &str) -> Option<SystemTime> {
+        fs::metadata(self.path(key))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn directory(&self) -> Option<&Path> {
+        Some(&self.dir)
+    }
+}
+
+/// Saves held in memory and lost with it. Clones share the same store.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBackend {
+    entries: Rc<RefCell<BTreeMap<String, String>>>,
+}
+
+thread_local! {
+    static SHARED: MemoryBackend = MemoryBackend::new();
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The store `SaveManager::new` uses on the web
+    pub fn shared() -> Self {
+        SHARED.with(Clone::clone)
+    }
+}
+
+impl SaveBackend for MemoryBackend {
+    fn read(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.entries.borrow().get(key).cloned())
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<(), String> {
+        self.entries
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<bool, String> {
+        Ok(self.entries.borrow_mut().remove(key).is_some())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, String> {
+        Ok(self.entries.borrow().keys().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_backend() {
+        let backend = MemoryBackend::new();
+        backend.write("settings", "{}").unwrap();
+        backend.write("save_quick", "[]").unwrap();
+        assert!(backend.remove("settings").unwrap());
+        assert!(!backend.remove("settings").unwrap());
+        assert_eq!(backend.keys().unwrap(), ["save_quick"]);
+
+        let clone = backend.clone();
+        clone.write("save_quick", "[1]").unwrap();
+        assert_eq!(backend.read("save_quick").unwrap().as_deref(), Some("[1]"));
+        assert_eq!(MemoryBackend::new().read("save_quick").unwrap(), None);
+    }
+}
@@ -0,0 +1,197 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::state::GameState;
+use crate::puzzle::PuzzleCategory;
+
+/// Name of the campaign file in the puzzles directory
+pub const CAMPAIGN_FILE: &str = "campaign.toml";
+
+/// A concept quiz standing between two chapters, made of instruction drills
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub id: String,
+    pub title: String,
+    /// Name of the chapter (puzzle directory without its number) the quiz unlocks
+    pub unlocks: String,
+    /// Instructions the questions are drawn from
+    pub mnemonics: Vec<String>,
+    #[serde(default = "default_questions")]
+    pub questions: u32,
+    /// Correct answers needed to pass
+    pub pass: u32,
+}
+
+fn default_questions() -> u32 {
+    5
+}
+
+/// How a checkpoint quiz has gone so far
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckpointResult {
+    pub attempts: u32,
+    /// Most correct answers in one attempt
+    pub best: u32,
+    pub passed: bool,
+}
+
+/// The checkpoints configured in the campaign file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Campaign {
+    #[serde(default, rename = "checkpoint")]
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+impl Campaign {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let campaign: Self =
+            toml::from_str(source).map_err(|e| format!("Failed to parse campaign: {}", e))?;
+        for checkpoint in &campaign.checkpoints {
+            if checkpoint.mnemonics.is_empty() {
+                return Err(format!(
+                    "Checkpoint {} has no mnemonics to ask about",
+                    checkpoint.id
+                ));
+            }
+            if checkpoint.pass > checkpoint.questions {
+                return Err(format!(
+                    "Checkpoint {} needs {} correct answers out of only {}",
+                    checkpoint.id, checkpoint.pass, checkpoint.questions
+                ));
+            }
+        }
+        Ok(campaign)
+    }
+
+    /// Load the campaign file from `puzzles_dir`. Without one there are no
+    /// checkpoints and every chapter is open.
+    pub fn load(puzzles_dir: &Path) -> Result<Self, String> {
+        let path = puzzles_dir.join(CAMPAIGN_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let source = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read campaign: {}", e))?;
+        Self::parse(&source)
+    }
+
+    pub fn checkpoint(&self, id: &str) -> Option<&Checkpoint> {
+        self.checkpoints
+            .iter()
+            .find(|checkpoint| checkpoint.id == id)
+    }
+
+    /// The checkpoint still standing in front of `chapter`, if any. A chapter
+    /// the player already solved puzzles in stays open, so saves from before
+    /// a checkpoint was added keep their progress.
+    pub fn gate<'a>(
+        &'a self,
+        chapter: &PuzzleCategory,
+        state: &GameState,
+    ) -> Option<&'a Checkpoint> {
+        if chapter
+            .puzzles
+            .iter()
+            .any(|puzzle| state.is_completed(&puzzle.id))
+        {
+            return None;
+        }
+        self.checkpoints.iter().find(|checkpoint| {
+            checkpoint.unlocks == chapter.name && !state.checkpoint_passed(&checkpoint.id)
+        })
+    }
+
+    /// Lock the puzzles of every chapter behind an unpassed checkpoint
+    pub fn apply_locks(&self, chapters: &mut [PuzzleCategory], state: &GameState) {
+        for chapter in chapters {
+            let locked = self.gate(chapter, state).is_some();
+            for puzzle in &mut chapter.puzzles {
+                puzzle.is_locked = locked;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::PuzzleListItem;
+
+    fn chapter(name: &str, ids: &[&str]) -> PuzzleCategory {
+        PuzzleCategory {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            puzzles: ids
+                .iter()
+                .map(|id| PuzzleListItem {
+                    id: id.to_string(),
+                    title: id.to_string(),
+                    difficulty: 1,
+                    category: name.to_string(),
+                    brief: String::new(),
+                    file_path: Default::default(),
+                    is_locked: false,
+                    prerequisites: Vec::new(),
+                    concepts: Vec::new(),
+                    estimated_time_minutes: None,
+                    mnemonics: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    const CAMPAIGN: &str = r#"
+        [[checkpoint]]
+        id = "flags"
+        title = "Flags"
+        unlocks = "control-flow"
+        mnemonics = ["CMP", "TEST"]
+        pass = 4
+    "#;
+
+    #[test]
+    fn test_checkpoint_locks_chapter_until_passed() {
+        let campaign = Campaign::parse(CAMPAIGN).unwrap();
+        assert_eq!(campaign.checkpoint("flags").unwrap().questions, 5);
+
+        let mut chapters = vec![
+            chapter("basics", &["basic-001"]),
+            chapter("control-flow", &["flow-001"]),
+        ];
+        let mut state = GameState::new();
+        campaign.apply_locks(&mut chapters, &state);
+        assert!(!chapters[0].puzzles[0].is_locked);
+        assert!(chapters[1].puzzles[0].is_locked);
+        assert_eq!(campaign.gate(&chapters[1], &state).unwrap().id, "flags");
+
+        assert!(!state.record_checkpoint("flags", 3, false));
+        campaign.apply_locks(&mut chapters, &state);
+        assert!(chapters[1].puzzles[0].is_locked);
+
+        assert!(state.record_checkpoint("flags", 4, true));
+        assert!(!state.record_checkpoint("flags", 1, false));
+        campaign.apply_locks(&mut chapters, &state);
+        assert!(!chapters[1].puzzles[0].is_locked);
+        let result = &state.checkpoints["flags"];
+        assert_eq!((result.attempts, result.best, result.passed), (3, 4, true));
+    }
+
+    #[test]
+    fn test_solved_chapters_stay_open() {
+        let campaign = Campaign::parse(CAMPAIGN).unwrap();
+        let mut chapters = vec![chapter("control-flow", &["flow-001", "flow-002"])];
+        let mut state = GameState::new();
+        state.completed_puzzles.insert("flow-002".to_string());
+        campaign.apply_locks(&mut chapters, &state);
+        assert!(!chapters[0].puzzles[0].is_locked);
+    }
+
+    #[test]
+    fn test_rejects_impossible_checkpoints() {
+        assert!(Campaign::parse(&CAMPAIGN.replace("pass = 4", "pass = 6")).is_err());
+        assert!(Campaign::parse(&CAMPAIGN.replace(r#"["CMP", "TEST"]"#, "[]")).is_err());
+        assert_eq!(Campaign::parse("").unwrap(), Campaign::default());
+    }
+}
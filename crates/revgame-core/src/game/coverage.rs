@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+/// How far the player has got with an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Encounter {
+    /// Never in the code of a puzzle played
+    Never,
+    /// In the code of a puzzle played, but never run
+    Seen,
+    /// Run at least once
+    Executed,
+}
+
+/// Instructions met in one puzzle
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PuzzleUsage {
+    /// Mnemonics in the puzzle's code as loaded
+    pub seen: BTreeSet<String>,
+    /// Times each mnemonic was executed
+    pub executed: BTreeMap<String, u64>,
+}
+
+/// Instructions met and run, by puzzle, across every session
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InstructionCoverage {
+    puzzles: BTreeMap<String, PuzzleUsage>,
+}
+
+impl InstructionCoverage {
+    /// Note the mnemonics in a puzzle's code
+    pub fn record_seen(&mut self, puzzle: &str, mnemonics: impl IntoIterator<Item = String>) {
+        self.puzzles
+            .entry(puzzle.to_string())
+            .or_default()
+            .seen
+            .extend(mnemonics);
+    }
+
+    /// Add executed counts by mnemonic for a puzzle
+    pub fn record_executed(&mut self, puzzle: &str, counts: BTreeMap<String, u64>) {
+        if counts.is_empty() {
+            return;
+        }
+        let usage = self.puzzles.entry(puzzle.to_string()).or_default();
+        for (mnemonic, count) in counts {
+            *usage.executed.entry(mnemonic).or_insert(0) += count;
+        }
+    }
+
+    pub fn puzzle(&self, id: &str) -> Option<&PuzzleUsage> {
+        self.puzzles.get(id)
+    }
+
+    /// Times a mnemonic was executed, over all puzzles
+    pub fn executed(&self, mnemonic: &str) -> u64 {
+        let mnemonic = mnemonic.to_uppercase();
+        self.puzzles
+            .values()
+            .filter_map(|usage| usage.executed.get(&mnemonic))
+            .sum()
+    }
+
+    /// Puzzles whose code had a mnemonic in it
+    pub fn puzzles_with(&self, mnemonic: &str) -> usize {
+        let mnemonic = mnemonic.to_uppercase();
+        self.puzzles
+            .values()
+            .filter(|usage| {
+                usage.seen.contains(&mnemonic) || usage.executed.contains_key(&mnemonic)
+            })
+            .count()
+    }
+
+    pub fn encounter(&self, mnemonic: &str) -> Encounter {
+        if self.executed(mnemonic) > 0 {
+            Encounter::Executed
+        } else if self.puzzles_with(mnemonic) > 0 {
+            Encounter::Seen
+        } else {
+            Encounter::Never
+        }
+    }
+
+    /// How many of `mnemonics` were executed, only seen, and never met
+    pub fn tally<'a>(&self, mnemonics: impl IntoIterator<Item = &'a str>) -> (usize, usize, usize) {
+        mnemonics
+            .into_iter()
+            .fold((0, 0, 0), |(executed, seen, never), mnemonic| {
+                match self.encounter(mnemonic) {
+                    Encounter::Executed => (executed + 1, seen, never),
+                    Encounter::Seen => (executed, seen + 1, never),
+                    Encounter::Never => (executed, seen, never + 1),
+                }
+            })
+    }
+
+    /// Those of `mnemonics` never executed, in the order given
+    pub fn unexplored<'a>(&self, mnemonics: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+        mnemonics
+            .into_iter()
+            .filter(|mnemonic| self.encounter(mnemonic) != Encounter::Executed)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_across_puzzles() {
+        let mut coverage = InstructionCoverage::default();
+        coverage.record_seen("a", ["MOV", "XOR", "HLT"].map(String::from));
+        coverage.record_executed(
+            "a",
+            BTreeMap::from([("MOV".to_string(), 3), ("HLT".to_string(), 1)]),
+        );
+        coverage.record_executed("b", BTreeMap::from([("MOV".to_string(), 2)]));
+
+        assert_eq!(coverage.executed("mov"), 5);
+        assert_eq!(coverage.puzzles_with("MOV"), 2);
+        assert_eq!(coverage.encounter("XOR"), Encounter::Seen);
+        assert_eq!(coverage.encounter("ROL"), Encounter::Never);
+        assert_eq!(coverage.tally(["MOV", "XOR", "ROL", "HLT"]), (2, 1, 1));
+        assert_eq!(coverage.unexplored(["MOV", "XOR", "ROL"]), ["XOR", "ROL"]);
+        assert_eq!(coverage.puzzle("a").unwrap().executed["MOV"], 3);
+    }
+}
@@ -0,0 +1,191 @@
+use super::format_time;
+
+/// One side of a hotseat duel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuelPlayer {
+    pub name: String,
+    /// Time spent on their own turns
+    pub time_ms: u64,
+    /// Hints and patches in their session so far
+    pub hints: usize,
+    pub patches: usize,
+    pub turns: usize,
+    pub solved: bool,
+    pub gave_up: bool,
+}
+
+impl DuelPlayer {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            time_ms: 0,
+            hints: 0,
+            patches: 0,
+            turns: 0,
+            solved: false,
+            gave_up: false,
+        }
+    }
+
+    /// Solved or gave up: no more turns
+    pub fn is_done(&self) -> bool {
+        self.solved || self.gave_up
+    }
+
+    fn result(&self) -> &'static str {
+        match (self.solved, self.gave_up) {
+            (true, _) => "solved",
+            (_, true) => "gave up",
+            _ => "playing",
+        }
+    }
+}
+
+/// Two players taking turns at the same seeded puzzle, each in their own
+/// session. Times are milliseconds on whatever clock the caller passes in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuelState {
+    pub puzzle_id: String,
+    /// RNG seed both sessions run with
+    pub seed: u32,
+    pub players: [DuelPlayer; 2],
+    /// Whose turn it is
+    pub current: usize,
+    turn_started_ms: u64,
+}
+
+impl DuelState {
+    pub fn new(puzzle_id: &str, seed: u32, names: [&str; 2], now_ms: u64) -> Self {
+        Self {
+            puzzle_id: puzzle_id.to_string(),
+            seed,
+            players: names.map(DuelPlayer::new),
+            current: 0,
+            turn_started_ms: now_ms,
+        }
+    }
+
+    pub fn current_player(&self) -> &DuelPlayer {
+        &self.players[self.current]
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.players.iter().all(DuelPlayer::is_done)
+    }
+
+    /// Time the current turn has taken so far
+    pub fn turn_elapsed_ms(&self, now_ms: u64) -> u64 {
+        now_ms.saturating_sub(self.turn_started_ms)
+    }
+
+    /// End the current player's turn with the hints and patches their
+    /// session has used so far. Returns whether the other player is up.
+    pub fn end_turn(&mut self, now_ms: u64, hints: usize, patches: usize, solved: bool) -> bool {
+        self.close_turn(now_ms, hints, patches);
+        self.players[self.current].solved |= solved;
+        self.next_turn(now_ms)
+    }
+
+    /// The current player stops trying. Returns whether the other player
+    /// is up.
+    pub fn give_up(&mut self, now_ms: u64, hints: usize, patches: usize) -> bool {
+        self.close_turn(now_ms, hints, patches);
+        self.players[self.current].gave_up = true;
+        self.next_turn(now_ms)
+    }
+
+    fn close_turn(&mut self, now_ms: u64, hints: usize, patches: usize) {
+        let elapsed = self.turn_elapsed_ms(now_ms);
+        let player = &mut self.players[self.current];
+        player.time_ms += elapsed;
+        player.hints = hints;
+        player.patches = patches;
+        player.turns += 1;
+    }
+
+    /// Hand over to the other player unless they're done
+    fn next_turn(&mut self, now_ms: u64) -> bool {
+        self.turn_started_ms = now_ms;
+        let other = 1 - self.current;
+        if self.players[other].is_done() {
+            return false;
+        }
+        self.current = other;
+        true
+    }
+
+    /// Index of the winner once the duel is over: of the players who
+    /// solved it, fewer hints wins, then the faster time, then fewer patches
+    pub fn winner(&self) -> Option<usize> {
+        if !self.is_finished() {
+            return None;
+        }
+        let key = |player: &DuelPlayer| (player.hints, player.time_ms, player.patches);
+        match self
+            .players
+            .each_ref()
+            .map(|player| player.solved.then(|| key(player)))
+        {
+            [Some(a), Some(b)] if a == b => None,
+            [Some(a), Some(b)] => Some(if a < b { 0 } else { 1 }),
+            [Some(_), None] => Some(0),
+            [None, Some(_)] => Some(1),
+            [None, None] => None,
+        }
+    }
+
+    /// Scoreboard rows: name, time, hints, patches and result per player
+    pub fn scoreboard(&self) -> Vec<String> {
+        let mut rows = vec![format!(
+            "{:<12} {:>8} {:>6} {:>8}  result",
+            "player", "time", "hints", "patches"
+        )];
+        rows.extend(self.players.iter().map(|player| {
+            format!(
+                "{:<12} {:>8} {:>6} {:>8}  {}",
+                player.name,
+                format_time(player.time_ms),
+                player.hints,
+                player.patches,
+                player.result()
+            )
+        }));
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duel_turns_and_winner() {
+        let mut duel = DuelState::new("basic-001", 7, ["Ada", "Bob"], 0);
+        assert!(duel.end_turn(10_000, 1, 0, false));
+        assert_eq!(duel.current_player().name, "Bob");
+        assert!(duel.end_turn(25_000, 0, 2, true));
+        assert_eq!(duel.current, 0);
+
+        // Bob is done, so Ada keeps going until she's done too
+        assert!(!duel.end_turn(30_000, 1, 1, false));
+        assert_eq!(duel.winner(), None);
+        assert!(!duel.end_turn(32_000, 1, 2, true));
+        assert!(duel.is_finished());
+        assert_eq!(duel.players[0].time_ms, 17_000);
+        assert_eq!(duel.players[0].turns, 3);
+        // Bob took longer but used no hints
+        assert_eq!(duel.winner(), Some(1));
+        assert_eq!(
+            duel.scoreboard()[2],
+            "Bob            0:15.0      0        2  solved"
+        );
+    }
+
+    #[test]
+    fn test_duel_give_up() {
+        let mut duel = DuelState::new("basic-001", 7, ["Ada", "Bob"], 0);
+        assert!(duel.give_up(1000, 0, 0));
+        assert!(!duel.end_turn(5000, 3, 1, true));
+        assert_eq!(duel.winner(), Some(1));
+    }
+}
@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+
+/// A learning-relevant action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LearningEvent {
+    PuzzleStarted,
+    Step,
+    Run,
+    BreakpointSet,
+    Patch,
+    HintUsed,
+    ValidationFailed,
+    Solved,
+}
+
+/// An event with the puzzle it happened in and when (unix seconds)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoggedEvent {
+    pub puzzle: String,
+    pub at: u64,
+    pub event: LearningEvent,
+}
+
+/// One attempt at a puzzle, from its start to the next puzzle start
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attempt {
+    pub puzzle: String,
+    /// Steps taken before the first breakpoint was set
+    pub steps_before_first_breakpoint: Option<usize>,
+    pub seconds_to_first_patch: Option<u64>,
+    pub validation_failures: usize,
+    /// Ran the program before stepping through any of it
+    pub ran_before_stepping: bool,
+    pub hints: usize,
+    pub solved: bool,
+}
+
+/// Opt-in, on-device log of learning events. Only kept in the local save.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LearningLog {
+    events: Vec<LoggedEvent>,
+}
+
+impl LearningLog {
+    /// Oldest events are dropped past this many
+    pub const MAX_EVENTS: usize = 5000;
+
+    /// Attempts needed before patterns are pointed out
+    pub const MIN_ATTEMPTS: usize = 3;
+
+    pub fn record(&mut self, puzzle: &str, event: LearningEvent, at: u64) {
+        self.events.push(LoggedEvent {
+            puzzle: puzzle.to_string(),
+            at,
+            event,
+        });
+        if self.events.len() > Self::MAX_EVENTS {
+            let excess = self.events.len() - Self::MAX_EVENTS;
+            self.events.drain(..excess);
+        }
+    }
+
+    pub fn events(&self) -> &[LoggedEvent] {
+        &self.events
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Events grouped into attempts, oldest first
+    pub fn attempts(&self) -> Vec<Attempt> {
+        let mut attempts = Vec::new();
+        let mut current: Option<(Attempt, u64, usize)> = None;
+
+        for logged in &self.events {
+            if logged.event == LearningEvent::PuzzleStarted {
+                attempts.extend(current.take().map(|(attempt, _, _)| attempt));
+                let attempt = Attempt {
+                    puzzle: logged.puzzle.clone(),
+                    ..Attempt::default()
+                };
+                current = Some((attempt, logged.at, 0));
+                continue;
+            }
+            // Events from before the first recorded start can't be placed
+            let Some((ref mut attempt, started, ref mut steps)) = current else {
+                continue;
+            };
+            match logged.event {
+                LearningEvent::Step => *steps += 1,
+                // Running to a breakpoint means the code was read first
+                LearningEvent::Run
+                    if *steps == 0 && attempt.steps_before_first_breakpoint.is_none() =>
+                {
+                    attempt.ran_before_stepping = true;
+                }
+                LearningEvent::BreakpointSet => {
+                    attempt.steps_before_first_breakpoint.get_or_insert(*steps);
+                }
+                LearningEvent::Patch => {
+                    attempt
+                        .seconds_to_first_patch
+                        .get_or_insert(logged.at.saturating_sub(started));
+                }
+                LearningEvent::HintUsed => attempt.hints += 1,
+                LearningEvent::ValidationFailed => attempt.validation_failures += 1,
+                LearningEvent::Solved => attempt.solved = true,
+                _ => {}
+            }
+        }
+        attempts.extend(current.map(|(attempt, _, _)| attempt));
+        attempts
+    }
+
+    /// Habits worth pointing out, once there are enough attempts to go on
+    pub fn insights(&self) -> Vec<String> {
+        let attempts = self.attempts();
+        if attempts.len() < Self::MIN_ATTEMPTS {
+            return Vec::new();
+        }
+        let count = attempts.len();
+        let mut insights = Vec::new();
+
+        let ran_first = attempts.iter().filter(|a| a.ran_before_stepping).count();
+        if ran_first * 2 > count {
+            insights.push(format!(
+                "You tend to run before reading the disassembly ({} of {} puzzles): stepping through the first few instructions shows what the program checks",
+                ran_first, count
+            ));
+        }
+
+        let failures: usize = attempts.iter().map(|a| a.validation_failures).sum();
+        if failures >= 2 * count {
+            insights.push(format!(
+                "About {} failed checks per puzzle: predict what a patch does before running it",
+                failures / count
+            ));
+        }
+
+        let steps: Vec<usize> = attempts
+            .iter()
+            .filter_map(|a| a.steps_before_first_breakpoint)
+            .collect();
+        if !steps.is_empty() && steps.iter().sum::<usize>() / steps.len() >= 20 {
+            insights.push(
+                "You step a lot before setting a breakpoint: F9 on a later instruction and F5 gets there in one go".to_string(),
+            );
+        }
+
+        let patch_times: Vec<u64> = attempts
+            .iter()
+            .filter_map(|a| a.seconds_to_first_patch)
+            .collect();
+        if !patch_times.is_empty() {
+            let average = patch_times.iter().sum::<u64>() / patch_times.len() as u64;
+            if average < 30 {
+                insights.push(format!(
+                    "Your first patch comes {}s in on average: a minute spent reading first often saves a few undos",
+                    average
+                ));
+            } else if average > 600 {
+                insights.push(format!(
+                    "Your first patch comes {} minutes in on average: trying a small patch early is cheap, since undo is always there",
+                    average / 60
+                ));
+            }
+        }
+
+        let hinted = attempts.iter().filter(|a| a.hints > 0).count();
+        if hinted == count {
+            insights.push(
+                "You've used hints on every puzzle: try the next one without, then ask if stuck"
+                    .to_string(),
+            );
+        }
+
+        insights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LearningEvent::*;
+
+    fn log(events: &[(&str, u64, LearningEvent)]) -> LearningLog {
+        let mut log = LearningLog::default();
+        for &(puzzle, at, event) in events {
+            log.record(puzzle, event, at);
+        }
+        log
+    }
+
+    #[test]
+    fn test_attempts() {
+        let log = log(&[
+            ("a", 0, Step),
+            ("a", 100, PuzzleStarted),
+            ("a", 101, Run),
+            ("a", 102, Step),
+            ("a", 103, Step),
+            ("a", 104, BreakpointSet),
+            ("a", 150, Patch),
+            ("a", 160, Patch),
+            ("a", 170, ValidationFailed),
+            ("a", 180, Solved),
+            ("b", 200, PuzzleStarted),
+            ("b", 201, Step),
+            ("b", 202, Run),
+        ]);
+
+        let attempts = log.attempts();
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(attempts[0].puzzle, "a");
+        assert!(attempts[0].ran_before_stepping);
+        assert_eq!(attempts[0].steps_before_first_breakpoint, Some(2));
+        assert_eq!(attempts[0].seconds_to_first_patch, Some(50));
+        assert_eq!(attempts[0].validation_failures, 1);
+        assert!(attempts[0].solved);
+        assert!(!attempts[1].ran_before_stepping);
+        assert!(!attempts[1].solved);
+    }
+
+    #[test]
+    fn test_insights_need_enough_attempts() {
+        let mut events = vec![
+            ("a", 0, PuzzleStarted),
+            ("a", 1, Run),
+            ("a", 5, Patch),
+            ("b", 1000, PuzzleStarted),
+            ("b", 1001, Run),
+            ("b", 1005, Patch),
+        ];
+        assert!(log(&events).insights().is_empty());
+
+        events.extend([("c", 3000, PuzzleStarted), ("c", 3001, Run)]);
+        let insights = log(&events).insights();
+        assert!(insights[0].starts_with("You tend to run before reading the disassembly (3 of 3"));
+        assert!(insights
+            .iter()
+            .any(|insight| insight.starts_with("Your first patch comes 5s in")));
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        let mut log = LearningLog::default();
+        for at in 0..(LearningLog::MAX_EVENTS as u64 + 10) {
+            log.record("a", Step, at);
+        }
+        assert_eq!(log.events().len(), LearningLog::MAX_EVENTS);
+        assert_eq!(log.events()[0].at, 10);
+    }
+}
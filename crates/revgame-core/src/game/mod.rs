@@ -1,7 +1,37 @@
-mod state;
 mod achievements;
+mod assist;
+mod backend;
+mod campaign;
+mod coverage;
+mod duel;
+mod learning;
+#[cfg(feature = "online")]
+mod online;
+mod recommend;
+mod replay;
 mod save;
+mod settings;
+mod skills;
+mod state;
+mod time_attack;
 
-pub use state::GameState;
 pub use achievements::{AchievementId, AchievementTracker, PuzzleStats};
-pub use save::{SaveManager, SaveInfo};
+pub use assist::AssistLevel;
+pub use backend::{FileBackend, MemoryBackend, SaveBackend};
+pub use campaign::{Campaign, Checkpoint, CheckpointResult, CAMPAIGN_FILE};
+pub use coverage::{Encounter, InstructionCoverage, PuzzleUsage};
+pub use duel::{DuelPlayer, DuelState};
+pub use learning::{Attempt, LearningEvent, LearningLog, LoggedEvent};
+#[cfg(feature = "online")]
+pub use online::{replay_hash, CompletionProof, LeaderboardClient, RemoteScore};
+pub use recommend::{
+    recommend, review_puzzles, struggle, struggled_concepts, Recommendation, STRUGGLE_THRESHOLD,
+};
+pub use replay::{Replay, ReplayEvent};
+pub use save::{SaveInfo, SaveManager, SaveOptions};
+pub use settings::{Experience, Settings};
+pub use skills::{skill_tree, ConceptMastery, SkillNode, MASTERY_SOLVES, SKILL_TREE};
+pub use state::{GameState, PuzzleMarks};
+pub use time_attack::{
+    format_time, Leaderboard, LeaderboardEntry, TimeAttack, LEADERBOARD_SIZE, TIME_ATTACK_COURSE,
+};
@@ -0,0 +1,324 @@
+//! Opt-in online leaderboard, built only with the `online` feature. Talks
+//! HTTPS through ureq; plain `http://` is only accepted for servers on this
+//! machine, so scores never cross the network in the clear.
+
+use std::io::Read;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::Replay;
+
+/// How long to wait for the server before giving up
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest response read, so a misbehaving server can't fill memory
+const MAX_RESPONSE: u64 = 256 * 1024;
+
+/// What gets sent when a puzzle is solved: no save data, just the result
+/// and a hash of the replay that produced it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletionProof {
+    pub puzzle_id: String,
+    pub profile: String,
+    pub replay_hash: String,
+    pub time_ms: u64,
+    pub patches: usize,
+}
+
+impl CompletionProof {
+    /// Proof for a recorded solve
+    pub fn from_replay(profile: &str, replay: &Replay) -> Self {
+        Self {
+            puzzle_id: replay.puzzle.clone(),
+            profile: profile.to_string(),
+            replay_hash: replay_hash(replay),
+            time_ms: replay.duration_ms(),
+            patches: replay
+                .events
+                .iter()
+                .rev()
+                .find_map(|event| event.patches)
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// A row of a fetched leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteScore {
+    pub profile: String,
+    pub time_ms: u64,
+    pub patches: usize,
+}
+
+/// FNV-1a of the replay's commands and times, as 16 hex digits
+pub fn replay_hash(replay: &Replay) -> String {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash = (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01B3);
+        }
+    };
+    feed(replay.puzzle.as_bytes());
+    for event in &replay.events {
+        feed(&event.at_ms.to_le_bytes());
+        feed(event.command.as_bytes());
+        feed(&[0]);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Client for a leaderboard server at `https://host[:port][/base]`, which
+/// takes `POST <base>/submit` and answers `GET <base>/leaderboard/<puzzle>`
+#[derive(Debug, Clone)]
+pub struct LeaderboardClient {
+    /// Endpoint without a trailing slash
+    base: String,
+    agent: ureq::Agent,
+}
+
+impl LeaderboardClient {
+    pub fn new(endpoint: &str) -> Result<Self, String> {
+        let endpoint = endpoint.trim().trim_end_matches('/');
+        let rest = match endpoint.split_once("://") {
+            Some(("https", rest)) => rest,
+            Some(("http", rest)) if is_local(rest) => rest,
+            Some(("http", _)) => {
+                return Err(format!(
+                    "Leaderboard endpoint must use https:// unless it's on this machine: {}",
+                    endpoint
+                ))
+            }
+            _ => {
+                return Err(format!(
+                    "Leaderboard endpoint must start with https://: {}",
+                    endpoint
+                ))
+            }
+        };
+        if authority(rest).is_empty() {
+            return Err(format!("No host in {}", endpoint));
+        }
+        Ok(Self {
+            base: endpoint.to_string(),
+            agent: ureq::AgentBuilder::new().timeout(TIMEOUT).build(),
+        })
+    }
+
+    pub fn submit(&self, proof: &CompletionProof) -> Result<(), String> {
+        let body =
+            serde_json::to_string(proof).map_err(|e| format!("Failed to encode score: {}", e))?;
+        let request = self
+            .agent
+            .post(&format!("{}/submit", self.base))
+            .set("Content-Type", "application/json");
+        read_body(request.send_string(&body)).map(|_| ())
+    }
+
+    /// Best scores for a puzzle, as the server orders them
+    pub fn fetch(&self, puzzle_id: &str) -> Result<Vec<RemoteScore>, String> {
+        let url = format!("{}/leaderboard/{}", self.base, path_segment(puzzle_id)?);
+        let body = read_body(self.agent.get(&url).call())?;
+        // A stray non-UTF-8 byte in a profile name shouldn't hide the board
+        serde_json::from_str(&String::from_utf8_lossy(&body))
+            .map_err(|e| format!("Bad leaderboard from server: {}", e))
+    }
+}
+
+/// The body of a 2xx answer, at most `MAX_RESPONSE` bytes
+fn read_body(response: Result<ureq::Response, ureq::Error>) -> Result<Vec<u8>, String> {
+    let response = match response {
+        Ok(response) => response,
+        Err(ureq::Error::Status(code, _)) => {
+            return Err(format!("Leaderboard server answered {}", code))
+        }
+        Err(e) => return Err(format!("Leaderboard server unreachable: {}", e)),
+    };
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_RESPONSE)
+        .read_to_end(&mut body)
+        .map_err(|e| format!("Leaderboard server unreachable: {}", e))?;
+    Ok(body)
+}
+
+/// `host[:port]` of the part of an endpoint after the scheme
+fn authority(rest: &str) -> &str {
+    rest.split('/').next().unwrap_or("")
+}
+
+/// Whether the server is this machine, where plain HTTP never leaves it
+fn is_local(rest: &str) -> bool {
+    let authority = authority(rest);
+    let host = match authority.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or(""),
+        None => authority.split(':').next().unwrap_or(""),
+    };
+    host == "localhost"
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Percent-encode a puzzle id for use as one path segment. Puzzle ids come
+/// from community files, so control characters are refused outright.
+fn path_segment(id: &str) -> Result<String, String> {
+    if id.chars().any(char::is_control) {
+        return Err(format!("Puzzle id {:?} can't be sent to a leaderboard", id));
+    }
+    Ok(id
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Answer one request with `response`, handing back what was asked
+    fn serve_once(response: &'static [u8]) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/scores", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8_lossy(&body));
+            stream.write_all(response).unwrap();
+            request
+        });
+        (endpoint, server)
+    }
+
+    #[test]
+    fn test_submit_and_fetch() {
+        let mut replay = Replay {
+            puzzle: "basic-001".to_string(),
+            ..Replay::default()
+        };
+        replay.record(4200, "patch! 0x1005 90 90".to_string(), 0, 1);
+        let proof = CompletionProof::from_replay("Ada", &replay);
+        assert_eq!(
+            (proof.time_ms, proof.patches, proof.replay_hash.len()),
+            (4200, 1, 16)
+        );
+
+        let (endpoint, server) = serve_once(b"HTTP/1.1 204 No Content\r\n\r\n");
+        LeaderboardClient::new(&endpoint)
+            .unwrap()
+            .submit(&proof)
+            .unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /scores/submit HTTP/1.1\r\n"));
+        assert!(request.ends_with(&serde_json::to_string(&proof).unwrap()));
+
+        let (endpoint, server) = serve_once(
+            b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n[{\"profile\":\"Bob\",\"time_ms\":3000,\"patches\":1}]",
+        );
+        let scores = LeaderboardClient::new(&endpoint)
+            .unwrap()
+            .fetch("basic-001")
+            .unwrap();
+        assert!(server
+            .join()
+            .unwrap()
+            .starts_with("GET /scores/leaderboard/basic-001 "));
+        assert_eq!(
+            scores,
+            [RemoteScore {
+                profile: "Bob".to_string(),
+                time_ms: 3000,
+                patches: 1
+            }]
+        );
+
+        let (endpoint, server) = serve_once(b"HTTP/1.1 503 Busy\r\nContent-Length: 0\r\n\r\n");
+        let err = LeaderboardClient::new(&endpoint)
+            .unwrap()
+            .fetch("basic-001")
+            .unwrap_err();
+        server.join().unwrap();
+        assert_eq!(err, "Leaderboard server answered 503");
+    }
+
+    #[test]
+    fn test_puzzle_ids_are_escaped() {
+        let (endpoint, server) = serve_once(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n[]");
+        let client = LeaderboardClient::new(&endpoint).unwrap();
+        client.fetch("my puzzle/../x?y").unwrap();
+        assert!(server
+            .join()
+            .unwrap()
+            .starts_with("GET /scores/leaderboard/my%20puzzle%2F..%2Fx%3Fy HTTP/1.1\r\n"));
+
+        // Refused before anything is sent
+        let err = client.fetch("a HTTP/1.1\r\nX-Injected: 1\r\n").unwrap_err();
+        assert!(err.contains("can't be sent"));
+    }
+
+    #[test]
+    fn test_chunked_and_non_utf8_bodies() {
+        let (endpoint, server) = serve_once(
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+              F\r\n[{\"profile\":\"Bo\r\n\
+              1F\r\nb\",\"time_ms\":3000,\"patches\":1}]\r\n\
+              0\r\n\r\n",
+        );
+        let scores = LeaderboardClient::new(&endpoint)
+            .unwrap()
+            .fetch("basic-001")
+            .unwrap();
+        server.join().unwrap();
+        assert_eq!(scores[0].profile, "Bob");
+
+        let (endpoint, server) =
+            serve_once(b"HTTP/1.1 200 OK\r\nContent-Length: 43\r\n\r\n[{\"profile\":\"B\xF6b\",\"time_ms\":1,\"patches\":0}]");
+        let scores = LeaderboardClient::new(&endpoint)
+            .unwrap()
+            .fetch("basic-001")
+            .unwrap();
+        server.join().unwrap();
+        assert_eq!(scores[0].profile, "B\u{FFFD}b");
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let client = LeaderboardClient::new("https://scores.example:8080/").unwrap();
+        assert_eq!(client.base, "https://scores.example:8080");
+        assert_eq!(
+            LeaderboardClient::new("https://example/api").unwrap().base,
+            "https://example/api"
+        );
+        assert!(LeaderboardClient::new("http://localhost:3000").is_ok());
+        assert!(LeaderboardClient::new("http://[::1]:3000/api").is_ok());
+        assert!(LeaderboardClient::new("http://scores.example")
+            .unwrap_err()
+            .contains("https://"));
+        assert!(LeaderboardClient::new("ftp://scores.example").is_err());
+        assert!(LeaderboardClient::new("https:///api").is_err());
+    }
+}
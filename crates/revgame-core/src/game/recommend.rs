@@ -0,0 +1,278 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use super::achievements::PuzzleStats;
+use super::state::GameState;
+use crate::puzzle::PuzzleListItem;
+
+/// Struggle at or above this marks a puzzle's concepts for review
+pub const STRUGGLE_THRESHOLD: u32 = 3;
+
+/// The puzzle to play next and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recommendation {
+    pub id: String,
+    pub reason: String,
+}
+
+/// How hard a puzzle went: one point per hint and failed check, three for
+/// revealing the solution, and up to two for taking longer than its estimate
+pub fn struggle(stats: &PuzzleStats, par_minutes: Option<u32>) -> u32 {
+    let mut struggle = (stats.hints_used + stats.failed_checks) as u32;
+    if stats.revealed {
+        struggle += 3;
+    }
+    if let (Some(seconds), Some(minutes)) = (stats.time_seconds, par_minutes) {
+        let par = minutes as u64 * 60;
+        if seconds > 2 * par {
+            struggle += 2;
+        } else if seconds > par {
+            struggle += 1;
+        }
+    }
+    struggle
+}
+
+/// Concepts of the puzzles the player struggled with
+pub fn struggled_concepts<'a>(
+    state: &GameState,
+    puzzles: &'a [PuzzleListItem],
+) -> HashSet<&'a str> {
+    puzzles
+        .iter()
+        .filter(|puzzle| {
+            state
+                .achievements
+                .puzzle_stats
+                .get(&puzzle.id)
+                .is_some_and(|stats| {
+                    struggle(stats, puzzle.estimated_time_minutes) >= STRUGGLE_THRESHOLD
+                })
+        })
+        .flat_map(|puzzle| puzzle.concepts.iter().map(String::as_str))
+        .collect()
+}
+
+/// Puzzles already played that cover a concept the player struggled with
+pub fn review_puzzles(state: &GameState, puzzles: &[PuzzleListItem]) -> HashSet<String> {
+    let struggled = struggled_concepts(state, puzzles);
+    puzzles
+        .iter()
+        .filter(|puzzle| state.achievements.puzzle_stats.contains_key(&puzzle.id))
+        .filter(|puzzle| {
+            puzzle
+                .concepts
+                .iter()
+                .any(|concept| struggled.contains(concept.as_str()))
+        })
+        .map(|puzzle| puzzle.id.clone())
+        .collect()
+}
+
+/// Instructions in a puzzle's code the player has never run
+fn unexplored<'a>(state: &GameState, puzzle: &'a PuzzleListItem) -> Vec<&'a str> {
+    state
+        .coverage
+        .unexplored(puzzle.mnemonics.iter().map(String::as_str))
+}
+
+/// The next unsolved puzzle to play: practice for a concept that gave
+/// trouble, otherwise one at the player's level, or a step up when every
+/// solve so far went smoothly, preferring the one with the most instructions
+/// not yet run
+pub fn recommend(state: &GameState, puzzles: &[PuzzleListItem]) -> Option<Recommendation> {
+    let available: Vec<&PuzzleListItem> = puzzles
+        .iter()
+        .filter(|puzzle| !state.is_completed(&puzzle.id) && !puzzle.is_locked)
+        .filter(|puzzle| {
+            puzzle
+                .prerequisites
+                .iter()
+                .all(|prereq| state.is_completed(prereq))
+        })
+        .collect();
+
+    // Easiest practice for a concept that gave trouble
+    let struggled = struggled_concepts(state, puzzles);
+    let practice = available
+        .iter()
+        .filter_map(|puzzle| {
+            let concept = puzzle
+                .concepts
+                .iter()
+                .find(|concept| struggled.contains(concept.as_str()))?;
+            Some((puzzle, concept))
+        })
+        .min_by_key(|(puzzle, _)| puzzle.difficulty);
+    if let Some((puzzle, concept)) = practice {
+        return Some(Recommendation {
+            id: puzzle.id.clone(),
+            reason: format!("More practice with {}, which gave you trouble", concept),
+        });
+    }
+
+    let solved: Vec<u32> = puzzles
+        .iter()
+        .filter(|puzzle| state.is_completed(&puzzle.id))
+        .filter_map(|puzzle| {
+            let stats = state.achievements.puzzle_stats.get(&puzzle.id)?;
+            Some(struggle(stats, puzzle.estimated_time_minutes))
+        })
+        .collect();
+    let smooth = !solved.is_empty() && solved.iter().all(|&struggle| struggle == 0);
+    let level = state.max_difficulty_completed.max(1);
+    let target = if smooth { level + 1 } else { level };
+
+    // Among those, the one with the most instructions not yet run
+    let puzzle = available.iter().min_by_key(|puzzle| {
+        (
+            puzzle.difficulty.abs_diff(target),
+            Reverse(unexplored(state, puzzle).len()),
+        )
+    })?;
+    let reason = if solved.is_empty() {
+        "A good place to start"
+    } else if puzzle.difficulty > level {
+        "A step up: you've been solving smoothly"
+    } else {
+        "Next at your level"
+    };
+    let new = unexplored(state, puzzle);
+    let reason = if solved.is_empty() || new.is_empty() {
+        reason.to_string()
+    } else {
+        format!(
+            "{}, with instructions you haven't run yet: {}",
+            reason,
+            new.join(", ")
+        )
+    };
+    Some(Recommendation {
+        id: puzzle.id.clone(),
+        reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle(id: &str, difficulty: u8, concepts: &[&str]) -> PuzzleListItem {
+        PuzzleListItem {
+            id: id.to_string(),
+            title: id.to_string(),
+            difficulty,
+            category: String::new(),
+            brief: String::new(),
+            file_path: Default::default(),
+            is_locked: false,
+            prerequisites: Vec::new(),
+            concepts: concepts.iter().map(|c| c.to_string()).collect(),
+            estimated_time_minutes: Some(5),
+            mnemonics: Vec::new(),
+        }
+    }
+
+    fn solve(state: &mut GameState, id: &str, difficulty: u8, hints: usize) {
+        state.start_puzzle(id);
+        for _ in 0..hints {
+            state.use_hint();
+        }
+        state.complete_puzzle(id, difficulty);
+    }
+
+    #[test]
+    fn test_struggle() {
+        let mut stats = PuzzleStats::new("a".to_string());
+        assert_eq!(struggle(&stats, Some(5)), 0);
+
+        stats.hints_used = 1;
+        stats.failed_checks = 1;
+        stats.time_seconds = Some(400);
+        assert_eq!(struggle(&stats, Some(5)), 3);
+        assert_eq!(struggle(&stats, None), 2);
+
+        stats.revealed = true;
+        stats.time_seconds = Some(700);
+        assert_eq!(struggle(&stats, Some(5)), 7);
+    }
+
+    #[test]
+    fn test_recommend_steps_up_when_smooth() {
+        let puzzles = vec![
+            puzzle("a", 1, &["jumps"]),
+            puzzle("b", 1, &["jumps"]),
+            puzzle("c", 2, &["loops"]),
+        ];
+        let mut state = GameState::new();
+        assert_eq!(
+            recommend(&state, &puzzles).unwrap().reason,
+            "A good place to start"
+        );
+
+        solve(&mut state, "a", 1, 0);
+        let next = recommend(&state, &puzzles).unwrap();
+        assert_eq!(next.id, "c");
+        assert!(next.reason.starts_with("A step up"));
+
+        // A bumpier solve keeps the level where it is
+        let mut state = GameState::new();
+        solve(&mut state, "a", 1, 1);
+        let next = recommend(&state, &puzzles).unwrap();
+        assert_eq!(
+            (next.id.as_str(), next.reason.as_str()),
+            ("b", "Next at your level")
+        );
+
+        // Instructions never run break the tie
+        let mut puzzles = puzzles;
+        puzzles.push(puzzle("d", 1, &["jumps"]));
+        puzzles[3].mnemonics = vec!["MOV".to_string(), "ROL".to_string()];
+        state
+            .coverage
+            .record_executed("a", [("MOV".to_string(), 1)].into());
+        let next = recommend(&state, &puzzles).unwrap();
+        assert_eq!(next.id, "d");
+        assert_eq!(
+            next.reason,
+            "Next at your level, with instructions you haven't run yet: ROL"
+        );
+        puzzles.pop();
+
+        // Nothing behind an unpassed checkpoint
+        puzzles[1].is_locked = true;
+        assert_eq!(recommend(&state, &puzzles).unwrap().id, "c");
+    }
+
+    #[test]
+    fn test_recommend_practice_and_review() {
+        let mut puzzles = vec![
+            puzzle("a", 1, &["flags"]),
+            puzzle("b", 1, &["jumps", "flags"]),
+            puzzle("c", 1, &["loops"]),
+            puzzle("d", 3, &["flags"]),
+        ];
+        puzzles[3].prerequisites = vec!["b".to_string()];
+
+        let mut state = GameState::new();
+        solve(&mut state, "c", 1, 0);
+        solve(&mut state, "a", 1, 3);
+
+        let next = recommend(&state, &puzzles).unwrap();
+        assert_eq!(next.id, "b");
+        assert_eq!(
+            next.reason,
+            "More practice with flags, which gave you trouble"
+        );
+
+        // Only puzzles already played are marked for review
+        assert_eq!(
+            review_puzzles(&state, &puzzles),
+            HashSet::from(["a".to_string()])
+        );
+
+        solve(&mut state, "b", 1, 0);
+        assert_eq!(review_puzzles(&state, &puzzles).len(), 2);
+        assert_eq!(recommend(&state, &puzzles).unwrap().id, "d");
+    }
+}
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+/// A run through a puzzle as the commands typed and when, written as TOML:
+///
+/// ```toml
+/// puzzle = "basic-001"
+///
+/// [[event]]
+/// at_ms = 1500
+/// command = "step"
+/// note = "JNE jumps when the compare failed"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replay {
+    /// Id of the puzzle the replay was made on
+    #[serde(default)]
+    pub puzzle: String,
+    #[serde(default, rename = "event")]
+    pub events: Vec<ReplayEvent>,
+}
+
+/// A command-line command, `at_ms` milliseconds into the replay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub at_ms: u64,
+    pub command: String,
+    /// Caption shown while the command plays
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Instructions run by the time the command finished (recorded replays)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<u64>,
+    /// Patches in place by the time the command finished (recorded replays)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patches: Option<usize>,
+}
+
+impl Replay {
+    /// Parse a replay file. Events have to be in time order.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        let replay: Replay =
+            toml::from_str(toml).map_err(|e| format!("Failed to parse replay: {}", e))?;
+        if let Some(pair) = replay
+            .events
+            .windows(2)
+            .find(|pair| pair[1].at_ms < pair[0].at_ms)
+        {
+            return Err(format!(
+                "Replay events out of order: {}ms comes after {}ms",
+                pair[1].at_ms, pair[0].at_ms
+            ));
+        }
+        Ok(replay)
+    }
+
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string(self).map_err(|e| format!("Failed to encode replay: {}", e))
+    }
+
+    /// How long the replay runs
+    pub fn duration_ms(&self) -> u64 {
+        self.events.last().map_or(0, |event| event.at_ms)
+    }
+
+    /// Record a command along with the progress it left behind
+    pub fn record(&mut self, at_ms: u64, command: String, instructions: u64, patches: usize) {
+        let at_ms = at_ms.max(self.duration_ms());
+        self.events.push(ReplayEvent {
+            at_ms,
+            command,
+            note: None,
+            instructions: Some(instructions),
+            patches: Some(patches),
+        });
+    }
+
+    /// Instructions run and patches made as of `at_ms`, from the latest
+    /// recorded event by then
+    pub fn progress_at(&self, at_ms: u64) -> (u64, usize) {
+        self.events[..self.due(at_ms)]
+            .iter()
+            .rev()
+            .find_map(|event| Some((event.instructions?, event.patches?)))
+            .unwrap_or((0, 0))
+    }
+
+    /// Number of events due by `at_ms`
+    pub fn due(&self, at_ms: u64) -> usize {
+        self.events.partition_point(|event| event.at_ms <= at_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_replay() {
+        let replay = Replay::parse(
+            r#"
+puzzle = "basic-001"
+
+[[event]]
+at_ms = 0
+command = "step"
+
+[[event]]
+at_ms = 1500
+command = "patch 0x1005 90 90"
+note = "Skip the branch"
+"#,
+        )
+        .unwrap();
+        assert_eq!(replay.puzzle, "basic-001");
+        assert_eq!(replay.events[1].note.as_deref(), Some("Skip the branch"));
+        assert_eq!(replay.duration_ms(), 1500);
+        assert_eq!(
+            (replay.due(0), replay.due(1499), replay.due(1500)),
+            (1, 1, 2)
+        );
+        assert_eq!(Replay::parse(&replay.to_toml().unwrap()).unwrap(), replay);
+
+        assert_eq!(replay.progress_at(2000), (0, 0));
+
+        let err = Replay::parse(
+            "[[event]]\nat_ms = 9\ncommand = \"run\"\n[[event]]\nat_ms = 2\ncommand = \"step\"",
+        )
+        .unwrap_err();
+        assert_eq!(err, "Replay events out of order: 2ms comes after 9ms");
+    }
+
+    #[test]
+    fn test_recorded_progress() {
+        let mut replay = Replay::default();
+        replay.record(100, "step".to_string(), 1, 0);
+        replay.record(900, "patch! 0x1005 90 90".to_string(), 1, 1);
+        replay.record(500, "run".to_string(), 40, 1);
+        assert_eq!(replay.events[2].at_ms, 900);
+        assert_eq!(replay.progress_at(50), (0, 0));
+        assert_eq!(replay.progress_at(899), (1, 0));
+        assert_eq!(replay.progress_at(900), (40, 1));
+        assert!(replay.to_toml().unwrap().contains("instructions = 40"));
+    }
+}
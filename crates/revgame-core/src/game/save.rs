@@ -1,81 +1,218 @@
 /// Save/load game progress
-use std::fs;
 use std::path::{Path, PathBuf};
-use serde::{Deserialize, Serialize};
 
-use super::GameState;
+use super::{FileBackend, GameState, Leaderboard, Replay, SaveBackend, Settings};
 
-/// Manages save/load operations
-pub struct SaveManager {
-    save_dir: PathBuf,
+/// File next to the executable that turns portable mode on
+pub const PORTABLE_MARKER: &str = "portable";
+
+/// Directory next to the executable that holds saves in portable mode
+pub const PORTABLE_DIR: &str = "saves";
+
+/// Directory in the save directory with one directory per named profile
+pub const PROFILES_DIR: &str = "profiles";
+
+/// Where `SaveManager::open` keeps saves, as chosen on the command line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveOptions {
+    /// Keep saves next to the executable instead of in the platform's save
+    /// directory (`--portable`)
+    pub portable: bool,
+    /// Keep progress and settings apart under this profile (`--profile`),
+    /// so several people can share one machine or stick
+    pub profile: Option<String>,
 }
 
-impl SaveManager {
-    /// Create a new save manager
-    pub fn new() -> Result<Self, String> {
-        let save_dir = Self::get_save_directory()?;
+#[cfg(not(target_arch = "wasm32"))]
+impl SaveOptions {
+    /// Whether saves are kept next to the executable: set by `--portable`,
+    /// `REVGAME_PORTABLE=1` or a `portable` file beside the executable, for
+    /// running from a USB stick
+    pub fn is_portable(&self) -> bool {
+        self.portable
+            || std::env::var("REVGAME_PORTABLE")
+                .is_ok_and(|value| matches!(value.trim(), "1" | "true" | "on" | "yes"))
+            || executable_dir().is_ok_and(|dir| dir.join(PORTABLE_MARKER).exists())
+    }
 
-        // Create save directory if it doesn't exist
-        if !save_dir.exists() {
-            fs::create_dir_all(&save_dir)
-                .map_err(|e| format!("Failed to create save directory: {}", e))?;
+    /// The directory saves go to
+    pub fn save_directory(&self) -> Result<PathBuf, String> {
+        if let Some(ref profile) = self.profile {
+            check_profile_name(profile)?;
+        }
+        let in_profile = |dir: PathBuf| match self.profile {
+            Some(ref profile) => dir.join(PROFILES_DIR).join(profile),
+            None => dir,
+        };
+        if self.is_portable() {
+            return Ok(in_profile(portable_directory()?));
         }
 
-        Ok(Self { save_dir })
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let dir = in_profile(save_directory_for(std::env::consts::OS, var)?);
+        Ok(match legacy_save_directory_for(std::env::consts::OS, var) {
+            Some(legacy) => pick_save_directory(dir, in_profile(legacy)),
+            None => dir,
+        })
     }
+}
 
-    /// Get the save directory path
-    fn get_save_directory() -> Result<PathBuf, String> {
-        #[cfg(target_os = "linux")]
-        {
-            let home = std::env::var("HOME")
-                .map_err(|_| "HOME environment variable not set".to_string())?;
-            Ok(PathBuf::from(home).join(".local/share/revgame"))
+/// The platform's save directory on `os` (as in `std::env::consts::OS`),
+/// with environment variables looked up by `var`: `%APPDATA%\RevGame` on
+/// Windows, `~/Library/Application Support/RevGame` on macOS, and
+/// `$XDG_DATA_HOME/revgame` (by default `~/.local/share/revgame`) on Linux
+/// and the BSDs
+pub fn save_directory_for(
+    os: &str,
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<PathBuf, String> {
+    let home = || {
+        var("HOME")
+            .map(PathBuf::from)
+            .ok_or_else(|| "HOME environment variable not set".to_string())
+    };
+    match os {
+        "windows" => var("APPDATA")
+            .map(PathBuf::from)
+            .or_else(|| {
+                var("USERPROFILE")
+                    .map(|profile| PathBuf::from(profile).join("AppData").join("Roaming"))
+            })
+            .map(|dir| dir.join("RevGame"))
+            .ok_or_else(|| "Neither APPDATA nor USERPROFILE environment variable set".to_string()),
+        "macos" => Ok(home()?.join("Library/Application Support/RevGame")),
+        "linux" | "freebsd" | "openbsd" | "netbsd" | "dragonfly" => {
+            // Relative XDG paths are invalid and ignored
+            let data = match var("XDG_DATA_HOME").map(PathBuf::from) {
+                Some(dir) if dir.is_absolute() => dir,
+                _ => home()?.join(".local/share"),
+            };
+            Ok(data.join("revgame"))
         }
+        _ => Ok(PathBuf::from(".revgame")),
+    }
+}
 
-        #[cfg(target_os = "windows")]
-        {
-            let appdata = std::env::var("APPDATA")
-                .map_err(|_| "APPDATA environment variable not set".to_string())?;
-            Ok(PathBuf::from(appdata).join("RevGame"))
-        }
+/// Where earlier versions kept saves on `os` when that isn't
+/// `save_directory_for`: they ignored `XDG_DATA_HOME` and always used
+/// `~/.local/share/revgame`
+pub fn legacy_save_directory_for(
+    os: &str,
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<PathBuf> {
+    if !matches!(os, "linux" | "freebsd" | "openbsd" | "netbsd" | "dragonfly") {
+        return None;
+    }
+    let legacy = PathBuf::from(var("HOME")?).join(".local/share/revgame");
+    (save_directory_for(os, &var).ok()? != legacy).then_some(legacy)
+}
+
+/// `dir`, unless only `legacy` has saves in it, so players who set
+/// `XDG_DATA_HOME` keep the progress they made before it was honoured
+#[cfg(not(target_arch = "wasm32"))]
+fn pick_save_directory(dir: PathBuf, legacy: PathBuf) -> PathBuf {
+    let has_saves = |dir: &Path| {
+        FileBackend::new(dir.to_path_buf())
+            .keys()
+            .is_ok_and(|keys| !keys.is_empty())
+    };
+    if !has_saves(&dir) && has_saves(&legacy) {
+        legacy
+    } else {
+        dir
+    }
+}
+
+/// Profile names become directory names, so keep them to a safe set
+#[cfg(not(target_arch = "wasm32"))]
+fn check_profile_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "Profile names are letters, digits, - and _, not \"{}\"",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Directory the running executable is in
+#[cfg(not(target_arch = "wasm32"))]
+fn executable_dir() -> Result<PathBuf, String> {
+    let exe =
+        std::env::current_exe().map_err(|e| format!("Failed to find the executable: {}", e))?;
+    exe.parent()
+        .map(std::path::Path::to_path_buf)
+        .ok_or_else(|| "The executable has no directory".to_string())
+}
+
+/// Where saves go in portable mode
+#[cfg(not(target_arch = "wasm32"))]
+fn portable_directory() -> Result<PathBuf, String> {
+    Ok(executable_dir()?.join(PORTABLE_DIR))
+}
+
+/// Manages save/load operations
+pub struct SaveManager {
+    backend: Box<dyn SaveBackend>,
+}
 
-        #[cfg(target_os = "macos")]
+impl SaveManager {
+    /// Create a new save manager: files in the platform's save directory,
+    /// or the shared in-memory store on the web
+    pub fn new() -> Result<Self, String> {
+        #[cfg(target_arch = "wasm32")]
         {
-            let home = std::env::var("HOME")
-                .map_err(|_| "HOME environment variable not set".to_string())?;
-            Ok(PathBuf::from(home).join("Library/Application Support/RevGame"))
+            Ok(Self::with_backend(Box::new(super::MemoryBackend::shared())))
         }
 
-        #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+        #[cfg(not(target_arch = "wasm32"))]
         {
-            Ok(PathBuf::from(".revgame"))
+            Self::open(&SaveOptions::default())
         }
     }
 
+    /// Create a save manager keeping files where `options` says
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(options: &SaveOptions) -> Result<Self, String> {
+        let backend = FileBackend::create(options.save_directory()?)?;
+        Ok(Self::with_backend(Box::new(backend)))
+    }
+
+    /// Create a save manager on top of `backend`
+    pub fn with_backend(backend: Box<dyn SaveBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// A save manager whose saves live in memory and vanish with it, for
+    /// tests that mustn't touch the player's files
+    pub fn in_memory() -> Self {
+        Self::with_backend(Box::new(super::MemoryBackend::new()))
+    }
+
+    /// The directory saves are written to, or `None` when they aren't
+    /// kept in files
+    pub fn directory(&self) -> Option<&Path> {
+        self.backend.directory()
+    }
+
     /// Save game state
     pub fn save(&self, game_state: &GameState, slot: &str) -> Result<(), String> {
-        let file_path = self.save_dir.join(format!("save_{}.json", slot));
-
         let json = serde_json::to_string_pretty(game_state)
             .map_err(|e| format!("Failed to serialize game state: {}", e))?;
 
-        fs::write(&file_path, json)
-            .map_err(|e| format!("Failed to write save file: {}", e))?;
-
-        Ok(())
+        self.backend.write(&Self::slot_key(slot), &json)
     }
 
     /// Load game state
     pub fn load(&self, slot: &str) -> Result<GameState, String> {
-        let file_path = self.save_dir.join(format!("save_{}.json", slot));
-
-        if !file_path.exists() {
-            return Err("Save file not found".to_string());
-        }
-
-        let json = fs::read_to_string(&file_path)
-            .map_err(|e| format!("Failed to read save file: {}", e))?;
+        let json = self
+            .backend
+            .read(&Self::slot_key(slot))?
+            .ok_or_else(|| "Save file not found".to_string())?;
 
         let game_state: GameState = serde_json::from_str(&json)
             .map_err(|e| format!("Failed to deserialize save file: {}", e))?;
@@ -83,79 +220,112 @@ impl SaveManager {
         Ok(game_state)
     }
 
+    /// Save user settings
+    pub fn save_settings(&self, settings: &Settings) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(settings)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+        self.backend.write(SETTINGS_KEY, &json)
+    }
+
+    /// Load user settings (defaults if none have been saved yet)
+    pub fn load_settings(&self) -> Result<Settings, String> {
+        let Some(json) = self.backend.read(SETTINGS_KEY)? else {
+            return Ok(Settings::default());
+        };
+
+        serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to deserialize settings file: {}", e))
+    }
+
+    /// Save the time attack leaderboard
+    pub fn save_leaderboard(&self, leaderboard: &Leaderboard) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(leaderboard)
+            .map_err(|e| format!("Failed to serialize leaderboard: {}", e))?;
+
+        self.backend.write(LEADERBOARD_KEY, &json)
+    }
+
+    /// Load the time attack leaderboard (empty if nothing was saved yet)
+    pub fn load_leaderboard(&self) -> Result<Leaderboard, String> {
+        let Some(json) = self.backend.read(LEADERBOARD_KEY)? else {
+            return Ok(Leaderboard::default());
+        };
+
+        serde_json::from_str(&json).map_err(|e| format!("Failed to deserialize leaderboard: {}", e))
+    }
+
+    /// Save the replay of a puzzle's best solve, under the puzzle it was made on
+    pub fn save_replay(&self, replay: &Replay) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(replay)
+            .map_err(|e| format!("Failed to serialize replay: {}", e))?;
+
+        self.backend
+            .write(&format!("{}{}", REPLAY_PREFIX, replay.puzzle), &json)
+    }
+
+    /// Load the replay of a puzzle's best solve, if there is one
+    pub fn load_replay(&self, puzzle_id: &str) -> Result<Option<Replay>, String> {
+        self.backend
+            .read(&format!("{}{}", REPLAY_PREFIX, puzzle_id))?
+            .map(|json| {
+                serde_json::from_str(&json)
+                    .map_err(|e| format!("Failed to deserialize replay: {}", e))
+            })
+            .transpose()
+    }
+
     /// Check if a save exists
     pub fn save_exists(&self, slot: &str) -> bool {
-        self.save_dir.join(format!("save_{}.json", slot)).exists()
+        matches!(self.backend.read(&Self::slot_key(slot)), Ok(Some(_)))
     }
 
     /// List all save slots
     pub fn list_saves(&self) -> Result<Vec<String>, String> {
-        let mut saves = Vec::new();
-
-        let entries = fs::read_dir(&self.save_dir)
-            .map_err(|e| format!("Failed to read save directory: {}", e))?;
-
-        for entry in entries {
-            let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-            let path = entry.path();
-
-            if let Some(filename) = path.file_name() {
-                if let Some(name) = filename.to_str() {
-                    if name.starts_with("save_") && name.ends_with(".json") {
-                        let slot = name.trim_start_matches("save_").trim_end_matches(".json");
-                        saves.push(slot.to_string());
-                    }
-                }
-            }
-        }
-
-        Ok(saves)
+        Ok(self
+            .backend
+            .keys()?
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(SLOT_PREFIX).map(str::to_string))
+            .collect())
     }
 
     /// Delete a save
     pub fn delete_save(&self, slot: &str) -> Result<(), String> {
-        let file_path = self.save_dir.join(format!("save_{}.json", slot));
-
-        if !file_path.exists() {
-            return Err("Save file not found".to_string());
+        if self.backend.remove(&Self::slot_key(slot))? {
+            Ok(())
+        } else {
+            Err("Save file not found".to_string())
         }
-
-        fs::remove_file(&file_path)
-            .map_err(|e| format!("Failed to delete save file: {}", e))?;
-
-        Ok(())
     }
 
     /// Get save file metadata
     pub fn get_save_info(&self, slot: &str) -> Result<SaveInfo, String> {
-        let file_path = self.save_dir.join(format!("save_{}.json", slot));
-
-        if !file_path.exists() {
-            return Err("Save file not found".to_string());
-        }
-
-        let metadata = fs::metadata(&file_path)
-            .map_err(|e| format!("Failed to read file metadata: {}", e))?;
-
-        let modified = metadata.modified()
-            .map_err(|e| format!("Failed to get modification time: {}", e))?;
-
         let game_state = self.load(slot)?;
 
         Ok(SaveInfo {
             slot: slot.to_string(),
-            modified_time: modified,
+            modified_time: self.backend.modified(&Self::slot_key(slot)),
             puzzles_completed: game_state.completed_puzzles.len(),
             total_achievements: game_state.achievements.unlocked.len(),
             total_points: game_state.achievements.total_points,
         })
     }
+
+    fn slot_key(slot: &str) -> String {
+        format!("{}{}", SLOT_PREFIX, slot)
+    }
 }
 
+const SLOT_PREFIX: &str = "save_";
+const SETTINGS_KEY: &str = "settings";
+const LEADERBOARD_KEY: &str = "time_attack";
+const REPLAY_PREFIX: &str = "replay_";
+
 impl Default for SaveManager {
     fn default() -> Self {
-        Self::new().unwrap_or_else(|_| Self {
-            save_dir: PathBuf::from(".revgame"),
+        Self::new().unwrap_or_else(|_| {
+            Self::with_backend(Box::new(FileBackend::new(PathBuf::from(".revgame"))))
         })
     }
 }
@@ -164,7 +334,8 @@ impl Default for SaveManager {
 #[derive(Debug, Clone)]
 pub struct SaveInfo {
     pub slot: String,
-    pub modified_time: std::time::SystemTime,
+    /// When the save was written, if the backend records it
+    pub modified_time: Option<std::time::SystemTime>,
     pub puzzles_completed: usize,
     pub total_achievements: usize,
     pub total_points: u32,
@@ -173,6 +344,132 @@ pub struct SaveInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::MemoryBackend;
+
+    #[test]
+    fn test_platform_save_directories() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        let home = env(&[("HOME", "/home/ada")]);
+        assert_eq!(
+            save_directory_for("linux", home).unwrap(),
+            PathBuf::from("/home/ada/.local/share/revgame")
+        );
+        assert_eq!(
+            save_directory_for(
+                "linux",
+                env(&[("HOME", "/home/ada"), ("XDG_DATA_HOME", "/data")])
+            )
+            .unwrap(),
+            PathBuf::from("/data/revgame")
+        );
+        assert_eq!(
+            save_directory_for(
+                "linux",
+                env(&[("HOME", "/home/ada"), ("XDG_DATA_HOME", "data")])
+            )
+            .unwrap(),
+            PathBuf::from("/home/ada/.local/share/revgame")
+        );
+        assert_eq!(
+            save_directory_for("macos", home).unwrap(),
+            PathBuf::from("/home/ada/Library/Application Support/RevGame")
+        );
+
+        assert_eq!(
+            save_directory_for(
+                "windows",
+                env(&[("APPDATA", "C:/Users/ada/AppData/Roaming")])
+            )
+            .unwrap(),
+            PathBuf::from("C:/Users/ada/AppData/Roaming").join("RevGame")
+        );
+        assert_eq!(
+            save_directory_for("windows", env(&[("USERPROFILE", "C:/Users/ada")])).unwrap(),
+            PathBuf::from("C:/Users/ada")
+                .join("AppData")
+                .join("Roaming")
+                .join("RevGame")
+        );
+        assert!(save_directory_for("windows", home).is_err());
+        assert!(save_directory_for("linux", env(&[])).is_err());
+    }
+
+    #[test]
+    fn test_legacy_save_directory() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            legacy_save_directory_for(
+                "linux",
+                env(&[("HOME", "/home/ada"), ("XDG_DATA_HOME", "/data")])
+            ),
+            Some(PathBuf::from("/home/ada/.local/share/revgame"))
+        );
+        assert_eq!(
+            legacy_save_directory_for("linux", env(&[("HOME", "/home/ada")])),
+            None
+        );
+        assert_eq!(
+            legacy_save_directory_for("macos", env(&[("HOME", "/home/ada")])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_saves_in_legacy_directory_are_kept() {
+        let root = std::env::temp_dir().join(format!("revgame-legacy-{}", std::process::id()));
+        let (dir, legacy) = (root.join("xdg"), root.join("home"));
+        std::fs::create_dir_all(&legacy).unwrap();
+
+        assert_eq!(pick_save_directory(dir.clone(), legacy.clone()), dir);
+        std::fs::write(legacy.join("save_session.json"), "{}").unwrap();
+        assert_eq!(pick_save_directory(dir.clone(), legacy.clone()), legacy);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("settings.json"), "{}").unwrap();
+        let picked = pick_save_directory(dir.clone(), legacy);
+        std::fs::remove_dir_all(&root).ok();
+        assert_eq!(picked, dir);
+    }
+
+    #[test]
+    fn test_profile_names() {
+        let options = |name: &str| SaveOptions {
+            portable: true,
+            profile: Some(name.to_string()),
+        };
+        assert!(options("../elsewhere").save_directory().is_err());
+        assert!(options("").save_directory().is_err());
+        let dir = options("class-a").save_directory().unwrap();
+        assert_eq!(
+            dir,
+            portable_directory()
+                .unwrap()
+                .join(PROFILES_DIR)
+                .join("class-a")
+        );
+    }
+
+    #[test]
+    fn test_portable_saves_beside_executable() {
+        let exe = std::env::current_exe().unwrap();
+        assert_eq!(
+            portable_directory().unwrap(),
+            exe.parent().unwrap().join(PORTABLE_DIR)
+        );
+    }
 
     #[test]
     fn test_save_load() {
@@ -202,4 +499,39 @@ mod tests {
 
         manager.delete_save("test2").ok();
     }
+
+    #[test]
+    fn test_save_load_with_backend() {
+        let backend = MemoryBackend::new();
+        let manager = SaveManager::with_backend(Box::new(backend.clone()));
+        let game_state = GameState::new();
+
+        manager.save(&game_state, "quick").unwrap();
+        manager.save_settings(&Settings::default()).unwrap();
+        assert_eq!(manager.load_leaderboard().unwrap(), Leaderboard::default());
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.record(crate::game::LeaderboardEntry {
+            profile: "ada".to_string(),
+            total_ms: 1000,
+            splits: vec![1000],
+            finished_at: 0,
+        });
+        manager.save_leaderboard(&leaderboard).unwrap();
+        assert_eq!(manager.load_leaderboard().unwrap(), leaderboard);
+        let mut replay = Replay {
+            puzzle: "basic-001".to_string(),
+            ..Replay::default()
+        };
+        replay.record(1200, "run".to_string(), 3, 0);
+        manager.save_replay(&replay).unwrap();
+        assert_eq!(manager.load_replay("basic-001").unwrap(), Some(replay));
+        assert_eq!(manager.load_replay("basic-002").unwrap(), None);
+        assert_eq!(manager.list_saves().unwrap(), ["quick"]);
+        assert_eq!(manager.load("quick").unwrap().completion_count(), 0);
+        assert_eq!(backend.keys().unwrap().len(), 4);
+
+        manager.delete_save("quick").unwrap();
+        assert!(!manager.save_exists("quick"));
+        assert_eq!(manager.load("quick").unwrap_err(), "Save file not found");
+    }
 }
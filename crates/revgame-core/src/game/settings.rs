@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::debugger::PredictMode;
+use crate::puzzle::substitute_vars;
+
+/// User preferences that persist across sessions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Name of the debugger screen layout (empty for the default layout)
+    pub debugger_layout: String,
+
+    /// Name of the color theme (empty for the default theme)
+    pub theme: String,
+
+    /// Show textual focus markers instead of relying on border color
+    pub focus_markers: bool,
+
+    /// Use the high-contrast theme
+    pub high_contrast: bool,
+
+    /// Draw borders, markers, icons and every other glyph with plain ASCII
+    pub ascii_borders: bool,
+
+    /// Describe each step and focus change in the status line
+    pub announce: bool,
+
+    /// Skip history capture while running (stepping is still recorded)
+    pub fast_run: bool,
+
+    /// Name chosen when the game was first started
+    pub profile_name: String,
+
+    /// Experience level picked during onboarding (empty before onboarding)
+    pub experience: String,
+
+    /// Offer a targeted hint after every failed attempt
+    pub eager_hints: bool,
+
+    /// Name of the predict mode asked before each step (empty for off)
+    pub predict_mode: String,
+
+    /// Delimit call frames in the stack view and animate them on return
+    pub stack_frames: bool,
+
+    /// Annotate the first occurrence of each common idiom in the disassembly
+    pub idiom_notes: bool,
+
+    /// Names of idioms no longer annotated
+    pub learned_idioms: Vec<String>,
+
+    /// Keep a local log of learning events for insights on the stats screen
+    pub learning_log: bool,
+
+    /// Show where your best solve of the puzzle was at the same point in time
+    pub ghost: bool,
+
+    /// Also write log records to `session.log` in the save directory
+    pub session_log: bool,
+
+    /// Leaderboard server solves are submitted to (empty for off)
+    #[cfg(feature = "online")]
+    pub leaderboard_url: String,
+
+    /// Command aliases: name to the command line it stands for, which may
+    /// use variables (`$sel`, `$eip`) and chain commands with `;`
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// How much reverse engineering a player says they've done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Experience {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+impl Experience {
+    /// All levels, least experienced first
+    pub const ALL: [Experience; 3] = [
+        Experience::Beginner,
+        Experience::Intermediate,
+        Experience::Advanced,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Experience::Beginner => "beginner",
+            Experience::Intermediate => "intermediate",
+            Experience::Advanced => "advanced",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Experience::Beginner => "New to assembly and debuggers",
+            Experience::Intermediate => "Know some x86, new to cracking",
+            Experience::Advanced => "Seasoned reverser",
+        }
+    }
+
+    /// Whether hints are offered without asking
+    pub fn eager_hints(&self) -> bool {
+        !matches!(self, Experience::Advanced)
+    }
+
+    /// Predict mode a new player starts with
+    pub fn predict_mode(&self) -> PredictMode {
+        match self {
+            Experience::Beginner => PredictMode::Flags,
+            _ => PredictMode::Off,
+        }
+    }
+
+    /// Whether the stack view marks call frames
+    pub fn stack_frames(&self) -> bool {
+        matches!(self, Experience::Beginner)
+    }
+
+    /// Whether idioms are explained in the disassembly
+    pub fn idiom_notes(&self) -> bool {
+        !matches!(self, Experience::Advanced)
+    }
+
+    /// Whether the interface tutorial starts right after onboarding
+    pub fn starts_tutorial(&self) -> bool {
+        matches!(self, Experience::Beginner)
+    }
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether every accessibility option is enabled
+    pub fn accessibility_enabled(&self) -> bool {
+        self.focus_markers && self.high_contrast && self.ascii_borders && self.announce
+    }
+
+    /// Adopt the defaults for an experience level
+    pub fn set_experience(&mut self, experience: Experience) {
+        self.experience = experience.name().to_string();
+        self.eager_hints = experience.eager_hints();
+        self.predict_mode = experience.predict_mode().name().to_string();
+        self.stack_frames = experience.stack_frames();
+        self.idiom_notes = experience.idiom_notes();
+    }
+
+    /// Define (or redefine) an alias
+    pub fn set_alias(&mut self, name: &str, command: &str) -> Result<(), String> {
+        let name = name.to_lowercase();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!(
+                "Alias names are letters, digits, - and _, not \"{}\"",
+                name
+            ));
+        }
+        if name == "alias" || name == "unalias" {
+            return Err(format!("{} can't be redefined", name));
+        }
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(format!("Usage: alias {} = <command>", name));
+        }
+        self.aliases.insert(name, command.to_string());
+        Ok(())
+    }
+
+    /// Expand a command line that starts with an alias into the commands it
+    /// stands for, with `$name` variables looked up by `var` and any words
+    /// after the alias appended. `None` when the line isn't an alias.
+    pub fn expand_alias(
+        &self,
+        line: &str,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Option<Result<Vec<String>, String>> {
+        let mut words = line.split_whitespace();
+        let command = self.aliases.get(&words.next()?.to_lowercase())?;
+        let extra: Vec<&str> = words.collect();
+
+        let mut expanded = match substitute_vars(command, var) {
+            Ok(expanded) => expanded,
+            Err(e) => return Some(Err(e)),
+        };
+        if !extra.is_empty() {
+            expanded.push(' ');
+            expanded.push_str(&extra.join(" "));
+        }
+
+        Some(Ok(expanded
+            .split(';')
+            .map(str::trim)
+            .filter(|command| !command.is_empty())
+            .map(str::to_string)
+            .collect()))
+    }
+
+    /// Turn all accessibility options on or off at once
+    pub fn set_accessibility(&mut self, enabled: bool) {
+        self.focus_markers = enabled;
+        self.high_contrast = enabled;
+        self.ascii_borders = enabled;
+        self.announce = enabled;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let settings: Settings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, Settings::new());
+
+        let settings: Settings = serde_json::from_str(r#"{"debugger_layout":"wide"}"#).unwrap();
+        assert_eq!(settings.debugger_layout, "wide");
+    }
+
+    #[test]
+    fn test_experience_defaults() {
+        let mut settings = Settings::new();
+        settings.set_experience(Experience::Beginner);
+        assert_eq!(settings.experience, "beginner");
+        assert!(settings.eager_hints);
+        assert_eq!(settings.predict_mode, "flags");
+        assert!(settings.stack_frames);
+
+        settings.set_experience(Experience::Advanced);
+        assert!(!settings.eager_hints);
+        assert!(!settings.stack_frames);
+        assert!(!settings.idiom_notes);
+        assert_eq!(
+            PredictMode::from_name(&settings.predict_mode),
+            PredictMode::Off
+        );
+        assert_eq!(
+            Experience::from_name(&settings.experience),
+            Some(Experience::Advanced)
+        );
+    }
+
+    #[test]
+    fn test_aliases_expand_variables_and_arguments() {
+        let mut settings = Settings::new();
+        settings.set_alias("n", "patch $sel 90").unwrap();
+        settings.set_alias("Go", "bp $eip; run").unwrap();
+        assert!(settings.set_alias("bad name", "run").is_err());
+        assert!(settings.set_alias("alias", "run").is_err());
+        assert!(settings.set_alias("empty", " ").is_err());
+
+        let var = |name: &str| match name {
+            "sel" => Some("0x1005".to_string()),
+            "eip" => Some("0x1000".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            settings.expand_alias("n 90", var),
+            Some(Ok(vec!["patch 0x1005 90 90".to_string()]))
+        );
+        assert_eq!(
+            settings.expand_alias("go", var),
+            Some(Ok(vec!["bp 0x1000".to_string(), "run".to_string()]))
+        );
+        assert_eq!(settings.expand_alias("run", var), None);
+
+        settings.set_alias("undo-last", "patch $last 00").unwrap();
+        assert_eq!(
+            settings.expand_alias("undo-last", var),
+            Some(Err("$last has no value here".to_string()))
+        );
+
+        // Aliases are saved with the other settings
+        let json = serde_json::to_string(&settings).unwrap();
+        let loaded: Settings = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.aliases["n"], "patch $sel 90");
+    }
+
+    #[test]
+    fn test_accessibility_toggle() {
+        let mut settings = Settings::new();
+        assert!(!settings.accessibility_enabled());
+
+        settings.set_accessibility(true);
+        assert!(settings.accessibility_enabled());
+
+        settings.ascii_borders = false;
+        assert!(!settings.accessibility_enabled());
+    }
+}
@@ -0,0 +1,241 @@
+use super::state::GameState;
+use crate::puzzle::PuzzleListItem;
+
+/// Solves needed to master a concept (or all its puzzles, if fewer)
+pub const MASTERY_SOLVES: usize = 2;
+
+/// A concept in the skill tree and the concepts it builds on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkillNode {
+    pub concept: &'static str,
+    pub name: &'static str,
+    pub requires: &'static [&'static str],
+}
+
+/// The campaign's concepts, each after the ones it requires
+pub const SKILL_TREE: &[SkillNode] = &[
+    SkillNode {
+        concept: "patching",
+        name: "Patching",
+        requires: &[],
+    },
+    SkillNode {
+        concept: "jumps",
+        name: "Jumps",
+        requires: &[],
+    },
+    SkillNode {
+        concept: "immediates",
+        name: "Immediate values",
+        requires: &["patching"],
+    },
+    SkillNode {
+        concept: "flags",
+        name: "Flags",
+        requires: &["jumps"],
+    },
+    SkillNode {
+        concept: "arithmetic",
+        name: "Arithmetic",
+        requires: &["immediates"],
+    },
+    SkillNode {
+        concept: "memory",
+        name: "Memory",
+        requires: &["immediates"],
+    },
+    SkillNode {
+        concept: "stack",
+        name: "The stack",
+        requires: &["memory"],
+    },
+    SkillNode {
+        concept: "loops",
+        name: "Loops",
+        requires: &["flags"],
+    },
+    SkillNode {
+        concept: "input",
+        name: "Program input",
+        requires: &["memory"],
+    },
+    SkillNode {
+        concept: "xor",
+        name: "XOR ciphers",
+        requires: &["arithmetic", "memory"],
+    },
+    SkillNode {
+        concept: "strings",
+        name: "Strings",
+        requires: &["memory", "loops"],
+    },
+    SkillNode {
+        concept: "checksums",
+        name: "Checksums",
+        requires: &["arithmetic", "loops"],
+    },
+    SkillNode {
+        concept: "timing",
+        name: "Timing checks",
+        requires: &["flags"],
+    },
+    SkillNode {
+        concept: "obfuscation",
+        name: "Obfuscation",
+        requires: &["flags", "patching"],
+    },
+];
+
+/// Progress on one concept of the skill tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConceptMastery {
+    pub concept: String,
+    pub name: String,
+    pub requires: Vec<String>,
+    /// Steps from the root of the tree
+    pub depth: usize,
+    /// Puzzles with this concept: (id, title, solved)
+    pub puzzles: Vec<(String, String, bool)>,
+    /// Whether every concept it requires is mastered
+    pub unlocked: bool,
+}
+
+impl ConceptMastery {
+    pub fn solved(&self) -> usize {
+        self.puzzles.iter().filter(|(_, _, solved)| *solved).count()
+    }
+
+    pub fn mastered(&self) -> bool {
+        !self.puzzles.is_empty() && self.solved() >= self.puzzles.len().min(MASTERY_SOLVES)
+    }
+}
+
+/// Mastery of every concept in the tree, plus any other concepts the
+/// puzzles use (as roots), in tree order
+pub fn skill_tree(state: &GameState, puzzles: &[PuzzleListItem]) -> Vec<ConceptMastery> {
+    let mut concepts: Vec<(String, String, Vec<String>)> = SKILL_TREE
+        .iter()
+        .map(|node| {
+            let requires = node.requires.iter().map(|r| r.to_string()).collect();
+            (node.concept.to_string(), node.name.to_string(), requires)
+        })
+        .collect();
+    let mut extra: Vec<&String> = puzzles
+        .iter()
+        .flat_map(|puzzle| &puzzle.concepts)
+        .filter(|concept| {
+            !SKILL_TREE
+                .iter()
+                .any(|node| node.concept == concept.as_str())
+        })
+        .collect();
+    extra.sort();
+    extra.dedup();
+    concepts.extend(
+        extra
+            .into_iter()
+            .map(|concept| (concept.clone(), concept.clone(), Vec::new())),
+    );
+
+    let mut tree: Vec<ConceptMastery> = Vec::with_capacity(concepts.len());
+    for (concept, name, requires) in concepts {
+        let required: Vec<&ConceptMastery> = tree
+            .iter()
+            .filter(|node| requires.contains(&node.concept))
+            .collect();
+        let depth = required
+            .iter()
+            .map(|node| node.depth + 1)
+            .max()
+            .unwrap_or(0);
+        let unlocked = required.iter().all(|node| node.mastered());
+        let puzzles = puzzles
+            .iter()
+            .filter(|puzzle| puzzle.concepts.contains(&concept))
+            .map(|puzzle| {
+                (
+                    puzzle.id.clone(),
+                    puzzle.title.clone(),
+                    state.is_completed(&puzzle.id),
+                )
+            })
+            .collect();
+        tree.push(ConceptMastery {
+            concept,
+            name,
+            requires,
+            depth,
+            puzzles,
+            unlocked,
+        });
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle(id: &str, concepts: &[&str]) -> PuzzleListItem {
+        PuzzleListItem {
+            id: id.to_string(),
+            title: id.to_string(),
+            difficulty: 1,
+            category: String::new(),
+            brief: String::new(),
+            file_path: Default::default(),
+            is_locked: false,
+            prerequisites: Vec::new(),
+            concepts: concepts.iter().map(|c| c.to_string()).collect(),
+            estimated_time_minutes: None,
+            mnemonics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tree_is_ordered() {
+        for (i, node) in SKILL_TREE.iter().enumerate() {
+            for required in node.requires {
+                assert!(
+                    SKILL_TREE[..i].iter().any(|n| n.concept == *required),
+                    "{} before {}",
+                    required,
+                    node.concept
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_mastery_unlocks_branches() {
+        let puzzles = vec![
+            puzzle("a", &["jumps"]),
+            puzzle("b", &["jumps", "flags"]),
+            puzzle("c", &["jumps"]),
+            puzzle("d", &["loops"]),
+            puzzle("e", &["custom"]),
+        ];
+        let node = |tree: &[ConceptMastery], concept: &str| {
+            tree.iter().find(|n| n.concept == concept).unwrap().clone()
+        };
+
+        let mut state = GameState::new();
+        let tree = skill_tree(&state, &puzzles);
+        assert_eq!(tree.last().unwrap().concept, "custom");
+        assert_eq!(node(&tree, "loops").depth, 2);
+        assert!(node(&tree, "jumps").unlocked);
+        assert!(!node(&tree, "flags").unlocked);
+
+        state.complete_puzzle("a", 1);
+        state.complete_puzzle("b", 1);
+        let tree = skill_tree(&state, &puzzles);
+        let jumps = node(&tree, "jumps");
+        assert_eq!((jumps.solved(), jumps.puzzles.len()), (2, 3));
+        assert!(jumps.mastered());
+        assert!(node(&tree, "flags").unlocked);
+        // One puzzle is all flags has, and it's solved
+        assert!(node(&tree, "flags").mastered());
+        assert!(node(&tree, "loops").unlocked);
+        assert!(!node(&tree, "stack").mastered());
+    }
+}
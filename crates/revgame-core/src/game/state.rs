@@ -1,11 +1,50 @@
-use std::collections::HashSet;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 
-use crate::puzzle::Puzzle;
 use super::achievements::{AchievementId, AchievementTracker};
+use super::assist::AssistLevel;
+use super::campaign::CheckpointResult;
+use super::coverage::InstructionCoverage;
+use super::learning::LearningLog;
+use crate::debugger::{BookmarkManager, Debugger};
+use crate::puzzle::Puzzle;
+use crate::reference::DrillStats;
+
+/// Breakpoints and bookmarks set in a puzzle, restored when it's loaded again
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PuzzleMarks {
+    #[serde(default)]
+    pub breakpoints: BTreeSet<u32>,
+    #[serde(default)]
+    pub bookmarks: BookmarkManager,
+}
+
+impl PuzzleMarks {
+    pub fn from_debugger(debugger: &Debugger) -> Self {
+        Self {
+            breakpoints: debugger.breakpoints.iter().copied().collect(),
+            bookmarks: debugger.bookmarks.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.breakpoints.is_empty() && self.bookmarks.count() == 0
+    }
+
+    /// Set the breakpoints and bookmarks in `debugger`
+    pub fn apply(&self, debugger: &mut Debugger) {
+        for &address in &self.breakpoints {
+            debugger.set_breakpoint(address);
+        }
+        debugger.bookmarks = self.bookmarks.clone();
+    }
+}
 
 /// Game state tracking progress and current puzzle
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameState {
     /// IDs of completed puzzles
     pub completed_puzzles: HashSet<String>,
@@ -28,23 +67,57 @@ pub struct GameState {
     /// Number of patches made in current puzzle
     pub patches_made: usize,
 
+    /// Validation checks failed in current puzzle
+    #[serde(default)]
+    pub failed_checks: usize,
+
     /// Start time of current puzzle (unix timestamp)
     pub puzzle_start_time: Option<u64>,
-}
 
-impl Default for GameState {
-    fn default() -> Self {
-        Self {
-            completed_puzzles: HashSet::new(),
-            current_puzzle_id: None,
-            hints_used: 0,
-            total_hints_used: 0,
-            max_difficulty_completed: 0,
-            achievements: AchievementTracker::new(),
-            patches_made: 0,
-            puzzle_start_time: None,
-        }
-    }
+    /// File of the puzzle played most recently, resumed by "Continue"
+    #[serde(default)]
+    pub last_puzzle: Option<PathBuf>,
+
+    /// How much help the player wants
+    #[serde(default)]
+    pub assist: AssistLevel,
+
+    /// Most help the current puzzle has been played with, which decides its score
+    #[serde(default)]
+    pub puzzle_assist: AssistLevel,
+
+    /// Points from solved puzzles, weighted by assist level
+    #[serde(default)]
+    pub score: u32,
+
+    /// Most points each solved puzzle has earned, so solving it again only
+    /// adds what beats that
+    #[serde(default)]
+    pub best_scores: BTreeMap<String, u32>,
+
+    /// Instruction drill results
+    #[serde(default)]
+    pub drills: DrillStats,
+
+    /// Step predictions made in predict mode, by instruction
+    #[serde(default)]
+    pub predictions: DrillStats,
+
+    /// Learning events, recorded only when the player opts in
+    #[serde(default)]
+    pub learning: LearningLog,
+
+    /// Breakpoints and bookmarks by puzzle ID
+    #[serde(default)]
+    pub marks: BTreeMap<String, PuzzleMarks>,
+
+    /// Campaign checkpoint quiz results by checkpoint ID
+    #[serde(default)]
+    pub checkpoints: BTreeMap<String, CheckpointResult>,
+
+    /// Instructions seen and executed in each puzzle
+    #[serde(default)]
+    pub coverage: InstructionCoverage,
 }
 
 impl GameState {
@@ -52,20 +125,42 @@ impl GameState {
         Self::default()
     }
 
+    /// Hash of everything a save holds, to tell whether progress changed
+    /// since the last save
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(self)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Keep the breakpoints and bookmarks set in a puzzle for next time
+    pub fn remember_marks(&mut self, puzzle_id: &str, debugger: &Debugger) {
+        let marks = PuzzleMarks::from_debugger(debugger);
+        if marks.is_empty() {
+            self.marks.remove(puzzle_id);
+        } else {
+            self.marks.insert(puzzle_id.to_string(), marks);
+        }
+    }
+
     /// Mark a puzzle as completed
     pub fn complete_puzzle(&mut self, puzzle_id: &str, difficulty: u8) -> Vec<AchievementId> {
+        self.score += self.puzzle_award(puzzle_id, difficulty);
+        let best = self.best_scores.entry(puzzle_id.to_string()).or_default();
+        *best = (*best).max(self.puzzle_assist.score(difficulty));
         self.completed_puzzles.insert(puzzle_id.to_string());
         self.total_hints_used += self.hints_used;
 
         // Calculate elapsed time
-        let elapsed = self.puzzle_start_time
-            .and_then(|start| {
-                use std::time::{SystemTime, UNIX_EPOCH};
-                SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .ok()
-                    .map(|d| d.as_secs().saturating_sub(start))
-            });
+        let elapsed = self.puzzle_start_time.and_then(|start| {
+            use std::time::{SystemTime, UNIX_EPOCH};
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs().saturating_sub(start))
+        });
 
         // Record in achievement tracker
         let achievements = self.achievements.record_completion(
@@ -75,6 +170,8 @@ impl GameState {
             elapsed,
         );
 
+        self.store_failed_checks(puzzle_id);
+
         // Reset puzzle-specific state
         self.hints_used = 0;
         self.patches_made = 0;
@@ -88,11 +185,72 @@ impl GameState {
         achievements
     }
 
+    /// Points the current puzzle is worth if solved now
+    pub fn puzzle_score(&self, difficulty: u8) -> u32 {
+        self.puzzle_assist.score(difficulty)
+    }
+
+    /// Points solving the current puzzle now adds to the score: all of it
+    /// the first time, afterwards only what beats the best earlier solve
+    pub fn puzzle_award(&self, puzzle_id: &str, difficulty: u8) -> u32 {
+        let best = match self.best_scores.get(puzzle_id) {
+            Some(&best) => best,
+            // Solved before scores were kept per puzzle
+            None if self.is_completed(puzzle_id) => return 0,
+            None => 0,
+        };
+        self.puzzle_score(difficulty).saturating_sub(best)
+    }
+
+    /// Points lost by revealing the current puzzle's solution: half its score
+    pub fn reveal_penalty(&self, difficulty: u8) -> u32 {
+        self.puzzle_score(difficulty) / 2
+    }
+
+    /// Give up on the current puzzle to see its solution. It stays unsolved,
+    /// the streak ends and the penalty comes off the score, which is returned.
+    pub fn reveal_puzzle(&mut self, puzzle_id: &str, difficulty: u8) -> u32 {
+        let penalty = self.reveal_penalty(difficulty);
+        self.score = self.score.saturating_sub(penalty);
+        self.total_hints_used += self.hints_used;
+        self.achievements.record_reveal(puzzle_id, self.hints_used);
+        self.store_failed_checks(puzzle_id);
+
+        // Reset puzzle-specific state
+        self.hints_used = 0;
+        self.patches_made = 0;
+        self.current_puzzle_id = None;
+        self.puzzle_start_time = None;
+
+        penalty
+    }
+
+    /// Whether the player has ever revealed this puzzle's solution
+    pub fn is_revealed(&self, puzzle_id: &str) -> bool {
+        self.achievements
+            .puzzle_stats
+            .get(puzzle_id)
+            .is_some_and(|stats| stats.revealed)
+    }
+
+    /// Change the assist level. Switching to more help mid-puzzle lowers
+    /// that puzzle's score; switching to less doesn't raise it.
+    pub fn set_assist(&mut self, assist: AssistLevel) {
+        self.assist = assist;
+        self.puzzle_assist = if self.current_puzzle_id.is_some() {
+            self.puzzle_assist.min(assist)
+        } else {
+            assist
+        };
+    }
+
     /// Start a new puzzle
     pub fn start_puzzle(&mut self, puzzle_id: &str) {
         self.current_puzzle_id = Some(puzzle_id.to_string());
+        self.puzzle_assist = self.assist;
         self.hints_used = 0;
         self.patches_made = 0;
+        self.failed_checks = 0;
 
         // Record start time
         use std::time::{SystemTime, UNIX_EPOCH};
@@ -112,6 +270,19 @@ impl GameState {
         self.patches_made += 1;
     }
 
+    /// Record a failed validation check
+    pub fn record_failed_check(&mut self) {
+        self.failed_checks += 1;
+    }
+
+    /// Add the current puzzle's failed checks to its stats
+    fn store_failed_checks(&mut self, puzzle_id: &str) {
+        if let Some(stats) = self.achievements.puzzle_stats.get_mut(puzzle_id) {
+            stats.failed_checks += self.failed_checks;
+        }
+        self.failed_checks = 0;
+    }
+
     /// Record an undo
     pub fn record_undo(&mut self) -> Option<AchievementId> {
         self.achievements.record_undo()
@@ -129,11 +300,35 @@ impl GameState {
         self.completed_puzzles.contains(puzzle_id)
     }
 
+    /// Whether the campaign checkpoint with this id has been passed
+    pub fn checkpoint_passed(&self, id: &str) -> bool {
+        self.checkpoints.get(id).is_some_and(|result| result.passed)
+    }
+
+    /// Record an attempt at a checkpoint quiz, giving whether it was the
+    /// first pass
+    pub fn record_checkpoint(&mut self, id: &str, correct: u32, passed: bool) -> bool {
+        let result = self.checkpoints.entry(id.to_string()).or_default();
+        let first_pass = passed && !result.passed;
+        result.attempts += 1;
+        result.best = result.best.max(correct);
+        result.passed |= passed;
+        first_pass
+    }
+
     /// Get completion count
     pub fn completion_count(&self) -> usize {
         self.completed_puzzles.len()
     }
 
+    /// Percentage of `ids` completed, rounded down
+    pub fn completion_percent<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> u32 {
+        let (done, total) = ids.into_iter().fold((0, 0), |(done, total), id| {
+            (done + self.is_completed(id) as u32, total + 1)
+        });
+        (done * 100).checked_div(total).unwrap_or(0)
+    }
+
     /// Check if puzzle prerequisites are met
     pub fn prerequisites_met(&self, puzzle: &Puzzle) -> bool {
         puzzle
@@ -167,4 +362,140 @@ mod tests {
         assert_eq!(state.hints_used, 0);
         assert_eq!(state.current_puzzle_id, None);
     }
+
+    #[test]
+    fn test_marks_survive_a_save_round_trip() {
+        let mut dbg = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        dbg.set_breakpoint(0x1004);
+        dbg.bookmarks.add(0x2000, "key");
+
+        let mut state = GameState::new();
+        state.remember_marks("a", &dbg);
+        let json = serde_json::to_string(&state).unwrap();
+        let loaded: GameState = serde_json::from_str(&json).unwrap();
+
+        let mut fresh = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        loaded.marks["a"].apply(&mut fresh);
+        assert!(fresh.has_breakpoint(0x1004));
+        assert_eq!(fresh.bookmarks.get(0x2000).unwrap().note, "key");
+
+        // Clearing everything forgets the puzzle
+        state.remember_marks(
+            "a",
+            &Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000),
+        );
+        assert!(state.marks.is_empty());
+    }
+
+    #[test]
+    fn test_assist_scoring() {
+        let mut state = GameState::new();
+        state.set_assist(AssistLevel::Hardcore);
+        state.start_puzzle("a");
+        assert_eq!(state.puzzle_score(1), 200);
+
+        // Asking for more help mid-puzzle sticks for that puzzle
+        state.set_assist(AssistLevel::Relaxed);
+        state.set_assist(AssistLevel::Hardcore);
+        assert_eq!(state.puzzle_score(1), 50);
+        state.complete_puzzle("a", 1);
+        assert_eq!(state.score, 50);
+
+        state.start_puzzle("b");
+        state.complete_puzzle("b", 2);
+        assert_eq!(state.score, 450);
+    }
+
+    #[test]
+    fn test_solving_again_scores_only_improvements() {
+        let mut state = GameState::new();
+        state.set_assist(AssistLevel::Relaxed);
+        state.start_puzzle("a");
+        state.complete_puzzle("a", 1);
+        assert_eq!(state.score, 50);
+
+        // The same solve again earns nothing
+        state.start_puzzle("a");
+        assert_eq!(state.puzzle_award("a", 1), 0);
+        state.complete_puzzle("a", 1);
+        assert_eq!(state.score, 50);
+
+        // With less help, only the difference
+        state.set_assist(AssistLevel::Hardcore);
+        state.start_puzzle("a");
+        assert_eq!(state.puzzle_award("a", 1), 150);
+        state.complete_puzzle("a", 1);
+        assert_eq!(state.score, 200);
+        assert_eq!(state.best_scores["a"], 200);
+
+        // A later solve with more help doesn't lower the best
+        state.set_assist(AssistLevel::Relaxed);
+        state.start_puzzle("a");
+        state.complete_puzzle("a", 1);
+        assert_eq!(state.score, 200);
+        assert_eq!(state.best_scores["a"], 200);
+
+        // Solved in an older save that kept no per-puzzle scores
+        state.completed_puzzles.insert("old".to_string());
+        assert_eq!(state.puzzle_award("old", 1), 0);
+    }
+
+    #[test]
+    fn test_reveal_penalty() {
+        let mut state = GameState::new();
+        state.start_puzzle("a");
+        state.complete_puzzle("a", 2);
+        assert_eq!(state.score, 200);
+
+        state.start_puzzle("b");
+        state.use_hint();
+        assert_eq!(state.reveal_penalty(3), 150);
+        assert_eq!(state.reveal_puzzle("b", 3), 150);
+        assert_eq!(state.score, 50);
+        assert!(state.is_revealed("b"));
+        assert!(!state.is_completed("b"));
+        assert_eq!(state.total_hints_used, 1);
+        assert_eq!(state.achievements.current_streak, 0);
+
+        // The score never goes negative
+        state.start_puzzle("c");
+        state.reveal_puzzle("c", 5);
+        assert_eq!(state.score, 0);
+    }
+
+    #[test]
+    fn test_completion_percent() {
+        let mut state = GameState::new();
+        assert_eq!(state.completion_percent([]), 0);
+
+        state.complete_puzzle("a", 1);
+        state.complete_puzzle("elsewhere", 1);
+        assert_eq!(state.completion_percent(["a", "b", "c"]), 33);
+    }
+
+    #[test]
+    fn test_old_save_loads() {
+        let json = r#"{
+            "completed_puzzles": ["basic-001"],
+            "current_puzzle_id": null,
+            "hints_used": 0,
+            "total_hints_used": 0,
+            "max_difficulty_completed": 1,
+            "achievements": {
+                "unlocked": [],
+                "total_points": 0,
+                "puzzle_stats": {},
+                "current_streak": 2,
+                "best_streak": 2,
+                "total_completed": 1,
+                "total_patches": 1,
+                "total_undos": 0
+            },
+            "patches_made": 0,
+            "puzzle_start_time": null
+        }"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        assert!(state.is_completed("basic-001"));
+        assert_eq!(state.last_puzzle, None);
+    }
 }
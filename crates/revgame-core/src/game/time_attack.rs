@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+
+/// Puzzles a time attack runs through, in order: the short patching
+/// puzzles of the first chapters
+pub const TIME_ATTACK_COURSE: [&str; 6] = [
+    "basic-001",
+    "basic-002",
+    "basic-003",
+    "basic-004",
+    "basic-005",
+    "flow-001",
+];
+
+/// Best runs kept per profile
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// A time attack in progress. Times are milliseconds on whatever clock the
+/// caller passes in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeAttack {
+    course: Vec<String>,
+    started_ms: u64,
+    /// Time since the start at which each puzzle so far was solved
+    splits: Vec<u64>,
+}
+
+impl TimeAttack {
+    pub fn new(course: &[&str], now_ms: u64) -> Self {
+        Self {
+            course: course.iter().map(|id| id.to_string()).collect(),
+            started_ms: now_ms,
+            splits: Vec::new(),
+        }
+    }
+
+    pub fn course(&self) -> &[String] {
+        &self.course
+    }
+
+    /// Id of the puzzle being played, `None` once the course is done
+    pub fn current(&self) -> Option<&str> {
+        self.course.get(self.splits.len()).map(String::as_str)
+    }
+
+    pub fn splits(&self) -> &[u64] {
+        &self.splits
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.splits.len() >= self.course.len()
+    }
+
+    /// Time on the global timer, stopped at the last split once finished
+    pub fn elapsed_ms(&self, now_ms: u64) -> u64 {
+        match self.splits.last() {
+            Some(&total) if self.is_finished() => total,
+            _ => now_ms.saturating_sub(self.started_ms),
+        }
+    }
+
+    /// Time spent on the current puzzle so far
+    pub fn split_elapsed_ms(&self, now_ms: u64) -> u64 {
+        self.elapsed_ms(now_ms) - self.splits.last().copied().unwrap_or(0)
+    }
+
+    /// Record that the current puzzle was solved, giving its split time
+    pub fn split(&mut self, now_ms: u64) -> Option<u64> {
+        if self.is_finished() {
+            return None;
+        }
+        let previous = self.splits.last().copied().unwrap_or(0);
+        let total = now_ms.saturating_sub(self.started_ms).max(previous);
+        self.splits.push(total);
+        Some(total - previous)
+    }
+}
+
+/// A finished time attack on the leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub profile: String,
+    pub total_ms: u64,
+    /// Time since the start at which each puzzle was solved
+    pub splits: Vec<u64>,
+    /// When the run finished (unix seconds)
+    pub finished_at: u64,
+}
+
+impl LeaderboardEntry {
+    /// Time spent on puzzle `index` of the course
+    pub fn split_ms(&self, index: usize) -> Option<u64> {
+        let previous = index
+            .checked_sub(1)
+            .map_or(Some(0), |i| self.splits.get(i).copied())?;
+        Some(self.splits.get(index)? - previous)
+    }
+}
+
+/// Local time attack results, best first within each profile
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// Add a finished run, giving its place among the profile's runs (1 is
+    /// best), or `None` if it was too slow to be kept
+    pub fn record(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+        let profile = entry.profile.clone();
+        let place = self
+            .for_profile(&profile)
+            .iter()
+            .filter(|other| other.total_ms <= entry.total_ms)
+            .count();
+        if place >= LEADERBOARD_SIZE {
+            return None;
+        }
+
+        let at = self
+            .entries
+            .partition_point(|other| other.total_ms <= entry.total_ms);
+        self.entries.insert(at, entry);
+        let mut kept = 0;
+        self.entries.retain(|other| {
+            if other.profile != profile {
+                return true;
+            }
+            kept += 1;
+            kept <= LEADERBOARD_SIZE
+        });
+        Some(place + 1)
+    }
+
+    /// A profile's runs, fastest first
+    pub fn for_profile(&self, profile: &str) -> Vec<&LeaderboardEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.profile == profile)
+            .collect()
+    }
+
+    pub fn best(&self, profile: &str) -> Option<&LeaderboardEntry> {
+        self.entries.iter().find(|entry| entry.profile == profile)
+    }
+}
+
+/// `m:ss.t` for a timer display
+pub fn format_time(ms: u64) -> String {
+    format!("{}:{:02}.{}", ms / 60_000, ms / 1000 % 60, ms / 100 % 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(profile: &str, total_ms: u64) -> LeaderboardEntry {
+        LeaderboardEntry {
+            profile: profile.to_string(),
+            total_ms,
+            splits: vec![total_ms],
+            finished_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_time_attack_splits() {
+        let mut run = TimeAttack::new(&["a", "b"], 1000);
+        assert_eq!(run.current(), Some("a"));
+        assert_eq!(run.split(4000), Some(3000));
+        assert_eq!(run.split_elapsed_ms(5500), 1500);
+        assert_eq!(run.split(6000), Some(2000));
+        assert!(run.is_finished());
+        assert_eq!(run.current(), None);
+        assert_eq!(run.split(7000), None);
+        assert_eq!(run.splits(), [3000, 5000]);
+        assert_eq!(run.elapsed_ms(60_000), 5000);
+        assert_eq!(format_time(83_456), "1:23.4");
+    }
+
+    #[test]
+    fn test_leaderboard_is_per_profile_and_bounded() {
+        let mut board = Leaderboard::default();
+        assert_eq!(board.record(entry("ada", 9000)), Some(1));
+        assert_eq!(board.record(entry("ada", 5000)), Some(1));
+        assert_eq!(board.record(entry("bob", 1000)), Some(1));
+        assert_eq!(board.record(entry("ada", 7000)), Some(2));
+        assert_eq!(board.best("ada").unwrap().total_ms, 5000);
+        let run = LeaderboardEntry {
+            splits: vec![3000, 5000],
+            ..entry("ada", 5000)
+        };
+        assert_eq!(
+            (run.split_ms(0), run.split_ms(1), run.split_ms(2)),
+            (Some(3000), Some(2000), None)
+        );
+
+        for total_ms in 0..LEADERBOARD_SIZE as u64 {
+            board.record(entry("ada", 100 + total_ms));
+        }
+        assert_eq!(board.for_profile("ada").len(), LEADERBOARD_SIZE);
+        assert_eq!(board.record(entry("ada", 50_000)), None);
+        assert_eq!(board.for_profile("bob").len(), 1);
+    }
+}
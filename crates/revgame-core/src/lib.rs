@@ -1,10 +1,12 @@
-pub mod emulator;
 pub mod debugger;
-pub mod puzzle;
+pub mod emulator;
 pub mod game;
+pub mod plugin;
+pub mod puzzle;
 pub mod reference;
 
-pub use emulator::{CpuState, Memory, Executor, EmulatorError};
-pub use debugger::{Debugger, DebuggerState, DebuggerError};
+pub use debugger::{Debugger, DebuggerError, DebuggerState};
+pub use emulator::{CpuState, EmulatorError, Executor, Memory};
+pub use plugin::{AnalysisPass, CommandPlugin, Finding, PluginRegistry};
 pub use puzzle::{Puzzle, PuzzleMetadata, ValidationResult};
-pub use reference::{InstructionReference, InstructionInfo, InstructionCategory};
+pub use reference::{InstructionCategory, InstructionInfo, InstructionReference};
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use iced_x86::{Mnemonic, OpKind};
+
+use super::{AnalysisPass, CommandPlugin, Finding};
+use crate::debugger::Debugger;
+use crate::emulator::{DisassemblyLine, DisassemblyMode};
+
+/// Lines the `calls` command walks through at most
+const MAX_LINES: usize = 4096;
+
+/// Example plugin (feature `plugin-calls`): finds the functions the code
+/// calls directly and where from
+pub struct CallsPlugin;
+
+/// Call sites of each direct call target, by target
+fn call_sites(lines: &[DisassemblyLine]) -> BTreeMap<u32, Vec<u32>> {
+    let mut targets: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+    for line in lines.iter().filter(|line| line.is_code()) {
+        let instr = &line.instruction;
+        if instr.mnemonic() == Mnemonic::Call && instr.op0_kind() == OpKind::NearBranch32 {
+            targets
+                .entry(instr.near_branch_target() as u32)
+                .or_default()
+                .push(line.address);
+        }
+    }
+    targets
+}
+
+impl AnalysisPass for CallsPlugin {
+    fn name(&self) -> &str {
+        "calls"
+    }
+
+    fn analyze(&self, _debugger: &Debugger, lines: &[DisassemblyLine]) -> Vec<Finding> {
+        call_sites(lines)
+            .into_iter()
+            .map(|(target, sites)| Finding {
+                address: Some(target),
+                text: format!(
+                    "function called from {}",
+                    sites
+                        .iter()
+                        .map(|site| format!("0x{:08X}", site))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            })
+            .collect()
+    }
+}
+
+impl CommandPlugin for CallsPlugin {
+    fn name(&self) -> &str {
+        "calls"
+    }
+
+    fn commands(&self) -> &[(&str, &str)] {
+        &[("calls", "calls (functions called in the code region)")]
+    }
+
+    fn run(
+        &mut self,
+        _command: &str,
+        _args: &[&str],
+        debugger: &mut Debugger,
+    ) -> Result<String, String> {
+        let start = debugger
+            .memory
+            .get_region(debugger.cpu.eip)
+            .map(|region| region.start)
+            .ok_or("EIP is outside memory")?;
+        let lines = debugger.disassemble_in(DisassemblyMode::Recursive, start, MAX_LINES);
+        let targets = call_sites(&lines);
+        if targets.is_empty() {
+            return Ok("No direct calls in the code".to_string());
+        }
+        let summary: Vec<String> = targets
+            .iter()
+            .map(|(target, sites)| format!("0x{:08X} x{}", target, sites.len()))
+            .collect();
+        Ok(format!("Functions called: {}", summary.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calls_finds_call_sites() {
+        let mut dbg = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        // CALL 0x100B; CALL 0x100B; HLT; RET
+        dbg.load_code(
+            0x1000,
+            &[0xE8, 0x06, 0, 0, 0, 0xE8, 0x01, 0, 0, 0, 0xF4, 0xC3],
+        )
+        .unwrap();
+        let lines = dbg.disassemble(4);
+
+        let findings = CallsPlugin.analyze(&dbg, &lines);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].address, Some(0x100B));
+        assert_eq!(
+            findings[0].text,
+            "function called from 0x00001000, 0x00001005"
+        );
+        assert_eq!(
+            CallsPlugin.run("calls", &[], &mut dbg).unwrap(),
+            "Functions called: 0x0000100B x2"
+        );
+    }
+}
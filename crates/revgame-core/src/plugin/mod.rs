@@ -0,0 +1,214 @@
+//! Extension points for code that isn't part of RevGame proper: analysis
+//! passes over the disassembly and extra commands for the command line.
+//! Plugins are compiled in, each behind its own cargo feature, and
+//! `PluginRegistry::builtin` picks up the ones that were enabled.
+
+#[cfg(feature = "plugin-calls")]
+mod calls;
+
+#[cfg(feature = "plugin-calls")]
+pub use calls::CallsPlugin;
+
+use crate::debugger::Debugger;
+use crate::emulator::DisassemblyLine;
+
+/// Something an analysis pass noticed, optionally at an address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub address: Option<u32>,
+    pub text: String,
+}
+
+/// Looks over the program and reports findings. Runs on demand, so it may
+/// take a while on big programs but must not change anything.
+pub trait AnalysisPass {
+    fn name(&self) -> &str;
+
+    /// `lines` is the disassembly currently on screen
+    fn analyze(&self, debugger: &Debugger, lines: &[DisassemblyLine]) -> Vec<Finding>;
+}
+
+/// Handles command-line commands the game itself doesn't know
+pub trait CommandPlugin {
+    fn name(&self) -> &str;
+
+    /// Commands handled, with a usage line for each
+    fn commands(&self) -> &[(&str, &str)];
+
+    /// Run `command`; the result is shown in the status bar
+    fn run(
+        &mut self,
+        command: &str,
+        args: &[&str],
+        debugger: &mut Debugger,
+    ) -> Result<String, String>;
+}
+
+/// Analysis passes and command handlers, asked in the order they were
+/// registered
+#[derive(Default)]
+pub struct PluginRegistry {
+    analyses: Vec<Box<dyn AnalysisPass>>,
+    commands: Vec<Box<dyn CommandPlugin>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with every plugin enabled at compile time
+    pub fn builtin() -> Self {
+        #[allow(unused_mut)]
+        let mut registry = Self::new();
+        #[cfg(feature = "plugin-calls")]
+        {
+            registry.register_analysis(Box::new(CallsPlugin));
+            registry.register_command(Box::new(CallsPlugin));
+        }
+        registry
+    }
+
+    pub fn register_analysis(&mut self, pass: Box<dyn AnalysisPass>) {
+        self.analyses.push(pass);
+    }
+
+    pub fn register_command(&mut self, plugin: Box<dyn CommandPlugin>) {
+        self.commands.push(plugin);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.analyses.is_empty() && self.commands.is_empty()
+    }
+
+    /// Names of the registered plugins, analysis passes first
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.analyses.iter().map(|pass| pass.name()).collect();
+        for name in self.commands.iter().map(|plugin| plugin.name()) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Usage lines of every plugin command
+    pub fn usage(&self) -> Vec<&str> {
+        self.commands
+            .iter()
+            .flat_map(|plugin| plugin.commands().iter().map(|(_, usage)| *usage))
+            .collect()
+    }
+
+    /// Run every analysis pass, pairing each finding with its pass's name
+    pub fn analyze(
+        &self,
+        debugger: &Debugger,
+        lines: &[DisassemblyLine],
+    ) -> Vec<(String, Finding)> {
+        self.analyses
+            .iter()
+            .flat_map(|pass| {
+                let name = pass.name().to_string();
+                pass.analyze(debugger, lines)
+                    .into_iter()
+                    .map(move |finding| (name.clone(), finding))
+            })
+            .collect()
+    }
+
+    /// Run a command with the first plugin that handles it, or `None` if
+    /// none does
+    pub fn run_command(
+        &mut self,
+        command: &str,
+        args: &[&str],
+        debugger: &mut Debugger,
+    ) -> Option<Result<String, String>> {
+        let plugin = self
+            .commands
+            .iter_mut()
+            .find(|plugin| plugin.commands().iter().any(|(name, _)| *name == command))?;
+        Some(plugin.run(command, args, debugger))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Halts;
+
+    impl AnalysisPass for Halts {
+        fn name(&self) -> &str {
+            "halts"
+        }
+
+        fn analyze(&self, _debugger: &Debugger, lines: &[DisassemblyLine]) -> Vec<Finding> {
+            lines
+                .iter()
+                .filter(|line| line.text == "hlt")
+                .map(|line| Finding {
+                    address: Some(line.address),
+                    text: "program stops here".to_string(),
+                })
+                .collect()
+        }
+    }
+
+    struct Poke;
+
+    impl CommandPlugin for Poke {
+        fn name(&self) -> &str {
+            "poke"
+        }
+
+        fn commands(&self) -> &[(&str, &str)] {
+            &[("poke", "poke <value>")]
+        }
+
+        fn run(
+            &mut self,
+            _command: &str,
+            args: &[&str],
+            debugger: &mut Debugger,
+        ) -> Result<String, String> {
+            let value = args
+                .first()
+                .and_then(|value| value.parse().ok())
+                .ok_or("Usage: poke <value>")?;
+            debugger.cpu.regs.eax = value;
+            Ok(format!("EAX = {}", value))
+        }
+    }
+
+    #[test]
+    fn test_registry_dispatches_to_plugins() {
+        let mut dbg = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        // NOP; HLT
+        dbg.load_code(0x1000, &[0x90, 0xF4]).unwrap();
+        let lines = dbg.disassemble(2);
+
+        let mut registry = PluginRegistry::new();
+        registry.register_analysis(Box::new(Halts));
+        registry.register_command(Box::new(Poke));
+        assert_eq!(registry.names(), ["halts", "poke"]);
+        assert_eq!(registry.usage(), ["poke <value>"]);
+
+        let findings = registry.analyze(&dbg, &lines);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].0, "halts");
+        assert_eq!(findings[0].1.address, Some(0x1001));
+
+        assert_eq!(
+            registry.run_command("poke", &["7"], &mut dbg),
+            Some(Ok("EAX = 7".to_string()))
+        );
+        assert_eq!(dbg.cpu.regs.eax, 7);
+        assert!(registry
+            .run_command("poke", &[], &mut dbg)
+            .unwrap()
+            .is_err());
+        assert_eq!(registry.run_command("peek", &[], &mut dbg), None);
+    }
+}
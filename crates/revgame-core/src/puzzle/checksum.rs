@@ -0,0 +1,56 @@
+/// How a self-checking program folds its code bytes into one value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChecksumAlgorithm {
+    /// 32-bit sum of the bytes (`movzx eax, byte [esi]; add ebx, eax`)
+    #[default]
+    Sum8,
+    /// XOR of the bytes (`xor bl, [esi]`)
+    Xor8,
+}
+
+impl ChecksumAlgorithm {
+    /// Parse an algorithm name from puzzle TOML
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "sum8" | "sum" => Some(ChecksumAlgorithm::Sum8),
+            "xor8" | "xor" => Some(ChecksumAlgorithm::Xor8),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sum8 => "sum8",
+            ChecksumAlgorithm::Xor8 => "xor8",
+        }
+    }
+
+    /// Checksum of `bytes`
+    pub fn compute(&self, bytes: &[u8]) -> u32 {
+        match self {
+            ChecksumAlgorithm::Sum8 => bytes
+                .iter()
+                .fold(0u32, |acc, &b| acc.wrapping_add(u32::from(b))),
+            ChecksumAlgorithm::Xor8 => u32::from(bytes.iter().fold(0u8, |acc, &b| acc ^ b)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        let bytes = [0x75, 0x06, 0xFF, 0x01];
+        assert_eq!(ChecksumAlgorithm::Sum8.compute(&bytes), 0x17B);
+        assert_eq!(
+            ChecksumAlgorithm::Xor8.compute(&bytes),
+            0x75 ^ 0x06 ^ 0xFF ^ 0x01
+        );
+        assert_eq!(
+            ChecksumAlgorithm::from_name("XOR"),
+            Some(ChecksumAlgorithm::Xor8)
+        );
+    }
+}
@@ -0,0 +1,482 @@
+//! Debugger expressions: one-line conditions and values over registers,
+//! flags and memory, used by hint rules, pinned expressions, the converter
+//! and scripts' `expr()`.
+//!
+//! Expressions use integers (decimal or 0x hex), registers (`eax`, `eip`,
+//! ...), flags (`zf`, `cf`, `sf`, `of`), `halted`, `steps`, `patches`,
+//! `breakpoints`, bookmark labels, `byte[addr]` / `word[addr]` /
+//! `dword[addr]`, and the usual arithmetic, bitwise, comparison and
+//! `and`/`or`/`not` operators.
+
+use crate::emulator::Register;
+
+use super::ScriptContext;
+
+/// A parsed expression, such as a hint rule's condition
+#[derive(Debug, Clone)]
+pub struct Condition {
+    expr: Expr,
+}
+
+impl Condition {
+    /// Parse a single-line expression
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            pos: 0,
+            depth: 0,
+        };
+        let expr = parser.expr()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(format!("Unexpected text in condition: {}", source));
+        }
+        Ok(Self { expr })
+    }
+
+    /// Whether the condition holds (false if it fails to evaluate)
+    pub fn holds(&self, ctx: &ScriptContext) -> bool {
+        self.value(ctx).is_ok_and(|value| value != 0)
+    }
+
+    /// Evaluate the expression
+    pub fn value(&self, ctx: &ScriptContext) -> Result<i64, String> {
+        eval(&self.expr, ctx)
+    }
+
+    /// The outermost operator and its operand values, for `a ^ b` style
+    /// expressions
+    pub fn operands(&self, ctx: &ScriptContext) -> Option<(&'static str, i64, i64)> {
+        let Expr::Binary(op, lhs, rhs) = &self.expr else {
+            return None;
+        };
+        Some((op.symbol(), eval(lhs, ctx).ok()?, eval(rhs, ctx).ok()?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnaryOp {
+    Neg,
+    BitNot,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shl,
+    Shr,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::Or => "or",
+            BinaryOp::And => "and",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::BitOr => "|",
+            BinaryOp::BitXor => "^",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::Shl => "<<",
+            BinaryOp::Shr => ">>",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Num(i64),
+    Name(String),
+    /// Memory read of 1, 2 or 4 bytes
+    Mem(usize, Box<Expr>),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(i64),
+    Ident(String),
+    Sym(&'static str),
+}
+
+const SYMBOLS: [&str; 21] = [
+    "==", "!=", "<=", ">=", "<<", ">>", "<", ">", "+", "-", "*", "/", "%", "&", "|", "^", "~", "(",
+    ")", "[", "]",
+];
+
+fn tokenize(line: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '#' {
+            break;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_alphanumeric() {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+                Some(hex) => i64::from_str_radix(hex, 16),
+                None => text.parse(),
+            }
+            .map_err(|_| format!("Invalid number: {}", text))?;
+            tokens.push(Token::Num(value));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(
+                chars[start..i].iter().collect::<String>().to_lowercase(),
+            ));
+        } else {
+            let rest: String = chars[i..].iter().take(2).collect();
+            let sym = SYMBOLS
+                .iter()
+                .find(|s| rest.starts_with(**s))
+                .ok_or_else(|| format!("Unexpected character '{}'", c))?;
+            tokens.push(Token::Sym(sym));
+            i += sym.len();
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Deepest an expression may nest: brackets, prefix operators and operator
+/// chains all count, since parsing and evaluating recurse on them
+/// and a hostile puzzle could otherwise overflow the stack
+const MAX_DEPTH: usize = 64;
+
+/// Recursive-descent parser over one line of tokens
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_sym(&mut self, sym: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Sym(s)) if *s == sym) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(w)) if w == word) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Go a level deeper, failing past `MAX_DEPTH`; callers step back out
+    /// with `ascend` once the nested part is parsed
+    fn descend(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("expression nested too deeply".to_string());
+        }
+        Ok(())
+    }
+
+    fn ascend(&mut self, levels: usize) {
+        self.depth -= levels;
+    }
+
+    fn expect_sym(&mut self, sym: &str) -> Result<(), String> {
+        if self.eat_sym(sym) {
+            Ok(())
+        } else {
+            Err(format!("Expected '{}'", sym))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        self.binary(0)
+    }
+
+    /// Precedence climbing; level 0 binds loosest
+    fn binary(&mut self, level: usize) -> Result<Expr, String> {
+        const LEVELS: [&[(&str, BinaryOp)]; 9] = [
+            &[("or", BinaryOp::Or)],
+            &[("and", BinaryOp::And)],
+            &[
+                ("==", BinaryOp::Eq),
+                ("!=", BinaryOp::Ne),
+                ("<=", BinaryOp::Le),
+                (">=", BinaryOp::Ge),
+                ("<", BinaryOp::Lt),
+                (">", BinaryOp::Gt),
+            ],
+            &[("|", BinaryOp::BitOr)],
+            &[("^", BinaryOp::BitXor)],
+            &[("&", BinaryOp::BitAnd)],
+            &[("<<", BinaryOp::Shl), (">>", BinaryOp::Shr)],
+            &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)],
+            &[
+                ("*", BinaryOp::Mul),
+                ("/", BinaryOp::Div),
+                ("%", BinaryOp::Rem),
+            ],
+        ];
+
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+
+        let mut lhs = self.binary(level + 1)?;
+        // Each operator in a chain nests the left-hand side one deeper
+        let mut chained = 0;
+        'outer: loop {
+            for (text, op) in LEVELS[level] {
+                let matched = match self.peek() {
+                    Some(Token::Sym(sym)) => sym == text,
+                    Some(Token::Ident(word)) => word == text,
+                    _ => false,
+                };
+                if matched {
+                    self.pos += 1;
+                    self.descend()?;
+                    chained += 1;
+                    let rhs = self.binary(level + 1)?;
+                    lhs = Expr::Binary(*op, Box::new(lhs), Box::new(rhs));
+                    continue 'outer;
+                }
+            }
+            self.ascend(chained);
+            return Ok(lhs);
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        let op = if self.eat_sym("-") {
+            UnaryOp::Neg
+        } else if self.eat_sym("~") {
+            UnaryOp::BitNot
+        } else if self.eat_word("not") {
+            UnaryOp::Not
+        } else {
+            return self.atom();
+        };
+        self.descend()?;
+        let operand = self.unary()?;
+        self.ascend(1);
+        Ok(Expr::Unary(op, Box::new(operand)))
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Num(value)) => Ok(Expr::Num(value)),
+            Some(Token::Ident(name)) => {
+                let width = match name.as_str() {
+                    "byte" => Some(1),
+                    "word" => Some(2),
+                    "dword" => Some(4),
+                    _ => None,
+                };
+                match width {
+                    Some(width) if self.eat_sym("[") => {
+                        self.descend()?;
+                        let address = self.expr()?;
+                        self.expect_sym("]")?;
+                        self.ascend(1);
+                        Ok(Expr::Mem(width, Box::new(address)))
+                    }
+                    _ => Ok(Expr::Name(name)),
+                }
+            }
+            Some(Token::Sym("(")) => {
+                self.descend()?;
+                let inner = self.expr()?;
+                self.expect_sym(")")?;
+                self.ascend(1);
+                Ok(inner)
+            }
+            _ => Err("Expected a value".to_string()),
+        }
+    }
+}
+
+fn eval(expr: &Expr, ctx: &ScriptContext) -> Result<i64, String> {
+    match expr {
+        Expr::Num(value) => Ok(*value),
+        Expr::Name(name) => lookup(name, ctx),
+        Expr::Mem(width, address) => {
+            let address = eval(address, ctx)? as u32;
+            // Peek so that scripts never trigger device side effects
+            let bytes = ctx
+                .memory
+                .slice(address, *width)
+                .ok_or_else(|| format!("Address 0x{:08X} out of bounds", address))?;
+            Ok(bytes
+                .iter()
+                .rev()
+                .fold(0i64, |acc, &b| (acc << 8) | b as i64))
+        }
+        Expr::Unary(op, inner) => {
+            let value = eval(inner, ctx)?;
+            Ok(match op {
+                UnaryOp::Neg => value.wrapping_neg(),
+                UnaryOp::BitNot => !value,
+                UnaryOp::Not => (value == 0) as i64,
+            })
+        }
+        Expr::Binary(op, lhs, rhs) => {
+            let a = eval(lhs, ctx)?;
+            // Short-circuit logical operators
+            match op {
+                BinaryOp::And if a == 0 => return Ok(0),
+                BinaryOp::Or if a != 0 => return Ok(1),
+                _ => {}
+            }
+            let b = eval(rhs, ctx)?;
+            Ok(match op {
+                BinaryOp::Or | BinaryOp::And => (b != 0) as i64,
+                BinaryOp::Eq => (a == b) as i64,
+                BinaryOp::Ne => (a != b) as i64,
+                BinaryOp::Lt => (a < b) as i64,
+                BinaryOp::Le => (a <= b) as i64,
+                BinaryOp::Gt => (a > b) as i64,
+                BinaryOp::Ge => (a >= b) as i64,
+                BinaryOp::BitOr => a | b,
+                BinaryOp::BitXor => a ^ b,
+                BinaryOp::BitAnd => a & b,
+                BinaryOp::Shl => a.wrapping_shl(b as u32),
+                BinaryOp::Shr => a.wrapping_shr(b as u32),
+                BinaryOp::Add => a.wrapping_add(b),
+                BinaryOp::Sub => a.wrapping_sub(b),
+                BinaryOp::Mul => a.wrapping_mul(b),
+                BinaryOp::Div | BinaryOp::Rem if b == 0 => {
+                    return Err("Division by zero".to_string())
+                }
+                BinaryOp::Div => a.wrapping_div(b),
+                BinaryOp::Rem => a.wrapping_rem(b),
+            })
+        }
+    }
+}
+
+fn lookup(name: &str, ctx: &ScriptContext) -> Result<i64, String> {
+    if let Some(register) = Register::from_name(name) {
+        return Ok(ctx.cpu.get_register(register) as i64);
+    }
+
+    let flags = &ctx.cpu.eflags;
+    match name {
+        "eip" => Ok(ctx.cpu.eip as i64),
+        "zf" => Ok(flags.zf as i64),
+        "cf" => Ok(flags.cf as i64),
+        "sf" => Ok(flags.sf as i64),
+        "of" => Ok(flags.of as i64),
+        "steps" => Ok(ctx.steps as i64),
+        "patches" => Ok(ctx.patches as i64),
+        "breakpoints" => Ok(ctx.breakpoints as i64),
+        "halted" => Ok(ctx.cpu.halted as i64),
+        "true" => Ok(1),
+        "false" => Ok(0),
+        _ => ctx
+            .labels
+            .and_then(|labels| labels.find_note(name))
+            .map(|bookmark| bookmark.address as i64)
+            .ok_or_else(|| format!("Unknown name '{}'", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emulator::{CpuState, Memory};
+
+    fn context<'a>(cpu: &'a CpuState, memory: &'a Memory) -> ScriptContext<'a> {
+        ScriptContext {
+            cpu,
+            memory,
+            steps: 10,
+            patches: 1,
+            breakpoints: 0,
+            labels: None,
+        }
+    }
+
+    #[test]
+    fn test_precedence_and_errors() {
+        let cpu = CpuState::default();
+        let mut memory = Memory::new(0x1000);
+        memory.write_u32(0x100, 0x1337).unwrap();
+        let ctx = context(&cpu, &memory);
+
+        let value = |text: &str| Condition::parse(text).unwrap().value(&ctx);
+        assert_eq!(
+            value("2 + 3 * 4 == 14 and (1 << 4 | 1) == 17 and not zf"),
+            Ok(1)
+        );
+        assert_eq!(value("dword[0x100] ^ 0xFF"), Ok(0x13C8));
+        assert_eq!(value("patches <= 2 and steps == 10"), Ok(1));
+        assert!(value("1 / (eax - eax)").is_err());
+        assert!(value("nothing").is_err());
+        assert!(Condition::parse("eax +").is_err());
+        assert!(Condition::parse("eax eax").is_err());
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        assert!(Condition::parse(&format!("{}1{}", "(".repeat(20), ")".repeat(20))).is_ok());
+        for deep in [
+            format!("{}1", "(".repeat(100_000)),
+            format!("{}zf", "not ".repeat(100_000)),
+            format!("{}0x2000", "byte[".repeat(1_000)),
+            format!("1{}", " + 1".repeat(1_000)),
+        ] {
+            assert_eq!(
+                Condition::parse(&deep).unwrap_err(),
+                "expression nested too deeply"
+            );
+        }
+    }
+}
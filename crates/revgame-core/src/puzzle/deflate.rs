@@ -0,0 +1,392 @@
+//! Raw DEFLATE (RFC 1951), just enough for share links: the encoder emits a
+//! single fixed-Huffman block, the decoder reads any valid stream.
+
+use std::collections::HashMap;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// Order code length code lengths are stored in a dynamic block header
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+const WINDOW: usize = 32 * 1024;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+/// Earlier occurrences tried per position
+const MAX_CHAIN: usize = 64;
+
+/// Largest output `inflate` produces, so a crafted link can't exhaust memory
+pub const MAX_INFLATED: usize = 1024 * 1024;
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, count: u32) {
+        self.bit |= value << self.bits;
+        self.bits += count;
+        while self.bits >= 8 {
+            self.bytes.push(self.bit as u8);
+            self.bit >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    /// Huffman codes go most significant bit first
+    fn write_code(&mut self, code: u32, length: u32) {
+        let reversed = code.reverse_bits() >> (32 - length);
+        self.write(reversed, length);
+    }
+
+    fn write_literal(&mut self, symbol: u16) {
+        let symbol = u32::from(symbol);
+        match symbol {
+            0..=143 => self.write_code(0x30 + symbol, 8),
+            144..=255 => self.write_code(0x190 + symbol - 144, 9),
+            256..=279 => self.write_code(symbol - 256, 7),
+            _ => self.write_code(0xC0 + symbol - 280, 8),
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.bit as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compress `data` into one fixed-Huffman block
+pub fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter {
+        bytes: Vec::new(),
+        bit: 0,
+        bits: 0,
+    };
+    // BFINAL, then BTYPE 01 (fixed codes)
+    out.write(1, 1);
+    out.write(1, 2);
+
+    let mut chains = Chains {
+        heads: HashMap::new(),
+        previous: vec![usize::MAX; data.len()],
+    };
+
+    let mut at = 0;
+    while at < data.len() {
+        let (length, distance) = chains.longest_match(data, at);
+        if length >= MIN_MATCH {
+            let code = LENGTH_BASE
+                .iter()
+                .rposition(|&base| base as usize <= length)
+                .unwrap();
+            out.write_literal(257 + code as u16);
+            out.write(
+                (length - LENGTH_BASE[code] as usize) as u32,
+                u32::from(LENGTH_EXTRA[code]),
+            );
+            let code = DIST_BASE
+                .iter()
+                .rposition(|&base| base as usize <= distance)
+                .unwrap();
+            out.write_code(code as u32, 5);
+            out.write(
+                (distance - DIST_BASE[code] as usize) as u32,
+                u32::from(DIST_EXTRA[code]),
+            );
+            for i in at..at + length {
+                chains.insert(data, i);
+            }
+            at += length;
+        } else {
+            out.write_literal(u16::from(data[at]));
+            chains.insert(data, at);
+            at += 1;
+        }
+    }
+    out.write_literal(256);
+    out.finish()
+}
+
+/// Earlier positions of each 3-byte sequence, most recent first
+struct Chains {
+    heads: HashMap<[u8; 3], usize>,
+    /// Previous position with the same 3 bytes, or `usize::MAX`
+    previous: Vec<usize>,
+}
+
+impl Chains {
+    fn insert(&mut self, data: &[u8], at: usize) {
+        if at + MIN_MATCH <= data.len() {
+            let key = [data[at], data[at + 1], data[at + 2]];
+            self.previous[at] = self.heads.insert(key, at).unwrap_or(usize::MAX);
+        }
+    }
+
+    /// Longest earlier match for the bytes at `at`, as (length, distance)
+    fn longest_match(&self, data: &[u8], at: usize) -> (usize, usize) {
+        if at + MIN_MATCH > data.len() {
+            return (0, 0);
+        }
+        let limit = (data.len() - at).min(MAX_MATCH);
+        let mut best = (0, 0);
+        let mut candidate = self
+            .heads
+            .get(&[data[at], data[at + 1], data[at + 2]])
+            .copied();
+        for _ in 0..MAX_CHAIN {
+            let Some(from) = candidate.filter(|&from| at - from <= WINDOW) else {
+                break;
+            };
+            let length = (0..limit)
+                .take_while(|&i| data[from + i] == data[at + i])
+                .count();
+            if length > best.0 {
+                best = (length, at - from);
+                if length == limit {
+                    break;
+                }
+            }
+            candidate = Some(self.previous[from]).filter(|&p| p != usize::MAX);
+        }
+        best
+    }
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    /// Position in bits
+    at: usize,
+}
+
+impl BitReader<'_> {
+    fn bit(&mut self) -> Result<u32, String> {
+        let byte = self
+            .data
+            .get(self.at / 8)
+            .ok_or("Compressed data ends early")?;
+        let bit = (byte >> (self.at % 8)) & 1;
+        self.at += 1;
+        Ok(u32::from(bit))
+    }
+
+    fn bits(&mut self, count: u8) -> Result<u32, String> {
+        let mut value = 0;
+        for i in 0..count {
+            value |= self.bit()? << i;
+        }
+        Ok(value)
+    }
+
+    fn align(&mut self) {
+        self.at = self.at.div_ceil(8) * 8;
+    }
+}
+
+/// Canonical Huffman code, decoded a bit at a time
+struct Huffman {
+    /// Codes of each length
+    counts: [u16; 16],
+    /// Symbols ordered by code
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0u16; 16];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut symbols: Vec<u16> = (0..lengths.len() as u16)
+            .filter(|&s| lengths[s as usize] != 0)
+            .collect();
+        symbols.sort_by_key(|&s| lengths[s as usize]);
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, input: &mut BitReader) -> Result<u16, String> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for length in 1..16 {
+            code |= input.bit()? as i32;
+            let count = i32::from(self.counts[length]);
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("Bad Huffman code in compressed data".to_string())
+    }
+}
+
+/// Decompress a raw DEFLATE stream
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut input = BitReader { data, at: 0 };
+    let mut out = Vec::new();
+    loop {
+        let last = input.bit()? == 1;
+        match input.bits(2)? {
+            0 => {
+                input.align();
+                let length = input.bits(16)? as usize;
+                let check = input.bits(16)? as usize;
+                if length != !check & 0xFFFF {
+                    return Err("Corrupt stored block in compressed data".to_string());
+                }
+                let start = input.at / 8;
+                let bytes = data
+                    .get(start..start + length)
+                    .ok_or("Compressed data ends early")?;
+                out.extend_from_slice(bytes);
+                input.at += length * 8;
+            }
+            1 => {
+                let mut lengths = [8u8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                inflate_block(
+                    &mut input,
+                    &mut out,
+                    &Huffman::new(&lengths),
+                    &Huffman::new(&[5; 30]),
+                )?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut input)?;
+                inflate_block(&mut input, &mut out, &literals, &distances)?;
+            }
+            _ => return Err("Unknown block type in compressed data".to_string()),
+        }
+        if out.len() > MAX_INFLATED {
+            return Err("Compressed data is too large".to_string());
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+fn dynamic_codes(input: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    let literal_count = input.bits(5)? as usize + 257;
+    let distance_count = input.bits(5)? as usize + 1;
+    let code_length_count = input.bits(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[symbol] = input.bits(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_lengths);
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let (value, repeat) = match code_lengths.decode(input)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => (
+                *lengths.last().ok_or("Repeat with no previous length")?,
+                3 + input.bits(2)?,
+            ),
+            17 => (0, 3 + input.bits(3)?),
+            _ => (0, 11 + input.bits(7)?),
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat as usize));
+    }
+    if lengths.len() != literal_count + distance_count {
+        return Err("Code lengths overrun in compressed data".to_string());
+    }
+    Ok((
+        Huffman::new(&lengths[..literal_count]),
+        Huffman::new(&lengths[literal_count..]),
+    ))
+}
+
+fn inflate_block(
+    input: &mut BitReader,
+    out: &mut Vec<u8>,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<(), String> {
+    loop {
+        let symbol = literals.decode(input)?;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            _ => {
+                let code = (symbol - 257) as usize;
+                let (&base, &extra) = LENGTH_BASE
+                    .get(code)
+                    .zip(LENGTH_EXTRA.get(code))
+                    .ok_or("Bad length code")?;
+                let length = base as usize + input.bits(extra)? as usize;
+                let code = distances.decode(input)? as usize;
+                let (&base, &extra) = DIST_BASE
+                    .get(code)
+                    .zip(DIST_EXTRA.get(code))
+                    .ok_or("Bad distance code")?;
+                let distance = base as usize + input.bits(extra)? as usize;
+                if distance > out.len() {
+                    return Err("Distance too far back in compressed data".to_string());
+                }
+                for _ in 0..length {
+                    out.push(out[out.len() - distance]);
+                }
+                if out.len() > MAX_INFLATED {
+                    return Err("Compressed data is too large".to_string());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deflate_round_trip() {
+        let text = b"[metadata]\nid = \"basic-001\"\n[setup]\ncode_start = 4096\nbytes = \"90 90 90 90 90 90 F4\"\n";
+        let packed = deflate(text);
+        assert!(packed.len() < text.len());
+        assert_eq!(inflate(&packed).unwrap(), text);
+        assert_eq!(inflate(&deflate(b"")).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_inflate_dynamic_and_stored_blocks() {
+        // Python's zlib at level 9, raw (wbits=-15): one dynamic block
+        let text = b"nop eax eax jne mov hlt nop cmp mov jne jne hlt hlt jne eax hlt nop mov jne cmp nop nop jne eax eax jne hlt jne mov eax";
+        let dynamic = [
+            0x35, 0x8C, 0x51, 0x0A, 0x00, 0x20, 0x08, 0xC5, 0xAE, 0xE2, 0xD5, 0x22, 0x82, 0x88,
+            0xB4, 0x3E, 0x22, 0x3A, 0x7E, 0x2D, 0x78, 0x1F, 0x03, 0x1D, 0xD3, 0x18, 0xD3, 0x4A,
+            0x3A, 0x9F, 0x16, 0xC5, 0x7C, 0x6C, 0xAB, 0x7D, 0x59, 0x3C, 0x9F, 0x7D, 0xFE, 0x1D,
+            0x0F, 0x78, 0x60, 0xA6, 0x57, 0xA7, 0x86, 0x9E, 0x1D, 0xD4, 0xE8, 0xAF, 0xEE, 0x68,
+            0x9F, 0xBB,
+        ];
+        assert_eq!(inflate(&dynamic).unwrap(), text);
+
+        // A stored block holding "hi"
+        assert_eq!(
+            inflate(&[0x01, 0x02, 0x00, 0xFD, 0xFF, b'h', b'i']).unwrap(),
+            b"hi"
+        );
+        assert!(inflate(&[0x01, 0x02, 0x00, 0x00, 0x00, b'h', b'i']).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::BTreeSet;
+
+use iced_x86::{Decoder, DecoderOptions, Instruction, Mnemonic, OpKind};
+
+use crate::emulator::Prng;
+
+/// Where flattened code goes and how it's scrambled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlattenOptions {
+    /// Address the source bytes were assembled for
+    pub origin: u32,
+    /// Address the flattened code will be loaded at
+    pub code_start: u32,
+    /// Writable dword holding the dispatcher's state variable
+    pub state_address: u32,
+    /// Seed for the state values and the block order
+    pub seed: u32,
+}
+
+/// Flattened code, ready for a puzzle's `[setup.code]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flattened {
+    pub bytes: Vec<u8>,
+    /// Number of basic blocks behind the dispatcher
+    pub blocks: usize,
+}
+
+/// How a basic block hands over to the next one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Exit {
+    /// The block ends with HLT or a return
+    Stop,
+    /// Fall through to the given block, or off the end
+    Next(Option<usize>),
+    /// Unconditional jump to a block
+    Jump(usize),
+    /// Conditional jump (condition code `cc`) to `taken`, else `next`
+    Branch {
+        cc: u8,
+        taken: usize,
+        next: Option<usize>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Block {
+    body: Vec<u8>,
+    exit: Exit,
+}
+
+// Fixed-size encodings, so the layout is known before anything is emitted
+const SET_STATE_LEN: usize = 10; // mov dword [state], imm32
+const DISPATCH_ENTRY_LEN: usize = 16; // cmp dword [state], imm32; je rel32
+const JMP_LEN: usize = 5; // jmp rel32
+
+/// Apply control-flow flattening to a straight-line instruction sequence:
+/// split it into basic blocks, shuffle them, and route every transfer
+/// through a dispatcher that switches on a state variable in memory.
+///
+/// Jumps must stay inside the sequence; calls and LOOP aren't supported.
+/// Flags don't survive from one block to the next (the dispatcher's CMP
+/// clobbers them), so a conditional jump must sit in the same block as the
+/// instruction that sets its flags.
+pub fn flatten(source: &[u8], options: &FlattenOptions) -> Result<Flattened, String> {
+    let instructions = decode(source, options.origin)?;
+    let blocks = split_blocks(source, options.origin, &instructions)?;
+
+    let mut prng = Prng::new(options.seed);
+    let mut states: Vec<u32> = Vec::with_capacity(blocks.len());
+    while states.len() < blocks.len() {
+        let state = prng.next_u32();
+        if !states.contains(&state) {
+            states.push(state);
+        }
+    }
+    // Shuffle the layout; the dispatcher checks states in yet another order
+    let mut layout: Vec<usize> = (0..blocks.len()).collect();
+    shuffle(&mut layout, &mut prng);
+    let mut dispatch_order = layout.clone();
+    shuffle(&mut dispatch_order, &mut prng);
+
+    let dispatcher = options.code_start + SET_STATE_LEN as u32;
+    let mut address = dispatcher + (blocks.len() * DISPATCH_ENTRY_LEN) as u32 + 1;
+    let mut starts = vec![0u32; blocks.len()];
+    for &index in &layout {
+        starts[index] = address;
+        address += (blocks[index].body.len() + exit_len(blocks[index].exit)) as u32;
+    }
+
+    let mut out = Emitter {
+        bytes: Vec::new(),
+        start: options.code_start,
+        state: options.state_address,
+    };
+    out.set_state(states[0]);
+    for &index in &dispatch_order {
+        out.bytes.extend([0x81, 0x3D]);
+        out.bytes.extend(options.state_address.to_le_bytes());
+        out.bytes.extend(states[index].to_le_bytes());
+        out.near_jump(&[0x0F, 0x84], starts[index]);
+    }
+    // No state matches: stop rather than run off into a block
+    out.bytes.push(0xF4);
+
+    for &index in &layout {
+        let block = &blocks[index];
+        out.bytes.extend(&block.body);
+        match block.exit {
+            Exit::Stop => {}
+            Exit::Next(None) => out.bytes.push(0xF4),
+            Exit::Next(Some(next)) | Exit::Jump(next) => {
+                out.set_state(states[next]);
+                out.near_jump(&[0xE9], dispatcher);
+            }
+            Exit::Branch { cc, taken, next } => {
+                // MOV leaves the flags alone, so the Jcc still sees them
+                let fallthrough = next.map_or(0, |next| states[next]);
+                out.set_state(fallthrough);
+                out.bytes.extend([0x70 | cc, JMP_LEN as u8]);
+                out.near_jump(&[0xE9], dispatcher);
+                out.set_state(states[taken]);
+                out.near_jump(&[0xE9], dispatcher);
+            }
+        }
+    }
+
+    Ok(Flattened {
+        bytes: out.bytes,
+        blocks: blocks.len(),
+    })
+}
+
+fn decode(source: &[u8], origin: u32) -> Result<Vec<Instruction>, String> {
+    let mut decoder = Decoder::with_ip(32, source, origin as u64, DecoderOptions::NONE);
+    let mut instructions = Vec::new();
+    while decoder.can_decode() {
+        let instr = decoder.decode();
+        if instr.is_invalid() {
+            return Err(format!("Invalid instruction at 0x{:08X}", instr.ip32()));
+        }
+        instructions.push(instr);
+    }
+    if instructions.is_empty() {
+        return Err("Nothing to flatten".to_string());
+    }
+    Ok(instructions)
+}
+
+/// What a source instruction does to control flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transfer {
+    Next,
+    Stop,
+    Jump(u32),
+    Branch { cc: u8, target: u32 },
+}
+
+fn transfer(source: &[u8], origin: u32, instr: &Instruction) -> Result<Transfer, String> {
+    let target = match instr.op0_kind() {
+        OpKind::NearBranch32 => Some(instr.near_branch32()),
+        _ => None,
+    };
+    let offset = (instr.ip32() - origin) as usize;
+    let opcode = match source[offset] {
+        0x0F => 0x0F00 | source[offset + 1] as u16,
+        byte => byte as u16,
+    };
+
+    match (instr.mnemonic(), target) {
+        (Mnemonic::Jmp, Some(target)) => Ok(Transfer::Jump(target)),
+        (_, Some(target)) if matches!(opcode, 0x70..=0x7F | 0x0F80..=0x0F8F) => {
+            Ok(Transfer::Branch {
+                cc: (opcode & 0x0F) as u8,
+                target,
+            })
+        }
+        (Mnemonic::Jmp, None) | (_, Some(_)) => Err(format!(
+            "Can't flatten {:?} at 0x{:08X}",
+            instr.mnemonic(),
+            instr.ip32()
+        )),
+        (Mnemonic::Hlt | Mnemonic::Ret | Mnemonic::Iretd, None) => Ok(Transfer::Stop),
+        (_, None) => Ok(Transfer::Next),
+    }
+}
+
+fn split_blocks(
+    source: &[u8],
+    origin: u32,
+    instructions: &[Instruction],
+) -> Result<Vec<Block>, String> {
+    let index_of = |target: u32| {
+        instructions
+            .iter()
+            .position(|instr| instr.ip32() == target)
+            .ok_or_else(|| format!("Jump to 0x{:08X} leaves the sequence", target))
+    };
+    let transfers = instructions
+        .iter()
+        .map(|instr| transfer(source, origin, instr))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Block leaders: the first instruction, jump targets, and whatever
+    // follows a jump
+    let mut leaders = BTreeSet::from([0]);
+    for (i, transfer) in transfers.iter().enumerate() {
+        if let Transfer::Jump(target) | Transfer::Branch { target, .. } = *transfer {
+            leaders.insert(index_of(target)?);
+            leaders.insert(i + 1);
+        }
+    }
+    leaders.retain(|&i| i < instructions.len());
+    let leaders: Vec<usize> = leaders.into_iter().collect();
+    let block_of = |target: u32| -> Result<usize, String> {
+        let instruction = index_of(target)?;
+        Ok(leaders
+            .iter()
+            .position(|&leader| leader == instruction)
+            .unwrap_or_default())
+    };
+
+    let mut blocks = Vec::with_capacity(leaders.len());
+    for (n, &first) in leaders.iter().enumerate() {
+        let end = leaders.get(n + 1).copied().unwrap_or(instructions.len());
+        let next = (end < instructions.len()).then_some(n + 1);
+        let last = transfers[end - 1];
+
+        let body_end = if matches!(last, Transfer::Next | Transfer::Stop) {
+            end
+        } else {
+            end - 1
+        };
+        let mut body = Vec::new();
+        for instr in &instructions[first..body_end] {
+            let offset = (instr.ip32() - origin) as usize;
+            body.extend(&source[offset..offset + instr.len()]);
+        }
+
+        let exit = match last {
+            Transfer::Next => Exit::Next(next),
+            Transfer::Stop => Exit::Stop,
+            Transfer::Jump(target) => Exit::Jump(block_of(target)?),
+            Transfer::Branch { cc, target } => Exit::Branch {
+                cc,
+                taken: block_of(target)?,
+                next,
+            },
+        };
+        blocks.push(Block { body, exit });
+    }
+    Ok(blocks)
+}
+
+fn exit_len(exit: Exit) -> usize {
+    match exit {
+        Exit::Stop => 0,
+        Exit::Next(None) => 1,
+        Exit::Next(Some(_)) | Exit::Jump(_) => SET_STATE_LEN + JMP_LEN,
+        Exit::Branch { .. } => SET_STATE_LEN + 2 + JMP_LEN + SET_STATE_LEN + JMP_LEN,
+    }
+}
+
+/// Fisher-Yates, driven by the seeded generator
+fn shuffle(items: &mut [usize], prng: &mut Prng) {
+    for i in (1..items.len()).rev() {
+        let j = prng.next_u32() as usize % (i + 1);
+        items.swap(i, j);
+    }
+}
+
+struct Emitter {
+    bytes: Vec<u8>,
+    start: u32,
+    state: u32,
+}
+
+impl Emitter {
+    fn set_state(&mut self, value: u32) {
+        self.bytes.extend([0xC7, 0x05]);
+        self.bytes.extend(self.state.to_le_bytes());
+        self.bytes.extend(value.to_le_bytes());
+    }
+
+    /// Emit `opcode` followed by a rel32 to `target`
+    fn near_jump(&mut self, opcode: &[u8], target: u32) {
+        self.bytes.extend(opcode);
+        let next = self.start + (self.bytes.len() + 4) as u32;
+        self.bytes.extend(target.wrapping_sub(next).to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::{Debugger, RunResult};
+
+    const OPTIONS: FlattenOptions = FlattenOptions {
+        origin: 0x1000,
+        code_start: 0x1000,
+        state_address: 0x2000,
+        seed: 7,
+    };
+
+    fn run(code: &[u8]) -> Debugger {
+        let mut dbg = Debugger::with_puzzle_layout(0x1000, 0x1000, 0x2000, 0x3000);
+        dbg.load_code(0x1000, code).unwrap();
+        dbg.save_initial_state();
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+        dbg
+    }
+
+    #[test]
+    fn test_flattened_code_computes_the_same() {
+        // MOV EAX, 0; MOV ECX, 5; loop: ADD EAX, ECX; DEC ECX; JNZ loop;
+        // CMP EAX, 15; JE ok; MOV EAX, 0xBAD; JMP done; ok: MOV EBX, 1; done: HLT
+        let source = [
+            0xB8, 0x00, 0x00, 0x00, 0x00, 0xB9, 0x05, 0x00, 0x00, 0x00, 0x01, 0xC8, 0x49, 0x75,
+            0xFB, 0x83, 0xF8, 0x0F, 0x74, 0x07, 0xB8, 0xAD, 0x0B, 0x00, 0x00, 0xEB, 0x05, 0xBB,
+            0x01, 0x00, 0x00, 0x00, 0xF4,
+        ];
+        let original = run(&source);
+        let flattened = flatten(&source, &OPTIONS).unwrap();
+        assert_eq!(flattened.blocks, 6);
+        let result = run(&flattened.bytes);
+        assert_eq!(result.cpu.regs.eax, original.cpu.regs.eax);
+        assert_eq!(result.cpu.regs.ebx, 1);
+
+        // The seed decides the layout
+        let other = flatten(&source, &FlattenOptions { seed: 8, ..OPTIONS }).unwrap();
+        assert_ne!(other.bytes, flattened.bytes);
+        assert_eq!(run(&other.bytes).cpu.regs.ebx, 1);
+    }
+
+    #[test]
+    fn test_flatten_rejects_what_it_cannot_rewrite() {
+        // JMP out of the sequence
+        assert!(flatten(&[0xEB, 0x10], &OPTIONS)
+            .unwrap_err()
+            .contains("leaves the sequence"));
+        // CALL
+        assert!(flatten(&[0xE8, 0x00, 0x00, 0x00, 0x00], &OPTIONS).is_err());
+        assert!(flatten(&[], &OPTIONS).is_err());
+    }
+}
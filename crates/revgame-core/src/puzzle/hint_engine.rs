@@ -0,0 +1,85 @@
+use super::{Condition, PuzzleHints, ScriptContext};
+
+/// Picks targeted hints by matching puzzle rules against the debugger state
+#[derive(Debug, Clone, Default)]
+pub struct HintEngine {
+    rules: Vec<(Condition, String)>,
+}
+
+impl HintEngine {
+    /// Compile a puzzle's hint rules
+    pub fn new(hints: &PuzzleHints) -> Result<Self, String> {
+        let rules = hints
+            .rules
+            .iter()
+            .map(|rule| {
+                Condition::parse(&rule.when)
+                    .map(|condition| (condition, rule.hint.clone()))
+                    .map_err(|e| format!("Hint rule '{}': {}", rule.when, e))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { rules })
+    }
+
+    /// Whether the puzzle defines any rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The first hint whose condition holds
+    pub fn suggest(&self, ctx: &ScriptContext) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|(condition, _)| condition.holds(ctx))
+            .map(|(_, hint)| hint.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::Debugger;
+    use crate::puzzle::HintRule;
+
+    fn rule(when: &str, hint: &str) -> HintRule {
+        HintRule {
+            when: when.to_string(),
+            hint: hint.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let hints = PuzzleHints {
+            rules: vec![
+                rule("halted and eax == 0", "EAX is still 0 at halt"),
+                rule("breakpoints == 0", "Try setting a breakpoint"),
+            ],
+            ..Default::default()
+        };
+        let engine = HintEngine::new(&hints).unwrap();
+        let mut debugger = Debugger::new(0x1000);
+
+        let ctx = ScriptContext::from_debugger(&debugger);
+        assert_eq!(engine.suggest(&ctx), Some("Try setting a breakpoint"));
+
+        debugger.cpu.halted = true;
+        let ctx = ScriptContext::from_debugger(&debugger);
+        assert_eq!(engine.suggest(&ctx), Some("EAX is still 0 at halt"));
+
+        debugger.cpu.halted = false;
+        debugger.set_breakpoint(0x100);
+        let ctx = ScriptContext::from_debugger(&debugger);
+        assert_eq!(engine.suggest(&ctx), None);
+    }
+
+    #[test]
+    fn test_invalid_rule_is_rejected() {
+        let hints = PuzzleHints {
+            rules: vec![rule("eax ==", "broken")],
+            ..Default::default()
+        };
+        assert!(HintEngine::new(&hints).is_err());
+    }
+}
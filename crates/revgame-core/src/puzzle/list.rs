@@ -1,6 +1,6 @@
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
-use serde::{Deserialize, Serialize};
 
 /// Metadata about a puzzle for the selection screen
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +13,11 @@ pub struct PuzzleListItem {
     pub file_path: PathBuf,
     pub is_locked: bool,
     pub prerequisites: Vec<String>,
+    pub concepts: Vec<String>,
+    pub estimated_time_minutes: Option<u32>,
+    /// Instructions the puzzle's code uses
+    #[serde(default)]
+    pub mnemonics: Vec<String>,
 }
 
 /// Category grouping for puzzles
@@ -61,12 +66,12 @@ pub fn load_puzzle_list(puzzles_dir: &Path) -> Result<Vec<PuzzleCategory>, Strin
 
         // Load puzzles from this category
         let mut puzzles = Vec::new();
-        let puzzle_entries = fs::read_dir(&path)
-            .map_err(|e| format!("Failed to read category directory: {}", e))?;
+        let puzzle_entries =
+            fs::read_dir(&path).map_err(|e| format!("Failed to read category directory: {}", e))?;
 
         for puzzle_entry in puzzle_entries {
-            let puzzle_entry = puzzle_entry
-                .map_err(|e| format!("Failed to read puzzle entry: {}", e))?;
+            let puzzle_entry =
+                puzzle_entry.map_err(|e| format!("Failed to read puzzle entry: {}", e))?;
             let puzzle_path = puzzle_entry.path();
 
             // Only process .toml files
@@ -103,11 +108,11 @@ pub fn load_puzzle_list(puzzles_dir: &Path) -> Result<Vec<PuzzleCategory>, Strin
 fn load_puzzle_metadata(path: &Path) -> Result<PuzzleListItem, String> {
     use super::Puzzle;
 
-    let content = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read puzzle file: {}", e))?;
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read puzzle file: {}", e))?;
 
-    let puzzle: Puzzle = toml::from_str(&content)
-        .map_err(|e| format!("Failed to parse puzzle TOML: {}", e))?;
+    let puzzle: Puzzle =
+        toml::from_str(&content).map_err(|e| format!("Failed to parse puzzle TOML: {}", e))?;
 
     Ok(PuzzleListItem {
         id: puzzle.metadata.id.clone(),
@@ -118,6 +123,9 @@ fn load_puzzle_metadata(path: &Path) -> Result<PuzzleListItem, String> {
         file_path: path.to_path_buf(),
         is_locked: false, // Will be determined by game state
         prerequisites: puzzle.metadata.prerequisites.clone(),
+        concepts: puzzle.metadata.concepts.clone(),
+        estimated_time_minutes: puzzle.metadata.estimated_time_minutes,
+        mnemonics: puzzle.mnemonics(),
     })
 }
 
@@ -131,6 +139,9 @@ fn category_display_name(name: &str) -> String {
         "arrays" => "Arrays".to_string(),
         "functions" => "Functions".to_string(),
         "advanced" => "Advanced".to_string(),
+        "integrity" => "Code Integrity".to_string(),
+        "obfuscation" => "Obfuscation".to_string(),
+        "packers" => "Packers".to_string(),
         _ => {
             // Capitalize first letter
             let mut chars = name.chars();
@@ -1,15 +1,23 @@
 use super::Puzzle;
 
-/// Load a puzzle from TOML string
+/// Load a puzzle from TOML string, rejecting it if it requires
+/// instructions the emulator can't run, starts outside memory, has no
+/// valid validation trigger or annotates something that isn't an address
 pub fn load_puzzle(toml_content: &str) -> Result<Puzzle, String> {
-    toml::from_str(toml_content).map_err(|e| format!("Failed to parse puzzle: {}", e))
+    let puzzle: Puzzle =
+        toml::from_str(toml_content).map_err(|e| format!("Failed to parse puzzle: {}", e))?;
+    puzzle.check_requirements()?;
+    puzzle.check_layout()?;
+    puzzle.validation.trigger()?;
+    puzzle.annotated_addresses()?;
+    Ok(puzzle)
 }
 
 /// Load a puzzle from file path
 #[cfg(not(target_arch = "wasm32"))]
 pub fn load_puzzle_from_file(path: &std::path::Path) -> Result<Puzzle, String> {
-    let content = std::fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read puzzle file: {}", e))?;
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read puzzle file: {}", e))?;
     load_puzzle(&content)
 }
 
@@ -52,4 +60,82 @@ expected = 0x42
         assert_eq!(puzzle.metadata.difficulty, 1);
         assert_eq!(puzzle.entry_point(), 0x1000);
     }
+
+    const REQUIRES_PUZZLE: &str = r#"
+[metadata]
+id = "test-requires"
+title = "Requires"
+difficulty = 1
+category = "patching"
+
+[description]
+brief = "b"
+detailed = "d"
+
+[setup]
+requires = REQUIRES
+
+[setup.code]
+bytes = "F4"
+
+[validation]
+type = "normal_halt"
+"#;
+
+    #[test]
+    fn test_load_checks_required_groups() {
+        let supported = REQUIRES_PUZZLE.replace("REQUIRES", r#"["mul", "jumps"]"#);
+        assert!(load_puzzle(&supported).is_ok());
+
+        let unsupported = REQUIRES_PUZZLE.replace("REQUIRES", r#"["mul", "string_ops", "div"]"#);
+        let error = load_puzzle(&unsupported).unwrap_err();
+        assert_eq!(
+            error,
+            "Puzzle 'test-requires' needs instructions this emulator can't run yet: string_ops, div"
+        );
+
+        let unknown = REQUIRES_PUZZLE.replace("REQUIRES", r#"["avx"]"#);
+        assert_eq!(
+            load_puzzle(&unknown).unwrap_err(),
+            "Unknown instruction group: avx"
+        );
+    }
+
+    #[test]
+    fn test_load_annotations() {
+        let annotated = format!(
+            "{}\n[annotations]\n\"0x1000\" = \"success path\"\n4097 = \"unused\"\n",
+            REQUIRES_PUZZLE.replace("REQUIRES", "[]")
+        );
+        let puzzle = load_puzzle(&annotated).unwrap();
+        let annotations = puzzle.annotated_addresses().unwrap();
+        assert_eq!(
+            annotations.get(&0x1000).map(String::as_str),
+            Some("success path")
+        );
+        assert_eq!(annotations.get(&0x1001).map(String::as_str), Some("unused"));
+
+        let bad = format!(
+            "{}\n[annotations]\nstart = \"oops\"\n",
+            REQUIRES_PUZZLE.replace("REQUIRES", "[]")
+        );
+        assert_eq!(
+            load_puzzle(&bad).unwrap_err(),
+            "Annotation address is not a number: start"
+        );
+    }
+
+    #[test]
+    fn test_load_checks_layout() {
+        let base = REQUIRES_PUZZLE.replace("REQUIRES", "[]");
+        let past_end = base.replace("bytes = \"F4\"", "bytes = \"F4\"\nentry_point = 0xFFFFFFFF");
+        assert!(load_puzzle(&past_end)
+            .unwrap_err()
+            .starts_with("Entry point 0xFFFFFFFF is past the end"));
+
+        let no_stack = base.replace("[setup]\n", "[setup]\nstack_start = 0x10\n");
+        assert!(load_puzzle(&no_stack)
+            .unwrap_err()
+            .starts_with("stack_start 0x10 must leave 4KB"));
+    }
 }
@@ -1,9 +1,36 @@
-mod types;
+mod checksum;
+mod condition;
+mod deflate;
+mod flatten;
+mod hint_engine;
+mod list;
 mod loader;
+mod pack;
+mod script;
+mod share;
+mod solution;
+mod tools;
+mod types;
 mod validator;
-mod list;
 
-pub use types::{Puzzle, PuzzleMetadata, PuzzleSetup, PuzzleHints, PuzzleValidation, Difficulty, Category};
+pub use checksum::ChecksumAlgorithm;
+pub use condition::Condition;
+pub use flatten::{flatten, FlattenOptions, Flattened};
+pub use hint_engine::HintEngine;
+pub use list::{load_puzzle_list, PuzzleCategory, PuzzleListItem};
 pub use loader::load_puzzle;
-pub use validator::{ValidationResult, ValidationRule, Validator};
-pub use list::{PuzzleListItem, PuzzleCategory, load_puzzle_list};
+#[cfg(not(target_arch = "wasm32"))]
+pub use loader::load_puzzle_from_file;
+pub use pack::{pack, PackOptions, Packed, STUB_LEN};
+pub use script::{
+    error_message, register_debugger_api, sandboxed_engine, DebuggerReader, Script, ScriptContext,
+    ScriptEvent, ScriptOutcome, MAX_OPERATIONS,
+};
+pub use share::{decode_share_link, puzzle_from_link, share_link, SHARE_BASE_URL};
+pub use solution::{PuzzleSolution, SolutionPatch, WalkthroughPage};
+pub use tools::{substitute_vars, ToolCommand, ToolScript};
+pub use types::{
+    Category, Difficulty, HintRule, Puzzle, PuzzleHints, PuzzleMetadata, PuzzleSetup,
+    PuzzleValidation, ValidationTrigger,
+};
+pub use validator::{Objective, ValidationResult, ValidationRule, Validator};
@@ -0,0 +1,128 @@
+/// Where packed code goes and how it's encrypted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackOptions {
+    /// Address the payload was assembled for, where the packed code is loaded
+    pub code_start: u32,
+    /// XOR key applied to every payload byte
+    pub key: u8,
+}
+
+/// Packed code, ready for a puzzle's `[setup.code]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packed {
+    /// Encrypted payload followed by the unpacking stub
+    pub bytes: Vec<u8>,
+    /// Offset of the stub from `code_start`, the puzzle's `entry_point`
+    pub entry_point: u32,
+}
+
+/// Length of the unpacking stub
+pub const STUB_LEN: usize = 22;
+
+/// XOR-encrypt `payload` and append a stub that decrypts it in place and
+/// jumps to it:
+///
+/// ```text
+/// stub: mov esi, code_start
+///       mov ecx, payload length
+/// next: xor byte [esi], key
+///       inc esi
+///       dec ecx
+///       jnz next
+///       jmp code_start
+/// ```
+///
+/// The payload stays at `code_start`, so it runs as assembled. The puzzle
+/// needs `writable_code = true` when it enforces permissions.
+pub fn pack(payload: &[u8], options: &PackOptions) -> Result<Packed, String> {
+    if payload.is_empty() {
+        return Err("Nothing to pack".to_string());
+    }
+    if options.key == 0 {
+        return Err("Key 0 would leave the payload unencrypted".to_string());
+    }
+
+    let mut bytes: Vec<u8> = payload.iter().map(|b| b ^ options.key).collect();
+    let entry_point = bytes.len() as u32;
+    let stub_end = options
+        .code_start
+        .wrapping_add(entry_point)
+        .wrapping_add(STUB_LEN as u32);
+
+    bytes.push(0xBE); // mov esi, imm32
+    bytes.extend(options.code_start.to_le_bytes());
+    bytes.push(0xB9); // mov ecx, imm32
+    bytes.extend((payload.len() as u32).to_le_bytes());
+    bytes.extend([0x80, 0x36, options.key]); // xor byte [esi], imm8
+    bytes.extend([0x46, 0x49]); // inc esi; dec ecx
+    bytes.extend([0x75, 0xF9]); // jnz next
+    bytes.push(0xE9); // jmp rel32
+    bytes.extend(options.code_start.wrapping_sub(stub_end).to_le_bytes());
+
+    Ok(Packed { bytes, entry_point })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::debugger::{Debugger, RunResult};
+    use crate::emulator::Permissions;
+
+    #[test]
+    fn test_packed_code_unpacks_and_runs() {
+        // MOV EAX, 0x1337; HLT
+        let payload = [0xB8, 0x37, 0x13, 0x00, 0x00, 0xF4];
+        let packed = pack(
+            &payload,
+            &PackOptions {
+                code_start: 0x1000,
+                key: 0x5A,
+            },
+        )
+        .unwrap();
+        assert_eq!(packed.bytes.len(), payload.len() + STUB_LEN);
+        assert_ne!(packed.bytes[..payload.len()], payload);
+
+        let entry = 0x1000 + packed.entry_point;
+        let mut dbg = Debugger::with_puzzle_layout(entry, 0x1000, 0x2000, 0x3000);
+        dbg.load_code(0x1000, &packed.bytes).unwrap();
+        dbg.memory.set_enforce_permissions(true);
+        dbg.save_initial_state();
+
+        // Read-only code stops the stub at its first write
+        assert!(dbg.run().unwrap_err().to_string().contains("not writable"));
+
+        dbg.reset();
+        dbg.memory.region_mut("code").unwrap().permissions = Permissions::rwx();
+        assert!(matches!(dbg.run().unwrap(), RunResult::Halted));
+        assert_eq!(dbg.cpu.regs.eax, 0x1337);
+        assert_eq!(dbg.memory.slice(0x1000, payload.len()).unwrap(), payload);
+
+        // Reset brings the encrypted bytes back
+        dbg.reset();
+        assert_eq!(
+            dbg.memory.slice(0x1000, payload.len()).unwrap(),
+            packed.bytes[..payload.len()]
+        );
+    }
+
+    #[test]
+    fn test_pack_rejects_empty_payload_and_zero_key() {
+        assert!(pack(
+            &[],
+            &PackOptions {
+                code_start: 0x1000,
+                key: 1
+            }
+        )
+        .is_err());
+        assert!(pack(
+            &[0xF4],
+            &PackOptions {
+                code_start: 0x1000,
+                key: 0
+            }
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,521 @@
+//! Puzzle scripts, written in Rhai.
+//!
+//! A script defines handlers for debugger events (`on_step`, `on_patch`,
+//! `on_halt`) that can decide the puzzle outcome or show hints. Top-level
+//! code runs when the puzzle loads or resets, and the variables it declares
+//! keep their values between events.
+//!
+//! ```text
+//! let visits = 0;
+//!
+//! fn on_step() {
+//!     visits += 1;
+//!     if reg("eip") == 0x1010 && visits > 20 {
+//!         hint("You keep coming back here...");
+//!     }
+//! }
+//!
+//! fn on_halt() {
+//!     if dword(0x2000) == 0x1337 && patches() <= 2 { pass(); }
+//!     fail("The key was not written");
+//! }
+//! ```
+//!
+//! Handlers read the debugger through the functions `register_debugger_api`
+//! adds and cannot change it. `pass()` and `fail(reason)` end the handler
+//! with a verdict, and `hint(text)` shows a hint. The engine has no files,
+//! modules or `eval`, and caps operations and nesting, so every handler
+//! finishes and a hostile puzzle can't overflow the stack.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{CallFnOptions, Dynamic, Engine, EvalAltResult, Position, Scope, AST};
+
+use crate::debugger::{BookmarkManager, Debugger};
+use crate::emulator::{CpuState, Flag, Memory};
+
+use super::{Condition, ValidationResult};
+
+/// Most operations a single script call may run before it's stopped
+pub const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Deepest expressions and function calls may nest
+const MAX_DEPTH: usize = 64;
+
+/// Largest string, array or map a script may build
+const MAX_SIZE: usize = 0x1_0000;
+
+/// Debugger events a script can handle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptEvent {
+    /// After a single step (and once when a run stops)
+    Step,
+    /// After memory was patched
+    Patch,
+    /// When the program halts
+    Halt,
+}
+
+impl ScriptEvent {
+    const ALL: [ScriptEvent; 3] = [ScriptEvent::Step, ScriptEvent::Patch, ScriptEvent::Halt];
+
+    /// Name of the script function handling the event
+    fn handler(self) -> &'static str {
+        match self {
+            ScriptEvent::Step => "on_step",
+            ScriptEvent::Patch => "on_patch",
+            ScriptEvent::Halt => "on_halt",
+        }
+    }
+}
+
+/// Read-only view of the debugger passed to a script
+pub struct ScriptContext<'a> {
+    pub cpu: &'a CpuState,
+    pub memory: &'a Memory,
+    /// Instructions executed so far
+    pub steps: u64,
+    /// Patches applied so far
+    pub patches: usize,
+    /// Breakpoints currently set
+    pub breakpoints: usize,
+    /// Bookmarks, whose notes name their addresses in expressions
+    pub labels: Option<&'a BookmarkManager>,
+}
+
+impl<'a> ScriptContext<'a> {
+    /// View of the debugger's current state
+    pub fn from_debugger(debugger: &'a Debugger) -> Self {
+        Self {
+            cpu: &debugger.cpu,
+            memory: &debugger.memory,
+            steps: debugger.total_instructions,
+            patches: debugger.undo_count(),
+            breakpoints: debugger.breakpoints.len(),
+            labels: Some(&debugger.bookmarks),
+        }
+    }
+}
+
+/// A Rhai engine for scripts from puzzles and players: no module imports or
+/// `eval`, `print` goes to the log, and operations, nesting and sizes are
+/// capped
+pub fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_module_resolver(DummyModuleResolver::new())
+        .disable_symbol("eval")
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_expr_depths(MAX_DEPTH, MAX_DEPTH)
+        .set_max_call_levels(MAX_DEPTH)
+        .set_max_string_size(MAX_SIZE)
+        .set_max_array_size(MAX_SIZE)
+        .set_max_map_size(MAX_SIZE)
+        .on_print(|text| log::info!("script: {}", text))
+        .on_debug(|text, _, _| log::debug!("script: {}", text));
+    engine
+}
+
+/// Runs a read on the debugger state a script currently sees, failing when
+/// there is none
+pub type DebuggerReader =
+    Rc<dyn Fn(&dyn Fn(&ScriptContext) -> Result<Dynamic, String>) -> Result<Dynamic, String>>;
+
+fn read(
+    reader: &DebuggerReader,
+    f: impl Fn(&ScriptContext) -> Result<Dynamic, String>,
+) -> Result<Dynamic, Box<EvalAltResult>> {
+    reader(&f).map_err(Into::into)
+}
+
+/// Add the read-only debugger functions: `reg(name)`, `flag(name)`,
+/// `byte(addr)`, `word(addr)`, `dword(addr)`, `label(note)`, `expr(text)`
+/// (a debugger expression, as in hint rules), `steps()`, `patches()`,
+/// `breakpoints()` and `halted()`
+pub fn register_debugger_api(engine: &mut Engine, reader: DebuggerReader) {
+    let r = reader.clone();
+    engine.register_fn("reg", move |name: &str| {
+        read(&r, |ctx| {
+            ctx.cpu
+                .get_register_by_name(name)
+                .map(|value| (value as i64).into())
+                .ok_or_else(|| format!("Unknown register '{}'", name))
+        })
+    });
+    let r = reader.clone();
+    engine.register_fn("flag", move |name: &str| {
+        read(&r, |ctx| {
+            Flag::from_name(name)
+                .map(|flag| flag.get(&ctx.cpu.eflags).into())
+                .ok_or_else(|| format!("Unknown flag '{}'", name))
+        })
+    });
+    for (name, width) in [("byte", 1), ("word", 2), ("dword", 4)] {
+        let r = reader.clone();
+        engine.register_fn(name, move |address: i64| {
+            read(&r, |ctx| {
+                // Peek so that scripts never trigger device side effects
+                let bytes = ctx
+                    .memory
+                    .slice(address as u32, width)
+                    .ok_or_else(|| format!("Address 0x{:08X} out of bounds", address as u32))?;
+                Ok(bytes
+                    .iter()
+                    .rev()
+                    .fold(0i64, |acc, &b| (acc << 8) | b as i64)
+                    .into())
+            })
+        });
+    }
+    let r = reader.clone();
+    engine.register_fn("label", move |note: &str| {
+        read(&r, |ctx| {
+            ctx.labels
+                .and_then(|labels| labels.find_note(note))
+                .map(|bookmark| (bookmark.address as i64).into())
+                .ok_or_else(|| format!("No bookmark labelled '{}'", note))
+        })
+    });
+    let r = reader.clone();
+    engine.register_fn("expr", move |text: &str| {
+        let condition = Condition::parse(text)?;
+        read(&r, |ctx| condition.value(ctx).map(Dynamic::from))
+    });
+    let r = reader.clone();
+    engine.register_fn("steps", move || {
+        read(&r, |ctx| Ok((ctx.steps as i64).into()))
+    });
+    let r = reader.clone();
+    engine.register_fn("patches", move || {
+        read(&r, |ctx| Ok((ctx.patches as i64).into()))
+    });
+    let r = reader.clone();
+    engine.register_fn("breakpoints", move || {
+        read(&r, |ctx| Ok((ctx.breakpoints as i64).into()))
+    });
+    let r = reader;
+    engine.register_fn("halted", move || read(&r, |ctx| Ok(ctx.cpu.halted.into())));
+}
+
+/// The message of a script error, without the calls it passed through
+pub fn error_message(error: &EvalAltResult) -> String {
+    match error {
+        EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => error_message(inner),
+        EvalAltResult::ErrorRuntime(value, _) => value.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// What a script handler decided
+#[derive(Debug, Clone, Default)]
+pub struct ScriptOutcome {
+    /// `pass`/`fail` verdict, or an error if the script failed to evaluate
+    pub verdict: Option<ValidationResult>,
+    /// Hints emitted by `hint` calls
+    pub hints: Vec<String>,
+}
+
+/// Copy of the debugger state a handler reads, since the engine's
+/// functions can't borrow it
+#[derive(Debug)]
+struct Snapshot {
+    cpu: CpuState,
+    memory: Memory,
+    steps: u64,
+    patches: usize,
+    breakpoints: usize,
+    labels: Option<BookmarkManager>,
+}
+
+impl Snapshot {
+    fn new(ctx: &ScriptContext) -> Self {
+        Self {
+            cpu: ctx.cpu.clone(),
+            memory: ctx.memory.clone(),
+            steps: ctx.steps,
+            patches: ctx.patches,
+            breakpoints: ctx.breakpoints,
+            labels: ctx.labels.cloned(),
+        }
+    }
+
+    fn context(&self) -> ScriptContext<'_> {
+        ScriptContext {
+            cpu: &self.cpu,
+            memory: &self.memory,
+            steps: self.steps,
+            patches: self.patches,
+            breakpoints: self.breakpoints,
+            labels: self.labels.as_ref(),
+        }
+    }
+}
+
+/// The event a script is handling, shared with the engine's functions
+#[derive(Debug, Default)]
+struct EventState {
+    snapshot: Option<Snapshot>,
+    outcome: ScriptOutcome,
+}
+
+/// Record a verdict and end the handler. Termination can't be caught with
+/// `try`, so a verdict always stands.
+fn decide(
+    state: &RefCell<EventState>,
+    verdict: ValidationResult,
+) -> Result<(), Box<EvalAltResult>> {
+    state.borrow_mut().outcome.verdict = Some(verdict);
+    Err(EvalAltResult::ErrorTerminated(Dynamic::UNIT, Position::NONE).into())
+}
+
+/// A compiled puzzle script with its variables
+#[derive(Debug, Clone)]
+pub struct Script {
+    engine: Rc<Engine>,
+    ast: AST,
+    /// Variables declared by the top-level code
+    scope: Scope<'static>,
+    state: Rc<RefCell<EventState>>,
+    handlers: HashSet<ScriptEvent>,
+}
+
+impl Script {
+    /// Compile script source and run its top-level code
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let state = Rc::new(RefCell::new(EventState::default()));
+        let mut engine = sandboxed_engine();
+
+        let s = state.clone();
+        register_debugger_api(
+            &mut engine,
+            Rc::new(move |f| {
+                let state = s.borrow();
+                let snapshot = state
+                    .snapshot
+                    .as_ref()
+                    .ok_or("The debugger can only be read from a handler")?;
+                f(&snapshot.context())
+            }),
+        );
+        let s = state.clone();
+        engine.register_fn("pass", move || decide(&s, ValidationResult::Success));
+        let s = state.clone();
+        engine.register_fn("fail", move |reason: &str| {
+            decide(&s, ValidationResult::Failure(reason.to_string()))
+        });
+        let s = state.clone();
+        engine.register_fn("fail", move || {
+            decide(&s, ValidationResult::Failure(String::new()))
+        });
+        let s = state.clone();
+        engine.register_fn("hint", move |text: &str| {
+            s.borrow_mut().outcome.hints.push(text.to_string())
+        });
+
+        let ast = engine
+            .compile(source)
+            .map_err(|e| format!("Script: {}", e))?;
+        let handlers = ScriptEvent::ALL
+            .into_iter()
+            .filter(|event| {
+                ast.iter_functions()
+                    .any(|f| f.name == event.handler() && f.params.is_empty())
+            })
+            .collect();
+
+        let mut script = Self {
+            engine: Rc::new(engine),
+            ast,
+            scope: Scope::new(),
+            state,
+            handlers,
+        };
+        script
+            .run_top_level()
+            .map_err(|e| format!("Script: {}", e))?;
+        Ok(script)
+    }
+
+    fn run_top_level(&mut self) -> Result<(), String> {
+        self.scope = Scope::new();
+        self.engine
+            .run_ast_with_scope(&mut self.scope, &self.ast)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Whether the script handles an event
+    pub fn handles(&self, event: ScriptEvent) -> bool {
+        self.handlers.contains(&event)
+    }
+
+    /// Put the variables back to their starting values (when the puzzle is
+    /// reset)
+    pub fn reset(&mut self) {
+        if let Err(e) = self.run_top_level() {
+            log::warn!("Puzzle script reset failed: {}", e);
+        }
+    }
+
+    /// Run the handler for `event`
+    pub fn fire(&mut self, event: ScriptEvent, ctx: &ScriptContext) -> ScriptOutcome {
+        if !self.handles(event) {
+            return ScriptOutcome::default();
+        }
+
+        self.state.borrow_mut().snapshot = Some(Snapshot::new(ctx));
+        let options = CallFnOptions::new().eval_ast(false).rewind_scope(false);
+        let result = self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut self.scope,
+            &self.ast,
+            event.handler(),
+            (),
+        );
+
+        let mut state = self.state.borrow_mut();
+        state.snapshot = None;
+        let mut outcome = std::mem::take(&mut state.outcome);
+        if let (Err(e), None) = (result, &outcome.verdict) {
+            outcome.verdict = Some(ValidationResult::Error(format!("Script error: {}", e)));
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(cpu: &'a CpuState, memory: &'a Memory) -> ScriptContext<'a> {
+        ScriptContext {
+            cpu,
+            memory,
+            steps: 10,
+            patches: 1,
+            breakpoints: 0,
+            labels: None,
+        }
+    }
+
+    #[test]
+    fn test_halt_verdict() {
+        let mut script = Script::parse(
+            r#"
+fn on_halt() {
+    if dword(0x100) == 0x1337 && patches() <= 2 { pass(); }
+    fail("Wrong key");
+}
+"#,
+        )
+        .unwrap();
+
+        let cpu = CpuState::default();
+        let mut memory = Memory::new(0x1000);
+
+        let outcome = script.fire(ScriptEvent::Halt, &context(&cpu, &memory));
+        assert!(
+            matches!(outcome.verdict, Some(ValidationResult::Failure(ref m)) if m == "Wrong key")
+        );
+
+        memory.write_u32(0x100, 0x1337).unwrap();
+        let outcome = script.fire(ScriptEvent::Halt, &context(&cpu, &memory));
+        assert!(matches!(outcome.verdict, Some(ValidationResult::Success)));
+    }
+
+    #[test]
+    fn test_variables_persist_and_hint() {
+        let mut script = Script::parse(
+            r#"
+// Count visits to the loop head
+let visits = 0;
+
+fn on_step() {
+    visits += 1;
+    if reg("eip") == 0x1000 && visits >= 2 { hint("Stuck in the loop?"); }
+}
+"#,
+        )
+        .unwrap();
+        assert!(script.handles(ScriptEvent::Step));
+        assert!(!script.handles(ScriptEvent::Halt));
+
+        let cpu = CpuState::new(0x1000, 0x3000);
+        let memory = Memory::new(0x1000);
+
+        assert!(script
+            .fire(ScriptEvent::Step, &context(&cpu, &memory))
+            .hints
+            .is_empty());
+        let outcome = script.fire(ScriptEvent::Step, &context(&cpu, &memory));
+        assert_eq!(outcome.hints, vec!["Stuck in the loop?".to_string()]);
+        assert!(outcome.verdict.is_none());
+
+        script.reset();
+        assert!(script
+            .fire(ScriptEvent::Step, &context(&cpu, &memory))
+            .hints
+            .is_empty());
+    }
+
+    #[test]
+    fn test_debugger_api_and_errors() {
+        let mut script = Script::parse(
+            r#"
+fn on_halt() {
+    if expr("2 + 3 * 4") == 14 && !flag("zf") && !halted() && steps() == 10 { pass(); }
+}
+fn on_patch() {
+    let x = 1 / (reg("eax") - reg("eax"));
+}
+fn on_step() {
+    // A verdict can't be caught
+    try { fail("caught?"); } catch { pass(); }
+}
+"#,
+        )
+        .unwrap();
+
+        let cpu = CpuState::default();
+        let memory = Memory::new(0x1000);
+        let outcome = script.fire(ScriptEvent::Halt, &context(&cpu, &memory));
+        assert!(matches!(outcome.verdict, Some(ValidationResult::Success)));
+
+        let outcome = script.fire(ScriptEvent::Patch, &context(&cpu, &memory));
+        assert!(matches!(outcome.verdict, Some(ValidationResult::Error(_))));
+
+        let outcome = script.fire(ScriptEvent::Step, &context(&cpu, &memory));
+        assert!(
+            matches!(outcome.verdict, Some(ValidationResult::Failure(ref m)) if m == "caught?")
+        );
+
+        assert!(Script::parse("fn on_step() {")
+            .unwrap_err()
+            .starts_with("Script: "));
+        assert!(Script::parse(r#"eval("1")"#).is_err());
+        assert!(Script::parse("let x = reg(\"eax\");")
+            .unwrap_err()
+            .contains("only be read from a handler"));
+    }
+
+    #[test]
+    fn test_hostile_scripts_are_stopped() {
+        let mut script =
+            Script::parse("fn on_step() { loop {} }\nfn on_halt() { on_halt() }").unwrap();
+        let cpu = CpuState::default();
+        let memory = Memory::new(0x1000);
+        for event in [ScriptEvent::Step, ScriptEvent::Halt] {
+            let outcome = script.fire(event, &context(&cpu, &memory));
+            assert!(matches!(outcome.verdict, Some(ValidationResult::Error(_))));
+        }
+
+        let deep = format!(
+            "fn on_step() {{ {}1{} }}",
+            "(".repeat(100_000),
+            ")".repeat(100_000)
+        );
+        assert!(Script::parse(&deep).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Puzzles carried in a link, so they can be shared without a server:
+//! `https://revgame.app/#puzzle=<base64url of the deflated TOML>`
+
+use super::{deflate, load_puzzle, Puzzle};
+
+/// Where shared links point
+pub const SHARE_BASE_URL: &str = "https://revgame.app/";
+
+/// Fragment key the puzzle is stored under
+const FRAGMENT_KEY: &str = "puzzle=";
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Link that opens `puzzle` in the web build. Comments in the original file
+/// don't survive, which keeps links short.
+pub fn share_link(puzzle: &Puzzle) -> Result<String, String> {
+    let toml = toml::to_string(puzzle).map_err(|e| format!("Failed to encode puzzle: {}", e))?;
+    Ok(format!(
+        "{}#{}{}",
+        SHARE_BASE_URL,
+        FRAGMENT_KEY,
+        encode_base64url(&deflate::deflate(toml.as_bytes()))
+    ))
+}
+
+/// Puzzle TOML from a share link, its `#puzzle=...` fragment or the bare
+/// encoded text
+pub fn decode_share_link(link: &str) -> Result<String, String> {
+    let link = link.trim();
+    let fragment = link.split_once('#').map_or(link, |(_, fragment)| fragment);
+    let encoded = fragment
+        .split('&')
+        .find_map(|part| part.strip_prefix(FRAGMENT_KEY))
+        .unwrap_or(fragment);
+
+    let packed = decode_base64url(encoded)?;
+    let bytes = deflate::inflate(&packed).map_err(|e| format!("Broken puzzle link: {}", e))?;
+    String::from_utf8(bytes).map_err(|_| "Broken puzzle link: not text".to_string())
+}
+
+/// Load the puzzle a share link carries
+pub fn puzzle_from_link(link: &str) -> Result<Puzzle, String> {
+    load_puzzle(&decode_share_link(link)?)
+}
+
+fn encode_base64url(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits =
+            chunk.iter().fold(0u32, |bits, &b| (bits << 8) | b as u32) << (8 * (3 - chunk.len()));
+        for i in 0..=chunk.len() {
+            out.push(BASE64URL[(bits >> (18 - 6 * i)) as usize & 63] as char);
+        }
+    }
+    out
+}
+
+/// Decode base64url without padding; standard base64 (`+`, `/`, `=`) is
+/// accepted too, for links that went through other tools
+fn decode_base64url(text: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut bits, mut count) = (0u32, 0);
+    for c in text.bytes().filter(|&c| c != b'=') {
+        let value = match c {
+            b'+' => 62,
+            b'/' => 63,
+            _ => BASE64URL
+                .iter()
+                .position(|&b| b == c)
+                .ok_or_else(|| format!("Broken puzzle link: unexpected '{}'", c as char))?
+                as u32,
+        };
+        bits = (bits << 6) | value;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            out.push((bits >> count) as u8);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = r#"
+[metadata]
+id = "shared-001"
+title = "Shared"
+difficulty = 1
+category = "patching"
+
+[description]
+brief = "Patch the jump"
+detailed = "Make EAX 1"
+
+[setup.code]
+# Comments are dropped from links
+bytes = "3D 37 13 00 00 75 07 B8 01 00 00 00 F4"
+
+[validation]
+type = "register_value"
+register = "eax"
+expected = 1
+"#;
+
+    #[test]
+    fn test_share_link_round_trip() {
+        let puzzle = load_puzzle(PUZZLE).unwrap();
+        let link = share_link(&puzzle).unwrap();
+        let encoded = link.strip_prefix("https://revgame.app/#puzzle=").unwrap();
+        assert!(!encoded.contains(['+', '/', '=']));
+
+        let shared = puzzle_from_link(&link).unwrap();
+        assert_eq!(shared.metadata.id, "shared-001");
+        assert_eq!(shared.code_bytes().unwrap(), puzzle.code_bytes().unwrap());
+
+        // The fragment alone works too
+        let fragment = &link[link.find('#').unwrap()..];
+        assert_eq!(
+            puzzle_from_link(fragment).unwrap().metadata.id,
+            "shared-001"
+        );
+        assert!(decode_share_link("#puzzle=not*base64")
+            .unwrap_err()
+            .starts_with("Broken puzzle link"));
+    }
+
+    #[test]
+    fn test_base64url() {
+        assert_eq!(encode_base64url(b"\xFB\xFF"), "-_8");
+        assert_eq!(decode_base64url("-_8").unwrap(), b"\xFB\xFF");
+        assert_eq!(decode_base64url("+/8=").unwrap(), b"\xFB\xFF");
+    }
+}
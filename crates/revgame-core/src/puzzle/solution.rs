@@ -0,0 +1,397 @@
+use serde::{Deserialize, Serialize};
+
+use crate::debugger::MemoryPatch;
+use crate::emulator::Disassembler;
+
+use super::Puzzle;
+
+/// The puzzle author's own solution, walked through after the puzzle is solved
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PuzzleSolution {
+    /// The approach in one line
+    #[serde(default)]
+    pub summary: String,
+
+    /// Commentary on how to find the patch, one step at a time
+    #[serde(default)]
+    pub steps: Vec<String>,
+
+    /// The patches that solve the puzzle
+    #[serde(default)]
+    pub patches: Vec<SolutionPatch>,
+}
+
+/// One patch of an official solution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolutionPatch {
+    pub address: u32,
+
+    /// New bytes as a hex string
+    pub bytes: String,
+
+    /// Why these bytes
+    #[serde(default)]
+    pub note: String,
+}
+
+impl SolutionPatch {
+    pub fn bytes(&self) -> Result<Vec<u8>, String> {
+        Puzzle::parse_hex(&self.bytes)
+    }
+}
+
+/// One page of a solution walkthrough
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalkthroughPage {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+impl PuzzleSolution {
+    /// The official patches, with the bytes they replace in the puzzle as shipped
+    pub fn official_patches(&self, puzzle: &Puzzle) -> Result<Vec<MemoryPatch>, String> {
+        let sections = sections(puzzle)?;
+        self.patches
+            .iter()
+            .map(|patch| {
+                let new_bytes = patch.bytes()?;
+                let old_bytes = (0..new_bytes.len() as u32)
+                    .map(|i| original_byte(&sections, patch.address.wrapping_add(i)))
+                    .collect();
+                Ok(MemoryPatch::new(patch.address, old_bytes, new_bytes))
+            })
+            .collect()
+    }
+
+    /// Bytes the official solution changes: the par to beat
+    pub fn par(&self, puzzle: &Puzzle) -> Result<usize, String> {
+        Ok(self
+            .official_patches(puzzle)?
+            .iter()
+            .map(changed_count)
+            .sum())
+    }
+
+    /// Pages walking through the official solution, each compared with
+    /// `player`, the net patches the player solved the puzzle with
+    pub fn walkthrough(
+        &self,
+        puzzle: &Puzzle,
+        player: &[MemoryPatch],
+    ) -> Result<Vec<WalkthroughPage>, String> {
+        let official = self.official_patches(puzzle)?;
+        let par: usize = official.iter().map(changed_count).sum();
+        let yours: usize = player.iter().map(changed_count).sum();
+        let player_byte = |address: u32| {
+            player.iter().find_map(|patch| {
+                let offset = address.wrapping_sub(patch.address) as usize;
+                patch.new_bytes.get(offset).copied()
+            })
+        };
+
+        let mut overview = Vec::new();
+        if !self.summary.is_empty() {
+            overview.push(self.summary.clone());
+            overview.push(String::new());
+        }
+        overview.push(format!(
+            "Official solution: {} patch{}, {} byte{} changed (par)",
+            official.len(),
+            plural_es(official.len()),
+            par,
+            plural(par)
+        ));
+        overview.push(format!(
+            "Your solution: {} patch{}, {} byte{} changed",
+            player.len(),
+            plural_es(player.len()),
+            yours,
+            plural(yours)
+        ));
+        let same = official.iter().all(|patch| {
+            patch.new_bytes.iter().enumerate().all(|(i, &new)| {
+                let address = patch.address.wrapping_add(i as u32);
+                player_byte(address).unwrap_or(patch.old_bytes[i]) == new
+            })
+        });
+        overview.push(String::new());
+        overview.push(if yours == 0 {
+            "You solved it without patching anything".to_string()
+        } else if same && yours == par {
+            "You found the official solution".to_string()
+        } else if yours < par {
+            format!("Under par by {} byte{}", par - yours, plural(par - yours))
+        } else if yours > par {
+            format!("{} byte{} over par", yours - par, plural(yours - par))
+        } else {
+            "Level with par, by a different route".to_string()
+        });
+
+        let mut pages = vec![WalkthroughPage {
+            title: "Overview".to_string(),
+            lines: overview,
+        }];
+
+        for (i, step) in self.steps.iter().enumerate() {
+            pages.push(WalkthroughPage {
+                title: format!("Step {} of {}", i + 1, self.steps.len()),
+                lines: vec![step.clone()],
+            });
+        }
+
+        for (patch, official_patch) in self.patches.iter().zip(&official) {
+            let mut lines = Vec::new();
+            if !patch.note.is_empty() {
+                lines.push(patch.note.clone());
+                lines.push(String::new());
+            }
+            lines.push("Before:".to_string());
+            lines.extend(
+                instructions(puzzle, official_patch, &official_patch.old_bytes)
+                    .into_iter()
+                    .map(indent),
+            );
+            lines.push("After:".to_string());
+            lines.extend(
+                instructions(puzzle, official_patch, &official_patch.new_bytes)
+                    .into_iter()
+                    .map(indent),
+            );
+            lines.push(String::new());
+
+            let addresses = (0..official_patch.new_bytes.len() as u32)
+                .map(|i| official_patch.address.wrapping_add(i));
+            let theirs: Vec<Option<u8>> = addresses.map(player_byte).collect();
+            lines.push(if theirs.iter().all(Option::is_none) {
+                "You left these bytes alone".to_string()
+            } else if theirs
+                .iter()
+                .zip(&official_patch.new_bytes)
+                .all(|(theirs, &new)| theirs.is_none_or(|byte| byte == new))
+            {
+                "You made the same change here".to_string()
+            } else {
+                let bytes: Vec<String> = theirs
+                    .iter()
+                    .zip(&official_patch.old_bytes)
+                    .map(|(theirs, &old)| format!("{:02X}", theirs.unwrap_or(old)))
+                    .collect();
+                format!("You wrote {} here instead", bytes.join(" "))
+            });
+
+            pages.push(WalkthroughPage {
+                title: format!("Official patch at 0x{:08X}", official_patch.address),
+                lines,
+            });
+        }
+
+        // The player's patches the official solution doesn't touch
+        let elsewhere: Vec<&MemoryPatch> = player
+            .iter()
+            .filter(|patch| !official.iter().any(|official| overlaps(patch, official)))
+            .collect();
+        if !elsewhere.is_empty() {
+            let mut lines = Vec::new();
+            for patch in elsewhere {
+                lines.push(format!("0x{:08X}:", patch.address));
+                lines.extend(
+                    instructions(puzzle, patch, &patch.old_bytes)
+                        .into_iter()
+                        .map(|line| format!("  - {}", line)),
+                );
+                lines.extend(
+                    instructions(puzzle, patch, &patch.new_bytes)
+                        .into_iter()
+                        .map(|line| format!("  + {}", line)),
+                );
+            }
+            pages.push(WalkthroughPage {
+                title: "Your other patches".to_string(),
+                lines,
+            });
+        }
+
+        Ok(pages)
+    }
+}
+
+/// The puzzle's code and data sections as shipped
+fn sections(puzzle: &Puzzle) -> Result<Vec<(u32, Vec<u8>)>, String> {
+    let mut sections = vec![(puzzle.setup.code_start, puzzle.code_bytes()?)];
+    if let Some(ref data) = puzzle.setup.data {
+        sections.push((puzzle.setup.data_start, Puzzle::parse_hex(&data.bytes)?));
+    }
+    Ok(sections)
+}
+
+/// A byte of the puzzle as shipped (zero outside its sections)
+fn original_byte(sections: &[(u32, Vec<u8>)], address: u32) -> u8 {
+    sections
+        .iter()
+        .find_map(|(start, bytes)| bytes.get(address.wrapping_sub(*start) as usize))
+        .copied()
+        .unwrap_or(0)
+}
+
+/// Instructions covering `patch` when its bytes are `bytes`, or the bytes
+/// themselves when it isn't in the code section
+fn instructions(puzzle: &Puzzle, patch: &MemoryPatch, bytes: &[u8]) -> Vec<String> {
+    let start = puzzle.setup.code_start;
+    let Ok(mut code) = puzzle.code_bytes() else {
+        return Vec::new();
+    };
+    let offset = patch.address.wrapping_sub(start) as usize;
+    if offset + bytes.len() > code.len() {
+        let hex: Vec<String> = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+        return vec![format!("0x{:08X}  {}", patch.address, hex.join(" "))];
+    }
+    code[offset..offset + bytes.len()].copy_from_slice(bytes);
+
+    let mut disassembler = Disassembler::new();
+    for region in &puzzle.setup.data_regions {
+        disassembler.mark_data(region.address, region.length);
+    }
+    let end = patch.address.wrapping_add(bytes.len() as u32);
+    disassembler
+        .disassemble(&code, start, code.len())
+        .into_iter()
+        .filter(|line| {
+            line.address < end && line.address.wrapping_add(line.length as u32) > patch.address
+        })
+        .map(|line| format!("0x{:08X}  {}", line.address, line.text))
+        .collect()
+}
+
+fn changed_count(patch: &MemoryPatch) -> usize {
+    patch
+        .old_bytes
+        .iter()
+        .zip(&patch.new_bytes)
+        .filter(|(old, new)| old != new)
+        .count()
+}
+
+fn overlaps(a: &MemoryPatch, b: &MemoryPatch) -> bool {
+    let end = |patch: &MemoryPatch| patch.address.wrapping_add(patch.new_bytes.len() as u32);
+    a.address < end(b) && b.address < end(a)
+}
+
+fn indent(line: String) -> String {
+    format!("  {}", line)
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+fn plural_es(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "es"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::load_puzzle;
+
+    const PUZZLE: &str = r#"
+[metadata]
+id = "solution-test"
+title = "Solution"
+difficulty = 1
+category = "patching"
+
+[description]
+brief = "b"
+detailed = "d"
+
+[setup]
+code_start = 0x1000
+
+[setup.code]
+bytes = "3D 37 13 00 00 75 07 B8 01 00 00 00 EB 05 B8 00 00 00 00 F4"
+
+[validation]
+type = "register_value"
+register = "eax"
+expected = 1
+
+[solution]
+summary = "Skip the failure branch"
+steps = ["Find the JNE"]
+
+[[solution.patches]]
+address = 0x1005
+bytes = "90 90"
+note = "NOP out the jump"
+"#;
+
+    fn page<'a>(pages: &'a [WalkthroughPage], title: &str) -> &'a WalkthroughPage {
+        pages.iter().find(|page| page.title == title).unwrap()
+    }
+
+    #[test]
+    fn test_walkthrough_matches_official_patch() {
+        let puzzle = load_puzzle(PUZZLE).unwrap();
+        let solution = puzzle.solution.as_ref().unwrap();
+        assert_eq!(solution.par(&puzzle).unwrap(), 2);
+
+        let player = [MemoryPatch::new(0x1005, vec![0x75, 0x07], vec![0x90, 0x90])];
+        let pages = solution.walkthrough(&puzzle, &player).unwrap();
+        assert_eq!(pages.len(), 3);
+        assert!(page(&pages, "Overview")
+            .lines
+            .contains(&"You found the official solution".to_string()));
+        assert_eq!(page(&pages, "Step 1 of 1").lines, ["Find the JNE"]);
+
+        let patch = page(&pages, "Official patch at 0x00001005");
+        assert_eq!(patch.lines[0], "NOP out the jump");
+        assert!(patch
+            .lines
+            .contains(&"  0x00001005  jne short 0x0000100E".to_string()));
+        assert!(patch.lines.contains(&"  0x00001006  nop".to_string()));
+        assert_eq!(patch.lines.last().unwrap(), "You made the same change here");
+    }
+
+    #[test]
+    fn test_walkthrough_compares_other_approaches() {
+        let puzzle = load_puzzle(PUZZLE).unwrap();
+        let solution = puzzle.solution.as_ref().unwrap();
+
+        // Flipping the failure value instead: one byte, elsewhere
+        let player = [MemoryPatch::new(0x100F, vec![0x00], vec![0x01])];
+        let pages = solution.walkthrough(&puzzle, &player).unwrap();
+        assert!(page(&pages, "Overview")
+            .lines
+            .contains(&"Under par by 1 byte".to_string()));
+        assert_eq!(
+            page(&pages, "Official patch at 0x00001005")
+                .lines
+                .last()
+                .unwrap(),
+            "You left these bytes alone"
+        );
+        let other = page(&pages, "Your other patches");
+        assert!(other
+            .lines
+            .contains(&"  + 0x0000100E  mov eax, 1".to_string()));
+
+        // Half of the official patch
+        let player = [MemoryPatch::new(0x1005, vec![0x75], vec![0xEB])];
+        let pages = solution.walkthrough(&puzzle, &player).unwrap();
+        assert_eq!(
+            page(&pages, "Official patch at 0x00001005")
+                .lines
+                .last()
+                .unwrap(),
+            "You wrote EB 07 here instead"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Tool scripts: Rhai files of new command-line commands, loaded with
+//! `script run <file>`.
+//!
+//! ```text
+//! /// Step until a register holds a value, at most 500 steps
+//! fn stepuntil(register, value) {
+//!     for i in 0..500 {
+//!         if reg(register) == expr(value) { return; }
+//!         run("step");
+//!     }
+//! }
+//! ```
+//!
+//! Every public function is a command, its parameters the command's
+//! arguments (as strings) and its doc comment the description. Besides the
+//! read-only debugger functions puzzle scripts have, a command can `run` a
+//! command line, so scripts can only do what the player could type, and
+//! read an alias variable with `variable("sel")`. A command that returns
+//! something shows it as a message. Top-level code runs once when the file
+//! is loaded; each call starts from the variables it set.
+
+use std::rc::Rc;
+
+use rhai::{CallFnOptions, Dynamic, Engine, FnAccess, Scope, AST};
+
+use super::error_message;
+
+/// A command defined by a tool script
+#[derive(Debug, Clone)]
+pub struct ToolCommand {
+    pub name: String,
+    pub params: Vec<String>,
+    /// Doc comment of the function
+    pub doc: String,
+    script: Rc<ToolScript>,
+}
+
+impl ToolCommand {
+    /// `name <param> ...`
+    pub fn usage(&self) -> String {
+        std::iter::once(self.name.clone())
+            .chain(self.params.iter().map(|param| format!("<{}>", param)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Run the command with one argument per parameter (extra ones are
+    /// ignored), returning what it returned unless that was nothing
+    pub fn call(&self, engine: &Engine, args: &[&str]) -> Result<Option<String>, String> {
+        let args: Vec<String> = args
+            .iter()
+            .take(self.params.len())
+            .map(|arg| arg.to_string())
+            .collect();
+        let mut scope = self.script.scope.clone();
+        let options = CallFnOptions::new().eval_ast(false);
+        engine
+            .call_fn_with_options::<Dynamic>(
+                options,
+                &mut scope,
+                &self.script.ast,
+                &self.name,
+                args,
+            )
+            .map(|value| (!value.is_unit()).then(|| value.to_string()))
+            .map_err(|e| error_message(&e))
+    }
+}
+
+/// A compiled tool script file
+#[derive(Debug)]
+pub struct ToolScript {
+    ast: AST,
+    /// Variables set by the top-level code
+    scope: Scope<'static>,
+}
+
+impl ToolScript {
+    /// Compile a tool script and run its top-level code
+    pub fn load(engine: &Engine, source: &str) -> Result<Rc<Self>, String> {
+        let ast = engine.compile(source).map_err(|e| e.to_string())?;
+        let mut scope = Scope::new();
+        engine
+            .run_ast_with_scope(&mut scope, &ast)
+            .map_err(|e| error_message(&e))?;
+        Ok(Rc::new(Self { ast, scope }))
+    }
+
+    /// The script's public functions as commands, by name
+    pub fn commands(self: &Rc<Self>) -> Vec<ToolCommand> {
+        let mut commands: Vec<ToolCommand> = self
+            .ast
+            .iter_functions()
+            .filter(|function| function.access == FnAccess::Public)
+            .map(|function| ToolCommand {
+                name: function.name.to_string(),
+                params: function
+                    .params
+                    .iter()
+                    .map(|param| param.to_string())
+                    .collect(),
+                doc: function
+                    .comments
+                    .iter()
+                    .map(|line| line.trim_start_matches('/').trim())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                script: self.clone(),
+            })
+            .collect();
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        commands
+    }
+}
+
+/// Replace every `$name` in `text` with its value from `var`
+pub fn substitute_vars(text: &str, var: impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut expanded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let len = after
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(after.len());
+        let name = &after[..len];
+        match var(name) {
+            Some(value) => expanded.push_str(&value),
+            None => return Err(format!("${} has no value here", name)),
+        }
+        rest = &after[len..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::sandboxed_engine;
+
+    #[test]
+    fn test_load_tool_script() {
+        let engine = sandboxed_engine();
+        let script = ToolScript::load(
+            &engine,
+            r#"
+const GREETING = "hello";
+
+/// Step until a register holds a value
+fn stepuntil(register, value) {
+    value
+}
+
+fn greet(name) { GREETING + " " + name }
+
+private fn helper() {}
+"#,
+        )
+        .unwrap();
+        let commands = script.commands();
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].usage(), "greet <name>");
+        let stepuntil = &commands[1];
+        assert_eq!(stepuntil.usage(), "stepuntil <register> <value>");
+        assert_eq!(stepuntil.doc, "Step until a register holds a value");
+
+        assert_eq!(
+            commands[0].call(&engine, &["world", "extra"]).unwrap(),
+            Some("hello world".to_string())
+        );
+        assert_eq!(
+            stepuntil.call(&engine, &["eax", "5"]).unwrap(),
+            Some("5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tool_script_errors() {
+        let engine = sandboxed_engine();
+        assert!(ToolScript::load(&engine, "fn a() {").is_err());
+        assert!(ToolScript::load(&engine, "import \"tools\" as t;").is_err());
+        assert_eq!(
+            ToolScript::load(&engine, "throw \"no\";").unwrap_err(),
+            "no"
+        );
+
+        let script = ToolScript::load(
+            &engine,
+            "fn spin() { loop {} }\nfn fail(why) { throw why; }",
+        )
+        .unwrap();
+        let commands = script.commands();
+        assert_eq!(
+            commands[0].call(&engine, &["broken"]).unwrap_err(),
+            "broken"
+        );
+        assert!(commands[1].call(&engine, &[]).is_err());
+        assert_eq!(
+            substitute_vars("bp $x", |_| None).unwrap_err(),
+            "$x has no value here"
+        );
+    }
+}
@@ -1,5 +1,15 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use serde::{Deserialize, Serialize};
 
+use crate::debugger::{Debugger, InputPlacement, InterruptHandler, Watch};
+use crate::emulator::{
+    CycleModel, Disassembler, Executor, InstructionGroup, Memory, Permissions, RandomDevice,
+    Register, SerialPort,
+};
+
+use super::{PuzzleSolution, Script};
+
 /// Puzzle difficulty level
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -36,15 +46,16 @@ impl Difficulty {
     }
 }
 
-/// Puzzle category
+/// Puzzle category; names other than the built-in ones become `Custom`
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(from = "String", into = "String")]
 pub enum Category {
     Patching,
     Crackme,
     Keygen,
     Reconstruction,
     AntiDebug,
+    Packer,
     Custom(String),
 }
 
@@ -56,7 +67,31 @@ impl Category {
             Category::Keygen => "Keygen",
             Category::Reconstruction => "Reconstruction",
             Category::AntiDebug => "Anti-Debug",
+            Category::Packer => "Packer",
+            Category::Custom(name) => name,
+        }
+    }
+}
+
+impl From<String> for Category {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "patching" => Category::Patching,
+            "crackme" => Category::Crackme,
+            "keygen" => Category::Keygen,
+            "reconstruction" => Category::Reconstruction,
+            "antidebug" => Category::AntiDebug,
+            "packer" => Category::Packer,
+            _ => Category::Custom(name),
+        }
+    }
+}
+
+impl From<Category> for String {
+    fn from(category: Category) -> Self {
+        match category {
             Category::Custom(name) => name,
+            known => known.name().to_lowercase().replace('-', ""),
         }
     }
 }
@@ -80,6 +115,11 @@ pub struct PuzzleMetadata {
     #[serde(default)]
     pub tags: Vec<String>,
 
+    /// Concepts the puzzle exercises (e.g. "flags", "stack", "loops"),
+    /// used to recommend puzzles and spot what needs review
+    #[serde(default)]
+    pub concepts: Vec<String>,
+
     /// Estimated time to solve in minutes (optional)
     #[serde(default)]
     pub estimated_time_minutes: Option<u32>,
@@ -167,6 +207,228 @@ pub struct PuzzleSetup {
     /// Data section (optional)
     #[serde(default)]
     pub data: Option<DataSetup>,
+
+    /// Memory-mapped devices
+    #[serde(default)]
+    pub devices: Vec<DeviceSetup>,
+
+    /// Cycle costs behind RDTSC, for timing checks
+    #[serde(default)]
+    pub cycles: Option<CycleSetup>,
+
+    /// Seed for the session's random numbers; without one, each session
+    /// picks its own (reproducible with the `seed` command)
+    #[serde(default)]
+    pub rng_seed: Option<u32>,
+
+    /// Handlers for software interrupts
+    #[serde(default)]
+    pub interrupts: Vec<InterruptSetup>,
+
+    /// Bytes inside the code section that are data, not instructions
+    #[serde(default)]
+    pub data_regions: Vec<DataRegionSetup>,
+
+    /// Program input (arguments in memory, stdin for `read` interrupts)
+    #[serde(default)]
+    pub input: Option<InputSetup>,
+
+    /// Stop running when a value the validation checks changes
+    #[serde(default)]
+    pub break_on_goal: bool,
+
+    /// Fault on writes to code and on executing data or the stack
+    #[serde(default)]
+    pub enforce_permissions: bool,
+
+    /// Let the program rewrite its own code (unpacking stubs)
+    #[serde(default)]
+    pub writable_code: bool,
+
+    /// Instruction groups the program needs (`"string_ops"`, `"div"`, ...),
+    /// checked against the emulator when the puzzle loads
+    #[serde(default)]
+    pub requires: Vec<String>,
+}
+
+/// Input the program runs with, which the player can change between runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputSetup {
+    /// Input used until the player enters their own
+    #[serde(default)]
+    pub default: String,
+
+    /// Buffer the input is copied into before each run (optional)
+    #[serde(default)]
+    pub address: Option<u32>,
+
+    /// Size of that buffer
+    #[serde(default = "default_input_length")]
+    pub max_length: u32,
+
+    /// Register loaded with the input length (optional)
+    #[serde(default)]
+    pub length_register: Option<String>,
+}
+
+impl InputSetup {
+    /// Where the input goes in memory, if anywhere
+    pub fn placement(&self) -> Result<Option<InputPlacement>, String> {
+        let length_register = self
+            .length_register
+            .as_deref()
+            .map(|name| {
+                Register::from_name(name).ok_or_else(|| format!("Unknown register: {}", name))
+            })
+            .transpose()?;
+
+        Ok(self.address.map(|address| InputPlacement {
+            address,
+            max_length: self.max_length,
+            length_register,
+        }))
+    }
+}
+
+/// A run of data bytes embedded in the code section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataRegionSetup {
+    /// Address of the first data byte
+    pub address: u32,
+
+    /// Number of data bytes
+    pub length: u32,
+}
+
+/// An `INT n` handler declared by a puzzle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterruptSetup {
+    /// Interrupt vector
+    pub vector: u8,
+
+    /// "jump", "set", "ticks", "random", "read" or "halt"
+    pub action: String,
+
+    /// Handler routine address (for "jump")
+    #[serde(default)]
+    pub address: Option<u32>,
+
+    /// Register to set (for "set", "ticks" and "random")
+    #[serde(default)]
+    pub register: Option<String>,
+
+    /// Value to set (for "set")
+    #[serde(default)]
+    pub value: Option<u32>,
+}
+
+impl InterruptSetup {
+    /// Build the debugger handler for this declaration
+    pub fn handler(&self) -> Result<InterruptHandler, String> {
+        let register = || {
+            let name = self
+                .register
+                .as_deref()
+                .ok_or_else(|| format!("INT 0x{:02X}: missing register", self.vector))?;
+            Register::from_name(name).ok_or_else(|| format!("Unknown register: {}", name))
+        };
+
+        match self.action.as_str() {
+            "jump" => self
+                .address
+                .map(InterruptHandler::Jump)
+                .ok_or_else(|| format!("INT 0x{:02X}: missing address", self.vector)),
+            "set" => {
+                let value = self
+                    .value
+                    .ok_or_else(|| format!("INT 0x{:02X}: missing value", self.vector))?;
+                Ok(InterruptHandler::SetRegister(register()?, value))
+            }
+            "ticks" => Ok(InterruptHandler::Ticks(register()?)),
+            "random" => Ok(InterruptHandler::Random(register()?)),
+            "read" => Ok(InterruptHandler::Read),
+            "halt" => Ok(InterruptHandler::Halt),
+            other => Err(format!("Unknown interrupt action: {}", other)),
+        }
+    }
+}
+
+/// A memory-mapped device declared by a puzzle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceSetup {
+    /// Device type: "serial" or "rng"
+    pub kind: String,
+
+    /// Address the device's registers are mapped at
+    pub address: u32,
+
+    /// Seed for the "rng" device (the session's seed when omitted)
+    #[serde(default)]
+    pub seed: Option<u32>,
+}
+
+impl DeviceSetup {
+    /// Create the device and map it into `memory`
+    pub fn map_into(&self, memory: &mut Memory) -> Result<(), String> {
+        match self.kind.as_str() {
+            "serial" => {
+                memory.map_device(self.address, SerialPort::SIZE, SerialPort::new());
+            }
+            "rng" => {
+                let device = self
+                    .seed
+                    .map_or_else(RandomDevice::session, RandomDevice::new);
+                memory.map_device(self.address, RandomDevice::SIZE, device);
+            }
+            other => return Err(format!("Unknown device kind: {}", other)),
+        }
+        Ok(())
+    }
+}
+
+/// Cycle costs a puzzle tunes for its timing checks (defaults from
+/// `CycleModel` for anything left out)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CycleSetup {
+    /// Cost of an instruction without its own entry
+    #[serde(default)]
+    pub base: Option<u32>,
+
+    /// Extra cost for a memory operand
+    #[serde(default)]
+    pub memory: Option<u32>,
+
+    /// Extra cost for a taken jump, call or return
+    #[serde(default)]
+    pub taken_branch: Option<u32>,
+
+    /// Cost by mnemonic, e.g. `mul = 10`
+    #[serde(default)]
+    pub costs: BTreeMap<String, u32>,
+}
+
+impl CycleSetup {
+    /// Build the cycle model
+    pub fn model(&self) -> Result<CycleModel, String> {
+        let mut model = CycleModel::default();
+        if let Some(base) = self.base {
+            model.base = base;
+        }
+        if let Some(memory) = self.memory {
+            model.memory = memory;
+        }
+        if let Some(taken_branch) = self.taken_branch {
+            model.taken_branch = taken_branch;
+        }
+        for (mnemonic, &cost) in &self.costs {
+            model.set_cost(mnemonic, cost)?;
+        }
+        Ok(model)
+    }
+}
+
+fn default_input_length() -> u32 {
+    64
 }
 
 fn default_memory_size() -> usize {
@@ -208,9 +470,168 @@ pub struct PuzzleValidation {
     #[serde(default)]
     pub expected_bytes: Option<Vec<u8>>,
 
+    /// Expected device output (for serial_output type)
+    #[serde(default)]
+    pub expected_output: Option<String>,
+
+    /// Number of bytes covered, starting at `address` (for checksum type)
+    #[serde(default)]
+    pub length: Option<u32>,
+
+    /// Checksum algorithm: "sum8" (default) or "xor8" (for checksum type)
+    #[serde(default)]
+    pub algorithm: Option<String>,
+
     /// Sub-conditions for compound validation
     #[serde(default)]
     pub conditions: Vec<PuzzleValidation>,
+
+    /// When the goal is checked: "halt" (default), "address" or "command"
+    /// (top level only)
+    #[serde(default)]
+    pub trigger: Option<String>,
+
+    /// Address whose execution triggers the check (for the "address" trigger)
+    #[serde(default)]
+    pub trigger_address: Option<u32>,
+}
+
+/// When a puzzle's goal is checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationTrigger {
+    /// When the program halts
+    #[default]
+    Halt,
+    /// When execution reaches the address, which stops a run like a breakpoint
+    Address(u32),
+    /// When the player types `check`
+    Command,
+}
+
+impl ValidationTrigger {
+    /// When the check happens, in plain words
+    pub fn describe(&self) -> String {
+        match self {
+            ValidationTrigger::Halt => "Checked when the program halts".to_string(),
+            ValidationTrigger::Address(address) => {
+                format!("Checked when execution reaches 0x{:08X}", address)
+            }
+            ValidationTrigger::Command => "Checked when you type `check`".to_string(),
+        }
+    }
+}
+
+impl PuzzleValidation {
+    /// What this condition asks for, in plain words
+    pub fn describe(&self) -> String {
+        let register = self.register.as_deref().unwrap_or("?").to_uppercase();
+        match self.validation_type.as_str() {
+            "register_value" => format!(
+                "{} = 0x{:08X} when the program halts",
+                register,
+                self.expected.unwrap_or(0)
+            ),
+            "memory_value" => {
+                let bytes = self
+                    .expected_bytes
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!(
+                    "Memory at 0x{:08X} holds {}",
+                    self.address.unwrap_or(0),
+                    bytes
+                )
+            }
+            "serial_output" => format!(
+                "The serial port prints {:?}",
+                self.expected_output.as_deref().unwrap_or_default()
+            ),
+            "checksum" => {
+                let start = self.address.unwrap_or(0);
+                let mut text = format!(
+                    "Code at 0x{:08X}..0x{:08X} stays intact ({} = 0x{:X})",
+                    start,
+                    start.wrapping_add(self.length.unwrap_or(0)),
+                    self.algorithm.as_deref().unwrap_or("sum8"),
+                    self.expected.unwrap_or(0)
+                );
+                if self.register.is_some() {
+                    text.push_str(&format!(" and the check leaves it in {}", register));
+                }
+                text
+            }
+            "normal_halt" => "The program halts normally".to_string(),
+            "script" => "The puzzle script accepts the result".to_string(),
+            "all" => self
+                .conditions
+                .iter()
+                .map(|c| c.describe())
+                .collect::<Vec<_>>()
+                .join(", and "),
+            "any" => format!(
+                "One of: {}",
+                self.conditions
+                    .iter()
+                    .map(|c| c.describe())
+                    .collect::<Vec<_>>()
+                    .join(", or ")
+            ),
+            other => format!("Unknown goal: {}", other),
+        }
+    }
+
+    /// When the goal is checked
+    pub fn trigger(&self) -> Result<ValidationTrigger, String> {
+        match self.trigger.as_deref() {
+            None | Some("halt") => Ok(ValidationTrigger::Halt),
+            Some("address") => self
+                .trigger_address
+                .map(ValidationTrigger::Address)
+                .ok_or_else(|| "Validation trigger \"address\" needs trigger_address".to_string()),
+            Some("command") => Ok(ValidationTrigger::Command),
+            Some(other) => Err(format!("Unknown validation trigger: {}", other)),
+        }
+    }
+
+    /// Registers and memory this validation looks at
+    pub fn watches(&self) -> Vec<Watch> {
+        let mut watches = Vec::new();
+        self.collect_watches(&mut watches);
+        watches
+    }
+
+    fn collect_watches(&self, watches: &mut Vec<Watch>) {
+        let register = self
+            .register
+            .as_deref()
+            .and_then(Register::from_name)
+            .map(Watch::Register);
+        let found = match self.validation_type.as_str() {
+            "register_value" => register.into_iter().collect(),
+            "memory_value" => match (self.address, &self.expected_bytes) {
+                (Some(address), Some(bytes)) => vec![Watch::Memory {
+                    address,
+                    length: bytes.len() as u32,
+                }],
+                _ => Vec::new(),
+            },
+            "checksum" => register.into_iter().collect(),
+            _ => Vec::new(),
+        };
+
+        for watch in found {
+            if !watches.contains(&watch) {
+                watches.push(watch);
+            }
+        }
+        for condition in &self.conditions {
+            condition.collect_watches(watches);
+        }
+    }
 }
 
 /// Hints for the puzzle
@@ -222,6 +643,20 @@ pub struct PuzzleHints {
     pub level2: Option<String>,
     #[serde(default)]
     pub level3: Option<String>,
+
+    /// Targeted hints chosen by inspecting the debugger state
+    #[serde(default)]
+    pub rules: Vec<HintRule>,
+}
+
+/// A hint offered when its condition holds (see `Condition` for the syntax)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HintRule {
+    /// Condition, e.g. `halted and eax == 0`
+    pub when: String,
+
+    /// Hint text shown when the condition holds
+    pub hint: String,
 }
 
 impl PuzzleHints {
@@ -236,9 +671,15 @@ impl PuzzleHints {
 
     pub fn hint_count(&self) -> usize {
         let mut count = 0;
-        if self.level1.is_some() { count += 1; }
-        if self.level2.is_some() { count += 1; }
-        if self.level3.is_some() { count += 1; }
+        if self.level1.is_some() {
+            count += 1;
+        }
+        if self.level2.is_some() {
+            count += 1;
+        }
+        if self.level3.is_some() {
+            count += 1;
+        }
         count
     }
 }
@@ -261,6 +702,19 @@ pub struct Puzzle {
     /// Hints
     #[serde(default)]
     pub hints: PuzzleHints,
+
+    /// Optional script reacting to debugger events (see `Script`)
+    #[serde(default)]
+    pub script: Option<String>,
+
+    /// Official solution, walked through once the puzzle is solved
+    #[serde(default)]
+    pub solution: Option<PuzzleSolution>,
+
+    /// Author comments shown after instructions in the disassembly, keyed
+    /// by address (`"0x1005" = "compare input with 0x1337"`)
+    #[serde(default)]
+    pub annotations: BTreeMap<String, String>,
 }
 
 impl Puzzle {
@@ -271,20 +725,93 @@ impl Puzzle {
 
     /// Parse hex string to bytes
     pub fn parse_hex(hex: &str) -> Result<Vec<u8>, String> {
-        let hex = hex.replace(' ', "").replace('\n', "").replace('\r', "");
+        let digits: Vec<char> = hex
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '\n' | '\r'))
+            .collect();
 
-        if hex.len() % 2 != 0 {
+        if !digits.len().is_multiple_of(2) {
             return Err("Hex string must have even length".to_string());
         }
 
-        let mut bytes = Vec::with_capacity(hex.len() / 2);
-        for i in (0..hex.len()).step_by(2) {
-            let byte = u8::from_str_radix(&hex[i..i + 2], 16)
-                .map_err(|e| format!("Invalid hex at position {}: {}", i, e))?;
-            bytes.push(byte);
+        digits
+            .chunks(2)
+            .enumerate()
+            .map(
+                |(i, pair)| match (pair[0].to_digit(16), pair[1].to_digit(16)) {
+                    (Some(high), Some(low)) => Ok((high * 16 + low) as u8),
+                    _ => Err(format!(
+                        "Invalid hex at position {}: {}{}",
+                        i * 2,
+                        pair[0],
+                        pair[1]
+                    )),
+                },
+            )
+            .collect()
+    }
+
+    /// Check the entry point and the 4KB either side of the stack start
+    /// fit in the 32-bit address space
+    pub fn check_layout(&self) -> Result<(), String> {
+        if self
+            .setup
+            .code_start
+            .checked_add(self.setup.code.entry_point)
+            .is_none()
+        {
+            return Err(format!(
+                "Entry point 0x{:X} is past the end of memory from code_start 0x{:X}",
+                self.setup.code.entry_point, self.setup.code_start
+            ));
+        }
+        if !(0x1000..=u32::MAX - 0x1000).contains(&self.setup.stack_start) {
+            return Err(format!(
+                "stack_start 0x{:X} must leave 4KB of stack either side (0x1000 to 0x{:X})",
+                self.setup.stack_start,
+                u32::MAX - 0x1000
+            ));
         }
+        Ok(())
+    }
+
+    /// Check the emulator can run every instruction group the puzzle
+    /// requires
+    pub fn check_requirements(&self) -> Result<(), String> {
+        let mut missing = Vec::new();
+        for name in &self.setup.requires {
+            let group = InstructionGroup::from_name(name)
+                .ok_or_else(|| format!("Unknown instruction group: {}", name))?;
+            if !Executor::supports(group) {
+                missing.push(group.name());
+            }
+        }
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Puzzle '{}' needs instructions this emulator can't run yet: {}",
+                self.metadata.id,
+                missing.join(", ")
+            ))
+        }
+    }
 
-        Ok(bytes)
+    /// Author comments by address
+    pub fn annotated_addresses(&self) -> Result<BTreeMap<u32, String>, String> {
+        self.annotations
+            .iter()
+            .map(|(key, text)| {
+                let address = match key.strip_prefix("0x").or_else(|| key.strip_prefix("0X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16),
+                    None => key.parse(),
+                };
+                address
+                    .map(|address| (address, text.clone()))
+                    .map_err(|_| format!("Annotation address is not a number: {}", key))
+            })
+            .collect()
     }
 
     /// Get code bytes
@@ -292,6 +819,86 @@ impl Puzzle {
         Self::parse_hex(&self.setup.code.bytes)
     }
 
+    /// Distinct mnemonics in the puzzle's code as loaded, sorted
+    pub fn mnemonics(&self) -> Vec<String> {
+        let bytes = self.code_bytes().unwrap_or_default();
+        let mnemonics: BTreeSet<String> = Disassembler::new()
+            .disassemble(&bytes, self.setup.code_start, bytes.len())
+            .iter()
+            .filter(|line| line.is_code())
+            .map(|line| format!("{:?}", line.instruction.mnemonic()).to_uppercase())
+            .collect();
+        mnemonics.into_iter().collect()
+    }
+
+    /// Map the puzzle's devices into `memory`
+    pub fn map_devices(&self, memory: &mut Memory) -> Result<(), String> {
+        for device in &self.setup.devices {
+            device.map_into(memory)?;
+        }
+        Ok(())
+    }
+
+    /// Install the puzzle's interrupt handlers into `debugger`
+    pub fn install_interrupts(&self, debugger: &mut Debugger) -> Result<(), String> {
+        for setup in &self.setup.interrupts {
+            debugger.set_interrupt_handler(setup.vector, setup.handler()?);
+        }
+        Ok(())
+    }
+
+    /// Give `debugger` the puzzle's cycle costs, if it tunes them
+    pub fn install_cycles(&self, debugger: &mut Debugger) -> Result<(), String> {
+        if let Some(ref cycles) = self.setup.cycles {
+            debugger.set_cycle_model(cycles.model()?);
+        }
+        Ok(())
+    }
+
+    /// Apply the puzzle's memory protection to `memory`
+    pub fn apply_permissions(&self, memory: &mut Memory) {
+        memory.set_enforce_permissions(self.setup.enforce_permissions);
+        if self.setup.writable_code {
+            if let Some(code) = memory.region_mut("code") {
+                code.permissions = Permissions::rwx();
+            }
+        }
+    }
+
+    /// Give `debugger` the puzzle's default input and where to place it
+    pub fn install_input(&self, debugger: &mut Debugger) -> Result<(), String> {
+        let Some(ref input) = self.setup.input else {
+            return Ok(());
+        };
+        debugger.set_input(input.default.as_bytes());
+        if let Some(placement) = input.placement()? {
+            debugger
+                .set_input_placement(placement)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Make runs stop where the goal is checked, for the "address" trigger
+    pub fn install_trigger(&self, debugger: &mut Debugger) -> Result<(), String> {
+        if let ValidationTrigger::Address(address) = self.validation.trigger()? {
+            debugger.set_stop_point(address);
+        }
+        Ok(())
+    }
+
+    /// Tell `debugger` which code bytes to disassemble as data
+    pub fn mark_data_regions(&self, debugger: &mut Debugger) {
+        for region in &self.setup.data_regions {
+            debugger.mark_data(region.address, region.length);
+        }
+    }
+
+    /// Parse the puzzle script, if there is one
+    pub fn load_script(&self) -> Result<Option<Script>, String> {
+        self.script.as_deref().map(Script::parse).transpose()
+    }
+
     /// Get data bytes
     pub fn data_bytes(&self) -> Result<Option<Vec<u8>>, String> {
         if let Some(ref data) = self.setup.data {
@@ -314,6 +921,24 @@ mod tests {
             Puzzle::parse_hex("DEADBEEF").unwrap(),
             vec![0xDE, 0xAD, 0xBE, 0xEF]
         );
+        assert_eq!(
+            Puzzle::parse_hex("9é").unwrap_err(),
+            "Invalid hex at position 0: 9é"
+        );
+    }
+
+    #[test]
+    fn test_category_names() {
+        assert_eq!(Category::from("antidebug".to_string()), Category::AntiDebug);
+        assert_eq!(
+            Category::from("strings".to_string()),
+            Category::Custom("strings".to_string())
+        );
+        assert_eq!(String::from(Category::AntiDebug), "antidebug");
+        assert_eq!(
+            String::from(Category::Custom("arrays".to_string())),
+            "arrays"
+        );
     }
 
     #[test]
@@ -321,4 +946,85 @@ mod tests {
         assert!(Difficulty::Tutorial < Difficulty::Beginner);
         assert!(Difficulty::Beginner < Difficulty::Expert);
     }
+
+    #[test]
+    fn test_validation_watches() {
+        let validation: PuzzleValidation = toml::from_str(
+            r#"
+type = "all"
+
+[[conditions]]
+type = "register_value"
+register = "eax"
+expected = 1
+
+[[conditions]]
+type = "memory_value"
+address = 0x2000
+expected_bytes = [1, 2]
+
+[[conditions]]
+type = "normal_halt"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            validation.watches(),
+            vec![
+                Watch::Register(Register::Eax),
+                Watch::Memory {
+                    address: 0x2000,
+                    length: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interrupt_setup_handler() {
+        let setup: InterruptSetup = toml::from_str(
+            r#"
+vector = 0x1A
+action = "set"
+register = "eax"
+value = 1234
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            setup.handler(),
+            Ok(InterruptHandler::SetRegister(Register::Eax, 1234))
+        );
+
+        let random: InterruptSetup =
+            toml::from_str("vector = 0x1B\naction = \"random\"\nregister = \"ebx\"").unwrap();
+        assert_eq!(
+            random.handler(),
+            Ok(InterruptHandler::Random(Register::Ebx))
+        );
+
+        let missing: InterruptSetup = toml::from_str("vector = 0x21\naction = \"jump\"").unwrap();
+        assert!(missing.handler().is_err());
+    }
+
+    #[test]
+    fn test_validation_trigger() {
+        let parse = |extra: &str| {
+            toml::from_str::<PuzzleValidation>(&format!("type = \"normal_halt\"\n{}", extra))
+                .unwrap()
+                .trigger()
+        };
+        assert_eq!(parse(""), Ok(ValidationTrigger::Halt));
+        assert_eq!(
+            parse("trigger = \"command\""),
+            Ok(ValidationTrigger::Command)
+        );
+        assert_eq!(
+            parse("trigger = \"address\"\ntrigger_address = 0x1010"),
+            Ok(ValidationTrigger::Address(0x1010))
+        );
+        assert!(parse("trigger = \"address\"").is_err());
+        assert!(parse("trigger = \"sometimes\"").is_err());
+    }
 }
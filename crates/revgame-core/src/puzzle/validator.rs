@@ -1,6 +1,6 @@
 use crate::emulator::{CpuState, Memory, Register};
 
-use super::{Puzzle, PuzzleValidation};
+use super::{ChecksumAlgorithm, Puzzle, PuzzleValidation, Script, ScriptContext, ScriptEvent};
 
 /// Result of puzzle validation
 #[derive(Debug, Clone)]
@@ -38,6 +38,13 @@ pub enum ValidationRule {
     Any(Vec<ValidationRule>),
 }
 
+/// One part of a puzzle's goal and whether it holds right now
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Objective {
+    pub description: String,
+    pub met: bool,
+}
+
 /// Puzzle validator
 pub struct Validator;
 
@@ -47,6 +54,42 @@ impl Validator {
         Self::validate_config(&puzzle.validation, cpu, memory)
     }
 
+    /// Validate using the puzzle script's halt handler when it gives a
+    /// verdict, falling back to the declarative rules. Also returns any
+    /// hints the script emitted.
+    pub fn validate_scripted(
+        puzzle: &Puzzle,
+        script: &mut Script,
+        ctx: &ScriptContext,
+    ) -> (ValidationResult, Vec<String>) {
+        let outcome = script.fire(ScriptEvent::Halt, ctx);
+        let result = outcome
+            .verdict
+            .unwrap_or_else(|| Self::validate(puzzle, ctx.cpu, ctx.memory));
+        (result, outcome.hints)
+    }
+
+    /// The goal split into its conditions, each checked against the
+    /// current state. Halt-only conditions stay unmet until the program halts.
+    pub fn objectives(
+        config: &PuzzleValidation,
+        cpu: &CpuState,
+        memory: &Memory,
+    ) -> Vec<Objective> {
+        let parts: Vec<&PuzzleValidation> = match config.validation_type.as_str() {
+            "all" => config.conditions.iter().collect(),
+            _ => vec![config],
+        };
+
+        parts
+            .into_iter()
+            .map(|part| Objective {
+                description: part.describe(),
+                met: Self::validate_config(part, cpu, memory).is_success(),
+            })
+            .collect()
+    }
+
     /// Validate based on configuration
     fn validate_config(
         config: &PuzzleValidation,
@@ -68,10 +111,7 @@ impl Validator {
                 let register = match Register::from_name(reg_name) {
                     Some(r) => r,
                     None => {
-                        return ValidationResult::Error(format!(
-                            "Unknown register: {}",
-                            reg_name
-                        ))
+                        return ValidationResult::Error(format!("Unknown register: {}", reg_name))
                     }
                 };
 
@@ -99,7 +139,7 @@ impl Validator {
                     None => return ValidationResult::Error("Missing expected bytes".to_string()),
                 };
 
-                match memory.read_bytes(address, expected.len()) {
+                match memory.peek_bytes(address, expected.len()) {
                     Ok(actual) => {
                         if actual == *expected {
                             ValidationResult::Success
@@ -114,6 +154,90 @@ impl Validator {
                 }
             }
 
+            "serial_output" => {
+                let expected = match &config.expected_output {
+                    Some(o) => o,
+                    None => return ValidationResult::Error("Missing expected output".to_string()),
+                };
+
+                let actual = memory
+                    .devices()
+                    .iter()
+                    .find(|m| m.name() == "serial")
+                    .and_then(|m| m.output());
+
+                match actual {
+                    Some(actual) if actual == *expected => ValidationResult::Success,
+                    Some(actual) => ValidationResult::Failure(format!(
+                        "Serial output {:?}, expected {:?}",
+                        actual, expected
+                    )),
+                    None => ValidationResult::Error("Puzzle has no serial device".to_string()),
+                }
+            }
+
+            "checksum" => {
+                let (address, length) = match (config.address, config.length) {
+                    (Some(a), Some(l)) => (a, l),
+                    _ => return ValidationResult::Error("Missing address or length".to_string()),
+                };
+
+                let expected = match config.expected {
+                    Some(v) => v,
+                    None => return ValidationResult::Error("Missing expected value".to_string()),
+                };
+
+                let algorithm = match config.algorithm.as_deref() {
+                    None => ChecksumAlgorithm::default(),
+                    Some(name) => match ChecksumAlgorithm::from_name(name) {
+                        Some(a) => a,
+                        None => {
+                            return ValidationResult::Error(format!(
+                                "Unknown checksum algorithm: {}",
+                                name
+                            ))
+                        }
+                    },
+                };
+
+                let bytes = match memory.peek_bytes(address, length as usize) {
+                    Ok(b) => b,
+                    Err(e) => return ValidationResult::Error(format!("Memory read error: {}", e)),
+                };
+
+                // The routine must have produced the checksum of untampered code
+                let actual = algorithm.compute(&bytes);
+                if actual != expected {
+                    return ValidationResult::Failure(format!(
+                        "Self-check broken: {} of 0x{:08X}..0x{:08X} is 0x{:08X}, expected 0x{:08X}",
+                        algorithm.name(),
+                        address,
+                        address.wrapping_add(length),
+                        actual,
+                        expected
+                    ));
+                }
+
+                if let Some(register) = config.register.as_deref().and_then(Register::from_name) {
+                    let computed = cpu.get_register(register);
+                    if computed != expected {
+                        return ValidationResult::Failure(format!(
+                            "The checksum routine left {} = 0x{:08X}, expected 0x{:08X}",
+                            register.name(),
+                            computed,
+                            expected
+                        ));
+                    }
+                }
+
+                ValidationResult::Success
+            }
+
+            // Decided by the puzzle script; reaching here means it gave no verdict
+            "script" => {
+                ValidationResult::Failure("The puzzle script did not accept this".to_string())
+            }
+
             "normal_halt" => {
                 if cpu.halted {
                     ValidationResult::Success
@@ -170,7 +294,12 @@ mod tests {
             expected: Some(0x42),
             address: None,
             expected_bytes: None,
+            expected_output: None,
+            length: None,
+            algorithm: None,
             conditions: Vec::new(),
+            trigger: None,
+            trigger_address: None,
         };
 
         let result = Validator::validate_config(&config, &cpu, &memory);
@@ -182,6 +311,38 @@ mod tests {
         assert!(!result.is_success());
     }
 
+    #[test]
+    fn test_objectives() {
+        let config: PuzzleValidation = toml::from_str(
+            r#"
+type = "all"
+
+[[conditions]]
+type = "register_value"
+register = "eax"
+expected = 1
+
+[[conditions]]
+type = "normal_halt"
+"#,
+        )
+        .unwrap();
+
+        let mut cpu = CpuState::default();
+        cpu.regs.eax = 1;
+        let memory = Memory::new(0x1000);
+
+        let objectives = Validator::objectives(&config, &cpu, &memory);
+        assert_eq!(objectives.len(), 2);
+        assert_eq!(
+            objectives[0].description,
+            "EAX = 0x00000001 when the program halts"
+        );
+        assert!(objectives[0].met);
+        assert_eq!(objectives[1].description, "The program halts normally");
+        assert!(!objectives[1].met);
+    }
+
     #[test]
     fn test_halt_validation() {
         let mut cpu = CpuState::default();
@@ -193,7 +354,12 @@ mod tests {
             expected: None,
             address: None,
             expected_bytes: None,
+            expected_output: None,
+            length: None,
+            algorithm: None,
             conditions: Vec::new(),
+            trigger: None,
+            trigger_address: None,
         };
 
         // Not halted
@@ -205,4 +371,74 @@ mod tests {
         let result = Validator::validate_config(&config, &cpu, &memory);
         assert!(result.is_success());
     }
+
+    #[test]
+    fn test_serial_output_validation() {
+        use crate::emulator::SerialPort;
+
+        let cpu = CpuState::default();
+        let mut memory = Memory::new(0x10000);
+        memory.map_device(0xF000, SerialPort::SIZE, SerialPort::new());
+
+        let config = PuzzleValidation {
+            validation_type: "serial_output".to_string(),
+            register: None,
+            expected: None,
+            address: None,
+            expected_bytes: None,
+            expected_output: Some("OK".to_string()),
+            length: None,
+            algorithm: None,
+            conditions: Vec::new(),
+            trigger: None,
+            trigger_address: None,
+        };
+
+        memory.write_u8(0xF000, b'O').unwrap();
+        let result = Validator::validate_config(&config, &cpu, &memory);
+        assert!(matches!(result, ValidationResult::Failure(_)));
+
+        memory.write_u8(0xF000, b'K').unwrap();
+        let result = Validator::validate_config(&config, &cpu, &memory);
+        assert!(result.is_success());
+    }
+
+    #[test]
+    fn test_checksum_validation() {
+        let mut cpu = CpuState::default();
+        let mut memory = Memory::new(0x2000);
+        memory.load(0x1000, &[0x75, 0x06, 0xB8, 0x01]).unwrap();
+
+        let config = PuzzleValidation {
+            validation_type: "checksum".to_string(),
+            register: Some("ebx".to_string()),
+            expected: Some(0x134),
+            address: Some(0x1000),
+            expected_bytes: None,
+            expected_output: None,
+            length: Some(4),
+            algorithm: None,
+            conditions: Vec::new(),
+            trigger: None,
+            trigger_address: None,
+        };
+
+        // Code intact but the routine never ran
+        let result = Validator::validate_config(&config, &cpu, &memory);
+        assert!(matches!(result, ValidationResult::Failure(ref msg) if msg.contains("EBX")));
+
+        cpu.regs.ebx = 0x134;
+        assert!(Validator::validate_config(&config, &cpu, &memory).is_success());
+
+        // JNE -> JE changes the sum by one
+        memory.write_u8(0x1000, 0x74).unwrap();
+        let result = Validator::validate_config(&config, &cpu, &memory);
+        assert!(
+            matches!(result, ValidationResult::Failure(ref msg) if msg.starts_with("Self-check broken"))
+        );
+
+        // ...unless another byte makes up for it
+        memory.write_u8(0x1003, 0x02).unwrap();
+        assert!(Validator::validate_config(&config, &cpu, &memory).is_success());
+    }
 }
@@ -0,0 +1,128 @@
+/// Names of the control characters 0x00-0x1F
+const CONTROL_NAMES: [&str; 32] = [
+    "NUL", "SOH", "STX", "ETX", "EOT", "ENQ", "ACK", "BEL", "BS", "TAB", "LF", "VT", "FF", "CR",
+    "SO", "SI", "DLE", "DC1", "DC2", "DC3", "DC4", "NAK", "SYN", "ETB", "CAN", "EM", "SUB", "ESC",
+    "FS", "GS", "RS", "US",
+];
+
+/// Highest ASCII code
+pub const ASCII_MAX: u8 = 0x7F;
+
+/// Short name of an ASCII code: the character itself, or "NUL", "SP", "DEL"
+pub fn ascii_name(code: u8) -> String {
+    match code {
+        0x00..=0x1F => CONTROL_NAMES[code as usize].to_string(),
+        0x20 => "SP".to_string(),
+        0x7F => "DEL".to_string(),
+        0x21..=0x7E => (code as char).to_string(),
+        _ => String::new(),
+    }
+}
+
+/// What a code is, with the arithmetic crackmes lean on (case flips, digit values)
+pub fn ascii_note(code: u8) -> String {
+    match code {
+        b'A'..=b'Z' => format!(
+            "Uppercase letter; lowercase '{}' is 0x{:02X} (+0x20, sets bit 5)",
+            (code + 0x20) as char,
+            code + 0x20
+        ),
+        b'a'..=b'z' => format!(
+            "Lowercase letter; uppercase '{}' is 0x{:02X} (-0x20, clears bit 5)",
+            (code - 0x20) as char,
+            code - 0x20
+        ),
+        b'0'..=b'9' => format!("Digit; its value is code - 0x30 = {}", code - b'0'),
+        0x00 => "Null: ends C strings".to_string(),
+        0x09 => "Tab, \\t".to_string(),
+        0x0A => "Line feed, \\n: ends lines".to_string(),
+        0x0D => "Carriage return, \\r: ends lines on DOS before \\n".to_string(),
+        0x1B => "Escape: starts terminal escape sequences".to_string(),
+        0x01..=0x1F => "Control character".to_string(),
+        0x20 => "Space: the first printable character".to_string(),
+        0x7F => "Delete: the last ASCII code".to_string(),
+        0x21..=0x7E => "Punctuation or symbol".to_string(),
+        _ => "Not ASCII".to_string(),
+    }
+}
+
+/// Codes matching a query: a character (`A`, `'7'`, `\n`), a number (`65`,
+/// `0x41`) or a name (`nul`, `esc`). A lone digit matches both readings.
+pub fn ascii_search(query: &str) -> Vec<u8> {
+    let mut found = Vec::new();
+    if query.is_empty() {
+        return found;
+    }
+
+    let unquoted = query
+        .strip_prefix('\'')
+        .and_then(|q| q.strip_suffix('\''))
+        .filter(|q| !q.is_empty());
+    let character = match unquoted.unwrap_or(query) {
+        "\\0" => Some('\0'),
+        "\\t" => Some('\t'),
+        "\\n" => Some('\n'),
+        "\\r" => Some('\r'),
+        text => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(c),
+                _ => None,
+            }
+        }
+    };
+    if let Some(c) = character.filter(char::is_ascii) {
+        found.push(c as u8);
+    }
+
+    if unquoted.is_none() {
+        let number = match query
+            .strip_prefix("0x")
+            .or_else(|| query.strip_prefix("0X"))
+        {
+            Some(hex) => u8::from_str_radix(hex, 16).ok(),
+            None => query.parse::<u8>().ok(),
+        };
+        found.extend(number.filter(|&n| n <= ASCII_MAX));
+        found.extend((0..=ASCII_MAX).filter(|&code| {
+            let name = ascii_name(code);
+            name.len() > 1 && name.eq_ignore_ascii_case(query)
+        }));
+    }
+
+    found.sort_unstable();
+    found.dedup();
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_search_by_char_code_and_name() {
+        assert_eq!(ascii_search("A"), vec![0x41]);
+        assert_eq!(ascii_search("'a'"), vec![0x61]);
+        assert_eq!(ascii_search("0x41"), vec![0x41]);
+        assert_eq!(ascii_search("65"), vec![0x41]);
+        // A lone digit is both a character and a code
+        assert_eq!(ascii_search("7"), vec![0x07, 0x37]);
+        assert_eq!(ascii_search("'7'"), vec![0x37]);
+        assert_eq!(ascii_search("esc"), vec![0x1B]);
+        assert_eq!(ascii_search("\\n"), vec![0x0A]);
+        assert_eq!(ascii_search("sp"), vec![0x20]);
+        assert_eq!(ascii_search(" "), vec![0x20]);
+        assert!(ascii_search("200").is_empty());
+        assert!(ascii_search("").is_empty());
+    }
+
+    #[test]
+    fn test_ascii_names_and_notes() {
+        assert_eq!(ascii_name(0x00), "NUL");
+        assert_eq!(ascii_name(0x41), "A");
+        assert_eq!(ascii_name(0x7F), "DEL");
+        assert!(ascii_note(b'A').contains("'a' is 0x61"));
+        assert!(ascii_note(b'z').contains("'Z' is 0x5A"));
+        assert!(ascii_note(b'7').contains("= 7"));
+    }
+}
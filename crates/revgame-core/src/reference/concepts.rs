@@ -0,0 +1,235 @@
+/// Background concepts that the instruction reference assumes
+#[derive(Debug, Clone)]
+pub struct Concept {
+    /// Short stable name used for cross-links, e.g. "endianness"
+    pub id: &'static str,
+    pub title: &'static str,
+    /// One-line summary shown in lists
+    pub summary: &'static str,
+    /// Explanation, with ASCII illustrations
+    pub body: &'static str,
+    /// Instructions where this concept matters
+    pub instructions: &'static [&'static str],
+    /// Ids of related concepts
+    pub related: &'static [&'static str],
+}
+
+const CONCEPTS: &[Concept] = &[
+    Concept {
+        id: "flags",
+        title: "Flags",
+        summary: "Single-bit results that conditional jumps read",
+        body: "\
+Arithmetic and logic instructions record facts about their result in
+EFLAGS. Conditional jumps, SETcc and CMOVcc only look at these bits.
+
+  ZF  zero       result was 0
+  SF  sign       top bit of the result was 1 (negative if signed)
+  CF  carry      unsigned overflow: a carry out, or a borrow in
+  OF  overflow   signed overflow: the sign came out wrong
+
+CMP is a SUB that throws the result away, and TEST is an AND that
+does the same, so they only set flags:
+
+  CMP EAX, 5        EAX - 5
+  EAX = 5   ->  0          ZF=1        JE taken
+  EAX = 3   ->  -2         SF=1 CF=1   JB, JL taken
+  EAX = 9   ->  4          all clear   JA, JG taken
+
+Unsigned jumps (JA, JB) use CF; signed jumps (JG, JL) use SF and OF.
+MOV, LEA, PUSH and POP leave the flags alone.",
+        instructions: &[
+            "CMP", "TEST", "JE", "JNE", "JZ", "JNZ", "JG", "JL", "JA", "JB", "ADD", "SUB", "AND",
+            "OR", "XOR", "INC", "DEC",
+        ],
+        related: &["twos-complement"],
+    },
+    Concept {
+        id: "twos-complement",
+        title: "Two's complement",
+        summary: "How the same bits mean both signed and unsigned numbers",
+        body: "\
+A register holds 32 bits; whether they are signed is up to the
+instruction reading them. Negative numbers use two's complement:
+flip every bit, then add one.
+
+    5  = 0000 0101
+   ~5  = 1111 1010
+   -5  = 1111 1011  = 0xFB (8-bit), 0xFFFFFFFB (32-bit)
+
+  bits        unsigned    signed
+  0x00000000           0         0
+  0x7FFFFFFF  2147483647  2147483647   largest positive
+  0x80000000  2147483648 -2147483648   smallest negative
+  0xFFFFFFFF  4294967295        -1
+
+Adding works the same either way, which is why ADD sets both CF (the
+unsigned view overflowed) and OF (the signed view did). NEG computes
+the two's complement, and -0x80000000 is 0x80000000 again.",
+        instructions: &[
+            "NEG", "NOT", "SUB", "CMP", "JG", "JL", "IMUL", "IDIV", "MOVSX",
+        ],
+        related: &["flags", "endianness"],
+    },
+    Concept {
+        id: "endianness",
+        title: "Endianness",
+        summary: "x86 stores the least significant byte first",
+        body: "\
+x86 is little-endian: a multi-byte value is stored with its lowest
+byte at the lowest address.
+
+  MOV DWORD [0x2000], 0x12345678
+
+  address  0x2000 0x2001 0x2002 0x2003
+  byte         78     56     34     12
+
+So the memory view shows values 'backwards'. Reading the four bytes
+right to left gives the number back. The same goes for immediates
+and displacements inside instructions:
+
+  B8 78 56 34 12      MOV EAX, 0x12345678
+
+Strings are byte sequences, so they read left to right:
+'AB' is 41 42, and as a DWORD it is 0x00004241.",
+        instructions: &["MOV", "PUSH", "POP"],
+        related: &["stack-frames", "twos-complement"],
+    },
+    Concept {
+        id: "stack-frames",
+        title: "Stack frames",
+        summary: "How a function keeps its arguments, locals and return address",
+        body: "\
+The stack grows down: PUSH subtracts 4 from ESP, then stores. A
+function usually sets up a frame so EBP marks a fixed point:
+
+  PUSH EBP            ; save caller's frame
+  MOV  EBP, ESP       ; EBP = our frame
+  SUB  ESP, 8         ; room for two locals
+
+  higher addresses
+  |  arg 2          |  [EBP+12]
+  |  arg 1          |  [EBP+8]
+  |  return address |  [EBP+4]   pushed by CALL
+  |  saved EBP      |  [EBP]     <- EBP
+  |  local 1        |  [EBP-4]
+  |  local 2        |  [EBP-8]   <- ESP
+  lower addresses
+
+On the way out, MOV ESP, EBP and POP EBP (or LEAVE) undo this and
+RET pops the return address into EIP. Overwriting that slot
+redirects where RET goes.",
+        instructions: &["PUSH", "POP", "CALL", "RET", "LEAVE"],
+        related: &["calling-conventions", "endianness"],
+    },
+    Concept {
+        id: "calling-conventions",
+        title: "Calling conventions",
+        summary: "Where arguments go and who cleans up after a call",
+        body: "\
+A calling convention is the agreement between caller and callee.
+The common 32-bit ones:
+
+  convention  arguments                 cleanup   return
+  cdecl       stack, right to left      caller    EAX
+  stdcall     stack, right to left      callee    EAX
+  fastcall    ECX, EDX, then stack      callee    EAX
+
+cdecl call to f(1, 2):
+
+  PUSH 2              ; last argument first
+  PUSH 1
+  CALL f              ; pushes return address
+  ADD  ESP, 8         ; caller drops the arguments
+
+stdcall ends with RET 8 in the callee instead of the ADD. EAX, ECX
+and EDX may be clobbered by the call; EBX, ESI, EDI and EBP must be
+preserved.",
+        instructions: &["CALL", "RET", "PUSH"],
+        related: &["stack-frames"],
+    },
+];
+
+/// Searchable collection of concepts
+pub struct Glossary {
+    concepts: &'static [Concept],
+}
+
+impl Default for Glossary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Glossary {
+    pub fn new() -> Self {
+        Self { concepts: CONCEPTS }
+    }
+
+    /// All concepts in reading order
+    pub fn all(&self) -> &[Concept] {
+        self.concepts
+    }
+
+    pub fn lookup(&self, id: &str) -> Option<&Concept> {
+        self.concepts.iter().find(|concept| concept.id == id)
+    }
+
+    /// Concepts whose title, summary or text mention the query
+    pub fn search(&self, query: &str) -> Vec<&Concept> {
+        let query = query.to_lowercase();
+        self.concepts
+            .iter()
+            .filter(|concept| {
+                concept.title.to_lowercase().contains(&query)
+                    || concept.summary.to_lowercase().contains(&query)
+                    || concept.body.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+
+    /// Concepts worth reading before using an instruction
+    pub fn for_instruction(&self, mnemonic: &str) -> Vec<&Concept> {
+        let mnemonic = mnemonic.to_uppercase();
+        self.concepts
+            .iter()
+            .filter(|concept| concept.instructions.contains(&mnemonic.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links_resolve() {
+        let glossary = Glossary::new();
+        for concept in glossary.all() {
+            for id in concept.related {
+                assert!(
+                    glossary.lookup(id).is_some(),
+                    "{} links to {}",
+                    concept.id,
+                    id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_and_instruction_links() {
+        let glossary = Glossary::new();
+        let results = glossary.search("LITTLE-ENDIAN");
+        assert_eq!(results[0].id, "endianness");
+        assert!(glossary.search("").len() == glossary.all().len());
+
+        let ids: Vec<_> = glossary
+            .for_instruction("call")
+            .iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(ids, ["stack-frames", "calling-conventions"]);
+        assert!(glossary.for_instruction("NOP").is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::emulator::{CpuState, Memory};
+use crate::puzzle::{Condition, ScriptContext};
+
+/// A 32-bit value shown every way a reverser needs to read it, from an
+/// expression such as `0x1337 ^ 0xFF` or `eax - 1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conversion {
+    pub value: u32,
+    /// Outermost operator and operands, truncated to 32 bits
+    pub operation: Option<(&'static str, u32, u32)>,
+}
+
+impl Conversion {
+    /// Evaluate an expression with script syntax. Registers and memory come
+    /// from `ctx`; without one, only constants are known (registers read 0).
+    pub fn evaluate(text: &str, ctx: Option<&ScriptContext>) -> Result<Self, String> {
+        if text.trim().is_empty() {
+            return Err("Enter a value or expression".to_string());
+        }
+        let expr = Condition::parse(text)?;
+
+        let cpu = CpuState::default();
+        let memory = Memory::new(0);
+        let empty = ScriptContext {
+            cpu: &cpu,
+            memory: &memory,
+            steps: 0,
+            patches: 0,
+            breakpoints: 0,
+            labels: None,
+        };
+        let ctx = ctx.unwrap_or(&empty);
+
+        Ok(Self {
+            value: expr.value(ctx)? as u32,
+            operation: expr
+                .operands(ctx)
+                .map(|(op, a, b)| (op, a as u32, b as u32)),
+        })
+    }
+
+    pub fn signed(&self) -> i32 {
+        self.value as i32
+    }
+
+    /// Binary in nibble groups, e.g. "0001 0011 0011 0111" for 0x1337
+    pub fn binary(&self) -> String {
+        binary(self.value)
+    }
+
+    /// Bytes as they sit in memory (little-endian)
+    pub fn memory_bytes(&self) -> String {
+        self.value
+            .to_le_bytes()
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Labelled readings, one per line
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let value = self.value;
+        let mut rows = vec![
+            ("Hex", format!("0x{:08X}", value)),
+            ("Unsigned", value.to_string()),
+            ("Signed", self.signed().to_string()),
+            ("Binary", self.binary()),
+            ("Memory", self.memory_bytes()),
+            (
+                "Low word/byte",
+                format!(
+                    "0x{:04X} ({}), 0x{:02X} ({})",
+                    value as u16, value as u16 as i16, value as u8, value as u8 as i8
+                ),
+            ),
+            (
+                "Negated",
+                format!("0x{:08X} (flip the bits, add 1)", value.wrapping_neg()),
+            ),
+            ("Bits set", format!("{} of 32", value.count_ones())),
+        ];
+        if let Some(c) = char::from_u32(value).filter(|c| value < 0x7F && !c.is_control()) {
+            rows.push(("Character", format!("'{}'", c)));
+        }
+        rows
+    }
+
+    /// The operands and result lined up in binary, for bitwise operators
+    pub fn bitwise_breakdown(&self) -> Option<[String; 3]> {
+        let (op, a, b) = self.operation?;
+        if !matches!(op, "&" | "|" | "^" | "<<" | ">>") {
+            return None;
+        }
+        Some([
+            format!("  {}  0x{:08X}", binary(a), a),
+            format!("{:<2}{}  0x{:08X}", op, binary(b), b),
+            format!("= {}  0x{:08X}", binary(self.value), self.value),
+        ])
+    }
+}
+
+fn binary(value: u32) -> String {
+    (0..8)
+        .rev()
+        .map(|nibble| format!("{:04b}", (value >> (nibble * 4)) & 0xF))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readings() {
+        let conversion = Conversion::evaluate("-2", None).unwrap();
+        assert_eq!(conversion.value, 0xFFFFFFFE);
+        assert_eq!(conversion.signed(), -2);
+        assert_eq!(conversion.memory_bytes(), "FE FF FF FF");
+        assert!(conversion.bitwise_breakdown().is_none());
+
+        let conversion = Conversion::evaluate("0x41", None).unwrap();
+        assert!(conversion
+            .rows()
+            .contains(&("Character", "'A'".to_string())));
+        assert_eq!(
+            conversion.binary(),
+            "0000 0000 0000 0000 0000 0000 0100 0001"
+        );
+
+        assert!(Conversion::evaluate("", None).is_err());
+        assert!(Conversion::evaluate("0x1337 ^", None).is_err());
+    }
+
+    #[test]
+    fn test_bitwise_breakdown() {
+        let conversion = Conversion::evaluate("0x1337 ^ 0xFF", None).unwrap();
+        assert_eq!(conversion.value, 0x13C8);
+        let [a, b, result] = conversion.bitwise_breakdown().unwrap();
+        assert!(a.ends_with("0x00001337"));
+        assert!(b.starts_with("^ "));
+        assert!(result.starts_with("= 0000 0000 0000 0000 0001 0011 1100 1000"));
+    }
+}
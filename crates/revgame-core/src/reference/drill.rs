@@ -0,0 +1,408 @@
+/// Practice drills built from the instruction reference
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::InstructionReference;
+use crate::emulator::{CpuState, EmulatorError, Executor, Memory, Register};
+
+/// Where a drill's instruction is placed
+const CODE_ADDRESS: u32 = 0x1000;
+
+/// Initial stack pointer for drills that touch the stack
+const STACK_TOP: u32 = 0x2000;
+
+/// Instruction forms drills are made of: mnemonic, assembly, encoding
+const FORMS: &[(&str, &str, &[u8])] = &[
+    ("MOV", "MOV EAX, EBX", &[0x89, 0xD8]),
+    ("ADD", "ADD EAX, EBX", &[0x01, 0xD8]),
+    ("SUB", "SUB EAX, EBX", &[0x29, 0xD8]),
+    ("INC", "INC EAX", &[0x40]),
+    ("DEC", "DEC EAX", &[0x48]),
+    ("NEG", "NEG EAX", &[0xF7, 0xD8]),
+    ("IMUL", "IMUL EAX, EBX", &[0x0F, 0xAF, 0xC3]),
+    ("AND", "AND EAX, EBX", &[0x21, 0xD8]),
+    ("OR", "OR EAX, EBX", &[0x09, 0xD8]),
+    ("XOR", "XOR EAX, EBX", &[0x31, 0xD8]),
+    ("NOT", "NOT EAX", &[0xF7, 0xD0]),
+    ("CMP", "CMP EAX, EBX", &[0x39, 0xD8]),
+    ("TEST", "TEST EAX, EBX", &[0x85, 0xD8]),
+    ("PUSH", "PUSH EBX", &[0x53]),
+];
+
+/// Operands that make for instructive edge cases
+const EDGE_VALUES: [u32; 6] = [0, 1, 0x7FFF_FFFF, 0x8000_0000, 0xFFFF_FFFF, 0xFF];
+
+/// What a drill asks the player to predict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrillQuestion {
+    /// A register's value afterwards
+    Register(Register),
+    /// Which of ZF, SF, CF and OF are set afterwards
+    Flags,
+}
+
+/// The flags drills ask about
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrillFlags {
+    pub zf: bool,
+    pub sf: bool,
+    pub cf: bool,
+    pub of: bool,
+}
+
+impl DrillFlags {
+    /// Parse e.g. "ZF CF", "zf,of" or "none"
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut flags = Self::default();
+        for token in text
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|t| !t.is_empty())
+        {
+            match token.to_uppercase().as_str() {
+                "ZF" => flags.zf = true,
+                "SF" => flags.sf = true,
+                "CF" => flags.cf = true,
+                "OF" => flags.of = true,
+                "NONE" => {}
+                other => return Err(format!("Unknown flag: {}", other)),
+            }
+        }
+        Ok(flags)
+    }
+
+    /// e.g. "ZF CF", or "none"
+    pub fn describe(&self) -> String {
+        let set: Vec<&str> = [
+            ("ZF", self.zf),
+            ("SF", self.sf),
+            ("CF", self.cf),
+            ("OF", self.of),
+        ]
+        .into_iter()
+        .filter(|(_, set)| *set)
+        .map(|(name, _)| name)
+        .collect();
+        if set.is_empty() {
+            "none".to_string()
+        } else {
+            set.join(" ")
+        }
+    }
+}
+
+/// The right answer to a drill, as the executor computed it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrillAnswer {
+    Value(u32),
+    Flags(DrillFlags),
+}
+
+impl DrillAnswer {
+    pub fn describe(&self) -> String {
+        match self {
+            DrillAnswer::Value(value) => format!("0x{:08X}", value),
+            DrillAnswer::Flags(flags) => flags.describe(),
+        }
+    }
+}
+
+/// One flashcard: an instruction, the registers it starts with, and a question
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drill {
+    pub mnemonic: &'static str,
+    /// The instruction in assembly
+    pub text: &'static str,
+    pub code: &'static [u8],
+    pub eax: u32,
+    pub ebx: u32,
+    pub question: DrillQuestion,
+}
+
+impl Drill {
+    /// The question in words
+    pub fn prompt(&self) -> String {
+        match self.question {
+            DrillQuestion::Register(register) => {
+                format!(
+                    "What is {} after {}? (hex with 0x, or decimal)",
+                    register.name(),
+                    self.text
+                )
+            }
+            DrillQuestion::Flags => format!(
+                "Which of ZF, SF, CF, OF are set after {}? (e.g. \"ZF CF\" or \"none\")",
+                self.text
+            ),
+        }
+    }
+
+    /// Run the instruction to find the answer
+    pub fn answer(&self) -> Result<DrillAnswer, EmulatorError> {
+        let mut memory = Memory::new(STACK_TOP as usize);
+        memory.load(CODE_ADDRESS, self.code)?;
+        let mut cpu = CpuState::new(CODE_ADDRESS, STACK_TOP);
+        cpu.regs.eax = self.eax;
+        cpu.regs.ebx = self.ebx;
+
+        Executor::new().execute_one(&mut cpu, &mut memory)?;
+
+        Ok(match self.question {
+            DrillQuestion::Register(register) => DrillAnswer::Value(cpu.get_register(register)),
+            DrillQuestion::Flags => DrillAnswer::Flags(DrillFlags {
+                zf: cpu.eflags.zf,
+                sf: cpu.eflags.sf,
+                cf: cpu.eflags.cf,
+                of: cpu.eflags.of,
+            }),
+        })
+    }
+
+    /// Grade the player's answer; `Err` if it can't be read as an answer
+    pub fn check(&self, input: &str) -> Result<bool, String> {
+        let correct = self.answer().map_err(|e| e.to_string())?;
+        let given = match self.question {
+            DrillQuestion::Register(_) => DrillAnswer::Value(parse_value(input)?),
+            DrillQuestion::Flags => DrillAnswer::Flags(DrillFlags::parse(input)?),
+        };
+        Ok(given == correct)
+    }
+}
+
+/// Parse "0x1F", "31" or "-1" (as two's complement)
+fn parse_value(text: &str) -> Result<u32, String> {
+    let text = text.trim();
+    let parsed = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => text
+            .parse::<u32>()
+            .ok()
+            .or_else(|| text.parse::<i32>().ok().map(|v| v as u32)),
+    };
+    parsed.ok_or_else(|| format!("Not a number: {}", text))
+}
+
+/// An endless shuffled supply of drills for the instructions the
+/// reference documents
+pub struct DrillDeck {
+    /// Forms with a reference entry, and whether the entry says the
+    /// instruction affects flags
+    forms: Vec<(&'static str, &'static str, &'static [u8], bool)>,
+    state: u32,
+}
+
+impl DrillDeck {
+    pub fn new(reference: &InstructionReference, seed: u32) -> Self {
+        let forms = FORMS
+            .iter()
+            .filter_map(|&(mnemonic, text, code)| {
+                let info = reference.lookup(mnemonic)?;
+                Some((mnemonic, text, code, info.flags_affected != "None"))
+            })
+            .collect();
+        // xorshift gets stuck at zero
+        let state = if seed == 0 { 0x2545_F491 } else { seed };
+        Self { forms, state }
+    }
+
+    /// Keep only the drills for these instructions
+    pub fn restricted_to(mut self, mnemonics: &[String]) -> Self {
+        self.forms.retain(|form| {
+            mnemonics
+                .iter()
+                .any(|mnemonic| mnemonic.eq_ignore_ascii_case(form.0))
+        });
+        self
+    }
+
+    /// Whether the deck has any drills to deal
+    pub fn is_empty(&self) -> bool {
+        self.forms.is_empty()
+    }
+
+    fn next_random(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// An operand: an edge case half the time, otherwise a small or any value
+    fn operand(&mut self) -> u32 {
+        let r = self.next_random();
+        match r % 4 {
+            0 | 1 => EDGE_VALUES[(r >> 8) as usize % EDGE_VALUES.len()],
+            2 => self.next_random() & 0xFF,
+            _ => self.next_random(),
+        }
+    }
+
+    /// The next drill
+    pub fn next_drill(&mut self) -> Option<Drill> {
+        if self.forms.is_empty() {
+            return None;
+        }
+        let index = self.next_random() as usize % self.forms.len();
+        let (mnemonic, text, code, affects_flags) = self.forms[index];
+
+        let question = match mnemonic {
+            "CMP" | "TEST" => DrillQuestion::Flags,
+            "PUSH" => DrillQuestion::Register(Register::Esp),
+            _ if affects_flags && self.next_random() & 1 == 0 => DrillQuestion::Flags,
+            _ => DrillQuestion::Register(Register::Eax),
+        };
+
+        Some(Drill {
+            mnemonic,
+            text,
+            code,
+            eax: self.operand(),
+            ebx: self.operand(),
+            question,
+        })
+    }
+}
+
+/// Drill results over time
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrillStats {
+    pub attempted: u32,
+    pub correct: u32,
+    pub streak: u32,
+    pub best_streak: u32,
+    /// Attempts and correct answers per mnemonic
+    pub by_mnemonic: HashMap<String, (u32, u32)>,
+}
+
+impl DrillStats {
+    pub fn record(&mut self, mnemonic: &str, correct: bool) {
+        self.attempted += 1;
+        let entry = self.by_mnemonic.entry(mnemonic.to_string()).or_default();
+        entry.0 += 1;
+        if correct {
+            self.correct += 1;
+            entry.1 += 1;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+        }
+    }
+
+    /// Correct answers, in percent
+    pub fn accuracy(&self) -> u32 {
+        (self.correct * 100)
+            .checked_div(self.attempted)
+            .unwrap_or(0)
+    }
+
+    /// The instruction answered worst so far, once it's been tried twice
+    pub fn weakest(&self) -> Option<&str> {
+        self.by_mnemonic
+            .iter()
+            .filter(|(_, (attempted, correct))| *attempted >= 2 && correct < attempted)
+            .min_by_key(|(mnemonic, (attempted, correct))| {
+                (correct * 100 / attempted, mnemonic.as_str())
+            })
+            .map(|(mnemonic, _)| mnemonic.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drill(text: &'static str, eax: u32, ebx: u32, question: DrillQuestion) -> Drill {
+        let &(mnemonic, text, code) = FORMS.iter().find(|form| form.1 == text).unwrap();
+        Drill {
+            mnemonic,
+            text,
+            code,
+            eax,
+            ebx,
+            question,
+        }
+    }
+
+    #[test]
+    fn test_answers_come_from_executor() {
+        let add = drill(
+            "ADD EAX, EBX",
+            0xFFFF_FFFF,
+            1,
+            DrillQuestion::Register(Register::Eax),
+        );
+        assert_eq!(add.answer().unwrap(), DrillAnswer::Value(0));
+        assert!(add.check("0").unwrap());
+        assert!(add.check("0x0").unwrap());
+        assert!(!add.check("0x100000000").is_ok_and(|ok| ok));
+
+        let flags = DrillQuestion::Flags;
+        let add = drill("ADD EAX, EBX", 0xFFFF_FFFF, 1, flags);
+        assert!(add.check("cf, zf").unwrap());
+        assert!(!add.check("ZF").unwrap());
+        assert!(add.check("ZF XF").is_err());
+
+        let cmp = drill("CMP EAX, EBX", 1, 2, flags);
+        assert_eq!(cmp.answer().unwrap().describe(), "SF CF");
+
+        let push = drill("PUSH EBX", 0, 0, DrillQuestion::Register(Register::Esp));
+        assert!(push.check(&format!("{}", STACK_TOP - 4)).unwrap());
+
+        let neg = drill("NEG EAX", 1, 0, DrillQuestion::Register(Register::Eax));
+        assert!(neg.check("-1").unwrap());
+        let neg = drill(
+            "NEG EAX",
+            0x8000_0000,
+            0,
+            DrillQuestion::Register(Register::Eax),
+        );
+        assert_eq!(neg.answer().unwrap(), DrillAnswer::Value(0x8000_0000));
+    }
+
+    #[test]
+    fn test_deck_covers_reference() {
+        let reference = InstructionReference::new();
+        let mut deck = DrillDeck::new(&reference, 7);
+        assert_eq!(deck.forms.len(), FORMS.len());
+
+        for _ in 0..200 {
+            let drill = deck.next_drill().unwrap();
+            assert!(drill.answer().is_ok(), "{} failed to execute", drill.text);
+            if drill.mnemonic == "MOV" || drill.mnemonic == "NOT" {
+                // The reference says these leave the flags alone
+                assert_ne!(drill.question, DrillQuestion::Flags);
+            }
+        }
+    }
+
+    #[test]
+    fn test_restricted_deck() {
+        let reference = InstructionReference::new();
+        let mut deck =
+            DrillDeck::new(&reference, 7).restricted_to(&["cmp".to_string(), "TEST".to_string()]);
+        for _ in 0..20 {
+            let drill = deck.next_drill().unwrap();
+            assert!(drill.mnemonic == "CMP" || drill.mnemonic == "TEST");
+        }
+        assert!(DrillDeck::new(&reference, 7)
+            .restricted_to(&["NOP".to_string()])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut stats = DrillStats::default();
+        stats.record("ADD", true);
+        stats.record("ADD", true);
+        stats.record("SUB", false);
+        stats.record("SUB", true);
+        assert_eq!(stats.accuracy(), 75);
+        assert_eq!(stats.streak, 1);
+        assert_eq!(stats.best_streak, 2);
+        assert_eq!(stats.weakest(), Some("SUB"));
+    }
+}
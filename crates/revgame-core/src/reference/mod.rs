@@ -1,6 +1,18 @@
 /// x86 instruction reference manual
 use std::collections::HashMap;
 
+mod ascii;
+mod concepts;
+mod converter;
+mod drill;
+mod snippet;
+
+pub use ascii::{ascii_name, ascii_note, ascii_search, ASCII_MAX};
+pub use concepts::{Concept, Glossary};
+pub use converter::Conversion;
+pub use drill::{Drill, DrillAnswer, DrillDeck, DrillFlags, DrillQuestion, DrillStats};
+pub use snippet::{Snippet, SANDBOX_PREFIX, SNIPPET_ORIGIN};
+
 /// Information about a single x86 instruction
 #[derive(Debug, Clone)]
 pub struct InstructionInfo {
@@ -281,10 +293,7 @@ impl InstructionReference {
             name: "Jump if Equal",
             description: "Jump if ZF=1 (result was zero/equal)",
             syntax: vec!["JE target"],
-            examples: vec![
-                "CMP EAX, EBX",
-                "JE equal         ; Jump if EAX == EBX",
-            ],
+            examples: vec!["CMP EAX, EBX", "JE equal         ; Jump if EAX == EBX"],
             flags_affected: "None",
             category: InstructionCategory::ControlFlow,
         });
@@ -294,10 +303,7 @@ impl InstructionReference {
             name: "Jump if Not Equal",
             description: "Jump if ZF=0 (result was not zero/equal)",
             syntax: vec!["JNE target"],
-            examples: vec![
-                "CMP EAX, 0",
-                "JNE not_zero     ; Jump if EAX != 0",
-            ],
+            examples: vec!["CMP EAX, 0", "JNE not_zero     ; Jump if EAX != 0"],
             flags_affected: "None",
             category: InstructionCategory::ControlFlow,
         });
@@ -307,10 +313,7 @@ impl InstructionReference {
             name: "Jump if Greater",
             description: "Jump if greater (signed: ZF=0 and SF=OF)",
             syntax: vec!["JG target"],
-            examples: vec![
-                "CMP EAX, EBX",
-                "JG greater       ; Jump if EAX > EBX",
-            ],
+            examples: vec!["CMP EAX, EBX", "JG greater       ; Jump if EAX > EBX"],
             flags_affected: "None",
             category: InstructionCategory::ControlFlow,
         });
@@ -320,9 +323,21 @@ impl InstructionReference {
             name: "Jump if Less",
             description: "Jump if less (signed: SF != OF)",
             syntax: vec!["JL target"],
+            examples: vec!["CMP EAX, EBX", "JL less          ; Jump if EAX < EBX"],
+            flags_affected: "None",
+            category: InstructionCategory::ControlFlow,
+        });
+
+        self.add(InstructionInfo {
+            mnemonic: "LOOP",
+            name: "Loop",
+            description:
+                "Decrement ECX and jump if it isn't zero yet (LOOPE/LOOPNE also need ZF set/clear)",
+            syntax: vec!["LOOP target"],
             examples: vec![
-                "CMP EAX, EBX",
-                "JL less          ; Jump if EAX < EBX",
+                "MOV ECX, 4",
+                "again: ...",
+                "LOOP again       ; Runs the body 4 times",
             ],
             flags_affected: "None",
             category: InstructionCategory::ControlFlow,
@@ -428,6 +443,19 @@ impl InstructionReference {
             flags_affected: "None",
             category: InstructionCategory::Special,
         });
+
+        self.add(InstructionInfo {
+            mnemonic: "RDTSC",
+            name: "Read Time-Stamp Counter",
+            description: "Load the cycles executed so far into EDX:EAX. Programs compare two readings to time a stretch of code, often to notice a debugger slowing them down",
+            syntax: vec!["RDTSC"],
+            examples: vec![
+                "RDTSC            ; EDX:EAX = cycle count",
+                "MOV EBX, EAX     ; Keep the start time",
+            ],
+            flags_affected: "None",
+            category: InstructionCategory::Special,
+        });
     }
 
     fn add(&mut self, info: InstructionInfo) {
@@ -0,0 +1,173 @@
+use crate::emulator::{assemble, AsmError};
+
+use super::InstructionInfo;
+
+/// Puzzle ids starting with this are scratch sandboxes, not real puzzles
+pub const SANDBOX_PREFIX: &str = "sandbox-";
+
+/// Where snippets are assembled and run
+pub const SNIPPET_ORIGIN: u32 = 0x1000;
+
+/// Starting values for the registers an example mentions, picked so the
+/// comparisons in the examples have something to say (7 > 3, loops of 4)
+const SEEDS: [(&str, [&str; 4], u32); 7] = [
+    ("eax", ["eax", "ax", "al", "ah"], 7),
+    ("ebx", ["ebx", "bx", "bl", "bh"], 3),
+    ("ecx", ["ecx", "cx", "cl", "ch"], 4),
+    ("edx", ["edx", "dx", "dl", "dh"], 2),
+    ("esi", ["esi", "si", "", ""], 0x2000),
+    ("edi", ["edi", "di", "", ""], 0x2010),
+    ("ebp", ["ebp", "bp", "", ""], 0x2FF0),
+];
+
+/// How many missing labels a snippet may point at the final HLT
+const MAX_FILLED_LABELS: usize = 8;
+
+/// A reference example ready to run: the example lines up to the one
+/// picked, ending in HLT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub mnemonic: &'static str,
+    /// Index of the picked example
+    pub example: usize,
+    pub source: String,
+    pub code: Vec<u8>,
+    /// Registers the snippet mentions, with their starting values
+    pub registers: Vec<(&'static str, u32)>,
+}
+
+impl Snippet {
+    /// Assemble `info`'s examples up to `example`. Labels the examples jump
+    /// to without defining land on the final HLT, and RET examples are
+    /// called from a stub so they have somewhere to return to.
+    pub fn from_example(info: &InstructionInfo, example: usize) -> Result<Self, AsmError> {
+        let lines = &info.examples[..=example.min(info.examples.len().saturating_sub(1))];
+        let mut body = lines.join("\n");
+        let returns = lines.iter().any(|line| {
+            line.split_whitespace()
+                .next()
+                .is_some_and(|word| word.eq_ignore_ascii_case("ret"))
+        });
+        if returns {
+            body = format!("call example\nhlt\nexample:\n{}", body);
+        }
+
+        let mut filled = Vec::new();
+        loop {
+            let labels: String = filled.iter().map(|label| format!("{}:\n", label)).collect();
+            let source = format!("{}\n{}hlt", body, labels);
+            match assemble(&source, SNIPPET_ORIGIN) {
+                Ok(code) => {
+                    return Ok(Self {
+                        mnemonic: info.mnemonic,
+                        example,
+                        registers: mentioned_registers(&source),
+                        source,
+                        code,
+                    })
+                }
+                Err(AsmError::UnknownLabel { label, .. }) if filled.len() < MAX_FILLED_LABELS => {
+                    filled.push(label)
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Id of the sandbox puzzle running this snippet
+    pub fn sandbox_id(&self) -> String {
+        format!(
+            "{}{}-{}",
+            SANDBOX_PREFIX,
+            self.mnemonic.to_lowercase(),
+            self.example + 1
+        )
+    }
+
+    /// A puzzle that loads the snippet with its registers seeded and never
+    /// checks a goal on its own
+    pub fn puzzle_toml(&self) -> String {
+        let quote = |text: &str| toml::Value::String(text.to_string()).to_string();
+        let bytes: Vec<String> = self.code.iter().map(|b| format!("{:02X}", b)).collect();
+        let registers: String = self
+            .registers
+            .iter()
+            .map(|(name, value)| format!("{} = 0x{:X}\n", name, value))
+            .collect();
+        format!(
+            "[metadata]\nid = {id}\ntitle = {title}\ndifficulty = 0\ncategory = \"sandbox\"\n\n\
+             [description]\nbrief = {brief}\ndetailed = {detailed}\n\n\
+             [setup]\nmemory_size = 16384\ncode_start = 0x{origin:X}\ndata_start = 0x2000\nstack_start = 0x3000\n\n\
+             [setup.registers]\n{registers}esp = 0x3000\n\n\
+             [setup.code]\nbytes = \"{bytes}\"\nentry_point = 0\n\n\
+             [validation]\ntype = \"normal_halt\"\ntrigger = \"command\"\n",
+            id = quote(&self.sandbox_id()),
+            title = quote(&format!("Try {}", self.mnemonic)),
+            brief = quote(&format!("Sandbox for the {} reference example", self.mnemonic)),
+            detailed = quote(&format!("Step through it with F10 and watch the registers:\n\n{}", self.source)),
+            origin = SNIPPET_ORIGIN,
+            registers = registers,
+            bytes = bytes.join(" "),
+        )
+    }
+}
+
+/// Seeded registers `source` names, in any of their sizes
+fn mentioned_registers(source: &str) -> Vec<(&'static str, u32)> {
+    let lower = source.to_lowercase();
+    let words: Vec<&str> = lower
+        .lines()
+        .map(|line| line.split(';').next().unwrap_or_default())
+        .flat_map(|line| line.split(|c: char| !c.is_ascii_alphanumeric()))
+        .collect();
+    SEEDS
+        .iter()
+        .filter(|(_, names, _)| {
+            names
+                .iter()
+                .any(|name| !name.is_empty() && words.contains(name))
+        })
+        .map(|&(reg, _, value)| (reg, value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::load_puzzle;
+    use crate::reference::InstructionReference;
+
+    #[test]
+    fn test_every_example_assembles_and_loads() {
+        let reference = InstructionReference::new();
+        for mnemonic in reference.all_mnemonics() {
+            let info = reference.lookup(&mnemonic).unwrap();
+            for example in 0..info.examples.len() {
+                let snippet = Snippet::from_example(info, example)
+                    .unwrap_or_else(|e| panic!("{} example {}: {}", mnemonic, example + 1, e));
+                assert_eq!(
+                    snippet.code.last(),
+                    Some(&0xF4),
+                    "{} should end in HLT",
+                    mnemonic
+                );
+                let puzzle = load_puzzle(&snippet.puzzle_toml()).unwrap();
+                assert!(puzzle.metadata.id.starts_with(SANDBOX_PREFIX));
+            }
+        }
+    }
+
+    #[test]
+    fn test_snippet_fills_labels_and_seeds_registers() {
+        let reference = InstructionReference::new();
+        let je = Snippet::from_example(reference.lookup("JE").unwrap(), 1).unwrap();
+        // cmp eax, ebx; je equal; equal: hlt
+        assert_eq!(je.code, [0x39, 0xD8, 0x74, 0x00, 0xF4]);
+        assert_eq!(je.registers, [("eax", 7), ("ebx", 3)]);
+        assert_eq!(je.sandbox_id(), "sandbox-je-2");
+
+        let ret = Snippet::from_example(reference.lookup("RET").unwrap(), 0).unwrap();
+        assert!(ret.source.starts_with("call example"));
+        assert!(ret.registers.is_empty());
+    }
+}
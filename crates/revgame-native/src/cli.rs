@@ -0,0 +1,331 @@
+//! Command-line options, and the headless runs (`--check`, `--replay`,
+//! `--export`) that finish without opening the terminal UI
+
+use std::path::{Path, PathBuf};
+
+use clap::{builder::PossibleValuesParser, ArgGroup, Parser};
+use revgame_core::game::{Campaign, Replay, SaveManager, CAMPAIGN_FILE};
+use revgame_ui::{app::Screen, export, App, Theme};
+
+/// Parsed command line
+#[derive(Debug, Clone, PartialEq, Eq, Parser)]
+#[command(
+    name = "revgame",
+    version,
+    about = "Opens PUZZLE straight away if given, otherwise the main menu.",
+    after_help = "Exit status is 0 on success, 1 when a check or replay fails, and 2 when the\n\
+                  arguments are wrong or a file can't be read.",
+    group = ArgGroup::new("target").args(["puzzle", "demo"]),
+    group = ArgGroup::new("action").args(["check", "replay", "export"]).multiple(true)
+)]
+pub struct Cli {
+    /// Puzzle file to open
+    #[arg(value_name = "PUZZLE.toml")]
+    pub puzzle: Option<PathBuf>,
+
+    /// Puzzle pack to browse and check
+    #[arg(long, value_name = "DIR", default_value = "puzzles")]
+    pub pack: PathBuf,
+
+    /// Keep saves apart under a named profile
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Color theme
+    #[arg(long, value_name = "NAME", value_parser = PossibleValuesParser::new(Theme::NAMES))]
+    pub theme: Option<String>,
+
+    /// Keep saves next to the executable
+    #[arg(long)]
+    pub portable: bool,
+
+    /// Open PUZZLE as a read-only demo (with --replay to play one)
+    #[arg(long, value_name = "PUZZLE", conflicts_with_all = ["check", "export", "headless"])]
+    pub demo: Option<PathBuf>,
+
+    /// Play a replay on PUZZLE without the UI and report the result
+    #[arg(long, value_name = "FILE", requires = "target")]
+    pub replay: Option<PathBuf>,
+
+    /// Check that PUZZLE, or every puzzle in the pack, loads
+    #[arg(long)]
+    pub check: bool,
+
+    /// Write PUZZLE's debugger view to FILE (.html or ANSI text)
+    #[arg(long, value_name = "FILE", requires = "puzzle")]
+    pub export: Option<PathBuf>,
+
+    /// Never open the terminal UI
+    #[arg(long, requires = "action")]
+    pub headless: bool,
+}
+
+impl Cli {
+    /// Whether the run ends without opening the terminal UI. A replay
+    /// without `--demo` is played headless.
+    pub fn is_headless(&self) -> bool {
+        self.headless
+            || self.check
+            || self.export.is_some()
+            || (self.replay.is_some() && self.demo.is_none())
+    }
+}
+
+/// Every puzzle file under `dir`, in name order, skipping the campaign file
+fn puzzle_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_dir() {
+            files.extend(puzzle_files(&path)?);
+        } else if path.extension().is_some_and(|ext| ext == "toml")
+            && !path.ends_with(CAMPAIGN_FILE)
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// A fresh app saving through `saves`, with the seed and clock pinned so
+/// headless runs come out the same every time
+fn headless_app(cli: &Cli, saves: &dyn Fn() -> SaveManager) -> App {
+    let mut app = App::with_save_manager(saves());
+    app.pin(1, 0);
+    if let Some(ref theme) = cli.theme {
+        app.settings.theme = theme.clone();
+    }
+    app.apply_settings();
+    app
+}
+
+/// Run every command of `replay`, stopping at the first one that fails.
+/// Gives whether the puzzle ended solved.
+fn play_replay(app: &mut App, replay: &Replay) -> Result<bool, String> {
+    for event in &replay.events {
+        app.process_command(&event.command);
+        if let Some(ref message) = app.message {
+            if message.is_error {
+                return Err(format!("`{}` failed: {}", event.command, message.text));
+            }
+        }
+    }
+    Ok(matches!(app.screen, Screen::PuzzleComplete { .. }))
+}
+
+/// Open a puzzle headless, playing the replay over it if there is one
+fn open_puzzle(
+    cli: &Cli,
+    saves: &dyn Fn() -> SaveManager,
+    path: &Path,
+    replay: Option<&Replay>,
+) -> Result<(App, Option<bool>), String> {
+    let mut app = headless_app(cli, saves);
+    app.load_puzzle_file(path)?;
+    app.begin_puzzle();
+    let solved = replay
+        .map(|replay| play_replay(&mut app, replay))
+        .transpose()?;
+    Ok((app, solved))
+}
+
+pub fn load_replay(path: &Path) -> Result<Replay, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Replay::parse(&source)
+}
+
+/// Check that each puzzle loads (and with a replay, that it solves the
+/// puzzle), and that the pack's campaign file parses. Gives whether
+/// everything passed.
+fn check(
+    cli: &Cli,
+    saves: &dyn Fn() -> SaveManager,
+    replay: Option<&Replay>,
+) -> Result<bool, String> {
+    let files = match cli.puzzle {
+        Some(ref puzzle) => vec![puzzle.clone()],
+        None => puzzle_files(&cli.pack)?,
+    };
+    if files.is_empty() {
+        return Err(format!("No puzzles in {}", cli.pack.display()));
+    }
+
+    let mut failed = 0;
+    for path in &files {
+        match open_puzzle(cli, saves, path, replay) {
+            Ok((_, Some(false))) => {
+                failed += 1;
+                println!(
+                    "FAIL {}: not solved at the end of the replay",
+                    path.display()
+                );
+            }
+            Ok((app, _)) => {
+                let id = app
+                    .puzzle
+                    .as_ref()
+                    .map(|p| p.metadata.id.as_str())
+                    .unwrap_or_default();
+                println!("ok   {} ({})", path.display(), id);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("FAIL {}: {}", path.display(), e);
+            }
+        }
+    }
+    if cli.puzzle.is_none() {
+        if let Err(e) = Campaign::load(&cli.pack) {
+            failed += 1;
+            println!("FAIL {}: {}", cli.pack.join(CAMPAIGN_FILE).display(), e);
+        }
+    }
+
+    println!("{} checked, {} failed", files.len(), failed);
+    Ok(failed == 0)
+}
+
+/// Carry out a headless run, each puzzle saving through a manager from
+/// `saves`. Gives whether it passed; an error means it couldn't run at all.
+pub fn run_headless(cli: &Cli, saves: &dyn Fn() -> SaveManager) -> Result<bool, String> {
+    let replay = cli.replay.as_deref().map(load_replay).transpose()?;
+    if cli.check {
+        if !check(cli, saves, replay.as_ref())? {
+            return Ok(false);
+        }
+        if cli.export.is_none() {
+            return Ok(true);
+        }
+    }
+
+    // --replay and/or --export on one puzzle
+    let Some(ref path) = cli.puzzle else {
+        return Ok(true);
+    };
+    let (app, solved) = open_puzzle(cli, saves, path, replay.as_ref())?;
+    if let Some(ref out) = cli.export {
+        let (width, height) = export::DEFAULT_EXPORT_SIZE;
+        export::export_view(&app, out, width, height)?;
+        println!("Exported {}", out.display());
+    }
+    match solved {
+        Some(true) if !cli.check => {
+            let debugger = app.debugger.as_ref();
+            println!(
+                "Solved {} in {} instructions and {} patches",
+                app.puzzle
+                    .as_ref()
+                    .map(|p| p.metadata.id.as_str())
+                    .unwrap_or_default(),
+                debugger.map(|d| d.total_instructions).unwrap_or_default(),
+                debugger.map(|d| d.undo_count()).unwrap_or_default(),
+            );
+            Ok(true)
+        }
+        Some(false) => {
+            println!("Not solved at the end of the replay");
+            Ok(false)
+        }
+        _ => Ok(true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::error::ErrorKind;
+    use revgame_core::game::MemoryBackend;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("revgame").chain(args.iter().copied()))
+    }
+
+    fn parse_error(args: &[&str]) -> String {
+        parse(args).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let cli = parse(&[]).unwrap();
+        assert_eq!(cli.pack, PathBuf::from("puzzles"));
+        assert_eq!((cli.puzzle, cli.profile, cli.theme), (None, None, None));
+        assert!(!cli.portable);
+
+        let cli = parse(&[
+            "--pack=packs/intro",
+            "--profile",
+            "class-a",
+            "--theme",
+            "light",
+            "intro/01.toml",
+        ])
+        .unwrap();
+        assert_eq!(cli.pack, PathBuf::from("packs/intro"));
+        assert_eq!(cli.profile.as_deref(), Some("class-a"));
+        assert_eq!(cli.theme.as_deref(), Some("light"));
+        assert_eq!(cli.puzzle, Some(PathBuf::from("intro/01.toml")));
+        assert!(!cli.is_headless());
+
+        let cli = parse(&["--replay", "solve.toml", "p.toml"]).unwrap();
+        assert!(cli.is_headless());
+        let cli = parse(&["--demo", "p.toml", "--replay", "solve.toml"]).unwrap();
+        assert!(!cli.is_headless());
+        assert!(parse(&["--check"]).unwrap().is_headless());
+        assert_eq!(
+            parse(&["--", "--odd.toml"]).unwrap().puzzle,
+            Some(PathBuf::from("--odd.toml"))
+        );
+        assert!(parse(&["--bogus", "--help"]).is_err());
+        assert_eq!(
+            parse(&["--help", "--headless"]).unwrap_err().kind(),
+            ErrorKind::DisplayHelp
+        );
+        assert_eq!(
+            parse(&["--version"]).unwrap_err().kind(),
+            ErrorKind::DisplayVersion
+        );
+    }
+
+    #[test]
+    fn test_check_leaves_saved_ghost_alone() {
+        let store = MemoryBackend::new();
+        let saves = || SaveManager::with_backend(Box::new(store.clone()));
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let solve = root.join("crates/revgame-ui/tests/replays/basic-001.toml");
+        let mut ghost = load_replay(&solve).unwrap();
+        ghost.events.last_mut().unwrap().at_ms = 60_000;
+        saves().save_replay(&ghost).unwrap();
+
+        let puzzle = root.join("puzzles/01-basics/001-first-patch.toml");
+        let args = [
+            "revgame",
+            "--check",
+            "--replay",
+            &solve.display().to_string(),
+            &puzzle.display().to_string(),
+        ];
+        assert_eq!(
+            run_headless(&Cli::try_parse_from(args).unwrap(), &saves),
+            Ok(true)
+        );
+        assert_eq!(saves().load_replay("basic-001").unwrap(), Some(ghost));
+    }
+
+    #[test]
+    fn test_rejects_meaningless_combinations() {
+        assert!(parse_error(&["--theme", "neon"]).contains("dark, light"));
+        assert!(parse_error(&["--profile"]).contains("value is required"));
+        assert!(parse(&["--check=yes"]).is_err());
+        assert!(parse(&["a.toml", "b.toml"]).is_err());
+        assert!(parse(&["--headless"]).is_err());
+        assert!(parse(&["--replay", "solve.toml"]).is_err());
+        assert!(parse(&["--export", "out.html"]).is_err());
+        assert!(parse(&["--demo", "p.toml", "p.toml"]).is_err());
+        assert!(parse(&["--demo", "p.toml", "--check"]).is_err());
+        assert!(parse(&["--headless", "--export", "out.html", "p.toml"]).is_ok());
+    }
+}
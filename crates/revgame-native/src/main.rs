@@ -1,27 +1,29 @@
-use std::io;
-use std::time::Duration;
+mod cli;
+
+use std::io::{self, Write};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    cursor::Show,
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    style::{Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
-    Frame, Terminal,
-};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
 
+use clap::Parser;
+use cli::Cli;
+use revgame_core::game::{SaveManager, SaveOptions};
 use revgame_ui::{
     app::{App, FocusedPanel, Screen},
-    screens::{
-        render_debugger, render_achievements, render_reference, render_search_dialog,
-        render_bookmarks_dialog, render_puzzle_select, SearchMode,
-    },
+    screens::{render_screen, timeline_area, MainMenuItem, ReferenceViewMode, SearchMode},
     TutorialTrigger,
 };
 
@@ -68,6 +70,11 @@ esp = 0x3000
 bytes = "3D 37 13 00 00 75 07 B8 01 00 00 00 EB 05 B8 00 00 00 00 F4"
 entry_point = 0
 
+[annotations]
+"0x1000" = "compare input with 0x1337"
+"0x1007" = "success path"
+"0x100E" = "failure path"
+
 [validation]
 type = "register_value"
 register = "eax"
@@ -79,26 +86,171 @@ level2 = "The NOP instruction does nothing and is 1 byte (0x90)"
 level3 = "Try replacing the JNE (75 07) with two NOPs (90 90) to skip the branch"
 "#;
 
+/// Puzzle pack given with `--pack`
+static PACK_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn pack_dir() -> &'static Path {
+    PACK_DIR
+        .get()
+        .map(PathBuf::as_path)
+        .unwrap_or(Path::new("puzzles"))
+}
+
+/// Message and backtrace of a panic on the main thread, kept by the panic
+/// hook for the crash report
+static PANIC: Mutex<Option<(String, String)>> = Mutex::new(None);
+
+/// Put the terminal back the way we found it, whatever state it's in
+fn restore_terminal() {
+    disable_raw_mode().ok();
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        Show
+    )
+    .ok();
+}
+
+/// On a panic, restore the terminal first so the message is readable, and
+/// keep the details for the crash report
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() != Some("main") {
+            default_hook(info);
+            return;
+        }
+        restore_terminal();
+        default_hook(info);
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+        *PANIC.lock().unwrap_or_else(|e| e.into_inner()) = Some((info.to_string(), backtrace));
+    }));
+}
+
+/// Write the crash report and tell the player what to do next
+fn report_crash(app: &App, save_options: &SaveOptions) {
+    let (panic, backtrace) = PANIC
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+        .unwrap_or_else(|| ("panic (no details)".to_string(), String::new()));
+    let report = revgame_ui::crash::crash_report(app, &panic, &backtrace);
+
+    eprintln!("\nRevGame crashed.");
+    match save_options
+        .save_directory()
+        .and_then(|dir| revgame_ui::crash::write_crash_report(&dir, &report))
+    {
+        Ok(path) => eprintln!("A crash report was written to {}", path.display()),
+        Err(e) => eprintln!(
+            "The crash report couldn't be saved ({}), so here it is:\n\n{}",
+            e, report
+        ),
+    }
+    eprintln!("Your progress up to the last save is safe; start RevGame again to continue.");
+    eprintln!("If the terminal still looks wrong, type `reset` and press Enter.");
+    eprintln!("Please attach the crash report when reporting the bug.");
+}
+
 fn main() -> Result<()> {
+    // Prints help or the version and exits, or exits with 2 on bad arguments
+    let cli = Cli::parse();
+    let save_options = SaveOptions {
+        portable: cli.portable,
+        profile: cli.profile.clone(),
+    };
+    if cli.is_headless() {
+        // Saves stay in memory, so checking a pack leaves the player's
+        // progress as it was
+        let code = match cli::run_headless(&cli, &SaveManager::in_memory) {
+            Ok(passed) => i32::from(!passed),
+            Err(e) => {
+                eprintln!("revgame: {}", e);
+                2
+            }
+        };
+        std::process::exit(code);
+    }
+    PACK_DIR.set(cli.pack.clone()).ok();
+    revgame_ui::console::init(log::LevelFilter::Debug).map_err(anyhow::Error::msg)?;
+    install_panic_hook();
+
+    // Create app, before the terminal so a bad file is reported plainly
+    let saves = match SaveManager::open(&save_options) {
+        Ok(saves) => saves,
+        Err(e) => {
+            eprintln!("revgame: {}", e);
+            std::process::exit(2);
+        }
+    };
+    let mut app = App::with_save_manager(saves);
+    app.ascii_terminal =
+        revgame_ui::glyphs::terminal_lacks_unicode(|name| std::env::var(name).ok(), cfg!(windows));
+    // Missing or unreadable settings just mean defaults
+    if app.load_settings().is_err() {
+        app.apply_settings();
+    }
+    if let Some(ref theme) = cli.theme {
+        app.settings.theme = theme.clone();
+        app.apply_settings();
+    }
+    if let Some(ref path) = cli.demo {
+        let puzzle = std::fs::read_to_string(path)?;
+        let replay = cli
+            .replay
+            .as_deref()
+            .map(cli::load_replay)
+            .transpose()
+            .map_err(anyhow::Error::msg)?;
+        app.start_demo(&puzzle, replay)
+            .map_err(anyhow::Error::msg)?;
+    } else {
+        let first_run = app.load_session().is_err() && app.settings.profile_name.is_empty();
+        if app.puzzle_select_state.load_puzzles(pack_dir()).is_ok() {
+            app.apply_campaign_locks();
+        }
+        if let Some(ref path) = cli.puzzle {
+            app.load_puzzle_file(path).map_err(anyhow::Error::msg)?;
+        } else if first_run {
+            // No session and no profile means a first run: ask a few questions
+            app.start_onboarding();
+        }
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app
-    let mut app = App::new();
-
     // Run the app
-    let result = run_app(&mut terminal, &mut app);
+    let result =
+        match std::panic::catch_unwind(AssertUnwindSafe(|| run_app(&mut terminal, &mut app))) {
+            Ok(result) => result,
+            Err(_) => {
+                // The hook already restored the terminal; the app may be half
+                // way through an update, so it isn't saved
+                report_crash(&app, &save_options);
+                std::process::exit(101);
+            }
+        };
+    app.save_session().ok();
 
     // Restore terminal
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
 
@@ -109,147 +261,127 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_app<B: ratatui::backend::Backend>(
-    terminal: &mut Terminal<B>,
-    app: &mut App,
-) -> Result<()> {
+fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+    let mut last_frame = Instant::now();
     loop {
-        terminal.draw(|f| ui(f, app))?;
+        terminal.draw(|f| render_screen(f, app))?;
 
         if app.should_quit {
             return Ok(());
         }
 
-        // Poll for events with timeout
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                handle_key(app, key.code, key.modifiers);
+        // Poll for events, redrawing more often while an effect or a search is running
+        let demo_playing = app.demo.as_ref().is_some_and(|demo| demo.is_playing());
+        let timeout =
+            if app.animations.is_active() || app.search_state.is_searching() || demo_playing {
+                Duration::from_millis(33)
+            } else {
+                Duration::from_millis(100)
+            };
+        if event::poll(timeout)? {
+            match event::read()? {
+                Event::Key(key) => {
+                    // Touching the keyboard during a demo takes it over
+                    app.take_over_demo();
+                    handle_key(app, key.code, key.modifiers);
+                }
+                Event::Paste(text) => app.paste(&text),
+                Event::Mouse(mouse) => {
+                    let size = terminal.size()?;
+                    handle_mouse(app, mouse, Rect::new(0, 0, size.width, size.height));
+                }
+                _ => {}
             }
         }
 
-        // Tick animations
-        app.rewind_effect.tick();
-    }
-}
+        if let Some(text) = app.clipboard.take() {
+            copy_to_terminal_clipboard(&text)?;
+        }
 
-fn ui(frame: &mut Frame, app: &App) {
-    match app.screen {
-        Screen::MainMenu => render_main_menu(frame, app),
-        Screen::PuzzleSelect => render_puzzle_select(frame, app, &app.puzzle_select_state, &app.theme),
-        Screen::Debugger => render_debugger(frame, app),
-        Screen::Achievements => render_achievements(frame, app, &app.theme),
-        Screen::Reference => render_reference(frame, app, &app.reference_state, &app.theme),
-        Screen::PuzzleComplete { ref message } => render_puzzle_complete(frame, app, message),
-        _ => render_main_menu(frame, app), // Fallback
-    }
+        // Advance animations by real elapsed time
+        app.animations.tick(Instant::now());
 
-    // Render search dialog overlay if open
-    if app.search_dialog_open {
-        render_search_dialog(frame, &app.search_state, &app.theme);
-    }
+        // Scan the next stretch of memory for a running search
+        app.advance_search();
 
-    // Render bookmarks dialog overlay if open
-    if app.bookmarks_dialog_open {
-        if let Some(ref dbg) = app.debugger {
-            let bookmarks = dbg.bookmarks.list();
-            render_bookmarks_dialog(frame, &bookmarks, &app.bookmarks_view_state, &app.theme);
-        }
+        // Play the demo replay on by real elapsed time
+        let now = Instant::now();
+        app.advance_demo(now.duration_since(last_frame).as_millis() as u64);
+        last_frame = now;
+
+        // Pick up answers from the leaderboard server
+        #[cfg(feature = "online")]
+        revgame_ui::online::poll(app);
     }
 }
 
-fn render_main_menu(frame: &mut Frame, app: &App) {
-    let area = frame.area();
-
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(8),  // Title
-            Constraint::Min(10),    // Menu
-            Constraint::Length(3), // Footer
-        ])
-        .split(area);
-
-    // Title
-    let title = r#"
-  ██████╗ ███████╗██╗   ██╗ ██████╗  █████╗ ███╗   ███╗███████╗
-  ██╔══██╗██╔════╝██║   ██║██╔════╝ ██╔══██╗████╗ ████║██╔════╝
-  ██████╔╝█████╗  ██║   ██║██║  ███╗███████║██╔████╔██║█████╗
-  ██╔══██╗██╔══╝  ╚██╗ ██╔╝██║   ██║██╔══██║██║╚██╔╝██║██╔══╝
-  ██║  ██║███████╗ ╚████╔╝ ╚██████╔╝██║  ██║██║ ╚═╝ ██║███████╗
-  ╚═╝  ╚═╝╚══════╝  ╚═══╝   ╚═════╝ ╚═╝  ╚═╝╚═╝     ╚═╝╚══════╝
-"#;
-
-    let title_para = Paragraph::new(title)
-        .style(Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD))
-        .alignment(ratatui::layout::Alignment::Center);
-
-    frame.render_widget(title_para, chunks[0]);
-
-    // Menu items
-    let menu_items = vec![
-        ListItem::new("  [1] Start Tutorial (with walkthrough)"),
-        ListItem::new("  [2] Quick Start (skip tutorial)"),
-        ListItem::new("  [3] Puzzle Select"),
-        ListItem::new("  [A] Achievements"),
-        ListItem::new("  [R] x86 Reference Manual"),
-        ListItem::new("  [Q] Quit"),
-    ];
-
-    let menu = List::new(menu_items)
-        .block(
-            Block::default()
-                .title(" Menu ")
-                .borders(Borders::ALL)
-                .border_style(app.theme.border_style()),
+/// Clicking or dragging on the execution timeline scrubs to that point
+fn handle_mouse(app: &mut App, mouse: MouseEvent, area: Rect) {
+    if app.screen != Screen::Debugger
+        || !matches!(
+            mouse.kind,
+            MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left)
         )
-        .style(app.theme.normal())
-        .highlight_style(app.theme.selected());
-
-    frame.render_widget(menu, chunks[1]);
-
-    // Footer
-    let footer = Paragraph::new("Learn reverse engineering through interactive puzzles")
-        .style(app.theme.muted_style())
-        .alignment(ratatui::layout::Alignment::Center);
+    {
+        return;
+    }
+    if let Some(timeline) = timeline_area(app, area) {
+        if mouse.row == timeline.y && (timeline.x..timeline.right()).contains(&mouse.column) {
+            app.scrub_timeline_to(mouse.column - timeline.x, timeline.width);
+        }
+    }
+}
 
-    frame.render_widget(footer, chunks[2]);
+/// Put text on the system clipboard with an OSC 52 escape, which the
+/// terminal forwards to the clipboard (also over SSH)
+fn copy_to_terminal_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    stdout.flush()
 }
 
-fn render_puzzle_complete(frame: &mut Frame, app: &App, message: &str) {
-    let area = frame.area();
-
-    let block = Block::default()
-        .title(" Puzzle Complete! ")
-        .borders(Borders::ALL)
-        .border_style(app.theme.success_style());
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    let text = vec![
-        Line::from(""),
-        Line::from(Span::styled(message, app.theme.success_style())),
-        Line::from(""),
-        Line::from(Span::styled(
-            format!("Hints used: {}", app.game_state.hints_used),
-            app.theme.normal(),
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Press [Enter] to continue or [Esc] for menu",
-            app.theme.muted_style(),
-        )),
-    ];
-
-    let para = Paragraph::new(text).alignment(ratatui::layout::Alignment::Center);
-
-    frame.render_widget(para, inner);
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits =
+            chunk.iter().fold(0u32, |bits, &b| (bits << 8) | b as u32) << (8 * (3 - chunk.len()));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(bits >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
 }
 
 fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    // F12 opens the debug console from anywhere; while open it takes every key
+    if code == KeyCode::F(12) {
+        app.toggle_console();
+        return;
+    }
+    if let Some(ref mut console) = app.console {
+        let count = revgame_ui::console::CONSOLE.lines(console.level).len();
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                console.scroll = (console.scroll + 1).min(count.saturating_sub(1))
+            }
+            KeyCode::Down | KeyCode::Char('j') => console.scroll = console.scroll.saturating_sub(1),
+            KeyCode::Left => console.cycle_level(false),
+            KeyCode::Right => console.cycle_level(true),
+            KeyCode::Char('c') | KeyCode::Char('C') => revgame_ui::console::CONSOLE.clear(),
+            KeyCode::Esc => app.console = None,
+            _ => {}
+        }
+        return;
+    }
+
     // If search dialog is open, handle search keys first
     if app.search_dialog_open {
-        handle_search_key(app, code);
+        handle_search_key(app, code, modifiers);
         return;
     }
 
@@ -259,20 +391,328 @@ fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
         return;
     }
 
+    // If go-to-address dialog is open, handle its keys first
+    if app.goto_dialog_open {
+        handle_goto_key(app, code);
+        return;
+    }
+
+    // Likewise for the program input dialog
+    if app.input_dialog_open {
+        handle_input_key(app, code);
+        return;
+    }
+
+    // And for the step prediction dialog
+    if app.predict_dialog_open {
+        handle_predict_key(app, code);
+        return;
+    }
+
+    // Esc, Enter or x closes the encoding inspector; g reads up on the instruction
+    if app.encoding.is_some() {
+        if code == KeyCode::Char('g') && app.linked_concept().is_some() {
+            app.show_linked_concept();
+            app.encoding = None;
+        } else if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('x')) {
+            app.encoding = None;
+        }
+        return;
+    }
+
+    // The converter takes typed input until closed
+    if app.converter_open {
+        match code {
+            KeyCode::Char(c) => app.converter_state.input.push(c),
+            KeyCode::Backspace => {
+                app.converter_state.input.pop();
+            }
+            KeyCode::Esc | KeyCode::Enter => app.converter_open = false,
+            _ => {}
+        }
+        return;
+    }
+
+    // Confirm or back out of revealing the solution
+    if app.reveal_confirm_open {
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => app.reveal_solution(),
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                app.reveal_confirm_open = false
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    // Confirm or back out of discarding patches
+    if app.hard_reset_confirm_open {
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                app.hard_reset();
+                app.trigger_tutorial(TutorialTrigger::Reset);
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                app.hard_reset_confirm_open = false
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    // Up/Down pick a constant; Enter jumps to it, Esc or K closes
+    if app.constants.is_some() {
+        let count = app.magic_constants().len();
+        if let Some(ref mut constants) = app.constants {
+            match code {
+                KeyCode::Up | KeyCode::Char('k') => constants.select_prev(count),
+                KeyCode::Down | KeyCode::Char('j') => constants.select_next(count),
+                KeyCode::Enter => app.goto_selected_constant(),
+                KeyCode::Esc | KeyCode::Char('K') => app.constants = None,
+                _ => {}
+            }
+        }
+        return;
+    }
+
+    // Up/Down scroll the plugin panel; Esc closes it
+    if app.plugin_panel.is_some() {
+        let count = revgame_ui::plugin::panel_lines(app).map_or(0, |(_, lines)| lines.len());
+        let Some(ref mut panel) = app.plugin_panel else {
+            return;
+        };
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => panel.scroll = panel.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                panel.scroll = (panel.scroll + 1).min(count.saturating_sub(1))
+            }
+            KeyCode::Esc | KeyCode::Enter => app.plugin_panel = None,
+            _ => {}
+        }
+        return;
+    }
+
+    // The ASCII table takes typed search input; arrows move, Enter finds the next match
+    if let Some(ref mut table) = app.ascii_table {
+        match code {
+            KeyCode::Up => table.move_selection(-1),
+            KeyCode::Down => table.move_selection(1),
+            KeyCode::Left => table.move_selection(-16),
+            KeyCode::Right => table.move_selection(16),
+            KeyCode::Enter => table.next_match(),
+            KeyCode::Backspace => table.type_query(None),
+            KeyCode::Char(c) => table.type_query(Some(c)),
+            KeyCode::Esc => app.ascii_table = None,
+            _ => {}
+        }
+        return;
+    }
+
+    // Enter shows the end of a followed pointer; Esc or f closes
+    if app.follow.is_some() {
+        match code {
+            KeyCode::Enter => app.goto_followed(),
+            KeyCode::Esc | KeyCode::Char('f') => app.follow = None,
+            _ => {}
+        }
+        return;
+    }
+
+    // Up/Down pick a hit of `find`; Enter jumps to it, Esc closes
+    if let Some(ref mut finder) = app.finder {
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => finder.select_prev(),
+            KeyCode::Down | KeyCode::Char('j') => finder.select_next(),
+            KeyCode::Enter => app.goto_selected_hit(),
+            KeyCode::Esc => app.finder = None,
+            _ => {}
+        }
+        return;
+    }
+
+    // Up/Down pick a region in the entropy overlay; Esc, Enter or H closes it
+    if app.entropy.is_some() {
+        let count = app.entropy_regions().len();
+        if let Some(ref mut entropy) = app.entropy {
+            match code {
+                KeyCode::Up | KeyCode::Char('k') => entropy.select_prev(count),
+                KeyCode::Down | KeyCode::Char('j') => entropy.select_next(count),
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('H') => app.entropy = None,
+                _ => {}
+            }
+        }
+        return;
+    }
+
+    // The decoder takes a typed transformation; arrows move and size the
+    // range, Enter writes the result back as a patch
+    if let Some(ref mut decoder) = app.decoder {
+        match code {
+            KeyCode::Char(c) => decoder.input.push(c),
+            KeyCode::Backspace => {
+                decoder.input.pop();
+            }
+            KeyCode::Left => decoder.address = decoder.address.wrapping_sub(1),
+            KeyCode::Right => decoder.address = decoder.address.wrapping_add(1),
+            KeyCode::Up => decoder.resize(1),
+            KeyCode::Down => decoder.resize(-1),
+            KeyCode::PageUp => decoder.resize(16),
+            KeyCode::PageDown => decoder.resize(-16),
+            KeyCode::Enter => {
+                if let Err(e) = app.apply_decoder() {
+                    app.message = Some(revgame_ui::app::Message {
+                        text: e,
+                        is_error: true,
+                    });
+                }
+            }
+            KeyCode::Esc => app.decoder = None,
+            _ => {}
+        }
+        return;
+    }
+
+    // The endianness visualizer slides a byte at a time; Esc, Enter or e closes it
+    if let Some(ref mut endian) = app.endian {
+        match code {
+            KeyCode::Left | KeyCode::Char('h') => app.move_endian_view(-1),
+            KeyCode::Right | KeyCode::Char('l') => app.move_endian_view(1),
+            KeyCode::Char('t') => endian.interpretation = endian.interpretation.next(),
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('e') => app.endian = None,
+            _ => {}
+        }
+        return;
+    }
+
+    // Esc, Enter or D closes the run diff
+    if app.trace_diff.is_some() {
+        if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('D')) {
+            app.trace_diff = None;
+        }
+        return;
+    }
+
+    // Esc or Enter closes the snapshot diff
+    if app.state_diff.is_some() {
+        if matches!(code, KeyCode::Esc | KeyCode::Enter) {
+            app.state_diff = None;
+        }
+        return;
+    }
+
+    // Esc, Enter or o closes the objectives; b shows the briefing again
+    if app.objectives_open {
+        if code == KeyCode::Char('b') {
+            app.show_briefing();
+        } else if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('o')) {
+            app.objectives_open = false;
+        }
+        return;
+    }
+
+    // Esc, Enter or E closes the run explanation
+    if app.explain_open {
+        if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('E')) {
+            app.explain_open = false;
+        }
+        return;
+    }
+
     match app.screen {
+        Screen::Onboarding => handle_onboarding_key(app, code),
         Screen::MainMenu => handle_main_menu_key(app, code),
+        Screen::Drills => handle_drills_key(app, code),
         Screen::PuzzleSelect => handle_puzzle_select_key(app, code),
+        Screen::Briefing => {
+            if matches!(code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char(' ')) {
+                app.begin_puzzle();
+            }
+        }
         Screen::Debugger => handle_debugger_key(app, code, modifiers),
         Screen::Achievements => handle_achievements_key(app, code),
+        Screen::SkillTree => handle_skill_tree_key(app, code),
         Screen::Reference => handle_reference_key(app, code),
-        Screen::PuzzleComplete { .. } => handle_complete_key(app, code),
+        Screen::Settings => handle_settings_key(app, code),
+        Screen::PuzzleComplete { .. } | Screen::SolutionRevealed { .. } => {
+            handle_complete_key(app, code)
+        }
+        _ => {}
+    }
+}
+
+fn handle_onboarding_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Up => {
+            app.onboarding_state.navigate_up();
+            app.preview_onboarding_theme();
+        }
+        KeyCode::Down => {
+            app.onboarding_state.navigate_down();
+            app.preview_onboarding_theme();
+        }
+        KeyCode::Char(c) => app.onboarding_state.push_char(c),
+        KeyCode::Backspace => app.onboarding_state.pop_char(),
+        KeyCode::Enter if !app.onboarding_state.advance() => finish_onboarding(app),
+        // Esc on the first question skips the rest with defaults
+        KeyCode::Esc if !app.onboarding_state.back() => finish_onboarding(app),
         _ => {}
     }
 }
 
+fn finish_onboarding(app: &mut App) {
+    let experience = app.finish_onboarding();
+    if let Err(e) = app.save_settings() {
+        app.message = Some(revgame_ui::app::Message {
+            text: format!("Settings not saved: {}", e),
+            is_error: true,
+        });
+    }
+
+    if experience.starts_tutorial() {
+        if let Err(e) = app.start_tutorial(SAMPLE_PUZZLE) {
+            app.message = Some(revgame_ui::app::Message {
+                text: format!("Failed to load tutorial: {}", e),
+                is_error: true,
+            });
+        }
+    }
+}
+
 fn handle_main_menu_key(app: &mut App, code: KeyCode) {
+    let items = app.main_menu_items();
     match code {
-        KeyCode::Char('1') => {
+        KeyCode::Up | KeyCode::Char('k') => app.main_menu_state.navigate_up(&items),
+        KeyCode::Down | KeyCode::Char('j') => app.main_menu_state.navigate_down(&items),
+        KeyCode::Enter => {
+            if let Some(item) = app.main_menu_state.selected_item(&items) {
+                activate_main_menu_item(app, item);
+            }
+        }
+        KeyCode::Char(c) => {
+            if let Some(item) = MainMenuItem::from_hotkey(c).filter(|item| items.contains(item)) {
+                app.main_menu_state.selected = item;
+                activate_main_menu_item(app, item);
+            }
+        }
+        KeyCode::Esc => {
+            app.should_quit = true;
+        }
+        _ => {}
+    }
+}
+
+fn activate_main_menu_item(app: &mut App, item: MainMenuItem) {
+    match item {
+        MainMenuItem::Continue => {
+            // Resume the puzzle played most recently
+            if let Err(e) = app.continue_last_puzzle() {
+                app.message = Some(revgame_ui::app::Message {
+                    text: e,
+                    is_error: true,
+                });
+            }
+        }
+        MainMenuItem::Tutorial => {
             // Start tutorial with walkthrough
             if let Err(e) = app.start_tutorial(SAMPLE_PUZZLE) {
                 app.message = Some(revgame_ui::app::Message {
@@ -281,7 +721,7 @@ fn handle_main_menu_key(app: &mut App, code: KeyCode) {
                 });
             }
         }
-        KeyCode::Char('2') => {
+        MainMenuItem::QuickStart => {
             // Quick start without tutorial
             if let Err(e) = app.load_puzzle(SAMPLE_PUZZLE) {
                 app.message = Some(revgame_ui::app::Message {
@@ -290,12 +730,9 @@ fn handle_main_menu_key(app: &mut App, code: KeyCode) {
                 });
             }
         }
-        KeyCode::Char('3') => {
-            // Open puzzle select
-            app.screen = Screen::PuzzleSelect;
+        MainMenuItem::PuzzleSelect => {
             // Load puzzles from the puzzles directory
-            let puzzles_dir = std::path::PathBuf::from("puzzles");
-            if let Err(e) = app.puzzle_select_state.load_puzzles(&puzzles_dir) {
+            if let Err(e) = app.open_puzzle_select(pack_dir()) {
                 app.message = Some(revgame_ui::app::Message {
                     text: format!("Failed to load puzzles: {}", e),
                     is_error: true,
@@ -303,27 +740,72 @@ fn handle_main_menu_key(app: &mut App, code: KeyCode) {
                 app.screen = Screen::MainMenu;
             }
         }
-        KeyCode::Char('a') | KeyCode::Char('A') => {
+        MainMenuItem::Drills => {
+            let seed = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or(0);
+            app.start_drills(seed);
+        }
+        MainMenuItem::TimeAttack => {
+            if let Err(e) = app.start_time_attack(pack_dir()) {
+                app.message = Some(revgame_ui::app::Message {
+                    text: format!("Failed to start time attack: {}", e),
+                    is_error: true,
+                });
+            }
+        }
+        MainMenuItem::Achievements => {
             app.screen = Screen::Achievements;
         }
-        KeyCode::Char('r') | KeyCode::Char('R') => {
-            app.screen = Screen::Reference;
+        MainMenuItem::SkillTree => {
+            if let Err(e) = app.open_skill_tree(pack_dir()) {
+                app.message = Some(revgame_ui::app::Message {
+                    text: format!("Failed to load puzzles: {}", e),
+                    is_error: true,
+                });
+            }
+        }
+        MainMenuItem::Reference => {
+            app.open_reference();
         }
-        KeyCode::Char('q') | KeyCode::Char('Q') => {
-            app.should_quit = true;
+        MainMenuItem::Settings => {
+            app.screen = Screen::Settings;
         }
-        KeyCode::Esc => {
+        MainMenuItem::Quit => {
             app.should_quit = true;
         }
+    }
+}
+
+fn handle_drills_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter if app.drills_state.answered() => app.next_drill(),
+        KeyCode::Enter => app.submit_drill_answer(),
+        KeyCode::Char(c) if !app.drills_state.answered() => app.drills_state.input.push(c),
+        KeyCode::Backspace => {
+            app.drills_state.input.pop();
+        }
+        KeyCode::Esc => app.leave_drills(),
         _ => {}
     }
 }
 
 fn handle_achievements_key(app: &mut App, code: KeyCode) {
+    if code == KeyCode::Esc {
+        app.screen = Screen::MainMenu;
+    }
+}
+
+fn handle_skill_tree_key(app: &mut App, code: KeyCode) {
     match code {
-        KeyCode::Esc => {
-            app.screen = Screen::MainMenu;
+        KeyCode::Up | KeyCode::Char('k') => app.skill_tree_state.navigate_up(),
+        KeyCode::Down | KeyCode::Char('j') => {
+            let count = app.skill_tree().len();
+            app.skill_tree_state.navigate_down(count);
         }
+        KeyCode::Enter => app.practice_selected_concept(),
+        KeyCode::Esc => app.screen = Screen::MainMenu,
         _ => {}
     }
 }
@@ -339,31 +821,43 @@ fn handle_puzzle_select_key(app: &mut App, code: KeyCode) {
         KeyCode::Enter => {
             app.puzzle_select_state.enter();
         }
+        KeyCode::Char('r') => {
+            app.select_recommended();
+        }
+        KeyCode::Char('c') => {
+            let seed = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or(0);
+            app.start_checkpoint(seed);
+        }
+        #[cfg(feature = "online")]
+        KeyCode::Char('l') | KeyCode::Char('L') => {
+            if let Some(id) = app
+                .puzzle_select_state
+                .get_selected_puzzle()
+                .map(|puzzle| puzzle.id.clone())
+            {
+                revgame_ui::online::fetch_scores(app, &id);
+            }
+        }
         KeyCode::Char('s') | KeyCode::Char('S') => {
             // Start selected puzzle
             if let Some(puzzle) = app.puzzle_select_state.get_selected_puzzle() {
                 if puzzle.is_locked {
                     app.message = Some(revgame_ui::app::Message {
-                        text: "This puzzle is locked. Complete prerequisites first.".to_string(),
+                        text: "This chapter is locked: press [c] to take its checkpoint quiz"
+                            .to_string(),
                         is_error: true,
                     });
                 } else {
                     // Load puzzle from file
-                    match std::fs::read_to_string(&puzzle.file_path) {
-                        Ok(content) => {
-                            if let Err(e) = app.load_puzzle(&content) {
-                                app.message = Some(revgame_ui::app::Message {
-                                    text: format!("Failed to load puzzle: {}", e),
-                                    is_error: true,
-                                });
-                            }
-                        }
-                        Err(e) => {
-                            app.message = Some(revgame_ui::app::Message {
-                                text: format!("Failed to read puzzle file: {}", e),
-                                is_error: true,
-                            });
-                        }
+                    let path = puzzle.file_path.clone();
+                    if let Err(e) = app.load_puzzle_file(&path) {
+                        app.message = Some(revgame_ui::app::Message {
+                            text: format!("Failed to load puzzle: {}", e),
+                            is_error: true,
+                        });
                     }
                 }
             }
@@ -371,31 +865,84 @@ fn handle_puzzle_select_key(app: &mut App, code: KeyCode) {
         KeyCode::Esc | KeyCode::Backspace => {
             app.puzzle_select_state.back();
             // If we're back at category list and press Esc, go to menu
-            if app.puzzle_select_state.view_mode == revgame_ui::screens::SelectViewMode::CategoryList {
-                app.screen = Screen::MainMenu;
+            // (or back to the open puzzle when picking one for a new tab)
+            if app.puzzle_select_state.view_mode
+                == revgame_ui::screens::SelectViewMode::CategoryList
+            {
+                app.screen = if std::mem::take(&mut app.new_tab_pending) && app.debugger.is_some() {
+                    Screen::Debugger
+                } else {
+                    Screen::MainMenu
+                };
             }
         }
         _ => {}
     }
 }
 
+fn handle_settings_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.settings_state.navigate_up();
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.settings_state.navigate_down();
+        }
+        KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right => {
+            let item = app.settings_state.selected_item();
+            app.change_setting(item);
+        }
+        KeyCode::Esc | KeyCode::Backspace => {
+            app.screen = Screen::MainMenu;
+        }
+        _ => {}
+    }
+}
+
 fn handle_reference_key(app: &mut App, code: KeyCode) {
+    // Typing on the concept list edits its search
+    if app.reference_state.view_mode == ReferenceViewMode::ConceptList {
+        match code {
+            KeyCode::Char(c) => return app.reference_state.type_concept_query(Some(c)),
+            KeyCode::Backspace => return app.reference_state.type_concept_query(None),
+            _ => {}
+        }
+    }
+
     match code {
+        KeyCode::Char('c') if app.reference_state.view_mode == ReferenceViewMode::CategoryList => {
+            app.reference_state.open_concepts();
+        }
+        KeyCode::Char('a') if app.reference_state.view_mode == ReferenceViewMode::CategoryList => {
+            app.open_ascii_table("");
+        }
+        KeyCode::Char('v')
+            if app.reference_state.view_mode == ReferenceViewMode::ConceptDetail
+                && app
+                    .reference_state
+                    .get_current_concept()
+                    .is_some_and(|c| c.id == "endianness") =>
+        {
+            app.open_endian_view();
+        }
         KeyCode::Up | KeyCode::Char('k') => {
             app.reference_state.navigate_up();
         }
         KeyCode::Down | KeyCode::Char('j') => {
             app.reference_state.navigate_down();
         }
+        KeyCode::Enter if app.reference_state.view_mode == ReferenceViewMode::InstructionDetail => {
+            app.try_reference_example();
+        }
         KeyCode::Enter => {
             app.reference_state.enter();
         }
         KeyCode::Esc | KeyCode::Backspace => {
-            app.reference_state.back();
-            // If we're back at category list and press Esc, go to menu
-            if app.reference_state.view_mode == revgame_ui::screens::ReferenceViewMode::CategoryList {
-                app.screen = Screen::MainMenu;
+            // Esc on the category list leaves the reference
+            if app.reference_state.view_mode == ReferenceViewMode::CategoryList {
+                app.close_reference();
             }
+            app.reference_state.back();
         }
         _ => {}
     }
@@ -437,27 +984,55 @@ fn handle_panel_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
     match code {
         // Navigation
         KeyCode::Tab => {
-            app.focused = app.focused.next();
+            app.focus_next();
         }
         KeyCode::BackTab => {
-            app.focused = app.focused.prev();
+            app.focus_prev();
+        }
+
+        // Cycle panel layout
+        KeyCode::Char('L') => {
+            app.cycle_layout();
+        }
+
+        // Accessibility mode
+        KeyCode::F(2) => {
+            app.toggle_accessibility();
+        }
+
+        // Register timeline selection
+        KeyCode::Up | KeyCode::Char('k') if app.focused == FocusedPanel::Registers => {
+            app.select_prev_register();
+        }
+        KeyCode::Down | KeyCode::Char('j') if app.focused == FocusedPanel::Registers => {
+            app.select_next_register();
+        }
+        KeyCode::Char('t') if app.focused == FocusedPanel::Registers => {
+            app.toggle_register_bits();
+        }
+
+        // Follow the pointer in the selected register, stack slot or memory cell
+        KeyCode::Char('f') if !modifiers.contains(KeyModifiers::CONTROL) => {
+            app.follow_pointer();
         }
 
         // Disassembly navigation
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.disasm_selection > 0 {
-                app.disasm_selection -= 1;
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.disasm_selection > 0 => {
+            app.disasm_selection -= 1;
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.disasm_selection < app.disasm_cache.len().saturating_sub(1) {
-                app.disasm_selection += 1;
-            }
+        KeyCode::Down | KeyCode::Char('j')
+            if app.disasm_selection < app.disasm_cache.len().saturating_sub(1) =>
+        {
+            app.disasm_selection += 1;
         }
 
         // Execution controls
         KeyCode::F(5) => {
-            app.run();
+            if modifiers.contains(KeyModifiers::SHIFT) {
+                app.fast_run();
+            } else {
+                app.run();
+            }
             app.trigger_tutorial(TutorialTrigger::Run);
             // Check if program halted
             if let Some(ref dbg) = app.debugger {
@@ -467,12 +1042,10 @@ fn handle_panel_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             }
         }
         KeyCode::F(10) => {
-            app.step();
-            app.trigger_tutorial(TutorialTrigger::Step);
+            step_key(app);
         }
         KeyCode::Char('s') if !modifiers.contains(KeyModifiers::CONTROL) => {
-            app.step();
-            app.trigger_tutorial(TutorialTrigger::Step);
+            step_key(app);
         }
         KeyCode::Char('r') if !modifiers.contains(KeyModifiers::CONTROL) => {
             app.run();
@@ -489,7 +1062,68 @@ fn handle_panel_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
 
         // x86 Reference Manual
         KeyCode::F(1) | KeyCode::Char('?') => {
-            app.screen = Screen::Reference;
+            app.open_reference();
+        }
+
+        // Scrub the execution timeline an instruction, or a mark, at a time
+        KeyCode::Char('[') => app.scrub_timeline(-1),
+        KeyCode::Char(']') => app.scrub_timeline(1),
+        KeyCode::Char('{') => app.jump_timeline_marker(false),
+        KeyCode::Char('}') => app.jump_timeline_marker(true),
+
+        // Concept behind the tutorial step (or the concept list)
+        KeyCode::Char('g') => {
+            app.show_linked_concept();
+        }
+
+        // Hex/decimal/two's complement converter
+        KeyCode::Char('c') => {
+            app.converter_open = true;
+        }
+
+        // ASCII table with character lookup
+        KeyCode::Char('A') => {
+            app.open_ascii_table("");
+        }
+
+        // Copy the selected disassembly line, or a memory dump when memory is focused
+        KeyCode::Char('y') if !modifiers.contains(KeyModifiers::CONTROL) => {
+            app.copy_focused();
+        }
+
+        // Stuck: give up and see the official solution, after confirming
+        KeyCode::Char('R') => {
+            app.request_reveal();
+        }
+
+        // Well-known constants (CRC, FNV, MD5...) found in code and data
+        KeyCode::Char('K') => {
+            app.open_constants();
+        }
+
+        // Entropy and byte histogram of each memory region
+        KeyCode::Char('H') => {
+            app.entropy = Some(revgame_ui::screens::EntropyState::default());
+        }
+
+        // Data decoder (XOR, ROT13, base64, add/sub) at the memory view
+        KeyCode::Char('T') => {
+            app.open_decoder();
+        }
+
+        // Pseudo-C pane
+        KeyCode::Char('C') => {
+            app.toggle_pseudo();
+        }
+
+        // Stop explaining the idiom on the selected line
+        KeyCode::Char('I') => {
+            if let Err(e) = app.learn_idiom(None) {
+                app.message = Some(revgame_ui::app::Message {
+                    text: e,
+                    is_error: true,
+                });
+            }
         }
         KeyCode::Char('h') => {
             app.show_hint();
@@ -501,7 +1135,13 @@ fn handle_panel_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             app.trigger_tutorial(TutorialTrigger::EnterCommand);
         }
 
-        // Reset
+        // Reset, keeping patches; Shift+F4 discards them too
+        KeyCode::F(4) if modifiers.contains(KeyModifiers::SHIFT) => {
+            app.request_hard_reset();
+            if !app.hard_reset_confirm_open {
+                app.trigger_tutorial(TutorialTrigger::Reset);
+            }
+        }
         KeyCode::F(4) => {
             app.reset();
             app.trigger_tutorial(TutorialTrigger::Reset);
@@ -599,11 +1239,17 @@ fn handle_panel_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             }
         }
 
-        // Search (Ctrl+F)
+        // Switch puzzle tab (Ctrl+PgUp/PgDn)
+        KeyCode::PageUp if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.cycle_tab(false);
+        }
+        KeyCode::PageDown if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.cycle_tab(true);
+        }
+
+        // Search (Ctrl+F), back to the last results
         KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => {
-            app.search_dialog_open = true;
-            app.search_state.clear_results();
-            app.search_state.input.clear();
+            app.open_search(&[]);
         }
 
         // Bookmarks
@@ -621,14 +1267,64 @@ fn handle_panel_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             app.goto_prev_bookmark();
         }
 
+        // Go to address in the focused panel
+        KeyCode::Char('G') => {
+            app.goto_dialog_open = true;
+            app.goto_state.clear();
+        }
+
+        // Explain the last run
+        KeyCode::Char('E') => {
+            app.explain_last_run();
+        }
+
+        // Endianness visualizer for the dword at the top of the memory view
+        KeyCode::Char('e') => {
+            app.open_endian_view();
+        }
+
+        // Restart with different program input
+        KeyCode::Char('i') => {
+            app.open_input_dialog();
+        }
+
+        // Stop when a value the goal checks changes
+        KeyCode::Char('w') => {
+            let enabled = app.debugger.as_ref().is_some_and(|d| d.breaks_on_watch());
+            app.set_break_on_goal(!enabled);
+        }
+
+        // Show the task, its goal and progress
+        KeyCode::Char('o') => {
+            app.show_objectives();
+        }
+
+        // Compare the last two runs
+        KeyCode::Char('D') => {
+            app.diff_runs();
+        }
+
+        // Break the selected instruction into its encoding fields
+        KeyCode::Char('x') => {
+            app.inspect_encoding();
+        }
+
+        // Switch between linear sweep and recursive traversal
+        KeyCode::Char('v') => {
+            app.set_disasm_mode(app.disasm_mode.toggled());
+        }
+
+        // A demo has no menu to go back to: start it over instead
+        KeyCode::Esc if app.demo.is_some() => app.restart_demo(),
+
         // Menu (only if not in tutorial)
-        KeyCode::Esc => {
-            if !app.is_tutorial_active() {
-                app.screen = Screen::MainMenu;
-                app.debugger = None;
-                app.puzzle = None;
-                app.tutorial = None;
-            }
+        KeyCode::Esc if !app.is_tutorial_active() => {
+            app.screen = Screen::MainMenu;
+            app.debugger = None;
+            app.puzzle = None;
+            app.tutorial = None;
+            app.time_attack = None;
+            app.duel = None;
         }
 
         _ => {}
@@ -664,7 +1360,7 @@ fn handle_command_input(app: &mut App, code: KeyCode) {
     }
 }
 
-fn handle_search_key(app: &mut App, code: KeyCode) {
+fn handle_search_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
     match code {
         // Mode selection
         KeyCode::Char('1') => {
@@ -680,23 +1376,44 @@ fn handle_search_key(app: &mut App, code: KeyCode) {
             app.search_state.clear_results();
         }
 
+        // Scope: all memory, each region, then the custom range
+        KeyCode::Tab => {
+            let regions: Vec<String> = app
+                .debugger
+                .as_ref()
+                .map(|dbg| {
+                    dbg.memory
+                        .regions()
+                        .iter()
+                        .map(|region| region.name.clone())
+                        .collect()
+                })
+                .unwrap_or_default();
+            app.search_state.cycle_scope(&regions);
+        }
+
+        // Live: search again whenever memory is patched
+        KeyCode::Char('l') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.search_state.live = !app.search_state.live;
+        }
+
         // Toggle case sensitivity (String mode only)
-        KeyCode::Char('c') | KeyCode::Char('C') => {
-            if app.search_state.mode == SearchMode::String {
-                app.search_state.case_sensitive = !app.search_state.case_sensitive;
-            }
+        KeyCode::Char('c') | KeyCode::Char('C') if app.search_state.mode == SearchMode::String => {
+            app.search_state.case_sensitive = !app.search_state.case_sensitive;
         }
 
         // Adjust min string length (FindStrings mode only)
-        KeyCode::Char('+') | KeyCode::Char('=') => {
-            if app.search_state.mode == SearchMode::FindStrings {
-                app.search_state.min_string_length = app.search_state.min_string_length.saturating_add(1);
-            }
+        KeyCode::Char('+') | KeyCode::Char('=')
+            if app.search_state.mode == SearchMode::FindStrings =>
+        {
+            app.search_state.min_string_length =
+                app.search_state.min_string_length.saturating_add(1);
         }
-        KeyCode::Char('-') | KeyCode::Char('_') => {
-            if app.search_state.mode == SearchMode::FindStrings {
-                app.search_state.min_string_length = app.search_state.min_string_length.saturating_sub(1).max(1);
-            }
+        KeyCode::Char('-') | KeyCode::Char('_')
+            if app.search_state.mode == SearchMode::FindStrings =>
+        {
+            app.search_state.min_string_length =
+                app.search_state.min_string_length.saturating_sub(1).max(1);
         }
 
         // Navigation
@@ -707,15 +1424,16 @@ fn handle_search_key(app: &mut App, code: KeyCode) {
             app.search_state.navigate_down();
         }
 
+        KeyCode::PageUp => {
+            app.search_state.page_up();
+        }
+        KeyCode::PageDown => {
+            app.search_state.page_down();
+        }
+
         // Execute search
         KeyCode::Enter => {
-            let result = match app.search_state.mode {
-                SearchMode::Bytes => app.search_bytes(),
-                SearchMode::String => app.search_string(),
-                SearchMode::FindStrings => app.find_strings(),
-            };
-
-            if let Err(e) = result {
+            if let Err(e) = app.start_search() {
                 app.message = Some(revgame_ui::app::Message {
                     text: e,
                     is_error: true,
@@ -729,7 +1447,12 @@ fn handle_search_key(app: &mut App, code: KeyCode) {
         }
 
         // Text input (for Bytes and String modes)
-        KeyCode::Char(c) if matches!(app.search_state.mode, SearchMode::Bytes | SearchMode::String) => {
+        KeyCode::Char(c)
+            if matches!(
+                app.search_state.mode,
+                SearchMode::Bytes | SearchMode::String
+            ) =>
+        {
             app.search_state.input.push(c);
         }
 
@@ -738,11 +1461,9 @@ fn handle_search_key(app: &mut App, code: KeyCode) {
             app.search_state.input.pop();
         }
 
-        // Close dialog
+        // Close dialog, keeping the results for next time
         KeyCode::Esc => {
             app.search_dialog_open = false;
-            app.search_state.clear_results();
-            app.search_state.input.clear();
         }
 
         _ => {}
@@ -778,13 +1499,19 @@ fn handle_bookmarks_key(app: &mut App, code: KeyCode) {
     match code {
         KeyCode::Up | KeyCode::Char('k') => {
             if let Some(ref dbg) = app.debugger {
-                let max = dbg.bookmarks.count();
+                let max = dbg
+                    .bookmarks
+                    .filtered(app.bookmarks_view_state.filter)
+                    .len();
                 app.bookmarks_view_state.navigate_up(max);
             }
         }
         KeyCode::Down | KeyCode::Char('j') => {
             if let Some(ref dbg) = app.debugger {
-                let max = dbg.bookmarks.count();
+                let max = dbg
+                    .bookmarks
+                    .filtered(app.bookmarks_view_state.filter)
+                    .len();
                 app.bookmarks_view_state.navigate_down(max);
             }
         }
@@ -797,6 +1524,12 @@ fn handle_bookmarks_key(app: &mut App, code: KeyCode) {
         KeyCode::Char('d') | KeyCode::Char('D') => {
             app.delete_selected_bookmark();
         }
+        KeyCode::Char('c') | KeyCode::Char('C') => {
+            app.cycle_bookmark_category();
+        }
+        KeyCode::Char('f') | KeyCode::Char('F') => {
+            app.bookmarks_view_state.cycle_filter();
+        }
         KeyCode::Esc => {
             app.bookmarks_dialog_open = false;
             app.bookmarks_view_state.selected = 0;
@@ -805,20 +1538,106 @@ fn handle_bookmarks_key(app: &mut App, code: KeyCode) {
     }
 }
 
+fn handle_goto_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => {
+            app.submit_goto();
+        }
+        KeyCode::Backspace => {
+            app.goto_state.input.pop();
+        }
+        KeyCode::Char(c) => {
+            app.goto_state.input.push(c);
+        }
+        KeyCode::Esc => {
+            app.goto_dialog_open = false;
+            app.goto_state.clear();
+        }
+        _ => {}
+    }
+}
+
+fn handle_input_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => {
+            app.submit_input();
+        }
+        KeyCode::Backspace => {
+            app.input_state.text.pop();
+        }
+        KeyCode::Char(c) => {
+            app.input_state.text.push(c);
+        }
+        KeyCode::Esc => {
+            app.input_dialog_open = false;
+            app.input_state.clear();
+        }
+        _ => {}
+    }
+}
+
+/// Step, or ask for a prediction first in predict mode
+fn step_key(app: &mut App) {
+    if app.request_step() {
+        app.trigger_tutorial(TutorialTrigger::Step);
+    }
+}
+
+fn handle_predict_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => {
+            app.submit_prediction();
+            if !app.predict_dialog_open {
+                app.trigger_tutorial(TutorialTrigger::Step);
+            }
+        }
+        KeyCode::Backspace => {
+            app.predict_state.text.pop();
+        }
+        KeyCode::Char(c) => {
+            app.predict_state.text.push(c);
+        }
+        KeyCode::Esc => {
+            app.predict_dialog_open = false;
+            app.predict_state.clear();
+        }
+        _ => {}
+    }
+}
+
 fn handle_complete_key(app: &mut App, code: KeyCode) {
+    if app.solution_open {
+        if let Some(ref mut solution) = app.solution {
+            match code {
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => solution.next_page(),
+                KeyCode::Left | KeyCode::Char('h') => solution.prev_page(),
+                KeyCode::Esc | KeyCode::Char('w') => app.solution_open = false,
+                _ => {}
+            }
+        }
+        return;
+    }
+
     match code {
+        KeyCode::Char('y') => app.copy_focused(),
+        KeyCode::Char('w') if app.solution.is_some() => app.solution_open = true,
+        KeyCode::Enter | KeyCode::Esc if app.demo.is_some() => app.restart_demo(),
         KeyCode::Enter => {
-            app.trigger_tutorial(TutorialTrigger::PuzzleSolved);
+            if matches!(app.screen, Screen::PuzzleComplete { .. }) {
+                app.trigger_tutorial(TutorialTrigger::PuzzleSolved);
+            }
             app.screen = Screen::MainMenu;
             app.debugger = None;
             app.puzzle = None;
             app.tutorial = None;
+            app.solution = None;
         }
         KeyCode::Esc => {
             app.screen = Screen::MainMenu;
             app.debugger = None;
             app.puzzle = None;
             app.tutorial = None;
+            app.solution = None;
         }
         _ => {}
     }
@@ -0,0 +1,158 @@
+use std::time::{Duration, Instant};
+
+use crate::widgets::RewindEffect;
+
+/// A fixed-length animation driven by elapsed wall-clock time
+#[derive(Debug, Clone)]
+pub struct Animation {
+    /// Time since the animation started
+    elapsed: Duration,
+    /// Total length of the animation
+    duration: Duration,
+    /// Whether the animation is running
+    active: bool,
+}
+
+impl Animation {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            duration,
+            active: false,
+        }
+    }
+
+    /// Start (or restart) the animation
+    pub fn start(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.active = true;
+    }
+
+    /// Advance by `dt`, finishing once the duration has passed
+    pub fn update(&mut self, dt: Duration) {
+        if self.active {
+            self.elapsed += dt;
+            if self.elapsed >= self.duration {
+                self.active = false;
+                self.elapsed = Duration::ZERO;
+            }
+        }
+    }
+
+    /// Check if the animation is running
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Time since the animation started
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Total length of the animation
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Get current progress (0.0 to 1.0)
+    pub fn progress(&self) -> f32 {
+        if !self.active || self.duration.is_zero() {
+            return 0.0;
+        }
+        (self.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+}
+
+/// Owns every UI effect and advances them on a shared clock
+#[derive(Debug, Clone)]
+pub struct AnimationManager {
+    /// VHS rewind effect shown on undo
+    pub rewind: RewindEffect,
+    /// Brief flash on registers changed by the last step
+    pub register_flash: Animation,
+    /// Stack frame closing after a RET
+    pub frame_collapse: Animation,
+    /// Time of the last tick
+    last_tick: Option<Instant>,
+}
+
+impl AnimationManager {
+    pub fn new() -> Self {
+        Self {
+            rewind: RewindEffect::new(),
+            register_flash: Animation::new(Duration::from_millis(600)),
+            frame_collapse: Animation::new(Duration::from_millis(800)),
+            last_tick: None,
+        }
+    }
+
+    /// Advance all effects to `now`
+    pub fn tick(&mut self, now: Instant) {
+        let dt = self
+            .last_tick
+            .map(|last| now.saturating_duration_since(last))
+            .unwrap_or(Duration::ZERO);
+        self.last_tick = Some(now);
+        self.update(dt);
+    }
+
+    /// Advance all effects by `dt`
+    pub fn update(&mut self, dt: Duration) {
+        self.rewind.update(dt);
+        self.register_flash.update(dt);
+        self.frame_collapse.update(dt);
+    }
+
+    /// Whether any effect is running (the event loop redraws faster while true)
+    pub fn is_active(&self) -> bool {
+        self.rewind.is_active()
+            || self.register_flash.is_active()
+            || self.frame_collapse.is_active()
+    }
+}
+
+impl Default for AnimationManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_animation_progress_is_time_based() {
+        let mut anim = Animation::new(Duration::from_millis(1000));
+        anim.start();
+
+        anim.update(Duration::from_millis(250));
+        assert!((anim.progress() - 0.25).abs() < 0.01);
+
+        // Many small updates cover the same ground as one large one
+        for _ in 0..25 {
+            anim.update(Duration::from_millis(10));
+        }
+        assert!((anim.progress() - 0.5).abs() < 0.01);
+
+        anim.update(Duration::from_millis(500));
+        assert!(!anim.is_active());
+        assert_eq!(anim.progress(), 0.0);
+    }
+
+    #[test]
+    fn test_manager_tick_uses_instants() {
+        let mut manager = AnimationManager::new();
+        let start = Instant::now();
+
+        manager.tick(start);
+        manager.register_flash.start();
+        assert!(manager.is_active());
+
+        manager.tick(start + Duration::from_millis(300));
+        assert!(manager.register_flash.is_active());
+
+        manager.tick(start + Duration::from_millis(700));
+        assert!(!manager.is_active());
+    }
+}
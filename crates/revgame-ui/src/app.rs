@@ -1,17 +1,64 @@
-use std::collections::HashSet;
+use std::cell::{OnceCell, RefCell};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::rc::Rc;
+
+use rhai::{Dynamic, Engine, EvalAltResult};
 
 use revgame_core::{
-    debugger::Debugger,
-    emulator::DisassemblyLine,
-    puzzle::{load_puzzle, Puzzle, ValidationResult, Validator},
-    game::{GameState, SaveManager},
+    debugger::{
+        basic_block, chase_pointer, constants_in_code, constants_in_memory, describe_changes,
+        explain_run, first_idioms, pad_with_nops, pseudo_c, Bookmark, BookmarkCategory,
+        CapturePolicy, ConstantMatch, ConstantSource, Debugger, DebuggerState, FlagHit, Frame,
+        Idiom, IdiomMatch, MemorySearch, PinList, PredictMode, ProgramInput, PseudoLine,
+        RegisterSample, RunTrace, StateDiff, TraceDiff, Transform, ValueLocation, CDECL_ARGS,
+    },
+    emulator::{DisassemblyLine, DisassemblyMode, Flag, InstructionEncoding, Register},
+    game::{
+        format_time, recommend, review_puzzles, skill_tree, AssistLevel, ConceptMastery, DuelState,
+        Experience, GameState, Leaderboard, LeaderboardEntry, LearningEvent, Recommendation,
+        Replay, SaveManager, Settings, TimeAttack, TIME_ATTACK_COURSE,
+    },
+    puzzle::{
+        load_puzzle, puzzle_from_link, register_debugger_api, sandboxed_engine, share_link,
+        HintEngine, Objective, Puzzle, PuzzleListItem, Script, ScriptContext, ScriptEvent,
+        ToolCommand, ToolScript, ValidationResult, ValidationTrigger, Validator,
+    },
+    reference::{Conversion, DrillDeck, Snippet, SANDBOX_PREFIX},
 };
 
-use crate::Theme;
-use crate::tutorial::{Tutorial, TutorialTrigger};
-use crate::widgets::RewindEffect;
-use crate::screens::{ReferenceState, SearchState, BookmarksViewState, PuzzleSelectState};
+use crate::animation::AnimationManager;
+use crate::console::ConsoleState;
+use crate::demo::Demo;
+use crate::plugin::{PanelState, Plugins};
+use crate::screens::{
+    AsciiTableState, BookmarksViewState, CheckpointQuiz, CompareSide, ConstantsState,
+    ConverterState, DecoderState, DrillsState, EndianState, EntropyRegion, EntropyState, FindState,
+    FollowState, GotoState, InputState, LayoutMode, MainMenuItem, MainMenuState, OnboardingState,
+    OnboardingStep, PredictState, PuzzleSelectState, ReferenceState, SearchMode, SearchScope,
+    SearchState, SettingsItem, SettingsState, SkillTreeState, SolutionState, FIND_STACK_SLOTS,
+    SAMPLE_BYTES, SEARCH_RESULT_LIMIT,
+};
+use crate::session::PuzzleSession;
 use crate::syntax::SyntaxHighlighter;
+use crate::tutorial::{Tutorial, TutorialTrigger};
+use crate::widgets::TimelineView;
+use crate::Theme;
+
+/// Instructions shown in the register timeline
+pub const TIMELINE_LEN: usize = 32;
+
+/// Save slot holding progress between sessions
+const SESSION_SLOT: &str = "session";
+
+/// Bytes of memory a search scans between redraws
+const SEARCH_STEP_BYTES: usize = 0x10000;
+
+/// How deep tool script commands may call each other
+const MAX_TOOL_DEPTH: usize = 8;
+
+/// Longest memory range a snapshot diff compares
+const MAX_DIFF_RANGE: u32 = 0x1000;
 
 /// Which panel is currently focused
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,15 +95,25 @@ impl FocusedPanel {
 /// Current screen/view
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Screen {
+    Onboarding,
     MainMenu,
     PuzzleSelect,
+    Drills,
+    /// A puzzle's description and goal, shown before its debugger
+    Briefing,
     Debugger,
     Tutorial,
     Settings,
     Help,
     Achievements,
+    SkillTree,
     Reference,
-    PuzzleComplete { message: String },
+    PuzzleComplete {
+        message: String,
+    },
+    SolutionRevealed {
+        message: String,
+    },
 }
 
 /// Message to display to user
@@ -80,24 +137,116 @@ pub struct App {
     /// Currently loaded puzzle
     pub puzzle: Option<Puzzle>,
 
+    /// Script attached to the current puzzle
+    pub script: Option<Script>,
+
+    /// Targeted hint rules for the current puzzle
+    pub hint_engine: HintEngine,
+
+    /// Last targeted hint shown (asking again moves on to the static hints)
+    last_rule_hint: Option<String>,
+
     /// Game progress state
     pub game_state: GameState,
 
+    /// Fingerprint of the progress last saved or loaded
+    saved_progress: u64,
+
+    /// Persisted user settings
+    pub settings: Settings,
+
+    /// The terminal can't be trusted with Unicode, so everything is drawn
+    /// in ASCII whatever the settings say
+    pub ascii_terminal: bool,
+
+    /// Settings screen state
+    pub settings_state: SettingsState,
+
+    /// Main menu selection, kept between visits
+    pub main_menu_state: MainMenuState,
+
+    /// Answers given so far on the first-run screens
+    pub onboarding_state: OnboardingState,
+
+    /// Instruction drills state
+    pub drills_state: DrillsState,
+    pub skill_tree_state: SkillTreeState,
+
+    /// Debugger screen panel layout
+    pub layout_mode: LayoutMode,
+
     /// Currently focused panel
     pub focused: FocusedPanel,
 
+    /// Memory or Stack, whichever was focused last (shown when they share a tab)
+    pub data_tab: FocusedPanel,
+
+    /// Show on-screen Run and Step buttons (turned on by the web build when
+    /// it sees a touch)
+    pub touch_controls: bool,
+
+    /// Embedded demo: nothing is saved, no achievements, no menus
+    pub demo: Option<Demo>,
+
+    /// Time attack in progress
+    pub time_attack: Option<TimeAttack>,
+    /// Time attack results, loaded when one starts
+    pub leaderboard: Leaderboard,
+
+    /// This attempt at the puzzle so far, kept as a replay
+    pub recording: Replay,
+    recording_started_ms: u64,
+    /// Best solve of the puzzle, shown as a ghost when turned on
+    pub ghost: Option<Replay>,
+
+    /// Hotseat duel in progress
+    pub duel: Option<DuelState>,
+    /// The session of the duel player who is waiting for their turn
+    duel_parked: PuzzleSession,
+
+    /// Online leaderboard requests and fetched scores
+    #[cfg(feature = "online")]
+    pub online: crate::online::OnlineState,
+
     /// Selected line in disassembly
     pub disasm_selection: usize,
 
+    /// Register whose timeline is shown (index into `Register::ALL`)
+    pub selected_register: usize,
+
+    /// Show the selected register's bits instead of its timeline
+    pub register_bits: bool,
+
     /// Memory view start address
     pub memory_view_addr: u32,
 
+    /// Disassembly view start address (None follows EIP)
+    pub disasm_view_addr: Option<u32>,
+
+    /// Linear sweep or recursive traversal
+    pub disasm_mode: DisassemblyMode,
+
+    /// Stack view start address (None follows ESP)
+    pub stack_view_addr: Option<u32>,
+
+    /// Frame closed by the last RET, shown while it collapses
+    pub collapsed_frame: Option<Frame>,
+
+    /// Pseudo-C side pane beside the disassembly
+    pub pseudo_open: bool,
+
     /// Command input buffer
     pub command_input: String,
 
     /// Recent changed registers (for highlighting)
     pub changed_registers: HashSet<String>,
 
+    /// Expressions pinned to the Watches panel
+    pub pins: PinList,
+
+    /// Parts of the goal that hold right now, out of how many
+    goal_progress: Option<(usize, usize)>,
+
     /// Message to display
     pub message: Option<Message>,
 
@@ -113,12 +262,15 @@ pub struct App {
     /// Tutorial state (if in tutorial mode)
     pub tutorial: Option<Tutorial>,
 
-    /// VHS rewind effect
-    pub rewind_effect: RewindEffect,
+    /// UI effects (rewind, highlight flashes) on a shared clock
+    pub animations: AnimationManager,
 
     /// Instruction reference state
     pub reference_state: ReferenceState,
 
+    /// Screen the reference returns to when closed
+    pub reference_return: Screen,
+
     /// Search dialog state
     pub search_state: SearchState,
 
@@ -131,11 +283,137 @@ pub struct App {
     /// Whether bookmarks dialog is open
     pub bookmarks_dialog_open: bool,
 
+    /// Go-to-address dialog state
+    pub goto_state: GotoState,
+
+    /// Whether go-to-address dialog is open
+    pub goto_dialog_open: bool,
+
+    /// Program input dialog state
+    pub input_state: InputState,
+
+    /// Whether the program input dialog is open
+    pub input_dialog_open: bool,
+
+    /// Step prediction dialog state
+    pub predict_state: PredictState,
+
+    /// Whether the step prediction dialog is open
+    pub predict_dialog_open: bool,
+
+    /// Summary of the last run shown in the explain overlay
+    pub run_explanation: Vec<String>,
+
+    /// Whether the explain overlay is open
+    pub explain_open: bool,
+
+    /// Whether the objectives overlay is open
+    pub objectives_open: bool,
+
+    /// Encoding of the selected instruction shown in the inspector
+    pub encoding: Option<InstructionEncoding>,
+
+    /// Endianness visualizer, when open
+    pub endian: Option<EndianState>,
+
+    /// Number converter input
+    pub converter_state: ConverterState,
+
+    /// Whether the number converter is open
+    pub converter_open: bool,
+
+    /// Data decoder, when open
+    pub decoder: Option<DecoderState>,
+
+    /// Entropy and histogram overlay, when open
+    pub entropy: Option<EntropyState>,
+
+    /// Magic constant popup, when open
+    pub constants: Option<ConstantsState>,
+
+    /// Analysis passes, commands and panels from plugins
+    pub plugins: Plugins,
+    /// Plugin panel, when open
+    pub plugin_panel: Option<PanelState>,
+
+    /// Debug console overlay, when open
+    pub console: Option<ConsoleState>,
+    /// Whether log records are going to the session log file
+    session_log_open: bool,
+
+    /// Commands loaded from tool scripts, by name
+    pub tool_commands: BTreeMap<String, ToolCommand>,
+    /// Tool commands running right now, innermost last
+    tool_depth: usize,
+
+    /// Hits of the last `find`, when open
+    pub finder: Option<FindState>,
+
+    /// Pointer chain being followed, when open
+    pub follow: Option<FollowState>,
+
+    /// ASCII table, when open
+    pub ascii_table: Option<AsciiTableState>,
+
+    /// Walkthrough of the official solution for the puzzle just solved
+    pub solution: Option<SolutionState>,
+
+    /// Whether that walkthrough is showing
+    pub solution_open: bool,
+
+    /// Whether the "reveal the solution" confirmation is showing
+    pub reveal_confirm_open: bool,
+
+    /// Whether the confirmation for discarding patches with a hard reset is open
+    pub hard_reset_confirm_open: bool,
+
+    /// Validation verdict from the last run, for the explanation
+    last_validation: Option<ValidationResult>,
+
+    /// Side-by-side comparison of the last two runs, when open
+    pub trace_diff: Option<TraceDiff>,
+
+    /// Registers, flags and memory compared between two snapshots, when open
+    pub state_diff: Option<StateDiff>,
+
+    /// Last change to a flag being broken on, highlighted at its instruction
+    pub flag_hit: Option<FlagHit>,
+
+    /// Traces of the two most recent captured runs, kept across resets
+    previous_trace: Option<RunTrace>,
+    latest_trace: Option<RunTrace>,
+
     /// Syntax highlighter for disassembly
     pub syntax_highlighter: SyntaxHighlighter,
 
     /// Puzzle select state
     pub puzzle_select_state: PuzzleSelectState,
+
+    /// Open puzzle tabs, empty while only one puzzle is open. The active
+    /// tab's slot is a placeholder: its state lives on `App` itself.
+    tabs: Vec<PuzzleSession>,
+    active_tab: usize,
+
+    /// The next puzzle loaded opens in a new tab instead of replacing this one
+    pub new_tab_pending: bool,
+
+    /// Tab shown side by side with the active one, when comparing
+    compare_tab: Option<usize>,
+
+    /// Steps in the active tab are repeated in the compared one
+    pub compare_sync: bool,
+
+    /// Text waiting to be put on the system clipboard by the front end
+    pub clipboard: Option<String>,
+
+    /// RNG seed and clock (unix ms) to use instead of the real ones, so
+    /// tests can build the same screen every run
+    pub pinned_seed: Option<u32>,
+    pub pinned_clock_ms: Option<u64>,
+
+    /// Where progress, settings and replays are saved: the platform's save
+    /// directory unless the app was made `with_save_manager`
+    saves: OnceCell<SaveManager>,
 }
 
 impl Default for App {
@@ -151,731 +429,7346 @@ impl App {
             screen: Screen::MainMenu,
             debugger: None,
             puzzle: None,
+            script: None,
+            hint_engine: HintEngine::default(),
+            last_rule_hint: None,
             game_state: GameState::new(),
+            saved_progress: GameState::new().fingerprint(),
+            settings: Settings::new(),
+            ascii_terminal: false,
+            settings_state: SettingsState::new(),
+            main_menu_state: MainMenuState::new(),
+            onboarding_state: OnboardingState::new(),
+            drills_state: DrillsState::new(),
+            skill_tree_state: SkillTreeState::new(),
+            layout_mode: LayoutMode::default(),
             focused: FocusedPanel::Disassembly,
+            data_tab: FocusedPanel::Memory,
+            touch_controls: false,
+            demo: None,
+            time_attack: None,
+            leaderboard: Leaderboard::default(),
+            recording: Replay::default(),
+            recording_started_ms: 0,
+            ghost: None,
+            duel: None,
+            duel_parked: PuzzleSession::default(),
+            #[cfg(feature = "online")]
+            online: Default::default(),
             disasm_selection: 0,
+            selected_register: 0,
+            register_bits: false,
             memory_view_addr: 0x1000,
+            disasm_view_addr: None,
+            disasm_mode: DisassemblyMode::default(),
+            stack_view_addr: None,
+            collapsed_frame: None,
+            pseudo_open: false,
             command_input: String::new(),
             changed_registers: HashSet::new(),
+            pins: PinList::new(),
+            goal_progress: None,
             message: None,
             disasm_cache: Vec::new(),
             should_quit: false,
             hint_level: 0,
             tutorial: None,
-            rewind_effect: RewindEffect::new(),
+            animations: AnimationManager::new(),
             reference_state: ReferenceState::new(),
+            reference_return: Screen::MainMenu,
             search_state: SearchState::new(),
             search_dialog_open: false,
             bookmarks_view_state: BookmarksViewState::new(),
             bookmarks_dialog_open: false,
+            goto_state: GotoState::new(),
+            goto_dialog_open: false,
+            input_state: InputState::new(),
+            input_dialog_open: false,
+            predict_state: PredictState::new(),
+            predict_dialog_open: false,
+            run_explanation: Vec::new(),
+            explain_open: false,
+            objectives_open: false,
+            encoding: None,
+            endian: None,
+            converter_state: ConverterState::new(),
+            converter_open: false,
+            decoder: None,
+            entropy: None,
+            constants: None,
+            plugins: Plugins::builtin(),
+            plugin_panel: None,
+            console: None,
+            session_log_open: false,
+            tool_commands: BTreeMap::new(),
+            tool_depth: 0,
+            finder: None,
+            follow: None,
+            ascii_table: None,
+            solution: None,
+            solution_open: false,
+            reveal_confirm_open: false,
+            hard_reset_confirm_open: false,
+            last_validation: None,
+            trace_diff: None,
+            state_diff: None,
+            flag_hit: None,
+            previous_trace: None,
+            latest_trace: None,
             syntax_highlighter: SyntaxHighlighter::new(),
             puzzle_select_state: PuzzleSelectState::new(),
+            tabs: Vec::new(),
+            active_tab: 0,
+            new_tab_pending: false,
+            compare_tab: None,
+            compare_sync: false,
+            clipboard: None,
+            pinned_seed: None,
+            pinned_clock_ms: None,
+            saves: OnceCell::new(),
         }
     }
 
-    /// Start the tutorial
-    pub fn start_tutorial(&mut self, puzzle_toml: &str) -> Result<(), String> {
-        self.load_puzzle(puzzle_toml)?;
-        self.tutorial = Some(Tutorial::interface_tutorial());
-        Ok(())
+    /// An app that saves through `manager` instead of the platform's save
+    /// directory, such as an in-memory one in tests
+    pub fn with_save_manager(manager: SaveManager) -> Self {
+        let mut app = Self::new();
+        app.saves = OnceCell::from(manager);
+        app
     }
 
-    /// Skip the tutorial
-    pub fn skip_tutorial(&mut self) {
-        if let Some(ref mut tutorial) = self.tutorial {
-            tutorial.skip();
+    /// The save manager, opening the platform's save directory on first use
+    fn saves(&self) -> Result<&SaveManager, String> {
+        if let Some(manager) = self.saves.get() {
+            return Ok(manager);
         }
-        self.tutorial = None;
+        let manager = SaveManager::new()?;
+        Ok(self.saves.get_or_init(|| manager))
     }
 
-    /// Check and advance tutorial based on trigger
-    pub fn trigger_tutorial(&mut self, trigger: TutorialTrigger) {
-        if let Some(ref mut tutorial) = self.tutorial {
-            tutorial.check_trigger(&trigger);
-
-            // Clear tutorial if finished
-            if tutorial.is_finished() {
-                self.tutorial = None;
-            }
-        }
+    /// Pin the RNG seed and clock so puzzles, timers and the screens showing
+    /// them come out the same every time
+    pub fn pin(&mut self, seed: u32, clock_ms: u64) {
+        self.pinned_seed = Some(seed);
+        self.pinned_clock_ms = Some(clock_ms);
     }
 
-    /// Check if tutorial is active
-    pub fn is_tutorial_active(&self) -> bool {
-        self.tutorial.as_ref().map(|t| t.active).unwrap_or(false)
+    fn session_seed(&self) -> u32 {
+        self.pinned_seed.unwrap_or_else(session_seed)
     }
 
-    /// Load a puzzle from TOML content
-    pub fn load_puzzle(&mut self, toml: &str) -> Result<(), String> {
-        let puzzle = load_puzzle(toml)?;
-        self.setup_puzzle(puzzle)
+    fn now_ms(&self) -> u64 {
+        self.pinned_clock_ms.unwrap_or_else(now_ms)
     }
 
-    /// Set up a puzzle for playing
-    pub fn setup_puzzle(&mut self, puzzle: Puzzle) -> Result<(), String> {
-        // Create debugger with puzzle layout
-        let mut debugger = Debugger::with_puzzle_layout(
-            puzzle.entry_point(),
-            puzzle.setup.code_start,
-            puzzle.setup.data_start,
-            puzzle.setup.stack_start,
-        );
+    /// Move focus to the next panel visible in the current layout
+    pub fn focus_next(&mut self) {
+        self.focused = self.focused.next();
+        while !self.layout_mode.shows(self.focused) {
+            self.focused = self.focused.next();
+        }
+        self.track_data_tab();
+        self.announce_focus();
+    }
 
-        // Load code
-        let code = puzzle.code_bytes()?;
-        debugger.load_code(puzzle.setup.code_start, &code).map_err(|e| e.to_string())?;
+    /// Move focus to the previous panel visible in the current layout
+    pub fn focus_prev(&mut self) {
+        self.focused = self.focused.prev();
+        while !self.layout_mode.shows(self.focused) {
+            self.focused = self.focused.prev();
+        }
+        self.track_data_tab();
+        self.announce_focus();
+    }
 
-        // Load data if present
-        if let Some(data) = puzzle.data_bytes()? {
-            debugger.load_data(puzzle.setup.data_start, &data).map_err(|e| e.to_string())?;
+    /// Focus `panel`, as when it's tapped
+    pub fn focus(&mut self, panel: FocusedPanel) {
+        if self.focused != panel {
+            self.focused = panel;
+            self.track_data_tab();
+            self.announce_focus();
         }
+    }
 
-        // Set up registers
-        let regs = &puzzle.setup.registers;
-        if let Some(v) = regs.eax { debugger.cpu.regs.eax = v; }
-        if let Some(v) = regs.ebx { debugger.cpu.regs.ebx = v; }
-        if let Some(v) = regs.ecx { debugger.cpu.regs.ecx = v; }
-        if let Some(v) = regs.edx { debugger.cpu.regs.edx = v; }
-        if let Some(v) = regs.esi { debugger.cpu.regs.esi = v; }
-        if let Some(v) = regs.edi { debugger.cpu.regs.edi = v; }
-        if let Some(v) = regs.ebp { debugger.cpu.regs.ebp = v; }
-        if let Some(v) = regs.esp { debugger.cpu.regs.esp = v; }
+    fn track_data_tab(&mut self) {
+        if matches!(self.focused, FocusedPanel::Memory | FocusedPanel::Stack) {
+            self.data_tab = self.focused;
+        }
+    }
 
-        // Save initial state for reset
-        debugger.save_initial_state();
+    /// Which of memory/stack to show when they share a tab
+    pub fn active_data_tab(&self) -> FocusedPanel {
+        match self.focused {
+            FocusedPanel::Memory | FocusedPanel::Stack => self.focused,
+            _ => self.data_tab,
+        }
+    }
 
-        // Update app state
-        self.game_state.start_puzzle(&puzzle.metadata.id);
-        self.memory_view_addr = puzzle.setup.data_start;
-        self.hint_level = 0;
-        self.debugger = Some(debugger);
-        self.puzzle = Some(puzzle);
-        self.screen = Screen::Debugger;
-        self.refresh_disasm();
+    /// Select the next register for the value timeline
+    pub fn select_next_register(&mut self) {
+        self.selected_register = (self.selected_register + 1) % Register::ALL.len();
+    }
 
-        Ok(())
+    /// Select the previous register for the value timeline
+    pub fn select_prev_register(&mut self) {
+        self.selected_register =
+            (self.selected_register + Register::ALL.len() - 1) % Register::ALL.len();
     }
 
-    /// Refresh disassembly cache
-    pub fn refresh_disasm(&mut self) {
-        if let Some(ref mut debugger) = self.debugger {
-            self.disasm_cache = debugger.disassemble(20);
-        }
+    /// Switch the Registers panel between the selected register's timeline and its bits
+    pub fn toggle_register_bits(&mut self) {
+        self.register_bits = !self.register_bits;
     }
 
-    /// Step one instruction
-    pub fn step(&mut self) {
-        if let Some(ref mut debugger) = self.debugger {
-            self.changed_registers.clear();
+    /// How the selected register changed over the last recorded instructions
+    pub fn register_timeline(&self) -> Vec<RegisterSample> {
+        let reg = Register::ALL[self.selected_register % Register::ALL.len()];
+        self.debugger
+            .as_ref()
+            .map(|d| d.register_timeline(reg, TIMELINE_LEN))
+            .unwrap_or_default()
+    }
 
-            match debugger.step() {
-                Ok(result) => {
-                    for reg in result.changed_registers {
-                        self.changed_registers.insert(reg);
-                    }
-                    self.refresh_disasm();
-                    self.check_completion();
-                }
-                Err(e) => {
-                    self.message = Some(Message {
-                        text: format!("Error: {}", e),
-                        is_error: true,
-                    });
-                }
-            }
+    /// Switch to the next debugger layout and persist the choice
+    pub fn cycle_layout(&mut self) {
+        self.layout_mode = self.layout_mode.next();
+        if !self.layout_mode.shows(self.focused) {
+            self.focused = FocusedPanel::Disassembly;
         }
+
+        self.settings.debugger_layout = self.layout_mode.name().to_string();
+        let text = match self.save_settings() {
+            Ok(()) => format!("Layout: {}", self.layout_mode.label()),
+            Err(e) => format!("Layout: {} (not saved: {})", self.layout_mode.label(), e),
+        };
+        self.message = Some(Message {
+            text,
+            is_error: false,
+        });
     }
 
-    /// Run until breakpoint or halt
-    pub fn run(&mut self) {
+    /// Load persisted settings and apply them
+    pub fn load_settings(&mut self) -> Result<(), String> {
+        let save_manager = self.saves()?;
+        self.settings = save_manager.load_settings()?;
+        self.apply_settings();
+        Ok(())
+    }
+
+    /// Rebuild theme and layout from the current settings
+    pub fn apply_settings(&mut self) {
+        let mut theme = if self.settings.high_contrast {
+            Theme::high_contrast()
+        } else {
+            Theme::from_name(&self.settings.theme)
+        };
+        theme.ascii = self.settings.ascii_borders || self.ascii_terminal;
+        theme.focus_markers = self.settings.focus_markers;
+
+        self.theme = theme;
+        self.layout_mode = LayoutMode::from_name(&self.settings.debugger_layout);
+
+        let policy = self.capture_policy();
         if let Some(ref mut debugger) = self.debugger {
-            self.changed_registers.clear();
+            debugger.set_capture_policy(policy);
+        }
 
-            match debugger.run() {
-                Ok(result) => {
-                    let msg = match result {
-                        revgame_core::debugger::RunResult::Breakpoint(addr) => {
-                            format!("Breakpoint at 0x{:08X}", addr)
-                        }
-                        revgame_core::debugger::RunResult::Halted => {
-                            "Program halted".to_string()
-                        }
-                        revgame_core::debugger::RunResult::LimitExceeded(n) => {
-                            format!("Execution limit exceeded ({} instructions)", n)
-                        }
-                        revgame_core::debugger::RunResult::Error(e) => {
-                            format!("Error: {}", e)
-                        }
-                    };
-                    self.message = Some(Message { text: msg, is_error: false });
-                    self.refresh_disasm();
-                    self.check_completion();
-                }
-                Err(e) => {
-                    self.message = Some(Message {
-                        text: format!("Error: {}", e),
-                        is_error: true,
-                    });
-                }
-            }
+        if self.settings.session_log != self.session_log_open {
+            self.session_log_open = self.settings.session_log;
+            self.apply_session_log();
         }
     }
 
-    /// Reset puzzle to initial state
-    pub fn reset(&mut self) {
-        if let Some(ref mut debugger) = self.debugger {
-            debugger.reset();
-            self.refresh_disasm();
-            self.changed_registers.clear();
+    /// Start or stop writing `session.log` next to the saves
+    fn apply_session_log(&mut self) {
+        if !self.session_log_open {
+            crate::console::CONSOLE.close_file();
+            return;
+        }
+        // Only a save manager that keeps files gets a log file; an in-memory
+        // one (tests, the browser) keeps the log in the console alone
+        let opened = self.saves().and_then(|saves| match saves.directory() {
+            Some(dir) => crate::console::CONSOLE.open_file(&dir.join("session.log")),
+            None => Ok(()),
+        });
+        if let Err(e) = opened {
+            self.session_log_open = false;
             self.message = Some(Message {
-                text: "Reset to initial state".to_string(),
-                is_error: false,
+                text: format!("Session log not written: {}", e),
+                is_error: true,
             });
         }
     }
 
-    /// Toggle breakpoint at selected address
-    pub fn toggle_breakpoint(&mut self) {
-        if let Some(ref mut debugger) = self.debugger {
-            if let Some(line) = self.disasm_cache.get(self.disasm_selection) {
-                let addr = line.address;
-                let set = debugger.toggle_breakpoint(addr);
-                self.message = Some(Message {
-                    text: if set {
-                        format!("Breakpoint set at 0x{:08X}", addr)
-                    } else {
-                        format!("Breakpoint removed at 0x{:08X}", addr)
-                    },
-                    is_error: false,
-                });
-            }
-        }
+    /// Open or close the debug console
+    pub fn toggle_console(&mut self) {
+        self.console = match self.console {
+            Some(_) => None,
+            None => Some(ConsoleState::default()),
+        };
     }
 
-    /// Show next hint
-    pub fn show_hint(&mut self) {
-        if let Some(ref puzzle) = self.puzzle {
-            self.hint_level += 1;
-            if let Some(hint) = puzzle.hints.get_hint(self.hint_level) {
-                self.game_state.use_hint();
-                self.message = Some(Message {
-                    text: format!("Hint {}: {}", self.hint_level, hint),
-                    is_error: false,
-                });
-            } else {
-                self.message = Some(Message {
-                    text: "No more hints available".to_string(),
-                    is_error: false,
-                });
-            }
+    /// History capture policy implied by the fast run setting
+    pub fn capture_policy(&self) -> CapturePolicy {
+        if self.settings.fast_run {
+            CapturePolicy::StepOnly
+        } else {
+            CapturePolicy::Always
         }
     }
 
-    /// Check if puzzle is completed
-    fn check_completion(&mut self) {
-        if let (Some(ref debugger), Some(ref puzzle)) = (&self.debugger, &self.puzzle) {
-            if debugger.cpu.halted {
-                let result = Validator::validate(puzzle, &debugger.cpu, &debugger.memory);
-                match result {
-                    ValidationResult::Success => {
-                        let achievements = self.game_state.complete_puzzle(
-                            &puzzle.metadata.id,
-                            puzzle.metadata.difficulty,
-                        );
-
-                        // Build completion message with achievements
-                        let mut msg = format!("Congratulations! You solved '{}'!", puzzle.metadata.title);
-
-                        if !achievements.is_empty() {
-                            msg.push_str("\n\nAchievements Unlocked:");
-                            for ach in achievements.iter() {
-                                msg.push_str(&format!("\n{} {} (+{} pts)", ach.icon(), ach.name(), ach.points()));
-                            }
-                        }
+    /// Change a settings screen item, then apply and persist the result
+    pub fn change_setting(&mut self, item: SettingsItem) {
+        item.change(&mut self.settings);
+        self.apply_settings();
 
-                        self.screen = Screen::PuzzleComplete { message: msg };
-                    }
-                    ValidationResult::Failure(msg) => {
-                        self.message = Some(Message {
-                            text: format!("Not quite: {}", msg),
-                            is_error: true,
-                        });
-                    }
-                    ValidationResult::Error(e) => {
-                        self.message = Some(Message {
-                            text: format!("Validation error: {}", e),
-                            is_error: true,
-                        });
-                    }
-                }
-            }
+        if let Err(e) = self.save_settings() {
+            self.message = Some(Message {
+                text: format!("Settings not saved: {}", e),
+                is_error: true,
+            });
         }
     }
 
-    /// Get current instruction address
-    pub fn current_eip(&self) -> Option<u32> {
-        self.debugger.as_ref().map(|d| d.cpu.eip)
+    /// Show the first-run questions
+    pub fn start_onboarding(&mut self) {
+        self.onboarding_state = OnboardingState::new();
+        self.onboarding_state.theme = Theme::NAMES
+            .iter()
+            .position(|name| *name == self.settings.theme)
+            .unwrap_or(0);
+        self.screen = Screen::Onboarding;
     }
 
-    /// Check if address has breakpoint
-    pub fn has_breakpoint(&self, addr: u32) -> bool {
-        self.debugger
-            .as_ref()
-            .map(|d| d.has_breakpoint(addr))
-            .unwrap_or(false)
+    /// Preview the theme picked during onboarding
+    pub fn preview_onboarding_theme(&mut self) {
+        if self.onboarding_state.step != OnboardingStep::Theme {
+            return;
+        }
+        self.settings.theme = self.onboarding_state.selected_theme().to_string();
+        self.apply_settings();
     }
 
-    /// Get stack values for display
-    pub fn get_stack(&self, count: usize) -> Vec<(u32, u32)> {
-        let mut result = Vec::new();
-        if let Some(ref debugger) = self.debugger {
-            let esp = debugger.cpu.regs.esp;
-            for i in 0..count {
-                let addr = esp.wrapping_add((i as u32) * 4);
-                if let Ok(value) = debugger.memory.read_u32(addr) {
-                    result.push((addr, value));
-                }
-            }
-        }
-        result
+    /// Take the onboarding answers into the settings and go to the main
+    /// menu. Saving is left to the caller.
+    pub fn finish_onboarding(&mut self) -> Experience {
+        let experience = self.onboarding_state.selected_experience();
+        self.settings.profile_name = self.onboarding_state.profile_name();
+        self.settings.theme = self.onboarding_state.selected_theme().to_string();
+        self.settings.set_experience(experience);
+        self.apply_settings();
+        self.screen = Screen::MainMenu;
+        experience
     }
 
-    /// Get memory for display
-    pub fn get_memory(&self, addr: u32, count: usize) -> Option<&[u8]> {
-        self.debugger.as_ref().and_then(|d| d.memory.slice(addr, count))
+    /// Open the drills screen with a fresh deck
+    pub fn start_drills(&mut self, seed: u32) {
+        self.drills_state = DrillsState::new();
+        self.drills_state.deck = Some(DrillDeck::new(&self.reference_state.reference, seed));
+        self.next_drill();
+        self.screen = Screen::Drills;
     }
 
-    /// Patch memory at address
-    pub fn patch_memory(&mut self, addr: u32, bytes: &[u8]) -> Result<(), String> {
-        if let Some(ref mut debugger) = self.debugger {
-            debugger.patch(addr, bytes).map_err(|e| e.to_string())?;
-            self.game_state.record_patch();
-            self.refresh_disasm();
+    /// Open the puzzle select screen on the puzzles in `puzzles_dir`, with
+    /// chapters behind an unpassed checkpoint locked
+    pub fn open_puzzle_select(&mut self, puzzles_dir: &Path) -> Result<(), String> {
+        self.puzzle_select_state.load_puzzles(puzzles_dir)?;
+        self.apply_campaign_locks();
+        self.screen = Screen::PuzzleSelect;
+        Ok(())
+    }
+
+    /// Lock or unlock chapters to match the checkpoints passed
+    pub fn apply_campaign_locks(&mut self) {
+        let state = &mut self.puzzle_select_state;
+        state
+            .campaign
+            .apply_locks(&mut state.categories, &self.game_state);
+    }
+
+    /// Take the checkpoint quiz locking the selected chapter
+    pub fn start_checkpoint(&mut self, seed: u32) {
+        let state = &self.puzzle_select_state;
+        let gate = state
+            .categories
+            .get(state.selected_category)
+            .and_then(|chapter| state.campaign.gate(chapter, &self.game_state));
+        let Some(checkpoint) = gate.cloned() else {
             self.message = Some(Message {
-                text: format!("Patched {} bytes at 0x{:08X}", bytes.len(), addr),
+                text: "This chapter is open: there's no checkpoint to pass".to_string(),
                 is_error: false,
             });
-            Ok(())
-        } else {
-            Err("No debugger active".to_string())
+            return;
+        };
+        let deck = DrillDeck::new(&self.reference_state.reference, seed)
+            .restricted_to(&checkpoint.mnemonics);
+        if deck.is_empty() {
+            self.message = Some(Message {
+                text: format!(
+                    "Checkpoint {} has no drills for {}",
+                    checkpoint.id,
+                    checkpoint.mnemonics.join(", ")
+                ),
+                is_error: true,
+            });
+            return;
         }
-    }
 
-    /// Undo the last patch
-    pub fn undo_patch(&mut self) -> Result<(), String> {
-        if let Some(ref mut debugger) = self.debugger {
-            debugger.undo_patch().map_err(|e| e.to_string())?;
-            let remaining = debugger.undo_count();
-
-            // Check for undo achievement
-            if let Some(achievement) = self.game_state.record_undo() {
-                self.message = Some(Message {
-                    text: format!("Achievement Unlocked: {} {} (+{} pts)",
-                        achievement.icon(), achievement.name(), achievement.points()),
-                    is_error: false,
-                });
-            }
+        self.drills_state = DrillsState::new();
+        self.drills_state.deck = Some(deck);
+        self.drills_state.quiz = Some(CheckpointQuiz::new(checkpoint));
+        self.next_drill();
+        self.screen = Screen::Drills;
+    }
 
-            self.rewind_effect.trigger();
-            self.refresh_disasm();
+    /// Leave the drills screen; a checkpoint quiz left early counts for nothing
+    pub fn leave_drills(&mut self) {
+        self.screen = if self.drills_state.quiz.take().is_some() {
+            Screen::PuzzleSelect
+        } else {
+            Screen::MainMenu
+        };
+    }
 
-            // Only override message if no achievement was unlocked
-            if self.message.is_none() {
-                self.message = Some(Message {
-                    text: format!("Undone ({} remaining)", remaining),
-                    is_error: false,
+    /// Record a finished checkpoint quiz and go back to the chapters
+    fn finish_checkpoint(&mut self, quiz: CheckpointQuiz) {
+        let checkpoint = &quiz.checkpoint;
+        let passed = quiz.passed();
+        self.game_state
+            .record_checkpoint(&checkpoint.id, quiz.correct, passed);
+        let text = if passed {
+            let chapter = self
+                .puzzle_select_state
+                .categories
+                .iter()
+                .find(|chapter| chapter.name == checkpoint.unlocks)
+                .map_or(checkpoint.unlocks.as_str(), |chapter| {
+                    chapter.display_name.as_str()
                 });
-            }
-            Ok(())
+            format!(
+                "Checkpoint passed with {}/{}: {} unlocked",
+                quiz.correct, checkpoint.questions, chapter
+            )
         } else {
-            Err("No debugger active".to_string())
-        }
+            format!(
+                "{}/{} correct, {} needed: brush up with the drills and try again",
+                quiz.correct, checkpoint.questions, checkpoint.pass
+            )
+        };
+        self.message = Some(Message {
+            text,
+            is_error: !passed,
+        });
+        self.apply_campaign_locks();
+        self.screen = Screen::PuzzleSelect;
     }
 
-    /// Redo the last undone patch
-    pub fn redo_patch(&mut self) -> Result<(), String> {
-        if let Some(ref mut debugger) = self.debugger {
-            debugger.redo_patch().map_err(|e| e.to_string())?;
-            let remaining = debugger.redo_count();
-            self.refresh_disasm();
-            self.message = Some(Message {
-                text: format!("Redone ({} remaining)", remaining),
-                is_error: false,
-            });
-            Ok(())
-        } else {
-            Err("No debugger active".to_string())
+    /// Deal the next drill, or end a checkpoint quiz once every question
+    /// is answered
+    pub fn next_drill(&mut self) {
+        if self
+            .drills_state
+            .quiz
+            .as_ref()
+            .is_some_and(CheckpointQuiz::is_finished)
+        {
+            if let Some(quiz) = self.drills_state.quiz.take() {
+                self.finish_checkpoint(quiz);
+            }
+            return;
         }
+        let state = &mut self.drills_state;
+        state.drill = state.deck.as_mut().and_then(DrillDeck::next_drill);
+        state.input.clear();
+        state.feedback = None;
     }
 
-    /// Process a command
-    pub fn process_command(&mut self, cmd: &str) {
-        let parts: Vec<&str> = cmd.trim().split_whitespace().collect();
-        if parts.is_empty() {
+    /// Grade the typed answer against what the executor computes
+    pub fn submit_drill_answer(&mut self) {
+        let Some(ref drill) = self.drills_state.drill else {
             return;
-        }
+        };
+        let verdict = drill.check(&self.drills_state.input).and_then(|correct| {
+            drill
+                .answer()
+                .map(|a| (correct, a))
+                .map_err(|e| e.to_string())
+        });
 
-        match parts[0].to_lowercase().as_str() {
-            "help" | "h" | "?" => {
-                self.message = Some(Message {
-                    text: "Commands: step/s, run/r, reset, bp <addr>, patch <addr> <bytes>, undo/u, redo, hint, quit".to_string(),
-                    is_error: false,
-                });
-            }
-            "step" | "s" => self.step(),
-            "run" | "r" => self.run(),
-            "reset" => self.reset(),
-            "undo" | "u" => {
-                if let Err(e) = self.undo_patch() {
-                    self.message = Some(Message {
-                        text: e,
-                        is_error: true,
-                    });
-                }
-            }
-            "redo" => {
-                if let Err(e) = self.redo_patch() {
-                    self.message = Some(Message {
-                        text: e,
-                        is_error: true,
-                    });
-                }
-            }
-            "bp" | "breakpoint" => {
-                if parts.len() > 1 {
-                    if let Ok(addr) = u32::from_str_radix(parts[1].trim_start_matches("0x"), 16) {
-                        if let Some(ref mut debugger) = self.debugger {
-                            let set = debugger.toggle_breakpoint(addr);
-                            self.message = Some(Message {
-                                text: if set {
-                                    format!("Breakpoint set at 0x{:08X}", addr)
-                                } else {
-                                    format!("Breakpoint removed at 0x{:08X}", addr)
-                                },
-                                is_error: false,
-                            });
-                        }
-                    } else {
-                        self.message = Some(Message {
-                            text: "Invalid address".to_string(),
-                            is_error: true,
-                        });
-                    }
-                } else {
-                    self.toggle_breakpoint();
+        match verdict {
+            Ok((correct, answer)) => {
+                self.game_state.drills.record(drill.mnemonic, correct);
+                if let Some(ref mut quiz) = self.drills_state.quiz {
+                    quiz.asked += 1;
+                    quiz.correct += u32::from(correct);
                 }
-            }
-            "patch" => {
-                if parts.len() >= 3 {
-                    if let Ok(addr) = u32::from_str_radix(parts[1].trim_start_matches("0x"), 16) {
-                        let bytes: Result<Vec<u8>, _> = parts[2..]
-                            .iter()
-                            .map(|s| u8::from_str_radix(s.trim_start_matches("0x"), 16))
-                            .collect();
-                        match bytes {
-                            Ok(b) => {
-                                if let Err(e) = self.patch_memory(addr, &b) {
-                                    self.message = Some(Message {
-                                        text: e,
-                                        is_error: true,
-                                    });
-                                }
-                            }
-                            Err(_) => {
-                                self.message = Some(Message {
-                                    text: "Invalid bytes".to_string(),
-                                    is_error: true,
-                                });
-                            }
-                        }
-                    } else {
-                        self.message = Some(Message {
-                            text: "Invalid address".to_string(),
-                            is_error: true,
-                        });
-                    }
+                let text = if correct {
+                    format!("Correct: {}", answer.describe())
                 } else {
-                    self.message = Some(Message {
-                        text: "Usage: patch <addr> <bytes...>".to_string(),
-                        is_error: true,
-                    });
-                }
+                    format!("Not quite: the answer is {}", answer.describe())
+                };
+                self.drills_state.feedback = Some((text, correct));
             }
-            "hint" => self.show_hint(),
-            "quit" | "q" => self.should_quit = true,
-            _ => {
+            Err(e) => {
                 self.message = Some(Message {
-                    text: format!("Unknown command: {}", parts[0]),
+                    text: e,
                     is_error: true,
                 });
             }
         }
     }
 
-    /// Save game progress
-    pub fn save_game(&mut self, slot: &str) -> Result<(), String> {
-        let save_manager = SaveManager::new()?;
-        save_manager.save(&self.game_state, slot)?;
-
-        self.message = Some(Message {
-            text: format!("Game saved to slot: {}", slot),
-            is_error: false,
-        });
-
-        Ok(())
+    /// Open the reference, remembering where to return to
+    pub fn open_reference(&mut self) {
+        if self.screen != Screen::Reference {
+            self.reference_return = self.screen.clone();
+        }
+        self.screen = Screen::Reference;
     }
 
-    /// Load game progress
-    pub fn load_game(&mut self, slot: &str) -> Result<(), String> {
-        let save_manager = SaveManager::new()?;
-        let game_state = save_manager.load(slot)?;
+    /// Leave the reference for the screen it was opened from
+    pub fn close_reference(&mut self) {
+        self.screen = std::mem::replace(&mut self.reference_return, Screen::MainMenu);
+    }
 
-        self.game_state = game_state;
+    /// Run the selected reference example in a scratch sandbox, opening a
+    /// tab for it unless the current puzzle is itself a sandbox
+    pub fn try_reference_example(&mut self) {
+        let state = &self.reference_state;
+        let snippet = match state
+            .get_current_instruction()
+            .map(|info| Snippet::from_example(info, state.selected_example))
+        {
+            Some(Ok(snippet)) => snippet,
+            Some(Err(e)) => {
+                self.message = Some(Message {
+                    text: format!("Can't assemble the example: {}", e),
+                    is_error: true,
+                });
+                return;
+            }
+            None => return,
+        };
+        self.new_tab_pending = self.debugger.is_some() && !self.in_sandbox();
+        if let Err(text) = self.load_puzzle(&snippet.puzzle_toml()) {
+            self.new_tab_pending = false;
+            self.message = Some(Message {
+                text,
+                is_error: true,
+            });
+            return;
+        }
 
+        self.begin_puzzle();
+        let seeded: Vec<String> = snippet
+            .registers
+            .iter()
+            .map(|(name, value)| format!("{}={}", name.to_uppercase(), value))
+            .collect();
+        let mut text = format!(
+            "Sandbox for {} example {}: step with F10",
+            snippet.mnemonic,
+            snippet.example + 1
+        );
+        if !seeded.is_empty() {
+            text.push_str(&format!(" (starting with {})", seeded.join(", ")));
+        }
         self.message = Some(Message {
-            text: format!("Game loaded from slot: {}", slot),
+            text,
             is_error: false,
         });
+    }
 
-        Ok(())
+    /// Whether the open puzzle is a scratch sandbox, which has no goal and
+    /// leaves no trace in the save
+    pub fn in_sandbox(&self) -> bool {
+        self.puzzle
+            .as_ref()
+            .is_some_and(|puzzle| puzzle.metadata.id.starts_with(SANDBOX_PREFIX))
     }
 
-    /// Quick save (slot "quick")
-    pub fn quick_save(&mut self) -> Result<(), String> {
-        self.save_game("quick")
+    /// Open a concept in the reference
+    pub fn open_concept(&mut self, id: &str) {
+        if self.reference_state.show_concept(id) {
+            self.open_reference();
+        }
     }
 
-    /// Quick load (slot "quick")
-    pub fn quick_load(&mut self) -> Result<(), String> {
-        self.load_game("quick")
+    /// Concept linked from what's on screen: the inspected instruction,
+    /// else the current tutorial step
+    pub fn linked_concept(&self) -> Option<&'static str> {
+        if let Some(ref encoding) = self.encoding {
+            let mnemonic = encoding.text.split_whitespace().next()?;
+            return self
+                .reference_state
+                .glossary
+                .for_instruction(mnemonic)
+                .first()
+                .map(|concept| concept.id);
+        }
+        self.tutorial.as_ref()?.current()?.concept
     }
 
-    /// Perform byte pattern search
-    pub fn search_bytes(&mut self) -> Result<(), String> {
-        use revgame_core::debugger::MemorySearch;
+    /// Open the linked concept, or the concept list if nothing is linked
+    pub fn show_linked_concept(&mut self) {
+        match self.linked_concept() {
+            Some(id) => self.open_concept(id),
+            None => {
+                self.reference_state.open_concepts();
+                self.open_reference();
+            }
+        }
+    }
 
-        if let Some(ref dbg) = self.debugger {
-            // Parse hex pattern
-            let pattern = MemorySearch::parse_hex_pattern(&self.search_state.input)
-                .map_err(|e| format!("Invalid hex pattern: {}", e))?;
+    /// Turn accessibility mode (all accessibility options) on or off
+    pub fn toggle_accessibility(&mut self) {
+        let enabled = !self.settings.accessibility_enabled();
+        self.settings.set_accessibility(enabled);
+        self.apply_settings();
 
-            // Search in memory (0x0 to 0x10000 for now)
-            let results = MemorySearch::search_bytes(&dbg.memory, &pattern, 0x0, 0x10000)
-                .map_err(|e| format!("Search error: {}", e))?;
+        let state = if enabled { "on" } else { "off" };
+        let text = match self.save_settings() {
+            Ok(()) => format!("Accessibility mode {}", state),
+            Err(e) => format!("Accessibility mode {} (not saved: {})", state, e),
+        };
+        self.message = Some(Message {
+            text,
+            is_error: false,
+        });
+    }
 
-            self.search_state.results = results;
-            self.search_state.selected_result = 0;
+    /// Set a single named option ("markers", "contrast", "ascii", "announce")
+    pub fn set_option(&mut self, name: &str, enabled: bool) -> Result<(), String> {
+        let option = match name {
+            "markers" => &mut self.settings.focus_markers,
+            "contrast" => &mut self.settings.high_contrast,
+            "ascii" => &mut self.settings.ascii_borders,
+            "announce" => &mut self.settings.announce,
+            "fastrun" => &mut self.settings.fast_run,
+            "frames" => &mut self.settings.stack_frames,
+            "idioms" => &mut self.settings.idiom_notes,
+            "learning" => &mut self.settings.learning_log,
+            "ghost" => &mut self.settings.ghost,
+            "log" => &mut self.settings.session_log,
+            _ => return Err(format!("Unknown option: {}", name)),
+        };
+        *option = enabled;
+        self.apply_settings();
+        self.save_settings()?;
 
-            self.message = Some(Message {
-                text: format!("Found {} matches", self.search_state.results.len()),
-                is_error: false,
-            });
+        self.message = Some(Message {
+            text: format!("{} {}", name, if enabled { "on" } else { "off" }),
+            is_error: false,
+        });
+        Ok(())
+    }
 
-            Ok(())
-        } else {
-            Err("No debugger active".to_string())
+    /// Describe the focused panel in the status line (announce mode)
+    fn announce_focus(&mut self) {
+        if !self.settings.announce {
+            return;
         }
+
+        let name = match self.focused {
+            FocusedPanel::Disassembly => "Disassembly",
+            FocusedPanel::Registers => "Registers",
+            FocusedPanel::Memory => "Memory",
+            FocusedPanel::Stack => "Stack",
+            FocusedPanel::Command => "Command",
+        };
+        self.message = Some(Message {
+            text: format!("Focus: {}", name),
+            is_error: false,
+        });
     }
 
-    /// Perform string search
-    pub fn search_string(&mut self) -> Result<(), String> {
-        use revgame_core::debugger::MemorySearch;
+    /// Describe the instruction just reached and what changed (announce mode)
+    fn announce_step(&mut self) {
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
 
-        if let Some(ref dbg) = self.debugger {
-            let results = MemorySearch::search_string(
-                &dbg.memory,
-                &self.search_state.input,
-                0x0,
-                0x10000,
-                self.search_state.case_sensitive,
-            )
-            .map_err(|e| format!("Search error: {}", e))?;
+        let eip = debugger.cpu.eip;
+        let instruction = self
+            .disasm_cache
+            .iter()
+            .find(|line| line.address == eip)
+            .map(|line| line.text.as_str())
+            .unwrap_or("?");
 
-            self.search_state.results = results;
-            self.search_state.selected_result = 0;
+        let mut changed: Vec<String> = self
+            .changed_registers
+            .iter()
+            .filter_map(|name| {
+                debugger
+                    .cpu
+                    .get_register_by_name(name)
+                    .map(|value| format!("{}=0x{:08X}", name, value))
+            })
+            .collect();
+        changed.sort();
 
-            self.message = Some(Message {
-                text: format!("Found {} matches", self.search_state.results.len()),
-                is_error: false,
-            });
+        let mut text = format!("At 0x{:08X}: {}", eip, instruction);
+        if !changed.is_empty() {
+            text.push_str(&format!(". Changed {}", changed.join(", ")));
+        }
+        if debugger.cpu.halted {
+            text.push_str(". Halted");
+        }
 
-            Ok(())
-        } else {
-            Err("No debugger active".to_string())
+        self.message = Some(Message {
+            text,
+            is_error: false,
+        });
+    }
+
+    /// Persist current settings
+    pub fn save_settings(&self) -> Result<(), String> {
+        if self.demo.is_some() {
+            return Ok(());
         }
+        let save_manager = self.saves()?;
+        save_manager.save_settings(&self.settings)
     }
 
-    /// Find all strings in memory
-    pub fn find_strings(&mut self) -> Result<(), String> {
-        use revgame_core::debugger::MemorySearch;
+    /// Start the tutorial
+    pub fn start_tutorial(&mut self, puzzle_toml: &str) -> Result<(), String> {
+        self.load_puzzle(puzzle_toml)?;
+        self.tutorial = Some(Tutorial::interface_tutorial());
+        Ok(())
+    }
 
-        if let Some(ref dbg) = self.debugger {
-            let results = MemorySearch::find_strings(
-                &dbg.memory,
-                self.search_state.min_string_length,
-                0x0,
-                0x10000,
-            )
-            .map_err(|e| format!("Search error: {}", e))?;
-
-            self.search_state.results = results;
-            self.search_state.selected_result = 0;
+    /// Skip the tutorial
+    pub fn skip_tutorial(&mut self) {
+        if let Some(ref mut tutorial) = self.tutorial {
+            tutorial.skip();
+        }
+        self.tutorial = None;
+    }
 
-            self.message = Some(Message {
-                text: format!("Found {} strings", self.search_state.results.len()),
-                is_error: false,
-            });
+    /// Check and advance tutorial based on trigger
+    pub fn trigger_tutorial(&mut self, trigger: TutorialTrigger) {
+        if let Some(ref mut tutorial) = self.tutorial {
+            tutorial.check_trigger(&trigger);
 
-            Ok(())
-        } else {
-            Err("No debugger active".to_string())
+            // Clear tutorial if finished
+            if tutorial.is_finished() {
+                self.tutorial = None;
+            }
         }
     }
 
-    /// Jump to selected search result
-    pub fn goto_search_result(&mut self) {
-        if let Some(address) = self.search_state.get_selected_address() {
-            self.memory_view_addr = address;
-            self.search_dialog_open = false;
+    /// Check if tutorial is active
+    pub fn is_tutorial_active(&self) -> bool {
+        self.tutorial.as_ref().map(|t| t.active).unwrap_or(false)
+    }
+
+    /// Load a puzzle from TOML content
+    pub fn load_puzzle(&mut self, toml: &str) -> Result<(), String> {
+        let puzzle = load_puzzle(toml)?;
+        self.setup_puzzle(puzzle)
+    }
+
+    /// Load the puzzle a share link (or its `#puzzle=...` fragment) carries
+    pub fn load_shared_puzzle(&mut self, link: &str) -> Result<(), String> {
+        let puzzle = puzzle_from_link(link)?;
+        self.setup_puzzle(puzzle)
+    }
 
+    /// Load a puzzle file, remembering it for "Continue"
+    pub fn load_puzzle_file(&mut self, path: &Path) -> Result<(), String> {
+        let toml = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read puzzle file: {}", e))?;
+        self.load_puzzle(&toml)?;
+        self.game_state.last_puzzle = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Open a puzzle as an embedded demo, optionally playing `replay`
+    pub fn start_demo(&mut self, puzzle_toml: &str, replay: Option<Replay>) -> Result<(), String> {
+        self.demo = Some(Demo::new(puzzle_toml, replay));
+        self.load_puzzle(puzzle_toml)?;
+        self.begin_puzzle();
+        Ok(())
+    }
+
+    /// Put the demo back to its start, replay included
+    pub fn restart_demo(&mut self) {
+        let Some(ref mut demo) = self.demo else {
+            return;
+        };
+        demo.rewind();
+        let puzzle = demo.puzzle.clone();
+        self.solution = None;
+        if let Err(text) = self.load_puzzle(&puzzle) {
             self.message = Some(Message {
-                text: format!("Jumped to 0x{:08X}", address),
-                is_error: false,
+                text,
+                is_error: true,
             });
         }
+        self.begin_puzzle();
     }
 
-    /// Toggle bookmark at current disassembly address
-    pub fn toggle_bookmark_at_cursor(&mut self) {
-        if let Some(ref mut dbg) = self.debugger {
-            if self.disasm_selection < self.disasm_cache.len() {
-                let address = self.disasm_cache[self.disasm_selection].address;
-                let added = dbg.bookmarks.toggle(address, format!("Address 0x{:08X}", address));
-
+    /// Play the demo's replay on by `elapsed_ms`, running each command that
+    /// came due and showing its note
+    pub fn advance_demo(&mut self, elapsed_ms: u64) {
+        let Some(ref mut demo) = self.demo else {
+            return;
+        };
+        for event in demo.advance(elapsed_ms) {
+            self.run_command(&event.command);
+            if let Some(text) = event.note {
                 self.message = Some(Message {
-                    text: if added {
-                        format!("Bookmark added at 0x{:08X}", address)
-                    } else {
-                        format!("Bookmark removed at 0x{:08X}", address)
-                    },
+                    text,
                     is_error: false,
                 });
             }
         }
     }
 
-    /// Go to next bookmark
-    pub fn goto_next_bookmark(&mut self) {
-        if let Some(ref dbg) = self.debugger {
-            let current_addr = if self.disasm_selection < self.disasm_cache.len() {
-                self.disasm_cache[self.disasm_selection].address
-            } else {
-                self.memory_view_addr
-            };
-
-            if let Some(next_addr) = dbg.bookmarks.next_after(current_addr) {
-                self.memory_view_addr = next_addr;
-                self.message = Some(Message {
-                    text: format!("Jumped to bookmark at 0x{:08X}", next_addr),
-                    is_error: false,
-                });
-            } else {
+    /// Hand the controls over, stopping the replay
+    pub fn take_over_demo(&mut self) {
+        if let Some(ref mut demo) = self.demo {
+            if demo.is_playing() {
+                demo.stop();
                 self.message = Some(Message {
-                    text: "No more bookmarks after current address".to_string(),
+                    text: "Demo stopped, you have the controls".to_string(),
                     is_error: false,
                 });
             }
         }
     }
 
-    /// Go to previous bookmark
-    pub fn goto_prev_bookmark(&mut self) {
-        if let Some(ref dbg) = self.debugger {
-            let current_addr = if self.disasm_selection < self.disasm_cache.len() {
-                self.disasm_cache[self.disasm_selection].address
-            } else {
-                self.memory_view_addr
-            };
+    /// Start a time attack through the course puzzles found in `puzzles_dir`
+    pub fn start_time_attack(&mut self, puzzles_dir: &Path) -> Result<(), String> {
+        if self.puzzle_select_state.categories.is_empty() {
+            self.puzzle_select_state.load_puzzles(puzzles_dir)?;
+        }
+        let course: Vec<&str> = TIME_ATTACK_COURSE
+            .into_iter()
+            .filter(|id| self.listed_puzzle_path(id).is_some())
+            .collect();
+        if course.is_empty() {
+            return Err("No time attack puzzles found".to_string());
+        }
+        self.leaderboard = self
+            .saves()
+            .and_then(|manager| manager.load_leaderboard())
+            .unwrap_or_default();
+        self.time_attack = Some(TimeAttack::new(&course, self.now_ms()));
+        self.load_time_attack_puzzle()
+    }
 
-            if let Some(prev_addr) = dbg.bookmarks.prev_before(current_addr) {
-                self.memory_view_addr = prev_addr;
-                self.message = Some(Message {
-                    text: format!("Jumped to bookmark at 0x{:08X}", prev_addr),
-                    is_error: false,
-                });
-            } else {
-                self.message = Some(Message {
-                    text: "No more bookmarks before current address".to_string(),
-                    is_error: false,
-                });
+    /// Path of a puzzle on the puzzle list
+    fn listed_puzzle_path(&self, id: &str) -> Option<std::path::PathBuf> {
+        self.puzzle_select_state
+            .categories
+            .iter()
+            .flat_map(|category| &category.puzzles)
+            .find(|puzzle| puzzle.id == id)
+            .map(|puzzle| puzzle.file_path.clone())
+    }
+
+    /// Open the time attack's current puzzle, straight into the debugger
+    fn load_time_attack_puzzle(&mut self) -> Result<(), String> {
+        let Some(id) = self.time_attack.as_ref().and_then(|run| run.current()) else {
+            return Ok(());
+        };
+        let path = self
+            .listed_puzzle_path(id)
+            .ok_or_else(|| format!("Puzzle not found: {}", id))?;
+        let toml = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read puzzle file: {}", e))?;
+        self.load_puzzle(&toml)?;
+        self.begin_puzzle();
+        Ok(())
+    }
+
+    /// Time attack progress for the status bar: puzzle, global timer and
+    /// the current puzzle's split
+    pub fn time_attack_status(&self) -> Option<String> {
+        let run = self.time_attack.as_ref()?;
+        let now = self.now_ms();
+        let elapsed = run.elapsed_ms(now);
+        let mut status = format!(
+            "time attack {}/{} {} (split {})",
+            run.splits().len() + 1,
+            run.course().len(),
+            format_time(elapsed),
+            format_time(run.split_elapsed_ms(now))
+        );
+        // The ghost of the best run is on the puzzle it hadn't solved yet
+        let best = self
+            .leaderboard
+            .best(&self.leaderboard_profile())
+            .filter(|_| self.settings.ghost);
+        if let Some(best) = best {
+            match best
+                .splits
+                .iter()
+                .filter(|&&split| split <= elapsed)
+                .count()
+            {
+                solved if solved >= best.splits.len() => status.push_str(", ghost finished"),
+                solved => status.push_str(&format!(", ghost on {}", solved + 1)),
             }
         }
+        Some(status)
     }
 
-    /// Delete selected bookmark
-    pub fn delete_selected_bookmark(&mut self) {
-        if let Some(ref mut dbg) = self.debugger {
-            let bookmarks = dbg.bookmarks.list();
-            if self.bookmarks_view_state.selected < bookmarks.len() {
-                let address = bookmarks[self.bookmarks_view_state.selected].address;
-                dbg.bookmarks.remove(address);
-
-                // Adjust selection if needed
-                if self.bookmarks_view_state.selected > 0
-                    && self.bookmarks_view_state.selected >= dbg.bookmarks.count()
-                {
-                    self.bookmarks_view_state.selected -= 1;
-                }
+    /// Add a command to the recording of this attempt, with the progress
+    /// it left behind
+    fn record_replay(&mut self, command: String) {
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
+        let at_ms = self.now_ms().saturating_sub(self.recording_started_ms);
+        self.recording.record(
+            at_ms,
+            command,
+            debugger.total_instructions,
+            debugger.undo_count(),
+        );
+    }
 
-                self.message = Some(Message {
-                    text: format!("Bookmark deleted at 0x{:08X}", address),
-                    is_error: false,
-                });
-            }
+    /// Start recording a fresh attempt and bring up the puzzle's ghost
+    fn start_recording(&mut self) {
+        let id = self
+            .puzzle
+            .as_ref()
+            .map(|puzzle| puzzle.metadata.id.clone())
+            .unwrap_or_default();
+        self.ghost = match self.demo {
+            Some(_) => None,
+            None => self
+                .saves()
+                .and_then(|manager| manager.load_replay(&id))
+                .ok()
+                .flatten(),
+        };
+        self.recording = Replay {
+            puzzle: id,
+            events: Vec::new(),
+        };
+        self.recording_started_ms = self.now_ms();
+    }
+
+    /// Keep this attempt as the puzzle's ghost if it's the fastest solve
+    /// (solves in a demo or a duel aren't the player's own, and times on a
+    /// pinned clock aren't real)
+    fn keep_best_solve(&mut self) {
+        if self.demo.is_some() || self.duel.is_some() {
+            return;
+        }
+        self.record_replay("check".to_string());
+        let faster = self.pinned_clock_ms.is_none()
+            && self
+                .ghost
+                .as_ref()
+                .is_none_or(|ghost| self.recording.duration_ms() < ghost.duration_ms());
+        if !faster {
+            return;
+        }
+        match self
+            .saves()
+            .and_then(|manager| manager.save_replay(&self.recording))
+        {
+            Ok(()) => self.ghost = Some(self.recording.clone()),
+            Err(e) => log::warn!("Best solve not saved: {}", e),
         }
     }
 
-    /// Start editing selected bookmark
-    pub fn start_editing_bookmark(&mut self) {
-        if let Some(ref dbg) = self.debugger {
-            let bookmarks = dbg.bookmarks.list();
-            if self.bookmarks_view_state.selected < bookmarks.len() {
-                let bookmark = bookmarks[self.bookmarks_view_state.selected];
-                self.bookmarks_view_state
-                    .start_editing(bookmark.address, bookmark.note.clone());
+    /// Where the ghost of the best solve was at this point in the attempt,
+    /// for the status bar
+    pub fn ghost_status(&self) -> Option<String> {
+        if !self.settings.ghost || self.screen != Screen::Debugger {
+            return None;
+        }
+        let ghost = self.ghost.as_ref()?;
+        let at_ms = self.now_ms().saturating_sub(self.recording_started_ms);
+        if at_ms >= ghost.duration_ms() {
+            return Some(format!(
+                "ghost solved in {}",
+                format_time(ghost.duration_ms())
+            ));
+        }
+        let (instructions, patches) = ghost.progress_at(at_ms);
+        Some(format!(
+            "ghost {} instr, {} patch{}",
+            instructions,
+            patches,
+            if patches == 1 { "" } else { "es" }
+        ))
+    }
+
+    /// Start a hotseat duel on the open puzzle: both players get their own
+    /// session of it with the same RNG seed and take turns, `names[0]` first
+    pub fn start_duel(&mut self, names: [&str; 2]) -> Result<(), String> {
+        let mut puzzle = self
+            .puzzle
+            .clone()
+            .ok_or_else(|| "Open a puzzle to duel on first".to_string())?;
+        let seed = self
+            .debugger
+            .as_ref()
+            .map(|debugger| debugger.rng_seed())
+            .unwrap_or_else(|| self.session_seed());
+        puzzle.setup.rng_seed = Some(seed);
+
+        // The second player's session waits parked until their first turn
+        self.setup_puzzle(puzzle.clone())?;
+        self.begin_puzzle();
+        let mut parked = PuzzleSession::default();
+        self.exchange_session(&mut parked);
+        self.duel_parked = parked;
+        self.setup_puzzle(puzzle)?;
+        self.begin_puzzle();
+
+        let duel = DuelState::new(
+            &self.puzzle.as_ref().unwrap().metadata.id,
+            seed,
+            names,
+            self.now_ms(),
+        );
+        self.message = Some(Message {
+            text: format!(
+                "Duel: {} vs {}, seed {}. {} goes first; `duel pass` ends a turn",
+                names[0], names[1], seed, names[0]
+            ),
+            is_error: false,
+        });
+        self.duel = Some(duel);
+        Ok(())
+    }
+
+    /// End the current duel turn, handing the keyboard to the other
+    /// player, or show the scoreboard once both are done
+    pub fn end_duel_turn(&mut self, solved: bool) {
+        let (hints, patches, now) = (
+            self.game_state.hints_used,
+            self.game_state.patches_made,
+            self.now_ms(),
+        );
+        let Some(ref mut duel) = self.duel else {
+            return;
+        };
+        let handed_over = duel.end_turn(now, hints, patches, solved);
+        self.after_duel_turn(handed_over);
+    }
+
+    /// The current duel player stops trying
+    pub fn forfeit_duel(&mut self) {
+        let (hints, patches, now) = (
+            self.game_state.hints_used,
+            self.game_state.patches_made,
+            self.now_ms(),
+        );
+        let Some(ref mut duel) = self.duel else {
+            return;
+        };
+        let handed_over = duel.give_up(now, hints, patches);
+        self.after_duel_turn(handed_over);
+    }
+
+    fn after_duel_turn(&mut self, handed_over: bool) {
+        let Some(duel) = self.duel.take() else {
+            return;
+        };
+        if duel.is_finished() {
+            let mut msg = match duel.winner() {
+                Some(winner) => format!("{} wins the duel!", duel.players[winner].name),
+                None => "The duel is a draw".to_string(),
+            };
+            msg.push_str("\n\n");
+            msg.push_str(&duel.scoreboard().join("\n"));
+            self.duel_parked = PuzzleSession::default();
+            self.screen = Screen::PuzzleComplete { message: msg };
+            return;
+        }
+
+        if handed_over {
+            let mut parked = std::mem::take(&mut self.duel_parked);
+            self.exchange_session(&mut parked);
+            self.duel_parked = parked;
+            self.refresh_disasm();
+            self.update_goal_progress();
+        }
+        let player = duel.current_player();
+        self.message = Some(Message {
+            text: format!("{}'s turn (turn {})", player.name, player.turns + 1),
+            is_error: false,
+        });
+        self.duel = Some(duel);
+    }
+
+    /// Duel progress for the status bar: whose turn and how long it's been
+    pub fn duel_status(&self) -> Option<String> {
+        let duel = self.duel.as_ref()?;
+        Some(format!(
+            "duel: {}'s turn {}",
+            duel.current_player().name,
+            format_time(duel.turn_elapsed_ms(self.now_ms()))
+        ))
+    }
+
+    /// `duel <name> <name>`, `duel pass`, `duel forfeit` or `duel` for the score
+    fn duel_command(&mut self, args: &[&str]) {
+        let result = match args {
+            [] => match self.duel {
+                Some(ref duel) => Ok(duel.scoreboard()[1..].join(" | ")),
+                None => Err("No duel going on. Start one with `duel <name> <name>`".to_string()),
+            },
+            ["pass"] | ["forfeit"] if self.duel.is_none() => Err("No duel going on".to_string()),
+            ["pass"] => {
+                self.end_duel_turn(false);
+                return;
+            }
+            ["forfeit"] => {
+                self.forfeit_duel();
+                return;
+            }
+            [first, second] => {
+                if let Err(text) = self.start_duel([first, second]) {
+                    self.message = Some(Message {
+                        text,
+                        is_error: true,
+                    });
+                }
+                return;
             }
+            _ => Err("Usage: duel <name> <name> | pass | forfeit".to_string()),
+        };
+        self.message = Some(match result {
+            Ok(text) => Message {
+                text,
+                is_error: false,
+            },
+            Err(text) => Message {
+                text,
+                is_error: true,
+            },
+        });
+    }
+
+    /// Profile the leaderboard files runs under
+    fn leaderboard_profile(&self) -> String {
+        match self.settings.profile_name.as_str() {
+            "" => "Player".to_string(),
+            name => name.to_string(),
         }
     }
 
-    /// Save edited bookmark
-    pub fn save_edited_bookmark(&mut self) {
-        if let Some(ref editing) = self.bookmarks_view_state.editing.clone() {
-            if let Some(ref mut dbg) = self.debugger {
-                dbg.bookmarks.update_note(editing.address, &editing.note);
-                self.bookmarks_view_state.cancel_editing();
+    /// The time attack puzzle was solved: take its split and move on to
+    /// the next one, or put the finished run on the leaderboard
+    fn time_attack_split(&mut self, solved: String) {
+        let (profile, now) = (self.leaderboard_profile(), self.now_ms());
+        let Some(ref mut run) = self.time_attack else {
+            return;
+        };
+        let index = run.splits().len();
+        let Some(split) = run.split(now) else {
+            return;
+        };
+        let best_split = self
+            .leaderboard
+            .best(&profile)
+            .and_then(|best| best.split_ms(index));
+        let mut text = format!(
+            "Split {}/{}: {}",
+            index + 1,
+            run.course().len(),
+            format_time(split)
+        );
+        if let Some(best) = best_split {
+            let (sign, delta) = if split <= best {
+                ('-', best - split)
+            } else {
+                ('+', split - best)
+            };
+            text.push_str(&format!(" ({}{} on best)", sign, format_time(delta)));
+        }
 
-                self.message = Some(Message {
-                    text: format!("Bookmark updated at 0x{:08X}", editing.address),
-                    is_error: false,
-                });
+        if !run.is_finished() {
+            if let Err(e) = self.load_time_attack_puzzle() {
+                text = e;
+            }
+            self.message = Some(Message {
+                text,
+                is_error: false,
+            });
+            return;
+        }
+
+        let splits = run.splits().to_vec();
+        let total_ms = splits.last().copied().unwrap_or(0);
+        let course = run.course().to_vec();
+        self.time_attack = None;
+        let finished_at = self.now_ms() / 1000;
+        let place = self.leaderboard.record(LeaderboardEntry {
+            profile: profile.clone(),
+            total_ms,
+            splits: splits.clone(),
+            finished_at,
+        });
+
+        let mut msg = format!(
+            "{}\n\nTime attack finished in {}",
+            solved,
+            format_time(total_ms)
+        );
+        match place {
+            Some(1) => msg.push_str(" - a new best!"),
+            Some(place) => msg.push_str(&format!(" - #{} for {}", place, profile)),
+            None => {}
+        }
+        let mut previous = 0;
+        for (id, total) in course.iter().zip(&splits) {
+            msg.push_str(&format!(
+                "\n  {:<12} {}  {}",
+                id,
+                format_time(total - previous),
+                format_time(*total)
+            ));
+            previous = *total;
+        }
+        msg.push_str("\n\nBest times:");
+        for (i, entry) in self
+            .leaderboard
+            .for_profile(&profile)
+            .iter()
+            .take(5)
+            .enumerate()
+        {
+            msg.push_str(&format!("\n  {}. {}", i + 1, format_time(entry.total_ms)));
+        }
+        if self.demo.is_none() {
+            if let Err(e) = self
+                .saves()
+                .and_then(|manager| manager.save_leaderboard(&self.leaderboard))
+            {
+                msg.push_str(&format!("\n\nLeaderboard not saved: {}", e));
             }
         }
+        self.screen = Screen::PuzzleComplete { message: msg };
     }
 
-    /// Jump to selected bookmark in bookmarks dialog
-    pub fn goto_selected_bookmark(&mut self) {
-        if let Some(ref dbg) = self.debugger {
-            let bookmarks = dbg.bookmarks.list();
-            if self.bookmarks_view_state.selected < bookmarks.len() {
-                let address = bookmarks[self.bookmarks_view_state.selected].address;
-                self.memory_view_addr = address;
-                self.bookmarks_dialog_open = false;
+    /// Resume the puzzle played most recently
+    pub fn continue_last_puzzle(&mut self) -> Result<(), String> {
+        let path = self
+            .game_state
+            .last_puzzle
+            .clone()
+            .ok_or_else(|| "No puzzle to continue yet".to_string())?;
+        self.load_puzzle_file(&path)
+    }
 
-                self.message = Some(Message {
-                    text: format!("Jumped to 0x{:08X}", address),
-                    is_error: false,
-                });
+    /// Title of the puzzle "Continue" resumes, from the puzzle list when
+    /// it's loaded, otherwise the file name
+    pub fn continue_title(&self) -> Option<String> {
+        let path = self.game_state.last_puzzle.as_ref()?;
+        let listed = self
+            .puzzle_select_state
+            .categories
+            .iter()
+            .flat_map(|category| &category.puzzles)
+            .find(|puzzle| &puzzle.file_path == path)
+            .map(|puzzle| puzzle.title.clone());
+        listed.or_else(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+    }
+
+    /// Main menu entries currently offered
+    pub fn main_menu_items(&self) -> Vec<MainMenuItem> {
+        MainMenuItem::ALL
+            .into_iter()
+            .filter(|item| *item != MainMenuItem::Continue || self.game_state.last_puzzle.is_some())
+            .collect()
+    }
+
+    /// Progress line for the main menu: puzzles solved and current streak
+    pub fn progress_summary(&self) -> String {
+        let ids: Vec<&str> = self
+            .puzzle_select_state
+            .categories
+            .iter()
+            .flat_map(|category| &category.puzzles)
+            .map(|puzzle| puzzle.id.as_str())
+            .collect();
+        let solved = ids
+            .iter()
+            .filter(|id| self.game_state.is_completed(id))
+            .count();
+
+        format!(
+            "Solved {}/{} puzzles ({}%)  Streak: {}",
+            solved,
+            ids.len(),
+            self.game_state.completion_percent(ids.iter().copied()),
+            self.game_state.achievements.current_streak
+        )
+    }
+
+    /// Every puzzle on the select screen, in order
+    fn puzzle_list(&self) -> Vec<PuzzleListItem> {
+        self.puzzle_select_state
+            .categories
+            .iter()
+            .flat_map(|category| category.puzzles.iter().cloned())
+            .collect()
+    }
+
+    /// The puzzle to play next, based on how earlier ones went
+    pub fn recommended_puzzle(&self) -> Option<Recommendation> {
+        recommend(&self.game_state, &self.puzzle_list())
+    }
+
+    /// Puzzles worth revisiting because their concepts gave trouble
+    pub fn review_puzzles(&self) -> HashSet<String> {
+        review_puzzles(&self.game_state, &self.puzzle_list())
+    }
+
+    /// Show the recommended puzzle's details on the select screen
+    pub fn select_recommended(&mut self) {
+        let selected = self.recommended_puzzle().is_some_and(|recommendation| {
+            self.puzzle_select_state.select_puzzle(&recommendation.id)
+        });
+        if !selected {
+            self.message = Some(Message {
+                text: "No puzzle to recommend: everything available is solved".to_string(),
+                is_error: false,
+            });
+        }
+    }
+
+    /// Mastery of each concept, from the puzzles solved so far
+    pub fn skill_tree(&self) -> Vec<ConceptMastery> {
+        skill_tree(&self.game_state, &self.puzzle_list())
+    }
+
+    /// Show the skill tree, loading the puzzle list first if needed
+    pub fn open_skill_tree(&mut self, puzzles_dir: &Path) -> Result<(), String> {
+        if self.puzzle_select_state.categories.is_empty() {
+            self.puzzle_select_state.load_puzzles(puzzles_dir)?;
+        }
+        self.skill_tree_state = SkillTreeState::new();
+        self.screen = Screen::SkillTree;
+        Ok(())
+    }
+
+    /// Show the first unsolved puzzle of the selected concept on the
+    /// puzzle select screen
+    pub fn practice_selected_concept(&mut self) {
+        let tree = self.skill_tree();
+        let Some(node) = tree.get(self.skill_tree_state.selected) else {
+            return;
+        };
+        let text = if !node.unlocked {
+            format!(
+                "{} is locked: master {} first",
+                node.name,
+                node.requires.join(" and ")
+            )
+        } else {
+            match node.puzzles.iter().find(|(_, _, solved)| !solved) {
+                Some((id, _, _)) if self.puzzle_select_state.select_puzzle(id) => {
+                    self.screen = Screen::PuzzleSelect;
+                    return;
+                }
+                _ => format!("Every {} puzzle is solved", node.name),
             }
+        };
+        self.message = Some(Message {
+            text,
+            is_error: !node.unlocked,
+        });
+    }
+
+    /// Set up a puzzle for playing
+    pub fn setup_puzzle(&mut self, puzzle: Puzzle) -> Result<(), String> {
+        log::info!(
+            "Loading puzzle {} ({})",
+            puzzle.metadata.id,
+            puzzle.metadata.title
+        );
+        self.remember_marks();
+        if std::mem::take(&mut self.new_tab_pending) && self.debugger.is_some() {
+            self.open_tab();
+        }
+
+        // Create debugger with puzzle layout
+        let mut debugger = Debugger::with_puzzle_layout(
+            puzzle.entry_point(),
+            puzzle.setup.code_start,
+            puzzle.setup.data_start,
+            puzzle.setup.stack_start,
+        );
+
+        // Load code
+        let code = puzzle.code_bytes()?;
+        debugger
+            .load_code(puzzle.setup.code_start, &code)
+            .map_err(|e| e.to_string())?;
+
+        // Load data if present
+        if let Some(data) = puzzle.data_bytes()? {
+            debugger
+                .load_data(puzzle.setup.data_start, &data)
+                .map_err(|e| e.to_string())?;
+        }
+
+        // Map memory-mapped devices
+        puzzle.map_devices(&mut debugger.memory)?;
+        puzzle.apply_permissions(&mut debugger.memory);
+        puzzle.install_interrupts(&mut debugger)?;
+        puzzle.install_cycles(&mut debugger)?;
+        puzzle.install_trigger(&mut debugger)?;
+        debugger.set_rng_seed(puzzle.setup.rng_seed.unwrap_or_else(|| self.session_seed()));
+        puzzle.mark_data_regions(&mut debugger);
+        let script = puzzle.load_script()?;
+        let hint_engine = HintEngine::new(&puzzle.hints)?;
+
+        // Set up registers
+        let regs = &puzzle.setup.registers;
+        if let Some(v) = regs.eax {
+            debugger.cpu.regs.eax = v;
+        }
+        if let Some(v) = regs.ebx {
+            debugger.cpu.regs.ebx = v;
+        }
+        if let Some(v) = regs.ecx {
+            debugger.cpu.regs.ecx = v;
         }
+        if let Some(v) = regs.edx {
+            debugger.cpu.regs.edx = v;
+        }
+        if let Some(v) = regs.esi {
+            debugger.cpu.regs.esi = v;
+        }
+        if let Some(v) = regs.edi {
+            debugger.cpu.regs.edi = v;
+        }
+        if let Some(v) = regs.ebp {
+            debugger.cpu.regs.ebp = v;
+        }
+        if let Some(v) = regs.esp {
+            debugger.cpu.regs.esp = v;
+        }
+
+        // Save initial state for reset
+        debugger.save_initial_state();
+        debugger.set_capture_policy(self.capture_policy());
+        puzzle.install_input(&mut debugger)?;
+        debugger.set_watches(puzzle.validation.watches());
+        debugger.set_break_on_watch(puzzle.setup.break_on_goal);
+
+        // Breakpoints and bookmarks from the last time this puzzle was open
+        let restored = self.game_state.marks.get(&puzzle.metadata.id).map(|marks| {
+            marks.apply(&mut debugger);
+            (marks.breakpoints.len(), marks.bookmarks.count())
+        });
+
+        // Update app state
+        self.game_state.start_puzzle(&puzzle.metadata.id);
+        self.game_state
+            .coverage
+            .record_seen(&puzzle.metadata.id, puzzle.mnemonics());
+        self.memory_view_addr = puzzle.setup.data_start;
+        self.follow_cpu();
+        self.hint_level = 0;
+        self.debugger = Some(debugger);
+        self.puzzle = Some(puzzle);
+        self.script = script;
+        self.hint_engine = hint_engine;
+        self.last_rule_hint = None;
+        self.previous_trace = None;
+        self.latest_trace = None;
+        self.solution = None;
+        self.search_state.clear_results();
+        self.solution_open = false;
+        self.reveal_confirm_open = false;
+        self.hard_reset_confirm_open = false;
+        self.screen = Screen::Briefing;
+        self.refresh_disasm();
+        self.update_pins();
+        self.update_goal_progress();
+        self.log_learning(LearningEvent::PuzzleStarted);
+        self.start_recording();
+        if let Some((breakpoints, bookmarks)) = restored {
+            self.message = Some(Message {
+                text: format!(
+                    "Restored {} breakpoint{} and {} bookmark{} from last time",
+                    breakpoints,
+                    if breakpoints == 1 { "" } else { "s" },
+                    bookmarks,
+                    if bookmarks == 1 { "" } else { "s" }
+                ),
+                is_error: false,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Record a learning event for the current puzzle, if the player opted in
+    fn log_learning(&mut self, event: LearningEvent) {
+        if !self.settings.learning_log || self.in_sandbox() {
+            return;
+        }
+        let Some(ref puzzle) = self.puzzle else {
+            return;
+        };
+        let now = self.now_ms() / 1000;
+        self.game_state
+            .learning
+            .record(&puzzle.metadata.id, event, now);
+    }
+
+    /// Pick a puzzle to open in a new tab, keeping the current one open
+    pub fn new_tab(&mut self) {
+        self.new_tab_pending = true;
+        self.screen = Screen::PuzzleSelect;
+    }
+
+    /// Keep the breakpoints and bookmarks of every open puzzle in the
+    /// progress save, for when it's loaded again
+    fn remember_marks(&mut self) {
+        let active = (self.puzzle.as_ref(), self.debugger.as_ref());
+        let parked = self
+            .tabs
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != self.active_tab)
+            .map(|(_, session)| (session.puzzle.as_ref(), session.debugger.as_ref()));
+        for (puzzle, debugger) in std::iter::once(active).chain(parked) {
+            if let (Some(puzzle), Some(debugger)) = (puzzle, debugger) {
+                if !puzzle.metadata.id.starts_with(SANDBOX_PREFIX) {
+                    self.game_state
+                        .remember_marks(&puzzle.metadata.id, debugger);
+                }
+            }
+        }
+    }
+
+    /// Park the current puzzle in its tab and make an empty active tab
+    fn open_tab(&mut self) {
+        if self.tabs.is_empty() {
+            self.tabs.push(PuzzleSession::default());
+        }
+        self.tabs.push(PuzzleSession::default());
+        let new = self.tabs.len() - 1;
+        self.swap_session(self.active_tab);
+        self.swap_session(new);
+        self.active_tab = new;
+    }
+
+    /// Titles of the open tabs (none while only one puzzle is open) and the active one
+    pub fn tab_titles(&self) -> (Vec<String>, usize) {
+        let titles = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(i, session)| {
+                if i == self.active_tab {
+                    self.puzzle
+                        .as_ref()
+                        .map(|puzzle| puzzle.metadata.title.clone())
+                        .unwrap_or_default()
+                } else {
+                    session.title()
+                }
+            })
+            .collect();
+        (titles, self.active_tab)
+    }
+
+    /// Switch to another open tab
+    pub fn switch_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() || index == self.active_tab {
+            return;
+        }
+        if self.compare_tab == Some(index) {
+            self.compare_tab = Some(self.active_tab);
+        }
+        self.swap_session(self.active_tab);
+        self.swap_session(index);
+        self.active_tab = index;
+        self.refresh_disasm();
+        self.update_goal_progress();
+
+        let (titles, _) = self.tab_titles();
+        self.message = Some(Message {
+            text: format!("Tab {}/{}: {}", index + 1, titles.len(), titles[index]),
+            is_error: false,
+        });
+    }
+
+    /// Switch to the next (or with `forward` false, previous) tab, wrapping around
+    pub fn cycle_tab(&mut self, forward: bool) {
+        let count = self.tabs.len();
+        if count < 2 {
+            self.message = Some(Message {
+                text: "Only one puzzle open (tab new opens another)".to_string(),
+                is_error: false,
+            });
+            return;
+        }
+        let index = if forward {
+            (self.active_tab + 1) % count
+        } else {
+            (self.active_tab + count - 1) % count
+        };
+        self.switch_tab(index);
+    }
+
+    /// Close the active tab and switch to its neighbor
+    pub fn close_tab(&mut self) {
+        if self.tabs.len() < 2 {
+            self.message = Some(Message {
+                text: "Only one puzzle open".to_string(),
+                is_error: true,
+            });
+            return;
+        }
+        self.compare_tab = None;
+        self.remember_marks();
+        let closing = self.active_tab;
+        let next = if closing + 1 < self.tabs.len() {
+            closing + 1
+        } else {
+            closing - 1
+        };
+        self.swap_session(closing);
+        self.swap_session(next);
+        self.tabs.remove(closing);
+        self.active_tab = if next > closing { next - 1 } else { next };
+        if self.tabs.len() == 1 {
+            self.tabs.clear();
+            self.active_tab = 0;
+        }
+        self.refresh_disasm();
+        self.update_goal_progress();
+    }
+
+    /// Show another tab side by side with the active one (the next tab by default)
+    pub fn start_compare(&mut self, index: Option<usize>) -> Result<(), String> {
+        if self.tabs.len() < 2 {
+            return Err("Open a second puzzle to compare with (tab new)".to_string());
+        }
+        let index = index.unwrap_or((self.active_tab + 1) % self.tabs.len());
+        if index >= self.tabs.len() || index == self.active_tab {
+            return Err(format!(
+                "Pick another open tab to compare with (1-{})",
+                self.tabs.len()
+            ));
+        }
+        self.compare_tab = Some(index);
+        Ok(())
+    }
+
+    pub fn stop_compare(&mut self) {
+        self.compare_tab = None;
+    }
+
+    /// The session shown beside the active one, when comparing
+    pub fn compared_session(&self) -> Option<&PuzzleSession> {
+        self.compare_tab.and_then(|index| self.tabs.get(index))
+    }
+
+    /// Both sides of the compare view, active tab first
+    pub fn compare_sides(&self) -> Option<(CompareSide<'_>, CompareSide<'_>)> {
+        let other = self.compared_session()?;
+        let left = compare_side(
+            self.debugger.as_ref()?,
+            self.puzzle.as_ref()?,
+            &self.disasm_cache,
+            self.active_tab,
+        );
+        let right = compare_side(
+            other.debugger.as_ref()?,
+            other.puzzle.as_ref()?,
+            &other.disasm_cache,
+            self.compare_tab?,
+        );
+        Some((left, right))
+    }
+
+    /// Repeat a step in the compared tab, when stepping is synced
+    fn step_compared(&mut self) {
+        let Some(session) = self.compare_tab.and_then(|index| self.tabs.get_mut(index)) else {
+            return;
+        };
+        if let Some(ref mut debugger) = session.debugger {
+            // A halted or faulted side just stays where it is
+            debugger.step().ok();
+            session.disasm_cache =
+                debugger.disassemble_in(session.disasm_mode, debugger.cpu.eip, 20);
+        }
+    }
+
+    /// Exchange the puzzle state on `App` with the session in tab `index`
+    fn swap_session(&mut self, index: usize) {
+        let mut session = std::mem::take(&mut self.tabs[index]);
+        self.exchange_session(&mut session);
+        self.tabs[index] = session;
+    }
+
+    /// Exchange the puzzle state on `App` with `session`
+    fn exchange_session(&mut self, session: &mut PuzzleSession) {
+        use std::mem::swap;
+
+        swap(&mut self.debugger, &mut session.debugger);
+        swap(&mut self.puzzle, &mut session.puzzle);
+        swap(&mut self.script, &mut session.script);
+        swap(&mut self.hint_engine, &mut session.hint_engine);
+        swap(&mut self.last_rule_hint, &mut session.last_rule_hint);
+        swap(&mut self.hint_level, &mut session.hint_level);
+        swap(&mut self.focused, &mut session.focused);
+        swap(&mut self.data_tab, &mut session.data_tab);
+        swap(&mut self.disasm_selection, &mut session.disasm_selection);
+        swap(&mut self.selected_register, &mut session.selected_register);
+        swap(&mut self.memory_view_addr, &mut session.memory_view_addr);
+        swap(&mut self.disasm_view_addr, &mut session.disasm_view_addr);
+        swap(&mut self.disasm_mode, &mut session.disasm_mode);
+        swap(&mut self.stack_view_addr, &mut session.stack_view_addr);
+        swap(&mut self.collapsed_frame, &mut session.collapsed_frame);
+        swap(&mut self.pseudo_open, &mut session.pseudo_open);
+        swap(&mut self.changed_registers, &mut session.changed_registers);
+        swap(&mut self.pins, &mut session.pins);
+        swap(&mut self.disasm_cache, &mut session.disasm_cache);
+        swap(&mut self.run_explanation, &mut session.run_explanation);
+        swap(&mut self.last_validation, &mut session.last_validation);
+        swap(&mut self.trace_diff, &mut session.trace_diff);
+        swap(&mut self.state_diff, &mut session.state_diff);
+        swap(&mut self.flag_hit, &mut session.flag_hit);
+        swap(&mut self.previous_trace, &mut session.previous_trace);
+        swap(&mut self.latest_trace, &mut session.latest_trace);
+        swap(&mut self.recording, &mut session.recording);
+        swap(
+            &mut self.recording_started_ms,
+            &mut session.recording_started_ms,
+        );
+        swap(&mut self.ghost, &mut session.ghost);
+
+        let game = &mut self.game_state;
+        swap(&mut game.current_puzzle_id, &mut session.puzzle_id);
+        swap(&mut game.hints_used, &mut session.hints_used);
+        swap(&mut game.patches_made, &mut session.patches_made);
+        swap(&mut game.puzzle_start_time, &mut session.puzzle_start_time);
+        swap(&mut game.puzzle_assist, &mut session.puzzle_assist);
+    }
+
+    /// Refresh disassembly cache
+    pub fn refresh_disasm(&mut self) {
+        if let Some(ref mut debugger) = self.debugger {
+            let addr = self.disasm_view_addr.unwrap_or(debugger.cpu.eip);
+            self.disasm_cache = debugger.disassemble_in(self.disasm_mode, addr, 20);
+        }
+    }
+
+    /// Re-evaluate the pinned expressions against the current state
+    fn update_pins(&mut self) {
+        if let Some(ref debugger) = self.debugger {
+            self.pins.update(&ScriptContext::from_debugger(debugger));
+        }
+    }
+
+    /// Add the instructions executed since last time to the coverage of
+    /// the current puzzle
+    fn record_coverage(&mut self) {
+        let (Some(debugger), Some(puzzle)) = (self.debugger.as_mut(), self.puzzle.as_ref()) else {
+            return;
+        };
+        let counts = debugger.take_mnemonic_counts();
+        self.game_state
+            .coverage
+            .record_executed(&puzzle.metadata.id, counts);
+    }
+
+    /// Check each part of the goal against the current state, without
+    /// firing the puzzle script or counting an attempt
+    fn update_goal_progress(&mut self) {
+        let objectives = self.objectives();
+        self.goal_progress = (!objectives.is_empty()).then(|| {
+            (
+                objectives.iter().filter(|o| o.met).count(),
+                objectives.len(),
+            )
+        });
+    }
+
+    /// How many parts of the goal hold right now, out of how many (the
+    /// goal meter in the status bar)
+    pub fn goal_progress(&self) -> Option<(usize, usize)> {
+        self.goal_progress
+    }
+
+    /// Pin an expression to the Watches panel
+    pub fn pin_expression(&mut self, text: &str) -> Result<(), String> {
+        let debugger = self.debugger.as_ref().ok_or("No debugger active")?;
+        self.pins
+            .add(text, &ScriptContext::from_debugger(debugger))?;
+        let pin = &self.pins.list()[self.pins.list().len() - 1];
+        let text = match pin.value {
+            Ok(value) => format!("Pinned {} = 0x{:08X}", pin.text, value),
+            Err(ref e) => format!("Pinned {} ({})", pin.text, e),
+        };
+        self.message = Some(Message {
+            text,
+            is_error: false,
+        });
+        Ok(())
+    }
+
+    /// Unpin the expression numbered `which` in the Watches panel, or `all`
+    pub fn unpin_expression(&mut self, which: &str) -> Result<(), String> {
+        if which == "all" {
+            self.pins.clear();
+            self.message = Some(Message {
+                text: "Unpinned all expressions".to_string(),
+                is_error: false,
+            });
+            return Ok(());
+        }
+        let pin = which
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|index| self.pins.remove(index))
+            .ok_or_else(|| format!("No pin numbered {} (1-{})", which, self.pins.list().len()))?;
+        self.message = Some(Message {
+            text: format!("Unpinned {}", pin.text),
+            is_error: false,
+        });
+        Ok(())
+    }
+
+    /// Make the disassembly and stack views follow EIP/ESP again
+    pub fn follow_cpu(&mut self) {
+        self.disasm_view_addr = None;
+        self.stack_view_addr = None;
+    }
+
+    /// Step one instruction (in the compared tab too, when synced)
+    pub fn step(&mut self) {
+        if self.step_once() {
+            self.log_learning(LearningEvent::Step);
+            if self.compare_sync {
+                self.step_compared();
+            }
+        }
+    }
+
+    /// Step one instruction, returning whether it executed
+    fn step_once(&mut self) -> bool {
+        self.follow_cpu();
+        if let Some(ref mut debugger) = self.debugger {
+            self.changed_registers.clear();
+            // Only a RET taken by this step collapses a frame
+            debugger.take_collapsed_frame();
+
+            match debugger.step() {
+                Ok(result) => {
+                    for reg in result.changed_registers {
+                        self.changed_registers.insert(reg);
+                    }
+                    if !self.changed_registers.is_empty() {
+                        self.animations.register_flash.start();
+                    }
+                    let returned = debugger.take_collapsed_frame();
+                    let hit = debugger.take_watch_hit();
+                    self.flag_hit = debugger.take_flag_hit();
+                    self.record_coverage();
+                    self.refresh_disasm();
+                    self.update_pins();
+                    self.update_goal_progress();
+                    if let Some(frame) = returned {
+                        let value = frame.return_value.unwrap_or_default();
+                        self.message = Some(Message {
+                            text: format!(
+                                "Returned from 0x{:08X} with EAX = 0x{:08X} ({})",
+                                frame.entry, value, value as i32
+                            ),
+                            is_error: false,
+                        });
+                        if self.settings.stack_frames {
+                            self.collapsed_frame = Some(frame);
+                            self.animations.frame_collapse.start();
+                        }
+                    }
+                    if self.settings.announce {
+                        self.announce_step();
+                    }
+                    if let Some(hit) = hit {
+                        self.message = Some(Message {
+                            text: format!("Goal: {}", hit.describe()),
+                            is_error: false,
+                        });
+                    }
+                    if let Some(ref hit) = self.flag_hit {
+                        self.message = Some(Message {
+                            text: hit.describe(),
+                            is_error: false,
+                        });
+                    }
+                    if let DebuggerState::Error(ref e) = result.state {
+                        self.message = Some(Message {
+                            text: stop_message(e),
+                            is_error: true,
+                        });
+                    }
+                    self.record_replay("step".to_string());
+                    self.fire_script(ScriptEvent::Step);
+                    self.check_completion();
+                    return true;
+                }
+                Err(e) => {
+                    self.message = Some(Message {
+                        text: format!("Error: {}", e),
+                        is_error: true,
+                    });
+                }
+            }
+        }
+        false
+    }
+
+    /// Show the dword at the top of the memory view in the endianness
+    /// visualizer, or an example when no program is loaded
+    pub fn open_endian_view(&mut self) {
+        let interpretation = self
+            .endian
+            .take()
+            .map(|e| e.interpretation)
+            .unwrap_or_default();
+        let address = self.memory_view_addr;
+        let state = match self
+            .get_memory(address, 4)
+            .and_then(|bytes| bytes.try_into().ok())
+        {
+            Some(bytes) => EndianState {
+                address,
+                bytes,
+                interpretation,
+                sample: false,
+            },
+            None => EndianState {
+                address: 0,
+                bytes: SAMPLE_BYTES,
+                interpretation,
+                sample: true,
+            },
+        };
+        self.endian = Some(state);
+    }
+
+    /// Slide the visualizer by a byte, moving the memory view along
+    pub fn move_endian_view(&mut self, delta: i32) {
+        if self.endian.as_ref().is_none_or(|e| e.sample) {
+            return;
+        }
+        self.memory_view_addr = self.memory_view_addr.wrapping_add_signed(delta);
+        self.open_endian_view();
+    }
+
+    /// Evaluate a converter expression against the current registers and memory
+    pub fn convert(&self, text: &str) -> Result<Conversion, String> {
+        let ctx = self.debugger.as_ref().map(ScriptContext::from_debugger);
+        Conversion::evaluate(text, ctx.as_ref())
+    }
+
+    /// Memory regions for the entropy overlay (the first 64 KiB of each),
+    /// or the bytes around the memory view when the layout has no regions
+    pub fn entropy_regions(&self) -> Vec<EntropyRegion> {
+        const LIMIT: u32 = 0x10000;
+        let Some(ref debugger) = self.debugger else {
+            return Vec::new();
+        };
+
+        let regions: Vec<EntropyRegion> = debugger
+            .memory
+            .regions()
+            .iter()
+            .filter_map(|region| {
+                let bytes = debugger
+                    .memory
+                    .slice(region.start, region.size().min(LIMIT) as usize)?;
+                Some(EntropyRegion {
+                    name: region.name.clone(),
+                    start: region.start,
+                    bytes,
+                })
+            })
+            .collect();
+        if !regions.is_empty() {
+            return regions;
+        }
+        self.get_memory(self.memory_view_addr, 256)
+            .map(|bytes| {
+                vec![EntropyRegion {
+                    name: "view".to_string(),
+                    start: self.memory_view_addr,
+                    bytes,
+                }]
+            })
+            .unwrap_or_default()
+    }
+
+    /// Well-known constants used as immediates in the disassembly or stored
+    /// in non-executable memory, code first
+    pub fn magic_constants(&self) -> Vec<ConstantMatch> {
+        let mut found = constants_in_code(&self.disasm_cache);
+        if let Some(ref debugger) = self.debugger {
+            for region in debugger
+                .memory
+                .regions()
+                .iter()
+                .filter(|region| !region.permissions.execute)
+            {
+                if let Some(bytes) = debugger
+                    .memory
+                    .slice(region.start, region.size().min(0x10000) as usize)
+                {
+                    found.extend(constants_in_memory(&bytes, region.start));
+                }
+            }
+        }
+        found
+    }
+
+    /// Open the magic constant popup, on the constant under the disassembly cursor if any
+    pub fn open_constants(&mut self) {
+        let cursor = self
+            .disasm_cache
+            .get(self.disasm_selection)
+            .map(|line| line.address);
+        let selected = self
+            .magic_constants()
+            .iter()
+            .position(|found| Some(found.address) == cursor)
+            .unwrap_or(0);
+        self.constants = Some(ConstantsState { selected });
+    }
+
+    /// Close the popup and show the selected constant in the disassembly or memory view
+    pub fn goto_selected_constant(&mut self) {
+        let Some(state) = self.constants.take() else {
+            return;
+        };
+        if let Some(found) = self.magic_constants().get(state.selected) {
+            self.focused = match found.source {
+                ConstantSource::Immediate => FocusedPanel::Disassembly,
+                ConstantSource::Memory => FocusedPanel::Memory,
+            };
+            self.goto_address(found.address);
+        }
+    }
+
+    /// List every register, stack slot and memory address holding `text`'s
+    /// value, in either byte order
+    pub fn find_value(&mut self, text: &str) {
+        if text.is_empty() {
+            self.message = Some(Message {
+                text: "Usage: find <value>".to_string(),
+                is_error: true,
+            });
+            return;
+        }
+        let value = match self.convert(text) {
+            Ok(c) => c.value,
+            Err(e) => {
+                self.message = Some(Message {
+                    text: e,
+                    is_error: true,
+                });
+                return;
+            }
+        };
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
+        let hits =
+            MemorySearch::find_value(&debugger.cpu, &debugger.memory, value, FIND_STACK_SLOTS);
+        self.finder = Some(FindState::new(value, hits));
+    }
+
+    /// Close the find popup and show the selected hit in its panel
+    pub fn goto_selected_hit(&mut self) {
+        let Some(state) = self.finder.take() else {
+            return;
+        };
+        let Some(hit) = state.hits.get(state.selected) else {
+            return;
+        };
+        match hit.location {
+            ValueLocation::Register(reg) => {
+                self.focused = FocusedPanel::Registers;
+                self.selected_register = Register::ALL.iter().position(|r| *r == reg).unwrap_or(0);
+            }
+            ValueLocation::Stack { address, .. } => {
+                self.focused = FocusedPanel::Stack;
+                self.goto_address(address);
+            }
+            ValueLocation::Memory { address } => {
+                self.focused = FocusedPanel::Memory;
+                self.goto_address(address);
+            }
+        }
+    }
+
+    /// Open the ASCII table, searching for `query` if given
+    pub fn open_ascii_table(&mut self, query: &str) {
+        let mut state = AsciiTableState::new();
+        for c in query.chars() {
+            state.type_query(Some(c));
+        }
+        self.ascii_table = Some(state);
+    }
+
+    /// Follow `value` as a pointer from `origin`, or explain why it isn't one
+    fn open_follow(&mut self, origin: String, value: u32) {
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
+        match chase_pointer(&debugger.memory, value) {
+            Some(chain) => self.follow = Some(FollowState { origin, chain }),
+            None => {
+                self.message = Some(Message {
+                    text: format!("{} = 0x{:08X}, which isn't an address", origin, value),
+                    is_error: true,
+                });
+            }
+        }
+    }
+
+    /// Follow the pointer in the selected register, the top stack slot or
+    /// the first dword of the memory view, depending on the focused panel
+    pub fn follow_pointer(&mut self) {
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
+        let (origin, value) = match self.focused {
+            FocusedPanel::Stack => {
+                let address = self.stack_view_addr.unwrap_or(debugger.cpu.regs.esp);
+                (
+                    format!("[0x{:08X}]", address),
+                    debugger.memory.slice(address, 4),
+                )
+            }
+            FocusedPanel::Memory => (
+                format!("[0x{:08X}]", self.memory_view_addr),
+                debugger.memory.slice(self.memory_view_addr, 4),
+            ),
+            _ => {
+                let reg = Register::ALL[self.selected_register % Register::ALL.len()];
+                (
+                    reg.name().to_string(),
+                    Some(debugger.cpu.get_register(reg).to_le_bytes().to_vec()),
+                )
+            }
+        };
+        match value {
+            Some(bytes) => self.open_follow(
+                origin,
+                u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            ),
+            None => {
+                self.message = Some(Message {
+                    text: format!("{} is outside memory", origin),
+                    is_error: true,
+                });
+            }
+        }
+    }
+
+    /// Follow the value of an expression (`follow dword[esp+4]`) as a pointer
+    pub fn follow_expression(&mut self, text: &str) {
+        if text.is_empty() {
+            self.follow_pointer();
+            return;
+        }
+        match self.convert(text) {
+            Ok(c) => self.open_follow(text.to_string(), c.value),
+            Err(e) => {
+                self.message = Some(Message {
+                    text: e,
+                    is_error: true,
+                })
+            }
+        }
+    }
+
+    /// Close the pointer chain and show where it ended in the memory view
+    pub fn goto_followed(&mut self) {
+        let Some(state) = self.follow.take() else {
+            return;
+        };
+        if let Some(&address) = state.chain.addresses.last() {
+            self.focused = FocusedPanel::Memory;
+            self.goto_address(address);
+        }
+    }
+
+    /// Disassembly from the selected line on, as address, bytes and instruction
+    pub fn disasm_copy_text(&self, count: usize) -> Option<String> {
+        let lines: Vec<String> = self
+            .disasm_cache
+            .iter()
+            .skip(self.disasm_selection)
+            .take(count.max(1))
+            .map(|line| {
+                let bytes: Vec<String> = line.bytes.iter().map(|b| format!("{:02X}", b)).collect();
+                format!(
+                    "{:08X}  {:<20}  {}",
+                    line.address,
+                    bytes.join(" "),
+                    line.text
+                )
+            })
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// Hex dump of memory from the memory view's address, 16 bytes a row
+    pub fn memory_copy_text(&self, length: usize) -> Option<String> {
+        let bytes = self.get_memory(self.memory_view_addr, length.max(1))?;
+        let rows: Vec<String> = bytes
+            .chunks(16)
+            .enumerate()
+            .map(|(row, chunk)| {
+                let hex: Vec<String> = chunk.iter().map(|b| format!("{:02X}", b)).collect();
+                let ascii: String = chunk
+                    .iter()
+                    .map(|&b| {
+                        if (0x20..0x7F).contains(&b) {
+                            b as char
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect();
+                format!(
+                    "{:08X}  {:<47}  {}",
+                    self.memory_view_addr.wrapping_add(row as u32 * 16),
+                    hex.join(" "),
+                    ascii
+                )
+            })
+            .collect();
+        Some(rows.join("\n"))
+    }
+
+    /// Copy what the player is looking at: the completion summary, a memory
+    /// dump when memory or the stack is focused, otherwise the selected
+    /// disassembly line
+    pub fn copy_focused(&mut self) {
+        if let Screen::PuzzleComplete { ref message } | Screen::SolutionRevealed { ref message } =
+            self.screen
+        {
+            let text = message.clone();
+            self.copy_to_clipboard(text, "completion summary");
+            return;
+        }
+        match self.focused {
+            FocusedPanel::Memory | FocusedPanel::Stack => self.copy("memory", None),
+            _ => self.copy("disasm", None),
+        }
+    }
+
+    /// Copy `disasm [lines]`, `memory [bytes]` or the completion `summary`
+    pub fn copy(&mut self, what: &str, count: Option<usize>) {
+        let copied = match what {
+            "disasm" | "d" => self
+                .disasm_copy_text(count.unwrap_or(1))
+                .map(|text| (text, "disassembly")),
+            "memory" | "mem" | "m" => self
+                .memory_copy_text(count.unwrap_or(64))
+                .map(|text| (text, "memory dump")),
+            "summary" => match self.screen {
+                Screen::PuzzleComplete { ref message }
+                | Screen::SolutionRevealed { ref message } => {
+                    Some((message.clone(), "completion summary"))
+                }
+                _ => None,
+            },
+            _ => {
+                self.message = Some(Message {
+                    text: "Usage: copy disasm [lines] | memory [bytes] | summary".to_string(),
+                    is_error: true,
+                });
+                return;
+            }
+        };
+        match copied {
+            Some((text, what)) => self.copy_to_clipboard(text, what),
+            None => {
+                self.message = Some(Message {
+                    text: "Nothing to copy".to_string(),
+                    is_error: true,
+                });
+            }
+        }
+    }
+
+    /// `script run <file>` loads a tool script's commands, `script` lists
+    /// them and `script clear` drops them
+    fn script_command(&mut self, args: &[&str]) {
+        let (text, is_error) = match args {
+            ["run", path] => match std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path, e))
+                .and_then(|source| {
+                    self.with_tool_engine(|engine| ToolScript::load(engine, &source))
+                }) {
+                Ok(script) => {
+                    let commands = script.commands();
+                    let names: Vec<&str> = commands.iter().map(|tool| tool.name.as_str()).collect();
+                    let text =
+                        format!("Loaded {} from {}: {}", names.len(), path, names.join(", "));
+                    for tool in commands {
+                        self.tool_commands.insert(tool.name.to_lowercase(), tool);
+                    }
+                    (text, false)
+                }
+                Err(e) => (e, true),
+            },
+            [] | ["list"] if self.tool_commands.is_empty() => (
+                "No tool commands. Load some with: script run <file>".to_string(),
+                false,
+            ),
+            [] | ["list"] => {
+                let usage: Vec<String> = self
+                    .tool_commands
+                    .values()
+                    .map(ToolCommand::usage)
+                    .collect();
+                (format!("Tool commands: {}", usage.join(", ")), false)
+            }
+            ["clear"] => {
+                self.tool_commands.clear();
+                ("Tool commands cleared".to_string(), false)
+            }
+            _ => ("Usage: script run <file> | list | clear".to_string(), true),
+        };
+        self.message = Some(Message { text, is_error });
+    }
+
+    /// Run a tool script command with its arguments
+    fn run_tool_command(&mut self, tool: &ToolCommand, args: &[&str]) {
+        let result = if args.len() < tool.params.len() {
+            Err(format!("Usage: {}", tool.usage()))
+        } else if self.tool_depth >= MAX_TOOL_DEPTH {
+            Err("Tool commands call each other too deeply".to_string())
+        } else {
+            self.tool_depth += 1;
+            let result = self.with_tool_engine(|engine| tool.call(engine, args));
+            self.tool_depth -= 1;
+            result
+        };
+        match result {
+            Ok(Some(text)) => {
+                self.message = Some(Message {
+                    text,
+                    is_error: false,
+                })
+            }
+            Ok(None) => {}
+            Err(e) => {
+                self.message = Some(Message {
+                    text: format!("{}: {}", tool.name, e),
+                    is_error: true,
+                })
+            }
+        }
+    }
+
+    /// Run `f` with an engine for tool scripts. Its `run` and `variable`
+    /// functions and the debugger reads need the app while a script runs,
+    /// so the app is lent to the engine and put back afterwards.
+    fn with_tool_engine<T>(&mut self, f: impl FnOnce(&Engine) -> T) -> T {
+        let app = Rc::new(RefCell::new(std::mem::take(self)));
+        let mut engine = sandboxed_engine();
+
+        let lent = app.clone();
+        register_debugger_api(
+            &mut engine,
+            Rc::new(move |read| {
+                let app = lent.borrow();
+                let debugger = app.debugger.as_ref().ok_or("No program loaded")?;
+                read(&ScriptContext::from_debugger(debugger))
+            }),
+        );
+        let lent = app.clone();
+        engine.register_fn("run", move |line: &str| -> Result<(), Box<EvalAltResult>> {
+            let mut app = lent.borrow_mut();
+            app.message = None;
+            app.process_command(line);
+            match app.message {
+                Some(Message {
+                    ref text,
+                    is_error: true,
+                }) => Err(text.clone().into()),
+                _ => Ok(()),
+            }
+        });
+        let lent = app.clone();
+        engine.register_fn("variable", move |name: &str| {
+            lent.borrow()
+                .alias_variable(name)
+                .map_or(Dynamic::UNIT, Dynamic::from)
+        });
+
+        let result = f(&engine);
+        drop(engine);
+        match Rc::try_unwrap(app) {
+            Ok(app) => *self = app.into_inner(),
+            Err(_) => unreachable!("the tool engine holds the only other references to the app"),
+        }
+        result
+    }
+
+    /// Copy a link that opens the current puzzle in the web build
+    fn share_puzzle(&mut self) {
+        let Some(ref puzzle) = self.puzzle else {
+            self.message = Some(Message {
+                text: "No puzzle to share".to_string(),
+                is_error: true,
+            });
+            return;
+        };
+        match share_link(puzzle) {
+            Ok(link) => self.copy_to_clipboard(link, "share link"),
+            Err(text) => {
+                self.message = Some(Message {
+                    text,
+                    is_error: true,
+                })
+            }
+        }
+    }
+
+    fn copy_to_clipboard(&mut self, text: String, what: &str) {
+        let lines = text.lines().count();
+        self.clipboard = Some(text);
+        self.message = Some(Message {
+            text: format!(
+                "Copied {} ({} line{})",
+                what,
+                lines,
+                if lines == 1 { "" } else { "s" }
+            ),
+            is_error: false,
+        });
+    }
+
+    /// Paste into whichever text input is active. Line breaks become spaces,
+    /// since every input is a single line.
+    pub fn paste(&mut self, text: &str) {
+        let text: String = text
+            .trim_end_matches(['\r', '\n'])
+            .chars()
+            .map(|c| if c == '\n' || c == '\r' { ' ' } else { c })
+            .collect();
+
+        let input = if self.converter_open {
+            &mut self.converter_state.input
+        } else if let Some(ref mut decoder) = self.decoder {
+            &mut decoder.input
+        } else if self.search_dialog_open {
+            &mut self.search_state.input
+        } else if self.goto_dialog_open {
+            &mut self.goto_state.input
+        } else if self.input_dialog_open {
+            &mut self.input_state.text
+        } else if self.predict_dialog_open {
+            &mut self.predict_state.text
+        } else if self.screen == Screen::Debugger {
+            self.focused = FocusedPanel::Command;
+            &mut self.command_input
+        } else {
+            return;
+        };
+        input.push_str(&text);
+    }
+
+    /// Open the data decoder on the memory view's address
+    pub fn open_decoder(&mut self) {
+        self.decoder = Some(DecoderState::new(self.memory_view_addr));
+    }
+
+    /// Bytes in the decoder's range, as they are in memory
+    pub fn decoder_input(&self) -> Vec<u8> {
+        self.decoder
+            .as_ref()
+            .and_then(|state| self.get_memory(state.address, state.length))
+            .unwrap_or_default()
+    }
+
+    /// The decoder's transformation applied to its range
+    pub fn decoder_preview(&self) -> Result<(Transform, Vec<u8>), String> {
+        let state = self.decoder.as_ref().ok_or("Decoder is not open")?;
+        let transform = Transform::parse(&state.input)?;
+        let bytes = transform.apply(&self.decoder_input())?;
+        Ok((transform, bytes))
+    }
+
+    /// Write the decoded bytes back over the range as a patch
+    pub fn apply_decoder(&mut self) -> Result<(), String> {
+        let (_, bytes) = self.decoder_preview()?;
+        let address = self
+            .decoder
+            .as_ref()
+            .map(|state| state.address)
+            .unwrap_or_default();
+        self.patch_memory(address, &bytes)?;
+        self.decoder = None;
+        Ok(())
+    }
+
+    /// What predict mode asks before each step
+    pub fn predict_mode(&self) -> PredictMode {
+        PredictMode::from_name(&self.settings.predict_mode)
+    }
+
+    /// Step one instruction, first asking for a prediction in predict mode.
+    /// Returns whether the step happened.
+    pub fn request_step(&mut self) -> bool {
+        let Some(ref debugger) = self.debugger else {
+            return false;
+        };
+        if self.predict_mode() == PredictMode::Off || debugger.cpu.halted {
+            self.step();
+            return true;
+        }
+
+        self.predict_state.clear();
+        self.predict_state.instruction = self.instruction_at_eip().unwrap_or_default();
+        self.predict_dialog_open = true;
+        false
+    }
+
+    /// Disassembly text of the instruction about to execute
+    fn instruction_at_eip(&self) -> Option<String> {
+        let eip = self.current_eip()?;
+        self.disasm_cache
+            .iter()
+            .find(|line| line.address == eip)
+            .map(|line| line.text.clone())
+    }
+
+    /// Step, then grade the dialog's prediction against what actually changed
+    pub fn submit_prediction(&mut self) {
+        let mode = self.predict_mode();
+        let guess = match mode.parse(&self.predict_state.text) {
+            Ok(guess) => guess,
+            Err(e) => {
+                self.message = Some(Message {
+                    text: e,
+                    is_error: true,
+                });
+                return;
+            }
+        };
+        let Some(before) = self.debugger.as_ref().map(|d| d.cpu.clone()) else {
+            return;
+        };
+        self.predict_dialog_open = false;
+        let instruction = std::mem::take(&mut self.predict_state.instruction);
+        self.predict_state.clear();
+
+        self.message = None;
+        if !self.step_once() {
+            return;
+        }
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
+        let actual = mode.changes(&before, &debugger.cpu);
+
+        let correct = guess == actual;
+        let mnemonic = instruction
+            .split_whitespace()
+            .next()
+            .unwrap_or("?")
+            .to_uppercase();
+        self.game_state.predictions.record(&mnemonic, correct);
+
+        let mut text = if correct {
+            format!(
+                "Predicted right: {} (streak {})",
+                describe_changes(&actual),
+                self.game_state.predictions.streak
+            )
+        } else {
+            format!(
+                "Predicted {}, but {} changed",
+                describe_changes(&guess),
+                describe_changes(&actual)
+            )
+        };
+        if let Some(achievement) = self
+            .game_state
+            .achievements
+            .record_prediction_streak(self.game_state.predictions.streak)
+            .filter(|_| self.demo.is_none())
+        {
+            text = format!(
+                "Achievement Unlocked: {} {} (+{} pts)",
+                achievement.icon(),
+                achievement.name(),
+                achievement.points()
+            );
+        }
+        // Keep whatever the step itself reported (goal hits, validation)
+        match self.message {
+            Some(ref mut message) => message.text = format!("{}. {}", text, message.text),
+            None => {
+                self.message = Some(Message {
+                    text,
+                    is_error: false,
+                })
+            }
+        }
+    }
+
+    /// Run until breakpoint or halt
+    pub fn run(&mut self) {
+        self.run_with(self.capture_policy());
+    }
+
+    /// Run once without capturing history, regardless of the fast run setting.
+    /// Steps taken afterwards are recorded as usual.
+    pub fn fast_run(&mut self) {
+        self.run_with(CapturePolicy::StepOnly);
+    }
+
+    /// Run with the given capture policy, then restore the configured one
+    fn run_with(&mut self, policy: CapturePolicy) {
+        self.follow_cpu();
+        self.log_learning(LearningEvent::Run);
+        let configured = self.capture_policy();
+        if let Some(ref mut debugger) = self.debugger {
+            self.changed_registers.clear();
+
+            self.last_validation = None;
+            debugger.set_capture_policy(policy);
+            let run_result = debugger.run();
+            debugger.set_capture_policy(configured);
+            debugger.take_watch_hit();
+            self.flag_hit = debugger.take_flag_hit();
+            if let Some(trace) = debugger.last_trace() {
+                self.previous_trace = self.latest_trace.replace(trace.clone());
+            }
+            self.record_coverage();
+
+            match run_result {
+                Ok(result) => {
+                    let is_error = matches!(result, revgame_core::debugger::RunResult::Error(_));
+                    let mut msg = match result {
+                        revgame_core::debugger::RunResult::Breakpoint(addr) => {
+                            format!("Breakpoint at 0x{:08X}", addr)
+                        }
+                        revgame_core::debugger::RunResult::Watch(hit) => {
+                            format!("Goal: {}", hit.describe())
+                        }
+                        revgame_core::debugger::RunResult::FlagChange(hit) => hit.describe(),
+                        revgame_core::debugger::RunResult::Halted => "Program halted".to_string(),
+                        revgame_core::debugger::RunResult::LimitExceeded(n) => {
+                            format!("Execution limit exceeded ({} instructions)", n)
+                        }
+                        revgame_core::debugger::RunResult::Error(ref e) => stop_message(e),
+                    };
+                    if policy.captures_runs() {
+                        msg.push_str("  [E] Explain");
+                        if self.previous_trace.is_some() {
+                            msg.push_str("  [D] Diff");
+                        }
+                    } else {
+                        msg.push_str(" (fast run: no step-back)");
+                    }
+                    self.message = Some(Message {
+                        text: msg,
+                        is_error,
+                    });
+                    self.refresh_disasm();
+                    self.update_pins();
+                    self.update_goal_progress();
+                    self.record_replay("run".to_string());
+                    self.fire_script(ScriptEvent::Step);
+                    self.check_completion();
+                }
+                Err(e) => {
+                    self.message = Some(Message {
+                        text: format!("Error: {}", e),
+                        is_error: true,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Reset puzzle to initial state
+    pub fn reset(&mut self) {
+        self.restart(false);
+    }
+
+    /// Reset and discard every patch, back to the puzzle as loaded
+    pub fn hard_reset(&mut self) {
+        self.hard_reset_confirm_open = false;
+        self.restart(true);
+    }
+
+    /// Ask before a hard reset that would throw patches away
+    pub fn request_hard_reset(&mut self) {
+        let patched = self
+            .debugger
+            .as_ref()
+            .is_some_and(|debugger| debugger.can_undo() || debugger.can_redo());
+        if patched {
+            self.hard_reset_confirm_open = true;
+        } else {
+            self.hard_reset();
+        }
+    }
+
+    /// Restart the program, keeping the player's patches unless `hard`
+    fn restart(&mut self, hard: bool) {
+        self.follow_cpu();
+        if let Some(ref mut script) = self.script {
+            script.reset();
+        }
+        self.last_validation = None;
+        self.flag_hit = None;
+        if let Some(ref mut debugger) = self.debugger {
+            let kept = if hard {
+                debugger.reset();
+                0
+            } else {
+                debugger.soft_reset();
+                debugger.net_patches().len()
+            };
+            self.refresh_disasm();
+            self.update_pins();
+            self.update_goal_progress();
+            self.changed_registers.clear();
+            let text = match kept {
+                _ if hard => "Reset to initial state; patches discarded".to_string(),
+                0 => "Reset to initial state".to_string(),
+                n => format!(
+                    "Reset to initial state, keeping {} patch{} (Shift+F4 or `reset hard` discards them)",
+                    n,
+                    if n == 1 { "" } else { "es" }
+                ),
+            };
+            self.message = Some(Message {
+                text,
+                is_error: false,
+            });
+            self.record_replay(if hard { "reset hard" } else { "reset" }.to_string());
+        }
+    }
+
+    /// Go to instruction `position` of the execution timeline, replaying
+    /// the run to get there
+    pub fn seek_timeline(&mut self, position: u64) {
+        self.follow_cpu();
+        let Some(ref mut debugger) = self.debugger else {
+            return;
+        };
+        let result = debugger.seek(position);
+        let text = match result {
+            Ok(()) => format!(
+                "Instruction {} of {} at 0x{:08X}",
+                debugger.position(),
+                debugger.timeline().length(),
+                debugger.cpu.eip
+            ),
+            Err(ref e) => format!("Error: {}", e),
+        };
+        self.changed_registers.clear();
+        self.last_validation = None;
+        self.flag_hit = None;
+        self.refresh_disasm();
+        self.update_pins();
+        self.update_goal_progress();
+        self.message = Some(Message {
+            text,
+            is_error: result.is_err(),
+        });
+    }
+
+    /// Move along the execution timeline by `delta` instructions
+    pub fn scrub_timeline(&mut self, delta: i64) {
+        if let Some(ref debugger) = self.debugger {
+            let position = debugger.position().saturating_add_signed(delta);
+            self.seek_timeline(position);
+        }
+    }
+
+    /// Go to the previous or next breakpoint hit, patch or snapshot on the timeline
+    pub fn jump_timeline_marker(&mut self, forward: bool) {
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
+        let position = debugger.position();
+        let target = if forward {
+            debugger.timeline().next_marker(position)
+        } else {
+            debugger.timeline().previous_marker(position)
+        };
+        match target {
+            Some(target) => self.seek_timeline(target),
+            None => {
+                self.message = Some(Message {
+                    text: format!(
+                        "No {} mark on the timeline",
+                        if forward { "later" } else { "earlier" }
+                    ),
+                    is_error: false,
+                });
+            }
+        }
+    }
+
+    /// Scrub to where a click lands on a `width`-wide timeline track
+    pub fn scrub_timeline_to(&mut self, column: u16, width: u16) {
+        if let Some(ref debugger) = self.debugger {
+            let length = debugger.timeline().length();
+            self.seek_timeline(TimelineView::position_at(column, width, length));
+        }
+    }
+
+    /// Mark the current instruction on the timeline to come back to
+    pub fn mark_snapshot(&mut self) {
+        if let Some(ref mut debugger) = self.debugger {
+            debugger.mark_snapshot();
+            self.message = Some(Message {
+                text: format!(
+                    "Snapshot marked at instruction {} ({{ and }} jump between marks)",
+                    debugger.position()
+                ),
+                is_error: false,
+            });
+        }
+    }
+
+    /// Toggle breakpoint at selected address
+    pub fn toggle_breakpoint(&mut self) {
+        if let Some(ref mut debugger) = self.debugger {
+            if let Some(line) = self.disasm_cache.get(self.disasm_selection) {
+                let addr = line.address;
+                let set = debugger.toggle_breakpoint(addr);
+                self.message = Some(Message {
+                    text: if set {
+                        format!("Breakpoint set at 0x{:08X}", addr)
+                    } else {
+                        format!("Breakpoint removed at 0x{:08X}", addr)
+                    },
+                    is_error: false,
+                });
+                if set {
+                    self.log_learning(LearningEvent::BreakpointSet);
+                }
+                self.record_replay(format!("bp 0x{:X}", addr));
+            }
+        }
+    }
+
+    /// Show next hint
+    pub fn show_hint(&mut self) {
+        if !self.game_state.assist.allows_hints() {
+            self.message = Some(Message {
+                text: self.assist_disabled("Hints"),
+                is_error: true,
+            });
+            return;
+        }
+
+        // A hint targeted at the current state comes first, unless it was
+        // just shown, in which case asking again moves down the hint ladder
+        let targeted = self
+            .debugger
+            .as_ref()
+            .and_then(|d| self.hint_engine.suggest(&ScriptContext::from_debugger(d)))
+            .map(str::to_string);
+        if let Some(hint) = targeted {
+            if self.last_rule_hint.as_deref() != Some(hint.as_str()) {
+                self.game_state.use_hint();
+                self.log_learning(LearningEvent::HintUsed);
+                self.message = Some(Message {
+                    text: format!("Hint: {}", hint),
+                    is_error: false,
+                });
+                self.last_rule_hint = Some(hint);
+                return;
+            }
+        }
+
+        if let Some(ref puzzle) = self.puzzle {
+            self.hint_level += 1;
+            if let Some(hint) = puzzle.hints.get_hint(self.hint_level) {
+                self.game_state.use_hint();
+                self.message = Some(Message {
+                    text: format!("Hint {}: {}", self.hint_level, hint),
+                    is_error: false,
+                });
+                self.log_learning(LearningEvent::HintUsed);
+            } else {
+                let text = if puzzle.solution.is_some() {
+                    "No more hints available. Still stuck? `reveal` shows the official solution"
+                        .to_string()
+                } else {
+                    "No more hints available".to_string()
+                };
+                self.message = Some(Message {
+                    text,
+                    is_error: false,
+                });
+            }
+        }
+    }
+
+    /// Ask to give up and see the official solution
+    pub fn request_reveal(&mut self) {
+        let error = match self.puzzle {
+            None => "No puzzle loaded",
+            Some(ref puzzle) if puzzle.solution.is_none() => {
+                "This puzzle has no official solution to reveal"
+            }
+            Some(_) => {
+                self.reveal_confirm_open = true;
+                return;
+            }
+        };
+        self.message = Some(Message {
+            text: error.to_string(),
+            is_error: true,
+        });
+    }
+
+    /// Points the player loses by revealing the current puzzle's solution
+    pub fn reveal_penalty(&self) -> u32 {
+        self.puzzle.as_ref().map_or(0, |puzzle| {
+            self.game_state.reveal_penalty(puzzle.metadata.difficulty)
+        })
+    }
+
+    /// Give up on the puzzle: apply the penalty, mark it revealed and start
+    /// the walkthrough of the official solution
+    pub fn reveal_solution(&mut self) {
+        self.reveal_confirm_open = false;
+        let Some(ref puzzle) = self.puzzle else {
+            return;
+        };
+        let Some(ref solution) = puzzle.solution else {
+            return;
+        };
+        let patches = self
+            .debugger
+            .as_ref()
+            .map(|d| d.net_patches())
+            .unwrap_or_default();
+        let pages = match solution.walkthrough(puzzle, &patches) {
+            Ok(pages) => pages,
+            Err(e) => {
+                self.message = Some(Message {
+                    text: format!("Invalid solution: {}", e),
+                    is_error: true,
+                });
+                return;
+            }
+        };
+
+        let penalty = self
+            .game_state
+            .reveal_puzzle(&puzzle.metadata.id, puzzle.metadata.difficulty);
+        let message = format!(
+            "Solution revealed for '{}'\n\nScore: -{} points\nThe puzzle stays unsolved and your win streak is reset",
+            puzzle.metadata.title, penalty
+        );
+        self.solution = Some(SolutionState::new(pages));
+        self.solution_open = true;
+        self.screen = Screen::SolutionRevealed { message };
+    }
+
+    /// Check if puzzle is completed
+    fn check_completion(&mut self) {
+        let (Some(debugger), Some(puzzle)) = (&self.debugger, &self.puzzle) else {
+            return;
+        };
+        if self.in_sandbox() {
+            return;
+        }
+        let halted = debugger.cpu.halted;
+        match puzzle.validation.trigger().unwrap_or_default() {
+            ValidationTrigger::Halt if halted => self.validate_goal(),
+            ValidationTrigger::Address(address) if debugger.cpu.eip == address && !halted => {
+                self.validate_goal()
+            }
+            ValidationTrigger::Address(address) if halted => {
+                self.apply_validation(ValidationResult::Failure(format!(
+                    "The program halted without reaching 0x{:08X}",
+                    address
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    /// Check the goal against the current state (through the script's halt
+    /// handler when there is one)
+    fn validate_goal(&mut self) {
+        let (Some(debugger), Some(puzzle)) = (&self.debugger, &self.puzzle) else {
+            return;
+        };
+        let result = match self.script {
+            Some(ref mut script) => {
+                let ctx = ScriptContext::from_debugger(debugger);
+                let (result, hints) = Validator::validate_scripted(puzzle, script, &ctx);
+                self.show_script_hints(&hints);
+                result
+            }
+            None => Validator::validate(puzzle, &debugger.cpu, &debugger.memory),
+        };
+
+        self.apply_validation(result);
+    }
+
+    /// Check the goal now, for puzzles validated on demand (`check`)
+    pub fn check_goal(&mut self) {
+        let Some(ref puzzle) = self.puzzle else {
+            return;
+        };
+        if self.in_sandbox() {
+            self.message = Some(Message {
+                text: "A sandbox has no goal to check".to_string(),
+                is_error: false,
+            });
+            return;
+        }
+        match puzzle.validation.trigger().unwrap_or_default() {
+            ValidationTrigger::Command => self.validate_goal(),
+            trigger => {
+                self.message = Some(Message {
+                    text: format!("{}, not with `check`", trigger.describe()),
+                    is_error: false,
+                });
+            }
+        }
+    }
+
+    /// Fire a puzzle script event, showing its hints and acting on any verdict
+    fn fire_script(&mut self, event: ScriptEvent) {
+        let (Some(script), Some(debugger)) = (self.script.as_mut(), self.debugger.as_ref()) else {
+            return;
+        };
+        // Halt verdicts are handled by check_completion
+        if (event == ScriptEvent::Step && debugger.cpu.halted) || !script.handles(event) {
+            return;
+        }
+
+        let ctx = ScriptContext::from_debugger(debugger);
+        let outcome = script.fire(event, &ctx);
+
+        self.show_script_hints(&outcome.hints);
+        if let Some(result) = outcome.verdict {
+            self.apply_validation(result);
+        }
+    }
+
+    /// Show the last hint a script emitted
+    fn show_script_hints(&mut self, hints: &[String]) {
+        if let Some(hint) = hints.last() {
+            self.message = Some(Message {
+                text: format!("Hint: {}", hint),
+                is_error: false,
+            });
+        }
+    }
+
+    /// Complete the puzzle or report why it is not solved yet
+    fn apply_validation(&mut self, result: ValidationResult) {
+        match result {
+            ValidationResult::Success => {
+                self.log_learning(LearningEvent::Solved);
+                self.keep_best_solve();
+                #[cfg(feature = "online")]
+                if self.demo.is_none() && self.duel.is_none() {
+                    let profile = self.leaderboard_profile();
+                    crate::online::submit_score(self, &profile);
+                }
+            }
+            ValidationResult::Failure(_) => self.log_learning(LearningEvent::ValidationFailed),
+            ValidationResult::Error(_) => {}
+        }
+        let Some(ref puzzle) = self.puzzle else {
+            return;
+        };
+        self.last_validation = Some(result.clone());
+
+        match result {
+            ValidationResult::Success if self.duel.is_some() => self.end_duel_turn(true),
+            ValidationResult::Success if self.demo.is_some() => {
+                self.screen = Screen::PuzzleComplete {
+                    message: format!("Solved '{}'!", puzzle.metadata.title),
+                };
+            }
+            ValidationResult::Success => {
+                let score = self.game_state.puzzle_score(puzzle.metadata.difficulty);
+                let award = self
+                    .game_state
+                    .puzzle_award(&puzzle.metadata.id, puzzle.metadata.difficulty);
+                let assist = self.game_state.puzzle_assist;
+                let achievements = self
+                    .game_state
+                    .complete_puzzle(&puzzle.metadata.id, puzzle.metadata.difficulty);
+
+                // Build completion message with achievements
+                let mut msg = format!("Congratulations! You solved '{}'!", puzzle.metadata.title);
+                if award == score {
+                    msg.push_str(&format!(
+                        "\n\nScore: +{} ({} assist)",
+                        score,
+                        assist.label()
+                    ));
+                } else {
+                    msg.push_str(&format!(
+                        "\n\nScore: +{} ({} assist; an earlier solve already earned the rest)",
+                        award,
+                        assist.label()
+                    ));
+                }
+
+                // Compare with the official solution while the patches are still there
+                let patches = self
+                    .debugger
+                    .as_ref()
+                    .map(|d| d.net_patches())
+                    .unwrap_or_default();
+                if let Some(ref solution) = puzzle.solution {
+                    if let Ok(par) = solution.par(puzzle) {
+                        let changed: usize = patches.iter().map(|p| p.new_bytes.len()).sum();
+                        msg.push_str(&format!("\nBytes patched: {} (par {})", changed, par));
+                    }
+                    self.solution = solution
+                        .walkthrough(puzzle, &patches)
+                        .ok()
+                        .map(SolutionState::new);
+                }
+
+                if !achievements.is_empty() {
+                    msg.push_str("\n\nAchievements Unlocked:");
+                    for ach in achievements.iter() {
+                        msg.push_str(&format!(
+                            "\n{} {} (+{} pts)",
+                            ach.icon(),
+                            ach.name(),
+                            ach.points()
+                        ));
+                    }
+                }
+
+                let in_time_attack = self
+                    .time_attack
+                    .as_ref()
+                    .is_some_and(|run| run.current() == Some(puzzle.metadata.id.as_str()));
+                if in_time_attack {
+                    self.time_attack_split(msg);
+                } else {
+                    self.screen = Screen::PuzzleComplete { message: msg };
+                }
+            }
+            ValidationResult::Failure(msg) => {
+                self.game_state.record_failed_check();
+                let mut text = format!("Not quite: {}", msg);
+                let assist = self.game_state.assist;
+                if assist.allows_hints()
+                    && (self.settings.eager_hints || assist.forces_eager_hints())
+                {
+                    let hint = self
+                        .debugger
+                        .as_ref()
+                        .and_then(|d| self.hint_engine.suggest(&ScriptContext::from_debugger(d)));
+                    if let Some(hint) = hint {
+                        text.push_str(&format!(". Hint: {}", hint));
+                    }
+                }
+                self.message = Some(Message {
+                    text,
+                    is_error: true,
+                });
+            }
+            ValidationResult::Error(e) => {
+                self.message = Some(Message {
+                    text: format!("Validation error: {}", e),
+                    is_error: true,
+                });
+            }
+        }
+    }
+
+    /// The puzzle's goal, each part checked against the current state
+    pub fn objectives(&self) -> Vec<Objective> {
+        match (&self.debugger, &self.puzzle) {
+            (Some(debugger), Some(puzzle)) => {
+                Validator::objectives(&puzzle.validation, &debugger.cpu, &debugger.memory)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Open the overlay showing the task, its goal and progress so far
+    pub fn show_objectives(&mut self) {
+        if self.puzzle.is_some() {
+            self.objectives_open = true;
+        }
+    }
+
+    /// Show the puzzle's briefing again, from the objectives overlay
+    pub fn show_briefing(&mut self) {
+        if self.puzzle.is_some() {
+            self.objectives_open = false;
+            self.screen = Screen::Briefing;
+        }
+    }
+
+    /// Leave the briefing for the debugger
+    pub fn begin_puzzle(&mut self) {
+        if self.screen == Screen::Briefing {
+            self.screen = Screen::Debugger;
+        }
+    }
+
+    /// Open the overlay explaining the last run
+    pub fn explain_last_run(&mut self) {
+        if !self.game_state.assist.allows_explainer() {
+            self.message = Some(Message {
+                text: self.assist_disabled("Run explanations"),
+                is_error: true,
+            });
+            return;
+        }
+        let Some(ref mut debugger) = self.debugger else {
+            return;
+        };
+        self.run_explanation = explain_run(debugger, self.last_validation.as_ref());
+        self.explain_open = true;
+    }
+
+    /// Compare the paths of the last two runs side by side
+    pub fn diff_runs(&mut self) {
+        match (&self.previous_trace, &self.latest_trace) {
+            (Some(before), Some(after)) => {
+                self.trace_diff = Some(TraceDiff::new(before, after));
+            }
+            _ => {
+                self.message = Some(Message {
+                    text: "Run the program twice (e.g. before and after a patch) to compare"
+                        .to_string(),
+                    is_error: true,
+                });
+            }
+        }
+    }
+
+    /// Compare registers, flags and optionally memory ranges between two
+    /// snapshots. `args` are two snapshot numbers (or `here`) followed by
+    /// address and length pairs; without any, the last two snapshots are
+    /// compared, or the only one with the current instruction.
+    pub fn diff_snapshots(&mut self, args: &[&str]) {
+        let Some(ref mut debugger) = self.debugger else {
+            return;
+        };
+        let result = snapshot_diff(debugger, args);
+
+        match result {
+            Ok(diff) => self.state_diff = Some(diff),
+            Err(text) => {
+                self.message = Some(Message {
+                    text,
+                    is_error: true,
+                })
+            }
+        }
+    }
+
+    /// Stop running whenever a value the puzzle checks changes
+    pub fn set_break_on_goal(&mut self, enabled: bool) {
+        let Some(ref mut debugger) = self.debugger else {
+            return;
+        };
+        let text = if debugger.watches().is_empty() {
+            "This puzzle's goal has no register or memory to watch".to_string()
+        } else {
+            debugger.set_break_on_watch(enabled);
+            let names: Vec<String> = debugger
+                .watches()
+                .iter()
+                .map(|watch| watch.name())
+                .collect();
+            format!(
+                "Break on goal change {}: {}",
+                if enabled { "on" } else { "off" },
+                names.join(", ")
+            )
+        };
+        self.message = Some(Message {
+            text,
+            is_error: false,
+        });
+    }
+
+    /// `break-on <flag> [off]` stops (or stops stopping) whenever the flag
+    /// changes; `break-on off` clears every flag, and `break-on` on its own
+    /// lists them
+    pub fn break_on_flags(&mut self, args: &[&str]) {
+        let Some(ref mut debugger) = self.debugger else {
+            return;
+        };
+        let enabled = !args
+            .get(1)
+            .is_some_and(|arg| arg.eq_ignore_ascii_case("off"));
+        let text = match args.first() {
+            Some(arg) if arg.eq_ignore_ascii_case("off") && args.len() == 1 => {
+                for flag in Flag::ALL {
+                    debugger.set_flag_break(flag, false);
+                }
+                Ok("Not breaking on any flag".to_string())
+            }
+            Some(arg) if args.len() <= 2 => match Flag::from_name(arg) {
+                Some(flag) => {
+                    debugger.set_flag_break(flag, enabled);
+                    Ok(format!(
+                        "{} {} changes",
+                        if enabled {
+                            "Breaking when"
+                        } else {
+                            "No longer breaking when"
+                        },
+                        flag.name()
+                    ))
+                }
+                None => Err(format!(
+                    "Unknown flag: {} (CF, ZF, SF, OF, PF, AF or DF)",
+                    arg
+                )),
+            },
+            Some(_) => Err("Usage: break-on [<flag> [off]|off]".to_string()),
+            None if debugger.flag_breaks().is_empty() => {
+                Ok("Not breaking on any flag (break-on <flag>)".to_string())
+            }
+            None => {
+                let names: Vec<&str> = debugger.flag_breaks().iter().map(Flag::name).collect();
+                Ok(format!("Breaking when {} changes", names.join(", ")))
+            }
+        };
+        self.message = Some(match text {
+            Ok(text) => Message {
+                text,
+                is_error: false,
+            },
+            Err(text) => Message {
+                text,
+                is_error: true,
+            },
+        });
+    }
+
+    /// Show the time-stamp counter and what instructions cost
+    fn show_cycles(&mut self) {
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
+        let model = debugger.cycle_model();
+        self.message = Some(Message {
+            text: format!(
+                "Cycles: {} (RDTSC reads this). Cost: {} per instruction, +{} for a memory operand, +{} for a taken jump",
+                debugger.cpu.cycles, model.base, model.memory, model.taken_branch
+            ),
+            is_error: false,
+        });
+    }
+
+    /// Show the session's random seed, or reseed and reset with `value`
+    fn seed_command(&mut self, value: Option<&str>) {
+        let Some(ref mut debugger) = self.debugger else {
+            return;
+        };
+        let Some(value) = value else {
+            self.message = Some(Message {
+                text: format!(
+                    "RNG seed: {} (every run repeats the same numbers). Usage: seed <n>",
+                    debugger.rng_seed()
+                ),
+                is_error: false,
+            });
+            return;
+        };
+        let seed = match value.strip_prefix("0x") {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => value.parse(),
+        };
+        let Ok(seed) = seed else {
+            self.message = Some(Message {
+                text: "Usage: seed <n>".to_string(),
+                is_error: true,
+            });
+            return;
+        };
+        debugger.set_rng_seed(seed);
+        self.reset();
+        let seed = self
+            .debugger
+            .as_ref()
+            .map_or(seed, |debugger| debugger.rng_seed());
+        self.message = Some(Message {
+            text: format!("RNG seed set to {}; reset to initial state", seed),
+            is_error: false,
+        });
+    }
+
+    /// Open the program input dialog with the current input
+    pub fn open_input_dialog(&mut self) {
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
+        self.input_state.text = ProgramInput::escape(debugger.input());
+        self.input_dialog_open = true;
+    }
+
+    /// Where the program's input goes, for the input dialog
+    pub fn input_placement_text(&self) -> Option<String> {
+        let placement = self.debugger.as_ref()?.input_placement()?;
+        let mut text = format!(
+            "Copied to 0x{:08X} (up to {} bytes)",
+            placement.address, placement.max_length
+        );
+        if let Some(register) = placement.length_register {
+            text.push_str(&format!(", length in {}", register.name()));
+        }
+        Some(text)
+    }
+
+    /// Apply the dialog's input
+    pub fn submit_input(&mut self) {
+        let text = std::mem::take(&mut self.input_state.text);
+        match self.set_program_input(&text) {
+            Ok(()) => self.input_dialog_open = false,
+            Err(e) => {
+                self.input_state.text = text;
+                self.message = Some(Message {
+                    text: e,
+                    is_error: true,
+                });
+            }
+        }
+    }
+
+    /// Restart the program with new input, keeping patches like a reset
+    pub fn set_program_input(&mut self, text: &str) -> Result<(), String> {
+        let bytes = ProgramInput::unescape(text)?;
+        let Some(ref mut debugger) = self.debugger else {
+            return Err("No debugger active".to_string());
+        };
+        debugger.set_input(&bytes);
+        self.reset();
+        self.message = Some(Message {
+            text: format!(
+                "Restarted with input \"{}\" ({} bytes)",
+                ProgramInput::escape(&bytes),
+                bytes.len()
+            ),
+            is_error: false,
+        });
+        Ok(())
+    }
+
+    /// Switch between linear sweep and recursive traversal disassembly
+    pub fn set_disasm_mode(&mut self, mode: DisassemblyMode) {
+        self.disasm_mode = mode;
+        self.disasm_selection = 0;
+        self.refresh_disasm();
+        self.message = Some(Message {
+            text: format!("Disassembly: {}", mode.name()),
+            is_error: false,
+        });
+    }
+
+    /// Open the encoding inspector for the selected disassembly line
+    pub fn inspect_encoding(&mut self) {
+        let Some(line) = self.disasm_cache.get(self.disasm_selection) else {
+            return;
+        };
+        self.encoding = InstructionEncoding::decode(&line.bytes, line.address);
+        if self.encoding.is_none() {
+            self.message = Some(Message {
+                text: format!("Cannot decode the bytes at 0x{:08X}", line.address),
+                is_error: true,
+            });
+        }
+    }
+
+    /// Get current instruction address
+    pub fn current_eip(&self) -> Option<u32> {
+        self.debugger.as_ref().map(|d| d.cpu.eip)
+    }
+
+    /// Check if address has breakpoint
+    pub fn has_breakpoint(&self, addr: u32) -> bool {
+        self.debugger
+            .as_ref()
+            .map(|d| d.has_breakpoint(addr))
+            .unwrap_or(false)
+    }
+
+    /// Idiom explanations for the disassembly: the first occurrence of each
+    /// idiom not yet learned
+    pub fn idiom_notes(&self) -> Vec<IdiomMatch> {
+        if !self.settings.idiom_notes {
+            return Vec::new();
+        }
+        first_idioms(&self.disasm_cache, &self.settings.learned_idioms)
+    }
+
+    /// The puzzle author's comments for the disassembly, by address
+    pub fn annotations(&self) -> BTreeMap<u32, String> {
+        self.puzzle
+            .as_ref()
+            .and_then(|puzzle| puzzle.annotated_addresses().ok())
+            .unwrap_or_default()
+    }
+
+    /// Stop annotating an idiom and save the choice
+    pub fn learn_idiom(&mut self, name: Option<&str>) -> Result<(), String> {
+        let idiom = self.mark_idiom_learned(name)?;
+        let text = match self.save_settings() {
+            Ok(()) => format!("Learned {}: no more notes for it", idiom.name()),
+            Err(e) => format!("Learned {} (not saved: {})", idiom.name(), e),
+        };
+        self.message = Some(Message {
+            text,
+            is_error: false,
+        });
+        Ok(())
+    }
+
+    /// Mark an idiom learned: the named one, or the one noted on the selected line
+    pub fn mark_idiom_learned(&mut self, name: Option<&str>) -> Result<Idiom, String> {
+        let idiom = match name {
+            Some(name) => Idiom::from_name(name).ok_or_else(|| {
+                let names: Vec<_> = Idiom::ALL.iter().map(|idiom| idiom.name()).collect();
+                format!("Unknown idiom: {} (one of {})", name, names.join(", "))
+            })?,
+            None => {
+                let address = self
+                    .disasm_cache
+                    .get(self.disasm_selection)
+                    .map(|line| line.address);
+                self.idiom_notes()
+                    .into_iter()
+                    .find(|found| Some(found.address) == address)
+                    .map(|found| found.idiom)
+                    .ok_or("No idiom note on the selected line")?
+            }
+        };
+
+        if !self
+            .settings
+            .learned_idioms
+            .iter()
+            .any(|name| name == idiom.name())
+        {
+            self.settings.learned_idioms.push(idiom.name().to_string());
+        }
+        Ok(idiom)
+    }
+
+    /// Show or hide the pseudo-C pane
+    pub fn toggle_pseudo(&mut self) {
+        self.pseudo_open = !self.pseudo_open;
+        self.message = Some(Message {
+            text: if self.pseudo_open {
+                "Pseudo-C: an approximate reading of the block under the cursor".to_string()
+            } else {
+                "Pseudo-C hidden".to_string()
+            },
+            is_error: false,
+        });
+    }
+
+    /// Approximate C for the basic block under the disassembly cursor
+    pub fn pseudo_code(&self) -> Vec<PseudoLine> {
+        match self.disasm_cache.get(self.disasm_selection) {
+            Some(line) => pseudo_c(basic_block(&self.disasm_cache, line.address)),
+            None => Vec::new(),
+        }
+    }
+
+    /// The CALL under the disassembly cursor, if any
+    pub fn selected_call(&self) -> Option<&DisassemblyLine> {
+        self.disasm_cache
+            .get(self.disasm_selection)
+            .filter(|line| line.is_call())
+    }
+
+    /// Likely cdecl arguments of the CALL under the cursor: the dwords on
+    /// top of the stack, first argument at ESP
+    pub fn call_arguments(&self) -> Option<Vec<u32>> {
+        self.selected_call()?;
+        let debugger = self.debugger.as_ref()?;
+        let esp = debugger.cpu.regs.esp;
+        Some(
+            (0..CDECL_ARGS)
+                .map_while(|i| {
+                    debugger
+                        .memory
+                        .peek_u32(esp.wrapping_add(4 * i as u32))
+                        .ok()
+                })
+                .collect(),
+        )
+    }
+
+    /// Frame to show collapsing above ESP, while its animation runs
+    pub fn collapsing_frame(&self) -> Option<&Frame> {
+        self.collapsed_frame
+            .as_ref()
+            .filter(|_| self.animations.frame_collapse.is_active())
+    }
+
+    /// First stack slot shown when following ESP. While a returned frame
+    /// collapses, its slots stay in view above ESP and shrink away.
+    fn stack_top(&self, esp: u32) -> u32 {
+        let Some(frame) = self.collapsing_frame() else {
+            return esp;
+        };
+        let slots = (esp.saturating_sub(frame.lowest) / 4).min(6) as f32;
+        let remaining = (slots * (1.0 - self.animations.frame_collapse.progress())).ceil() as u32;
+        esp.wrapping_sub(remaining * 4)
+    }
+
+    /// Get stack values for display
+    pub fn get_stack(&self, count: usize) -> Vec<(u32, u32)> {
+        let mut result = Vec::new();
+        if let Some(ref debugger) = self.debugger {
+            let start = self
+                .stack_view_addr
+                .unwrap_or_else(|| self.stack_top(debugger.cpu.regs.esp));
+            for i in 0..count {
+                let addr = start.wrapping_add((i as u32) * 4);
+                if let Ok(value) = debugger.memory.peek_u32(addr) {
+                    result.push((addr, value));
+                }
+            }
+        }
+        result
+    }
+
+    /// List mapped devices and their output in the status line
+    fn show_devices(&mut self) {
+        let Some(ref debugger) = self.debugger else {
+            return;
+        };
+
+        let devices: Vec<String> = debugger
+            .memory
+            .devices()
+            .iter()
+            .map(|m| match m.output() {
+                Some(output) => format!("{} @ 0x{:08X}: {:?}", m.name(), m.start, output),
+                None => format!("{} @ 0x{:08X}", m.name(), m.start),
+            })
+            .collect();
+
+        let text = if devices.is_empty() {
+            "No devices mapped".to_string()
+        } else {
+            devices.join(", ")
+        };
+        self.message = Some(Message {
+            text,
+            is_error: false,
+        });
+    }
+
+    /// Get memory for display
+    pub fn get_memory(&self, addr: u32, count: usize) -> Option<Vec<u8>> {
+        self.debugger
+            .as_ref()
+            .and_then(|d| d.memory.slice(addr, count))
+    }
+
+    /// Patch memory at address, refusing code patches that would shift the
+    /// instructions after them
+    pub fn patch_memory(&mut self, addr: u32, bytes: &[u8]) -> Result<(), String> {
+        self.apply_patch(addr, bytes, false)
+    }
+
+    /// Patch memory at address as is (`patch!`)
+    pub fn force_patch_memory(&mut self, addr: u32, bytes: &[u8]) -> Result<(), String> {
+        self.apply_patch(addr, bytes, true)
+    }
+
+    fn apply_patch(&mut self, addr: u32, bytes: &[u8], force: bool) -> Result<(), String> {
+        if let Some(ref mut debugger) = self.debugger {
+            let result = if force {
+                debugger.force_patch(addr, bytes)
+            } else {
+                debugger.patch(addr, bytes)
+            };
+            result.map_err(|e| e.to_string())?;
+            self.game_state.record_patch();
+            self.log_learning(LearningEvent::Patch);
+            let hex: Vec<String> = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+            self.record_replay(format!("patch! 0x{:X} {}", addr, hex.join(" ")));
+            self.refresh_disasm();
+            self.update_pins();
+            self.update_goal_progress();
+            self.refresh_search();
+            self.message = Some(Message {
+                text: format!("Patched {} bytes at 0x{:08X}", bytes.len(), addr),
+                is_error: false,
+            });
+            self.fire_script(ScriptEvent::Patch);
+            Ok(())
+        } else {
+            Err("No debugger active".to_string())
+        }
+    }
+
+    /// Patch code only if the instructions after it still decode the same
+    /// way, optionally filling a short patch with NOPs
+    pub fn patch_checked(&mut self, addr: u32, bytes: &[u8], pad: bool) -> Result<(), String> {
+        let fit = match self.debugger {
+            Some(ref mut debugger) => debugger.check_patch(addr, bytes),
+            None => return Err("No debugger active".to_string()),
+        };
+
+        let Some(warning) = fit.warning() else {
+            return self.patch_memory(addr, bytes);
+        };
+
+        let padding = fit.padding();
+        if pad && padding > 0 {
+            return self.patch_memory(addr, &pad_with_nops(bytes, padding));
+        }
+
+        let advice = if padding > 0 {
+            format!(
+                "add `pad` to fill with {} NOP{}, or use `patch!` to write it anyway",
+                padding,
+                if padding == 1 { "" } else { "s" }
+            )
+        } else {
+            "use `patch!` to write it anyway".to_string()
+        };
+        Err(format!("{}: {}", warning, advice))
+    }
+
+    /// Undo the last patch
+    pub fn undo_patch(&mut self) -> Result<(), String> {
+        if !self.game_state.assist.allows_undo() {
+            return Err(self.assist_disabled("Undo"));
+        }
+        if let Some(ref mut debugger) = self.debugger {
+            debugger.undo_patch().map_err(|e| e.to_string())?;
+            let remaining = debugger.undo_count();
+
+            // Check for undo achievement
+            if let Some(achievement) = self
+                .game_state
+                .record_undo()
+                .filter(|_| self.demo.is_none())
+            {
+                self.message = Some(Message {
+                    text: format!(
+                        "Achievement Unlocked: {} {} (+{} pts)",
+                        achievement.icon(),
+                        achievement.name(),
+                        achievement.points()
+                    ),
+                    is_error: false,
+                });
+            }
+
+            self.animations.rewind.trigger();
+            self.refresh_disasm();
+            self.update_pins();
+            self.update_goal_progress();
+            self.refresh_search();
+
+            // Only override message if no achievement was unlocked
+            if self.message.is_none() {
+                self.message = Some(Message {
+                    text: format!("Undone ({} remaining)", remaining),
+                    is_error: false,
+                });
+            }
+            self.record_replay("undo".to_string());
+            Ok(())
+        } else {
+            Err("No debugger active".to_string())
+        }
+    }
+
+    /// Redo the last undone patch
+    pub fn redo_patch(&mut self) -> Result<(), String> {
+        if !self.game_state.assist.allows_undo() {
+            return Err(self.assist_disabled("Redo"));
+        }
+        if let Some(ref mut debugger) = self.debugger {
+            debugger.redo_patch().map_err(|e| e.to_string())?;
+            let remaining = debugger.redo_count();
+            self.refresh_disasm();
+            self.update_pins();
+            self.update_goal_progress();
+            self.refresh_search();
+            self.message = Some(Message {
+                text: format!("Redone ({} remaining)", remaining),
+                is_error: false,
+            });
+            self.record_replay("redo".to_string());
+            Ok(())
+        } else {
+            Err("No debugger active".to_string())
+        }
+    }
+
+    /// Process a command
+    pub fn process_command(&mut self, cmd: &str) {
+        match self
+            .settings
+            .expand_alias(cmd, |name| self.alias_variable(name))
+        {
+            Some(Ok(commands)) => {
+                for command in commands {
+                    self.run_command(&command);
+                }
+            }
+            Some(Err(e)) => {
+                self.message = Some(Message {
+                    text: e,
+                    is_error: true,
+                })
+            }
+            None => self.run_command(cmd),
+        }
+    }
+
+    /// Value of an alias variable: `$sel` (the selected disassembly line),
+    /// `$eip`, `$last` (the last patch) or `$mem` (the memory view)
+    fn alias_variable(&self, name: &str) -> Option<String> {
+        let debugger = self.debugger.as_ref()?;
+        let address = match name {
+            "sel" => self.disasm_cache.get(self.disasm_selection)?.address,
+            "eip" => debugger.cpu.eip,
+            "last" => debugger.last_patch()?.address,
+            "mem" => self.memory_view_addr,
+            _ => return None,
+        };
+        Some(format!("0x{:X}", address))
+    }
+
+    /// Define, list or remove aliases
+    fn alias_command(&mut self, cmd: &str, remove: bool) {
+        let args = cmd
+            .trim_start()
+            .split_once(char::is_whitespace)
+            .map_or("", |(_, rest)| rest.trim());
+        let (text, is_error) = if remove {
+            if self.settings.aliases.remove(&args.to_lowercase()).is_some() {
+                (format!("Removed alias {}", args), false)
+            } else {
+                (format!("No alias named \"{}\"", args), true)
+            }
+        } else if args.is_empty() {
+            if self.settings.aliases.is_empty() {
+                ("No aliases. Define one with: alias <name> = <command>; variables $sel $eip $last $mem".to_string(), false)
+            } else {
+                let list: Vec<String> = self
+                    .settings
+                    .aliases
+                    .iter()
+                    .map(|(name, command)| format!("{} = {}", name, command))
+                    .collect();
+                (format!("Aliases: {}", list.join(" | ")), false)
+            }
+        } else {
+            let (name, command) = match args.split_once('=') {
+                Some((name, command)) => (name.trim(), command),
+                None => args.split_once(char::is_whitespace).unwrap_or((args, "")),
+            };
+            match self.settings.set_alias(name, command) {
+                Ok(()) => (
+                    format!("Alias {} = {}", name.to_lowercase(), command.trim()),
+                    false,
+                ),
+                Err(e) => (e, true),
+            }
+        };
+        let text = match (is_error, self.save_settings()) {
+            (false, Err(e)) => format!("{} (not saved: {})", text, e),
+            _ => text,
+        };
+        self.message = Some(Message { text, is_error });
+    }
+
+    /// Run one built-in command
+    fn run_command(&mut self, cmd: &str) {
+        let parts: Vec<&str> = cmd.split_whitespace().collect();
+        if parts.is_empty() {
+            return;
+        }
+
+        match parts[0].to_lowercase().as_str() {
+            "help" | "h" | "?" => {
+                self.message = Some(Message {
+                    text: "Commands: step/s, run/r, explain, diff, goal, watch on|off, pin <expr>, unpin <n|all>, input <text>, encoding, disasm [linear|recursive], pseudo, learn [idiom], reset [hard], bp <addr>, patch[!] <addr> <bytes> [pad], undo/u, redo, hint, reveal, assist <level>, predict <mode>, calc <expr>, decode [addr len xor|rot13|base64|add|sub], entropy, constants, find <value>, follow [expr], ascii [char|code], tab new|close|next|prev|<n>, compare [<tab>|off|sync on|off], copy [disasm n|memory n|summary], export view <path> [WxH], learning [clear], set <option> on|off, devices, seed [n], cycles, check, snapshot, seek <n>, break-on [<flag> [off]|off], snapdiff [<a> <b> [<addr> <len>]...], search [scope], alias [name = command], unalias <name>, share, duel [<name> <name>|pass|forfeit], script run <file>|list|clear, console, plugins, panel <n|name>, quit".to_string(),
+                    is_error: false,
+                });
+            }
+            "step" | "s" => {
+                self.request_step();
+            }
+            "run" | "r" => self.run(),
+            "reset" => match parts.get(1) {
+                Some(&"hard") => self.hard_reset(),
+                _ => self.reset(),
+            },
+            "undo" | "u" => {
+                if let Err(e) = self.undo_patch() {
+                    self.message = Some(Message {
+                        text: e,
+                        is_error: true,
+                    });
+                }
+            }
+            "redo" => {
+                if let Err(e) = self.redo_patch() {
+                    self.message = Some(Message {
+                        text: e,
+                        is_error: true,
+                    });
+                }
+            }
+            "bp" | "breakpoint" => {
+                if parts.len() > 1 {
+                    if let Ok(addr) = u32::from_str_radix(parts[1].trim_start_matches("0x"), 16) {
+                        if let Some(ref mut debugger) = self.debugger {
+                            let set = debugger.toggle_breakpoint(addr);
+                            self.message = Some(Message {
+                                text: if set {
+                                    format!("Breakpoint set at 0x{:08X}", addr)
+                                } else {
+                                    format!("Breakpoint removed at 0x{:08X}", addr)
+                                },
+                                is_error: false,
+                            });
+                        }
+                    } else {
+                        self.message = Some(Message {
+                            text: "Invalid address".to_string(),
+                            is_error: true,
+                        });
+                    }
+                } else {
+                    self.toggle_breakpoint();
+                }
+            }
+            "patch" | "patch!" => {
+                // `patch!` writes as is; a trailing `pad` fills with NOPs
+                let force = parts[0].ends_with('!');
+                let pad = parts.len() > 3 && parts[parts.len() - 1].eq_ignore_ascii_case("pad");
+                if parts.len() >= 3 {
+                    if let Ok(addr) = u32::from_str_radix(parts[1].trim_start_matches("0x"), 16) {
+                        let byte_parts = if pad {
+                            &parts[2..parts.len() - 1]
+                        } else {
+                            &parts[2..]
+                        };
+                        let bytes: Result<Vec<u8>, _> = byte_parts
+                            .iter()
+                            .map(|s| u8::from_str_radix(s.trim_start_matches("0x"), 16))
+                            .collect();
+                        match bytes {
+                            Ok(b) => {
+                                let result = if force {
+                                    self.force_patch_memory(addr, &b)
+                                } else {
+                                    self.patch_checked(addr, &b, pad)
+                                };
+                                if let Err(e) = result {
+                                    self.message = Some(Message {
+                                        text: e,
+                                        is_error: true,
+                                    });
+                                }
+                            }
+                            Err(_) => {
+                                self.message = Some(Message {
+                                    text: "Invalid bytes".to_string(),
+                                    is_error: true,
+                                });
+                            }
+                        }
+                    } else {
+                        self.message = Some(Message {
+                            text: "Invalid address".to_string(),
+                            is_error: true,
+                        });
+                    }
+                } else {
+                    self.message = Some(Message {
+                        text: "Usage: patch <addr> <bytes...> [pad]".to_string(),
+                        is_error: true,
+                    });
+                }
+            }
+            "hint" => self.show_hint(),
+            "devices" => self.show_devices(),
+            "seed" => self.seed_command(parts.get(1).copied()),
+            "cycles" => self.show_cycles(),
+            "check" => self.check_goal(),
+            "snapshot" | "snap" => self.mark_snapshot(),
+            "seek" => match parts.get(1).map(|n| n.parse::<u64>()) {
+                Some(Ok(position)) => self.seek_timeline(position),
+                _ => {
+                    self.message = Some(Message {
+                        text: "Usage: seek <instruction number>".to_string(),
+                        is_error: true,
+                    });
+                }
+            },
+            "explain" => self.explain_last_run(),
+            "diff" => self.diff_runs(),
+            "snapdiff" => self.diff_snapshots(&parts[1..]),
+            "objectives" | "goal" => self.show_objectives(),
+            "assist" => match parts
+                .get(1)
+                .map(|v| AssistLevel::from_name(&v.to_lowercase()))
+            {
+                Some(Some(level)) => self.set_assist(level),
+                _ => {
+                    self.message = Some(Message {
+                        text: format!(
+                            "Assist: {}. Usage: assist <relaxed|standard|hardcore>",
+                            self.game_state.assist.label()
+                        ),
+                        is_error: parts.len() > 1,
+                    });
+                }
+            },
+            "decode" => {
+                // decode [<addr> [<len> [<transform>]]]
+                let address = match parts.get(1).map(|text| self.resolve_address(text)) {
+                    Some(Ok(address)) => Some(address),
+                    Some(Err(e)) => {
+                        self.message = Some(Message {
+                            text: e,
+                            is_error: true,
+                        });
+                        return;
+                    }
+                    None => None,
+                };
+                self.open_decoder();
+                if let Some(ref mut state) = self.decoder {
+                    state.address = address.unwrap_or(state.address);
+                    if let Some(length) = parts.get(2).and_then(|text| text.parse::<i32>().ok()) {
+                        state.length = 1;
+                        state.resize(length - 1);
+                    }
+                    state.input = parts
+                        .get(3..)
+                        .map(|rest| rest.join(" "))
+                        .unwrap_or_default();
+                }
+            }
+            "entropy" => self.entropy = Some(EntropyState::default()),
+            "learning" => {
+                let text = if parts.get(1) == Some(&"clear") {
+                    self.game_state.learning.clear();
+                    "Learning log cleared".to_string()
+                } else if !self.settings.learning_log {
+                    "The learning log is off (set learning on; it stays on this device)".to_string()
+                } else {
+                    let insights = self.game_state.learning.insights();
+                    match insights.first() {
+                        Some(insight) => {
+                            format!("{} (all insights on the achievements screen)", insight)
+                        }
+                        None => format!(
+                            "{} events logged, no insights yet",
+                            self.game_state.learning.events().len()
+                        ),
+                    }
+                };
+                self.message = Some(Message {
+                    text,
+                    is_error: false,
+                });
+            }
+            "export" => {
+                let result = match (parts.get(1).copied(), parts.get(2).copied()) {
+                    (Some("view"), Some(path)) => parse_export_size(parts.get(3).copied())
+                        .and_then(|(width, height)| crate::export::export_view(self, Path::new(path), width, height))
+                        .map(|()| format!("Exported the debugger view to {}", path)),
+                    _ => Err("Usage: export view <path> [WIDTHxHEIGHT] (.html for a web page, otherwise ANSI text)".to_string()),
+                };
+                self.message = Some(match result {
+                    Ok(text) => Message {
+                        text,
+                        is_error: false,
+                    },
+                    Err(text) => Message {
+                        text,
+                        is_error: true,
+                    },
+                });
+            }
+            "copy" | "yank" => {
+                let count = parts.get(2).and_then(|n| n.parse().ok());
+                match parts.get(1).copied() {
+                    Some(what) => self.copy(what, count),
+                    None => self.copy_focused(),
+                }
+            }
+            "share" => self.share_puzzle(),
+            "duel" => self.duel_command(&parts[1..]),
+            #[cfg(feature = "online")]
+            "online" => crate::online::command(self, &parts[1..]),
+            "compare" => {
+                let result = match (parts.get(1).copied(), parts.get(2).copied()) {
+                    (Some("off"), _) => {
+                        self.stop_compare();
+                        Ok("Compare closed".to_string())
+                    }
+                    (Some("sync"), Some(state @ ("on" | "off"))) => {
+                        self.compare_sync = state == "on";
+                        Ok(format!("Synced stepping {}", state))
+                    }
+                    (Some(n), _) => match n.parse::<usize>() {
+                        Ok(n) if n > 0 => self
+                            .start_compare(Some(n - 1))
+                            .map(|()| format!("Comparing with tab {}", n)),
+                        _ => Err("Usage: compare [<tab>|off|sync on|off]".to_string()),
+                    },
+                    (None, _) => self
+                        .start_compare(None)
+                        .map(|()| "Comparing side by side (compare off to close)".to_string()),
+                };
+                self.message = Some(match result {
+                    Ok(text) => Message {
+                        text,
+                        is_error: false,
+                    },
+                    Err(text) => Message {
+                        text,
+                        is_error: true,
+                    },
+                });
+            }
+            "tab" => match parts.get(1).copied() {
+                Some("new") => self.new_tab(),
+                Some("close") => self.close_tab(),
+                Some("next") | None => self.cycle_tab(true),
+                Some("prev") => self.cycle_tab(false),
+                Some(n) => match n.parse::<usize>() {
+                    Ok(n) if (1..=self.tabs.len()).contains(&n) => self.switch_tab(n - 1),
+                    _ => {
+                        self.message = Some(Message {
+                            text: "Usage: tab new|close|next|prev|<number>".to_string(),
+                            is_error: true,
+                        });
+                    }
+                },
+            },
+            "constants" => self.open_constants(),
+            "ascii" => {
+                let text = cmd.trim_start()[parts[0].len()..].trim();
+                self.open_ascii_table(text);
+            }
+            "follow" => {
+                let text = cmd.trim_start()[parts[0].len()..].trim();
+                self.follow_expression(text);
+            }
+            "alias" => self.alias_command(cmd, false),
+            "unalias" => self.alias_command(cmd, true),
+            "search" => self.open_search(&parts[1..]),
+            "find" => {
+                let text = cmd.trim_start()[parts[0].len()..].trim();
+                self.find_value(text);
+            }
+            "reveal" => self.request_reveal(),
+            "calc" => {
+                let text = cmd.trim_start()[parts[0].len()..].trim();
+                self.message = Some(match self.convert(text) {
+                    Ok(c) => Message {
+                        text: format!(
+                            "{} = 0x{:08X} = {} (signed {})",
+                            text,
+                            c.value,
+                            c.value,
+                            c.signed()
+                        ),
+                        is_error: false,
+                    },
+                    Err(e) => Message {
+                        text: e,
+                        is_error: true,
+                    },
+                });
+            }
+            "predict" => match parts.get(1).map(|v| v.to_lowercase()) {
+                Some(v) if PredictMode::ALL.iter().any(|mode| mode.name() == v) => {
+                    self.settings.predict_mode = v;
+                    let text = match self.save_settings() {
+                        Ok(()) => format!("Predict mode: {}", self.predict_mode().name()),
+                        Err(e) => format!(
+                            "Predict mode: {} (not saved: {})",
+                            self.predict_mode().name(),
+                            e
+                        ),
+                    };
+                    self.message = Some(Message {
+                        text,
+                        is_error: false,
+                    });
+                }
+                _ => {
+                    self.message = Some(Message {
+                        text: format!(
+                            "Predict mode: {}. Usage: predict <off|flags|full>",
+                            self.predict_mode().name()
+                        ),
+                        is_error: parts.len() > 1,
+                    });
+                }
+            },
+            "pin" => {
+                let text = cmd.trim_start()[parts[0].len()..].trim();
+                if let Err(e) = self.pin_expression(text) {
+                    self.message = Some(Message {
+                        text: format!("{}. Usage: pin <expr> (e.g. pin dword[esp+4])", e),
+                        is_error: true,
+                    });
+                }
+            }
+            "unpin" => {
+                let result = match parts.get(1) {
+                    Some(which) => self.unpin_expression(&which.to_lowercase()),
+                    None => Err("Usage: unpin <n|all>".to_string()),
+                };
+                if let Err(e) = result {
+                    self.message = Some(Message {
+                        text: e,
+                        is_error: true,
+                    });
+                }
+            }
+            "break-on" => self.break_on_flags(&parts[1..]),
+            "watch" => match parts.get(1).map(|v| v.to_lowercase()) {
+                Some(v) if v == "on" => self.set_break_on_goal(true),
+                Some(v) if v == "off" => self.set_break_on_goal(false),
+                _ => {
+                    self.message = Some(Message {
+                        text: "Usage: watch <on|off>".to_string(),
+                        is_error: true,
+                    });
+                }
+            },
+            "input" => {
+                // Keep the player's spacing: everything after the command name
+                let text = cmd.trim_start()[parts[0].len()..].trim_start();
+                if let Err(e) = self.set_program_input(text) {
+                    self.message = Some(Message {
+                        text: e,
+                        is_error: true,
+                    });
+                }
+            }
+            "encoding" => self.inspect_encoding(),
+            "pseudo" => self.toggle_pseudo(),
+            "learn" => {
+                if let Err(e) = self.learn_idiom(parts.get(1).copied()) {
+                    self.message = Some(Message {
+                        text: e,
+                        is_error: true,
+                    });
+                }
+            }
+            "disasm" => {
+                let mode = match parts.get(1) {
+                    None => Some(self.disasm_mode.toggled()),
+                    Some(name) => DisassemblyMode::from_name(&name.to_lowercase()),
+                };
+                match mode {
+                    Some(mode) => self.set_disasm_mode(mode),
+                    None => {
+                        self.message = Some(Message {
+                            text: "Usage: disasm [linear|recursive]".to_string(),
+                            is_error: true,
+                        });
+                    }
+                }
+            }
+            "set" => {
+                let enabled = match parts.get(2).map(|v| v.to_lowercase()) {
+                    Some(v) if v == "on" => Some(true),
+                    Some(v) if v == "off" => Some(false),
+                    _ => None,
+                };
+                let result = match (parts.get(1), enabled) {
+                    (Some(name), Some(enabled)) => self.set_option(&name.to_lowercase(), enabled),
+                    _ => Err("Usage: set <markers|contrast|ascii|announce|fastrun|frames|idioms|learning> <on|off>".to_string()),
+                };
+                if let Err(e) = result {
+                    self.message = Some(Message {
+                        text: e,
+                        is_error: true,
+                    });
+                }
+            }
+            "quit" | "q" => self.should_quit = true,
+            "script" => self.script_command(&parts[1..]),
+            "console" => self.toggle_console(),
+            "plugins" => crate::plugin::list(self),
+            "panel" => crate::plugin::open_panel(self, parts.get(1).copied().unwrap_or("1")),
+            // Tool scripts first, then plugins
+            _ => match self.tool_commands.get(&parts[0].to_lowercase()).cloned() {
+                Some(tool) => self.run_tool_command(&tool, &parts[1..]),
+                None => crate::plugin::command(self, &parts),
+            },
+        }
+    }
+
+    /// Save game progress
+    pub fn save_game(&mut self, slot: &str) -> Result<(), String> {
+        if self.demo.is_some() {
+            return Err("Saving is off in demo mode".to_string());
+        }
+        self.remember_marks();
+        let save_manager = self.saves()?;
+        save_manager.save(&self.game_state, slot)?;
+        self.saved_progress = self.game_state.fingerprint();
+        log::debug!("Saved progress to slot {}", slot);
+
+        self.message = Some(Message {
+            text: format!("Game saved to slot: {}", slot),
+            is_error: false,
+        });
+
+        Ok(())
+    }
+
+    /// Load game progress
+    pub fn load_game(&mut self, slot: &str) -> Result<(), String> {
+        let save_manager = self.saves()?;
+        let game_state = save_manager.load(slot)?;
+
+        self.game_state = game_state;
+        self.saved_progress = self.game_state.fingerprint();
+
+        self.message = Some(Message {
+            text: format!("Game loaded from slot: {}", slot),
+            is_error: false,
+        });
+
+        Ok(())
+    }
+
+    /// Restore progress from the session save, written on quit
+    pub fn load_session(&mut self) -> Result<(), String> {
+        let save_manager = self.saves()?;
+        self.game_state = save_manager.load(SESSION_SLOT)?;
+        self.saved_progress = self.game_state.fingerprint();
+        Ok(())
+    }
+
+    /// Whether progress changed since it was last saved or loaded (the
+    /// session is also saved on quit)
+    pub fn has_unsaved_progress(&self) -> bool {
+        self.demo.is_none() && self.game_state.fingerprint() != self.saved_progress
+    }
+
+    /// Write progress to the session save without a status message
+    pub fn save_session(&mut self) -> Result<(), String> {
+        if self.demo.is_some() {
+            return Ok(());
+        }
+        self.remember_marks();
+        let save_manager = self.saves()?;
+        save_manager.save(&self.game_state, SESSION_SLOT)
+    }
+
+    /// Quick save (slot "quick")
+    pub fn quick_save(&mut self) -> Result<(), String> {
+        if !self.game_state.assist.allows_snapshots() {
+            return Err(self.assist_disabled("Quick save"));
+        }
+        self.save_game("quick")
+    }
+
+    /// Quick load (slot "quick")
+    pub fn quick_load(&mut self) -> Result<(), String> {
+        if !self.game_state.assist.allows_snapshots() {
+            return Err(self.assist_disabled("Quick load"));
+        }
+        self.load_game("quick")
+    }
+
+    /// Change how much help the game gives
+    pub fn set_assist(&mut self, assist: AssistLevel) {
+        self.game_state.set_assist(assist);
+        let mut text = format!(
+            "Assist: {} ({}% score)",
+            assist.label(),
+            assist.score_percent()
+        );
+        if self.game_state.puzzle_assist != assist && self.puzzle.is_some() {
+            text.push_str(&format!(
+                "; this puzzle still scores as {}",
+                self.game_state.puzzle_assist.label()
+            ));
+        }
+        self.message = Some(Message {
+            text,
+            is_error: false,
+        });
+    }
+
+    /// Error for a feature the assist level turns off
+    fn assist_disabled(&self, feature: &str) -> String {
+        format!(
+            "{} is off at {} assist (`assist <level>` to change)",
+            feature,
+            self.game_state.assist.label()
+        )
+    }
+
+    /// Open the search dialog, optionally scoped: `all`, a region name
+    /// (`code`, `data`, `stack`) or a custom `<start> <end>` range
+    pub fn open_search(&mut self, args: &[&str]) {
+        let scope = match *args {
+            [] => None,
+            ["all"] => Some(SearchScope::All),
+            [name] => match self.debugger {
+                Some(ref dbg)
+                    if dbg
+                        .memory
+                        .regions()
+                        .iter()
+                        .any(|region| region.name == name) =>
+                {
+                    Some(SearchScope::Region(name.to_string()))
+                }
+                _ => {
+                    self.message = Some(Message {
+                        text: format!("No {} region (search [all|<region>|<start> <end>])", name),
+                        is_error: true,
+                    });
+                    return;
+                }
+            },
+            [start, end] => match (self.resolve_address(start), self.resolve_address(end)) {
+                (Ok(start), Ok(end)) if start < end => {
+                    self.search_state.custom_range = Some((start, end));
+                    Some(SearchScope::Range { start, end })
+                }
+                (Ok(_), Ok(_)) => {
+                    self.message = Some(Message {
+                        text: "The start of the range must come before its end".to_string(),
+                        is_error: true,
+                    });
+                    return;
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    self.message = Some(Message {
+                        text: e,
+                        is_error: true,
+                    });
+                    return;
+                }
+            },
+            _ => {
+                self.message = Some(Message {
+                    text: "Usage: search [all|<region>|<start> <end>]".to_string(),
+                    is_error: true,
+                });
+                return;
+            }
+        };
+        if let Some(scope) = scope {
+            self.search_state.scope = scope;
+            self.search_state.clear_results();
+        }
+        self.search_dialog_open = true;
+    }
+
+    /// Start a search in the dialog's mode. Results arrive as
+    /// [`advance_search`](Self::advance_search) scans memory.
+    pub fn start_search(&mut self) -> Result<(), String> {
+        use revgame_core::debugger::{MemorySearch, SearchQuery, SearchScan};
+
+        let Some(ref dbg) = self.debugger else {
+            return Err("No debugger active".to_string());
+        };
+        let query = match self.search_state.mode {
+            SearchMode::Bytes => SearchQuery::Bytes(
+                MemorySearch::parse_hex_pattern(&self.search_state.input)
+                    .map_err(|e| format!("Invalid hex pattern: {}", e))?,
+            ),
+            SearchMode::String => SearchQuery::Text {
+                text: self.search_state.input.clone(),
+                case_sensitive: self.search_state.case_sensitive,
+            },
+            SearchMode::FindStrings => SearchQuery::Strings {
+                min_length: self.search_state.min_string_length,
+            },
+        };
+        let (start, end) = match self.search_state.scope {
+            SearchScope::All => (0, dbg.memory.end()),
+            SearchScope::Region(ref name) => dbg
+                .memory
+                .regions()
+                .iter()
+                .find(|region| region.name == *name)
+                .map(|region| (region.start, region.end))
+                .ok_or_else(|| format!("No {} region", name))?,
+            SearchScope::Range { start, end } => (start, end),
+        };
+        let scan = SearchScan::new(query, start, end, SEARCH_RESULT_LIMIT)
+            .map_err(|e| format!("Search error: {}", e))?;
+
+        self.search_state.clear_results();
+        self.search_state.scan = Some(scan);
+        self.advance_search();
+        Ok(())
+    }
+
+    /// Run the last search again after memory was patched, when the dialog
+    /// is set to live, keeping the selection where it was
+    fn refresh_search(&mut self) {
+        if !self.search_state.live {
+            return;
+        }
+        if let Some(ref mut scan) = self.search_state.scan {
+            scan.restart();
+            self.search_state.results.clear();
+            self.advance_search();
+        }
+    }
+
+    /// Scan the next stretch of memory for the running search, reporting
+    /// the total in the open dialog once it's done
+    pub fn advance_search(&mut self) {
+        let (Some(dbg), Some(scan)) = (&self.debugger, &mut self.search_state.scan) else {
+            return;
+        };
+        if scan.is_done() {
+            return;
+        }
+        let found = scan.step(&dbg.memory, SEARCH_STEP_BYTES);
+        self.search_state.results.extend(found);
+        if !scan.is_done() || !self.search_dialog_open {
+            return;
+        }
+
+        let noun = if self.search_state.mode == SearchMode::FindStrings {
+            "strings"
+        } else {
+            "matches"
+        };
+        self.message = Some(Message {
+            text: if scan.is_capped() {
+                format!(
+                    "Stopped at {} {} - refine your search",
+                    self.search_state.results.len(),
+                    noun
+                )
+            } else {
+                format!("Found {} {}", self.search_state.results.len(), noun)
+            },
+            is_error: false,
+        });
+    }
+
+    /// Jump to selected search result
+    pub fn goto_search_result(&mut self) {
+        if let Some(address) = self.search_state.get_selected_address() {
+            self.search_dialog_open = false;
+            self.goto_address(address);
+        }
+    }
+
+    /// Toggle bookmark at current disassembly address
+    pub fn toggle_bookmark_at_cursor(&mut self) {
+        if let Some(ref mut dbg) = self.debugger {
+            if self.disasm_selection < self.disasm_cache.len() {
+                let address = self.disasm_cache[self.disasm_selection].address;
+                let added = dbg
+                    .bookmarks
+                    .toggle(address, format!("Address 0x{:08X}", address));
+
+                self.message = Some(Message {
+                    text: if added {
+                        format!("Bookmark added at 0x{:08X}", address)
+                    } else {
+                        format!("Bookmark removed at 0x{:08X}", address)
+                    },
+                    is_error: false,
+                });
+            }
+        }
+    }
+
+    /// Address bookmark navigation starts from: the memory view's when
+    /// memory is focused, otherwise the selected disassembly line's
+    fn bookmark_cursor(&self) -> u32 {
+        match self.disasm_cache.get(self.disasm_selection) {
+            Some(line) if self.focused != FocusedPanel::Memory => line.address,
+            _ => self.memory_view_addr,
+        }
+    }
+
+    /// Go to next bookmark
+    pub fn goto_next_bookmark(&mut self) {
+        let current_addr = self.bookmark_cursor();
+        let Some(ref dbg) = self.debugger else {
+            return;
+        };
+
+        if let Some(next_addr) = dbg.bookmarks.next_after(current_addr) {
+            self.goto_bookmark(next_addr);
+        } else {
+            self.message = Some(Message {
+                text: "No more bookmarks after current address".to_string(),
+                is_error: false,
+            });
+        }
+    }
+
+    /// Go to previous bookmark
+    pub fn goto_prev_bookmark(&mut self) {
+        let current_addr = self.bookmark_cursor();
+        let Some(ref dbg) = self.debugger else {
+            return;
+        };
+
+        if let Some(prev_addr) = dbg.bookmarks.prev_before(current_addr) {
+            self.goto_bookmark(prev_addr);
+        } else {
+            self.message = Some(Message {
+                text: "No more bookmarks before current address".to_string(),
+                is_error: false,
+            });
+        }
+    }
+
+    /// Show a bookmark where it belongs: code in the disassembly with the
+    /// cursor on its line, anything else in the memory panel
+    pub fn goto_bookmark(&mut self, address: u32) {
+        let Some(ref dbg) = self.debugger else {
+            return;
+        };
+        let in_code = dbg
+            .memory
+            .get_region(address)
+            .is_some_and(|region| region.permissions.execute);
+        let note = dbg
+            .bookmarks
+            .get(address)
+            .map(|b| b.note.clone())
+            .unwrap_or_default();
+
+        let panel = if in_code || !self.layout_mode.shows(FocusedPanel::Memory) {
+            // Keep the listing still when the line is already in view
+            match self
+                .disasm_cache
+                .iter()
+                .position(|line| line.address == address)
+            {
+                Some(index) => self.disasm_selection = index,
+                None => {
+                    self.disasm_view_addr = Some(address);
+                    self.disasm_selection = 0;
+                    self.refresh_disasm();
+                }
+            }
+            FocusedPanel::Disassembly
+        } else {
+            self.memory_view_addr = address;
+            FocusedPanel::Memory
+        };
+        self.focused = panel;
+
+        self.message = Some(Message {
+            text: format!(
+                "{} (0x{:08X}) in {}",
+                if note.is_empty() { "Bookmark" } else { &note },
+                address,
+                if panel == FocusedPanel::Memory {
+                    "memory"
+                } else {
+                    "disassembly"
+                }
+            ),
+            is_error: false,
+        });
+    }
+
+    /// Bookmarks listed in the bookmarks dialog, under its category filter
+    pub fn listed_bookmarks(&self) -> Vec<&Bookmark> {
+        match self.debugger {
+            Some(ref dbg) => dbg.bookmarks.filtered(self.bookmarks_view_state.filter),
+            None => Vec::new(),
+        }
+    }
+
+    /// Address of the bookmark selected in the bookmarks dialog
+    fn selected_bookmark_address(&self) -> Option<u32> {
+        self.listed_bookmarks()
+            .get(self.bookmarks_view_state.selected)
+            .map(|b| b.address)
+    }
+
+    /// Delete selected bookmark
+    pub fn delete_selected_bookmark(&mut self) {
+        let Some(address) = self.selected_bookmark_address() else {
+            return;
+        };
+        if let Some(ref mut dbg) = self.debugger {
+            dbg.bookmarks.remove(address);
+
+            // Adjust selection if needed
+            let count = dbg
+                .bookmarks
+                .filtered(self.bookmarks_view_state.filter)
+                .len();
+            if self.bookmarks_view_state.selected > 0 && self.bookmarks_view_state.selected >= count
+            {
+                self.bookmarks_view_state.selected -= 1;
+            }
+
+            self.message = Some(Message {
+                text: format!("Bookmark deleted at 0x{:08X}", address),
+                is_error: false,
+            });
+        }
+    }
+
+    /// Start editing selected bookmark
+    pub fn start_editing_bookmark(&mut self) {
+        let note = self
+            .listed_bookmarks()
+            .get(self.bookmarks_view_state.selected)
+            .map(|b| (b.address, b.note.clone()));
+        if let Some((address, note)) = note {
+            self.bookmarks_view_state.start_editing(address, note);
+        }
+    }
+
+    /// Give the selected bookmark the next category (or none after the last)
+    pub fn cycle_bookmark_category(&mut self) {
+        let Some(address) = self.selected_bookmark_address() else {
+            return;
+        };
+        if let Some(ref mut dbg) = self.debugger {
+            let category =
+                BookmarkCategory::cycle(dbg.bookmarks.get(address).and_then(|b| b.category));
+            dbg.bookmarks.set_category(address, category);
+            self.message = Some(Message {
+                text: format!(
+                    "Bookmark at 0x{:08X}: {}",
+                    address,
+                    category.map_or("no category", |category| category.name())
+                ),
+                is_error: false,
+            });
+        }
+    }
+
+    /// Save edited bookmark
+    pub fn save_edited_bookmark(&mut self) {
+        if let Some(ref editing) = self.bookmarks_view_state.editing.clone() {
+            if let Some(ref mut dbg) = self.debugger {
+                dbg.bookmarks.update_note(editing.address, &editing.note);
+                self.bookmarks_view_state.cancel_editing();
+
+                self.message = Some(Message {
+                    text: format!("Bookmark updated at 0x{:08X}", editing.address),
+                    is_error: false,
+                });
+            }
+        }
+    }
+
+    /// Jump to selected bookmark in bookmarks dialog
+    pub fn goto_selected_bookmark(&mut self) {
+        if let Some(address) = self.selected_bookmark_address() {
+            self.bookmarks_dialog_open = false;
+            self.goto_bookmark(address);
+        }
+    }
+
+    /// Panel that a go-to-address jump repositions
+    pub fn goto_target(&self) -> FocusedPanel {
+        match self.focused {
+            FocusedPanel::Disassembly => FocusedPanel::Disassembly,
+            FocusedPanel::Stack => FocusedPanel::Stack,
+            _ if self.layout_mode.shows(FocusedPanel::Memory) => FocusedPanel::Memory,
+            _ => FocusedPanel::Disassembly,
+        }
+    }
+
+    /// Resolve a go-to expression: register name, bookmark note or hex address
+    pub fn resolve_address(&self, input: &str) -> Result<u32, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Enter an address".to_string());
+        }
+
+        if let Some(ref dbg) = self.debugger {
+            if let Some(value) = dbg.cpu.get_register_by_name(input) {
+                return Ok(value);
+            }
+
+            if let Some(bookmark) = dbg.bookmarks.find_note(input) {
+                return Ok(bookmark.address);
+            }
+        }
+
+        let hex = input
+            .strip_prefix("0x")
+            .or_else(|| input.strip_prefix("0X"))
+            .unwrap_or(input);
+        u32::from_str_radix(hex, 16).map_err(|_| format!("Unknown address: {}", input))
+    }
+
+    /// Reposition the focused panel (memory, disassembly or stack) at an address
+    pub fn goto_address(&mut self, address: u32) {
+        match self.goto_target() {
+            FocusedPanel::Disassembly => {
+                self.disasm_view_addr = Some(address);
+                self.disasm_selection = 0;
+                self.refresh_disasm();
+            }
+            FocusedPanel::Stack => {
+                self.stack_view_addr = Some(address);
+            }
+            _ => {
+                self.memory_view_addr = address;
+            }
+        }
+
+        self.message = Some(Message {
+            text: format!("Jumped to 0x{:08X}", address),
+            is_error: false,
+        });
+    }
+
+    /// Resolve the go-to dialog input and jump to it
+    pub fn submit_goto(&mut self) {
+        match self.resolve_address(&self.goto_state.input) {
+            Ok(address) => {
+                self.goto_dialog_open = false;
+                self.goto_state.clear();
+                self.goto_address(address);
+            }
+            Err(e) => {
+                self.message = Some(Message {
+                    text: e,
+                    is_error: true,
+                });
+            }
+        }
+    }
+}
+
+/// Capture the two points `diff_snapshots` was asked about and compare them
+fn snapshot_diff(debugger: &mut Debugger, args: &[&str]) -> Result<StateDiff, String> {
+    let snapshots = debugger.snapshots();
+    let here = debugger.position();
+    let point = |arg: &str| -> Result<u64, String> {
+        if arg.eq_ignore_ascii_case("here") {
+            return Ok(here);
+        }
+        arg.parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|index| snapshots.get(index).copied())
+            .ok_or_else(|| format!("No snapshot {} ({} marked so far)", arg, snapshots.len()))
+    };
+    let (from, to) = match args {
+        [] => match snapshots[..] {
+            [.., from, to] => (from, to),
+            [from] => (from, here),
+            [] => return Err("Mark snapshots with `snapshot` first, then compare them".to_string()),
+        },
+        [a, b, ..] if args.len().is_multiple_of(2) => (point(a)?, point(b)?),
+        _ => return Err("Usage: snapdiff [<a> <b> [<addr> <len>]...]".to_string()),
+    };
+
+    let mut ranges = Vec::new();
+    for pair in args.get(2..).unwrap_or_default().chunks(2) {
+        let address = u32::from_str_radix(pair[0].trim_start_matches("0x"), 16)
+            .map_err(|_| format!("Not an address: {}", pair[0]))?;
+        let length = match pair[1].strip_prefix("0x") {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => pair[1].parse::<u32>(),
+        }
+        .map_err(|_| format!("Not a length: {}", pair[1]))?;
+        ranges.push((address, length.min(MAX_DIFF_RANGE)));
+    }
+
+    let before = debugger.capture_at(from).map_err(|e| e.to_string())?;
+    let after = debugger.capture_at(to).map_err(|e| e.to_string())?;
+    Ok(StateDiff::new(&before, &after, &ranges))
+}
+
+/// Parse an export size like "160x50", defaulting when absent
+fn parse_export_size(size: Option<&str>) -> Result<(u16, u16), String> {
+    let Some(size) = size else {
+        return Ok(crate::export::DEFAULT_EXPORT_SIZE);
+    };
+    size.split_once(['x', 'X'])
+        .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+        .filter(|&(w, h): &(u16, u16)| (40..=500).contains(&w) && (10..=200).contains(&h))
+        .ok_or_else(|| format!("Bad size {} (e.g. 120x40)", size))
+}
+
+/// A fresh random seed for a puzzle session that doesn't pin one
+fn session_seed() -> u32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() ^ d.as_secs() as u32)
+        .unwrap_or(1)
+}
+
+/// Milliseconds since the unix epoch, for the time attack timer
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// What to tell the player when execution stops with an error; an
+/// instruction the emulator can't run points them at the reference
+fn stop_message(error: &str) -> String {
+    if error.starts_with("Unsupported instruction") {
+        format!(
+            "Stopped: {}. The emulator can't run it yet; press ? to look it up in the reference",
+            error
+        )
+    } else {
+        format!("Error: {}", error)
+    }
+}
+
+/// A tab's side of the compare view: its CPU, disassembly and first data bytes
+fn compare_side<'a>(
+    debugger: &'a Debugger,
+    puzzle: &Puzzle,
+    lines: &'a [DisassemblyLine],
+    tab: usize,
+) -> CompareSide<'a> {
+    let data_start = puzzle.setup.data_start;
+    CompareSide {
+        title: format!(" {}: {} ", tab + 1, puzzle.metadata.title),
+        cpu: &debugger.cpu,
+        lines,
+        data_start,
+        data: debugger.memory.slice(data_start, 256).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::screens::RESULTS_PER_PAGE;
+    use revgame_core::debugger::SlotRole;
+    use revgame_core::game::{Encounter, FileBackend};
+    use revgame_core::reference::{DrillAnswer, DrillFlags};
+
+    const PUZZLE: &str = r#"
+[metadata]
+id = "test-goto"
+title = "Goto"
+difficulty = 1
+category = "patching"
+
+[description]
+brief = "test"
+detailed = "test"
+
+[setup]
+code_start = 0x1000
+data_start = 0x2000
+stack_start = 0x3000
+
+[setup.registers]
+eax = 0x2010
+esp = 0x3000
+
+[setup.code]
+bytes = "90 90 F4"
+
+[validation]
+type = "normal_halt"
+
+[hints]
+"#;
+
+    fn app() -> App {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(PUZZLE).unwrap();
+        app.begin_puzzle();
+        app
+    }
+
+    #[test]
+    fn test_settings_saved_through_save_manager() {
+        let mut app = app();
+        app.cycle_layout();
+        assert!(!app.message.as_ref().unwrap().text.contains("not saved"));
+        app.set_option("markers", true).unwrap();
+        let layout = app.settings.debugger_layout.clone();
+
+        app.settings = Settings::default();
+        app.load_settings().unwrap();
+        assert_eq!(app.settings.debugger_layout, layout);
+        assert!(app.settings.focus_markers);
+    }
+
+    #[test]
+    fn test_session_log_follows_save_manager() {
+        let mut app = app();
+        app.set_option("log", true).unwrap();
+        assert!(!app.message.as_ref().unwrap().is_error);
+        app.set_option("log", false).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("revgame-session-{}", std::process::id()));
+        let backend = FileBackend::create(dir.clone()).unwrap();
+        let mut app = App::with_save_manager(SaveManager::with_backend(Box::new(backend)));
+        app.set_option("log", true).unwrap();
+        app.set_option("log", false).unwrap();
+        let written = dir.join("session.log").exists();
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(written);
+    }
+
+    #[test]
+    fn test_resolve_address() {
+        let mut app = app();
+        app.debugger.as_mut().unwrap().bookmarks.add(0x1002, "end");
+
+        assert_eq!(app.resolve_address("0x2000"), Ok(0x2000));
+        assert_eq!(app.resolve_address("1f00"), Ok(0x1F00));
+        assert_eq!(app.resolve_address("EAX"), Ok(0x2010));
+        assert_eq!(app.resolve_address("End"), Ok(0x1002));
+        assert!(app.resolve_address("nowhere").is_err());
+        assert!(app.resolve_address("").is_err());
+    }
+
+    #[test]
+    fn test_goto_repositions_focused_panel() {
+        let mut app = app();
+
+        app.focused = FocusedPanel::Disassembly;
+        app.goto_address(0x1001);
+        assert_eq!(app.disasm_view_addr, Some(0x1001));
+        assert_eq!(app.disasm_cache[0].address, 0x1001);
+
+        app.focused = FocusedPanel::Stack;
+        app.goto_address(0x2FF8);
+        assert_eq!(app.stack_view_addr, Some(0x2FF8));
+        assert_eq!(app.get_stack(1)[0].0, 0x2FF8);
+
+        app.focused = FocusedPanel::Registers;
+        app.goto_address(0x2040);
+        assert_eq!(app.memory_view_addr, 0x2040);
+
+        // Stepping snaps disassembly and stack back to EIP/ESP
+        app.step();
+        assert_eq!(app.disasm_view_addr, None);
+        assert_eq!(app.stack_view_addr, None);
+    }
+
+    #[test]
+    fn test_fast_run_skips_history_only_while_running() {
+        let mut app = app();
+        app.debugger.as_mut().unwrap().set_breakpoint(0x1001);
+
+        app.fast_run();
+        let debugger = app.debugger.as_ref().unwrap();
+        assert_eq!(debugger.cpu.eip, 0x1001);
+        assert!(debugger.history().is_empty());
+        assert_eq!(debugger.capture_policy(), CapturePolicy::Always);
+
+        app.step();
+        assert_eq!(app.debugger.as_ref().unwrap().history().len(), 1);
+    }
+
+    #[test]
+    fn test_puzzle_script_hints_and_verdict() {
+        // Top-level keys must come before the first table
+        let source = format!(
+            r#"script = """
+fn on_step() {{
+    if reg("eip") == 0x1001 {{ hint("One more NOP"); }}
+}}
+fn on_halt() {{
+    if reg("eax") == 0x2010 {{ fail("EAX was never changed"); }}
+}}
+"""
+{}"#,
+            PUZZLE
+        );
+
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        assert!(app.script.is_some());
+
+        app.step();
+        assert_eq!(app.message.as_ref().unwrap().text, "Hint: One more NOP");
+
+        app.step();
+        app.step();
+        let message = app.message.as_ref().unwrap();
+        assert!(message.is_error);
+        assert_eq!(message.text, "Not quite: EAX was never changed");
+    }
+
+    #[test]
+    fn test_explain_last_run_includes_verdict() {
+        let source = PUZZLE.replace(
+            "type = \"normal_halt\"",
+            "type = \"register_value\"\nregister = \"eax\"\nexpected = 1",
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+
+        app.run();
+        assert!(app.message.as_ref().unwrap().is_error);
+
+        app.explain_last_run();
+        assert!(app.explain_open);
+        assert!(app.run_explanation[0].starts_with("The program ran 3 instructions and halted"));
+        assert!(app
+            .run_explanation
+            .last()
+            .unwrap()
+            .starts_with("Validation failed:"));
+    }
+
+    #[test]
+    fn test_register_timeline_follows_selection() {
+        let mut app = app();
+        app.select_prev_register();
+        assert_eq!(Register::ALL[app.selected_register], Register::Eip);
+
+        app.step();
+        app.step();
+        let eips: Vec<u32> = app.register_timeline().iter().map(|s| s.value).collect();
+        assert_eq!(eips, vec![0x1001, 0x1002]);
+
+        app.select_next_register();
+        assert_eq!(app.register_timeline()[0].value, 0x2010);
+    }
+
+    #[test]
+    fn test_register_bits_view() {
+        let mut app = app();
+        app.toggle_register_bits();
+        let buffer = crate::export::render_view(&app, 160, 50).unwrap();
+        let text = crate::export::buffer_to_ansi(&buffer);
+        assert!(text.contains("bits, "));
+        assert!(text.contains("AL = "));
+
+        app.toggle_register_bits();
+        let text =
+            crate::export::buffer_to_ansi(&crate::export::render_view(&app, 160, 50).unwrap());
+        assert!(!text.contains("bits, "));
+    }
+
+    #[test]
+    fn test_bookmark_categories() {
+        let mut app = app();
+        let dbg = app.debugger.as_mut().unwrap();
+        dbg.bookmarks.add(0x1000, "start");
+        dbg.bookmarks.add(0x1001, "second");
+
+        // Cycling the selected bookmark's category, then filtering by it
+        app.bookmarks_view_state.selected = 1;
+        app.cycle_bookmark_category();
+        app.cycle_bookmark_category();
+        let bookmarks = &app.debugger.as_ref().unwrap().bookmarks;
+        assert_eq!(
+            bookmarks.get(0x1001).unwrap().category,
+            Some(BookmarkCategory::Check)
+        );
+
+        app.bookmarks_view_state.cycle_filter();
+        assert!(app.listed_bookmarks().is_empty());
+        app.bookmarks_view_state.cycle_filter();
+        assert_eq!(app.listed_bookmarks().len(), 1);
+
+        // Both disassembly lines carry a gutter marker, as does the memory row holding them
+        app.memory_view_addr = 0x1000;
+        let text =
+            crate::export::buffer_to_ansi(&crate::export::render_view(&app, 120, 40).unwrap());
+        assert_eq!(text.matches("▎").count(), 3);
+    }
+
+    #[test]
+    fn test_bookmark_jumps_pick_the_panel() {
+        let mut app = app();
+        let dbg = app.debugger.as_mut().unwrap();
+        dbg.bookmarks.add(0x1002, "hlt");
+        dbg.bookmarks.add(0x2100, "buffer");
+
+        // Code lands on its disassembly line, without scrolling it away
+        app.goto_next_bookmark();
+        assert_eq!(app.focused, FocusedPanel::Disassembly);
+        assert_eq!(app.disasm_cache[app.disasm_selection].address, 0x1002);
+        assert_eq!(app.disasm_cache[0].address, 0x1000);
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "hlt (0x00001002) in disassembly"
+        );
+
+        // Anything else moves the memory panel
+        app.goto_next_bookmark();
+        assert_eq!(app.focused, FocusedPanel::Memory);
+        assert_eq!(app.memory_view_addr, 0x2100);
+        assert!(app.message.as_ref().unwrap().text.ends_with("in memory"));
+
+        // Back from memory: previous is measured from the memory view
+        app.goto_prev_bookmark();
+        assert_eq!(app.focused, FocusedPanel::Disassembly);
+        assert_eq!(app.disasm_cache[app.disasm_selection].address, 0x1002);
+    }
+
+    #[test]
+    fn test_status_bar_shows_state_and_hints() {
+        let mut app = app();
+        let status = |app: &App| {
+            let buffer = crate::export::render_view(app, 160, 40).unwrap();
+            let area = buffer.area;
+            (area.x..area.width)
+                .map(|x| buffer[(x, area.height - 1)].symbol().to_string())
+                .collect::<String>()
+        };
+        let text = status(&app);
+        assert!(text.contains(" READY ") && text.contains("0 instr | test-goto"));
+        assert!(text.contains("[F9] BP"));
+
+        app.run();
+        app.focused = FocusedPanel::Memory;
+        let text = status(&app);
+        assert!(text.contains(" HALTED ") && text.contains("3 instr"));
+        assert!(text.contains("[T] Decode"));
+
+        // Progress changed since the last save
+        app.game_state.record_patch();
+        assert!(app.has_unsaved_progress());
+        assert!(status(&app).contains("unsaved"));
+    }
+
+    #[test]
+    fn test_marks_restored_when_the_puzzle_is_loaded_again() {
+        let mut app = app();
+        let dbg = app.debugger.as_mut().unwrap();
+        dbg.set_breakpoint(0x1001);
+        dbg.bookmarks.add(0x1002, "end");
+
+        // Loading the puzzle again, as after a restart with the session save
+        app.remember_marks();
+        let mut restarted = App::with_save_manager(SaveManager::in_memory());
+        restarted.game_state = app.game_state.clone();
+        restarted.load_puzzle(PUZZLE).unwrap();
+        let dbg = restarted.debugger.as_ref().unwrap();
+        assert!(dbg.has_breakpoint(0x1001));
+        assert_eq!(dbg.bookmarks.get(0x1002).unwrap().note, "end");
+        assert!(restarted
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("Restored 1 breakpoint and 1 bookmark"));
+
+        // Marks live per puzzle: a fresh progress save restores nothing
+        let mut other = App::with_save_manager(SaveManager::in_memory());
+        other.load_puzzle(PUZZLE).unwrap();
+        assert!(other.debugger.as_ref().unwrap().breakpoints.is_empty());
+    }
+
+    #[test]
+    fn test_pinned_expressions() {
+        let mut app = app();
+        app.debugger.as_mut().unwrap().bookmarks.add(0x1002, "end");
+        app.process_command("pin eip");
+        app.process_command("pin end - eip");
+        app.process_command("pin eax +");
+        assert!(app.message.as_ref().unwrap().is_error);
+        assert_eq!(app.pins.list().len(), 2);
+        assert_eq!(app.pins.list()[1].value, Ok(2));
+
+        app.step();
+        assert_eq!(app.pins.list()[0].value, Ok(0x1001));
+        assert!(app.pins.list()[1].changed);
+        let text =
+            crate::export::buffer_to_ansi(&crate::export::render_view(&app, 120, 40).unwrap());
+        assert!(text.contains("Watches"));
+
+        app.process_command("unpin 1");
+        assert_eq!(app.pins.list()[0].text, "end - eip");
+        app.process_command("unpin 5");
+        assert!(app.message.as_ref().unwrap().is_error);
+        app.process_command("unpin all");
+        assert!(app.pins.is_empty());
+    }
+
+    #[test]
+    fn test_inspect_encoding_of_selected_line() {
+        let mut app = app();
+        app.disasm_selection = 2;
+        app.inspect_encoding();
+
+        let encoding = app.encoding.as_ref().unwrap();
+        assert_eq!(encoding.address, 0x1002);
+        assert_eq!(encoding.text, "hlt");
+    }
+
+    #[test]
+    fn test_patch_command_guards_instruction_lengths() {
+        let source = PUZZLE.replace("90 90 F4", "75 00 F4");
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+
+        app.process_command("patch 0x1000 90");
+        let message = app.message.as_ref().unwrap();
+        assert!(message.is_error);
+        assert!(message.text.contains("add `pad` to fill with 1 NOP"));
+        assert_eq!(app.get_memory(0x1000, 2).unwrap(), vec![0x75, 0x00]);
+
+        // Patches that don't come from the command are guarded too
+        assert!(app
+            .patch_memory(0x1000, &[0x90])
+            .unwrap_err()
+            .contains("stray byte"));
+        assert_eq!(app.get_memory(0x1000, 2).unwrap(), vec![0x75, 0x00]);
+
+        app.process_command("patch 0x1000 90 pad");
+        assert_eq!(app.get_memory(0x1000, 2).unwrap(), vec![0x90, 0x90]);
+
+        app.process_command("patch! 0x1000 B8");
+        assert_eq!(app.get_memory(0x1000, 1).unwrap(), vec![0xB8]);
+    }
+
+    #[test]
+    fn test_self_checking_puzzle() {
+        let source = include_str!("../../../puzzles/06-integrity/001-self-check.toml");
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+
+        // Flipping the jump alone trips the self-check
+        app.load_puzzle(source).unwrap();
+        app.process_command("patch 0x1022 74");
+        app.run();
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.regs.eax, 0xBAD);
+
+        // Updating the stored sum fools the program but not the validator
+        app.load_puzzle(source).unwrap();
+        app.process_command("patch 0x1022 74");
+        app.process_command("patch 0x2000 BE");
+        app.run();
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.regs.eax, 1);
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("Self-check broken"));
+
+        // Making up the difference in the unused branch keeps the sum intact
+        app.load_puzzle(source).unwrap();
+        app.process_command("patch 0x1022 74");
+        app.process_command("patch 0x1031 AE");
+        app.run();
+        assert!(matches!(app.screen, Screen::PuzzleComplete { .. }));
+    }
+
+    #[test]
+    fn test_official_solutions_solve_their_puzzles() {
+        let sources = [
+            include_str!("../../../puzzles/01-basics/001-first-patch.toml"),
+            include_str!("../../../puzzles/01-basics/002-change-the-value.toml"),
+            include_str!("../../../puzzles/01-basics/003-jump-reversal.toml"),
+            include_str!("../../../puzzles/01-basics/004-force-the-jump.toml"),
+            include_str!("../../../puzzles/01-basics/005-nop-sled.toml"),
+            include_str!("../../../puzzles/06-integrity/001-self-check.toml"),
+            include_str!("../../../puzzles/06-integrity/002-timing-check.toml"),
+            include_str!("../../../puzzles/07-obfuscation/001-junk-bytes.toml"),
+            include_str!("../../../puzzles/07-obfuscation/003-flattened.toml"),
+            include_str!("../../../puzzles/08-packers/001-xor-stub.toml"),
+        ];
+        for source in sources {
+            let mut app = App::with_save_manager(SaveManager::in_memory());
+            app.load_puzzle(source).unwrap();
+            app.begin_puzzle();
+            let solution = app.puzzle.as_ref().unwrap().solution.clone().unwrap();
+            for patch in &solution.patches {
+                app.patch_memory(patch.address, &patch.bytes().unwrap())
+                    .unwrap();
+            }
+            // Puzzles that break on the goal changing need another run to halt
+            for _ in 0..3 {
+                if app.screen == Screen::Debugger {
+                    app.run();
+                }
+            }
+
+            let id = &app.puzzle.as_ref().unwrap().metadata.id;
+            let Screen::PuzzleComplete { ref message } = app.screen else {
+                panic!("{}: official solution did not solve the puzzle", id);
+            };
+            assert!(message.contains("(par "), "{}", id);
+            let overview = &app.solution.as_ref().unwrap().pages[0];
+            assert!(
+                overview
+                    .lines
+                    .contains(&"You found the official solution".to_string()),
+                "{}",
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn test_solution_walkthrough_after_completion() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(include_str!(
+            "../../../puzzles/01-basics/001-first-patch.toml"
+        ))
+        .unwrap();
+        assert!(app.solution.is_none());
+
+        // Solving it another way is compared against the official patch
+        app.process_command("patch 0x100F 01");
+        app.run();
+        let Screen::PuzzleComplete { ref message } = app.screen else {
+            panic!("not solved");
+        };
+        assert!(message.contains("Bytes patched: 1 (par 2)"));
+        let solution = app.solution.as_mut().unwrap();
+        assert!(solution.pages[0]
+            .lines
+            .contains(&"Under par by 1 byte".to_string()));
+        assert_eq!(solution.pages.last().unwrap().title, "Your other patches");
+
+        solution.next_page();
+        assert_eq!(solution.page, 1);
+        solution.prev_page();
+        solution.prev_page();
+        assert_eq!(solution.page, 0);
+
+        // Puzzles without a solution have no walkthrough
+        let mut app = self::app();
+        app.run();
+        assert!(app.solution.is_none());
+    }
+
+    #[test]
+    fn test_reveal_solution() {
+        // Nothing to reveal without an official solution
+        let mut app = app();
+        app.process_command("reveal");
+        assert!(!app.reveal_confirm_open);
+        assert!(app.message.as_ref().unwrap().is_error);
+
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(include_str!(
+            "../../../puzzles/01-basics/001-first-patch.toml"
+        ))
+        .unwrap();
+        app.game_state.score = 100;
+        app.game_state.achievements.current_streak = 4;
+
+        // Running out of hints points at the reveal
+        for _ in 0..4 {
+            app.show_hint();
+        }
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("`reveal` shows the official solution"));
+
+        app.process_command("reveal");
+        assert!(app.reveal_confirm_open);
+        assert_eq!(app.reveal_penalty(), 50);
+
+        app.reveal_solution();
+        assert!(!app.reveal_confirm_open);
+        let Screen::SolutionRevealed { ref message } = app.screen else {
+            panic!("solution not revealed");
+        };
+        assert!(message.contains("Score: -50 points"));
+        assert!(app.solution_open);
+        assert_eq!(app.solution.as_ref().unwrap().pages[0].title, "Overview");
+
+        assert_eq!(app.game_state.score, 50);
+        assert_eq!(app.game_state.achievements.current_streak, 0);
+        assert!(app.game_state.is_revealed("basic-001"));
+        assert!(!app.game_state.is_completed("basic-001"));
+    }
+
+    #[test]
+    fn test_junk_byte_puzzle() {
+        let source = include_str!("../../../puzzles/07-obfuscation/001-junk-bytes.toml");
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(source).unwrap();
+
+        // The declared junk byte doesn't swallow the real comparison
+        let junk = app
+            .disasm_cache
+            .iter()
+            .find(|line| line.address == 0x1004)
+            .unwrap();
+        assert_eq!(junk.text, "db 0xE8");
+        assert!(app
+            .disasm_cache
+            .iter()
+            .any(|line| line.address == 0x1005 && line.text.starts_with("cmp")));
+
+        app.process_command("patch 0x1008 90 90");
+        app.run();
+        assert!(matches!(app.screen, Screen::PuzzleComplete { .. }));
+    }
+
+    #[test]
+    fn test_recursive_disassembly_puzzle() {
+        let source = include_str!("../../../puzzles/07-obfuscation/002-linear-sweep.toml");
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(source).unwrap();
+
+        // Linear sweep decodes the junk byte as a CALL and hides the CMP
+        let text_at = |app: &App, address: u32| {
+            app.disasm_cache
+                .iter()
+                .find(|line| line.address == address)
+                .map(|line| line.text.clone())
+        };
+        assert!(text_at(&app, 0x1007).unwrap().starts_with("call"));
+        assert_eq!(text_at(&app, 0x1008), None);
+
+        app.process_command("disasm recursive");
+        assert_eq!(app.disasm_mode, DisassemblyMode::Recursive);
+        assert_eq!(text_at(&app, 0x1007).unwrap(), "db 0xE8");
+        assert!(text_at(&app, 0x1008).unwrap().starts_with("cmp ecx"));
+        assert!(text_at(&app, 0x1014).unwrap().starts_with("mov eax"));
+
+        app.process_command("disasm");
+        assert_eq!(app.disasm_mode, DisassemblyMode::Linear);
+
+        app.process_command("patch 0x100A 03");
+        app.run();
+        assert!(matches!(app.screen, Screen::PuzzleComplete { .. }));
+    }
+
+    #[test]
+    fn test_reset_keeps_patches_unless_hard() {
+        let mut app = app();
+        app.process_command("patch 0x1000 40");
+        app.run();
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.regs.eax, 0x2011);
+
+        app.reset();
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("keeping 1 patch"));
+        app.run();
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.regs.eax, 0x2011);
+
+        // Hard reset asks first when there are patches to lose
+        app.request_hard_reset();
+        assert!(app.hard_reset_confirm_open);
+        app.hard_reset();
+        assert!(!app.hard_reset_confirm_open);
+        let debugger = app.debugger.as_ref().unwrap();
+        assert_eq!(debugger.memory.read_u8(0x1000).unwrap(), 0x90);
+        assert!(!debugger.can_undo());
+
+        app.request_hard_reset();
+        assert!(!app.hard_reset_confirm_open);
+        app.process_command("reset hard");
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .ends_with("patches discarded"));
+    }
+
+    #[test]
+    fn test_diff_runs_before_and_after_patch() {
+        let mut app = app();
+
+        app.diff_runs();
+        assert!(app.trace_diff.is_none());
+        assert!(app.message.as_ref().unwrap().is_error);
+
+        // JE over the failure path, not taken while EAX != 0x42
+        let source = PUZZLE.replace("90 90 F4", "83 F8 42 74 05 B8 00 00 00 00 F4");
+        app.load_puzzle(&source).unwrap();
+        app.run();
+        app.reset();
+        app.process_command("patch 0x1003 EB");
+        app.run();
+        assert!(app.message.as_ref().unwrap().text.contains("[D] Diff"));
+
+        app.process_command("diff");
+        let diff = app.trace_diff.as_ref().unwrap();
+        assert_eq!(diff.divergence, Some(2));
+        assert_eq!(diff.decisive().unwrap().address, 0x1003);
+        assert_eq!(diff.before[2].address, 0x1005);
+        assert_eq!(diff.after[2].address, 0x100A);
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(PUZZLE).unwrap();
+        app.process_command("snapdiff");
+        assert!(app.message.as_ref().unwrap().is_error);
+
+        app.process_command("snapshot");
+        app.step();
+        app.step();
+        app.process_command("snapshot");
+        app.step();
+        let here = app.debugger.as_ref().unwrap().position();
+
+        app.process_command("snapdiff");
+        let diff = app.state_diff.take().unwrap();
+        assert_eq!((diff.from, diff.to), (0, 2));
+        assert!(diff
+            .changed_registers()
+            .any(|(reg, _, _)| *reg == Register::Eip));
+        assert_eq!(app.debugger.as_ref().unwrap().position(), here);
+
+        app.process_command("snapdiff 2 here 0x2000 0x10");
+        let diff = app.state_diff.take().unwrap();
+        assert_eq!(
+            (diff.from, diff.to, diff.ranges.as_slice()),
+            (2, here, &[(0x2000, 16)][..])
+        );
+
+        app.process_command("snapdiff 1 3");
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("No snapshot 3"));
+        app.process_command("snapdiff 1 2 0x2000");
+        assert!(app.message.as_ref().unwrap().text.starts_with("Usage"));
+        assert!(app.state_diff.is_none());
+    }
+
+    #[test]
+    fn test_break_on_flag_change() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        // xor eax, eax; nop; hlt
+        app.load_puzzle(&PUZZLE.replace("90 90 F4", "31 C0 90 F4"))
+            .unwrap();
+        app.process_command("break-on qf");
+        assert!(app.message.as_ref().unwrap().is_error);
+
+        app.process_command("break-on zf");
+        app.process_command("break-on");
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "Breaking when ZF changes"
+        );
+
+        app.run();
+        let debugger = app.debugger.as_ref().unwrap();
+        assert_eq!(debugger.state, DebuggerState::AtFlagChange(Flag::Zf));
+        assert_eq!(debugger.cpu.eip, 0x1002);
+        let hit = app.flag_hit.as_ref().unwrap();
+        assert_eq!((hit.eip, hit.set), (0x1000, true));
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("ZF set by xor eax, eax"));
+
+        app.process_command("break-on off");
+        app.restart(true);
+        assert!(app.flag_hit.is_none());
+        app.run();
+        assert!(app.flag_hit.is_none());
+        assert_eq!(app.debugger.as_ref().unwrap().state, DebuggerState::Halted);
+    }
+
+    #[test]
+    fn test_instruction_coverage() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&PUZZLE.replace("90 90 F4", "31 C0 90 F4"))
+            .unwrap();
+        let coverage = &app.game_state.coverage;
+        assert_eq!(coverage.encounter("XOR"), Encounter::Seen);
+        assert_eq!(coverage.encounter("ROL"), Encounter::Never);
+
+        app.step();
+        app.run();
+        let coverage = &app.game_state.coverage;
+        assert_eq!(coverage.tally(["XOR", "NOP", "HLT"]), (3, 0, 0));
+        assert_eq!(coverage.executed("NOP"), 1);
+
+        // Going back over the timeline doesn't run anything again
+        app.seek_timeline(1);
+        app.run();
+        assert_eq!(app.game_state.coverage.executed("NOP"), 2);
+        assert_eq!(app.game_state.coverage.executed("XOR"), 1);
+    }
+
+    #[test]
+    fn test_program_input_from_stdin() {
+        let source = include_str!("../../../puzzles/03-crackmes/004-stdin-serial.toml");
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(source).unwrap();
+
+        // The default input is rejected
+        app.run();
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.regs.eax, 0);
+        assert!(!matches!(app.screen, Screen::PuzzleComplete { .. }));
+
+        app.open_input_dialog();
+        assert_eq!(app.input_state.text, "hello");
+        app.input_state.text = r"bad\q".to_string();
+        app.submit_input();
+        assert!(app.input_dialog_open);
+        assert!(app.message.as_ref().unwrap().is_error);
+
+        app.input_state.text = "ddddd".to_string();
+        app.submit_input();
+        assert!(!app.input_dialog_open);
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1000);
+        app.run();
+        assert!(matches!(app.screen, Screen::PuzzleComplete { .. }));
+    }
+
+    #[test]
+    fn test_input_placed_in_memory() {
+        let source = PUZZLE.replace(
+            "[validation]",
+            "[setup.input]\ndefault = \"AB\"\naddress = 0x2100\nmax_length = 8\nlength_register = \"ecx\"\n\n[validation]",
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        let debugger = app.debugger.as_ref().unwrap();
+        assert_eq!(debugger.memory.slice(0x2100, 3).unwrap(), b"AB\0");
+        assert_eq!(debugger.cpu.regs.ecx, 2);
+        assert!(app
+            .input_placement_text()
+            .unwrap()
+            .contains("length in ECX"));
+
+        app.process_command(r"input x y\n");
+        let debugger = app.debugger.as_ref().unwrap();
+        assert_eq!(debugger.memory.slice(0x2100, 5).unwrap(), b"x y\n\0");
+        assert_eq!(debugger.cpu.regs.ecx, 4);
+    }
+
+    #[test]
+    fn test_break_on_goal_change() {
+        // MOV EAX, 1; NOP; HLT
+        let source = PUZZLE.replace("90 90 F4", "B8 01 00 00 00 90 F4").replace(
+            "type = \"normal_halt\"",
+            "type = \"register_value\"\nregister = \"eax\"\nexpected = 1",
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+
+        // Stepping highlights the change even when not breaking
+        app.step();
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "Goal: EAX just became 0x00000001 (was 0x00002010) at 0x00001000"
+        );
+
+        app.reset();
+        app.process_command("watch on");
+        assert!(app.message.as_ref().unwrap().text.ends_with("on: EAX"));
+        app.run();
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("Goal: EAX just became 0x00000001"));
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1005);
+
+        app.process_command("watch off");
+        app.reset();
+        app.run();
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("Program halted"));
+    }
+
+    #[test]
+    fn test_objectives_track_progress() {
+        // MOV EAX, 1; NOP; HLT
+        let source = PUZZLE.replace("90 90 F4", "B8 01 00 00 00 90 F4").replace(
+            "type = \"normal_halt\"",
+            "type = \"register_value\"\nregister = \"eax\"\nexpected = 1",
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+
+        app.process_command("goal");
+        assert!(app.objectives_open);
+        let objectives = app.objectives();
+        assert_eq!(objectives.len(), 1);
+        assert_eq!(
+            objectives[0].description,
+            "EAX = 0x00000001 when the program halts"
+        );
+        assert!(!objectives[0].met);
+
+        app.step();
+        assert!(app.objectives()[0].met);
+    }
+
+    #[test]
+    fn test_recommended_puzzle() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.message = None;
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../puzzles"));
+        app.puzzle_select_state.load_puzzles(dir).unwrap();
+
+        let recommended = app.recommended_puzzle().unwrap();
+        assert_eq!(recommended.id, "basic-001");
+        app.select_recommended();
+        assert_eq!(
+            app.puzzle_select_state.get_selected_puzzle().unwrap().id,
+            "basic-001"
+        );
+        assert!(app.message.is_none());
+
+        // Failed checks count towards struggling with the puzzle's concepts
+        app.load_puzzle(include_str!(
+            "../../../puzzles/01-basics/001-first-patch.toml"
+        ))
+        .unwrap();
+        for _ in 0..3 {
+            app.reset();
+            app.run();
+        }
+        app.reset();
+        app.process_command("patch 0x1005 90 90");
+        app.run();
+        assert!(matches!(app.screen, Screen::PuzzleComplete { .. }));
+        assert_eq!(
+            app.game_state.achievements.puzzle_stats["basic-001"].failed_checks,
+            3
+        );
+        assert!(app.review_puzzles().contains("basic-001"));
+        assert!(app
+            .recommended_puzzle()
+            .unwrap()
+            .reason
+            .contains("which gave you trouble"));
+    }
+
+    #[test]
+    fn test_timing_check_puzzle() {
+        let source = include_str!("../../../puzzles/06-integrity/002-timing-check.toml");
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(source).unwrap();
+
+        // NOPs are a cycle slower than the jump they replace
+        app.process_command("patch 0x100A 90 90");
+        app.run();
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.regs.eax, 0xDEB);
+        app.process_command("cycles");
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("Cycles: 51 "));
+
+        // Author-tuned costs change what RDTSC sees
+        let tuned = source.replace(
+            "[setup.code]",
+            "[setup.cycles]\nbase = 2\ncosts = { rdtsc = 4 }\n\n[setup.code]",
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&tuned).unwrap();
+        app.process_command("patch 0x100A 90 90");
+        app.run();
+        assert!(matches!(app.screen, Screen::PuzzleComplete { .. }));
+    }
+
+    #[test]
+    fn test_rng_seed_command() {
+        let random_puzzle = PUZZLE.replace(
+            "[setup]\n",
+            "[setup]\nrng_seed = 1234\ninterrupts = [{ vector = 0x1B, action = \"random\", register = \"ebx\" }]\n",
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&random_puzzle).unwrap();
+        assert_eq!(app.debugger.as_ref().unwrap().rng_seed(), 1234);
+        app.process_command("seed");
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("RNG seed: 1234"));
+
+        app.process_command("seed 0x10");
+        assert_eq!(app.debugger.as_ref().unwrap().rng_seed(), 16);
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "RNG seed set to 16; reset to initial state"
+        );
+
+        app.process_command("seed lots");
+        assert!(app.message.as_ref().unwrap().is_error);
+        assert_eq!(app.debugger.as_ref().unwrap().rng_seed(), 16);
+    }
+
+    #[test]
+    fn test_skill_tree() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.message = None;
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../puzzles"));
+        app.open_skill_tree(dir).unwrap();
+        assert_eq!(app.screen, Screen::SkillTree);
+
+        let tree = app.skill_tree();
+        let index = |concept: &str| {
+            tree.iter()
+                .position(|node| node.concept == concept)
+                .unwrap()
+        };
+        assert!(tree.iter().all(|node| !node.mastered()));
+        assert!(tree[index("patching")].unlocked);
+
+        // A locked branch says what it needs
+        app.skill_tree_state.selected = index("immediates");
+        app.practice_selected_concept();
+        assert_eq!(app.screen, Screen::SkillTree);
+        assert!(app.message.take().unwrap().text.contains("master patching"));
+
+        // An open one points at its first unsolved puzzle
+        app.skill_tree_state.selected = index("patching");
+        app.practice_selected_concept();
+        assert_eq!(app.screen, Screen::PuzzleSelect);
+        let first = tree[index("patching")].puzzles[0].0.clone();
+        assert_eq!(
+            app.puzzle_select_state.get_selected_puzzle().unwrap().id,
+            first
+        );
+
+        // Mastering the root unlocks the branches built on it
+        for (id, _, _) in &tree[index("patching")].puzzles {
+            app.game_state.complete_puzzle(id, 1);
+        }
+        let tree = app.skill_tree();
+        assert!(tree[index("patching")].mastered());
+        assert!(tree[index("immediates")].unlocked);
+    }
+
+    #[test]
+    fn test_continue_last_puzzle() {
+        let mut app = app();
+        assert!(app.continue_title().is_none());
+        assert!(app.continue_last_puzzle().is_err());
+        assert!(!app.main_menu_items().contains(&MainMenuItem::Continue));
+
+        let path = Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../puzzles/01-basics/002-change-the-value.toml"
+        ));
+        app.load_puzzle_file(path).unwrap();
+        let id = app.puzzle.as_ref().unwrap().metadata.id.clone();
+        assert_eq!(
+            app.continue_title().as_deref(),
+            Some("002-change-the-value")
+        );
+        assert_eq!(app.main_menu_items()[0], MainMenuItem::Continue);
+
+        app.screen = Screen::MainMenu;
+        app.puzzle = None;
+        app.continue_last_puzzle().unwrap();
+        assert_eq!(app.puzzle.as_ref().unwrap().metadata.id, id);
+        assert_eq!(app.screen, Screen::Briefing);
+
+        assert_eq!(app.progress_summary(), "Solved 0/0 puzzles (0%)  Streak: 0");
+    }
+
+    #[test]
+    fn test_onboarding_sets_profile() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.start_onboarding();
+        assert_eq!(app.screen, Screen::Onboarding);
+
+        for c in "Ada".chars() {
+            app.onboarding_state.push_char(c);
+        }
+        app.onboarding_state.advance();
+        app.onboarding_state.navigate_down();
+        app.preview_onboarding_theme();
+        assert_eq!(app.settings.theme, "light");
+
+        app.onboarding_state.advance();
+        let experience = app.finish_onboarding();
+        assert_eq!(experience, Experience::Beginner);
+        assert!(experience.starts_tutorial());
+        assert_eq!(app.settings.profile_name, "Ada");
+        assert_eq!(app.settings.experience, "beginner");
+        assert!(app.settings.eager_hints);
+        assert_eq!(app.screen, Screen::MainMenu);
+    }
+
+    #[test]
+    fn test_eager_hint_after_failed_run() {
+        // The default puzzle halts without EAX = 1
+        let source = PUZZLE
+            .replace(
+                "type = \"normal_halt\"",
+                "type = \"register_value\"\nregister = \"eax\"\nexpected = 1",
+            )
+            .replace(
+                "[hints]",
+                "[hints]\n\n[[hints.rules]]\nwhen = \"patches == 0\"\nhint = \"Try a patch\"",
+            );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        app.run();
+        assert!(!app.message.as_ref().unwrap().text.contains("Hint"));
+
+        app.settings.eager_hints = true;
+        app.reset();
+        app.run();
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .ends_with(". Hint: Try a patch"));
+    }
+
+    #[test]
+    fn test_hardcore_assist_disables_help() {
+        let mut app = app();
+        app.process_command("assist hardcore");
+        assert_eq!(app.game_state.assist, AssistLevel::Hardcore);
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("still scores as Standard"));
+
+        app.patch_memory(0x1000, &[0xF4]).unwrap();
+        assert!(app
+            .undo_patch()
+            .unwrap_err()
+            .starts_with("Undo is off at Hardcore assist"));
+        app.show_hint();
+        assert!(app.message.as_ref().unwrap().is_error);
+        app.explain_last_run();
+        assert!(!app.explain_open);
+
+        app.process_command("assist standard");
+        assert!(app.undo_patch().is_ok());
+
+        app.process_command("assist easy");
+        assert!(app.message.as_ref().unwrap().text.contains("Usage: assist"));
+    }
+
+    #[test]
+    fn test_predict_mode_grades_steps() {
+        let mut app = app();
+        app.settings.predict_mode = "flags".to_string();
+
+        assert!(!app.request_step());
+        assert!(app.predict_dialog_open);
+        assert_eq!(app.predict_state.instruction, "nop");
+
+        app.predict_state.text = "EAX".to_string();
+        app.submit_prediction();
+        assert!(app.message.as_ref().unwrap().is_error);
+        assert!(app.predict_dialog_open);
+        assert_eq!(app.current_eip(), Some(0x1000));
+
+        app.predict_state.text = "ZF".to_string();
+        app.submit_prediction();
+        assert!(!app.predict_dialog_open);
+        assert_eq!(app.current_eip(), Some(0x1001));
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("but nothing changed"));
+
+        app.request_step();
+        app.predict_state.text = "none".to_string();
+        app.submit_prediction();
+        assert_eq!(app.game_state.predictions.attempted, 2);
+        assert_eq!(app.game_state.predictions.streak, 1);
+        assert_eq!(app.game_state.predictions.by_mnemonic["NOP"], (2, 1));
+
+        app.settings.predict_mode = "off".to_string();
+        assert!(app.request_step());
+    }
+
+    #[test]
+    fn test_concepts_linked_from_tutorial_and_reference() {
+        let mut app = app();
+        app.screen = Screen::Debugger;
+        let mut tutorial = Tutorial::interface_tutorial();
+        while tutorial.current().unwrap().concept.is_none() {
+            tutorial.advance();
+        }
+        app.tutorial = Some(tutorial);
+
+        let id = app.linked_concept().unwrap();
+        app.show_linked_concept();
+        assert_eq!(app.screen, Screen::Reference);
+        assert_eq!(app.reference_state.get_current_concept().unwrap().id, id);
+
+        app.close_reference();
+        assert_eq!(app.screen, Screen::Debugger);
+
+        app.tutorial = None;
+        app.show_linked_concept();
+        assert_eq!(
+            app.reference_state.view_mode,
+            crate::screens::ReferenceViewMode::ConceptList
+        );
+        app.reference_state.type_concept_query(Some('z'));
+        app.reference_state.type_concept_query(Some('f'));
+        assert_eq!(
+            app.reference_state.get_current_concept().unwrap().id,
+            "flags"
+        );
+    }
+
+    #[test]
+    fn test_endian_view_follows_memory() {
+        let mut empty = App::with_save_manager(SaveManager::in_memory());
+        empty.open_endian_view();
+        assert!(empty.endian.as_ref().unwrap().sample);
+
+        let mut app = app();
+        app.patch_memory(0x2000, &[0x78, 0x56, 0x34, 0x12, 0xAA])
+            .unwrap();
+        app.memory_view_addr = 0x2000;
+        app.open_endian_view();
+        let endian = app.endian.as_ref().unwrap();
+        assert!(!endian.sample);
+        assert_eq!(u32::from_le_bytes(endian.bytes), 0x12345678);
+
+        app.move_endian_view(1);
+        assert_eq!(app.endian.as_ref().unwrap().bytes, [0x56, 0x34, 0x12, 0xAA]);
+        assert_eq!(app.memory_view_addr, 0x2001);
+    }
+
+    #[test]
+    fn test_converter_reads_registers() {
+        let mut app = app();
+        assert_eq!(app.convert("eax ^ 0x10").unwrap().value, 0x2000);
+        assert!(App::with_save_manager(SaveManager::in_memory())
+            .convert("eax + 1")
+            .is_ok());
+
+        app.process_command("calc 0x1337 ^ 0xFF");
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "0x1337 ^ 0xFF = 0x000013C8 = 5064 (signed 5064)"
+        );
+        app.process_command("calc 1 +");
+        assert!(app.message.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn test_drills_track_accuracy() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.start_drills(42);
+        assert_eq!(app.screen, Screen::Drills);
+
+        let answer = app.drills_state.drill.as_ref().unwrap().answer().unwrap();
+        app.drills_state.input = answer.describe();
+        app.submit_drill_answer();
+        assert!(app.drills_state.answered());
+        assert!(app.drills_state.feedback.as_ref().unwrap().1);
+
+        app.next_drill();
+        assert!(!app.drills_state.answered());
+        app.drills_state.input = "nonsense!".to_string();
+        app.submit_drill_answer();
+        assert!(app.message.as_ref().unwrap().is_error);
+        assert!(!app.drills_state.answered());
+
+        assert_eq!(app.game_state.drills.attempted, 1);
+        assert_eq!(app.game_state.drills.accuracy(), 100);
+    }
+
+    #[test]
+    fn test_checkpoint_quiz_unlocks_chapter() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../puzzles"));
+        app.open_puzzle_select(dir).unwrap();
+        let flow = app
+            .puzzle_select_state
+            .categories
+            .iter()
+            .position(|c| c.name == "control-flow")
+            .unwrap();
+        assert!(app.puzzle_select_state.categories[flow]
+            .puzzles
+            .iter()
+            .all(|p| p.is_locked));
+
+        // Basics has no checkpoint in front of it
+        app.puzzle_select_state.selected_category = app
+            .puzzle_select_state
+            .categories
+            .iter()
+            .position(|c| c.name == "basics")
+            .unwrap();
+        app.start_checkpoint(1);
+        assert_eq!(app.screen, Screen::PuzzleSelect);
+
+        app.puzzle_select_state.selected_category = flow;
+        let answer_all = |app: &mut App, right: bool| {
+            app.start_checkpoint(9);
+            assert_eq!(app.screen, Screen::Drills);
+            while app.screen == Screen::Drills {
+                let answer = app.drills_state.drill.as_ref().unwrap().answer().unwrap();
+                app.drills_state.input = match (right, answer) {
+                    (true, _) => answer.describe(),
+                    (false, DrillAnswer::Value(value)) => format!("0x{:X}", value ^ 1),
+                    (false, DrillAnswer::Flags(flags)) => DrillFlags {
+                        zf: !flags.zf,
+                        ..flags
+                    }
+                    .describe(),
+                };
+                app.submit_drill_answer();
+                app.next_drill();
+            }
+        };
+
+        answer_all(&mut app, false);
+        assert!(app.message.as_ref().unwrap().is_error);
+        assert!(app.puzzle_select_state.categories[flow].puzzles[0].is_locked);
+
+        // Giving up part way doesn't count as an attempt
+        app.start_checkpoint(3);
+        app.leave_drills();
+        assert_eq!(app.screen, Screen::PuzzleSelect);
+
+        answer_all(&mut app, true);
+        assert!(!app.message.as_ref().unwrap().is_error);
+        assert!(!app.puzzle_select_state.categories[flow].puzzles[0].is_locked);
+        let result = &app.game_state.checkpoints["arithmetic"];
+        assert_eq!((result.attempts, result.best, result.passed), (2, 5, true));
+        assert_eq!(app.game_state.drills.attempted, 10);
+    }
+
+    #[test]
+    fn test_bundled_campaign_is_playable() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../../puzzles"));
+        app.open_puzzle_select(dir).unwrap();
+        let state = &app.puzzle_select_state;
+        assert!(!state.campaign.checkpoints.is_empty());
+        for checkpoint in &state.campaign.checkpoints {
+            assert!(
+                state
+                    .categories
+                    .iter()
+                    .any(|c| c.name == checkpoint.unlocks),
+                "{} unlocks no chapter",
+                checkpoint.id
+            );
+            for mnemonic in &checkpoint.mnemonics {
+                let deck = DrillDeck::new(&app.reference_state.reference, 1)
+                    .restricted_to(std::slice::from_ref(mnemonic));
+                assert!(
+                    !deck.is_empty(),
+                    "{} asks about {}, which has no drills",
+                    checkpoint.id,
+                    mnemonic
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_targeted_hint_before_static_hints() {
+        let source = PUZZLE.replace(
+            "[hints]",
+            r#"[hints]
+level1 = "Static hint"
+
+[[hints.rules]]
+when = "breakpoints == 0"
+hint = "Set a breakpoint first""#,
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+
+        app.show_hint();
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "Hint: Set a breakpoint first"
+        );
+
+        // Asking again moves on to the puzzle's static hints
+        app.show_hint();
+        assert_eq!(app.message.as_ref().unwrap().text, "Hint 1: Static hint");
+        assert_eq!(app.game_state.hints_used, 2);
+    }
+
+    #[test]
+    fn test_focus_skips_hidden_panels() {
+        let mut app = app();
+        app.layout_mode = LayoutMode::Minimal;
+        app.focused = FocusedPanel::Registers;
+
+        app.focus_next();
+        assert_eq!(app.focused, FocusedPanel::Command);
+        app.focus_prev();
+        assert_eq!(app.focused, FocusedPanel::Registers);
+        assert_eq!(app.goto_target(), FocusedPanel::Disassembly);
+    }
+
+    #[test]
+    fn test_announce_step() {
+        let mut app = app();
+        app.settings.announce = true;
+
+        app.step();
+        let text = &app.message.as_ref().unwrap().text;
+        assert!(text.starts_with("At 0x00001001: nop"), "{}", text);
+    }
+
+    #[test]
+    fn test_stack_frame_collapses_on_ret() {
+        // CALL 0x1006; HLT; PUSH EBP; POP EBP; RET
+        let source = PUZZLE.replace("90 90 F4", "E8 01 00 00 00 F4 55 5D C3");
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        app.settings.stack_frames = true;
+
+        app.step();
+        app.step();
+        let frames = app.debugger.as_ref().unwrap().frames().frames();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].saved_ebp_slot, Some(0x2FF8));
+
+        app.step();
+        app.step();
+        assert_eq!(app.current_eip(), Some(0x1005));
+        assert!(app.animations.frame_collapse.is_active());
+        assert_eq!(app.collapsing_frame().unwrap().entry, 0x1006);
+        // The returned slots stay in view above ESP as the frame collapses
+        assert_eq!(app.get_stack(1)[0].0, 0x2FF8);
+
+        app.animations.update(std::time::Duration::from_secs(1));
+        assert!(app.collapsing_frame().is_none());
+        assert_eq!(app.get_stack(1)[0].0, 0x3000);
+    }
+
+    #[test]
+    fn test_call_arguments_and_return_value() {
+        // PUSH 2; PUSH 1; CALL 0x100A; HLT; MOV EAX, 3; RET
+        let source = PUZZLE.replace(
+            "90 90 F4",
+            "6A 02 6A 01 E8 01 00 00 00 F4 B8 03 00 00 00 C3",
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        assert!(app.call_arguments().is_none());
+
+        app.step();
+        app.step();
+        assert_eq!(app.disasm_cache[app.disasm_selection].address, 0x1004);
+        assert_eq!(&app.call_arguments().unwrap()[..2], &[1, 2]);
+
+        app.step();
+        let frames = app.debugger.as_ref().unwrap().frames();
+        assert_eq!(
+            frames.classify(0x2FF8, 0x2FF4),
+            Some((0, SlotRole::Argument(0)))
+        );
+
+        app.step();
+        app.step();
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "Returned from 0x0000100A with EAX = 0x00000003 (3)"
+        );
+    }
+
+    #[test]
+    fn test_pseudo_code_follows_cursor() {
+        let mut app = app();
+        app.process_command("pseudo");
+        assert!(app.pseudo_open);
+
+        let lines = app.pseudo_code();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].text, "halt();");
+
+        app.disasm_selection = app.disasm_cache.len();
+        assert!(app.pseudo_code().is_empty());
+    }
+
+    #[test]
+    fn test_idiom_notes_until_learned() {
+        // XOR EAX, EAX; XOR ECX, ECX; HLT
+        let source = PUZZLE.replace("90 90 F4", "31 C0 31 C9 F4");
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        app.settings.idiom_notes = true;
+
+        let notes = app.idiom_notes();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].address, 0x1000);
+
+        app.disasm_selection = 1;
+        assert!(app.mark_idiom_learned(None).is_err());
+        assert!(app.mark_idiom_learned(Some("bogus")).is_err());
+
+        app.disasm_selection = 0;
+        assert_eq!(app.mark_idiom_learned(None), Ok(Idiom::ZeroRegister));
+        assert_eq!(app.settings.learned_idioms, ["zeroing"]);
+        assert!(app.idiom_notes().is_empty());
+    }
+
+    #[test]
+    fn test_decode_and_patch_back() {
+        let mut app = app();
+        app.patch_memory(0x2000, b"Uryyb").unwrap();
+
+        app.process_command("decode 0x2000 5 rot13");
+        assert_eq!(app.decoder_input(), b"Uryyb");
+        assert_eq!(app.decoder_preview().unwrap().1, b"Hello");
+
+        app.apply_decoder().unwrap();
+        assert!(app.decoder.is_none());
+        assert_eq!(app.get_memory(0x2000, 5).unwrap(), b"Hello");
+
+        app.process_command("decode 0x2000 5 xor");
+        assert!(app.decoder_preview().is_err());
+        assert!(app.apply_decoder().is_err());
+    }
+
+    #[test]
+    fn test_magic_constant_in_data() {
+        let mut app = app();
+        app.patch_memory(0x2004, &0x811C_9DC5u32.to_le_bytes())
+            .unwrap();
+
+        let found = app.magic_constants();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].address, 0x2004);
+        assert_eq!(found[0].constant.name, "FNV-1 offset basis");
+
+        app.process_command("constants");
+        app.goto_selected_constant();
+        assert!(app.constants.is_none());
+        assert_eq!(app.memory_view_addr, 0x2004);
+    }
+
+    #[test]
+    fn test_search_pages_and_caps_results() {
+        let mut app = app();
+        app.search_dialog_open = true;
+        app.search_state.mode = SearchMode::Bytes;
+        app.search_state.input = "90 90 F4".to_string();
+        app.start_search().unwrap();
+        while app.search_state.is_searching() {
+            app.advance_search();
+        }
+        assert_eq!(app.search_state.get_selected_address(), Some(0x1000));
+        assert_eq!(app.message.as_ref().unwrap().text, "Found 1 matches");
+
+        // Zero bytes are everywhere: the search stops at the limit
+        app.search_state.input = "00".to_string();
+        app.start_search().unwrap();
+        while app.search_state.is_searching() {
+            app.advance_search();
+        }
+        assert!(app.search_state.is_capped());
+        assert_eq!(app.search_state.results.len(), SEARCH_RESULT_LIMIT);
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("refine your search"));
+
+        assert_eq!(
+            app.search_state.page_count(),
+            SEARCH_RESULT_LIMIT / RESULTS_PER_PAGE
+        );
+        app.search_state.page_down();
+        assert_eq!(app.search_state.page(), 1);
+        assert_eq!(app.search_state.selected_result, RESULTS_PER_PAGE);
+        app.search_state.page_up();
+        app.search_state.page_up();
+        assert_eq!(app.search_state.selected_result, 0);
+
+        app.search_state.input = "9".to_string();
+        assert!(app.start_search().is_err());
+    }
+
+    #[test]
+    fn test_search_scope_and_live_refresh() {
+        let mut app = app();
+        app.debugger
+            .as_mut()
+            .unwrap()
+            .memory
+            .write_bytes(0x2000, &[0x90, 0x90])
+            .unwrap();
+        let search = |app: &mut App| {
+            app.start_search().unwrap();
+            while app.search_state.is_searching() {
+                app.advance_search();
+            }
+            app.search_state
+                .results
+                .iter()
+                .map(|r| r.address)
+                .collect::<Vec<u32>>()
+        };
+        app.search_state.mode = SearchMode::Bytes;
+        app.search_state.input = "90 90".to_string();
+        assert_eq!(search(&mut app), vec![0x1000, 0x2000]);
+
+        app.process_command("search code");
+        assert!(app.search_dialog_open);
+        assert_eq!(
+            app.search_state.scope,
+            SearchScope::Region("code".to_string())
+        );
+        assert_eq!(search(&mut app), vec![0x1000]);
+
+        app.process_command("search 0x1000 0x1001");
+        assert_eq!(search(&mut app), Vec::<u32>::new());
+        app.process_command("search nowhere");
+        assert!(app.message.as_ref().unwrap().is_error);
+
+        // Cycling wraps from the custom range back to all memory
+        app.search_state.cycle_scope(&["code".to_string()]);
+        assert_eq!(app.search_state.scope, SearchScope::All);
+        app.search_state.cycle_scope(&["code".to_string()]);
+        assert_eq!(
+            app.search_state.scope,
+            SearchScope::Region("code".to_string())
+        );
+
+        // Patching with live search on drops the stale hit
+        app.search_dialog_open = false;
+        app.search_state.scope = SearchScope::All;
+        search(&mut app);
+        app.search_state.live = true;
+        app.patch_memory(0x2000, &[0xCC]).unwrap();
+        while app.search_state.is_searching() {
+            app.advance_search();
+        }
+        assert_eq!(app.search_state.results.len(), 1);
+        assert!(app.message.as_ref().unwrap().text.starts_with("Patched"));
+    }
+
+    #[test]
+    fn test_find_value() {
+        let mut app = app();
+        app.debugger
+            .as_mut()
+            .unwrap()
+            .memory
+            .write_u32(0x2000, 0x2010)
+            .unwrap();
+
+        app.process_command("find eax");
+        let finder = app.finder.as_ref().unwrap();
+        assert_eq!(finder.value, 0x2010);
+        let described: Vec<String> = finder.hits.iter().map(|hit| hit.describe()).collect();
+        assert_eq!(described, vec!["EAX".to_string(), "0x00002000".to_string()]);
+
+        app.finder.as_mut().unwrap().select_next();
+        app.goto_selected_hit();
+        assert!(app.finder.is_none());
+        assert_eq!(app.focused, FocusedPanel::Memory);
+        assert_eq!(app.memory_view_addr, 0x2000);
+
+        app.process_command("find");
+        assert!(app.finder.is_none());
+        assert!(app.message.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn test_follow_pointer() {
+        let mut app = app();
+        let dbg = app.debugger.as_mut().unwrap();
+        dbg.memory.write_u32(0x2010, 0x2020).unwrap();
+        dbg.memory.write_bytes(0x2020, b"SECRET\0").unwrap();
+
+        // EAX (0x2010) is selected in the Registers panel
+        app.focused = FocusedPanel::Registers;
+        app.follow_pointer();
+        let follow = app.follow.as_ref().unwrap();
+        assert_eq!(
+            follow.chain.breadcrumbs(&follow.origin).join(" → "),
+            "EAX → [0x00002010] → [0x00002020] → \"SECRET\""
+        );
+
+        app.goto_followed();
+        assert!(app.follow.is_none());
+        assert_eq!(app.memory_view_addr, 0x2020);
+
+        app.process_command("follow eax + 0x10");
+        assert_eq!(app.follow.take().unwrap().chain.addresses, vec![0x2020]);
+
+        app.process_command("follow 7");
+        assert!(app.follow.is_none());
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("isn't an address"));
+    }
+
+    #[test]
+    fn test_briefing_before_debugger() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(PUZZLE).unwrap();
+        assert_eq!(app.screen, Screen::Briefing);
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+        terminal
+            .draw(|frame| crate::screens::render_briefing(frame, &app))
+            .unwrap();
+        let text = crate::export::buffer_to_ansi(terminal.backend().buffer());
+        assert!(text.contains(&app.puzzle.as_ref().unwrap().metadata.title));
+        assert!(text.contains("Goal"));
+        assert!(text.contains("Begin"));
+
+        app.begin_puzzle();
+        assert_eq!(app.screen, Screen::Debugger);
+
+        // Re-opened from the objectives overlay
+        app.show_objectives();
+        app.show_briefing();
+        assert!(!app.objectives_open);
+        assert_eq!(app.screen, Screen::Briefing);
+    }
+
+    #[test]
+    fn test_command_aliases() {
+        let mut app = app();
+        app.disasm_selection = 1;
+        app.process_command("alias n = patch $sel 40; step");
+        assert!(!app.message.as_ref().unwrap().is_error);
+
+        app.process_command("n");
+        let debugger = app.debugger.as_ref().unwrap();
+        assert_eq!(debugger.memory.read_u8(0x1001).unwrap(), 0x40);
+        assert_eq!(debugger.cpu.eip, 0x1001);
+
+        // Extra words are appended, and $last is the last patch
+        app.process_command("alias again patch $last");
+        app.process_command("again 48");
+        assert_eq!(
+            app.debugger
+                .as_ref()
+                .unwrap()
+                .memory
+                .read_u8(0x1001)
+                .unwrap(),
+            0x48
+        );
+
+        app.process_command("alias");
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .contains("n = patch $sel 40; step"));
+        app.process_command("unalias n");
+        app.process_command("n");
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("Unknown command"));
+        app.process_command("alias alias = run");
+        assert!(app.message.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn test_ascii_table() {
+        let mut app = app();
+        app.process_command("ascii 0x61");
+        let table = app.ascii_table.as_mut().unwrap();
+        assert_eq!(table.selected, b'a');
+
+        // A lone digit matches both BEL and '7'
+        table.query.clear();
+        table.type_query(Some('7'));
+        assert_eq!(table.selected, 0x07);
+        table.next_match();
+        assert_eq!(table.selected, b'7');
+        table.next_match();
+        assert_eq!(table.selected, 0x07);
+        table.move_selection(-16);
+        assert_eq!(table.selected, 0x77);
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 30)).unwrap();
+        terminal
+            .draw(|frame| {
+                crate::screens::render_ascii_overlay(
+                    frame,
+                    app.ascii_table.as_ref().unwrap(),
+                    &app.theme,
+                )
+            })
+            .unwrap();
+        let text = crate::export::buffer_to_ansi(terminal.backend().buffer());
+        assert!(text.contains("ASCII Table"));
+        assert!(text.contains("Lowercase letter; uppercase 'W' is 0x57"));
+    }
+
+    #[test]
+    fn test_try_reference_example_opens_sandbox_tab() {
+        use crate::screens::ReferenceViewMode;
+        use revgame_core::reference::InstructionCategory;
+
+        let mut app = app();
+        let state = &mut app.reference_state;
+        state.selected_category = InstructionCategory::all()
+            .iter()
+            .position(|c| *c == InstructionCategory::ControlFlow)
+            .unwrap();
+        let jumps = state
+            .reference
+            .by_category(InstructionCategory::ControlFlow);
+        state.selected_instruction = jumps.iter().position(|info| info.mnemonic == "JG").unwrap();
+        state.view_mode = ReferenceViewMode::InstructionDetail;
+        state.navigate_down();
+        state.navigate_down();
+        assert_eq!(state.selected_example, 1);
+        app.open_reference();
+
+        app.try_reference_example();
+        assert!(app.in_sandbox());
+        assert_eq!(app.screen, Screen::Debugger);
+        assert_eq!(app.tab_titles().0, ["Goto", "Try JG"]);
+        let regs = &app.debugger.as_ref().unwrap().cpu.regs;
+        assert_eq!((regs.eax, regs.ebx), (7, 3));
+        assert!(app.message.as_ref().unwrap().text.contains("EAX=7, EBX=3"));
+
+        // Halting checks no goal, and the sandbox leaves no marks behind
+        app.run();
+        assert!(app.debugger.as_ref().unwrap().cpu.halted);
+        assert_eq!(app.screen, Screen::Debugger);
+        assert!(app.last_validation.is_none());
+        app.check_goal();
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "A sandbox has no goal to check"
+        );
+        app.remember_marks();
+        assert!(app
+            .game_state
+            .marks
+            .keys()
+            .all(|id| !id.starts_with(SANDBOX_PREFIX)));
+
+        // Another example reuses the sandbox's tab
+        app.try_reference_example();
+        assert_eq!(app.tab_titles().0.len(), 2);
+    }
+
+    #[test]
+    fn test_tabs_keep_separate_state() {
+        let mut app = app();
+        app.step();
+        app.memory_view_addr = 0x2040;
+        assert!(app.tab_titles().0.is_empty());
+
+        app.process_command("tab new");
+        assert_eq!(app.screen, Screen::PuzzleSelect);
+        app.load_puzzle(PUZZLE).unwrap();
+        assert_eq!(
+            app.tab_titles(),
+            (vec!["Goto".to_string(), "Goto".to_string()], 1)
+        );
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1000);
+        assert_eq!(app.memory_view_addr, 0x2000);
+
+        app.cycle_tab(true);
+        assert_eq!(app.tab_titles().1, 0);
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1001);
+        assert_eq!(app.memory_view_addr, 0x2040);
+
+        app.process_command("tab close");
+        assert!(app.tab_titles().0.is_empty());
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1000);
+    }
+
+    #[test]
+    fn test_compare_with_synced_stepping() {
+        let mut app = app();
+        app.process_command("compare");
+        assert!(app.message.as_ref().unwrap().is_error);
+
+        app.new_tab();
+        app.load_puzzle(PUZZLE).unwrap();
+        app.process_command("compare sync on");
+        app.process_command("compare 1");
+        let (left, right) = app.compare_sides().unwrap();
+        assert_eq!(left.title, " 2: Goto ");
+        assert_eq!(right.title, " 1: Goto ");
+
+        app.step();
+        let eip = |app: &App| {
+            app.compared_session()
+                .and_then(|s| s.debugger.as_ref())
+                .map(|d| d.cpu.eip)
+        };
+        assert_eq!(eip(&app), Some(0x1001));
+        app.compare_sync = false;
+        app.step();
+        assert_eq!(eip(&app), Some(0x1001));
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1002);
+
+        // Switching to the compared tab keeps the pair, with the sides swapped
+        app.cycle_tab(true);
+        assert_eq!(app.compare_sides().unwrap().1.title, " 2: Goto ");
+
+        app.process_command("compare off");
+        assert!(app.compare_sides().is_none());
+    }
+
+    #[test]
+    fn test_copy_and_paste() {
+        let mut app = app();
+        app.process_command("copy disasm 2");
+        assert_eq!(
+            app.clipboard.take().unwrap(),
+            "00001000  90                    nop\n00001001  90                    nop"
+        );
+
+        app.patch_memory(0x2000, b"Hi").unwrap();
+        app.focused = FocusedPanel::Memory;
+        app.copy_focused();
+        let dump = app.clipboard.take().unwrap();
+        assert_eq!(dump.lines().count(), 4);
+        assert!(dump.starts_with("00002000  48 69 00"));
+        assert!(dump.lines().next().unwrap().ends_with("Hi.............."));
+
+        app.paste("bp 1001\n");
+        assert_eq!(app.focused, FocusedPanel::Command);
+        assert_eq!(app.command_input, "bp 1001");
+
+        app.goto_dialog_open = true;
+        app.paste("0x2000");
+        assert_eq!(app.goto_state.input, "0x2000");
+    }
+
+    #[test]
+    fn test_export_view_renders_debugger() {
+        let app = app();
+        let buffer = crate::export::render_view(&app, 100, 30).unwrap();
+        let text = crate::export::buffer_to_ansi(&buffer);
+        assert!(text.contains("Disassembly"));
+        assert_eq!(text.lines().count(), 30);
+
+        assert_eq!(parse_export_size(None), Ok((120, 40)));
+        assert_eq!(parse_export_size(Some("160x50")), Ok((160, 50)));
+        assert!(parse_export_size(Some("5x5")).is_err());
+
+        let mut empty = App::with_save_manager(SaveManager::in_memory());
+        empty.process_command("export view out.html");
+        assert!(empty.message.unwrap().is_error);
+    }
+
+    #[test]
+    fn test_learning_log_is_opt_in() {
+        let mut app = app();
+        app.step();
+        assert!(app.game_state.learning.events().is_empty());
+
+        app.settings.learning_log = true;
+        app.load_puzzle(PUZZLE).unwrap();
+        app.step();
+        app.patch_memory(0x1001, &[0x90]).unwrap();
+        let events: Vec<LearningEvent> = app
+            .game_state
+            .learning
+            .events()
+            .iter()
+            .map(|e| e.event)
+            .collect();
+        assert_eq!(
+            events,
+            [
+                LearningEvent::PuzzleStarted,
+                LearningEvent::Step,
+                LearningEvent::Patch
+            ]
+        );
+        assert_eq!(app.game_state.learning.attempts()[0].puzzle, "test-goto");
+
+        app.process_command("learning clear");
+        assert!(app.game_state.learning.events().is_empty());
+    }
+
+    #[test]
+    fn test_entropy_regions() {
+        let mut app = app();
+        app.process_command("entropy");
+        assert!(app.entropy.is_some());
+
+        let regions = app.entropy_regions();
+        assert!(!regions.is_empty());
+        assert!(regions.iter().all(|region| region.bytes.len() <= 0x10000));
+    }
+
+    #[test]
+    fn test_unsupported_instruction_stops_run() {
+        let mut app = app();
+        // DIV ECX isn't implemented
+        app.patch_memory(0x1001, &[0xF7, 0xF1]).unwrap();
+
+        app.run();
+        let message = app.message.as_ref().unwrap();
+        assert!(message.is_error);
+        assert!(message
+            .text
+            .starts_with("Stopped: Unsupported instruction: Div at 0x00001001"));
+        assert!(message.text.contains("reference"));
+
+        let debugger = app.debugger.as_ref().unwrap();
+        assert_eq!(debugger.cpu.eip, 0x1001);
+        assert!(matches!(debugger.state, DebuggerState::Error(_)));
+
+        app.step();
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("Stopped: Unsupported instruction"));
+    }
+
+    #[test]
+    fn test_timeline_scrubbing() {
+        let mut app = app();
+        app.run();
+        assert_eq!(app.debugger.as_ref().unwrap().timeline().length(), 3);
+
+        app.scrub_timeline(-1);
+        let debugger = app.debugger.as_ref().unwrap();
+        assert_eq!(debugger.position(), 2);
+        assert_eq!(debugger.cpu.eip, 0x1002);
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "Instruction 2 of 3 at 0x00001002"
+        );
+
+        app.process_command("snapshot");
+        app.process_command("seek 0");
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1000);
+        app.jump_timeline_marker(true);
+        assert_eq!(app.debugger.as_ref().unwrap().position(), 2);
+
+        // Clicking the far end of the track goes to the last instruction
+        app.scrub_timeline_to(39, 40);
+        assert_eq!(app.debugger.as_ref().unwrap().position(), 3);
+
+        let text =
+            crate::export::buffer_to_ansi(&crate::export::render_view(&app, 120, 40).unwrap());
+        assert!(text.contains("instr 3 / 3"));
+    }
+
+    #[test]
+    fn test_goal_progress_updates_live() {
+        let source = PUZZLE.replace(
+            "type = \"normal_halt\"",
+            "type = \"all\"\n\n[[validation.conditions]]\ntype = \"register_value\"\nregister = \"eax\"\nexpected = 0x2010\n\n[[validation.conditions]]\ntype = \"normal_halt\"",
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        app.begin_puzzle();
+        assert_eq!(app.goal_progress(), Some((1, 2)));
+
+        let text =
+            crate::export::buffer_to_ansi(&crate::export::render_view(&app, 120, 40).unwrap());
+        assert!(text.contains("goal 1/2"));
+
+        app.step();
+        app.step();
+        assert_eq!(app.goal_progress(), Some((1, 2)));
+        app.step();
+        assert_eq!(app.goal_progress(), Some((2, 2)));
+    }
+
+    #[test]
+    fn test_validation_triggers() {
+        let goal = "type = \"register_value\"\nregister = \"eax\"\nexpected = 0x2010";
+
+        // Reaching the address stops the run and checks the goal
+        let source = PUZZLE.replace(
+            "type = \"normal_halt\"",
+            &format!("{}\ntrigger = \"address\"\ntrigger_address = 0x1001", goal),
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        app.begin_puzzle();
+        app.run();
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1001);
+        assert!(matches!(app.screen, Screen::PuzzleComplete { .. }));
+
+        // On demand: halting alone doesn't check
+        let source = PUZZLE.replace(
+            "type = \"normal_halt\"",
+            &format!("{}\ntrigger = \"command\"", goal),
+        );
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        app.begin_puzzle();
+        app.run();
+        assert!(app.last_validation.is_none());
+        app.process_command("check");
+        assert!(matches!(app.screen, Screen::PuzzleComplete { .. }));
+
+        let mut halting = super::tests::app();
+        halting.process_command("check");
+        assert_eq!(
+            halting.message.as_ref().unwrap().text,
+            "Checked when the program halts, not with `check`"
+        );
+    }
+
+    #[test]
+    fn test_annotations_shown_in_disassembly() {
+        let source = format!("{}\n[annotations]\n\"0x1002\" = \"success path\"\n", PUZZLE);
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.load_puzzle(&source).unwrap();
+        app.begin_puzzle();
+        assert_eq!(
+            app.annotations().get(&0x1002).map(String::as_str),
+            Some("success path")
+        );
+
+        let text =
+            crate::export::buffer_to_ansi(&crate::export::render_view(&app, 120, 40).unwrap());
+        assert!(text.contains("; success path"));
+    }
+
+    #[test]
+    fn test_patched_instructions_marked_in_disassembly() {
+        let mut app = app();
+        let text =
+            crate::export::buffer_to_ansi(&crate::export::render_view(&app, 120, 40).unwrap());
+        assert!(!text.contains("; was"));
+
+        app.process_command("patch 0x1001 F4");
+        let text =
+            crate::export::buffer_to_ansi(&crate::export::render_view(&app, 120, 40).unwrap());
+        assert!(text.contains(app.theme.patched_marker()));
+        assert!(text.contains("; was nop"));
+    }
+
+    #[test]
+    fn test_touch_gestures() {
+        use crate::touch::{handle_gesture, Gesture, SwipeDirection, TouchButton, TouchTarget};
+
+        let mut app = app();
+        let area = ratatui::layout::Rect::new(0, 0, 120, 40);
+
+        // Long press on the second disassembly line sets a breakpoint there
+        handle_gesture(&mut app, Gesture::LongPress { column: 5, row: 2 }, area);
+        assert_eq!(app.disasm_selection, 1);
+        assert!(app.debugger.as_ref().unwrap().breakpoints.contains(&0x1001));
+
+        handle_gesture(&mut app, Gesture::Swipe(SwipeDirection::Left), area);
+        assert_eq!(app.focused, FocusedPanel::Registers);
+
+        // The Step button only shows with touch controls on
+        app.touch_controls = true;
+        let step = (0..40)
+            .flat_map(|row| (0..120).map(move |column| (column, row)))
+            .find(|&(column, row)| {
+                crate::screens::touch_target(&app, area, column, row)
+                    == Some(TouchTarget::Button(TouchButton::Step))
+            })
+            .unwrap();
+        handle_gesture(
+            &mut app,
+            Gesture::Tap {
+                column: step.0,
+                row: step.1,
+            },
+            area,
+        );
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1001);
+
+        let text =
+            crate::export::buffer_to_ansi(&crate::export::render_view(&app, 120, 40).unwrap());
+        assert!(text.contains("Step F10"));
+    }
+
+    #[test]
+    fn test_share_and_load_puzzle_link() {
+        let mut app = app();
+        app.run_command("share");
+        let link = app.clipboard.clone().unwrap();
+        assert!(link.starts_with(revgame_core::puzzle::SHARE_BASE_URL));
+        assert!(!app.message.as_ref().unwrap().is_error);
+
+        let mut other = App::with_save_manager(SaveManager::in_memory());
+        other.load_shared_puzzle(&link).unwrap();
+        assert_eq!(other.puzzle.as_ref().unwrap().metadata.id, "test-goto");
+        assert!(other.load_shared_puzzle("#puzzle=%%").is_err());
+    }
+
+    #[test]
+    fn test_demo_mode() {
+        let replay = Replay::parse(
+            "[[event]]\nat_ms = 0\ncommand = \"step\"\n[[event]]\nat_ms = 1000\ncommand = \"run\"\nnote = \"Off it goes\"\n",
+        )
+        .unwrap();
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.start_demo(PUZZLE, Some(replay)).unwrap();
+        assert_eq!(app.screen, Screen::Debugger);
+
+        app.advance_demo(10);
+        assert_eq!(app.debugger.as_ref().unwrap().total_instructions, 1);
+        app.advance_demo(1000);
+        assert_eq!(app.message.as_ref().unwrap().text, "Off it goes");
+        assert!(
+            matches!(app.screen, Screen::PuzzleComplete { ref message } if message == "Solved 'Goto'!")
+        );
+        assert!(!app.game_state.is_completed("test-goto"));
+        assert!(!app.has_unsaved_progress());
+        assert_eq!(
+            app.save_game("demo").unwrap_err(),
+            "Saving is off in demo mode"
+        );
+
+        app.restart_demo();
+        assert_eq!(app.screen, Screen::Debugger);
+        assert_eq!(app.debugger.as_ref().unwrap().total_instructions, 0);
+        app.take_over_demo();
+        app.advance_demo(5000);
+        assert_eq!(app.debugger.as_ref().unwrap().total_instructions, 0);
+    }
+
+    #[test]
+    fn test_time_attack_runs_through_the_course() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        let puzzles = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../puzzles");
+        app.start_time_attack(&puzzles).unwrap();
+        assert_eq!(app.screen, Screen::Debugger);
+        assert_eq!(app.puzzle.as_ref().unwrap().metadata.id, "basic-001");
+        assert!(app
+            .time_attack_status()
+            .unwrap()
+            .starts_with("time attack 1/6 0:00"));
+
+        app.apply_validation(ValidationResult::Success);
+        assert_eq!(app.screen, Screen::Debugger);
+        assert_eq!(app.puzzle.as_ref().unwrap().metadata.id, "basic-002");
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("Split 1/6: 0:00"));
+
+        // Skip to the last puzzle
+        app.time_attack = Some(TimeAttack::new(&["basic-002"], now_ms()));
+        app.apply_validation(ValidationResult::Success);
+        assert!(app.time_attack.is_none());
+        assert!(
+            matches!(app.screen, Screen::PuzzleComplete { ref message } if message.contains("Time attack finished in 0:00"))
+        );
+        assert!(!app.leaderboard.for_profile("Player").is_empty());
+        assert!(
+            matches!(app.screen, Screen::PuzzleComplete { ref message } if !message.contains("not saved"))
+        );
+        let saved = app.saves().unwrap().load_leaderboard().unwrap();
+        assert_eq!(saved.for_profile("Player").len(), 1);
+    }
+
+    #[test]
+    fn test_best_solve_becomes_the_ghost() {
+        let mut app = app();
+        app.settings.ghost = true;
+        app.ghost = None;
+        app.patch_memory(0x1000, &[0x90]).unwrap();
+        app.step();
+        assert_eq!(app.recording.events.len(), 2);
+        assert_eq!(app.recording.events[0].command, "patch! 0x1000 90");
+        assert_eq!(app.recording.events[1].instructions, Some(1));
+
+        app.run();
+        assert!(matches!(app.screen, Screen::PuzzleComplete { .. }));
+        let ghost = app.ghost.clone().unwrap();
+        assert_eq!(ghost.events.last().unwrap().command, "check");
+        assert_eq!(ghost.progress_at(ghost.duration_ms()), (3, 1));
+
+        // A fresh attempt races the saved solve
+        app.load_puzzle(PUZZLE).unwrap();
+        app.begin_puzzle();
+        assert_eq!(app.ghost, Some(ghost));
+        assert!(app.ghost_status().unwrap().starts_with("ghost"));
+        app.settings.ghost = false;
+        assert_eq!(app.ghost_status(), None);
+    }
+
+    #[test]
+    fn test_hotseat_duel() {
+        let mut app = app();
+        app.process_command("duel Ada Bob");
+        let seed = app.duel.as_ref().unwrap().seed;
+        assert!(app.duel_status().unwrap().starts_with("duel: Ada's turn"));
+
+        // Ada patches in her own session and passes
+        app.patch_memory(0x1000, &[0xF4]).unwrap();
+        app.process_command("duel pass");
+        assert_eq!(app.message.as_ref().unwrap().text, "Bob's turn (turn 1)");
+        assert!(!app.debugger.as_ref().unwrap().can_undo());
+        assert_eq!(app.debugger.as_ref().unwrap().rng_seed(), seed);
+
+        // Bob solves it, then Ada gets her session back and gives up
+        app.run();
+        assert_eq!(app.message.as_ref().unwrap().text, "Ada's turn (turn 2)");
+        assert!(app.debugger.as_ref().unwrap().can_undo());
+        app.process_command("duel forfeit");
+        let Screen::PuzzleComplete { ref message } = app.screen else {
+            panic!("no scoreboard");
+        };
+        assert!(message.starts_with("Bob wins the duel!"));
+        assert!(message.contains("Ada") && message.contains("gave up"));
+        assert!(app.duel.is_none());
+    }
+
+    #[test]
+    fn test_tool_script_commands() {
+        let path = std::env::temp_dir().join(format!("revgame-tools-{}.rhai", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"
+/// Step to an address, at most 10 steps
+fn stepto(addr) {
+    for i in 0..10 {
+        if reg("eip") == expr(addr) { return; }
+        run("step");
+    }
+}
+
+fn haltnop() {
+    if byte(reg("eip")) == 0x90 { run("patch! " + variable("eip") + " F4"); }
+}
+
+fn where() { `EIP is ${variable("eip")}` }
+
+fn broken() { run("patch! nowhere"); }
+
+fn again() { run("again"); }
+"#,
+        )
+        .unwrap();
+        let mut app = app();
+        app.process_command(&format!("script run {}", path.display()));
+        std::fs::remove_file(&path).ok();
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .ends_with("again, broken, haltnop, stepto, where"));
+
+        app.process_command("stepto 0x1001");
+        assert_eq!(app.debugger.as_ref().unwrap().cpu.eip, 0x1001);
+        app.process_command("haltnop");
+        assert_eq!(
+            app.debugger
+                .as_ref()
+                .unwrap()
+                .memory
+                .read_u8(0x1001)
+                .unwrap(),
+            0xF4
+        );
+        app.process_command("where");
+        assert_eq!(app.message.as_ref().unwrap().text, "EIP is 0x1001");
+
+        app.process_command("broken");
+        let message = app.message.as_ref().unwrap();
+        assert!(message.is_error);
+        assert!(message.text.starts_with("broken: "));
+        app.process_command("again");
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .ends_with("Tool commands call each other too deeply"));
+
+        app.process_command("stepto");
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "stepto: Usage: stepto <addr>"
+        );
+        app.process_command("script");
+        assert_eq!(
+            app.message.as_ref().unwrap().text,
+            "Tool commands: again, broken, haltnop, stepto <addr>, where"
+        );
+        app.process_command("script run /nonexistent/tools.rhai");
+        assert!(app.message.as_ref().unwrap().is_error);
+    }
+
+    #[test]
+    fn test_pin_seed_and_clock() {
+        let mut app = App::with_save_manager(SaveManager::in_memory());
+        app.pin(42, 5_000);
+        app.load_puzzle(PUZZLE).unwrap();
+        assert_eq!(app.debugger.as_ref().unwrap().rng_seed(), 42);
+        assert_eq!(app.recording_started_ms, 5_000);
     }
 }
@@ -0,0 +1,197 @@
+//! In-app log: `log::` records go into a ring buffer the debug console
+//! overlay (F12) shows, and optionally into a session log file, instead of
+//! vanishing behind the TUI.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Records kept for the console
+pub const LOG_CAPACITY: usize = 500;
+
+/// The logger `init` installs
+pub static CONSOLE: LogConsole = LogConsole::new();
+
+/// A logged record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    pub level: Level,
+    /// Module the record came from
+    pub target: String,
+    pub message: String,
+}
+
+/// Ring buffer of the latest records, plus the session log file if open
+pub struct LogConsole {
+    lines: Mutex<VecDeque<LogLine>>,
+    file: Mutex<Option<File>>,
+}
+
+impl Default for LogConsole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogConsole {
+    pub const fn new() -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::new()),
+            file: Mutex::new(None),
+        }
+    }
+
+    /// Also write every record to `path`, replacing what's there
+    pub fn open_file(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        *self.file.lock().unwrap_or_else(|e| e.into_inner()) = Some(file);
+        Ok(())
+    }
+
+    pub fn close_file(&self) {
+        self.file.lock().unwrap_or_else(|e| e.into_inner()).take();
+    }
+
+    /// Records at `min` or more severe, oldest first
+    pub fn lines(&self, min: Level) -> Vec<LogLine> {
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        lines
+            .iter()
+            .filter(|line| line.level <= min)
+            .cloned()
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.lines.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+impl Log for LogConsole {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let line = LogLine {
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        if let Some(ref mut file) = *self.file.lock().unwrap_or_else(|e| e.into_inner()) {
+            // A full disk shouldn't take the game down with it
+            writeln!(file, "{:<5} {}: {}", line.level, line.target, line.message).ok();
+        }
+        let mut lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.len() >= LOG_CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    fn flush(&self) {
+        if let Some(ref mut file) = *self.file.lock().unwrap_or_else(|e| e.into_inner()) {
+            file.flush().ok();
+        }
+    }
+}
+
+/// Send `log::` output to `CONSOLE`, keeping records up to `level`
+pub fn init(level: LevelFilter) -> Result<(), String> {
+    log::set_logger(&CONSOLE).map_err(|e| e.to_string())?;
+    log::set_max_level(level);
+    Ok(())
+}
+
+/// Debug console overlay: scroll position and the least severe level shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsoleState {
+    pub scroll: usize,
+    pub level: Level,
+}
+
+impl Default for ConsoleState {
+    fn default() -> Self {
+        Self {
+            scroll: 0,
+            level: Level::Debug,
+        }
+    }
+}
+
+impl ConsoleState {
+    /// Show one level more (towards trace) or less (towards error)
+    pub fn cycle_level(&mut self, more: bool) {
+        let levels = [
+            Level::Error,
+            Level::Warn,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ];
+        let i = levels
+            .iter()
+            .position(|&level| level == self.level)
+            .unwrap_or(3);
+        self.level = if more {
+            levels[(i + 1).min(levels.len() - 1)]
+        } else {
+            levels[i.saturating_sub(1)]
+        };
+        self.scroll = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(console: &LogConsole, level: Level, message: &str) {
+        console.log(
+            &Record::builder()
+                .level(level)
+                .target("test")
+                .args(format_args!("{}", message))
+                .build(),
+        );
+    }
+
+    #[test]
+    fn test_console_keeps_latest_records() {
+        let console = LogConsole::new();
+        log(&console, Level::Warn, "first");
+        log(&console, Level::Debug, "noise");
+        assert_eq!(console.lines(Level::Info).len(), 1);
+        assert_eq!(console.lines(Level::Trace)[1].message, "noise");
+
+        for i in 0..LOG_CAPACITY {
+            log(&console, Level::Info, &i.to_string());
+        }
+        let lines = console.lines(Level::Trace);
+        assert_eq!(lines.len(), LOG_CAPACITY);
+        assert_eq!(lines[0].message, "0");
+
+        let path = std::env::temp_dir().join(format!("revgame-log-{}.log", std::process::id()));
+        console.open_file(&path).unwrap();
+        log(&console, Level::Error, "to disk");
+        console.close_file();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "ERROR test: to disk\n"
+        );
+        std::fs::remove_file(&path).ok();
+
+        let mut state = ConsoleState::default();
+        state.cycle_level(false);
+        assert_eq!(state.level, Level::Info);
+    }
+}
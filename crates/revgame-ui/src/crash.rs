@@ -0,0 +1,126 @@
+//! Crash reports: what the game was doing when it panicked, written next to
+//! the saves so a bug report has more to go on than a wrecked terminal.
+
+use log::Level;
+
+use crate::app::App;
+use crate::console::CONSOLE;
+
+/// Executed instructions included in a crash report
+pub const CRASH_TRACE_LEN: usize = 50;
+
+/// Log records included in a crash report
+const CRASH_LOG_LEN: usize = 20;
+
+/// Crash report text for a panic in `app`
+pub fn crash_report(app: &App, panic: &str, backtrace: &str) -> String {
+    let mut report = format!(
+        "RevGame {} crash report\n\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        panic
+    );
+    report.push_str(&format!("\nScreen: {:?}\n", app.screen));
+    match app.puzzle {
+        Some(ref puzzle) => report.push_str(&format!(
+            "Puzzle: {} ({})\n",
+            puzzle.metadata.id, puzzle.metadata.title
+        )),
+        None => report.push_str("Puzzle: none\n"),
+    }
+
+    if let Some(ref debugger) = app.debugger {
+        report.push_str(&format!(
+            "EIP: 0x{:08X}, {} instructions run, {} patches\n",
+            debugger.cpu.eip,
+            debugger.total_instructions,
+            debugger.undo_count()
+        ));
+        let history = debugger.history();
+        report.push_str(&format!(
+            "\nLast {} instructions:\n",
+            CRASH_TRACE_LEN.min(history.len())
+        ));
+        for entry in history
+            .iter()
+            .skip(history.len().saturating_sub(CRASH_TRACE_LEN))
+        {
+            report.push_str(&format!(
+                "  0x{:08X}  {}\n",
+                entry.eip, entry.instruction_text
+            ));
+        }
+    }
+
+    let log = CONSOLE.lines(Level::Debug);
+    if !log.is_empty() {
+        report.push_str("\nRecent log:\n");
+        for line in log.iter().skip(log.len().saturating_sub(CRASH_LOG_LEN)) {
+            report.push_str(&format!(
+                "  {:<5} {}: {}\n",
+                line.level, line.target, line.message
+            ));
+        }
+    }
+
+    report.push_str(&format!("\nBacktrace:\n{}\n", backtrace));
+    report
+}
+
+/// Write a crash report to `dir`, the save directory, giving its path
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_crash_report(
+    dir: &std::path::Path,
+    report: &str,
+) -> Result<std::path::PathBuf, String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let path = dir.join(format!("crash-{}.txt", secs));
+    std::fs::write(&path, report)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crash_report_has_context() {
+        let mut app = App::with_save_manager(revgame_core::game::SaveManager::in_memory());
+        app.load_puzzle(
+            r#"
+[metadata]
+id = "crash-test"
+title = "Crash"
+difficulty = 1
+category = "patching"
+
+[description]
+brief = "test"
+detailed = "test"
+
+[setup]
+code_start = 0x1000
+data_start = 0x2000
+stack_start = 0x3000
+
+[setup.code]
+bytes = "90 90 F4"
+
+[validation]
+type = "normal_halt"
+"#,
+        )
+        .unwrap();
+        app.step();
+
+        let report = crash_report(&app, "panicked at 'oops'", "<backtrace>");
+        assert!(report.contains("panicked at 'oops'"));
+        assert!(report.contains("Puzzle: crash-test (Crash)"));
+        assert!(report.contains("Last 1 instructions:\n  0x00001000  nop\n"));
+        assert!(report.ends_with("Backtrace:\n<backtrace>\n"));
+    }
+}
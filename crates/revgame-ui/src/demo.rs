@@ -0,0 +1,81 @@
+use revgame_core::game::{Replay, ReplayEvent};
+
+/// A read-only demo for embedding in slides and course pages: one puzzle,
+/// nothing saved, no achievements, no menus, and optionally a replay that
+/// plays itself until someone takes over
+#[derive(Debug, Clone)]
+pub struct Demo {
+    /// Puzzle TOML, kept for starting over
+    pub(crate) puzzle: String,
+    replay: Option<Replay>,
+    /// Time since the replay started
+    elapsed_ms: u64,
+    /// Events already played
+    played: usize,
+}
+
+impl Demo {
+    pub fn new(puzzle: &str, replay: Option<Replay>) -> Self {
+        Self {
+            puzzle: puzzle.to_string(),
+            replay,
+            elapsed_ms: 0,
+            played: 0,
+        }
+    }
+
+    /// Whether the replay still has events to play
+    pub fn is_playing(&self) -> bool {
+        self.replay
+            .as_ref()
+            .is_some_and(|replay| self.played < replay.events.len())
+    }
+
+    /// Move the replay on by `elapsed_ms`, giving the events that came due
+    pub fn advance(&mut self, elapsed_ms: u64) -> Vec<ReplayEvent> {
+        let Some(ref replay) = self.replay else {
+            return Vec::new();
+        };
+        self.elapsed_ms += elapsed_ms;
+        let due = replay.due(self.elapsed_ms);
+        let events = replay.events[self.played.min(due)..due].to_vec();
+        self.played = self.played.max(due);
+        events
+    }
+
+    /// Stop playing the rest of the replay
+    pub fn stop(&mut self) {
+        if let Some(ref replay) = self.replay {
+            self.played = replay.events.len();
+        }
+    }
+
+    /// Play the replay again from the start
+    pub fn rewind(&mut self) {
+        self.elapsed_ms = 0;
+        self.played = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo_plays_events_as_they_come_due() {
+        let replay = Replay::parse(
+            "[[event]]\nat_ms = 0\ncommand = \"step\"\n[[event]]\nat_ms = 1000\ncommand = \"run\"\n",
+        )
+        .unwrap();
+        let mut demo = Demo::new("", Some(replay));
+        assert_eq!(demo.advance(10).len(), 1);
+        assert!(demo.advance(500).is_empty());
+        assert_eq!(demo.advance(500)[0].command, "run");
+        assert!(!demo.is_playing());
+
+        demo.rewind();
+        assert!(demo.is_playing());
+        demo.stop();
+        assert!(demo.advance(5000).is_empty());
+    }
+}
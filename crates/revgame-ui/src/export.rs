@@ -0,0 +1,288 @@
+use std::path::Path;
+
+use ratatui::{
+    backend::TestBackend,
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+    Terminal,
+};
+
+use crate::{app::App, screens::render_debugger};
+
+/// Size of an exported view when none is given
+pub const DEFAULT_EXPORT_SIZE: (u16, u16) = (120, 40);
+
+/// Render the debugger screen off-screen at the given size
+pub fn render_view(app: &App, width: u16, height: u16) -> Result<Buffer, String> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).map_err(|e| e.to_string())?;
+    terminal
+        .draw(|frame| render_debugger(frame, app))
+        .map_err(|e| e.to_string())?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// Write the debugger screen to `path`: standalone HTML for .html/.htm,
+/// otherwise text with ANSI colors (view it with `cat` or `less -R`)
+pub fn export_view(app: &App, path: &Path, width: u16, height: u16) -> Result<(), String> {
+    if app.debugger.is_none() {
+        return Err("No puzzle loaded to export".to_string());
+    }
+    let buffer = render_view(app, width, height)?;
+    let html = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+    let content = if html {
+        let title = app
+            .puzzle
+            .as_ref()
+            .map(|p| p.metadata.title.as_str())
+            .unwrap_or("RevGame");
+        buffer_to_html(&buffer, title)
+    } else {
+        buffer_to_ansi(&buffer)
+    };
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Buffer contents with SGR escape codes wherever the style changes
+pub fn buffer_to_ansi(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buffer) {
+        let mut style = None;
+        for cell in row {
+            let key = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(key) {
+                out.push_str(&sgr(cell));
+                style = Some(key);
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Buffer contents as plain text, one line per row with trailing blanks
+/// trimmed, for snapshot tests and bug reports
+pub fn buffer_to_text(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buffer) {
+        let line: String = row.iter().map(|cell| cell.symbol()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// A standalone HTML page showing the buffer in a <pre> block
+pub fn buffer_to_html(buffer: &Buffer, title: &str) -> String {
+    let mut body = String::new();
+    for row in rows(buffer) {
+        let mut start = 0;
+        while start < row.len() {
+            let cell = &row[start];
+            let end = row[start..]
+                .iter()
+                .position(|other| {
+                    (other.fg, other.bg, other.modifier) != (cell.fg, cell.bg, cell.modifier)
+                })
+                .map_or(row.len(), |len| start + len);
+            let text: String = row[start..end]
+                .iter()
+                .map(|cell| escape_html(cell.symbol()))
+                .collect();
+            let css = css(cell);
+            if css.is_empty() {
+                body.push_str(&text);
+            } else {
+                body.push_str(&format!("<span style=\"{}\">{}</span>", css, text));
+            }
+            start = end;
+        }
+        body.push('\n');
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+         body {{ background: #101010; color: #d0d0d0; }}\n\
+         pre {{ font-family: \"DejaVu Sans Mono\", Menlo, Consolas, monospace; line-height: 1.2; }}\n\
+         </style>\n</head>\n<body>\n<pre>\n{}</pre>\n</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+fn rows(buffer: &Buffer) -> impl Iterator<Item = &[Cell]> {
+    buffer.content().chunks(buffer.area.width.max(1) as usize)
+}
+
+fn sgr(cell: &Cell) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (modifier, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if cell.modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.extend(ansi_color(cell.fg, false));
+    codes.extend(ansi_color(cell.bg, true));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn ansi_color(color: Color, background: bool) -> Option<String> {
+    let offset = if background { 10 } else { 0 };
+    let base = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some(format!("{};2;{};{};{}", 38 + offset, r, g, b)),
+        Color::Indexed(i) => return Some(format!("{};5;{}", 38 + offset, i)),
+        Color::Black => 30,
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Blue => 34,
+        Color::Magenta => 35,
+        Color::Cyan => 36,
+        Color::Gray => 37,
+        Color::DarkGray => 90,
+        Color::LightRed => 91,
+        Color::LightGreen => 92,
+        Color::LightYellow => 93,
+        Color::LightBlue => 94,
+        Color::LightMagenta => 95,
+        Color::LightCyan => 96,
+        Color::White => 97,
+    };
+    Some((base + offset).to_string())
+}
+
+fn css(cell: &Cell) -> String {
+    let (mut fg, mut bg) = (rgb(cell.fg), rgb(cell.bg));
+    if cell.modifier.contains(Modifier::REVERSED) {
+        std::mem::swap(&mut fg, &mut bg);
+        fg = fg.or(Some((0x10, 0x10, 0x10)));
+        bg = bg.or(Some((0xd0, 0xd0, 0xd0)));
+    }
+
+    let mut css = String::new();
+    if let Some((r, g, b)) = fg {
+        css.push_str(&format!("color:#{:02x}{:02x}{:02x};", r, g, b));
+    }
+    if let Some((r, g, b)) = bg {
+        css.push_str(&format!("background:#{:02x}{:02x}{:02x};", r, g, b));
+    }
+    if cell.modifier.contains(Modifier::BOLD) {
+        css.push_str("font-weight:bold;");
+    }
+    if cell.modifier.contains(Modifier::DIM) {
+        css.push_str("opacity:0.6;");
+    }
+    if cell.modifier.contains(Modifier::ITALIC) {
+        css.push_str("font-style:italic;");
+    }
+    if cell.modifier.contains(Modifier::UNDERLINED) {
+        css.push_str("text-decoration:underline;");
+    }
+    css
+}
+
+/// A color as RGB, using the xterm palette for named and indexed colors
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    const PALETTE: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00),
+        (0xcd, 0x00, 0x00),
+        (0x00, 0xcd, 0x00),
+        (0xcd, 0xcd, 0x00),
+        (0x00, 0x00, 0xee),
+        (0xcd, 0x00, 0xcd),
+        (0x00, 0xcd, 0xcd),
+        (0xe5, 0xe5, 0xe5),
+        (0x7f, 0x7f, 0x7f),
+        (0xff, 0x00, 0x00),
+        (0x00, 0xff, 0x00),
+        (0xff, 0xff, 0x00),
+        (0x5c, 0x5c, 0xff),
+        (0xff, 0x00, 0xff),
+        (0x00, 0xff, 0xff),
+        (0xff, 0xff, 0xff),
+    ];
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(i) => i,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+    Some(match index {
+        0..=15 => PALETTE[index as usize],
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let n = index - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    })
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, style::Style};
+
+    #[test]
+    fn test_ansi_and_html() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 1));
+        buffer.set_string(
+            0,
+            0,
+            "a<b",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        );
+
+        let ansi = buffer_to_ansi(&buffer);
+        assert_eq!(ansi, "\x1b[0;1;31ma<b\x1b[0m   \x1b[0m\n");
+
+        let html = buffer_to_html(&buffer, "T&T");
+        assert!(html.contains("<title>T&amp;T</title>"));
+        assert!(html.contains("<span style=\"color:#cd0000;font-weight:bold;\">a&lt;b</span>   \n"));
+
+        assert_eq!(buffer_to_text(&buffer), "a<b\n");
+    }
+
+    #[test]
+    fn test_indexed_colors() {
+        assert_eq!(rgb(Color::Indexed(16)), Some((0, 0, 0)));
+        assert_eq!(rgb(Color::Indexed(231)), Some((255, 255, 255)));
+        assert_eq!(rgb(Color::Indexed(232)), Some((8, 8, 8)));
+        assert_eq!(rgb(Color::Reset), None);
+    }
+}
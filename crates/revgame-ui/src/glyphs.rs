@@ -0,0 +1,140 @@
+use ratatui::buffer::Buffer;
+
+/// ASCII stand-in for a symbol the terminal might not draw. Box drawing,
+/// block and arrow characters get a look-alike, pictographs a `*`, and
+/// anything else outside ASCII a `?`.
+pub fn ascii_glyph(symbol: &str) -> &str {
+    let Some(c) = symbol.chars().next() else {
+        return symbol;
+    };
+    if c.is_ascii() {
+        return symbol;
+    }
+    match c {
+        '─' | '━' | '═' | '┄' | '┈' | '▃' => "-",
+        '│' | '┃' | '║' | '┆' | '┊' | '▌' | '▐' | '▎' | '▏' => "|",
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╔' | '╗' | '╚' | '╝' | '╠' | '╣'
+        | '╦' | '╩' | '╬' | '╭' | '╮' | '╯' | '╰' => "+",
+        '█' | '▓' | '▆' | '▇' => "#",
+        '▄' | '▅' => "=",
+        '▀' => "\"",
+        '▁' | '▂' => "_",
+        '▒' => ":",
+        '░' | '·' => ".",
+        '→' | '►' | '▶' | '▸' | '»' => ">",
+        '←' | '◄' | '◀' | '◂' | '«' => "<",
+        '↑' | '▲' | '⏫' => "^",
+        '↓' | '▼' | '⏬' => "v",
+        '•' | '●' | '★' | '◆' | '■' => "*",
+        '○' | '☆' | '◇' | '□' => "o",
+        '✓' | '✔' => "+",
+        '✗' | '✘' | '×' => "x",
+        '…' => ".",
+        'à' | 'á' | 'â' | 'ä' => "a",
+        'è' | 'é' | 'ê' | 'ë' => "e",
+        'ì' | 'í' | 'î' | 'ï' => "i",
+        'ò' | 'ó' | 'ô' | 'ö' => "o",
+        'ù' | 'ú' | 'û' | 'ü' => "u",
+        // Emoji and other pictographs
+        '\u{2190}'..='\u{2BFF}' | '\u{1F000}'..='\u{1FAFF}' => "*",
+        _ => "?",
+    }
+}
+
+/// Swap every symbol in `buffer` for its ASCII stand-in, for terminals
+/// that mangle Unicode. Wide symbols already leave a blank cell after them,
+/// so the layout doesn't move.
+pub fn asciify(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        let glyph = ascii_glyph(cell.symbol());
+        if glyph != cell.symbol() {
+            let glyph = glyph.to_string();
+            cell.set_symbol(&glyph);
+        }
+    }
+}
+
+/// Whether the terminal is one that can't be trusted with Unicode: a
+/// legacy Windows console, a serial or kernel console, or a locale without
+/// UTF-8. `REVGAME_ASCII` set to `1` or `0` overrides the guess.
+pub fn terminal_lacks_unicode(var: impl Fn(&str) -> Option<String>, windows: bool) -> bool {
+    match var("REVGAME_ASCII").as_deref().map(str::trim) {
+        Some("1" | "on" | "true" | "yes") => return true,
+        Some("0" | "off" | "false" | "no") => return false,
+        _ => {}
+    }
+
+    let term = var("TERM").unwrap_or_default();
+    if matches!(
+        term.as_str(),
+        "linux" | "vt100" | "vt102" | "vt220" | "dumb" | "ansi" | "cons25"
+    ) {
+        return true;
+    }
+    // Windows Terminal and editor terminals set these; conhost doesn't
+    if windows && var("WT_SESSION").is_none() && var("TERM_PROGRAM").is_none() && term.is_empty() {
+        return true;
+    }
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .filter_map(&var)
+        .find(|value| !value.is_empty());
+    locale.is_some_and(|locale| {
+        let locale = locale.to_lowercase();
+        !locale.contains("utf-8") && !locale.contains("utf8")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, style::Style};
+
+    #[test]
+    fn test_asciify_keeps_layout() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 12, 1));
+        buffer.set_string(0, 0, "╔═► 🔒 ✓é", Style::default());
+        asciify(&mut buffer);
+        let text: String = buffer.content.iter().map(|cell| cell.symbol()).collect();
+        assert_eq!(text, "+-> *  +e   ");
+    }
+
+    #[test]
+    fn test_detects_terminals_without_unicode() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert!(!terminal_lacks_unicode(
+            env(&[("TERM", "xterm-256color"), ("LANG", "en_US.UTF-8")]),
+            false
+        ));
+        assert!(terminal_lacks_unicode(env(&[("TERM", "vt220")]), false));
+        assert!(terminal_lacks_unicode(
+            env(&[("TERM", "xterm"), ("LANG", "C")]),
+            false
+        ));
+        assert!(!terminal_lacks_unicode(
+            env(&[("TERM", "xterm"), ("LC_ALL", "C.utf8"), ("LANG", "C")]),
+            false
+        ));
+
+        // A bare Windows console, unlike Windows Terminal
+        assert!(terminal_lacks_unicode(env(&[]), true));
+        assert!(!terminal_lacks_unicode(env(&[("WT_SESSION", "1")]), true));
+
+        assert!(!terminal_lacks_unicode(
+            env(&[("TERM", "linux"), ("REVGAME_ASCII", "0")]),
+            false
+        ));
+        assert!(terminal_lacks_unicode(
+            env(&[("REVGAME_ASCII", "1")]),
+            false
+        ));
+    }
+}
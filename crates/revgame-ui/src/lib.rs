@@ -1,11 +1,22 @@
+pub mod animation;
 pub mod app;
-pub mod widgets;
+pub mod console;
+pub mod crash;
+pub mod demo;
+pub mod export;
+pub mod glyphs;
+#[cfg(feature = "online")]
+pub mod online;
+pub mod plugin;
 pub mod screens;
+pub mod session;
+pub mod syntax;
 pub mod theme;
+pub mod touch;
 pub mod tutorial;
-pub mod syntax;
+pub mod widgets;
 
 pub use app::App;
+pub use syntax::{InstructionCategory, SyntaxHighlighter, SyntaxPalette};
 pub use theme::Theme;
 pub use tutorial::{Tutorial, TutorialStep, TutorialTrigger};
-pub use syntax::{SyntaxHighlighter, InstructionCategory};
@@ -0,0 +1,197 @@
+//! Online leaderboard (the `online` feature): solves go to the server set
+//! with `online <url>`, and the puzzle detail screen shows the scores
+//! fetched for a puzzle. Requests run on their own thread so a slow server
+//! never holds up the game; `poll` picks up their answers.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+
+use revgame_core::game::{format_time, CompletionProof, LeaderboardClient, RemoteScore};
+
+use crate::app::{App, Message};
+
+/// Rows of a fetched leaderboard shown on the puzzle detail screen
+const SHOWN_SCORES: usize = 5;
+
+enum Reply {
+    Submitted(Result<(), String>),
+    Scores(String, Result<Vec<RemoteScore>, String>),
+}
+
+/// Requests in flight and leaderboards fetched so far
+#[derive(Default)]
+pub struct OnlineState {
+    pending: Vec<Receiver<Reply>>,
+    scores: HashMap<String, Result<Vec<RemoteScore>, String>>,
+}
+
+fn client(app: &App) -> Option<LeaderboardClient> {
+    match app.settings.leaderboard_url.as_str() {
+        "" => None,
+        url => LeaderboardClient::new(url).ok(),
+    }
+}
+
+fn spawn(app: &mut App, request: impl FnOnce() -> Reply + Send + 'static) {
+    let (sender, receiver) = channel();
+    std::thread::spawn(move || sender.send(request()).ok());
+    app.online.pending.push(receiver);
+}
+
+/// Send the recorded solve of the current puzzle, if a server is set
+pub fn submit_score(app: &mut App, profile: &str) {
+    let Some(client) = client(app) else {
+        return;
+    };
+    let proof = CompletionProof::from_replay(profile, &app.recording);
+    spawn(app, move || Reply::Submitted(client.submit(&proof)));
+}
+
+/// Fetch the leaderboard of a puzzle in the background
+pub fn fetch_scores(app: &mut App, puzzle_id: &str) {
+    let Some(client) = client(app) else {
+        app.message = Some(Message {
+            text: "No leaderboard server set; `online <url>` sets one".to_string(),
+            is_error: true,
+        });
+        return;
+    };
+    let id = puzzle_id.to_string();
+    spawn(app, move || {
+        let scores = client.fetch(&id);
+        Reply::Scores(id, scores)
+    });
+}
+
+/// Pick up answers to requests that have finished
+pub fn poll(app: &mut App) {
+    let mut replies = Vec::new();
+    app.online
+        .pending
+        .retain(|receiver| match receiver.try_recv() {
+            Ok(reply) => {
+                replies.push(reply);
+                false
+            }
+            Err(TryRecvError::Empty) => true,
+            Err(TryRecvError::Disconnected) => false,
+        });
+    for reply in replies {
+        match reply {
+            Reply::Submitted(Ok(())) => {}
+            Reply::Submitted(Err(e)) => {
+                app.message = Some(Message {
+                    text: format!("Score not submitted: {}", e),
+                    is_error: true,
+                });
+            }
+            Reply::Scores(id, scores) => {
+                app.online.scores.insert(id, scores);
+            }
+        }
+    }
+}
+
+/// Lines for the puzzle detail screen: the fetched leaderboard, or why
+/// there isn't one
+pub fn score_lines(app: &App, puzzle_id: &str) -> Vec<String> {
+    if app.settings.leaderboard_url.is_empty() {
+        return Vec::new();
+    }
+    match app.online.scores.get(puzzle_id) {
+        None => vec!["Online leaderboard: press L to fetch".to_string()],
+        Some(Err(e)) => vec![format!("Online leaderboard: {}", e)],
+        Some(Ok(scores)) if scores.is_empty() => {
+            vec!["Online leaderboard: no scores yet".to_string()]
+        }
+        Some(Ok(scores)) => std::iter::once("Online leaderboard:".to_string())
+            .chain(
+                scores
+                    .iter()
+                    .take(SHOWN_SCORES)
+                    .enumerate()
+                    .map(|(i, score)| {
+                        format!(
+                            "  {}. {:<16} {}  {} patch{}",
+                            i + 1,
+                            score.profile,
+                            format_time(score.time_ms),
+                            score.patches,
+                            if score.patches == 1 { "" } else { "es" }
+                        )
+                    }),
+            )
+            .collect(),
+    }
+}
+
+/// `online` shows the server, `online <url>` opts in, `online off` opts out
+pub fn command(app: &mut App, args: &[&str]) {
+    let text = match args {
+        [] if app.settings.leaderboard_url.is_empty() => "Online leaderboard off".to_string(),
+        [] => format!("Submitting solves to {}", app.settings.leaderboard_url),
+        ["off"] => {
+            app.settings.leaderboard_url.clear();
+            "Online leaderboard off".to_string()
+        }
+        [url] => match LeaderboardClient::new(url) {
+            Ok(_) => {
+                app.settings.leaderboard_url = url.to_string();
+                format!("Submitting solves to {}", url)
+            }
+            Err(text) => {
+                app.message = Some(Message {
+                    text,
+                    is_error: true,
+                });
+                return;
+            }
+        },
+        _ => {
+            app.message = Some(Message {
+                text: "Usage: online [<https://server>|off]".to_string(),
+                is_error: true,
+            });
+            return;
+        }
+    };
+    let text = match app.save_settings() {
+        Ok(()) => text,
+        Err(e) => format!("{} (not saved: {})", text, e),
+    };
+    app.message = Some(Message {
+        text,
+        is_error: false,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_online_command_and_scores() {
+        let mut app = App::with_save_manager(revgame_core::game::SaveManager::in_memory());
+        assert!(score_lines(&app, "basic-001").is_empty());
+        command(&mut app, &["http://scores.example"]);
+        assert!(app.message.as_ref().unwrap().is_error);
+
+        app.settings.leaderboard_url = "http://127.0.0.1:9".to_string();
+        assert_eq!(
+            score_lines(&app, "basic-001"),
+            ["Online leaderboard: press L to fetch"]
+        );
+        app.online.scores.insert(
+            "basic-001".to_string(),
+            Ok(vec![RemoteScore {
+                profile: "Bob".to_string(),
+                time_ms: 3000,
+                patches: 1,
+            }]),
+        );
+        assert_eq!(
+            score_lines(&app, "basic-001")[1],
+            "  1. Bob              0:03.0  1 patch"
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! UI side of the plugin system: extra panels on top of the analysis passes
+//! and commands `revgame_core::plugin` provides, and the `plugins` and
+//! `panel` commands. Panels open as an overlay over the debugger with
+//! `panel <name>`.
+
+use revgame_core::PluginRegistry;
+
+use crate::app::{App, Message};
+
+/// An extra panel: a title and the lines to show, worked out from the
+/// app each frame
+pub trait PanelPlugin {
+    fn title(&self) -> &str;
+
+    fn lines(&self, app: &App) -> Vec<String>;
+}
+
+/// Findings of every analysis pass over the disassembly on screen
+struct FindingsPanel;
+
+impl PanelPlugin for FindingsPanel {
+    fn title(&self) -> &str {
+        "Analysis"
+    }
+
+    fn lines(&self, app: &App) -> Vec<String> {
+        let Some(ref debugger) = app.debugger else {
+            return vec!["No program loaded".to_string()];
+        };
+        let findings = app.plugins.core.analyze(debugger, &app.disasm_cache);
+        if findings.is_empty() {
+            return vec!["Nothing found".to_string()];
+        }
+        findings
+            .into_iter()
+            .map(|(pass, finding)| match finding.address {
+                Some(address) => format!("[{}] 0x{:08X} {}", pass, address, finding.text),
+                None => format!("[{}] {}", pass, finding.text),
+            })
+            .collect()
+    }
+}
+
+/// Every plugin the App consults: core analysis passes and commands, and
+/// UI panels
+pub struct Plugins {
+    pub core: PluginRegistry,
+    panels: Vec<Box<dyn PanelPlugin>>,
+}
+
+impl Default for Plugins {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl Plugins {
+    /// The plugins compiled in, plus the built-in analysis panel
+    pub fn builtin() -> Self {
+        Self {
+            core: PluginRegistry::builtin(),
+            panels: vec![Box::new(FindingsPanel)],
+        }
+    }
+
+    pub fn register_panel(&mut self, panel: Box<dyn PanelPlugin>) {
+        self.panels.push(panel);
+    }
+
+    pub fn panels(&self) -> &[Box<dyn PanelPlugin>] {
+        &self.panels
+    }
+
+    /// Index of a panel by number (from 1) or title, ignoring case
+    pub fn find_panel(&self, name: &str) -> Option<usize> {
+        match name.parse::<usize>() {
+            Ok(n) => n.checked_sub(1).filter(|&i| i < self.panels.len()),
+            Err(_) => self
+                .panels
+                .iter()
+                .position(|panel| panel.title().eq_ignore_ascii_case(name)),
+        }
+    }
+}
+
+/// An open plugin panel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PanelState {
+    pub index: usize,
+    pub scroll: usize,
+}
+
+/// `plugins`: show the plugins compiled in, their commands and panels
+pub(crate) fn list(app: &mut App) {
+    let names = app.plugins.core.names();
+    let panels: Vec<String> = app
+        .plugins
+        .panels()
+        .iter()
+        .enumerate()
+        .map(|(i, panel)| format!("{} {}", i + 1, panel.title()))
+        .collect();
+    let mut text = if names.is_empty() {
+        "No plugins compiled in".to_string()
+    } else {
+        format!("Plugins: {}", names.join(", "))
+    };
+    let usage = app.plugins.core.usage();
+    if !usage.is_empty() {
+        text.push_str(&format!("; commands: {}", usage.join(", ")));
+    }
+    text.push_str(&format!("; panels: {}", panels.join(", ")));
+    app.message = Some(Message {
+        text,
+        is_error: false,
+    });
+}
+
+/// `panel <n|name>`: open a plugin panel by number or title
+pub(crate) fn open_panel(app: &mut App, name: &str) {
+    match app.plugins.find_panel(name) {
+        Some(index) => app.plugin_panel = Some(PanelState { index, scroll: 0 }),
+        None => {
+            app.message = Some(Message {
+                text: format!("No panel {}; `plugins` lists them", name),
+                is_error: true,
+            });
+        }
+    }
+}
+
+/// Title and lines of the open plugin panel
+pub fn panel_lines(app: &App) -> Option<(&str, Vec<String>)> {
+    let panel = app.plugins.panels().get(app.plugin_panel?.index)?;
+    Some((panel.title(), panel.lines(app)))
+}
+
+/// Run a command the game doesn't know through the plugins
+pub(crate) fn command(app: &mut App, parts: &[&str]) {
+    let result = match app.debugger.as_mut() {
+        Some(debugger) => app
+            .plugins
+            .core
+            .run_command(parts[0], &parts[1..], debugger),
+        None => None,
+    };
+    app.message = Some(match result {
+        Some(Ok(text)) => {
+            app.refresh_disasm();
+            Message {
+                text,
+                is_error: false,
+            }
+        }
+        Some(Err(text)) => Message {
+            text,
+            is_error: true,
+        },
+        None => Message {
+            text: format!("Unknown command: {}", parts[0]),
+            is_error: true,
+        },
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Eip;
+
+    impl PanelPlugin for Eip {
+        fn title(&self) -> &str {
+            "EIP"
+        }
+
+        fn lines(&self, app: &App) -> Vec<String> {
+            app.debugger
+                .iter()
+                .map(|debugger| format!("0x{:08X}", debugger.cpu.eip))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_panels_by_number_or_title() {
+        let mut plugins = Plugins::builtin();
+        plugins.register_panel(Box::new(Eip));
+        assert_eq!(plugins.find_panel("analysis"), Some(0));
+        assert_eq!(plugins.find_panel("2"), Some(1));
+        assert_eq!(plugins.find_panel("3"), None);
+        assert_eq!(plugins.find_panel("0"), None);
+        assert_eq!(plugins.panels()[1].lines(&App::new()), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_plugin_commands_and_panels() {
+        use revgame_core::{CommandPlugin, Debugger};
+
+        struct Nop;
+
+        impl CommandPlugin for Nop {
+            fn name(&self) -> &str {
+                "nop"
+            }
+
+            fn commands(&self) -> &[(&str, &str)] {
+                &[("nopall", "nopall")]
+            }
+
+            fn run(
+                &mut self,
+                _command: &str,
+                _args: &[&str],
+                debugger: &mut Debugger,
+            ) -> Result<String, String> {
+                debugger.patch(0x1000, &[0x90]).map_err(|e| e.to_string())?;
+                Ok("Patched".to_string())
+            }
+        }
+
+        let mut app = App::with_save_manager(revgame_core::game::SaveManager::in_memory());
+        app.load_puzzle(
+            r#"
+[metadata]
+id = "plugin-test"
+title = "Plugins"
+difficulty = 1
+category = "patching"
+
+[description]
+brief = "test"
+detailed = "test"
+
+[setup]
+code_start = 0x1000
+data_start = 0x2000
+stack_start = 0x3000
+
+[setup.code]
+bytes = "90 90 F4"
+
+[validation]
+type = "normal_halt"
+"#,
+        )
+        .unwrap();
+        app.process_command("nopall");
+        assert!(app
+            .message
+            .as_ref()
+            .unwrap()
+            .text
+            .starts_with("Unknown command"));
+
+        app.plugins.core.register_command(Box::new(Nop));
+        app.process_command("nopall");
+        assert_eq!(app.message.as_ref().unwrap().text, "Patched");
+        app.process_command("plugins");
+        assert!(app.message.as_ref().unwrap().text.contains("nopall"));
+
+        app.process_command("panel analysis");
+        let (title, lines) = panel_lines(&app).unwrap();
+        assert_eq!(title, "Analysis");
+        assert!(!lines.is_empty());
+        app.process_command("panel 9");
+        assert!(app.message.as_ref().unwrap().is_error);
+    }
+}
@@ -2,16 +2,13 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 
-use crate::{
-    app::App,
-    theme::Theme,
-};
+use crate::{app::App, theme::Theme};
 
-use revgame_core::game::AchievementId;
+use revgame_core::game::{AchievementId, LearningLog};
 
 /// Render the achievements screen
 pub fn render_achievements(frame: &mut Frame, app: &App, theme: &Theme) {
@@ -21,17 +18,18 @@ pub fn render_achievements(frame: &mut Frame, app: &App, theme: &Theme) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5),  // Header
-            Constraint::Min(10),    // Achievement list
-            Constraint::Length(3),  // Stats
-            Constraint::Length(1),  // Help bar
+            Constraint::Length(5), // Header
+            Constraint::Min(10),   // Achievement list
+            Constraint::Length(3), // Stats
+            Constraint::Length(5), // Learning insights
+            Constraint::Length(1), // Help bar
         ])
         .split(area);
 
     // Header
     let total_points = app.game_state.achievements.total_points;
     let unlocked_count = app.game_state.achievements.unlocked.len();
-    let total_achievements = 15; // Total number of achievements
+    let total_achievements = 16; // Total number of achievements
     let progress = app.game_state.achievements.progress_percentage();
 
     let header_text = vec![
@@ -45,12 +43,28 @@ pub fn render_achievements(frame: &mut Frame, app: &App, theme: &Theme) {
         Line::from(vec![
             Span::styled("Progress: ", theme.muted_style()),
             Span::styled(
-                format!("{}/{} ({:.0}%)", unlocked_count, total_achievements, progress),
+                format!(
+                    "{}/{} ({:.0}%)",
+                    unlocked_count, total_achievements, progress
+                ),
                 theme.highlight(),
             ),
             Span::raw("  "),
             Span::styled("Total Points: ", theme.muted_style()),
-            Span::styled(format!("{}", total_points), Style::default().fg(theme.success)),
+            Span::styled(
+                format!("{}", total_points),
+                Style::default().fg(theme.success),
+            ),
+            Span::raw("  "),
+            Span::styled("Puzzle Score: ", theme.muted_style()),
+            Span::styled(
+                format!(
+                    "{} ({} assist)",
+                    app.game_state.score,
+                    app.game_state.assist.label()
+                ),
+                Style::default().fg(theme.success),
+            ),
         ]),
     ];
 
@@ -74,6 +88,7 @@ pub fn render_achievements(frame: &mut Frame, app: &App, theme: &Theme) {
         AchievementId::UndoMaster,
         AchievementId::Perfectionist,
         AchievementId::Experimenter,
+        AchievementId::Clairvoyant,
         AchievementId::WinStreak3,
         AchievementId::WinStreak5,
         AchievementId::WinStreak10,
@@ -86,7 +101,11 @@ pub fn render_achievements(frame: &mut Frame, app: &App, theme: &Theme) {
 
             let icon = if unlocked { ach.icon() } else { "🔒" };
             let name = if unlocked { ach.name() } else { "???" };
-            let desc = if unlocked { ach.description() } else { "Locked" };
+            let desc = if unlocked {
+                ach.description()
+            } else {
+                "Locked"
+            };
             let points = ach.points();
 
             let style = if unlocked {
@@ -113,8 +132,11 @@ pub fn render_achievements(frame: &mut Frame, app: &App, theme: &Theme) {
         })
         .collect();
 
-    let achievement_list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(" Achievements "));
+    let achievement_list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Achievements "),
+    );
 
     frame.render_widget(achievement_list, chunks[1]);
 
@@ -151,6 +173,12 @@ pub fn render_achievements(frame: &mut Frame, app: &App, theme: &Theme) {
                 format!("{}", app.game_state.achievements.total_undos),
                 theme.highlight(),
             ),
+            Span::raw("  "),
+            Span::styled("Revealed: ", theme.muted_style()),
+            Span::styled(
+                format!("{}", app.game_state.achievements.total_revealed),
+                Style::default().fg(theme.warning),
+            ),
         ]),
     ];
 
@@ -160,10 +188,42 @@ pub fn render_achievements(frame: &mut Frame, app: &App, theme: &Theme) {
 
     frame.render_widget(stats_para, chunks[2]);
 
+    // Insights from the local learning log
+    let insights: Vec<Line> = if !app.settings.learning_log {
+        vec![Line::from(Span::styled(
+            "Turn on \"Learning log\" in Settings for insights into how you play (kept on this device only)",
+            theme.muted_style(),
+        ))]
+    } else {
+        let found = app.game_state.learning.insights();
+        if found.is_empty() {
+            let attempts = app.game_state.learning.attempts().len();
+            vec![Line::from(Span::styled(
+                format!(
+                    "Insights appear after {} logged puzzles ({} so far)",
+                    LearningLog::MIN_ATTEMPTS,
+                    attempts
+                ),
+                theme.muted_style(),
+            ))]
+        } else {
+            found
+                .into_iter()
+                .map(|insight| Line::from(Span::styled(format!("• {}", insight), theme.normal())))
+                .collect()
+        }
+    };
+
+    let insights_para = Paragraph::new(insights)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::TOP).title(" Insights "));
+
+    frame.render_widget(insights_para, chunks[3]);
+
     // Help bar
     let help = Paragraph::new(" [Esc] Back ")
         .style(theme.muted_style())
         .alignment(Alignment::Center);
 
-    frame.render_widget(help, chunks[3]);
+    frame.render_widget(help, chunks[4]);
 }
@@ -0,0 +1,158 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use revgame_core::reference::{ascii_name, ascii_note, ascii_search, ASCII_MAX};
+
+use crate::theme::Theme;
+
+/// ASCII table search and selection
+#[derive(Debug, Clone, Default)]
+pub struct AsciiTableState {
+    pub query: String,
+    pub selected: u8,
+}
+
+impl AsciiTableState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Codes matching the query
+    pub fn matches(&self) -> Vec<u8> {
+        ascii_search(&self.query)
+    }
+
+    /// Add (or with `None`, delete) a query character, selecting the first match
+    pub fn type_query(&mut self, c: Option<char>) {
+        match c {
+            Some(c) => self.query.push(c),
+            None => {
+                self.query.pop();
+            }
+        }
+        if let Some(&first) = self.matches().first() {
+            self.selected = first;
+        }
+    }
+
+    /// Select the next match after the selection, wrapping around
+    pub fn next_match(&mut self) {
+        let matches = self.matches();
+        if let Some(&next) = matches
+            .iter()
+            .find(|&&code| code > self.selected)
+            .or(matches.first())
+        {
+            self.selected = next;
+        }
+    }
+
+    /// Move the selection by `delta` codes, wrapping within 0-127
+    pub fn move_selection(&mut self, delta: i32) {
+        self.selected = (self.selected as i32 + delta).rem_euclid(ASCII_MAX as i32 + 1) as u8;
+    }
+}
+
+/// Render the 128 ASCII codes in columns of 16, with search matches highlighted
+pub fn render_ascii_overlay(frame: &mut Frame, state: &AsciiTableState, theme: &Theme) {
+    let area = centered_fixed(72, 23, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),  // Search
+            Constraint::Length(17), // Table
+            Constraint::Length(2),  // Selected code
+            Constraint::Length(1),  // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" ASCII Table ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let matches = state.matches();
+    let search = if state.query.is_empty() {
+        "Find: type a character, a code (65, 0x41) or a name (esc)".to_string()
+    } else {
+        format!("Find: {}  ({} found)", state.query, matches.len())
+    };
+    frame.render_widget(
+        Paragraph::new(search).style(Style::default().fg(theme.accent)),
+        chunks[0],
+    );
+
+    // Columns go up by 0x10, rows by 1, so the low nibble reads across
+    let mut header = vec![Span::raw("   ")];
+    header.extend((0..8).map(|column| {
+        Span::styled(
+            format!("{:<8}", format!("{:X}_", column)),
+            theme.muted_style(),
+        )
+    }));
+    let mut lines = vec![Line::from(header)];
+    for row in 0..16u8 {
+        let mut spans = vec![Span::styled(format!("_{:X} ", row), theme.muted_style())];
+        for column in 0..8u8 {
+            let code = column * 16 + row;
+            let style = if code == state.selected {
+                theme.selected()
+            } else if matches.contains(&code) {
+                theme.highlight()
+            } else if code < 0x20 || code == ASCII_MAX {
+                theme.muted_style()
+            } else {
+                theme.normal()
+            };
+            spans.push(Span::styled(format!("{:02X}", code), theme.hex_byte()));
+            spans.push(Span::styled(format!(" {:<3}", ascii_name(code)), style));
+            spans.push(Span::raw("  "));
+        }
+        lines.push(Line::from(spans));
+    }
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let code = state.selected;
+    let detail = vec![
+        Line::from(vec![
+            Span::styled(format!("{} ", ascii_name(code)), theme.register_name()),
+            Span::styled(
+                format!("= 0x{:02X} = {} = 0b{:08b}", code, code, code),
+                theme.register_value(),
+            ),
+        ]),
+        Line::from(Span::styled(ascii_note(code), theme.normal())),
+    ];
+    frame.render_widget(Paragraph::new(detail), chunks[2]);
+
+    // Help
+    let help = Paragraph::new(" [↑↓←→] Move  [Enter] Next match  [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[3]);
+}
+
+/// A `width` x `height` rectangle centered in `r`, shrunk to fit
+fn centered_fixed(width: u16, height: u16, r: Rect) -> Rect {
+    let width = width.min(r.width);
+    let height = height.min(r.height);
+    Rect::new(
+        r.x + (r.width - width) / 2,
+        r.y + (r.height - height) / 2,
+        width,
+        height,
+    )
+}
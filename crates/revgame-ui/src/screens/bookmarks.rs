@@ -6,14 +6,17 @@ use ratatui::{
     Frame,
 };
 
-use revgame_core::debugger::Bookmark;
+use revgame_core::debugger::{Bookmark, BookmarkCategory};
 
 use crate::theme::Theme;
 
 /// Bookmarks viewer state
+#[derive(Default)]
 pub struct BookmarksViewState {
     pub selected: usize,
     pub editing: Option<EditingBookmark>,
+    /// Only list bookmarks in this category
+    pub filter: Option<BookmarkCategory>,
 }
 
 /// State when editing a bookmark
@@ -23,21 +26,12 @@ pub struct EditingBookmark {
     pub note: String,
 }
 
-impl Default for BookmarksViewState {
-    fn default() -> Self {
-        Self {
-            selected: 0,
-            editing: None,
-        }
-    }
-}
-
 impl BookmarksViewState {
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub fn navigate_up(&mut self, max: usize) {
+    pub fn navigate_up(&mut self, _max: usize) {
         if self.selected > 0 {
             self.selected -= 1;
         }
@@ -63,6 +57,12 @@ impl BookmarksViewState {
     pub fn is_editing(&self) -> bool {
         self.editing.is_some()
     }
+
+    /// Show the next category only, or all bookmarks after the last
+    pub fn cycle_filter(&mut self) {
+        self.filter = BookmarkCategory::cycle(self.filter);
+        self.selected = 0;
+    }
 }
 
 /// Render the bookmarks dialog
@@ -83,9 +83,9 @@ pub fn render_bookmarks_dialog(
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(5),     // Bookmark list
-            Constraint::Length(2),  // Help
+            Constraint::Length(3), // Header
+            Constraint::Min(5),    // Bookmark list
+            Constraint::Length(2), // Help
         ])
         .split(area);
 
@@ -93,14 +93,21 @@ pub fn render_bookmarks_dialog(
     let block = Block::default()
         .title(" Bookmarks ")
         .borders(Borders::ALL)
+        .border_set(theme.border_set())
         .border_style(theme.border_style());
 
     frame.render_widget(block, area);
 
     // Header
-    let header = Paragraph::new(format!("{} Bookmarks", bookmarks.len()))
-        .style(theme.normal())
-        .alignment(Alignment::Center);
+    let header = Paragraph::new(Line::from(match state.filter {
+        None => vec![Span::raw(format!("{} Bookmarks", bookmarks.len()))],
+        Some(category) => vec![
+            Span::raw(format!("{} Bookmarks in ", bookmarks.len())),
+            Span::styled(category.name(), theme.bookmark(Some(category))),
+        ],
+    }))
+    .style(theme.normal())
+    .alignment(Alignment::Center);
 
     frame.render_widget(header, chunks[0]);
 
@@ -116,7 +123,7 @@ pub fn render_bookmarks_dialog(
                     bookmark.note.clone()
                 };
 
-                let text = format!("  0x{:08X}: {}", bookmark.address, note);
+                let text = format!("0x{:08X}: {}", bookmark.address, note);
 
                 let style = if idx == state.selected {
                     Style::default()
@@ -126,7 +133,16 @@ pub fn render_bookmarks_dialog(
                     theme.normal()
                 };
 
-                ListItem::new(Line::from(Span::styled(text, style)))
+                let category = bookmark.category.map_or("", |category| category.name());
+                ListItem::new(Line::from(vec![
+                    Span::raw(" "),
+                    Span::styled(theme.bookmark_marker(), theme.bookmark(bookmark.category)),
+                    Span::styled(
+                        format!(" {:<9}", category),
+                        theme.bookmark(bookmark.category),
+                    ),
+                    Span::styled(text, style),
+                ]))
             })
             .collect();
 
@@ -134,7 +150,14 @@ pub fn render_bookmarks_dialog(
 
         frame.render_widget(list, chunks[1]);
     } else {
-        let no_bookmarks = Paragraph::new("No bookmarks yet\n\nPress [B] on any address to add a bookmark")
+        let empty = match state.filter {
+            None => "No bookmarks yet\n\nPress [B] on any address to add a bookmark".to_string(),
+            Some(category) => format!(
+                "No {} bookmarks\n\nPress [F] to show another category",
+                category.name()
+            ),
+        };
+        let no_bookmarks = Paragraph::new(empty)
             .style(theme.muted_style())
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL));
@@ -144,9 +167,9 @@ pub fn render_bookmarks_dialog(
 
     // Help
     let help = if bookmarks.is_empty() {
-        " [Esc] Close "
+        " [F] Filter  [Esc] Close "
     } else {
-        " [↑↓] Navigate  [G] Go to  [E] Edit  [D] Delete  [Esc] Close "
+        " [↑↓] Navigate  [G] Go to  [E] Edit  [C] Category  [F] Filter  [D] Delete  [Esc] Close "
     };
 
     let help_para = Paragraph::new(help)
@@ -163,9 +186,9 @@ fn render_edit_dialog(frame: &mut Frame, editing: &EditingBookmark, theme: &Them
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Address
-            Constraint::Length(3),  // Note input
-            Constraint::Length(2),  // Help
+            Constraint::Length(3), // Address
+            Constraint::Length(3), // Note input
+            Constraint::Length(2), // Help
         ])
         .split(area);
 
@@ -173,6 +196,7 @@ fn render_edit_dialog(frame: &mut Frame, editing: &EditingBookmark, theme: &Them
     let block = Block::default()
         .title(" Edit Bookmark ")
         .borders(Borders::ALL)
+        .border_set(theme.border_set())
         .border_style(theme.border_style());
 
     frame.render_widget(block, area);
@@ -0,0 +1,141 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Keys a new player needs, shown under every briefing
+const CONTROLS: [(&str, &str); 8] = [
+    ("s", "Step"),
+    ("r", "Run"),
+    ("b", "Breakpoint"),
+    ("F4", "Reset"),
+    (":", "Command (patch <addr> <bytes>)"),
+    ("h", "Hint"),
+    ("o", "Objectives"),
+    ("?", "Reference"),
+];
+
+/// Render the puzzle's briefing: the full description, the goal, difficulty
+/// and par, and a controls recap
+pub fn render_briefing(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let Some(ref puzzle) = app.puzzle else {
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(6),    // Description and goal
+            Constraint::Length(4), // Controls
+            Constraint::Length(1), // Help
+        ])
+        .split(frame.area());
+
+    let (star, no_star) = if theme.ascii {
+        ("*", ".")
+    } else {
+        ("★", "☆")
+    };
+    let difficulty = puzzle.metadata.difficulty.min(5) as usize;
+    let title = Paragraph::new(Line::from(vec![
+        Span::styled(puzzle.metadata.title.clone(), theme.highlight()),
+        Span::styled(
+            format!("  {}  ", puzzle.metadata.category.name()),
+            theme.muted_style(),
+        ),
+        Span::styled(
+            star.repeat(difficulty) + no_star.repeat(5 - difficulty).as_str(),
+            theme.changed(),
+        ),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_set(theme.border_set()),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines = vec![
+        Line::styled(puzzle.description.brief.clone(), theme.highlight()),
+        Line::default(),
+    ];
+    lines.extend(
+        puzzle
+            .description
+            .detailed
+            .trim()
+            .lines()
+            .map(|line| Line::styled(line.to_string(), theme.normal())),
+    );
+
+    lines.push(Line::default());
+    lines.push(Line::styled("Goal", theme.register_name()));
+    for objective in app.objectives() {
+        lines.push(Line::styled(
+            format!("  • {}", objective.description),
+            theme.normal(),
+        ));
+    }
+
+    lines.push(Line::default());
+    let mut details = Vec::new();
+    if let Some(minutes) = puzzle.metadata.estimated_time_minutes {
+        details.push(format!("Par: {} min", minutes));
+    }
+    let stats = app
+        .game_state
+        .achievements
+        .puzzle_stats
+        .get(&puzzle.metadata.id);
+    if let Some(seconds) = stats.filter(|s| s.completed).and_then(|s| s.time_seconds) {
+        details.push(format!("Your time: {}m {:02}s", seconds / 60, seconds % 60));
+    }
+    if !puzzle.metadata.concepts.is_empty() {
+        details.push(format!("Concepts: {}", puzzle.metadata.concepts.join(", ")));
+    }
+    if puzzle.hints.hint_count() > 0 {
+        details.push(format!("Hints: {}", puzzle.hints.hint_count()));
+    }
+    if !details.is_empty() {
+        lines.push(Line::styled(details.join("   "), theme.muted_style()));
+    }
+
+    let description = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(description, chunks[1]);
+
+    // Controls recap, four to a line
+    let controls: Vec<Line> = CONTROLS
+        .chunks(4)
+        .map(|row| {
+            Line::from(
+                row.iter()
+                    .flat_map(|(key, action)| {
+                        [
+                            Span::styled(format!("[{}] ", key), theme.register_name()),
+                            Span::styled(format!("{}   ", action), theme.normal()),
+                        ]
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    let controls = Paragraph::new(controls).alignment(Alignment::Center).block(
+        Block::default()
+            .title(" Controls ")
+            .borders(Borders::TOP)
+            .border_set(theme.border_set()),
+    );
+    frame.render_widget(controls, chunks[2]);
+
+    let help = Paragraph::new(" [Enter] Begin ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[3]);
+}
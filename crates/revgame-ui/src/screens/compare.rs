@@ -0,0 +1,153 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use revgame_core::emulator::{CpuState, DisassemblyLine, Register};
+
+use crate::theme::Theme;
+
+/// One side of the split-screen compare
+pub struct CompareSide<'a> {
+    pub title: String,
+    pub cpu: &'a CpuState,
+    pub lines: &'a [DisassemblyLine],
+    /// Start of the puzzle's data region and its first bytes
+    pub data_start: u32,
+    pub data: Vec<u8>,
+}
+
+/// Render two sessions side by side, highlighting where they differ
+pub fn render_compare(
+    frame: &mut Frame,
+    left: &CompareSide,
+    right: &CompareSide,
+    sync: bool,
+    theme: &Theme,
+    area: Rect,
+) {
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    render_side(frame, left, right, theme, halves[0], true);
+    render_side(frame, right, left, theme, halves[1], false);
+
+    // Summary of the differences along the bottom of the left pane's block
+    let registers = Register::ALL
+        .iter()
+        .filter(|&&reg| left.cpu.get_register(reg) != right.cpu.get_register(reg))
+        .count();
+    let bytes = differing_bytes(&left.data, &right.data);
+    let mut summary = format!(
+        " {} register{} differ, {} data byte{} ",
+        registers,
+        if registers == 1 { "" } else { "s" },
+        bytes.len(),
+        if bytes.len() == 1 { "" } else { "s" },
+    );
+    if let Some(&first) = bytes.first() {
+        summary.push_str(&format!(
+            "(first at 0x{:08X}) ",
+            left.data_start.wrapping_add(first as u32)
+        ));
+    }
+    if sync {
+        summary.push_str("| synced stepping ");
+    }
+    let row = Rect {
+        y: area.y + area.height.saturating_sub(1),
+        height: 1,
+        ..area
+    };
+    frame.render_widget(
+        Paragraph::new(Span::styled(summary, theme.muted_style())),
+        row,
+    );
+}
+
+fn render_side(
+    frame: &mut Frame,
+    side: &CompareSide,
+    other: &CompareSide,
+    theme: &Theme,
+    area: Rect,
+    focused: bool,
+) {
+    let block = theme.panel_block(side.title.as_str(), focused);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut lines: Vec<Line> = Register::ALL
+        .iter()
+        .map(|&reg| {
+            let value = side.cpu.get_register(reg);
+            let style = if value == other.cpu.get_register(reg) {
+                theme.register_value()
+            } else {
+                theme.changed()
+            };
+            Line::from(vec![
+                Span::styled(format!("{:<4}", reg.name()), theme.register_name()),
+                Span::styled(format!("0x{:08X}", value), style),
+            ])
+        })
+        .collect();
+
+    let flags = side.cpu.eflags.display();
+    let style = if flags == other.cpu.eflags.display() {
+        theme.register_value()
+    } else {
+        theme.changed()
+    };
+    lines.push(Line::from(vec![
+        Span::styled("FLG ", theme.register_name()),
+        Span::styled(flags, style),
+    ]));
+
+    let state = match (&side.cpu.fault, side.cpu.halted) {
+        (Some(fault), _) => Span::styled(format!("Fault: {:?}", fault), theme.error_style()),
+        (None, true) => Span::styled("Halted", theme.warning_style()),
+        (None, false) => Span::styled("Running", theme.muted_style()),
+    };
+    lines.push(Line::from(state));
+    lines.push(Line::from(""));
+
+    for line in side.lines {
+        let current = line.address == side.cpu.eip;
+        let style = if current {
+            theme.current_instruction()
+        } else {
+            theme.normal()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if current { "> " } else { "  " }, style),
+            Span::styled(format!("{:08X}  ", line.address), theme.address()),
+            Span::styled(line.text.clone(), style),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Offsets where two byte ranges differ (a shorter range differs past its end)
+fn differing_bytes(a: &[u8], b: &[u8]) -> Vec<usize> {
+    (0..a.len().max(b.len()))
+        .filter(|&i| a.get(i) != b.get(i))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_differing_bytes() {
+        assert_eq!(differing_bytes(b"abcd", b"abXd"), [2]);
+        assert_eq!(differing_bytes(b"ab", b"abc"), [2]);
+        assert!(differing_bytes(b"", b"").is_empty());
+    }
+}
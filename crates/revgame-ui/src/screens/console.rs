@@ -0,0 +1,93 @@
+use log::Level;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::console::{ConsoleState, LogLine};
+use crate::theme::Theme;
+
+/// Render the debug console: the latest log records, newest at the bottom,
+/// scrolled back by `state.scroll`
+pub fn render_console(frame: &mut Frame, state: &ConsoleState, lines: &[LogLine], theme: &Theme) {
+    let area = centered_rect(90, 70, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),    // Records
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(format!(" Debug Console ({} and up) ", state.level))
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    if lines.is_empty() {
+        let empty = Paragraph::new("Nothing logged yet")
+            .style(theme.muted_style())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, chunks[0]);
+    } else {
+        let visible = chunks[0].height as usize;
+        let end = lines
+            .len()
+            .saturating_sub(state.scroll.min(lines.len().saturating_sub(1)));
+        let shown: Vec<Line> = lines[end.saturating_sub(visible)..end]
+            .iter()
+            .map(|line| {
+                let style = match line.level {
+                    Level::Error => Style::default().fg(theme.error),
+                    Level::Warn => Style::default().fg(theme.warning),
+                    Level::Info => theme.normal(),
+                    Level::Debug | Level::Trace => theme.muted_style(),
+                };
+                Line::from(vec![
+                    Span::styled(format!("{:<5} ", line.level), style),
+                    Span::styled(format!("{}: ", line.target), theme.muted_style()),
+                    Span::styled(line.message.as_str(), style),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(shown), chunks[0]);
+    }
+
+    // Help
+    let help = Paragraph::new(" [↑↓] Scroll  [←→] Level  [C] Clear  [F12/Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,130 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::debugger::{ConstantMatch, ConstantSource};
+
+use crate::theme::Theme;
+
+/// Magic constant popup selection
+#[derive(Debug, Clone, Default)]
+pub struct ConstantsState {
+    pub selected: usize,
+}
+
+impl ConstantsState {
+    pub fn select_next(&mut self, count: usize) {
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_prev(&mut self, count: usize) {
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+}
+
+/// Render the known constants found in code and data, explaining the selected one
+pub fn render_constants_overlay(
+    frame: &mut Frame,
+    state: &ConstantsState,
+    found: &[ConstantMatch],
+    theme: &Theme,
+) {
+    let area = centered_rect(70, 60, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),    // Matches
+            Constraint::Length(4), // Explanation
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Magic Constants ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    if found.is_empty() {
+        let empty = Paragraph::new("No well-known constants in the code or data")
+            .style(theme.muted_style())
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, chunks[0]);
+    } else {
+        // Keep the selection in view
+        let visible = chunks[0].height as usize;
+        let first = state.selected.saturating_sub(visible.saturating_sub(1));
+        let lines: Vec<Line> = found
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(i, found)| {
+                let style = if i == state.selected {
+                    theme.selected()
+                } else {
+                    theme.normal()
+                };
+                let source = match found.source {
+                    ConstantSource::Immediate => "code",
+                    ConstantSource::Memory => "data",
+                };
+                Line::from(vec![
+                    Span::styled(format!("0x{:08X} ", found.address), theme.address()),
+                    Span::styled(format!("{:<5}", source), theme.muted_style()),
+                    Span::styled(format!("{:08X}  ", found.constant.value), theme.hex_byte()),
+                    Span::styled(found.constant.name, style),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        if let Some(selected) = found.get(state.selected) {
+            let hint = Paragraph::new(selected.constant.hint)
+                .style(theme.normal())
+                .wrap(Wrap { trim: true });
+            frame.render_widget(hint, chunks[1]);
+        }
+    }
+
+    // Help
+    let help = Paragraph::new(" [↑↓] Select  [Enter] Go to  [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,119 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use revgame_core::reference::Conversion;
+
+use crate::theme::Theme;
+
+/// Number converter input
+#[derive(Default)]
+pub struct ConverterState {
+    pub input: String,
+}
+
+impl ConverterState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Render the converter with the current input's readings
+pub fn render_converter_overlay(
+    frame: &mut Frame,
+    state: &ConverterState,
+    result: Result<Conversion, String>,
+    theme: &Theme,
+) {
+    let area = centered_rect(70, 70, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Input
+            Constraint::Min(9),    // Readings
+            Constraint::Length(4), // Bitwise breakdown
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Converter ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let input =
+        Paragraph::new(format!("Value: {}", state.input)).style(Style::default().fg(theme.accent));
+
+    frame.render_widget(input, chunks[0]);
+
+    match result {
+        Ok(conversion) => {
+            let lines: Vec<Line> = conversion
+                .rows()
+                .into_iter()
+                .map(|(label, value)| {
+                    Line::from(vec![
+                        Span::styled(format!("{:<15}", label), theme.register_name()),
+                        Span::styled(value, theme.normal()),
+                    ])
+                })
+                .collect();
+            frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+            if let Some(breakdown) = conversion.bitwise_breakdown() {
+                let lines: Vec<Line> = breakdown
+                    .into_iter()
+                    .map(|line| Line::from(Span::styled(line, theme.hex_byte())))
+                    .collect();
+                frame.render_widget(Paragraph::new(lines), chunks[2]);
+            }
+        }
+        Err(e) => {
+            let error = if state.input.is_empty() {
+                theme.muted_style()
+            } else {
+                theme.error_style()
+            };
+            frame.render_widget(Paragraph::new(e).style(error), chunks[1]);
+        }
+    }
+
+    // Help
+    let help = Paragraph::new(" 0x hex, decimal, registers, + - * / & | ^ ~ << >>  [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -1,68 +1,427 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
+    style::Modifier,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Clear, Paragraph, Tabs, Wrap},
     Frame,
 };
 
+use revgame_core::{
+    debugger::{constants_in_code, DebuggerState, PseudoLine},
+    emulator::DisassemblyMode,
+};
+
 use crate::{
-    app::{App, FocusedPanel},
-    widgets::{DisasmView, MemoryView, RegisterView, StackView, TutorialOverlay, DebuggerLayout, RewindOverlay},
+    app::{App, FocusedPanel, Message},
+    screens::render_compare,
+    touch::{TouchButton, TouchTarget},
+    widgets::{
+        DebuggerLayout, DisasmView, MemoryView, RegisterView, RewindOverlay, StackView,
+        TimelineView, TutorialOverlay,
+    },
 };
 
-/// Render the debugger screen
-pub fn render_debugger(frame: &mut Frame, app: &App) {
-    let area = frame.area();
+/// Arrangement of panels on the debugger screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutMode {
+    /// Disassembly + memory on the left, registers + stack on the right
+    #[default]
+    Standard,
+    /// Full-width disassembly above a row of registers, memory and stack
+    WideDisassembly,
+    /// Disassembly + registers on the left, memory + stack stacked on the right
+    Stacked,
+    /// Disassembly and registers only
+    Minimal,
+}
+
+impl LayoutMode {
+    /// Next layout in the cycle
+    pub fn next(&self) -> Self {
+        match self {
+            LayoutMode::Standard => LayoutMode::WideDisassembly,
+            LayoutMode::WideDisassembly => LayoutMode::Stacked,
+            LayoutMode::Stacked => LayoutMode::Minimal,
+            LayoutMode::Minimal => LayoutMode::Standard,
+        }
+    }
+
+    /// Parse a layout from its settings name (unknown names fall back to standard)
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "wide" => LayoutMode::WideDisassembly,
+            "stacked" => LayoutMode::Stacked,
+            "minimal" => LayoutMode::Minimal,
+            _ => LayoutMode::Standard,
+        }
+    }
+
+    /// Name stored in settings
+    pub fn name(&self) -> &'static str {
+        match self {
+            LayoutMode::Standard => "standard",
+            LayoutMode::WideDisassembly => "wide",
+            LayoutMode::Stacked => "stacked",
+            LayoutMode::Minimal => "minimal",
+        }
+    }
+
+    /// Human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            LayoutMode::Standard => "Standard",
+            LayoutMode::WideDisassembly => "Wide disassembly",
+            LayoutMode::Stacked => "Stacked",
+            LayoutMode::Minimal => "Minimal",
+        }
+    }
+
+    /// Whether a panel is visible in this layout
+    pub fn shows(&self, panel: FocusedPanel) -> bool {
+        match self {
+            LayoutMode::Minimal => !matches!(panel, FocusedPanel::Memory | FocusedPanel::Stack),
+            _ => true,
+        }
+    }
+
+    /// Split the panel area into rects for each panel (hidden panels get an empty rect)
+    fn split(&self, area: Rect) -> DebuggerLayout {
+        match self {
+            LayoutMode::Standard => {
+                let columns = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(area);
+                let left = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(columns[0]);
+                let right = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(columns[1]);
+
+                DebuggerLayout {
+                    disassembly: left[0],
+                    memory: left[1],
+                    registers: right[0],
+                    stack: right[1],
+                    ..Default::default()
+                }
+            }
+            LayoutMode::WideDisassembly => {
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                    .split(area);
+                let bottom = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([
+                        Constraint::Percentage(30),
+                        Constraint::Percentage(45),
+                        Constraint::Percentage(25),
+                    ])
+                    .split(rows[1]);
+
+                DebuggerLayout {
+                    disassembly: rows[0],
+                    registers: bottom[0],
+                    memory: bottom[1],
+                    stack: bottom[2],
+                    ..Default::default()
+                }
+            }
+            LayoutMode::Stacked => {
+                let columns = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                    .split(area);
+                let left = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(columns[0]);
+                let right = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(columns[1]);
+
+                DebuggerLayout {
+                    disassembly: left[0],
+                    registers: left[1],
+                    memory: right[0],
+                    stack: right[1],
+                    ..Default::default()
+                }
+            }
+            LayoutMode::Minimal => {
+                let columns = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+                    .split(area);
+
+                DebuggerLayout {
+                    disassembly: columns[0],
+                    registers: columns[1],
+                    ..Default::default()
+                }
+            }
+        }
+    }
+}
+
+/// Panel area width below which memory and stack collapse into tabs
+const COMPACT_WIDTH: u16 = 100;
+
+/// Panel area height below which memory and stack collapse into tabs
+const COMPACT_HEIGHT: u16 = 24;
+
+/// Split a cramped panel area: memory and stack share one tabbed slot
+/// under the disassembly, registers get the full right column.
+/// Returns the panel rects and the rect for the tab bar.
+fn compact_split(area: Rect) -> (DebuggerLayout, Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(62), Constraint::Percentage(38)])
+        .split(area);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(55),
+            Constraint::Length(1),
+            Constraint::Min(3),
+        ])
+        .split(columns[0]);
+
+    let layout = DebuggerLayout {
+        disassembly: left[0],
+        memory: left[2],
+        stack: left[2],
+        registers: columns[1],
+        ..Default::default()
+    };
+
+    (layout, left[1])
+}
+
+/// Rows of the execution timeline under the disassembly
+const TIMELINE_HEIGHT: u16 = 2;
+
+/// Where everything on the debugger screen goes
+struct ScreenLayout {
+    /// Puzzle tab row, once more than one puzzle is open
+    puzzle_tabs: Option<Rect>,
+    /// Everything below the puzzle tabs
+    area: Rect,
+    /// Area the panels share
+    main: Rect,
+    /// Panel rects
+    layout: DebuggerLayout,
+    /// Memory/stack tab bar on cramped screens
+    tab_bar: Option<Rect>,
+}
+
+fn screen_layout(app: &App, mut area: Rect) -> ScreenLayout {
+    let mut puzzle_tabs = None;
+    if !app.tab_titles().0.is_empty() {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        puzzle_tabs = Some(rows[0]);
+        area = rows[1];
+    }
 
     // Main layout: top section (debugger panels) + bottom (command/status)
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(10),    // Main content
+            Constraint::Min(10),   // Main content
             Constraint::Length(3), // Command input
             Constraint::Length(1), // Status bar
         ])
         .split(area);
 
-    // Left/right split for main content
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(60), // Left: Disassembly + Memory
-            Constraint::Percentage(40), // Right: Registers + Stack
-        ])
-        .split(main_chunks[0]);
+    // Small terminals collapse memory and stack into tabs
+    let compact = app.layout_mode != LayoutMode::Minimal
+        && (main_chunks[0].width < COMPACT_WIDTH || main_chunks[0].height < COMPACT_HEIGHT);
 
-    // Left column: Disassembly on top, Memory below
-    let left_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(60), // Disassembly
-            Constraint::Percentage(40), // Memory
-        ])
-        .split(content_chunks[0]);
-
-    // Right column: Registers on top, Stack below
-    let right_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(50), // Registers
-            Constraint::Percentage(50), // Stack
-        ])
-        .split(content_chunks[1]);
+    let (panels, tab_bar) = if compact {
+        let (panels, tab_bar) = compact_split(main_chunks[0]);
+        (panels, Some(tab_bar))
+    } else {
+        (app.layout_mode.split(main_chunks[0]), None)
+    };
 
-    // Build layout info for tutorial overlay
-    let layout = DebuggerLayout {
-        disassembly: left_chunks[0],
-        registers: right_chunks[0],
-        memory: left_chunks[1],
-        stack: right_chunks[1],
+    // Panel rects for the current layout (also used by the tutorial overlay)
+    let mut layout = DebuggerLayout {
         command: main_chunks[1],
         status: main_chunks[2],
+        ..panels
     };
 
+    // The timeline takes the bottom of the disassembly once something has run
+    let has_timeline = app
+        .debugger
+        .as_ref()
+        .is_some_and(|debugger| !debugger.timeline().is_empty());
+    if has_timeline && layout.disassembly.height > TIMELINE_HEIGHT + 4 {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(TIMELINE_HEIGHT)])
+            .split(layout.disassembly);
+        layout.disassembly = rows[0];
+        layout.timeline = rows[1];
+    }
+
+    ScreenLayout {
+        puzzle_tabs,
+        area,
+        main: main_chunks[0],
+        layout,
+        tab_bar,
+    }
+}
+
+/// Where the execution timeline is drawn on a debugger screen of `area`,
+/// if it's shown (for mouse scrubbing)
+pub fn timeline_area(app: &App, area: Rect) -> Option<Rect> {
+    if app.compare_sides().is_some() {
+        return None;
+    }
+    let timeline = screen_layout(app, area).layout.timeline;
+    (!timeline.is_empty()).then_some(timeline)
+}
+
+/// Width of an on-screen button, borders included
+const TOUCH_BUTTON_WIDTH: u16 = 12;
+
+/// On-screen buttons at the right of the command box, if it has room
+fn touch_buttons(command: Rect) -> Vec<(TouchButton, Rect)> {
+    let total = TOUCH_BUTTON_WIDTH * TouchButton::ALL.len() as u16;
+    if command.width < total + 16 {
+        return Vec::new();
+    }
+    let start = command.right() - total;
+    TouchButton::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, &button)| {
+            let x = start + TOUCH_BUTTON_WIDTH * i as u16;
+            (
+                button,
+                Rect::new(x, command.y, TOUCH_BUTTON_WIDTH, command.height),
+            )
+        })
+        .collect()
+}
+
+/// What a touch at `column`, `row` on a debugger screen of `area` lands on
+pub fn touch_target(app: &App, area: Rect, column: u16, row: u16) -> Option<TouchTarget> {
+    let ScreenLayout {
+        layout, tab_bar, ..
+    } = screen_layout(app, area);
+    let position = Position::new(column, row);
+
+    if app.touch_controls {
+        if let Some((button, _)) = touch_buttons(layout.command)
+            .into_iter()
+            .find(|(_, rect)| rect.contains(position))
+        {
+            return Some(TouchTarget::Button(button));
+        }
+    }
+    if layout.command.contains(position) {
+        return Some(TouchTarget::Panel(FocusedPanel::Command));
+    }
+    if app.compare_sides().is_some() {
+        return None;
+    }
+
+    if layout.timeline.contains(position) {
+        return (row == layout.timeline.y).then_some(TouchTarget::Timeline {
+            column: column - layout.timeline.x,
+            width: layout.timeline.width,
+        });
+    }
+    if layout.disassembly.contains(position) {
+        // Lines start inside the panel border
+        let first = layout.disassembly.y + 1;
+        let last = layout.disassembly.bottom().saturating_sub(1);
+        return Some(if (first..last).contains(&row) {
+            TouchTarget::Disassembly((row - first) as usize)
+        } else {
+            TouchTarget::Panel(FocusedPanel::Disassembly)
+        });
+    }
+    if layout.registers.contains(position) {
+        return Some(TouchTarget::Panel(FocusedPanel::Registers));
+    }
+    if tab_bar.is_some() && layout.memory.contains(position) {
+        return Some(TouchTarget::Panel(app.data_tab));
+    }
+    [
+        (FocusedPanel::Memory, layout.memory),
+        (FocusedPanel::Stack, layout.stack),
+    ]
+    .into_iter()
+    .find(|(_, rect)| rect.contains(position))
+    .map(|(panel, _)| TouchTarget::Panel(panel))
+}
+
+/// Render the debugger screen
+pub fn render_debugger(frame: &mut Frame, app: &App) {
+    let ScreenLayout {
+        puzzle_tabs,
+        area,
+        main,
+        layout,
+        tab_bar,
+    } = screen_layout(app, frame.area());
+    let compact = tab_bar.is_some();
+
+    // Puzzle tabs, once more than one puzzle is open
+    if let Some(tabs_row) = puzzle_tabs {
+        let (titles, active) = app.tab_titles();
+        let tabs = Tabs::new(
+            titles
+                .iter()
+                .enumerate()
+                .map(|(i, title)| format!(" {}: {} ", i + 1, title)),
+        )
+        .select(active)
+        .style(app.theme.muted_style())
+        .highlight_style(app.theme.highlight())
+        .divider("|");
+
+        frame.render_widget(tabs, tabs_row);
+    }
+
+    // Two tabs side by side replace the panels while comparing
+    let compare = app.compare_sides();
+
+    let active_tab = app.active_data_tab();
+    if let (Some(tab_bar), None) = (tab_bar, &compare) {
+        let tabs = Tabs::new(vec![" Memory ", " Stack "])
+            .select(if active_tab == FocusedPanel::Stack {
+                1
+            } else {
+                0
+            })
+            .style(app.theme.muted_style())
+            .highlight_style(app.theme.highlight())
+            .divider("|");
+
+        frame.render_widget(tabs, tab_bar);
+    }
+
     // Render disassembly
-    if let Some(ref debugger) = app.debugger {
+    if let Some((ref left, ref right)) = compare {
+        render_compare(frame, left, right, app.compare_sync, &app.theme, main);
+    } else if let Some(ref debugger) = app.debugger {
+        let idioms = app.idiom_notes();
+        let constants = constants_in_code(&app.disasm_cache);
+        let annotations = app.annotations();
+        let modified = debugger.modified_lines(&app.disasm_cache);
         let disasm_view = DisasmView::new(
             &app.disasm_cache,
             debugger.cpu.eip,
@@ -71,89 +430,341 @@ pub fn render_debugger(frame: &mut Frame, app: &App) {
             &app.syntax_highlighter,
         )
         .selected(app.disasm_selection)
-        .focused(app.focused == FocusedPanel::Disassembly);
+        .idioms(&idioms)
+        .constants(&constants)
+        .bookmarks(&debugger.bookmarks)
+        .annotations(&annotations)
+        .modified(&modified)
+        .flag_hit(app.flag_hit.as_ref())
+        .faulted(matches!(debugger.state, DebuggerState::Error(_)))
+        .focused(app.focused == FocusedPanel::Disassembly)
+        .title(match app.disasm_mode {
+            DisassemblyMode::Linear => " Disassembly ",
+            DisassemblyMode::Recursive => " Disassembly (recursive) ",
+        });
 
-        frame.render_widget(disasm_view, left_chunks[0]);
+        if app.pseudo_open {
+            let halves = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(layout.disassembly);
+            frame.render_widget(disasm_view, halves[0]);
+            render_pseudo_pane(frame, app, &app.pseudo_code(), debugger.cpu.eip, halves[1]);
+        } else {
+            frame.render_widget(disasm_view, layout.disassembly);
+        }
+
+        if !layout.timeline.is_empty() {
+            let timeline = TimelineView::new(debugger.timeline(), debugger.position(), &app.theme);
+            frame.render_widget(timeline, layout.timeline);
+        }
 
         // Render registers
+        let timeline = app.register_timeline();
         let reg_view = RegisterView::new(&debugger.cpu, &app.changed_registers, &app.theme)
-            .focused(app.focused == FocusedPanel::Registers);
+            .focused(app.focused == FocusedPanel::Registers)
+            .flash(app.animations.register_flash.is_active())
+            .timeline(app.selected_register, &timeline)
+            .bits(app.register_bits);
 
-        frame.render_widget(reg_view, right_chunks[0]);
+        // Pinned expressions take the bottom of the registers column
+        let registers_area = if app.pins.is_empty() {
+            layout.registers
+        } else {
+            let height = (app.pins.list().len() as u16 + 2).min(MAX_WATCHES_HEIGHT);
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(12), Constraint::Length(height)])
+                .split(layout.registers);
+            render_watches(frame, app, rows[1]);
+            rows[0]
+        };
+        frame.render_widget(reg_view, registers_area);
 
         // Render memory
-        let mem_data = app.get_memory(app.memory_view_addr, 256);
-        let mem_view = MemoryView::new(mem_data, app.memory_view_addr, &app.theme)
-            .focused(app.focused == FocusedPanel::Memory)
-            .bytes_per_row(8);
+        if app.layout_mode.shows(FocusedPanel::Memory)
+            && (!compact || active_tab == FocusedPanel::Memory)
+        {
+            let mem_data = app.get_memory(app.memory_view_addr, 256);
+            let mem_view = MemoryView::new(mem_data.as_deref(), app.memory_view_addr, &app.theme)
+                .focused(app.focused == FocusedPanel::Memory)
+                .bytes_per_row(8)
+                .memory_map(&debugger.memory)
+                .bookmarks(&debugger.bookmarks);
 
-        frame.render_widget(mem_view, left_chunks[1]);
+            frame.render_widget(mem_view, layout.memory);
+        }
 
         // Render stack
-        let stack_entries = app.get_stack(10);
-        let stack_view = StackView::new(stack_entries, debugger.cpu.regs.esp, &app.theme)
-            .focused(app.focused == FocusedPanel::Stack);
+        if app.layout_mode.shows(FocusedPanel::Stack)
+            && (!compact || active_tab == FocusedPanel::Stack)
+        {
+            let stack_entries = app.get_stack(10);
+            let mut stack_view = StackView::new(stack_entries, debugger.cpu.regs.esp, &app.theme)
+                .focused(app.focused == FocusedPanel::Stack);
+            if let Some(args) = app.call_arguments() {
+                stack_view = stack_view.call_args(args.len());
+            }
+            if app.settings.stack_frames {
+                stack_view = stack_view.frames(debugger.frames());
+                if let Some(frame) = app.collapsing_frame() {
+                    stack_view = stack_view.collapsing(frame);
+                }
+            }
 
-        frame.render_widget(stack_view, right_chunks[1]);
+            frame.render_widget(stack_view, layout.stack);
+        }
     }
 
     // Command input
-    let command_style = if app.focused == FocusedPanel::Command {
-        app.theme.border_focused()
-    } else {
-        app.theme.border_style()
-    };
-
-    let command_block = Block::default()
-        .title(" Command ")
-        .borders(Borders::ALL)
-        .border_style(command_style);
+    let command_block = app
+        .theme
+        .panel_block(" Command ", app.focused == FocusedPanel::Command);
 
     let command_text = format!("> {}", app.command_input);
     let command_para = Paragraph::new(command_text)
         .block(command_block)
         .style(app.theme.normal());
 
-    frame.render_widget(command_para, main_chunks[1]);
+    frame.render_widget(command_para, layout.command);
 
-    // Status bar
-    let status_content = if let Some(ref msg) = app.message {
-        let style = if msg.is_error {
-            app.theme.error_style()
-        } else {
-            app.theme.success_style()
-        };
-        Span::styled(&msg.text, style)
-    } else {
-        Span::styled(
-            " [F5] Run  [F10] Step  [F9] BP  [u] Undo  [Ctrl+Y] Redo  [Tab] Focus  [:] Cmd  [Esc] Menu ",
-            app.theme.muted_style(),
-        )
-    };
-
-    let status_line = Line::from(status_content);
-    let status_para = Paragraph::new(status_line);
+    if app.touch_controls {
+        for (button, rect) in touch_buttons(layout.command) {
+            let label = Paragraph::new(Span::styled(button.label(), app.theme.highlight()))
+                .alignment(Alignment::Center)
+                .block(app.theme.panel_block("", false));
+            frame.render_widget(Clear, rect);
+            frame.render_widget(label, rect);
+        }
+    }
 
-    frame.render_widget(status_para, main_chunks[2]);
+    // Status bar, with the latest message over the command box's bottom edge
+    render_status_bar(frame, app, layout.status);
+    if let Some(ref msg) = app.message {
+        render_toast(frame, app, msg, layout.command);
+    }
 
     // Render tutorial overlay if active
     if let Some(ref tutorial) = app.tutorial {
         if tutorial.active {
             if let Some(step) = tutorial.current() {
-                let overlay = TutorialOverlay::new(
-                    step,
-                    &layout,
-                    &app.theme,
-                    tutorial.progress(),
-                );
+                let overlay = TutorialOverlay::new(step, &layout, &app.theme, tutorial.progress());
                 frame.render_widget(overlay, area);
             }
         }
     }
 
     // Render rewind effect if active
-    if app.rewind_effect.is_active() {
-        let rewind_overlay = RewindOverlay::new(&app.rewind_effect);
+    if app.animations.rewind.is_active() {
+        let rewind_overlay = RewindOverlay::new(&app.animations.rewind);
         frame.render_widget(rewind_overlay, area);
     }
 }
+
+/// Key hints for the focused panel, shown at the right of the status bar
+fn key_hints(panel: FocusedPanel) -> &'static str {
+    match panel {
+        FocusedPanel::Disassembly => {
+            "[F10] Step  [F5] Run  [F9] BP  [Ctrl+B] Mark  [x] Encoding  [Tab] Focus  [:] Cmd"
+        }
+        FocusedPanel::Registers => {
+            "[↑↓] Register  [t] Bits  [f] Follow  [F10] Step  [Tab] Focus  [:] Cmd"
+        }
+        FocusedPanel::Memory => {
+            "[G] Go to  [e] Endian  [T] Decode  [f] Follow  [y] Copy  [Tab] Focus"
+        }
+        FocusedPanel::Stack => "[G] Go to  [f] Follow  [y] Copy  [F10] Step  [Tab] Focus  [:] Cmd",
+        FocusedPanel::Command => "[Enter] Run  [Esc] Back  help: all commands",
+    }
+}
+
+/// Execution state, instructions run, puzzle and save state on the left;
+/// key hints for the focused panel on the right
+fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let mut spans = Vec::new();
+
+    if let Some(ref debugger) = app.debugger {
+        let (mode, style) = match debugger.state {
+            DebuggerState::Ready => ("READY".to_string(), theme.highlight()),
+            DebuggerState::Running => ("RUNNING".to_string(), theme.highlight()),
+            DebuggerState::AtBreakpoint(address) => {
+                (format!("BREAK 0x{:X}", address), theme.warning_style())
+            }
+            DebuggerState::AtWatch => ("WATCH".to_string(), theme.warning_style()),
+            DebuggerState::AtFlagChange(flag) => {
+                (format!("FLAG {}", flag.name()), theme.warning_style())
+            }
+            DebuggerState::Halted => ("HALTED".to_string(), theme.success_style()),
+            DebuggerState::LimitExceeded => ("LIMIT".to_string(), theme.error_style()),
+            DebuggerState::Error(_) => ("ERROR".to_string(), theme.error_style()),
+        };
+        spans.push(Span::styled(
+            format!(" {} ", mode),
+            style.add_modifier(Modifier::REVERSED),
+        ));
+        spans.push(Span::styled(
+            format!(" {} instr", debugger.total_instructions),
+            theme.normal(),
+        ));
+    }
+    if let Some(ref puzzle) = app.puzzle {
+        spans.push(Span::styled(" | ", theme.muted_style()));
+        spans.push(Span::styled(puzzle.metadata.id.clone(), theme.normal()));
+    }
+    if let Some((met, total)) = app.goal_progress() {
+        let style = match met {
+            0 => theme.muted_style(),
+            _ if met == total => theme.success_style(),
+            _ => theme.warning_style(),
+        };
+        spans.push(Span::styled(" | ", theme.muted_style()));
+        spans.push(Span::styled(format!("goal {}/{}", met, total), style));
+    }
+    if app.has_unsaved_progress() {
+        spans.push(Span::styled(" | ", theme.muted_style()));
+        spans.push(Span::styled(
+            format!("{} unsaved", theme.breakpoint_marker()),
+            theme.warning_style(),
+        ));
+    }
+    if let Some(status) = app.time_attack_status() {
+        spans.push(Span::styled(" | ", theme.muted_style()));
+        spans.push(Span::styled(status, theme.highlight()));
+    }
+    if let Some(status) = app.duel_status() {
+        spans.push(Span::styled(" | ", theme.muted_style()));
+        spans.push(Span::styled(status, theme.highlight()));
+    }
+    if let Some(status) = app.ghost_status() {
+        spans.push(Span::styled(" | ", theme.muted_style()));
+        spans.push(Span::styled(status, theme.muted_style()));
+    }
+    if let Some(ref demo) = app.demo {
+        spans.push(Span::styled(" | ", theme.muted_style()));
+        let label = if demo.is_playing() {
+            "DEMO playing"
+        } else {
+            "DEMO"
+        };
+        spans.push(Span::styled(label, theme.highlight()));
+    }
+    let state = Line::from(spans);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(state.width() as u16 + 2),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    frame.render_widget(Paragraph::new(state), chunks[0]);
+    frame.render_widget(
+        Paragraph::new(Span::styled(key_hints(app.focused), theme.muted_style()))
+            .alignment(Alignment::Right),
+        chunks[1],
+    );
+}
+
+/// The latest message, laid over the bottom edge of the command box just
+/// above the status bar
+fn render_toast(frame: &mut Frame, app: &App, msg: &Message, command: Rect) {
+    if command.height < 2 || command.width < 4 {
+        return;
+    }
+    let style = if msg.is_error {
+        app.theme.error_style()
+    } else {
+        app.theme.success_style()
+    };
+    let text = format!(" {} ", msg.text);
+    let width = (text.chars().count() as u16).min(command.width - 2);
+    let area = Rect::new(command.x + 1, command.y + command.height - 1, width, 1);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(Span::styled(text, style)), area);
+}
+
+/// Side pane with approximate C for the basic block under the cursor
+/// Tallest the Watches panel grows before it scrolls off
+const MAX_WATCHES_HEIGHT: u16 = 10;
+
+/// Pinned expressions and their values, changed ones highlighted
+fn render_watches(frame: &mut Frame, app: &App, area: Rect) {
+    let block = app.theme.panel_block(" Watches ", false);
+    let width = app
+        .pins
+        .list()
+        .iter()
+        .map(|pin| pin.text.len())
+        .max()
+        .unwrap_or(0)
+        .min(24);
+
+    let text: Vec<Line> = app
+        .pins
+        .list()
+        .iter()
+        .enumerate()
+        .map(|(i, pin)| {
+            let value = match pin.value {
+                Ok(value) => Span::styled(
+                    format!("0x{:08X} {}", value, value as i32),
+                    if pin.changed {
+                        app.theme.changed()
+                    } else {
+                        app.theme.register_value()
+                    },
+                ),
+                Err(ref e) => Span::styled(e.clone(), app.theme.error_style()),
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", i + 1), app.theme.muted_style()),
+                Span::styled(
+                    format!("{:<width$} ", pin.text, width = width),
+                    app.theme.register_name(),
+                ),
+                value,
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(text).block(block), area);
+}
+
+fn render_pseudo_pane(frame: &mut Frame, app: &App, lines: &[PseudoLine], eip: u32, area: Rect) {
+    let block = app.theme.panel_block(" Pseudo-C (approximate) ", false);
+
+    let mut text: Vec<Line> = lines
+        .iter()
+        .map(|line| {
+            let style = if line.address == eip {
+                app.theme.highlight()
+            } else {
+                app.theme.normal()
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:04X}  ", line.address & 0xFFFF),
+                    app.theme.address(),
+                ),
+                Span::styled(line.text.as_str(), style),
+            ])
+        })
+        .collect();
+    if text.is_empty() {
+        text.push(Line::from(Span::styled(
+            "No code under the cursor",
+            app.theme.muted_style(),
+        )));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "Registers stand in for variables; flags and sizes are simplified.",
+        app.theme.muted_style(),
+    )));
+
+    frame.render_widget(
+        Paragraph::new(text).block(block).wrap(Wrap { trim: false }),
+        area,
+    );
+}
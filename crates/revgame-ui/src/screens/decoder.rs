@@ -0,0 +1,176 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use revgame_core::debugger::Transform;
+
+use crate::theme::Theme;
+
+/// Longest range the decoder previews
+pub const MAX_DECODE_LENGTH: usize = 64;
+
+/// Data decoder: a memory range and the transformation to preview on it
+#[derive(Debug, Clone)]
+pub struct DecoderState {
+    pub address: u32,
+    pub length: usize,
+    /// Transformation as typed, e.g. "xor 5A"
+    pub input: String,
+}
+
+impl DecoderState {
+    pub fn new(address: u32) -> Self {
+        Self {
+            address,
+            length: 16,
+            input: String::new(),
+        }
+    }
+
+    /// Grow or shrink the range, keeping it between 1 and the preview limit
+    pub fn resize(&mut self, delta: i32) {
+        self.length = self
+            .length
+            .saturating_add_signed(delta as isize)
+            .clamp(1, MAX_DECODE_LENGTH);
+    }
+}
+
+/// Render the decoder with the original bytes and the transformed preview
+pub fn render_decoder_overlay(
+    frame: &mut Frame,
+    state: &DecoderState,
+    original: &[u8],
+    result: Result<(Transform, Vec<u8>), String>,
+    theme: &Theme,
+) {
+    let area = centered_rect(80, 70, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Transformation input
+            Constraint::Min(5),    // Original
+            Constraint::Min(5),    // Result
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(format!(
+            " Decode 0x{:08X}, {} bytes ",
+            state.address, state.length
+        ))
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let input = Paragraph::new(format!("Transform: {}", state.input))
+        .style(Style::default().fg(theme.accent));
+
+    frame.render_widget(input, chunks[0]);
+
+    let mut lines = vec![Line::from(Span::styled("Memory", theme.register_name()))];
+    lines.extend(dump(original, state.address, theme));
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let lines = match result {
+        Ok((transform, bytes)) => {
+            let mut lines = vec![Line::from(Span::styled(
+                transform.describe(),
+                theme.register_name(),
+            ))];
+            lines.extend(dump(&bytes, state.address, theme));
+            lines
+        }
+        Err(e) => {
+            let style = if state.input.is_empty() {
+                theme.muted_style()
+            } else {
+                theme.error_style()
+            };
+            vec![Line::from(Span::styled(e, style))]
+        }
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[2]);
+
+    // Help
+    let help = Paragraph::new(" xor <key> | rot13 | base64 | add <n> | sub <n>  [←→] Move  [↑↓] Length  [Enter] Patch  [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Hex and ASCII rows, 16 bytes each
+fn dump<'a>(bytes: &[u8], address: u32, theme: &Theme) -> Vec<Line<'a>> {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{:02X}", b)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if (0x20..0x7F).contains(&b) {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            Line::from(vec![
+                Span::styled(
+                    format!("{:08X}  ", address.wrapping_add(row as u32 * 16)),
+                    theme.address(),
+                ),
+                Span::styled(format!("{:<48}", hex.join(" ")), theme.hex_byte()),
+                Span::styled(ascii, theme.ascii()),
+            ])
+        })
+        .collect()
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resize_clamps() {
+        let mut state = DecoderState::new(0x2000);
+        state.resize(-100);
+        assert_eq!(state.length, 1);
+        state.resize(1000);
+        assert_eq!(state.length, MAX_DECODE_LENGTH);
+    }
+}
@@ -0,0 +1,179 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::game::Checkpoint;
+use revgame_core::reference::{Drill, DrillDeck};
+
+use crate::app::App;
+
+/// Drills screen state
+#[derive(Default)]
+pub struct DrillsState {
+    pub deck: Option<DrillDeck>,
+    /// The drill being answered
+    pub drill: Option<Drill>,
+    pub input: String,
+    /// Verdict on the last answer, and whether it was right
+    pub feedback: Option<(String, bool)>,
+    /// The campaign checkpoint being taken, if this is a quiz
+    pub quiz: Option<CheckpointQuiz>,
+}
+
+/// A campaign checkpoint quiz in progress
+#[derive(Debug, Clone)]
+pub struct CheckpointQuiz {
+    pub checkpoint: Checkpoint,
+    /// Questions answered so far
+    pub asked: u32,
+    pub correct: u32,
+}
+
+impl CheckpointQuiz {
+    pub fn new(checkpoint: Checkpoint) -> Self {
+        Self {
+            checkpoint,
+            asked: 0,
+            correct: 0,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.asked >= self.checkpoint.questions
+    }
+
+    pub fn passed(&self) -> bool {
+        self.correct >= self.checkpoint.pass
+    }
+}
+
+impl DrillsState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the current drill has been answered
+    pub fn answered(&self) -> bool {
+        self.feedback.is_some()
+    }
+}
+
+/// Render the drills screen
+pub fn render_drills(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let state = &app.drills_state;
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(8),    // Drill
+            Constraint::Length(3), // Stats
+            Constraint::Length(1), // Help
+        ])
+        .split(area);
+
+    let title = match state.quiz {
+        Some(ref quiz) => format!("CHECKPOINT: {}", quiz.checkpoint.title.to_uppercase()),
+        None => "INSTRUCTION DRILLS".to_string(),
+    };
+    let title = Paragraph::new(title)
+        .style(theme.highlight())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_set(theme.border_set())
+                .border_style(theme.border_style()),
+        );
+
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines = Vec::new();
+    if let Some(ref drill) = state.drill {
+        lines.push(Line::styled(format!("  {}", drill.text), theme.highlight()));
+        lines.push(Line::default());
+        lines.push(Line::from(vec![
+            Span::styled("  Before: ", theme.muted_style()),
+            Span::styled(
+                format!(
+                    "EAX = 0x{:08X}  EBX = 0x{:08X}  ESP = 0x00002000",
+                    drill.eax, drill.ebx
+                ),
+                theme.normal(),
+            ),
+        ]));
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            format!("  {}", drill.prompt()),
+            theme.normal(),
+        ));
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            format!("  Answer: {}", state.input),
+            Style::default().fg(theme.accent),
+        ));
+    }
+    if let Some((ref text, correct)) = state.feedback {
+        let style = if correct {
+            theme.success_style()
+        } else {
+            theme.error_style()
+        };
+        lines.push(Line::default());
+        lines.push(Line::styled(format!("  {}", text), style));
+    }
+
+    let drill = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_set(theme.border_set())
+            .border_style(theme.border_focused()),
+    );
+
+    frame.render_widget(drill, chunks[1]);
+
+    let stats = &app.game_state.drills;
+    let mut summary = match state.quiz {
+        Some(ref quiz) => format!(
+            "Question {}/{}  Correct: {}  Needed to pass: {}",
+            (quiz.asked + u32::from(!state.answered())).min(quiz.checkpoint.questions),
+            quiz.checkpoint.questions,
+            quiz.correct,
+            quiz.checkpoint.pass
+        ),
+        None => format!(
+            "Accuracy: {}% ({}/{})  Streak: {}  Best: {}",
+            stats.accuracy(),
+            stats.correct,
+            stats.attempted,
+            stats.streak,
+            stats.best_streak
+        ),
+    };
+    if let (None, Some(weakest)) = (&state.quiz, stats.weakest()) {
+        summary.push_str(&format!("  Practice: {}", weakest));
+    }
+    let stats = Paragraph::new(summary)
+        .style(theme.normal())
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP));
+
+    frame.render_widget(stats, chunks[2]);
+
+    let help = if state.quiz.is_some() {
+        " Type your answer  [Enter] Check / Next  [Esc] Give up "
+    } else {
+        " Type your answer  [Enter] Check / Next  [Esc] Menu "
+    };
+    let help = Paragraph::new(help)
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[3]);
+}
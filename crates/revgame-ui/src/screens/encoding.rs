@@ -0,0 +1,123 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::emulator::{FieldKind, InstructionEncoding};
+use revgame_core::reference::Glossary;
+
+use crate::theme::Theme;
+
+/// Style for the bytes of each encoding field
+fn field_style(kind: FieldKind, theme: &Theme) -> Style {
+    match kind {
+        FieldKind::Prefix => theme.warning_style(),
+        FieldKind::Opcode => theme.highlight(),
+        FieldKind::ModRm | FieldKind::Sib => theme.success_style(),
+        FieldKind::Displacement => theme.changed(),
+        FieldKind::Immediate => theme.region("data"),
+    }
+}
+
+/// Render the instruction encoding inspector
+pub fn render_encoding_overlay(frame: &mut Frame, encoding: &InstructionEncoding, theme: &Theme) {
+    let area = centered_rect(80, 60, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Instruction
+            Constraint::Min(3),    // Fields
+            Constraint::Length(2), // NOP advice
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Instruction Encoding ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    // Address, bytes colored by field, and the instruction text
+    let mut header = vec![Span::styled(
+        format!("0x{:08X}  ", encoding.address),
+        theme.address(),
+    )];
+    for field in &encoding.fields {
+        let hex: Vec<String> = field.bytes.iter().map(|b| format!("{:02X}", b)).collect();
+        header.push(Span::styled(
+            format!("{} ", hex.join(" ")),
+            field_style(field.kind, theme),
+        ));
+    }
+    header.push(Span::styled(format!(" {}", encoding.text), theme.normal()));
+
+    frame.render_widget(Paragraph::new(Line::from(header)), chunks[0]);
+
+    // One line per field
+    let lines: Vec<Line> = encoding
+        .fields
+        .iter()
+        .map(|field| {
+            let hex: Vec<String> = field.bytes.iter().map(|b| format!("{:02X}", b)).collect();
+            Line::from(vec![
+                Span::styled(format!("{:<13}", field.kind.label()), theme.register_name()),
+                Span::styled(
+                    format!("{:<12}", hex.join(" ")),
+                    field_style(field.kind, theme),
+                ),
+                Span::styled(field.explanation.clone(), theme.normal()),
+            ])
+        })
+        .collect();
+
+    let fields = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(fields, chunks[1]);
+
+    let advice = Paragraph::new(encoding.nop_advice())
+        .style(theme.muted_style())
+        .wrap(Wrap { trim: false });
+    frame.render_widget(advice, chunks[2]);
+
+    // Help, linking the first concept behind the instruction
+    let mnemonic = encoding.text.split_whitespace().next().unwrap_or_default();
+    let help = match Glossary::new().for_instruction(mnemonic).first() {
+        Some(concept) => format!(" [g] Read about {}  [Esc] Close ", concept.title),
+        None => " [Esc] Close ".to_string(),
+    };
+    let help = Paragraph::new(help)
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,232 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::theme::Theme;
+
+/// Bytes shown when there is no program loaded (MOV DWORD [x], 0x12345678)
+pub const SAMPLE_BYTES: [u8; 4] = [0x78, 0x56, 0x34, 0x12];
+
+/// How the dword's bytes are read as a number
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Interpretation {
+    /// Unsigned, the way the CPU reads it
+    #[default]
+    Unsigned,
+    /// Signed two's complement
+    Signed,
+    /// Two 16-bit words
+    Words,
+    /// Four characters
+    Text,
+}
+
+impl Interpretation {
+    pub fn next(&self) -> Self {
+        match self {
+            Interpretation::Unsigned => Interpretation::Signed,
+            Interpretation::Signed => Interpretation::Words,
+            Interpretation::Words => Interpretation::Text,
+            Interpretation::Text => Interpretation::Unsigned,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Interpretation::Unsigned => "unsigned dword",
+            Interpretation::Signed => "signed dword",
+            Interpretation::Words => "two words",
+            Interpretation::Text => "characters",
+        }
+    }
+
+    /// Read bytes in memory order with this interpretation
+    pub fn format(&self, bytes: [u8; 4], little_endian: bool) -> String {
+        let value = if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        };
+        match self {
+            Interpretation::Unsigned => format!("0x{:08X} = {}", value, value),
+            Interpretation::Signed => format!("0x{:08X} = {}", value, value as i32),
+            Interpretation::Words => {
+                let word = |pair: [u8; 2]| {
+                    if little_endian {
+                        u16::from_le_bytes(pair)
+                    } else {
+                        u16::from_be_bytes(pair)
+                    }
+                };
+                format!(
+                    "0x{:04X} 0x{:04X}",
+                    word([bytes[0], bytes[1]]),
+                    word([bytes[2], bytes[3]])
+                )
+            }
+            Interpretation::Text => bytes
+                .iter()
+                .map(|&b| {
+                    if (0x20..0x7F).contains(&b) {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .map(|c| format!("'{}'", c))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+/// Endianness visualizer state
+#[derive(Debug, Clone, Default)]
+pub struct EndianState {
+    /// Address of the first byte
+    pub address: u32,
+    /// Bytes at the address, in memory order
+    pub bytes: [u8; 4],
+    pub interpretation: Interpretation,
+    /// Showing the sample instead of program memory
+    pub sample: bool,
+}
+
+/// Render the endianness visualizer
+pub fn render_endian_overlay(frame: &mut Frame, state: &EndianState, theme: &Theme) {
+    let area = centered_rect(70, 55, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // Bytes with their addresses
+            Constraint::Min(4),    // Readings
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let title = if state.sample {
+        " Endianness (example) ".to_string()
+    } else {
+        format!(" Endianness at 0x{:08X} ", state.address)
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    // Memory order: one column per byte, lowest address first
+    let mut addresses = vec![Span::styled("address   ", theme.muted_style())];
+    let mut bytes = vec![Span::styled("memory    ", theme.muted_style())];
+    let mut weights = vec![Span::styled("weight    ", theme.muted_style())];
+    for (i, byte) in state.bytes.iter().enumerate() {
+        addresses.push(Span::styled(format!("+{:<6}", i), theme.address()));
+        bytes.push(Span::styled(format!("{:02X}     ", byte), theme.hex_byte()));
+        weights.push(Span::styled(
+            format!("x{:<6}", format!("2^{}", i * 8)),
+            theme.muted_style(),
+        ));
+    }
+    let order = Paragraph::new(vec![
+        Line::from(addresses),
+        Line::from(bytes),
+        Line::from(weights),
+    ]);
+    frame.render_widget(order, chunks[0]);
+
+    // The same bytes read both ways
+    let interpretation = state.interpretation;
+    let readings = vec![
+        Line::from(vec![
+            Span::styled("Little-endian (x86): ", theme.highlight()),
+            Span::styled(interpretation.format(state.bytes, true), theme.changed()),
+        ]),
+        Line::from(vec![
+            Span::styled("Read left to right:  ", theme.register_name()),
+            Span::styled(interpretation.format(state.bytes, false), theme.normal()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "As {}. The lowest address holds the least significant byte, \
+                 so the value reads from the right.",
+                interpretation.label()
+            ),
+            theme.muted_style(),
+        )),
+    ];
+    frame.render_widget(
+        Paragraph::new(readings).wrap(Wrap { trim: false }),
+        chunks[1],
+    );
+
+    // Help
+    let help = if state.sample {
+        " [t] Interpretation  [Esc] Close "
+    } else {
+        " [←→] Move a byte  [t] Interpretation  [Esc] Close "
+    };
+    let help = Paragraph::new(help)
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpretations() {
+        let bytes = SAMPLE_BYTES;
+        assert_eq!(
+            Interpretation::Unsigned.format(bytes, true),
+            "0x12345678 = 305419896"
+        );
+        assert_eq!(
+            Interpretation::Unsigned.format(bytes, false),
+            "0x78563412 = 2018915346"
+        );
+        assert_eq!(
+            Interpretation::Signed.format([0xFE, 0xFF, 0xFF, 0xFF], true),
+            "0xFFFFFFFE = -2"
+        );
+        assert_eq!(Interpretation::Words.format(bytes, true), "0x5678 0x1234");
+        assert_eq!(
+            Interpretation::Text.format(*b"AB\0C", true),
+            "'A' 'B' '.' 'C'"
+        );
+        assert_eq!(Interpretation::Text.next(), Interpretation::Unsigned);
+    }
+}
@@ -0,0 +1,183 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Clear, Paragraph, Sparkline},
+    Frame,
+};
+
+use revgame_core::debugger::{describe_entropy, window_entropy, ByteStats};
+
+use crate::theme::Theme;
+
+/// Bytes per entropy window in the strip under the histogram
+pub const ENTROPY_WINDOW: usize = 64;
+
+/// A named stretch of memory to analyze
+#[derive(Debug, Clone)]
+pub struct EntropyRegion {
+    pub name: String,
+    pub start: u32,
+    pub bytes: Vec<u8>,
+}
+
+/// Entropy overlay selection
+#[derive(Debug, Clone, Default)]
+pub struct EntropyState {
+    pub selected: usize,
+}
+
+impl EntropyState {
+    pub fn select_next(&mut self, count: usize) {
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_prev(&mut self, count: usize) {
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+}
+
+/// Render per-region entropy with the selected region's byte histogram
+pub fn render_entropy_overlay(
+    frame: &mut Frame,
+    state: &EntropyState,
+    regions: &[EntropyRegion],
+    theme: &Theme,
+) {
+    let area = centered_rect(80, 80, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(regions.len().clamp(1, 6) as u16), // Regions
+            Constraint::Length(2),                                // Summary
+            Constraint::Min(6),                                   // Histogram
+            Constraint::Length(3),                                // Window entropy
+            Constraint::Length(1),                                // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Entropy ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let lines: Vec<Line> = regions
+        .iter()
+        .enumerate()
+        .map(|(i, region)| {
+            let entropy = ByteStats::of(&region.bytes).entropy();
+            let style = if i == state.selected {
+                theme.selected()
+            } else {
+                theme.normal()
+            };
+            Line::from(vec![
+                Span::styled(format!("{:<8}", region.name), style),
+                Span::styled(
+                    format!("0x{:08X} {:>6} bytes  ", region.start, region.bytes.len()),
+                    theme.address(),
+                ),
+                Span::styled(
+                    format!("{:.2} bits/byte  ", entropy),
+                    theme.register_value(),
+                ),
+                Span::styled(describe_entropy(entropy), theme.muted_style()),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let Some(region) = regions.get(state.selected) else {
+        frame.render_widget(
+            Paragraph::new("No memory to analyze").style(theme.muted_style()),
+            chunks[1],
+        );
+        return;
+    };
+
+    // Summary of the selected region
+    let stats = ByteStats::of(&region.bytes);
+    let common: Vec<String> = stats
+        .most_common(4)
+        .into_iter()
+        .map(|(byte, count)| format!("{:02X} x{}", byte, count))
+        .collect();
+    let summary = Line::from(vec![
+        Span::styled(
+            format!("{} distinct values, most common: ", stats.distinct()),
+            theme.muted_style(),
+        ),
+        Span::styled(common.join(", "), theme.hex_byte()),
+    ]);
+    frame.render_widget(Paragraph::new(summary), chunks[1]);
+
+    // Histogram: byte values grouped in sixteens (00-0F, 10-1F, ...)
+    let bars: Vec<Bar> = stats
+        .buckets(16)
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            Bar::default()
+                .value(count)
+                .label(Line::from(format!("{:X}x", i)))
+                .text_value(String::new())
+        })
+        .collect();
+    let chart = BarChart::default()
+        .block(Block::default().title("Byte values"))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(3)
+        .bar_gap(1)
+        .bar_style(theme.highlight());
+    frame.render_widget(chart, chunks[2]);
+
+    // Entropy of each window across the region (0 to 8 bits per byte)
+    let windows: Vec<u64> = window_entropy(&region.bytes, ENTROPY_WINDOW)
+        .into_iter()
+        .map(|entropy| (entropy * 100.0) as u64)
+        .collect();
+    let strip = Sparkline::default()
+        .block(Block::default().title(format!("Entropy per {} bytes", ENTROPY_WINDOW)))
+        .data(&windows)
+        .max(800)
+        .style(theme.changed());
+    frame.render_widget(strip, chunks[3]);
+
+    // Help
+    let help = Paragraph::new(" Code sits around 5-6 bits/byte; packed or encrypted data near 8  [↑↓] Region  [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[4]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
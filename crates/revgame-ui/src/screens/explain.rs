@@ -0,0 +1,72 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::theme::Theme;
+
+/// Render the "explain the last run" overlay
+pub fn render_explain_overlay(frame: &mut Frame, lines: &[String], theme: &Theme) {
+    let area = centered_rect(70, 60, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),    // Summary
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" What Happened ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    // One bullet per sentence
+    let text: Vec<Line> = lines
+        .iter()
+        .map(|line| Line::from(format!("• {}", line)))
+        .collect();
+
+    let summary = Paragraph::new(text)
+        .style(theme.normal())
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(summary, chunks[0]);
+
+    // Help
+    let help = Paragraph::new(" [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,134 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use revgame_core::debugger::{ValueHit, ValueLocation};
+
+use crate::theme::Theme;
+
+/// Stack slots above ESP reported as stack hits rather than plain memory
+pub const FIND_STACK_SLOTS: u32 = 16;
+
+/// Hits of a `find` command, snapshotted when it ran
+#[derive(Debug, Clone)]
+pub struct FindState {
+    pub value: u32,
+    pub hits: Vec<ValueHit>,
+    pub selected: usize,
+}
+
+impl FindState {
+    pub fn new(value: u32, hits: Vec<ValueHit>) -> Self {
+        Self {
+            value,
+            hits,
+            selected: 0,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.hits.is_empty() {
+            self.selected = (self.selected + 1) % self.hits.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.hits.is_empty() {
+            self.selected = (self.selected + self.hits.len() - 1) % self.hits.len();
+        }
+    }
+}
+
+/// Render every register, stack slot and memory address holding the value
+pub fn render_find_overlay(frame: &mut Frame, state: &FindState, theme: &Theme) {
+    let area = centered_rect(60, 60, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Summary
+            Constraint::Min(3),    // Hits
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(format!(" Find 0x{:08X} ", state.value))
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let summary = match state.hits.len() {
+        0 => "Not in any register, on the stack or in memory".to_string(),
+        1 => "1 hit".to_string(),
+        n => format!("{} hits", n),
+    };
+    frame.render_widget(
+        Paragraph::new(summary).style(theme.muted_style()),
+        chunks[0],
+    );
+
+    // Keep the selection in view
+    let visible = chunks[1].height as usize;
+    let first = state.selected.saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = state
+        .hits
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .map(|(i, hit)| {
+            let style = if i == state.selected {
+                theme.selected()
+            } else {
+                theme.normal()
+            };
+            let kind = match hit.location {
+                ValueLocation::Register(_) => "register",
+                ValueLocation::Stack { .. } => "stack",
+                ValueLocation::Memory { .. } => "memory",
+            };
+            Line::from(vec![
+                Span::styled(format!("{:<9}", kind), theme.muted_style()),
+                Span::styled(hit.describe(), style),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    // Help
+    let help = Paragraph::new(" [↑↓] Select  [Enter] Go to  [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
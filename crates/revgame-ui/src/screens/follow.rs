@@ -0,0 +1,122 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::debugger::{ChainEnd, PointerChain};
+
+use crate::theme::Theme;
+
+/// A pointer chain being shown, and where it started
+#[derive(Debug, Clone)]
+pub struct FollowState {
+    /// Register, stack slot or expression the first pointer came from
+    pub origin: String,
+    pub chain: PointerChain,
+}
+
+/// Render the breadcrumbs of a followed pointer, one line per dereference
+pub fn render_follow_overlay(frame: &mut Frame, state: &FollowState, theme: &Theme) {
+    let area = centered_rect(70, 50, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Breadcrumbs
+            Constraint::Min(2),    // Hops
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Follow Pointer ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let arrow = if theme.ascii { " -> " } else { " → " };
+    let mut crumbs = Vec::new();
+    for (i, crumb) in state
+        .chain
+        .breadcrumbs(&state.origin)
+        .into_iter()
+        .enumerate()
+    {
+        if i > 0 {
+            crumbs.push(Span::styled(arrow, theme.muted_style()));
+        }
+        let style = if i == 0 {
+            theme.register_name()
+        } else {
+            theme.address()
+        };
+        crumbs.push(Span::styled(crumb, style));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(crumbs)).wrap(Wrap { trim: true }),
+        chunks[0],
+    );
+
+    // What each dereference read
+    let addresses = &state.chain.addresses;
+    let mut lines: Vec<Line> = addresses
+        .iter()
+        .zip(addresses.iter().skip(1))
+        .map(|(address, next)| {
+            Line::from(vec![
+                Span::styled(format!("[0x{:08X}]", address), theme.address()),
+                Span::styled(" holds a pointer to ", theme.muted_style()),
+                Span::styled(format!("0x{:08X}", next), theme.address()),
+            ])
+        })
+        .collect();
+    if let Some(last) = addresses.last() {
+        let (what, value) = match &state.chain.end {
+            ChainEnd::String(text) => (" holds the string ", format!("{:?}", text)),
+            ChainEnd::Value(value) => (" holds the value ", format!("0x{:08X} ({})", value, value)),
+            ChainEnd::Cycle(address) => (" points back to ", format!("0x{:08X}, a cycle", address)),
+            ChainEnd::TooDeep => (" points further; stopped following", String::new()),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("[0x{:08X}]", last), theme.address()),
+            Span::styled(what, theme.muted_style()),
+            Span::styled(value, theme.highlight()),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    // Help
+    let help = Paragraph::new(" [Enter] Show in memory  [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,103 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::FocusedPanel;
+use crate::theme::Theme;
+
+/// Go-to-address dialog state
+#[derive(Default)]
+pub struct GotoState {
+    pub input: String,
+}
+
+impl GotoState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.input.clear();
+    }
+}
+
+/// Render the go-to-address dialog for the given target panel
+pub fn render_goto_dialog(
+    frame: &mut Frame,
+    state: &GotoState,
+    target: FocusedPanel,
+    theme: &Theme,
+) {
+    let area = centered_rect(50, 25, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Target
+            Constraint::Length(2), // Input
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Go to Address ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    // Target panel
+    let target_name = match target {
+        FocusedPanel::Disassembly => "Disassembly",
+        FocusedPanel::Stack => "Stack",
+        _ => "Memory",
+    };
+
+    let target_para = Paragraph::new(format!("Panel: {}", target_name))
+        .style(theme.normal())
+        .alignment(Alignment::Left);
+
+    frame.render_widget(target_para, chunks[0]);
+
+    // Input
+    let input_para = Paragraph::new(format!("Address: {}", state.input))
+        .style(Style::default().fg(theme.accent))
+        .alignment(Alignment::Left);
+
+    frame.render_widget(input_para, chunks[1]);
+
+    // Help
+    let help = Paragraph::new(" hex, register or bookmark  [Enter] Go  [Esc] Cancel ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
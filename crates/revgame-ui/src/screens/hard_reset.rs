@@ -0,0 +1,84 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::theme::Theme;
+
+/// Render the confirmation for a hard reset that discards `patches` patched runs of bytes
+pub fn render_hard_reset_dialog(frame: &mut Frame, patches: usize, theme: &Theme) {
+    let area = centered_rect(50, 30, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),    // Consequences
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Hard Reset? ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.warning_style());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let lines = vec![
+        Line::styled(
+            "Back to the puzzle exactly as it was loaded.",
+            theme.normal(),
+        ),
+        Line::from(""),
+        Line::styled(
+            format!(
+                "- {} patched location{} will be discarded",
+                patches,
+                if patches == 1 { "" } else { "s" }
+            ),
+            theme.warning_style(),
+        ),
+        Line::styled("- Undo history is cleared", theme.warning_style()),
+        Line::from(""),
+        Line::styled(
+            "F4 restarts the program and keeps your patches.",
+            theme.muted_style(),
+        ),
+    ];
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), chunks[0]);
+
+    // Help
+    let help = Paragraph::new(" [y] Discard patches  [n/Esc] Cancel ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,96 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::theme::Theme;
+
+/// Program input dialog state
+#[derive(Default)]
+pub struct InputState {
+    pub text: String,
+}
+
+impl InputState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
+}
+
+/// Render the dialog for the input the next run uses
+pub fn render_input_dialog(
+    frame: &mut Frame,
+    state: &InputState,
+    placement: Option<String>,
+    theme: &Theme,
+) {
+    let area = centered_rect(60, 25, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Where the input goes
+            Constraint::Length(2), // Input
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Program Input ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let target = placement.unwrap_or_else(|| "Read from stdin with INT".to_string());
+    let target_para = Paragraph::new(target)
+        .style(theme.normal())
+        .alignment(Alignment::Left);
+
+    frame.render_widget(target_para, chunks[0]);
+
+    // Input
+    let input_para = Paragraph::new(format!("Input: {}", state.text))
+        .style(Style::default().fg(theme.accent))
+        .alignment(Alignment::Left);
+
+    frame.render_widget(input_para, chunks[1]);
+
+    // Help
+    let help = Paragraph::new(" \\n \\xNN escapes  [Enter] Restart with input  [Esc] Cancel ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,234 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+/// An entry on the main menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MainMenuItem {
+    #[default]
+    Continue,
+    Tutorial,
+    QuickStart,
+    PuzzleSelect,
+    Drills,
+    TimeAttack,
+    Achievements,
+    SkillTree,
+    Reference,
+    Settings,
+    Quit,
+}
+
+impl MainMenuItem {
+    /// All items in display order
+    pub const ALL: [MainMenuItem; 11] = [
+        MainMenuItem::Continue,
+        MainMenuItem::Tutorial,
+        MainMenuItem::QuickStart,
+        MainMenuItem::PuzzleSelect,
+        MainMenuItem::Drills,
+        MainMenuItem::TimeAttack,
+        MainMenuItem::Achievements,
+        MainMenuItem::SkillTree,
+        MainMenuItem::Reference,
+        MainMenuItem::Settings,
+        MainMenuItem::Quit,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MainMenuItem::Continue => "Continue",
+            MainMenuItem::Tutorial => "Start Tutorial (with walkthrough)",
+            MainMenuItem::QuickStart => "Quick Start (skip tutorial)",
+            MainMenuItem::PuzzleSelect => "Puzzle Select",
+            MainMenuItem::Drills => "Instruction Drills",
+            MainMenuItem::TimeAttack => "Time Attack",
+            MainMenuItem::Achievements => "Achievements",
+            MainMenuItem::SkillTree => "Skill Tree",
+            MainMenuItem::Reference => "x86 Reference Manual",
+            MainMenuItem::Settings => "Settings",
+            MainMenuItem::Quit => "Quit",
+        }
+    }
+
+    /// Key that activates this item directly
+    pub fn hotkey(&self) -> char {
+        match self {
+            MainMenuItem::Continue => 'C',
+            MainMenuItem::Tutorial => '1',
+            MainMenuItem::QuickStart => '2',
+            MainMenuItem::PuzzleSelect => '3',
+            MainMenuItem::Drills => 'D',
+            MainMenuItem::TimeAttack => 'T',
+            MainMenuItem::Achievements => 'A',
+            MainMenuItem::SkillTree => 'K',
+            MainMenuItem::Reference => 'R',
+            MainMenuItem::Settings => 'S',
+            MainMenuItem::Quit => 'Q',
+        }
+    }
+
+    /// Item for a hotkey, ignoring case
+    pub fn from_hotkey(key: char) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|item| item.hotkey().eq_ignore_ascii_case(&key))
+    }
+}
+
+/// Main menu state. The selection is kept as an item rather than an index
+/// so it survives entries appearing or disappearing between visits.
+#[derive(Default)]
+pub struct MainMenuState {
+    pub selected: MainMenuItem,
+}
+
+impl MainMenuState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Position of the selection among the items shown, or the first item
+    /// when the selected one isn't shown
+    pub fn selected_index(&self, items: &[MainMenuItem]) -> usize {
+        items
+            .iter()
+            .position(|item| *item == self.selected)
+            .unwrap_or(0)
+    }
+
+    pub fn navigate_up(&mut self, items: &[MainMenuItem]) {
+        let index = self.selected_index(items);
+        if let Some(&item) = items.get(index.saturating_sub(1)) {
+            self.selected = item;
+        }
+    }
+
+    pub fn navigate_down(&mut self, items: &[MainMenuItem]) {
+        let index = self.selected_index(items);
+        if let Some(&item) = items.get((index + 1).min(items.len().saturating_sub(1))) {
+            self.selected = item;
+        }
+    }
+
+    /// The selected item, falling back to the first one shown
+    pub fn selected_item(&self, items: &[MainMenuItem]) -> Option<MainMenuItem> {
+        items.get(self.selected_index(items)).copied()
+    }
+}
+
+/// Render the main menu: title art, menu items and progress
+pub fn render_main_menu(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(8), // Title
+            Constraint::Min(10),   // Menu
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    // Title
+    let title = r#"
+  ██████╗ ███████╗██╗   ██╗ ██████╗  █████╗ ███╗   ███╗███████╗
+  ██╔══██╗██╔════╝██║   ██║██╔════╝ ██╔══██╗████╗ ████║██╔════╝
+  ██████╔╝█████╗  ██║   ██║██║  ███╗███████║██╔████╔██║█████╗
+  ██╔══██╗██╔══╝  ╚██╗ ██╔╝██║   ██║██╔══██║██║╚██╔╝██║██╔══╝
+  ██║  ██║███████╗ ╚████╔╝ ╚██████╔╝██║  ██║██║ ╚═╝ ██║███████╗
+  ╚═╝  ╚═╝╚══════╝  ╚═══╝   ╚═════╝ ╚═╝  ╚═╝╚═╝     ╚═╝╚══════╝
+"#;
+
+    let title_para = Paragraph::new(title)
+        .style(
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+
+    frame.render_widget(title_para, chunks[0]);
+
+    // Menu items
+    let items = app.main_menu_items();
+    let menu_items: Vec<ListItem> = items
+        .iter()
+        .map(|item| match item {
+            MainMenuItem::Continue => {
+                let title = app.continue_title().unwrap_or_default();
+                ListItem::new(format!("  [{}] {}: {}", item.hotkey(), item.label(), title))
+            }
+            _ => ListItem::new(format!("  [{}] {}", item.hotkey(), item.label())),
+        })
+        .collect();
+
+    let menu = List::new(menu_items)
+        .block(
+            Block::default()
+                .title(match app.settings.profile_name.as_str() {
+                    "" => " Menu ".to_string(),
+                    name => format!(" Menu: welcome, {} ", name),
+                })
+                .title_bottom(format!(" {} ", app.progress_summary()))
+                .borders(Borders::ALL)
+                .border_style(app.theme.border_style()),
+        )
+        .style(app.theme.normal())
+        .highlight_style(app.theme.selected());
+
+    let mut menu_state =
+        ListState::default().with_selected(Some(app.main_menu_state.selected_index(&items)));
+    frame.render_stateful_widget(menu, chunks[1], &mut menu_state);
+
+    // Footer
+    let footer = Paragraph::new("Learn reverse engineering through interactive puzzles")
+        .style(app.theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(footer, chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_navigation_skips_hidden_items() {
+        let without_continue = &MainMenuItem::ALL[1..];
+        let mut state = MainMenuState::new();
+
+        // Continue isn't shown, so the first shown item is selected
+        assert_eq!(
+            state.selected_item(without_continue),
+            Some(MainMenuItem::Tutorial)
+        );
+        state.navigate_down(without_continue);
+        assert_eq!(state.selected, MainMenuItem::QuickStart);
+        state.navigate_up(without_continue);
+        state.navigate_up(without_continue);
+        assert_eq!(state.selected, MainMenuItem::Tutorial);
+
+        // The selection stays on its item when another entry appears
+        assert_eq!(state.selected_index(&MainMenuItem::ALL), 1);
+
+        state.selected = MainMenuItem::Quit;
+        state.navigate_down(&MainMenuItem::ALL);
+        assert_eq!(state.selected, MainMenuItem::Quit);
+    }
+
+    #[test]
+    fn test_hotkeys() {
+        assert_eq!(MainMenuItem::from_hotkey('c'), Some(MainMenuItem::Continue));
+        assert_eq!(
+            MainMenuItem::from_hotkey('3'),
+            Some(MainMenuItem::PuzzleSelect)
+        );
+        assert_eq!(MainMenuItem::from_hotkey('z'), None);
+    }
+}
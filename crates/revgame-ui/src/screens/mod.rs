@@ -1,13 +1,78 @@
-mod debugger;
 mod achievements;
-mod reference;
-mod search;
+mod ascii;
 mod bookmarks;
+mod briefing;
+mod compare;
+mod console;
+mod constants;
+mod converter;
+mod debugger;
+mod decoder;
+mod drills;
+mod encoding;
+mod endian;
+mod entropy;
+mod explain;
+mod find;
+mod follow;
+mod goto;
+mod hard_reset;
+mod input;
+mod main_menu;
+mod objectives;
+mod onboarding;
+mod plugin_panel;
+mod predict;
+mod puzzle_complete;
 mod puzzle_select;
+mod reference;
+mod reveal;
+mod screen;
+mod search;
+mod settings;
+mod skills;
+mod solution;
+mod state_diff;
+mod too_small;
+mod trace_diff;
 
-pub use debugger::render_debugger;
 pub use achievements::render_achievements;
-pub use reference::{render_reference, ReferenceState, ReferenceViewMode};
-pub use search::{render_search_dialog, SearchState, SearchMode};
+pub use ascii::{render_ascii_overlay, AsciiTableState};
 pub use bookmarks::{render_bookmarks_dialog, BookmarksViewState};
+pub use briefing::render_briefing;
+pub use compare::{render_compare, CompareSide};
+pub use console::render_console;
+pub use constants::{render_constants_overlay, ConstantsState};
+pub use converter::{render_converter_overlay, ConverterState};
+pub use debugger::{render_debugger, timeline_area, touch_target, LayoutMode};
+pub use decoder::{render_decoder_overlay, DecoderState, MAX_DECODE_LENGTH};
+pub use drills::{render_drills, CheckpointQuiz, DrillsState};
+pub use encoding::render_encoding_overlay;
+pub use endian::{render_endian_overlay, EndianState, Interpretation, SAMPLE_BYTES};
+pub use entropy::{render_entropy_overlay, EntropyRegion, EntropyState, ENTROPY_WINDOW};
+pub use explain::render_explain_overlay;
+pub use find::{render_find_overlay, FindState, FIND_STACK_SLOTS};
+pub use follow::{render_follow_overlay, FollowState};
+pub use goto::{render_goto_dialog, GotoState};
+pub use hard_reset::render_hard_reset_dialog;
+pub use input::{render_input_dialog, InputState};
+pub use main_menu::{render_main_menu, MainMenuItem, MainMenuState};
+pub use objectives::render_objectives_overlay;
+pub use onboarding::{render_onboarding, OnboardingState, OnboardingStep};
+pub use plugin_panel::render_plugin_panel;
+pub use predict::{render_predict_dialog, PredictState};
+pub use puzzle_complete::render_puzzle_complete;
 pub use puzzle_select::{render_puzzle_select, PuzzleSelectState, SelectViewMode};
+pub use reference::{render_reference, ReferenceState, ReferenceViewMode};
+pub use reveal::render_reveal_dialog;
+pub use screen::render_screen;
+pub use search::{
+    render_search_dialog, SearchMode, SearchScope, SearchState, RESULTS_PER_PAGE,
+    SEARCH_RESULT_LIMIT,
+};
+pub use settings::{render_settings, SettingsItem, SettingsState};
+pub use skills::{render_skill_tree, SkillTreeState};
+pub use solution::{render_solution_overlay, SolutionState};
+pub use state_diff::render_state_diff_overlay;
+pub use too_small::{is_too_small, render_too_small, MIN_HEIGHT, MIN_WIDTH};
+pub use trace_diff::render_trace_diff_overlay;
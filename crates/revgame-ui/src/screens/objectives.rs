@@ -0,0 +1,132 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::puzzle::ValidationTrigger;
+
+use crate::app::App;
+
+/// Render the overlay with the task, its goal and progress so far
+pub fn render_objectives_overlay(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let Some(ref puzzle) = app.puzzle else {
+        return;
+    };
+    let area = centered_rect(70, 60, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),    // Objectives
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(format!(" Objectives: {} ", puzzle.metadata.title))
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let mut lines = vec![
+        Line::styled(puzzle.description.brief.clone(), theme.highlight()),
+        Line::styled(
+            puzzle.description.detailed.trim().to_string(),
+            theme.normal(),
+        ),
+        Line::default(),
+        Line::styled("Goal", theme.register_name()),
+    ];
+
+    for objective in app.objectives() {
+        let (mark, style) = if objective.met {
+            ("✓", theme.success_style())
+        } else {
+            ("✗", theme.muted_style())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", mark), style),
+            Span::styled(objective.description, theme.normal()),
+        ]));
+    }
+    if let Ok(trigger @ (ValidationTrigger::Address(_) | ValidationTrigger::Command)) =
+        puzzle.validation.trigger()
+    {
+        lines.push(Line::styled(
+            format!("  {}", trigger.describe()),
+            theme.muted_style(),
+        ));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled("Progress", theme.register_name()));
+    lines.push(Line::styled(
+        format!(
+            "  Hints used: {}/{}",
+            app.hint_level.min(puzzle.hints.hint_count()),
+            puzzle.hints.hint_count()
+        ),
+        theme.normal(),
+    ));
+    lines.push(Line::styled(
+        format!(
+            "  Assist: {} (worth {} points)",
+            app.game_state.puzzle_assist.label(),
+            app.game_state.puzzle_score(puzzle.metadata.difficulty)
+        ),
+        theme.normal(),
+    ));
+    if let Some(ref debugger) = app.debugger {
+        lines.push(Line::styled(
+            format!("  Patches: {}", debugger.undo_count()),
+            theme.normal(),
+        ));
+        let status = if debugger.cpu.halted { ", halted" } else { "" };
+        lines.push(Line::styled(
+            format!(
+                "  Instructions this run: {}{}",
+                debugger.instructions_executed, status
+            ),
+            theme.normal(),
+        ));
+    }
+
+    let para = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(para, chunks[0]);
+
+    // Help
+    let help = Paragraph::new(" [b] Briefing  [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,252 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::game::Experience;
+
+use crate::theme::Theme;
+
+/// Longest profile name accepted
+pub const MAX_NAME_LEN: usize = 24;
+
+/// A question asked on first launch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStep {
+    Name,
+    Theme,
+    Experience,
+}
+
+/// First-run onboarding state
+pub struct OnboardingState {
+    pub step: OnboardingStep,
+    pub name: String,
+    /// Index into `Theme::NAMES`
+    pub theme: usize,
+    /// Index into `Experience::ALL`
+    pub experience: usize,
+}
+
+impl Default for OnboardingState {
+    fn default() -> Self {
+        Self {
+            step: OnboardingStep::Name,
+            name: String::new(),
+            theme: 0,
+            experience: 0,
+        }
+    }
+}
+
+impl OnboardingState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn navigate_up(&mut self) {
+        match self.step {
+            OnboardingStep::Name => {}
+            OnboardingStep::Theme => self.theme = self.theme.saturating_sub(1),
+            OnboardingStep::Experience => self.experience = self.experience.saturating_sub(1),
+        }
+    }
+
+    pub fn navigate_down(&mut self) {
+        match self.step {
+            OnboardingStep::Name => {}
+            OnboardingStep::Theme => self.theme = (self.theme + 1).min(Theme::NAMES.len() - 1),
+            OnboardingStep::Experience => {
+                self.experience = (self.experience + 1).min(Experience::ALL.len() - 1)
+            }
+        }
+    }
+
+    /// Type a character into the profile name
+    pub fn push_char(&mut self, c: char) {
+        if self.step == OnboardingStep::Name
+            && !c.is_control()
+            && self.name.chars().count() < MAX_NAME_LEN
+        {
+            self.name.push(c);
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        if self.step == OnboardingStep::Name {
+            self.name.pop();
+        }
+    }
+
+    /// Go back to the previous question; false on the first one
+    pub fn back(&mut self) -> bool {
+        self.step = match self.step {
+            OnboardingStep::Name => return false,
+            OnboardingStep::Theme => OnboardingStep::Name,
+            OnboardingStep::Experience => OnboardingStep::Theme,
+        };
+        true
+    }
+
+    /// Move on to the next question; false when all are answered
+    pub fn advance(&mut self) -> bool {
+        self.step = match self.step {
+            OnboardingStep::Name => OnboardingStep::Theme,
+            OnboardingStep::Theme => OnboardingStep::Experience,
+            OnboardingStep::Experience => return false,
+        };
+        true
+    }
+
+    /// Name to save, with a stand-in if none was typed
+    pub fn profile_name(&self) -> String {
+        match self.name.trim() {
+            "" => "Player".to_string(),
+            name => name.to_string(),
+        }
+    }
+
+    pub fn selected_theme(&self) -> &'static str {
+        Theme::NAMES[self.theme.min(Theme::NAMES.len() - 1)]
+    }
+
+    pub fn selected_experience(&self) -> Experience {
+        Experience::ALL[self.experience.min(Experience::ALL.len() - 1)]
+    }
+}
+
+/// Render the first-run questions
+pub fn render_onboarding(frame: &mut Frame, state: &OnboardingState, theme: &Theme) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Length(3), // Question
+            Constraint::Min(5),    // Answer
+            Constraint::Length(2), // Help
+        ])
+        .split(area);
+
+    let title = Paragraph::new("WELCOME TO REVGAME")
+        .style(theme.highlight())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_set(theme.border_set())
+                .border_style(theme.border_style()),
+        );
+
+    frame.render_widget(title, chunks[0]);
+
+    let (number, question) = match state.step {
+        OnboardingStep::Name => (1, "What should we call you?"),
+        OnboardingStep::Theme => (
+            2,
+            "Pick a color theme (you can change it later in Settings)",
+        ),
+        OnboardingStep::Experience => (3, "How much reverse engineering have you done?"),
+    };
+    let question = Paragraph::new(vec![
+        Line::styled(format!("Step {} of 3", number), theme.muted_style()),
+        Line::styled(question, theme.normal()),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+
+    frame.render_widget(question, chunks[1]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    match state.step {
+        OnboardingStep::Name => {
+            let input = Paragraph::new(format!("Name: {}_", state.name))
+                .style(Style::default().fg(theme.accent))
+                .block(block);
+            frame.render_widget(input, chunks[2]);
+        }
+        OnboardingStep::Theme => {
+            let items: Vec<ListItem> = Theme::NAMES
+                .iter()
+                .enumerate()
+                .map(|(idx, name)| choice(name.to_string(), idx == state.theme, theme))
+                .collect();
+            frame.render_widget(List::new(items).block(block), chunks[2]);
+        }
+        OnboardingStep::Experience => {
+            let items: Vec<ListItem> = Experience::ALL
+                .iter()
+                .enumerate()
+                .map(|(idx, level)| {
+                    let mut text = level.label().to_string();
+                    if level.starts_tutorial() {
+                        text.push_str(" (starts with the tutorial)");
+                    } else if level.eager_hints() {
+                        text.push_str(" (hints offered after failed attempts)");
+                    }
+                    choice(text, idx == state.experience, theme)
+                })
+                .collect();
+            frame.render_widget(List::new(items).block(block), chunks[2]);
+        }
+    }
+
+    let help = match state.step {
+        OnboardingStep::Name => " Type your name  [Enter] Next  [Esc] Skip setup ",
+        _ => " [Up/Down] Choose  [Enter] Next  [Esc] Back ",
+    };
+    let help = Paragraph::new(help)
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[3]);
+}
+
+fn choice(text: String, selected: bool, theme: &Theme) -> ListItem<'static> {
+    let style = if selected {
+        theme.selected()
+    } else {
+        theme.normal()
+    };
+    ListItem::new(Line::styled(format!("  {}", text), style))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_and_answers() {
+        let mut state = OnboardingState::new();
+        assert!(!state.back());
+        assert_eq!(state.profile_name(), "Player");
+
+        for c in "  Ada ".chars() {
+            state.push_char(c);
+        }
+        state.pop_char();
+        assert_eq!(state.profile_name(), "Ada");
+
+        assert!(state.advance());
+        state.navigate_down();
+        assert_eq!(state.selected_theme(), "light");
+        // Typing only goes into the name
+        state.push_char('x');
+        assert_eq!(state.name, "  Ada");
+
+        assert!(state.advance());
+        state.navigate_down();
+        state.navigate_down();
+        state.navigate_down();
+        assert_eq!(state.selected_experience(), Experience::Advanced);
+        assert!(!state.advance());
+    }
+}
@@ -0,0 +1,101 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use revgame_core::debugger::PredictMode;
+
+use crate::theme::Theme;
+
+/// Step prediction dialog state
+#[derive(Default)]
+pub struct PredictState {
+    pub text: String,
+    /// Instruction about to execute
+    pub instruction: String,
+}
+
+impl PredictState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.instruction.clear();
+    }
+}
+
+/// Render the question asked before a step in predict mode
+pub fn render_predict_dialog(
+    frame: &mut Frame,
+    state: &PredictState,
+    mode: PredictMode,
+    streak: u32,
+    theme: &Theme,
+) {
+    let area = centered_rect(60, 30, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Instruction and question
+            Constraint::Length(2), // Guess
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(format!(" Predict (streak {}) ", streak))
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let question = Paragraph::new(format!("{}\n{}", state.instruction, mode.prompt()))
+        .style(theme.normal())
+        .alignment(Alignment::Left);
+
+    frame.render_widget(question, chunks[0]);
+
+    // Guess
+    let input_para = Paragraph::new(format!("Changes: {}", state.text))
+        .style(Style::default().fg(theme.accent))
+        .alignment(Alignment::Left);
+
+    frame.render_widget(input_para, chunks[1]);
+
+    // Help
+    let help = Paragraph::new(" \"none\" if nothing changes  [Enter] Step  [Esc] Cancel ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,57 @@
+use ratatui::{
+    layout::Alignment,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::render_solution_overlay;
+use crate::app::App;
+
+/// Render the end of a puzzle, solved or revealed, with the solution
+/// walkthrough on top when open
+pub fn render_puzzle_complete(frame: &mut Frame, app: &App, message: &str, revealed: bool) {
+    let area = frame.area();
+
+    let (title, style) = if revealed {
+        (" Solution Revealed ", app.theme.warning_style())
+    } else {
+        (" Puzzle Complete! ", app.theme.success_style())
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(style);
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(Span::styled(message, style)),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Hints used: {}", app.game_state.hints_used),
+            app.theme.normal(),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Press [Enter] to continue, [Esc] for menu or [y] to copy this summary",
+            app.theme.muted_style(),
+        )),
+    ];
+    if app.solution.is_some() {
+        text.push(Line::from(Span::styled(
+            "[w] Walk through the official solution",
+            app.theme.muted_style(),
+        )));
+    }
+
+    let para = Paragraph::new(text).alignment(Alignment::Center);
+
+    frame.render_widget(para, inner);
+
+    if let (true, Some(solution)) = (app.solution_open, &app.solution) {
+        render_solution_overlay(frame, solution, &app.theme);
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Modifier, Style},
@@ -6,6 +8,7 @@ use ratatui::{
     Frame,
 };
 
+use revgame_core::game::{Campaign, Recommendation};
 use revgame_core::puzzle::{PuzzleCategory, PuzzleListItem};
 
 use crate::{app::App, theme::Theme};
@@ -13,6 +16,8 @@ use crate::{app::App, theme::Theme};
 /// Puzzle select state
 pub struct PuzzleSelectState {
     pub categories: Vec<PuzzleCategory>,
+    /// Checkpoint quizzes between the chapters
+    pub campaign: Campaign,
     pub selected_category: usize,
     pub selected_puzzle: usize,
     pub view_mode: SelectViewMode,
@@ -30,6 +35,7 @@ impl Default for PuzzleSelectState {
     fn default() -> Self {
         Self {
             categories: Vec::new(),
+            campaign: Campaign::default(),
             selected_category: 0,
             selected_puzzle: 0,
             view_mode: SelectViewMode::CategoryList,
@@ -113,8 +119,22 @@ impl PuzzleSelectState {
         }
     }
 
+    /// Show the details of the puzzle with this id
+    pub fn select_puzzle(&mut self, id: &str) -> bool {
+        for (category, puzzles) in self.categories.iter().enumerate() {
+            if let Some(puzzle) = puzzles.puzzles.iter().position(|puzzle| puzzle.id == id) {
+                self.selected_category = category;
+                self.selected_puzzle = puzzle;
+                self.view_mode = SelectViewMode::PuzzleDetail;
+                return true;
+            }
+        }
+        false
+    }
+
     pub fn load_puzzles(&mut self, puzzles_dir: &std::path::Path) -> Result<(), String> {
         self.categories = revgame_core::puzzle::load_puzzle_list(puzzles_dir)?;
+        self.campaign = Campaign::load(puzzles_dir)?;
         self.selected_category = 0;
         self.selected_puzzle = 0;
         self.view_mode = SelectViewMode::CategoryList;
@@ -123,28 +143,56 @@ impl PuzzleSelectState {
 }
 
 /// Render the puzzle select screen
-pub fn render_puzzle_select(frame: &mut Frame, _app: &App, state: &PuzzleSelectState, theme: &Theme) {
+pub fn render_puzzle_select(
+    frame: &mut Frame,
+    app: &App,
+    state: &PuzzleSelectState,
+    theme: &Theme,
+) {
     let area = frame.area();
+    let recommended = app.recommended_puzzle();
+    let review = app.review_puzzles();
+    #[cfg(feature = "online")]
+    let online = state
+        .get_selected_puzzle()
+        .map(|puzzle| crate::online::score_lines(app, &puzzle.id))
+        .unwrap_or_default();
+    #[cfg(not(feature = "online"))]
+    let online = Vec::new();
 
     match state.view_mode {
-        SelectViewMode::CategoryList => render_category_list(frame, state, theme, area),
-        SelectViewMode::PuzzleList => render_puzzle_list(frame, state, theme, area),
-        SelectViewMode::PuzzleDetail => render_puzzle_detail(frame, state, theme, area),
+        SelectViewMode::CategoryList => {
+            render_category_list(frame, state, recommended.as_ref(), theme, area)
+        }
+        SelectViewMode::PuzzleList => {
+            render_puzzle_list(frame, state, recommended.as_ref(), &review, theme, area)
+        }
+        SelectViewMode::PuzzleDetail => render_puzzle_detail(
+            frame,
+            state,
+            recommended.as_ref(),
+            &review,
+            &online,
+            theme,
+            area,
+        ),
     }
 }
 
 fn render_category_list(
     frame: &mut Frame,
     state: &PuzzleSelectState,
+    recommended: Option<&Recommendation>,
     theme: &Theme,
     area: ratatui::layout::Rect,
 ) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(10),    // Categories
-            Constraint::Length(1),  // Help
+            Constraint::Length(3),                                         // Header
+            Constraint::Length(if recommended.is_some() { 2 } else { 0 }), // Recommendation
+            Constraint::Min(10),                                           // Categories
+            Constraint::Length(1),                                         // Help
         ])
         .split(area);
 
@@ -160,14 +208,36 @@ fn render_category_list(
 
     frame.render_widget(header, chunks[0]);
 
+    // Recommendation
+    if let Some(recommended) = recommended {
+        let title = state
+            .categories
+            .iter()
+            .flat_map(|category| &category.puzzles)
+            .find(|puzzle| puzzle.id == recommended.id)
+            .map_or(recommended.id.as_str(), |puzzle| puzzle.title.as_str());
+        let line = Line::from(vec![
+            Span::styled("Recommended for you: ", theme.muted_style()),
+            Span::styled(
+                title,
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" - {}", recommended.reason), theme.muted_style()),
+        ]);
+        frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), chunks[1]);
+    }
+
     // Categories
     if state.categories.is_empty() {
-        let no_puzzles = Paragraph::new("No puzzles found!\n\nMake sure the puzzles directory exists.")
-            .style(theme.muted_style())
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL));
+        let no_puzzles =
+            Paragraph::new("No puzzles found!\n\nMake sure the puzzles directory exists.")
+                .style(theme.muted_style())
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL));
 
-        frame.render_widget(no_puzzles, chunks[1]);
+        frame.render_widget(no_puzzles, chunks[2]);
     } else {
         let items: Vec<ListItem> = state
             .categories
@@ -175,12 +245,28 @@ fn render_category_list(
             .enumerate()
             .map(|(idx, cat)| {
                 let count = cat.puzzles.len();
-                let text = format!("  {} ({} puzzles)", cat.display_name, count);
+                let locked = cat.puzzles.iter().any(|puzzle| puzzle.is_locked);
+                let gate = state
+                    .campaign
+                    .checkpoints
+                    .iter()
+                    .find(|checkpoint| checkpoint.unlocks == cat.name);
+                let text = match gate {
+                    Some(checkpoint) if locked => {
+                        format!(
+                            "  🔒 {} ({} puzzles) - checkpoint: {}",
+                            cat.display_name, count, checkpoint.title
+                        )
+                    }
+                    _ => format!("  {} ({} puzzles)", cat.display_name, count),
+                };
 
                 let style = if idx == state.selected_category {
                     Style::default()
                         .fg(theme.accent)
                         .add_modifier(Modifier::BOLD)
+                } else if locked {
+                    theme.muted_style()
                 } else {
                     theme.normal()
                 };
@@ -195,29 +281,33 @@ fn render_category_list(
                 .borders(Borders::ALL),
         );
 
-        frame.render_widget(list, chunks[1]);
+        frame.render_widget(list, chunks[2]);
     }
 
     // Help
-    let help = Paragraph::new(" [↑↓] Navigate  [Enter] Select  [Esc] Back to Menu ")
-        .style(theme.muted_style())
-        .alignment(Alignment::Center);
+    let help = Paragraph::new(
+        " [↑↓] Navigate  [Enter] Select  [c] Checkpoint  [r] Recommended  [Esc] Back to Menu ",
+    )
+    .style(theme.muted_style())
+    .alignment(Alignment::Center);
 
-    frame.render_widget(help, chunks[2]);
+    frame.render_widget(help, chunks[3]);
 }
 
 fn render_puzzle_list(
     frame: &mut Frame,
     state: &PuzzleSelectState,
+    recommended: Option<&Recommendation>,
+    review: &HashSet<String>,
     theme: &Theme,
     area: ratatui::layout::Rect,
 ) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(10),    // Puzzles
-            Constraint::Length(1),  // Help
+            Constraint::Length(3), // Header
+            Constraint::Min(10),   // Puzzles
+            Constraint::Length(1), // Help
         ])
         .split(area);
 
@@ -257,7 +347,21 @@ fn render_puzzle_list(
                 theme.normal()
             };
 
-            ListItem::new(Line::from(Span::styled(text, style)))
+            let mut spans = vec![Span::styled(text, style)];
+            if recommended.is_some_and(|r| r.id == puzzle.id) {
+                spans.push(Span::styled(
+                    " [recommended]",
+                    Style::default().fg(theme.success),
+                ));
+            }
+            if review.contains(&puzzle.id) {
+                spans.push(Span::styled(
+                    " [review]",
+                    Style::default().fg(theme.warning),
+                ));
+            }
+
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -270,9 +374,11 @@ fn render_puzzle_list(
     frame.render_widget(list, chunks[1]);
 
     // Help
-    let help = Paragraph::new(" [↑↓] Navigate  [Enter] View Details  [S] Start  [Esc] Back ")
-        .style(theme.muted_style())
-        .alignment(Alignment::Center);
+    let help = Paragraph::new(
+        " [↑↓] Navigate  [Enter] View Details  [S] Start  [r] Recommended  [Esc] Back ",
+    )
+    .style(theme.muted_style())
+    .alignment(Alignment::Center);
 
     frame.render_widget(help, chunks[2]);
 }
@@ -280,6 +386,9 @@ fn render_puzzle_list(
 fn render_puzzle_detail(
     frame: &mut Frame,
     state: &PuzzleSelectState,
+    recommended: Option<&Recommendation>,
+    review: &HashSet<String>,
+    online: &[String],
     theme: &Theme,
     area: ratatui::layout::Rect,
 ) {
@@ -318,6 +427,31 @@ fn render_puzzle_detail(
         ]));
         lines.push(Line::from(""));
 
+        // Concepts
+        if !puzzle.concepts.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Concepts: ", theme.highlight()),
+                Span::styled(puzzle.concepts.join(", "), theme.normal()),
+            ]));
+            lines.push(Line::from(""));
+        }
+
+        // Why it's suggested
+        if let Some(recommended) = recommended.filter(|r| r.id == puzzle.id) {
+            lines.push(Line::from(Span::styled(
+                format!("Recommended for you: {}", recommended.reason),
+                Style::default().fg(theme.success),
+            )));
+            lines.push(Line::from(""));
+        }
+        if review.contains(&puzzle.id) {
+            lines.push(Line::from(Span::styled(
+                "Worth a review: you struggled with these concepts",
+                Style::default().fg(theme.warning),
+            )));
+            lines.push(Line::from(""));
+        }
+
         // Description
         lines.push(Line::from(Span::styled("Description:", theme.highlight())));
         lines.push(Line::from(Span::styled(&puzzle.brief, theme.normal())));
@@ -325,7 +459,10 @@ fn render_puzzle_detail(
 
         // Prerequisites
         if !puzzle.prerequisites.is_empty() {
-            lines.push(Line::from(Span::styled("Prerequisites:", theme.highlight())));
+            lines.push(Line::from(Span::styled(
+                "Prerequisites:",
+                theme.highlight(),
+            )));
             for prereq in &puzzle.prerequisites {
                 lines.push(Line::from(Span::styled(
                     format!("  - {}", prereq),
@@ -338,11 +475,19 @@ fn render_puzzle_detail(
         // Locked status
         if puzzle.is_locked {
             lines.push(Line::from(Span::styled(
-                "🔒 This puzzle is locked. Complete prerequisites first.",
+                "🔒 This chapter is locked. Press [c] to take its checkpoint quiz.",
                 Style::default().fg(theme.error),
             )));
         }
 
+        // Online leaderboard, when one is set up
+        if !online.is_empty() {
+            lines.push(Line::from(""));
+            for line in online {
+                lines.push(Line::from(Span::styled(line.clone(), theme.normal())));
+            }
+        }
+
         let para = Paragraph::new(lines)
             .block(
                 Block::default()
@@ -355,9 +500,13 @@ fn render_puzzle_detail(
     }
 
     // Help
-    let help = Paragraph::new(" [S] Start Puzzle  [Esc] Back ")
-        .style(theme.muted_style())
-        .alignment(Alignment::Center);
+    let help = Paragraph::new(if online.is_empty() {
+        " [S] Start Puzzle  [Esc] Back "
+    } else {
+        " [S] Start Puzzle  [L] Leaderboard  [Esc] Back "
+    })
+    .style(theme.muted_style())
+    .alignment(Alignment::Center);
 
     frame.render_widget(help, chunks[1]);
 }
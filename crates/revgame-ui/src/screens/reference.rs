@@ -6,7 +6,10 @@ use ratatui::{
     Frame,
 };
 
-use revgame_core::reference::{InstructionCategory, InstructionInfo, InstructionReference};
+use revgame_core::{
+    game::{Encounter, InstructionCoverage},
+    reference::{Concept, Glossary, InstructionCategory, InstructionInfo, InstructionReference},
+};
 
 use crate::{app::App, theme::Theme};
 
@@ -15,7 +18,13 @@ pub struct ReferenceState {
     pub reference: InstructionReference,
     pub selected_category: usize,
     pub selected_instruction: usize,
+    /// Example picked in the instruction detail, for "Try it"
+    pub selected_example: usize,
     pub view_mode: ReferenceViewMode,
+    pub glossary: Glossary,
+    /// Filter typed on the concept list
+    pub concept_query: String,
+    pub selected_concept: usize,
 }
 
 /// What the user is currently viewing
@@ -24,6 +33,8 @@ pub enum ReferenceViewMode {
     CategoryList,
     InstructionList,
     InstructionDetail,
+    ConceptList,
+    ConceptDetail,
 }
 
 impl Default for ReferenceState {
@@ -32,7 +43,11 @@ impl Default for ReferenceState {
             reference: InstructionReference::new(),
             selected_category: 0,
             selected_instruction: 0,
+            selected_example: 0,
             view_mode: ReferenceViewMode::CategoryList,
+            glossary: Glossary::new(),
+            concept_query: String::new(),
+            selected_concept: 0,
         }
     }
 }
@@ -54,7 +69,13 @@ impl ReferenceState {
                     self.selected_instruction -= 1;
                 }
             }
-            ReferenceViewMode::InstructionDetail => {}
+            ReferenceViewMode::ConceptList => {
+                self.selected_concept = self.selected_concept.saturating_sub(1);
+            }
+            ReferenceViewMode::InstructionDetail => {
+                self.selected_example = self.selected_example.saturating_sub(1);
+            }
+            ReferenceViewMode::ConceptDetail => {}
         }
     }
 
@@ -73,7 +94,20 @@ impl ReferenceState {
                     self.selected_instruction += 1;
                 }
             }
-            ReferenceViewMode::InstructionDetail => {}
+            ReferenceViewMode::ConceptList => {
+                if self.selected_concept + 1 < self.matching_concepts().len() {
+                    self.selected_concept += 1;
+                }
+            }
+            ReferenceViewMode::InstructionDetail => {
+                let examples = self
+                    .get_current_instruction()
+                    .map_or(0, |info| info.examples.len());
+                if self.selected_example + 1 < examples {
+                    self.selected_example += 1;
+                }
+            }
+            ReferenceViewMode::ConceptDetail => {}
         }
     }
 
@@ -85,8 +119,14 @@ impl ReferenceState {
             }
             ReferenceViewMode::InstructionList => {
                 self.view_mode = ReferenceViewMode::InstructionDetail;
+                self.selected_example = 0;
             }
-            ReferenceViewMode::InstructionDetail => {}
+            ReferenceViewMode::ConceptList => {
+                if self.get_current_concept().is_some() {
+                    self.view_mode = ReferenceViewMode::ConceptDetail;
+                }
+            }
+            ReferenceViewMode::InstructionDetail | ReferenceViewMode::ConceptDetail => {}
         }
     }
 
@@ -99,9 +139,58 @@ impl ReferenceState {
             ReferenceViewMode::InstructionDetail => {
                 self.view_mode = ReferenceViewMode::InstructionList;
             }
+            ReferenceViewMode::ConceptList => {
+                self.view_mode = ReferenceViewMode::CategoryList;
+            }
+            ReferenceViewMode::ConceptDetail => {
+                self.view_mode = ReferenceViewMode::ConceptList;
+            }
         }
     }
 
+    /// Switch to the concept list with an empty filter
+    pub fn open_concepts(&mut self) {
+        self.concept_query.clear();
+        self.selected_concept = 0;
+        self.view_mode = ReferenceViewMode::ConceptList;
+    }
+
+    /// Show a concept by id, returning false if there's no such concept
+    pub fn show_concept(&mut self, id: &str) -> bool {
+        self.concept_query.clear();
+        let Some(index) = self
+            .glossary
+            .all()
+            .iter()
+            .position(|concept| concept.id == id)
+        else {
+            return false;
+        };
+        self.selected_concept = index;
+        self.view_mode = ReferenceViewMode::ConceptDetail;
+        true
+    }
+
+    /// Edit the concept filter, keeping the selection in range
+    pub fn type_concept_query(&mut self, c: Option<char>) {
+        match c {
+            Some(c) => self.concept_query.push(c),
+            None => {
+                self.concept_query.pop();
+            }
+        }
+        self.selected_concept = 0;
+    }
+
+    /// Concepts matching the filter
+    pub fn matching_concepts(&self) -> Vec<&Concept> {
+        self.glossary.search(&self.concept_query)
+    }
+
+    pub fn get_current_concept(&self) -> Option<&Concept> {
+        self.matching_concepts().get(self.selected_concept).copied()
+    }
+
     pub fn get_current_instruction(&self) -> Option<&InstructionInfo> {
         if self.view_mode == ReferenceViewMode::InstructionList
             || self.view_mode == ReferenceViewMode::InstructionDetail
@@ -116,40 +205,93 @@ impl ReferenceState {
 }
 
 /// Render the instruction reference screen
-pub fn render_reference(frame: &mut Frame, _app: &App, state: &ReferenceState, theme: &Theme) {
+pub fn render_reference(frame: &mut Frame, app: &App, state: &ReferenceState, theme: &Theme) {
     let area = frame.area();
+    let coverage = &app.game_state.coverage;
 
     match state.view_mode {
-        ReferenceViewMode::CategoryList => render_category_list(frame, state, theme, area),
-        ReferenceViewMode::InstructionList => render_instruction_list(frame, state, theme, area),
+        ReferenceViewMode::CategoryList => {
+            render_category_list(frame, state, coverage, theme, area)
+        }
+        ReferenceViewMode::InstructionList => {
+            render_instruction_list(frame, state, coverage, theme, area)
+        }
         ReferenceViewMode::InstructionDetail => {
-            render_instruction_detail(frame, state, theme, area)
+            render_instruction_detail(frame, state, coverage, theme, area)
         }
+        ReferenceViewMode::ConceptList => render_concept_list(frame, state, theme, area),
+        ReferenceViewMode::ConceptDetail => render_concept_detail(frame, state, theme, area),
     }
 }
 
+/// Marker and style for how far the player has got with an instruction
+fn encounter_marker(encounter: Encounter, theme: &Theme) -> (&'static str, Style) {
+    match (encounter, theme.ascii) {
+        (Encounter::Executed, false) => ("●", Style::default().fg(theme.success)),
+        (Encounter::Executed, true) => ("*", Style::default().fg(theme.success)),
+        (Encounter::Seen, false) => ("○", theme.normal()),
+        (Encounter::Seen, true) => ("o", theme.normal()),
+        (Encounter::Never, false) => ("·", theme.muted_style()),
+        (Encounter::Never, true) => (".", theme.muted_style()),
+    }
+}
+
+/// Counts of instructions run, seen and never met, and a few to go looking
+/// for next: ones seen but never run first
+fn coverage_line<'a>(
+    reference: &InstructionReference,
+    coverage: &InstructionCoverage,
+    theme: &Theme,
+) -> Line<'a> {
+    let mnemonics = reference.all_mnemonics();
+    let (executed, seen, never) = coverage.tally(mnemonics.iter().map(String::as_str));
+    let mut next = coverage.unexplored(mnemonics.iter().map(String::as_str));
+    next.sort_by_key(|mnemonic| std::cmp::Reverse(coverage.encounter(mnemonic)));
+    next.truncate(5);
+
+    let mut spans = vec![
+        Span::styled(
+            format!("{} run", executed),
+            Style::default().fg(theme.success),
+        ),
+        Span::styled(format!(", {} seen, ", seen), theme.normal()),
+        Span::styled(format!("{} never met", never), theme.muted_style()),
+    ];
+    if !next.is_empty() {
+        spans.push(Span::styled(
+            format!("  Try next: {}", next.join(", ")),
+            theme.highlight(),
+        ));
+    }
+    Line::from(spans)
+}
+
 fn render_category_list(
     frame: &mut Frame,
     state: &ReferenceState,
+    coverage: &InstructionCoverage,
     theme: &Theme,
     area: ratatui::layout::Rect,
 ) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(10),    // Categories
-            Constraint::Length(1),  // Help
+            Constraint::Length(3), // Header
+            Constraint::Min(10),   // Categories
+            Constraint::Length(1), // Help
         ])
         .split(area);
 
     // Header
-    let header = Paragraph::new(vec![Line::from(Span::styled(
-        "📚 x86 INSTRUCTION REFERENCE",
-        Style::default()
-            .fg(theme.accent)
-            .add_modifier(Modifier::BOLD),
-    ))])
+    let header = Paragraph::new(vec![
+        Line::from(Span::styled(
+            "📚 x86 INSTRUCTION REFERENCE",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        coverage_line(&state.reference, coverage, theme),
+    ])
     .alignment(Alignment::Center)
     .block(Block::default().borders(Borders::BOTTOM));
 
@@ -161,8 +303,14 @@ fn render_category_list(
         .iter()
         .enumerate()
         .map(|(idx, cat)| {
-            let count = state.reference.by_category(*cat).len();
-            let text = format!("  {} ({} instructions)", cat.name(), count);
+            let instructions = state.reference.by_category(*cat);
+            let (executed, _, _) = coverage.tally(instructions.iter().map(|info| info.mnemonic));
+            let text = format!(
+                "  {} ({} instructions, {} run)",
+                cat.name(),
+                instructions.len(),
+                executed
+            );
 
             let style = if idx == state.selected_category {
                 Style::default()
@@ -185,9 +333,10 @@ fn render_category_list(
     frame.render_widget(list, chunks[1]);
 
     // Help
-    let help = Paragraph::new(" [↑↓] Navigate  [Enter] Select  [Esc] Back ")
-        .style(theme.muted_style())
-        .alignment(Alignment::Center);
+    let help =
+        Paragraph::new(" [↑↓] Navigate  [Enter] Select  [c] Concepts  [a] ASCII  [Esc] Back ")
+            .style(theme.muted_style())
+            .alignment(Alignment::Center);
 
     frame.render_widget(help, chunks[2]);
 }
@@ -195,15 +344,16 @@ fn render_category_list(
 fn render_instruction_list(
     frame: &mut Frame,
     state: &ReferenceState,
+    coverage: &InstructionCoverage,
     theme: &Theme,
     area: ratatui::layout::Rect,
 ) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Header
-            Constraint::Min(10),    // Instructions
-            Constraint::Length(1),  // Help
+            Constraint::Length(3), // Header
+            Constraint::Min(10),   // Instructions
+            Constraint::Length(1), // Help
         ])
         .split(area);
 
@@ -226,7 +376,7 @@ fn render_instruction_list(
         .iter()
         .enumerate()
         .map(|(idx, info)| {
-            let text = format!("  {:<8} - {}", info.mnemonic, info.name);
+            let text = format!("{:<8} - {}", info.mnemonic, info.name);
 
             let style = if idx == state.selected_instruction {
                 Style::default()
@@ -236,7 +386,19 @@ fn render_instruction_list(
                 theme.normal()
             };
 
-            ListItem::new(Line::from(Span::styled(text, style)))
+            let (marker, marker_style) = encounter_marker(coverage.encounter(info.mnemonic), theme);
+            let mut spans = vec![
+                Span::styled(format!(" {} ", marker), marker_style),
+                Span::styled(text, style),
+            ];
+            let executed = coverage.executed(info.mnemonic);
+            if executed > 0 {
+                spans.push(Span::styled(
+                    format!("  x{}", executed),
+                    theme.muted_style(),
+                ));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -249,7 +411,12 @@ fn render_instruction_list(
     frame.render_widget(list, chunks[1]);
 
     // Help
-    let help = Paragraph::new(" [↑↓] Navigate  [Enter] Details  [Esc] Back ")
+    let help = if theme.ascii {
+        " [↑↓] Navigate  [Enter] Details  [Esc] Back   * run  o seen  . never met "
+    } else {
+        " [↑↓] Navigate  [Enter] Details  [Esc] Back   ● run  ○ seen  · never met "
+    };
+    let help = Paragraph::new(help)
         .style(theme.muted_style())
         .alignment(Alignment::Center);
 
@@ -259,6 +426,7 @@ fn render_instruction_list(
 fn render_instruction_detail(
     frame: &mut Frame,
     state: &ReferenceState,
+    coverage: &InstructionCoverage,
     theme: &Theme,
     area: ratatui::layout::Rect,
 ) {
@@ -296,21 +464,80 @@ fn render_instruction_detail(
 
         // Examples
         lines.push(Line::from(Span::styled("Examples:", theme.highlight())));
-        for example in &info.examples {
+        for (idx, example) in info.examples.iter().enumerate() {
+            let (marker, style) = if idx == state.selected_example {
+                (
+                    "▶ ",
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", theme.muted_style())
+            };
             lines.push(Line::from(Span::styled(
-                format!("  {}", example),
-                theme.muted_style(),
+                format!("{}{}", marker, example),
+                style,
             )));
         }
         lines.push(Line::from(""));
 
         // Flags
-        lines.push(Line::from(Span::styled("Flags Affected:", theme.highlight())));
+        lines.push(Line::from(Span::styled(
+            "Flags Affected:",
+            theme.highlight(),
+        )));
         lines.push(Line::from(Span::styled(
             format!("  {}", info.flags_affected),
             theme.normal(),
         )));
 
+        let puzzles = coverage.puzzles_with(info.mnemonic);
+        let plural = |count: u64| if count == 1 { "" } else { "s" };
+        let (marker, style) = encounter_marker(coverage.encounter(info.mnemonic), theme);
+        let text = match coverage.encounter(info.mnemonic) {
+            Encounter::Executed => {
+                let executed = coverage.executed(info.mnemonic);
+                format!(
+                    "Run {} time{}, in {} puzzle{}",
+                    executed,
+                    plural(executed),
+                    puzzles,
+                    plural(puzzles as u64)
+                )
+            }
+            Encounter::Seen => format!(
+                "In {} puzzle{} you played, but never run yet",
+                puzzles,
+                plural(puzzles as u64)
+            ),
+            Encounter::Never => "Not met in any puzzle yet".to_string(),
+        };
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Your Coverage:",
+            theme.highlight(),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("  {} {}", marker, text),
+            style,
+        )));
+
+        let concepts: Vec<&str> = state
+            .glossary
+            .for_instruction(info.mnemonic)
+            .iter()
+            .map(|concept| concept.title)
+            .collect();
+        if !concepts.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Concepts:", theme.highlight())));
+            lines.push(Line::from(Span::styled(
+                format!("  {}", concepts.join(", ")),
+                theme.normal(),
+            )));
+        }
+
         let para = Paragraph::new(lines)
             .block(
                 Block::default()
@@ -323,7 +550,159 @@ fn render_instruction_detail(
     }
 
     // Help
-    let help = Paragraph::new(" [Esc] Back ")
+    let help = Paragraph::new(" [↑↓] Example  [Enter] Try it  [Esc] Back ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[1]);
+}
+
+fn render_concept_list(
+    frame: &mut Frame,
+    state: &ReferenceState,
+    theme: &Theme,
+    area: ratatui::layout::Rect,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(1), // Filter
+            Constraint::Min(10),   // Concepts
+            Constraint::Length(1), // Help
+        ])
+        .split(area);
+
+    // Header
+    let header = Paragraph::new(vec![Line::from(Span::styled(
+        "📚 CONCEPTS",
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    ))])
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::BOTTOM));
+
+    frame.render_widget(header, chunks[0]);
+
+    let filter = Paragraph::new(format!(" Search: {}", state.concept_query))
+        .style(Style::default().fg(theme.accent));
+
+    frame.render_widget(filter, chunks[1]);
+
+    // Concepts
+    let concepts = state.matching_concepts();
+    let items: Vec<ListItem> = if concepts.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "  No matching concepts",
+            theme.muted_style(),
+        )))]
+    } else {
+        concepts
+            .iter()
+            .enumerate()
+            .map(|(idx, concept)| {
+                let text = format!("  {:<20} - {}", concept.title, concept.summary);
+
+                let style = if idx == state.selected_concept {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    theme.normal()
+                };
+
+                ListItem::new(Line::from(Span::styled(text, style)))
+            })
+            .collect()
+    };
+
+    let list = List::new(items).block(
+        Block::default()
+            .title(" Select Concept ")
+            .borders(Borders::ALL),
+    );
+
+    frame.render_widget(list, chunks[2]);
+
+    // Help
+    let help = Paragraph::new(" Type to search  [↑↓] Navigate  [Enter] Read  [Esc] Back ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[3]);
+}
+
+fn render_concept_detail(
+    frame: &mut Frame,
+    state: &ReferenceState,
+    theme: &Theme,
+    area: ratatui::layout::Rect,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(10), Constraint::Length(1)])
+        .split(area);
+
+    if let Some(concept) = state.get_current_concept() {
+        let mut lines = Vec::new();
+
+        lines.push(Line::from(Span::styled(
+            concept.title,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            concept.summary,
+            theme.muted_style(),
+        )));
+        lines.push(Line::from(""));
+
+        for line in concept.body.lines() {
+            lines.push(Line::from(Span::styled(line, theme.normal())));
+        }
+        lines.push(Line::from(""));
+
+        if !concept.instructions.is_empty() {
+            lines.push(Line::from(Span::styled("Instructions:", theme.highlight())));
+            lines.push(Line::from(Span::styled(
+                format!("  {}", concept.instructions.join(" ")),
+                Style::default().fg(theme.success),
+            )));
+        }
+
+        let related: Vec<&str> = concept
+            .related
+            .iter()
+            .filter_map(|id| state.glossary.lookup(id))
+            .map(|related| related.title)
+            .collect();
+        if !related.is_empty() {
+            lines.push(Line::from(Span::styled("See also:", theme.highlight())));
+            lines.push(Line::from(Span::styled(
+                format!("  {}", related.join(", ")),
+                theme.normal(),
+            )));
+        }
+
+        let para = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!(" {} ", concept.title))
+                    .borders(Borders::ALL),
+            )
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(para, chunks[0]);
+    }
+
+    // Help
+    let help = match state.get_current_concept() {
+        Some(concept) if concept.id == "endianness" => " [v] Visualize  [Esc] Back ",
+        _ => " [Esc] Back ",
+    };
+    let help = Paragraph::new(help)
         .style(theme.muted_style())
         .alignment(Alignment::Center);
 
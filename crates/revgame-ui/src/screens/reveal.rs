@@ -0,0 +1,84 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::theme::Theme;
+
+/// Render the "give up and reveal the solution" confirmation
+pub fn render_reveal_dialog(frame: &mut Frame, penalty: u32, streak: u32, theme: &Theme) {
+    let area = centered_rect(50, 30, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),    // Consequences
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Reveal Solution? ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.warning_style());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let mut lines = vec![
+        Line::styled(
+            "Stuck? The official solution will be walked through step by step.",
+            theme.normal(),
+        ),
+        Line::from(""),
+        Line::styled(
+            format!("- You lose {} points", penalty),
+            theme.warning_style(),
+        ),
+    ];
+    if streak > 0 {
+        lines.push(Line::styled(
+            format!("- Your win streak of {} ends", streak),
+            theme.warning_style(),
+        ));
+    }
+    lines.push(Line::styled(
+        "- The puzzle is marked revealed, not solved",
+        theme.warning_style(),
+    ));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), chunks[0]);
+
+    // Help
+    let help = Paragraph::new(" [y] Reveal  [n/Esc] Keep trying ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
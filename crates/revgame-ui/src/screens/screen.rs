@@ -0,0 +1,199 @@
+use ratatui::Frame;
+
+use super::{
+    is_too_small, render_achievements, render_ascii_overlay, render_bookmarks_dialog,
+    render_briefing, render_console, render_constants_overlay, render_converter_overlay,
+    render_debugger, render_decoder_overlay, render_drills, render_encoding_overlay,
+    render_endian_overlay, render_entropy_overlay, render_explain_overlay, render_find_overlay,
+    render_follow_overlay, render_goto_dialog, render_hard_reset_dialog, render_input_dialog,
+    render_main_menu, render_objectives_overlay, render_onboarding, render_plugin_panel,
+    render_predict_dialog, render_puzzle_complete, render_puzzle_select, render_reference,
+    render_reveal_dialog, render_search_dialog, render_settings, render_skill_tree,
+    render_state_diff_overlay, render_too_small, render_trace_diff_overlay,
+};
+use crate::app::{App, Screen};
+use crate::glyphs::asciify;
+
+/// Render the current screen and every open overlay, as the game shows it
+pub fn render_screen(frame: &mut Frame, app: &App) {
+    render_layers(frame, app);
+    if app.theme.ascii {
+        asciify(frame.buffer_mut());
+    }
+}
+
+/// The screen, then each overlay open on top of it
+fn render_layers(frame: &mut Frame, app: &App) {
+    if is_too_small(frame.area()) {
+        render_too_small(frame, &app.theme);
+        return;
+    }
+
+    match app.screen {
+        Screen::Onboarding => render_onboarding(frame, &app.onboarding_state, &app.theme),
+        Screen::MainMenu => render_main_menu(frame, app),
+        Screen::Drills => render_drills(frame, app),
+        Screen::PuzzleSelect => {
+            render_puzzle_select(frame, app, &app.puzzle_select_state, &app.theme)
+        }
+        Screen::Briefing => render_briefing(frame, app),
+        Screen::Debugger => render_debugger(frame, app),
+        Screen::Achievements => render_achievements(frame, app, &app.theme),
+        Screen::SkillTree => render_skill_tree(frame, app, &app.theme),
+        Screen::Reference => render_reference(frame, app, &app.reference_state, &app.theme),
+        Screen::Settings => render_settings(frame, &app.settings, &app.settings_state, &app.theme),
+        Screen::PuzzleComplete { ref message } => {
+            render_puzzle_complete(frame, app, message, false)
+        }
+        Screen::SolutionRevealed { ref message } => {
+            render_puzzle_complete(frame, app, message, true)
+        }
+        _ => render_main_menu(frame, app), // Fallback
+    }
+
+    // Render search dialog overlay if open
+    if app.search_dialog_open {
+        render_search_dialog(frame, &app.search_state, &app.theme);
+    }
+
+    // Render bookmarks dialog overlay if open
+    if app.bookmarks_dialog_open {
+        if let Some(ref dbg) = app.debugger {
+            let bookmarks = dbg.bookmarks.filtered(app.bookmarks_view_state.filter);
+            render_bookmarks_dialog(frame, &bookmarks, &app.bookmarks_view_state, &app.theme);
+        }
+    }
+
+    // Render go-to-address dialog overlay if open
+    if app.goto_dialog_open {
+        render_goto_dialog(frame, &app.goto_state, app.goto_target(), &app.theme);
+    }
+
+    // Render program input dialog if open
+    if app.input_dialog_open {
+        render_input_dialog(
+            frame,
+            &app.input_state,
+            app.input_placement_text(),
+            &app.theme,
+        );
+    }
+
+    // Render step prediction dialog if open
+    if app.predict_dialog_open {
+        render_predict_dialog(
+            frame,
+            &app.predict_state,
+            app.predict_mode(),
+            app.game_state.predictions.streak,
+            &app.theme,
+        );
+    }
+
+    // Render endianness visualizer if open
+    if let Some(ref endian) = app.endian {
+        render_endian_overlay(frame, endian, &app.theme);
+    }
+
+    // Render magic constant popup if open
+    if let Some(ref constants) = app.constants {
+        render_constants_overlay(frame, constants, &app.magic_constants(), &app.theme);
+    }
+
+    // Render the open plugin panel
+    if let (Some(panel), Some((title, lines))) = (app.plugin_panel, crate::plugin::panel_lines(app))
+    {
+        render_plugin_panel(frame, title, &lines, panel.scroll, &app.theme);
+    }
+
+    // Render the find popup if open
+    if let Some(ref finder) = app.finder {
+        render_find_overlay(frame, finder, &app.theme);
+    }
+
+    // Render the ASCII table if open
+    if let Some(ref table) = app.ascii_table {
+        render_ascii_overlay(frame, table, &app.theme);
+    }
+
+    // Render the pointer chain if open
+    if let Some(ref follow) = app.follow {
+        render_follow_overlay(frame, follow, &app.theme);
+    }
+
+    // Render the reveal confirmation if open
+    if app.reveal_confirm_open {
+        let streak = app.game_state.achievements.current_streak;
+        render_reveal_dialog(frame, app.reveal_penalty(), streak, &app.theme);
+    }
+
+    // Render the hard reset confirmation if open
+    if app.hard_reset_confirm_open {
+        let patches = app
+            .debugger
+            .as_ref()
+            .map_or(0, |debugger| debugger.net_patches().len());
+        render_hard_reset_dialog(frame, patches, &app.theme);
+    }
+
+    // Render entropy overlay if open
+    if let Some(ref entropy) = app.entropy {
+        render_entropy_overlay(frame, entropy, &app.entropy_regions(), &app.theme);
+    }
+
+    // Render data decoder if open
+    if let Some(ref decoder) = app.decoder {
+        render_decoder_overlay(
+            frame,
+            decoder,
+            &app.decoder_input(),
+            app.decoder_preview(),
+            &app.theme,
+        );
+    }
+
+    // Render number converter if open
+    if app.converter_open {
+        render_converter_overlay(
+            frame,
+            &app.converter_state,
+            app.convert(&app.converter_state.input),
+            &app.theme,
+        );
+    }
+
+    // Render run explanation overlay if open
+    if app.explain_open {
+        render_explain_overlay(frame, &app.run_explanation, &app.theme);
+    }
+
+    // Render objectives overlay if open
+    if app.objectives_open {
+        render_objectives_overlay(frame, app);
+    }
+
+    // Render run diff if open
+    if let Some(ref diff) = app.trace_diff {
+        render_trace_diff_overlay(frame, diff, &app.theme);
+    }
+
+    // Render snapshot diff if open
+    if let Some(ref diff) = app.state_diff {
+        render_state_diff_overlay(frame, diff, &app.theme);
+    }
+
+    // Render instruction encoding inspector if open
+    if let Some(ref encoding) = app.encoding {
+        render_encoding_overlay(frame, encoding, &app.theme);
+    }
+
+    // Render the debug console on top of everything else
+    if let Some(ref console) = app.console {
+        render_console(
+            frame,
+            console,
+            &crate::console::CONSOLE.lines(console.level),
+            &app.theme,
+        );
+    }
+}
@@ -2,11 +2,11 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
-use revgame_core::debugger::SearchResult;
+use revgame_core::debugger::{SearchResult, SearchScan};
 
 use crate::theme::Theme;
 
@@ -18,14 +18,50 @@ pub enum SearchMode {
     FindStrings,
 }
 
+/// Which part of memory a search covers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchScope {
+    All,
+    /// A named memory region (code, data, stack, a device)
+    Region(String),
+    /// A custom range, end exclusive
+    Range {
+        start: u32,
+        end: u32,
+    },
+}
+
+impl SearchScope {
+    pub fn label(&self) -> String {
+        match self {
+            SearchScope::All => "all memory".to_string(),
+            SearchScope::Region(name) => name.clone(),
+            SearchScope::Range { start, end } => format!("0x{:X}-0x{:X}", start, end),
+        }
+    }
+}
+
+/// Results shown at once; the rest are a page away
+pub const RESULTS_PER_PAGE: usize = 100;
+
+/// Results kept before a search stops and asks for something narrower
+pub const SEARCH_RESULT_LIMIT: usize = 1000;
+
 /// Search dialog state
 pub struct SearchState {
     pub mode: SearchMode,
     pub input: String,
     pub results: Vec<SearchResult>,
+    /// Search still scanning memory, or the last one (to tell if it was capped)
+    pub scan: Option<SearchScan>,
     pub selected_result: usize,
     pub case_sensitive: bool,
     pub min_string_length: usize,
+    pub scope: SearchScope,
+    /// Last custom range, kept in the scope cycle once set
+    pub custom_range: Option<(u32, u32)>,
+    /// Search again whenever memory is patched, so results never go stale
+    pub live: bool,
 }
 
 impl Default for SearchState {
@@ -34,9 +70,13 @@ impl Default for SearchState {
             mode: SearchMode::String,
             input: String::new(),
             results: Vec::new(),
+            scan: None,
             selected_result: 0,
             case_sensitive: false,
             min_string_length: 4,
+            scope: SearchScope::All,
+            custom_range: None,
+            live: false,
         }
     }
 }
@@ -48,13 +88,46 @@ impl SearchState {
 
     pub fn clear_results(&mut self) {
         self.results.clear();
+        self.scan = None;
         self.selected_result = 0;
     }
 
+    /// Whether a search is still scanning memory
+    pub fn is_searching(&self) -> bool {
+        self.scan.as_ref().is_some_and(|scan| !scan.is_done())
+    }
+
+    /// Whether the last search stopped at [`SEARCH_RESULT_LIMIT`]
+    pub fn is_capped(&self) -> bool {
+        self.scan.as_ref().is_some_and(|scan| scan.is_capped())
+    }
+
+    /// Selected result, kept in range while a refreshed search refills the list
+    fn selected(&self) -> usize {
+        self.selected_result
+            .min(self.results.len().saturating_sub(1))
+    }
+
+    /// Page holding the selected result (0-based)
+    pub fn page(&self) -> usize {
+        self.selected() / RESULTS_PER_PAGE
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.results.len().div_ceil(RESULTS_PER_PAGE).max(1)
+    }
+
+    pub fn page_up(&mut self) {
+        self.selected_result = self.selected().saturating_sub(RESULTS_PER_PAGE);
+    }
+
+    pub fn page_down(&mut self) {
+        self.selected_result =
+            (self.selected_result + RESULTS_PER_PAGE).min(self.results.len().saturating_sub(1));
+    }
+
     pub fn navigate_up(&mut self) {
-        if self.selected_result > 0 {
-            self.selected_result -= 1;
-        }
+        self.selected_result = self.selected().saturating_sub(1);
     }
 
     pub fn navigate_down(&mut self) {
@@ -64,7 +137,23 @@ impl SearchState {
     }
 
     pub fn get_selected_address(&self) -> Option<u32> {
-        self.results.get(self.selected_result).map(|r| r.address)
+        self.results.get(self.selected()).map(|r| r.address)
+    }
+
+    /// Move to the next scope: all memory, each of `regions`, then the
+    /// custom range if one was given
+    pub fn cycle_scope(&mut self, regions: &[String]) {
+        let mut scopes = vec![SearchScope::All];
+        scopes.extend(regions.iter().cloned().map(SearchScope::Region));
+        if let Some((start, end)) = self.custom_range {
+            scopes.push(SearchScope::Range { start, end });
+        }
+        let next = scopes
+            .iter()
+            .position(|scope| *scope == self.scope)
+            .map_or(0, |i| i + 1);
+        self.scope = scopes[next % scopes.len()].clone();
+        self.clear_results();
     }
 }
 
@@ -75,11 +164,11 @@ pub fn render_search_dialog(frame: &mut Frame, state: &SearchState, theme: &Them
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // Mode selection
-            Constraint::Length(3),  // Input
-            Constraint::Length(2),  // Options
-            Constraint::Min(5),     // Results
-            Constraint::Length(2),  // Help
+            Constraint::Length(3), // Mode selection
+            Constraint::Length(3), // Input
+            Constraint::Length(2), // Options
+            Constraint::Min(5),    // Results
+            Constraint::Length(2), // Help
         ])
         .split(area);
 
@@ -87,6 +176,7 @@ pub fn render_search_dialog(frame: &mut Frame, state: &SearchState, theme: &Them
     let block = Block::default()
         .title(" Search Memory ")
         .borders(Borders::ALL)
+        .border_set(theme.border_set())
         .border_style(theme.border_style());
 
     frame.render_widget(block, area);
@@ -117,30 +207,53 @@ pub fn render_search_dialog(frame: &mut Frame, state: &SearchState, theme: &Them
 
     frame.render_widget(input_para, chunks[1]);
 
-    // Options
-    let options_text = if state.mode == SearchMode::String {
-        format!(
-            "Case Sensitive: {} [Toggle: C]",
+    // Options, and how the search is going
+    let mut options_text = format!(
+        "Scope: {} [Tab]  Live: {} [Ctrl+L]",
+        state.scope.label(),
+        if state.live { "On" } else { "Off" }
+    );
+    if state.mode == SearchMode::String {
+        options_text.push_str(&format!(
+            "  Case Sensitive: {} [Toggle: C]",
             if state.case_sensitive { "Yes" } else { "No" }
-        )
-    } else {
-        String::new()
+        ));
+    }
+    let status = match &state.scan {
+        Some(scan) if !scan.is_done() => Span::styled(
+            format!("Searching... {}%", scan.percent()),
+            Style::default().fg(theme.accent),
+        ),
+        Some(scan) if scan.is_capped() => Span::styled(
+            format!(
+                "Showing the first {} results - refine your search",
+                state.results.len()
+            ),
+            theme.warning_style(),
+        ),
+        _ => Span::raw(""),
     };
 
-    let options_para = Paragraph::new(options_text)
-        .style(theme.muted_style())
-        .alignment(Alignment::Left);
+    let options_para = Paragraph::new(vec![
+        Line::from(Span::styled(options_text, theme.muted_style())),
+        Line::from(status),
+    ])
+    .alignment(Alignment::Left);
 
     frame.render_widget(options_para, chunks[2]);
 
-    // Results
+    // Results, a page at a time
     if !state.results.is_empty() {
-        let items: Vec<ListItem> = state
-            .results
+        let first = state.page() * RESULTS_PER_PAGE;
+        let page = &state.results[first..state.results.len().min(first + RESULTS_PER_PAGE)];
+        let items: Vec<ListItem> = page
             .iter()
-            .enumerate()
-            .map(|(idx, result)| {
-                let data_str = if result.data.iter().all(|&b| b.is_ascii_graphic() || b.is_ascii_whitespace()) {
+            .map(|result| {
+                let data_str = if result
+                    .data
+                    .iter()
+                    .all(|&b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+                {
                     // Display as string if all ASCII
                     String::from_utf8_lossy(&result.data).to_string()
                 } else {
@@ -153,41 +266,50 @@ pub fn render_search_dialog(frame: &mut Frame, state: &SearchState, theme: &Them
                         .join(" ")
                 };
 
-                let text = format!("  0x{:08X}: {}", result.address, data_str);
-
-                let style = if idx == state.selected_result {
-                    Style::default()
-                        .fg(theme.accent)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    theme.normal()
-                };
-
-                ListItem::new(Line::from(Span::styled(text, style)))
+                ListItem::new(format!("  0x{:08X}: {}", result.address, data_str))
+                    .style(theme.normal())
             })
             .collect();
 
-        let list = List::new(items).block(
-            Block::default()
-                .title(format!(" {} Results ", state.results.len()))
-                .borders(Borders::ALL),
-        );
-
-        frame.render_widget(list, chunks[3]);
+        let title = if state.page_count() > 1 {
+            format!(
+                " {} Results (page {}/{}) ",
+                state.results.len(),
+                state.page() + 1,
+                state.page_count()
+            )
+        } else {
+            format!(" {} Results ", state.results.len())
+        };
+        let list = List::new(items)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .highlight_style(
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(state.selected() - first));
+        frame.render_stateful_widget(list, chunks[3], &mut list_state);
     } else {
-        let no_results = Paragraph::new("No results")
-            .style(theme.muted_style())
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL));
+        let no_results = Paragraph::new(if state.is_searching() {
+            "Searching..."
+        } else {
+            "No results"
+        })
+        .style(theme.muted_style())
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
 
         frame.render_widget(no_results, chunks[3]);
     }
 
     // Help
     let help_text = match state.mode {
-        SearchMode::Bytes => " [1-3] Mode  [Enter] Search  [↑↓] Navigate  [G] Go to  [Esc] Close ",
-        SearchMode::String => " [1-3] Mode  [C] Case  [Enter] Search  [↑↓] Navigate  [G] Go to  [Esc] Close ",
-        SearchMode::FindStrings => " [1-3] Mode  [+/-] Length  [Enter] Search  [↑↓] Navigate  [G] Go to  [Esc] Close ",
+        SearchMode::Bytes => " [1-3] Mode  [Enter] Search  [↑↓/PgUp/PgDn] Navigate  [G] Go to  [Esc] Close ",
+        SearchMode::String => " [1-3] Mode  [C] Case  [Enter] Search  [↑↓/PgUp/PgDn] Navigate  [G] Go to  [Esc] Close ",
+        SearchMode::FindStrings => " [1-3] Mode  [+/-] Length  [Enter] Search  [↑↓/PgUp/PgDn] Navigate  [G] Go to  [Esc] Close ",
     };
 
     let help = Paragraph::new(help_text)
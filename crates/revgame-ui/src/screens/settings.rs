@@ -0,0 +1,249 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+use revgame_core::debugger::PredictMode;
+use revgame_core::game::Settings;
+
+use crate::screens::LayoutMode;
+use crate::theme::Theme;
+
+/// An adjustable entry on the settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsItem {
+    Theme,
+    HighContrast,
+    FocusMarkers,
+    AsciiBorders,
+    Announce,
+    Layout,
+    FastRun,
+    EagerHints,
+    PredictMode,
+    StackFrames,
+    IdiomNotes,
+    LearningLog,
+    Ghost,
+    SessionLog,
+}
+
+impl SettingsItem {
+    /// All items in display order
+    pub const ALL: [SettingsItem; 14] = [
+        SettingsItem::Theme,
+        SettingsItem::HighContrast,
+        SettingsItem::FocusMarkers,
+        SettingsItem::AsciiBorders,
+        SettingsItem::Announce,
+        SettingsItem::Layout,
+        SettingsItem::FastRun,
+        SettingsItem::EagerHints,
+        SettingsItem::PredictMode,
+        SettingsItem::StackFrames,
+        SettingsItem::IdiomNotes,
+        SettingsItem::LearningLog,
+        SettingsItem::Ghost,
+        SettingsItem::SessionLog,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsItem::Theme => "Color theme",
+            SettingsItem::HighContrast => "High contrast",
+            SettingsItem::FocusMarkers => "Focus markers",
+            SettingsItem::AsciiBorders => "ASCII only",
+            SettingsItem::Announce => "Announce changes",
+            SettingsItem::Layout => "Debugger layout",
+            SettingsItem::FastRun => "Fast run",
+            SettingsItem::EagerHints => "Automatic hints",
+            SettingsItem::PredictMode => "Predict steps",
+            SettingsItem::StackFrames => "Stack frames",
+            SettingsItem::IdiomNotes => "Idiom notes",
+            SettingsItem::LearningLog => "Learning log (local)",
+            SettingsItem::Ghost => "Ghost of best solve",
+            SettingsItem::SessionLog => "Session log file",
+        }
+    }
+
+    /// Current value as display text
+    pub fn value(&self, settings: &Settings) -> String {
+        let on_off = |enabled: bool| if enabled { "on" } else { "off" }.to_string();
+        match self {
+            SettingsItem::Theme => {
+                if settings.theme.is_empty() {
+                    Theme::NAMES[0].to_string()
+                } else {
+                    settings.theme.clone()
+                }
+            }
+            SettingsItem::HighContrast => on_off(settings.high_contrast),
+            SettingsItem::FocusMarkers => on_off(settings.focus_markers),
+            SettingsItem::AsciiBorders => on_off(settings.ascii_borders),
+            SettingsItem::Announce => on_off(settings.announce),
+            SettingsItem::Layout => LayoutMode::from_name(&settings.debugger_layout)
+                .label()
+                .to_string(),
+            SettingsItem::FastRun => on_off(settings.fast_run),
+            SettingsItem::EagerHints => on_off(settings.eager_hints),
+            SettingsItem::PredictMode => PredictMode::from_name(&settings.predict_mode)
+                .name()
+                .to_string(),
+            SettingsItem::StackFrames => on_off(settings.stack_frames),
+            SettingsItem::IdiomNotes => on_off(settings.idiom_notes),
+            SettingsItem::LearningLog => on_off(settings.learning_log),
+            SettingsItem::Ghost => on_off(settings.ghost),
+            SettingsItem::SessionLog => on_off(settings.session_log),
+        }
+    }
+
+    /// Toggle or cycle this item's value
+    pub fn change(&self, settings: &mut Settings) {
+        match self {
+            SettingsItem::Theme => {
+                let current = Theme::NAMES
+                    .iter()
+                    .position(|name| *name == settings.theme)
+                    .unwrap_or(0);
+                settings.theme = Theme::NAMES[(current + 1) % Theme::NAMES.len()].to_string();
+            }
+            SettingsItem::HighContrast => settings.high_contrast = !settings.high_contrast,
+            SettingsItem::FocusMarkers => settings.focus_markers = !settings.focus_markers,
+            SettingsItem::AsciiBorders => settings.ascii_borders = !settings.ascii_borders,
+            SettingsItem::Announce => settings.announce = !settings.announce,
+            SettingsItem::Layout => {
+                let next = LayoutMode::from_name(&settings.debugger_layout).next();
+                settings.debugger_layout = next.name().to_string();
+            }
+            SettingsItem::FastRun => settings.fast_run = !settings.fast_run,
+            SettingsItem::EagerHints => settings.eager_hints = !settings.eager_hints,
+            SettingsItem::PredictMode => {
+                let next = PredictMode::from_name(&settings.predict_mode).next();
+                settings.predict_mode = next.name().to_string();
+            }
+            SettingsItem::StackFrames => settings.stack_frames = !settings.stack_frames,
+            SettingsItem::IdiomNotes => settings.idiom_notes = !settings.idiom_notes,
+            SettingsItem::LearningLog => settings.learning_log = !settings.learning_log,
+            SettingsItem::Ghost => settings.ghost = !settings.ghost,
+            SettingsItem::SessionLog => settings.session_log = !settings.session_log,
+        }
+    }
+}
+
+/// Settings screen state
+#[derive(Default)]
+pub struct SettingsState {
+    pub selected: usize,
+}
+
+impl SettingsState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn navigate_up(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+        }
+    }
+
+    pub fn navigate_down(&mut self) {
+        if self.selected < SettingsItem::ALL.len() - 1 {
+            self.selected += 1;
+        }
+    }
+
+    pub fn selected_item(&self) -> SettingsItem {
+        SettingsItem::ALL[self.selected.min(SettingsItem::ALL.len() - 1)]
+    }
+}
+
+/// Render the settings screen
+pub fn render_settings(
+    frame: &mut Frame,
+    settings: &Settings,
+    state: &SettingsState,
+    theme: &Theme,
+) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(5),    // Options
+            Constraint::Length(2), // Help
+        ])
+        .split(area);
+
+    let title = Paragraph::new("SETTINGS")
+        .style(theme.highlight())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_set(theme.border_set())
+                .border_style(theme.border_style()),
+        );
+
+    frame.render_widget(title, chunks[0]);
+
+    let items: Vec<ListItem> = SettingsItem::ALL
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let style = if idx == state.selected {
+                theme.selected()
+            } else {
+                theme.normal()
+            };
+
+            // The terminal can force ASCII on with the setting off
+            let value =
+                if *item == SettingsItem::AsciiBorders && theme.ascii && !settings.ascii_borders {
+                    "on (terminal)".to_string()
+                } else {
+                    item.value(settings)
+                };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {:<20}", item.label()), style),
+                Span::styled(value, theme.highlight()),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_set(theme.border_set())
+            .border_style(theme.border_style()),
+    );
+
+    frame.render_widget(list, chunks[1]);
+
+    let help = Paragraph::new(" [Up/Down] Navigate  [Enter/Space] Change  [Esc] Back ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_cycles_through_names() {
+        let mut settings = Settings::new();
+        assert_eq!(SettingsItem::Theme.value(&settings), "dark");
+
+        SettingsItem::Theme.change(&mut settings);
+        assert_eq!(settings.theme, "light");
+        SettingsItem::Theme.change(&mut settings);
+        assert_eq!(settings.theme, "deuteranopia");
+        SettingsItem::Theme.change(&mut settings);
+        assert_eq!(settings.theme, "dark");
+    }
+}
@@ -0,0 +1,189 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::game::ConceptMastery;
+
+use crate::{app::App, theme::Theme};
+
+/// Width of a mastery bar, in cells
+const BAR_WIDTH: usize = 10;
+
+/// Skill tree screen state
+#[derive(Debug, Clone, Default)]
+pub struct SkillTreeState {
+    pub selected: usize,
+}
+
+impl SkillTreeState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn navigate_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn navigate_down(&mut self, count: usize) {
+        if self.selected + 1 < count {
+            self.selected += 1;
+        }
+    }
+}
+
+/// Render the skill tree screen
+pub fn render_skill_tree(frame: &mut Frame, app: &App, theme: &Theme) {
+    let area = frame.area();
+    let tree = app.skill_tree();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(8),    // Tree and details
+            Constraint::Length(1), // Help bar
+        ])
+        .split(area);
+
+    let mastered = tree.iter().filter(|node| node.mastered()).count();
+    let header = Paragraph::new(vec![
+        Line::from(Span::styled(
+            "SKILL TREE",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::styled("Concepts mastered: ", theme.muted_style()),
+            Span::styled(format!("{}/{}", mastered, tree.len()), theme.highlight()),
+        ]),
+    ])
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::BOTTOM));
+    frame.render_widget(header, chunks[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[1]);
+
+    // The tree, one concept per row, indented by depth
+    let items: Vec<ListItem> = tree
+        .iter()
+        .map(|node| {
+            let (icon, style) = if node.mastered() {
+                ("★", theme.success_style())
+            } else if node.unlocked {
+                ("○", theme.normal())
+            } else {
+                ("🔒", theme.muted_style())
+            };
+            let indent = "  ".repeat(node.depth);
+            let mut spans = vec![
+                Span::styled(format!("{}{} ", indent, icon), style),
+                Span::styled(
+                    format!("{:<18}", node.name),
+                    style.add_modifier(Modifier::BOLD),
+                ),
+            ];
+            if node.unlocked {
+                spans.push(Span::styled(mastery_bar(node), style));
+            } else {
+                spans.push(Span::styled(
+                    format!("requires {}", requirement_names(node, &tree)),
+                    theme.muted_style(),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut list_state = ListState::default();
+    list_state
+        .select((!tree.is_empty()).then_some(app.skill_tree_state.selected.min(tree.len() - 1)));
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(" Concepts "))
+        .highlight_style(theme.selected());
+    frame.render_stateful_widget(list, body[0], &mut list_state);
+
+    // Puzzles that teach the selected concept
+    let details: Vec<Line> = match tree.get(app.skill_tree_state.selected) {
+        None => vec![Line::from(Span::styled(
+            "No puzzles found",
+            theme.muted_style(),
+        ))],
+        Some(node) => {
+            let mut lines = vec![Line::from(Span::styled(
+                node.name.clone(),
+                theme.highlight(),
+            ))];
+            if !node.unlocked {
+                lines.push(Line::from(Span::styled(
+                    format!("Master {} to unlock", requirement_names(node, &tree)),
+                    Style::default().fg(theme.warning),
+                )));
+            } else if node.mastered() {
+                lines.push(Line::from(Span::styled("Mastered", theme.success_style())));
+            }
+            lines.push(Line::from(""));
+            if node.puzzles.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "No puzzles teach this yet",
+                    theme.muted_style(),
+                )));
+            }
+            for (_, title, solved) in &node.puzzles {
+                let (mark, style) = if *solved {
+                    ("✓", theme.success_style())
+                } else {
+                    ("·", theme.normal())
+                };
+                lines.push(Line::from(Span::styled(
+                    format!("{} {}", mark, title),
+                    style,
+                )));
+            }
+            lines
+        }
+    };
+    let details = Paragraph::new(details)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title(" Puzzles "));
+    frame.render_widget(details, body[1]);
+
+    let help = Paragraph::new(" [↑↓] Navigate  [Enter] Next puzzle for this concept  [Esc] Back ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+/// "solved/total" with a bar filling towards mastery
+fn mastery_bar(node: &ConceptMastery) -> String {
+    let total = node.puzzles.len();
+    let needed = total.clamp(1, revgame_core::game::MASTERY_SOLVES);
+    let filled = (node.solved().min(needed) * BAR_WIDTH) / needed;
+    format!(
+        "[{}{}] {}/{}",
+        "█".repeat(filled),
+        "░".repeat(BAR_WIDTH - filled),
+        node.solved(),
+        total
+    )
+}
+
+/// Display names of the concepts `node` requires
+fn requirement_names(node: &ConceptMastery, tree: &[ConceptMastery]) -> String {
+    node.requires
+        .iter()
+        .map(|concept| {
+            tree.iter()
+                .find(|other| &other.concept == concept)
+                .map_or(concept.as_str(), |other| other.name.as_str())
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
@@ -0,0 +1,118 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::puzzle::WalkthroughPage;
+
+use crate::theme::Theme;
+
+/// Walkthrough of a puzzle's official solution, one page at a time
+#[derive(Debug, Clone)]
+pub struct SolutionState {
+    pub pages: Vec<WalkthroughPage>,
+    pub page: usize,
+}
+
+impl SolutionState {
+    pub fn new(pages: Vec<WalkthroughPage>) -> Self {
+        Self { pages, page: 0 }
+    }
+
+    pub fn next_page(&mut self) {
+        if self.page + 1 < self.pages.len() {
+            self.page += 1;
+        }
+    }
+
+    pub fn prev_page(&mut self) {
+        self.page = self.page.saturating_sub(1);
+    }
+}
+
+/// Render the current walkthrough page
+pub fn render_solution_overlay(frame: &mut Frame, state: &SolutionState, theme: &Theme) {
+    let area = centered_rect(70, 70, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Page title
+            Constraint::Min(3),    // Page
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Official Solution ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let Some(page) = state.pages.get(state.page) else {
+        return;
+    };
+
+    let title = Line::from(vec![
+        Span::styled(page.title.clone(), theme.highlight()),
+        Span::styled(
+            format!("  ({}/{})", state.page + 1, state.pages.len()),
+            theme.muted_style(),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(title), chunks[0]);
+
+    let lines: Vec<Line> = page
+        .lines
+        .iter()
+        .map(|line| {
+            let style = if line.starts_with("  ") {
+                theme.normal()
+            } else if line.ends_with(':') {
+                theme.muted_style()
+            } else if line.starts_with("You") || line.contains("par") {
+                theme.success_style()
+            } else {
+                theme.normal()
+            };
+            Line::from(Span::styled(line.clone(), style))
+        })
+        .collect();
+    let body = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(body, chunks[1]);
+
+    // Help
+    let help = Paragraph::new(" [←→] Page  [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,133 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::debugger::StateDiff;
+
+use crate::theme::Theme;
+
+/// Render registers, flags and memory at two snapshots, coloring what changed
+pub fn render_state_diff_overlay(frame: &mut Frame, diff: &StateDiff, theme: &Theme) {
+    let area = centered_rect(70, 70, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Summary
+            Constraint::Min(3),    // Registers, flags and memory
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Snapshot Diff ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let summary = Paragraph::new(diff.summary())
+        .style(theme.highlight())
+        .wrap(Wrap { trim: true });
+    frame.render_widget(summary, chunks[0]);
+
+    let arrow = if theme.ascii { "->" } else { "→" };
+    let style = |changed: bool| {
+        if changed {
+            theme.changed()
+        } else {
+            theme.register_value()
+        }
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("{:<5}", ""), theme.muted_style()),
+        Span::styled(
+            format!("{:<14}", format!("at {}", diff.from)),
+            theme.muted_style(),
+        ),
+        Span::styled(format!("at {}", diff.to), theme.muted_style()),
+    ])];
+    for &(reg, was, now) in &diff.registers {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<5}", reg.name()), theme.register_name()),
+            Span::styled(format!("0x{:08X}    ", was), theme.register_value()),
+            Span::styled(format!("0x{:08X}", now), style(was != now)),
+        ]));
+    }
+
+    let mut flags = vec![Span::styled(format!("{:<5}", "FLG"), theme.register_name())];
+    for &(name, was, now) in &diff.flags {
+        let text = if was == now {
+            format!("{}={} ", name, u8::from(now))
+        } else {
+            format!("{}={}{}{} ", name, u8::from(was), arrow, u8::from(now))
+        };
+        flags.push(Span::styled(text, style(was != now)));
+    }
+    lines.push(Line::from(flags));
+
+    for &(start, length) in &diff.ranges {
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            format!(
+                "Memory 0x{:08X}..0x{:08X}",
+                start,
+                start.wrapping_add(length)
+            ),
+            theme.register_name(),
+        ));
+    }
+    if !diff.ranges.is_empty() {
+        if diff.memory.is_empty() {
+            lines.push(Line::styled("  no bytes changed", theme.muted_style()));
+        }
+        for change in &diff.memory {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  0x{:08X}  ", change.address), theme.address()),
+                Span::styled(
+                    format!("{:02X} {} ", change.before, arrow),
+                    theme.hex_byte(),
+                ),
+                Span::styled(format!("{:02X}", change.after), theme.changed()),
+            ]));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    // Help
+    let help = Paragraph::new(" [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::theme::Theme;
+
+/// Smallest terminal width the UI renders correctly at
+pub const MIN_WIDTH: u16 = 80;
+
+/// Smallest terminal height the UI renders correctly at
+pub const MIN_HEIGHT: u16 = 24;
+
+/// Whether an area is below the supported minimum size
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// Render a notice asking the user to enlarge the terminal
+pub fn render_too_small(frame: &mut Frame, theme: &Theme) {
+    let area = frame.area();
+
+    let text = vec![
+        Line::from(Span::styled("Terminal too small", theme.warning_style())),
+        Line::from(Span::styled(
+            format!(
+                "need {}x{}, have {}x{}",
+                MIN_WIDTH, MIN_HEIGHT, area.width, area.height
+            ),
+            theme.normal(),
+        )),
+        Line::from(Span::styled(
+            "Resize the window to continue",
+            theme.muted_style(),
+        )),
+    ];
+
+    // Vertically center the notice
+    let top = area.height.saturating_sub(text.len() as u16) / 2;
+    let notice = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+
+    let para = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(para, notice);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_too_small() {
+        assert!(!is_too_small(Rect::new(0, 0, 80, 24)));
+        assert!(!is_too_small(Rect::new(0, 0, 200, 60)));
+        assert!(is_too_small(Rect::new(0, 0, 79, 24)));
+        assert!(is_too_small(Rect::new(0, 0, 80, 23)));
+    }
+}
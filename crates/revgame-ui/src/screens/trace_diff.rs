@@ -0,0 +1,116 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use revgame_core::debugger::{TraceDiff, TraceStep};
+
+use crate::theme::Theme;
+
+/// Steps shown above the divergence for context
+const CONTEXT: usize = 4;
+
+/// Render the side-by-side comparison of two runs
+pub fn render_trace_diff_overlay(frame: &mut Frame, diff: &TraceDiff, theme: &Theme) {
+    let area = centered_rect(90, 70, frame.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Summary
+            Constraint::Length(1), // Column headers
+            Constraint::Min(3),    // Steps
+            Constraint::Length(1), // Help
+        ])
+        .margin(1)
+        .split(area);
+
+    // Background
+    let block = Block::default()
+        .title(" Run Diff ")
+        .borders(Borders::ALL)
+        .border_set(theme.border_set())
+        .border_style(theme.border_focused());
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let summary = Paragraph::new(diff.summary())
+        .style(theme.highlight())
+        .wrap(Wrap { trim: true });
+    frame.render_widget(summary, chunks[0]);
+
+    let column = (chunks[1].width.saturating_sub(8) / 2) as usize;
+    let header = Line::from(vec![
+        Span::styled(format!("{:>6}  ", "step"), theme.muted_style()),
+        Span::styled(format!("{:<column$}", "Before"), theme.register_name()),
+        Span::styled("After", theme.register_name()),
+    ]);
+    frame.render_widget(Paragraph::new(header), chunks[1]);
+
+    // Start a few steps above where the paths split
+    let rows = chunks[2].height as usize;
+    let first = diff
+        .divergence
+        .map(|index| index.saturating_sub(CONTEXT))
+        .unwrap_or(0);
+
+    let lines: Vec<Line> = (first..diff.len())
+        .take(rows)
+        .map(|index| {
+            let style = match diff.divergence {
+                Some(split) if index + 1 == split => theme.warning_style(),
+                Some(split) if index >= split => theme.error_style(),
+                _ => theme.normal(),
+            };
+            Line::from(vec![
+                Span::styled(format!("{:>6}  ", index), theme.muted_style()),
+                step_span(diff.before.get(index), column, style),
+                step_span(diff.after.get(index), column, style),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunks[2]);
+
+    // Help
+    let help = Paragraph::new(" [Esc] Close ")
+        .style(theme.muted_style())
+        .alignment(Alignment::Center);
+
+    frame.render_widget(help, chunks[3]);
+}
+
+/// One side of a row, padded to the column width
+fn step_span(step: Option<&TraceStep>, width: usize, style: Style) -> Span<'static> {
+    let text = match step {
+        Some(step) => format!("{:08X}: {}", step.address, step.text),
+        None => "-".to_string(),
+    };
+    let text: String = text.chars().take(width.saturating_sub(1)).collect();
+    Span::styled(format!("{:<width$}", text), style)
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,105 @@
+use std::collections::HashSet;
+
+use revgame_core::{
+    debugger::{Debugger, FlagHit, Frame, PinList, RunTrace, StateDiff, TraceDiff},
+    emulator::{DisassemblyLine, DisassemblyMode},
+    game::{AssistLevel, Replay},
+    puzzle::{HintEngine, Puzzle, Script, ValidationResult},
+};
+
+use crate::app::FocusedPanel;
+
+/// Everything that belongs to one open puzzle. The active puzzle's state
+/// lives on `App`; the others are parked here while their tab is in the
+/// background and swapped back in when it's selected.
+pub struct PuzzleSession {
+    pub(crate) debugger: Option<Debugger>,
+    pub(crate) puzzle: Option<Puzzle>,
+    pub(crate) script: Option<Script>,
+    pub(crate) hint_engine: HintEngine,
+    pub(crate) last_rule_hint: Option<String>,
+    pub(crate) hint_level: usize,
+
+    pub(crate) focused: FocusedPanel,
+    pub(crate) data_tab: FocusedPanel,
+    pub(crate) disasm_selection: usize,
+    pub(crate) selected_register: usize,
+    pub(crate) memory_view_addr: u32,
+    pub(crate) disasm_view_addr: Option<u32>,
+    pub(crate) disasm_mode: DisassemblyMode,
+    pub(crate) stack_view_addr: Option<u32>,
+    pub(crate) collapsed_frame: Option<Frame>,
+    pub(crate) pseudo_open: bool,
+    pub(crate) changed_registers: HashSet<String>,
+    pub(crate) pins: PinList,
+    pub(crate) disasm_cache: Vec<DisassemblyLine>,
+
+    pub(crate) run_explanation: Vec<String>,
+    pub(crate) last_validation: Option<ValidationResult>,
+    pub(crate) trace_diff: Option<TraceDiff>,
+    pub(crate) state_diff: Option<StateDiff>,
+    pub(crate) flag_hit: Option<FlagHit>,
+    pub(crate) previous_trace: Option<RunTrace>,
+    pub(crate) latest_trace: Option<RunTrace>,
+    pub(crate) recording: Replay,
+    pub(crate) recording_started_ms: u64,
+    pub(crate) ghost: Option<Replay>,
+
+    /// The puzzle's run in progress (mirrors the `GameState` fields)
+    pub(crate) puzzle_id: Option<String>,
+    pub(crate) hints_used: usize,
+    pub(crate) patches_made: usize,
+    pub(crate) puzzle_start_time: Option<u64>,
+    pub(crate) puzzle_assist: AssistLevel,
+}
+
+impl PuzzleSession {
+    /// Title shown on the session's tab
+    pub fn title(&self) -> String {
+        self.puzzle
+            .as_ref()
+            .map(|puzzle| puzzle.metadata.title.clone())
+            .unwrap_or_else(|| "(empty)".to_string())
+    }
+}
+
+impl Default for PuzzleSession {
+    fn default() -> Self {
+        Self {
+            debugger: None,
+            puzzle: None,
+            script: None,
+            hint_engine: HintEngine::default(),
+            last_rule_hint: None,
+            hint_level: 0,
+            focused: FocusedPanel::Disassembly,
+            data_tab: FocusedPanel::Memory,
+            disasm_selection: 0,
+            selected_register: 0,
+            memory_view_addr: 0x1000,
+            disasm_view_addr: None,
+            disasm_mode: DisassemblyMode::default(),
+            stack_view_addr: None,
+            collapsed_frame: None,
+            pseudo_open: false,
+            changed_registers: HashSet::new(),
+            pins: PinList::new(),
+            disasm_cache: Vec::new(),
+            run_explanation: Vec::new(),
+            last_validation: None,
+            trace_diff: None,
+            state_diff: None,
+            flag_hit: None,
+            previous_trace: None,
+            latest_trace: None,
+            recording: Replay::default(),
+            recording_started_ms: 0,
+            ghost: None,
+            puzzle_id: None,
+            hints_used: 0,
+            patches_made: 0,
+            puzzle_start_time: None,
+            puzzle_assist: AssistLevel::default(),
+        }
+    }
+}
@@ -3,15 +3,15 @@ use ratatui::style::Color;
 /// Instruction category for syntax highlighting
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InstructionCategory {
-    DataMovement,   // MOV, LEA, XCHG, etc.
-    Arithmetic,     // ADD, SUB, MUL, DIV, INC, DEC
-    Logic,          // AND, OR, XOR, NOT, TEST
-    ControlFlow,    // JMP, Jcc, CALL, RET, LOOP
-    Comparison,     // CMP
-    Stack,          // PUSH, POP
-    Shift,          // SHL, SHR, ROL, ROR, SAL, SAR
-    Special,        // NOP, HLT, INT, SYSCALL
-    Conditional,    // CMOVcc, SETcc
+    DataMovement, // MOV, LEA, XCHG, etc.
+    Arithmetic,   // ADD, SUB, MUL, DIV, INC, DEC
+    Logic,        // AND, OR, XOR, NOT, TEST
+    ControlFlow,  // JMP, Jcc, CALL, RET, LOOP
+    Comparison,   // CMP
+    Stack,        // PUSH, POP
+    Shift,        // SHL, SHR, ROL, ROR, SAL, SAR
+    Special,      // NOP, HLT, INT, SYSCALL
+    Conditional,  // CMOVcc, SETcc
     Unknown,
 }
 
@@ -37,17 +37,7 @@ impl InstructionCategory {
         // Arithmetic
         if matches!(
             base,
-            "ADD"
-                | "SUB"
-                | "MUL"
-                | "IMUL"
-                | "DIV"
-                | "IDIV"
-                | "INC"
-                | "DEC"
-                | "NEG"
-                | "ADC"
-                | "SBB"
+            "ADD" | "SUB" | "MUL" | "IMUL" | "DIV" | "IDIV" | "INC" | "DEC" | "NEG" | "ADC" | "SBB"
         ) {
             return Self::Arithmetic;
         }
@@ -79,10 +69,7 @@ impl InstructionCategory {
         }
 
         // Conditional jumps and moves
-        if base.starts_with("J")
-            || base.starts_with("CMOV")
-            || base.starts_with("SET")
-        {
+        if base.starts_with("J") || base.starts_with("CMOV") || base.starts_with("SET") {
             return Self::Conditional;
         }
 
@@ -146,6 +133,56 @@ impl InstructionCategory {
     }
 }
 
+/// Color palette for instruction categories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyntaxPalette {
+    /// Terminal colors (red/green distinctions)
+    #[default]
+    Standard,
+    /// Okabe-Ito colors, distinguishable with red-green color blindness
+    Deuteranopia,
+}
+
+impl SyntaxPalette {
+    /// Color for an instruction category's mnemonic
+    pub fn color(&self, category: InstructionCategory) -> Color {
+        match self {
+            SyntaxPalette::Standard => category.color(),
+            SyntaxPalette::Deuteranopia => match category {
+                InstructionCategory::DataMovement => Color::Rgb(86, 180, 233), // sky blue
+                InstructionCategory::Arithmetic => Color::Rgb(0, 114, 178),    // blue
+                InstructionCategory::Logic => Color::Rgb(240, 228, 66),        // yellow
+                InstructionCategory::ControlFlow => Color::Rgb(213, 94, 0),    // vermillion
+                InstructionCategory::Comparison => Color::Rgb(204, 121, 167),  // reddish purple
+                InstructionCategory::Stack => Color::Rgb(0, 158, 115),         // bluish green
+                InstructionCategory::Shift => Color::Rgb(150, 150, 230),       // lavender
+                InstructionCategory::Special => Color::Gray,
+                InstructionCategory::Conditional => Color::Rgb(230, 159, 0), // orange
+                InstructionCategory::Unknown => Color::White,
+            },
+        }
+    }
+
+    /// Lighter color for an instruction category's operands
+    pub fn light_color(&self, category: InstructionCategory) -> Color {
+        match self {
+            SyntaxPalette::Standard => category.light_color(),
+            SyntaxPalette::Deuteranopia => match category {
+                InstructionCategory::DataMovement => Color::Rgb(160, 212, 242),
+                InstructionCategory::Arithmetic => Color::Rgb(100, 170, 215),
+                InstructionCategory::Logic => Color::Rgb(245, 238, 150),
+                InstructionCategory::ControlFlow => Color::Rgb(235, 160, 110),
+                InstructionCategory::Comparison => Color::Rgb(225, 175, 200),
+                InstructionCategory::Stack => Color::Rgb(90, 195, 160),
+                InstructionCategory::Shift => Color::Rgb(190, 190, 240),
+                InstructionCategory::Special => Color::DarkGray,
+                InstructionCategory::Conditional => Color::Rgb(240, 195, 100),
+                InstructionCategory::Unknown => Color::Gray,
+            },
+        }
+    }
+}
+
 /// Syntax highlighter for disassembly
 pub struct SyntaxHighlighter {
     pub enabled: bool,
@@ -163,21 +200,21 @@ impl SyntaxHighlighter {
     }
 
     /// Get the color for an instruction mnemonic
-    pub fn mnemonic_color(&self, mnemonic: &str) -> Color {
+    pub fn mnemonic_color(&self, mnemonic: &str, palette: SyntaxPalette) -> Color {
         if !self.enabled {
             return Color::White;
         }
 
-        InstructionCategory::from_mnemonic(mnemonic).color()
+        palette.color(InstructionCategory::from_mnemonic(mnemonic))
     }
 
     /// Get the color for operands (lighter than mnemonic)
-    pub fn operand_color(&self, mnemonic: &str) -> Color {
+    pub fn operand_color(&self, mnemonic: &str, palette: SyntaxPalette) -> Color {
         if !self.enabled {
             return Color::Gray;
         }
 
-        InstructionCategory::from_mnemonic(mnemonic).light_color()
+        palette.light_color(InstructionCategory::from_mnemonic(mnemonic))
     }
 
     /// Get the color for addresses
@@ -260,4 +297,15 @@ mod tests {
             InstructionCategory::DataMovement
         );
     }
+
+    #[test]
+    fn test_deuteranopia_avoids_standard_red_green() {
+        let palette = SyntaxPalette::Deuteranopia;
+        let control = palette.color(InstructionCategory::ControlFlow);
+        let arithmetic = palette.color(InstructionCategory::Arithmetic);
+
+        assert_ne!(control, Color::Red);
+        assert_ne!(arithmetic, Color::Green);
+        assert_ne!(control, arithmetic);
+    }
 }
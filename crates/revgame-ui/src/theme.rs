@@ -1,4 +1,25 @@
-use ratatui::style::{Color, Modifier, Style};
+use crate::syntax::SyntaxPalette;
+
+use revgame_core::debugger::{BookmarkCategory, TimelineMark};
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders},
+};
+
+/// Border set drawn with plain ASCII characters
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
 
 /// Color theme for the TUI
 #[derive(Debug, Clone)]
@@ -15,12 +36,26 @@ pub struct Theme {
     pub error: Color,
     /// Warning color
     pub warning: Color,
+    /// Values that changed since the last step
+    pub changed: Color,
     /// Muted/secondary text
     pub muted: Color,
     /// Border color
     pub border: Color,
     /// Selection/highlight background
     pub selection_bg: Color,
+    /// Code region bytes in the memory view
+    pub region_code: Color,
+    /// Data region bytes in the memory view
+    pub region_data: Color,
+    /// Stack region bytes in the memory view
+    pub region_stack: Color,
+    /// Instruction category colors
+    pub syntax: SyntaxPalette,
+    /// Draw borders, markers and every other glyph with plain ASCII
+    pub ascii: bool,
+    /// Mark the focused panel with text, not just border color
+    pub focus_markers: bool,
 }
 
 impl Default for Theme {
@@ -33,15 +68,22 @@ impl Theme {
     /// Dark theme (default)
     pub fn dark() -> Self {
         Self {
-            bg: Color::Rgb(30, 30, 46),        // Catppuccin base
-            fg: Color::Rgb(205, 214, 244),     // Catppuccin text
-            accent: Color::Rgb(137, 180, 250), // Catppuccin blue
-            success: Color::Rgb(166, 227, 161), // Catppuccin green
-            error: Color::Rgb(243, 139, 168),  // Catppuccin red
-            warning: Color::Rgb(249, 226, 175), // Catppuccin yellow
-            muted: Color::Rgb(127, 132, 156),  // Catppuccin overlay0
-            border: Color::Rgb(88, 91, 112),   // Catppuccin surface2
-            selection_bg: Color::Rgb(69, 71, 90), // Catppuccin surface1
+            bg: Color::Rgb(30, 30, 46),              // Catppuccin base
+            fg: Color::Rgb(205, 214, 244),           // Catppuccin text
+            accent: Color::Rgb(137, 180, 250),       // Catppuccin blue
+            success: Color::Rgb(166, 227, 161),      // Catppuccin green
+            error: Color::Rgb(243, 139, 168),        // Catppuccin red
+            warning: Color::Rgb(249, 226, 175),      // Catppuccin yellow
+            changed: Color::Rgb(166, 227, 161),      // Catppuccin green
+            muted: Color::Rgb(127, 132, 156),        // Catppuccin overlay0
+            border: Color::Rgb(88, 91, 112),         // Catppuccin surface2
+            selection_bg: Color::Rgb(69, 71, 90),    // Catppuccin surface1
+            region_code: Color::Rgb(203, 166, 247),  // Catppuccin mauve
+            region_data: Color::Rgb(250, 179, 135),  // Catppuccin peach
+            region_stack: Color::Rgb(148, 226, 213), // Catppuccin teal
+            syntax: SyntaxPalette::Standard,
+            ascii: false,
+            focus_markers: false,
         }
     }
 
@@ -54,9 +96,65 @@ impl Theme {
             success: Color::Rgb(64, 160, 43),
             error: Color::Rgb(210, 15, 57),
             warning: Color::Rgb(223, 142, 29),
+            changed: Color::Rgb(64, 160, 43),
             muted: Color::Rgb(140, 143, 161),
             border: Color::Rgb(172, 176, 190),
             selection_bg: Color::Rgb(204, 208, 218),
+            region_code: Color::Rgb(136, 57, 239),
+            region_data: Color::Rgb(254, 100, 11),
+            region_stack: Color::Rgb(23, 146, 153),
+            syntax: SyntaxPalette::Standard,
+            ascii: false,
+            focus_markers: false,
+        }
+    }
+
+    /// Dark theme safe for red-green color blindness (Okabe-Ito colors)
+    pub fn deuteranopia() -> Self {
+        Self {
+            success: Color::Rgb(86, 180, 233),      // sky blue
+            error: Color::Rgb(230, 159, 0),         // orange
+            warning: Color::Rgb(240, 228, 66),      // yellow
+            changed: Color::Rgb(240, 228, 66),      // yellow
+            region_code: Color::Rgb(204, 121, 167), // reddish purple
+            region_data: Color::Rgb(230, 159, 0),   // orange
+            region_stack: Color::Rgb(86, 180, 233), // sky blue
+            syntax: SyntaxPalette::Deuteranopia,
+            ..Self::dark()
+        }
+    }
+
+    /// Theme by settings name (unknown names fall back to dark)
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "light" => Self::light(),
+            "deuteranopia" => Self::deuteranopia(),
+            _ => Self::dark(),
+        }
+    }
+
+    /// Names accepted by `from_name`, in display order
+    pub const NAMES: [&'static str; 3] = ["dark", "light", "deuteranopia"];
+
+    /// High-contrast theme using the basic terminal palette
+    pub fn high_contrast() -> Self {
+        Self {
+            bg: Color::Black,
+            fg: Color::White,
+            accent: Color::LightYellow,
+            success: Color::LightGreen,
+            error: Color::LightRed,
+            warning: Color::Yellow,
+            changed: Color::LightCyan,
+            muted: Color::Gray,
+            border: Color::White,
+            selection_bg: Color::Blue,
+            region_code: Color::LightMagenta,
+            region_data: Color::LightCyan,
+            region_stack: Color::LightBlue,
+            syntax: SyntaxPalette::Standard,
+            ascii: false,
+            focus_markers: false,
         }
     }
 
@@ -116,47 +214,199 @@ impl Theme {
             .add_modifier(Modifier::BOLD)
     }
 
-    /// Get style for breakpoints
-    pub fn breakpoint(&self) -> Style {
+    /// Get style for the instruction execution stopped on with an error
+    pub fn faulted_instruction(&self) -> Style {
         Style::default()
-            .fg(self.error)
+            .fg(self.bg)
+            .bg(self.error)
             .add_modifier(Modifier::BOLD)
     }
 
+    /// Get style for breakpoints
+    pub fn breakpoint(&self) -> Style {
+        Style::default().fg(self.error).add_modifier(Modifier::BOLD)
+    }
+
     /// Get style for changed values
     pub fn changed(&self) -> Style {
         Style::default()
-            .fg(self.success)
+            .fg(self.changed)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Get style for register names
     pub fn register_name(&self) -> Style {
-        Style::default()
-            .fg(self.accent)
+        Style::default().fg(self.accent)
     }
 
     /// Get style for register values
     pub fn register_value(&self) -> Style {
-        Style::default()
-            .fg(self.fg)
+        Style::default().fg(self.fg)
     }
 
     /// Get style for memory addresses
     pub fn address(&self) -> Style {
-        Style::default()
-            .fg(self.muted)
+        Style::default().fg(self.muted)
     }
 
     /// Get style for hex bytes
     pub fn hex_byte(&self) -> Style {
-        Style::default()
-            .fg(self.fg)
+        Style::default().fg(self.fg)
     }
 
     /// Get style for ASCII representation
     pub fn ascii(&self) -> Style {
-        Style::default()
-            .fg(self.muted)
+        Style::default().fg(self.muted)
+    }
+
+    /// Get style for bytes belonging to a named memory region
+    pub fn region(&self, name: &str) -> Style {
+        match name {
+            "code" => Style::default().fg(self.region_code),
+            "data" => Style::default().fg(self.region_data),
+            "stack" => Style::default().fg(self.region_stack),
+            _ => self.hex_byte(),
+        }
+    }
+
+    /// Get style for memory that cannot be read under enforced permissions
+    pub fn unreadable(&self) -> Style {
+        Style::default().fg(self.muted).add_modifier(Modifier::DIM)
+    }
+
+    /// Border characters for panels and dialogs
+    pub fn border_set(&self) -> border::Set {
+        if self.ascii {
+            ASCII_BORDER
+        } else {
+            border::PLAIN
+        }
+    }
+
+    /// Bordered panel block with focus styling (and a text marker when enabled)
+    pub fn panel_block<'a>(&self, title: impl Into<Line<'a>>, focused: bool) -> Block<'a> {
+        let mut title = title.into();
+        if focused && self.focus_markers {
+            title
+                .spans
+                .insert(0, Span::styled(" [focused]", self.border_focused()));
+        }
+
+        let border_style = if focused {
+            self.border_focused()
+        } else {
+            self.border_style()
+        };
+
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_set(self.border_set())
+            .border_style(border_style)
+    }
+
+    /// Marker for the current instruction
+    pub fn current_marker(&self) -> &'static str {
+        if self.ascii {
+            ">"
+        } else {
+            "►"
+        }
+    }
+
+    /// Marker for a breakpoint
+    pub fn breakpoint_marker(&self) -> &'static str {
+        if self.ascii {
+            "*"
+        } else {
+            "●"
+        }
+    }
+
+    /// Gutter marker for a bookmarked address
+    pub fn bookmark_marker(&self) -> &'static str {
+        if self.ascii {
+            "#"
+        } else {
+            "▎"
+        }
+    }
+
+    /// Gutter marker for an instruction that differs from the program as
+    /// loaded
+    pub fn patched_marker(&self) -> &'static str {
+        if self.ascii {
+            "~"
+        } else {
+            "◆"
+        }
+    }
+
+    /// Color of a bookmark's category
+    pub fn bookmark(&self, category: Option<BookmarkCategory>) -> Style {
+        let color = match category {
+            None => self.muted,
+            Some(BookmarkCategory::Function) => self.accent,
+            Some(BookmarkCategory::Check) => self.error,
+            Some(BookmarkCategory::Data) => self.region_data,
+            Some(BookmarkCategory::Loop) => self.changed,
+            Some(BookmarkCategory::Todo) => self.warning,
+        };
+        Style::default().fg(color)
+    }
+
+    /// Line the execution timeline is drawn with
+    pub fn timeline_track(&self) -> &'static str {
+        if self.ascii {
+            "-"
+        } else {
+            "─"
+        }
+    }
+
+    /// Current position on the execution timeline
+    pub fn timeline_cursor(&self) -> &'static str {
+        if self.ascii {
+            "|"
+        } else {
+            "┃"
+        }
+    }
+
+    /// Marker for an event on the execution timeline
+    pub fn timeline_marker(&self, mark: TimelineMark) -> &'static str {
+        match (mark, self.ascii) {
+            (TimelineMark::Breakpoint, false) => "●",
+            (TimelineMark::Breakpoint, true) => "o",
+            (TimelineMark::Patch, false) => "◆",
+            (TimelineMark::Patch, true) => "*",
+            (TimelineMark::Snapshot, false) => "▲",
+            (TimelineMark::Snapshot, true) => "^",
+        }
+    }
+
+    /// Color of an event on the execution timeline
+    pub fn timeline_mark(&self, mark: TimelineMark) -> Style {
+        let color = match mark {
+            TimelineMark::Breakpoint => self.error,
+            TimelineMark::Patch => self.changed,
+            TimelineMark::Snapshot => self.success,
+        };
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
+    }
+
+    /// Style for a call frame, cycling colors with nesting depth
+    pub fn frame(&self, depth: usize) -> Style {
+        let colors = [self.accent, self.success, self.warning, self.changed];
+        Style::default().fg(colors[depth % colors.len()])
+    }
+
+    /// Bar drawn beside the slots of a call frame
+    pub fn frame_marker(&self) -> &'static str {
+        if self.ascii {
+            "|"
+        } else {
+            "▌"
+        }
     }
 }
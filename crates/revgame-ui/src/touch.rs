@@ -0,0 +1,243 @@
+use ratatui::layout::Rect;
+
+use crate::{
+    app::{App, FocusedPanel, Screen},
+    screens::touch_target,
+    TutorialTrigger,
+};
+
+/// How long a finger stays down for a long press
+pub const LONG_PRESS_MS: u64 = 500;
+
+/// Cells a finger can drift and still count as a tap or long press
+pub const TAP_SLOP: u16 = 1;
+
+/// Columns a sideways swipe has to cover
+pub const SWIPE_COLUMNS: u16 = 6;
+
+/// A touch gesture, in terminal cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    /// Select the line or focus the panel under the finger
+    Tap { column: u16, row: u16 },
+    /// Toggle a breakpoint on the disassembly line under the finger
+    LongPress { column: u16, row: u16 },
+    /// Switch to the next or previous panel
+    Swipe(SwipeDirection),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+}
+
+/// Turns touch start/end events into gestures. A long press fires from
+/// `poll` while the finger is still down, so it takes effect without
+/// waiting for the finger to lift.
+#[derive(Debug, Default)]
+pub struct GestureRecognizer {
+    /// Where and when (milliseconds) the current touch started
+    start: Option<(u16, u16, u64)>,
+    /// A long press already fired for the current touch
+    fired: bool,
+}
+
+impl GestureRecognizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn touch_start(&mut self, column: u16, row: u16, at_ms: u64) {
+        self.start = Some((column, row, at_ms));
+        self.fired = false;
+    }
+
+    /// Check a touch that is still down, giving a long press once it has
+    /// been held in place long enough
+    pub fn poll(&mut self, column: u16, row: u16, at_ms: u64) -> Option<Gesture> {
+        let (start_column, start_row, started) = self.start?;
+        if self.fired || !Self::in_place(start_column, start_row, column, row) {
+            return None;
+        }
+        if at_ms.saturating_sub(started) < LONG_PRESS_MS {
+            return None;
+        }
+        self.fired = true;
+        Some(Gesture::LongPress {
+            column: start_column,
+            row: start_row,
+        })
+    }
+
+    pub fn touch_end(&mut self, column: u16, row: u16, at_ms: u64) -> Option<Gesture> {
+        let (start_column, start_row, started) = self.start.take()?;
+        if self.fired {
+            return None;
+        }
+
+        let dx = i32::from(column) - i32::from(start_column);
+        let dy = i32::from(row) - i32::from(start_row);
+        if dx.unsigned_abs() >= u32::from(SWIPE_COLUMNS) && dx.abs() > 2 * dy.abs() {
+            let direction = if dx < 0 {
+                SwipeDirection::Left
+            } else {
+                SwipeDirection::Right
+            };
+            return Some(Gesture::Swipe(direction));
+        }
+        if !Self::in_place(start_column, start_row, column, row) {
+            return None;
+        }
+        if at_ms.saturating_sub(started) >= LONG_PRESS_MS {
+            Some(Gesture::LongPress {
+                column: start_column,
+                row: start_row,
+            })
+        } else {
+            Some(Gesture::Tap {
+                column: start_column,
+                row: start_row,
+            })
+        }
+    }
+
+    /// Forget the current touch (the browser cancelled it)
+    pub fn cancel(&mut self) {
+        self.start = None;
+    }
+
+    fn in_place(start_column: u16, start_row: u16, column: u16, row: u16) -> bool {
+        start_column.abs_diff(column) <= TAP_SLOP && start_row.abs_diff(row) <= TAP_SLOP
+    }
+}
+
+/// On-screen stand-ins for keys a tablet doesn't have
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchButton {
+    /// F5
+    Run,
+    /// F10
+    Step,
+}
+
+impl TouchButton {
+    pub const ALL: [TouchButton; 2] = [TouchButton::Run, TouchButton::Step];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TouchButton::Run => "Run F5",
+            TouchButton::Step => "Step F10",
+        }
+    }
+}
+
+/// What a touch on the debugger screen landed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchTarget {
+    Button(TouchButton),
+    /// The timeline track, `column` cells along a `width`-wide track
+    Timeline {
+        column: u16,
+        width: u16,
+    },
+    /// A disassembly line, by index
+    Disassembly(usize),
+    Panel(FocusedPanel),
+}
+
+/// Act on a gesture made on a screen of `area`
+pub fn handle_gesture(app: &mut App, gesture: Gesture, area: Rect) {
+    if app.screen != Screen::Debugger {
+        return;
+    }
+
+    match gesture {
+        Gesture::Tap { column, row } => match touch_target(app, area, column, row) {
+            Some(TouchTarget::Button(button)) => press(app, button),
+            Some(TouchTarget::Timeline { column, width }) => app.scrub_timeline_to(column, width),
+            Some(TouchTarget::Disassembly(line)) => {
+                select_line(app, line);
+            }
+            Some(TouchTarget::Panel(panel)) => app.focus(panel),
+            None => {}
+        },
+        Gesture::LongPress { column, row } => {
+            if let Some(TouchTarget::Disassembly(line)) = touch_target(app, area, column, row) {
+                if select_line(app, line) {
+                    app.toggle_breakpoint();
+                    app.trigger_tutorial(TutorialTrigger::SetBreakpoint);
+                }
+            }
+        }
+        Gesture::Swipe(SwipeDirection::Left) => app.focus_next(),
+        Gesture::Swipe(SwipeDirection::Right) => app.focus_prev(),
+    }
+}
+
+/// Do what the key an on-screen button stands for does
+fn press(app: &mut App, button: TouchButton) {
+    match button {
+        TouchButton::Run => {
+            app.run();
+            app.trigger_tutorial(TutorialTrigger::Run);
+            if app
+                .debugger
+                .as_ref()
+                .is_some_and(|debugger| debugger.cpu.halted)
+            {
+                app.trigger_tutorial(TutorialTrigger::ProgramHalts);
+            }
+        }
+        TouchButton::Step => {
+            if app.request_step() {
+                app.trigger_tutorial(TutorialTrigger::Step);
+            }
+        }
+    }
+}
+
+/// Focus the disassembly and select `line`, if there is one
+fn select_line(app: &mut App, line: usize) -> bool {
+    app.focus(FocusedPanel::Disassembly);
+    if line >= app.disasm_cache.len() {
+        return false;
+    }
+    app.disasm_selection = line;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recognize_gestures() {
+        let mut touch = GestureRecognizer::new();
+        touch.touch_start(10, 5, 0);
+        assert_eq!(
+            touch.touch_end(11, 5, 120),
+            Some(Gesture::Tap { column: 10, row: 5 })
+        );
+
+        touch.touch_start(10, 5, 0);
+        assert_eq!(touch.poll(10, 5, 200), None);
+        assert_eq!(
+            touch.poll(10, 6, 600),
+            Some(Gesture::LongPress { column: 10, row: 5 })
+        );
+        assert_eq!(touch.poll(10, 6, 900), None);
+        assert_eq!(touch.touch_end(10, 6, 1000), None);
+
+        touch.touch_start(30, 5, 0);
+        assert_eq!(
+            touch.touch_end(20, 7, 200),
+            Some(Gesture::Swipe(SwipeDirection::Left))
+        );
+
+        // Dragged off but not far enough to swipe
+        touch.touch_start(30, 5, 0);
+        assert_eq!(touch.touch_end(33, 5, 200), None);
+        assert_eq!(touch.touch_end(33, 5, 300), None);
+    }
+}
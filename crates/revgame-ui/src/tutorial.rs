@@ -1,5 +1,3 @@
-use ratatui::layout::Rect;
-
 /// Direction for tutorial arrows
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ArrowDirection {
@@ -66,6 +64,8 @@ pub struct TutorialStep {
     pub trigger: TutorialTrigger,
     /// Hint text shown at bottom
     pub hint: Option<String>,
+    /// Id of a concept explaining the background, opened with `g`
+    pub concept: Option<&'static str>,
 }
 
 impl TutorialStep {
@@ -77,6 +77,7 @@ impl TutorialStep {
             arrow: None,
             trigger: TutorialTrigger::Continue,
             hint: None,
+            concept: None,
         }
     }
 
@@ -99,6 +100,11 @@ impl TutorialStep {
         self.hint = Some(hint.to_string());
         self
     }
+
+    pub fn concept(mut self, id: &'static str) -> Self {
+        self.concept = Some(id);
+        self
+    }
 }
 
 /// Tutorial state machine
@@ -122,78 +128,73 @@ impl Tutorial {
                 "Welcome to RevGame!",
                 "This tutorial will teach you how to use the debugger\n\
                  interface to solve reverse engineering puzzles.\n\n\
-                 Press ENTER or SPACE to continue..."
+                 Press ENTER or SPACE to continue...",
             )
             .trigger(TutorialTrigger::Continue),
-
             TutorialStep::new(
                 "The Disassembly View",
                 "This panel shows the program's machine code\n\
                  translated into assembly language.\n\n\
                  The '►' arrow marks the current instruction.\n\
                  The '●' symbol marks breakpoints.\n\n\
-                 Press ENTER to continue..."
+                 Press ENTER to continue...",
             )
             .highlight(HighlightTarget::Disassembly)
             .arrow(ArrowDirection::Right)
             .trigger(TutorialTrigger::Continue),
-
             TutorialStep::new(
                 "Registers",
                 "Registers are the CPU's fast storage.\n\n\
                  EAX, EBX, ECX, EDX - General purpose\n\
                  ESP - Stack pointer\n\
                  EIP - Instruction pointer (next instruction)\n\n\
-                 Watch how values change as you step!"
+                 Watch how values change as you step!",
             )
             .highlight(HighlightTarget::Registers)
             .arrow(ArrowDirection::Left)
             .trigger(TutorialTrigger::Continue),
-
             TutorialStep::new(
                 "The Memory View",
                 "This shows raw bytes in memory.\n\n\
                  Left side: hex values\n\
                  Right side: ASCII representation\n\n\
-                 You can patch these bytes to modify the program!"
+                 You can patch these bytes to modify the program!",
             )
             .highlight(HighlightTarget::Memory)
             .arrow(ArrowDirection::Right)
-            .trigger(TutorialTrigger::Continue),
-
+            .trigger(TutorialTrigger::Continue)
+            .concept("endianness"),
             TutorialStep::new(
                 "The Stack",
                 "The stack stores temporary values, return\n\
                  addresses, and function arguments.\n\n\
                  It grows downward (lower addresses).\n\
-                 ESP points to the top of the stack."
+                 ESP points to the top of the stack.",
             )
             .highlight(HighlightTarget::Stack)
             .arrow(ArrowDirection::Left)
-            .trigger(TutorialTrigger::Continue),
-
+            .trigger(TutorialTrigger::Continue)
+            .concept("stack-frames"),
             TutorialStep::new(
                 "Let's Step!",
                 "Press F10 to execute ONE instruction.\n\n\
                  Watch the registers change!\n\
-                 The '►' arrow will move to the next instruction."
+                 The '►' arrow will move to the next instruction.",
             )
             .highlight(HighlightTarget::CurrentInstruction)
             .arrow(ArrowDirection::Right)
             .trigger(TutorialTrigger::Step)
             .hint("Press F10 to step"),
-
             TutorialStep::new(
                 "Great!",
                 "Did you see EIP change? That's the instruction\n\
                  pointer moving to the next instruction.\n\n\
                  Changed registers are highlighted in GREEN.\n\n\
-                 Step a few more times to see the program flow..."
+                 Step a few more times to see the program flow...",
             )
             .highlight(HighlightTarget::Registers)
             .trigger(TutorialTrigger::Step)
             .hint("Press F10 again"),
-
             TutorialStep::new(
                 "Understanding the Code",
                 "Look at the disassembly:\n\n\
@@ -201,62 +202,57 @@ impl Tutorial {
                  JNE jumps if they're Not Equal\n\n\
                  The program checks if EAX equals 0x1337.\n\
                  But EAX is 0xDEAD - so it will FAIL!\n\n\
-                 Press ENTER to continue..."
+                 Press ENTER to continue...",
             )
             .highlight(HighlightTarget::Disassembly)
-            .trigger(TutorialTrigger::Continue),
-
+            .trigger(TutorialTrigger::Continue)
+            .concept("flags"),
             TutorialStep::new(
                 "The Challenge",
                 "Your goal: Make the program succeed!\n\n\
                  When it ends, EAX should be 1 (success)\n\
                  not 0 (failure).\n\n\
                  One way: patch the JNE to never jump.\n\
-                 The NOP instruction (0x90) does nothing!"
+                 The NOP instruction (0x90) does nothing!",
             )
             .trigger(TutorialTrigger::Continue),
-
             TutorialStep::new(
                 "Command Mode",
                 "Press ':' to enter command mode.\n\n\
                  Then type:\n\
                  patch 0x1005 90 90\n\n\
-                 This replaces JNE with two NOPs!"
+                 This replaces JNE with two NOPs!",
             )
             .highlight(HighlightTarget::CommandLine)
             .arrow(ArrowDirection::Up)
             .trigger(TutorialTrigger::EnterCommand)
             .hint("Press ':' to enter command mode"),
-
             TutorialStep::new(
                 "Patch the Code",
                 "Type: patch 0x1005 90 90\n\n\
                  Then press ENTER to apply the patch.\n\n\
                  0x1005 is the address of JNE\n\
-                 90 90 are two NOP instructions"
+                 90 90 are two NOP instructions",
             )
             .highlight(HighlightTarget::CommandLine)
             .trigger(TutorialTrigger::Patch)
             .hint("Type: patch 0x1005 90 90"),
-
             TutorialStep::new(
                 "Patched!",
                 "Look at the disassembly - the JNE is gone!\n\n\
                  Now let's reset and run the patched program.\n\
-                 Press F4 to reset to the start."
+                 Press F4 to reset to the start.",
             )
             .highlight(HighlightTarget::Disassembly)
             .trigger(TutorialTrigger::Reset)
             .hint("Press F4 to reset"),
-
             TutorialStep::new(
                 "Run the Program",
                 "Now press F5 to run until the program halts.\n\n\
-                 The patched code should take the success path!"
+                 The patched code should take the success path!",
             )
             .trigger(TutorialTrigger::Run)
             .hint("Press F5 to run"),
-
             TutorialStep::new(
                 "Tutorial Complete!",
                 "If EAX is 1, you solved it!\n\n\
@@ -265,7 +261,7 @@ impl Tutorial {
                  • Understanding registers\n\
                  • Stepping through code\n\
                  • Patching instructions\n\n\
-                 Press ENTER to finish the tutorial."
+                 Press ENTER to finish the tutorial.",
             )
             .trigger(TutorialTrigger::Continue),
         ];
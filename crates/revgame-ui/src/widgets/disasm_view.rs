@@ -1,16 +1,19 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::Widget,
 };
 
-use revgame_core::emulator::DisassemblyLine;
+use revgame_core::{
+    debugger::{BookmarkManager, ConstantMatch, FlagHit, IdiomMatch, ModifiedLine},
+    emulator::{DisassemblyLine, LineKind},
+};
 
-use crate::{Theme, SyntaxHighlighter};
+use crate::{SyntaxHighlighter, Theme};
 
 /// Disassembly view widget
 pub struct DisasmView<'a> {
@@ -30,6 +33,20 @@ pub struct DisasmView<'a> {
     title: &'a str,
     /// Syntax highlighter
     syntax_highlighter: &'a SyntaxHighlighter,
+    /// Idiom explanations shown after their instruction
+    idioms: &'a [IdiomMatch],
+    /// Well-known constants used by an instruction
+    constants: &'a [ConstantMatch],
+    /// Bookmarks, marked in the gutter in their category's color
+    bookmarks: Option<&'a BookmarkManager>,
+    /// Execution stopped with an error at the current instruction
+    faulted: bool,
+    /// Instructions changed from the program as loaded
+    modified: &'a [ModifiedLine],
+    /// Puzzle author's comments by address
+    annotations: Option<&'a BTreeMap<u32, String>>,
+    /// Flag change execution stopped on, marked at the instruction that made it
+    flag_hit: Option<&'a FlagHit>,
 }
 
 impl<'a> DisasmView<'a> {
@@ -49,6 +66,13 @@ impl<'a> DisasmView<'a> {
             theme,
             title: " Disassembly ",
             syntax_highlighter,
+            idioms: &[],
+            constants: &[],
+            bookmarks: None,
+            faulted: false,
+            modified: &[],
+            annotations: None,
+            flag_hit: None,
         }
     }
 
@@ -66,21 +90,52 @@ impl<'a> DisasmView<'a> {
         self.title = title;
         self
     }
+
+    pub fn idioms(mut self, idioms: &'a [IdiomMatch]) -> Self {
+        self.idioms = idioms;
+        self
+    }
+
+    pub fn constants(mut self, constants: &'a [ConstantMatch]) -> Self {
+        self.constants = constants;
+        self
+    }
+
+    pub fn bookmarks(mut self, bookmarks: &'a BookmarkManager) -> Self {
+        self.bookmarks = Some(bookmarks);
+        self
+    }
+
+    pub fn faulted(mut self, faulted: bool) -> Self {
+        self.faulted = faulted;
+        self
+    }
+
+    pub fn modified(mut self, modified: &'a [ModifiedLine]) -> Self {
+        self.modified = modified;
+        self
+    }
+
+    pub fn annotations(mut self, annotations: &'a BTreeMap<u32, String>) -> Self {
+        self.annotations = Some(annotations);
+        self
+    }
+
+    pub fn flag_hit(mut self, flag_hit: Option<&'a FlagHit>) -> Self {
+        self.flag_hit = flag_hit;
+        self
+    }
 }
 
 impl<'a> Widget for DisasmView<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let border_style = if self.focused {
-            self.theme.border_focused()
+        let block = self.theme.panel_block(self.title, self.focused);
+        let current_style = if self.faulted {
+            self.theme.faulted_instruction()
         } else {
-            self.theme.border_style()
+            self.theme.current_instruction()
         };
 
-        let block = Block::default()
-            .title(self.title)
-            .borders(Borders::ALL)
-            .border_style(border_style);
-
         let inner = block.inner(area);
         block.render(area, buf);
 
@@ -99,19 +154,46 @@ impl<'a> Widget for DisasmView<'a> {
 
             // Breakpoint indicator
             if has_bp {
-                spans.push(Span::styled("●", self.theme.breakpoint()));
+                spans.push(Span::styled(
+                    self.theme.breakpoint_marker(),
+                    self.theme.breakpoint(),
+                ));
             } else {
                 spans.push(Span::raw(" "));
             }
 
             // Current instruction arrow
             if is_current {
-                spans.push(Span::styled("►", self.theme.current_instruction()));
+                spans.push(Span::styled(self.theme.current_marker(), current_style));
             } else {
                 spans.push(Span::raw(" "));
             }
 
-            spans.push(Span::raw(" "));
+            // Bookmark marker
+            match self
+                .bookmarks
+                .and_then(|bookmarks| bookmarks.get(line.address))
+            {
+                Some(bookmark) => spans.push(Span::styled(
+                    self.theme.bookmark_marker(),
+                    self.theme.bookmark(bookmark.category),
+                )),
+                None => spans.push(Span::raw(" ")),
+            }
+
+            // Patch marker
+            let modified = self
+                .modified
+                .iter()
+                .find(|modified| modified.address == line.address);
+            if modified.is_some() {
+                spans.push(Span::styled(
+                    self.theme.patched_marker(),
+                    self.theme.changed(),
+                ));
+            } else {
+                spans.push(Span::raw(" "));
+            }
 
             // Address
             spans.push(Span::styled(
@@ -125,21 +207,30 @@ impl<'a> Widget for DisasmView<'a> {
             if is_current || is_selected {
                 // For current/selected lines, use theme colors
                 let instr_style = if is_current {
-                    self.theme.current_instruction()
+                    current_style
                 } else {
                     self.theme.selected()
                 };
                 spans.push(Span::styled(&line.text, instr_style));
+            } else if !line.is_code() {
+                // Embedded data and undecodable bytes aren't highlighted
+                spans.push(Span::styled(&line.text, self.theme.muted_style()));
             } else {
                 // Apply syntax highlighting
                 // Split instruction into mnemonic and operands
                 if let Some((mnemonic, operands)) = line.text.split_once(' ') {
-                    let mnemonic_color = self.syntax_highlighter.mnemonic_color(mnemonic);
-                    let operand_color = self.syntax_highlighter.operand_color(mnemonic);
+                    let mnemonic_color = self
+                        .syntax_highlighter
+                        .mnemonic_color(mnemonic, self.theme.syntax);
+                    let operand_color = self
+                        .syntax_highlighter
+                        .operand_color(mnemonic, self.theme.syntax);
 
                     spans.push(Span::styled(
                         mnemonic.to_string(),
-                        Style::default().fg(mnemonic_color).add_modifier(Modifier::BOLD),
+                        Style::default()
+                            .fg(mnemonic_color)
+                            .add_modifier(Modifier::BOLD),
                     ));
                     spans.push(Span::raw(" "));
                     spans.push(Span::styled(
@@ -148,14 +239,75 @@ impl<'a> Widget for DisasmView<'a> {
                     ));
                 } else {
                     // No operands (e.g., NOP, HLT, RET)
-                    let mnemonic_color = self.syntax_highlighter.mnemonic_color(&line.text);
+                    let mnemonic_color = self
+                        .syntax_highlighter
+                        .mnemonic_color(&line.text, self.theme.syntax);
                     spans.push(Span::styled(
                         line.text.clone(),
-                        Style::default().fg(mnemonic_color).add_modifier(Modifier::BOLD),
+                        Style::default()
+                            .fg(mnemonic_color)
+                            .add_modifier(Modifier::BOLD),
                     ));
                 }
             }
 
+            if let Some(text) = self
+                .annotations
+                .and_then(|annotations| annotations.get(&line.address))
+            {
+                spans.push(Span::styled(
+                    format!("  ; {}", text),
+                    Style::default().fg(self.syntax_highlighter.comment_color()),
+                ));
+            }
+
+            if let Some(original) = modified.and_then(|modified| modified.original.as_ref()) {
+                spans.push(Span::styled(
+                    format!("  ; was {}", original),
+                    self.theme.muted_style(),
+                ));
+            }
+
+            if let Some(hit) = self.flag_hit.filter(|hit| hit.eip == line.address) {
+                let arrow = if self.theme.ascii { "->" } else { "→" };
+                spans.push(Span::styled(
+                    format!(
+                        "  ; {} {}{}{}",
+                        hit.flag.name(),
+                        u8::from(!hit.set),
+                        arrow,
+                        u8::from(hit.set)
+                    ),
+                    self.theme.warning_style().add_modifier(Modifier::BOLD),
+                ));
+            }
+
+            if line.kind == LineKind::Junk {
+                spans.push(Span::styled("  ; junk", self.theme.warning_style()));
+            }
+
+            if let Some(found) = self
+                .constants
+                .iter()
+                .find(|found| found.address == line.address)
+            {
+                spans.push(Span::styled(
+                    format!("  ; {}", found.constant.name),
+                    self.theme.warning_style(),
+                ));
+            }
+
+            if let Some(found) = self
+                .idioms
+                .iter()
+                .find(|found| found.address == line.address)
+            {
+                spans.push(Span::styled(
+                    format!("  ; {}", found.note),
+                    self.theme.muted_style(),
+                ));
+            }
+
             // Pad and render
             let line_widget = Line::from(spans);
 
@@ -1,10 +1,13 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
+    style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, Widget},
+    widgets::Widget,
 };
 
+use revgame_core::{debugger::BookmarkManager, emulator::Memory};
+
 use crate::Theme;
 
 /// Memory hex view widget
@@ -19,6 +22,10 @@ pub struct MemoryView<'a> {
     theme: &'a Theme,
     /// Bytes per row
     bytes_per_row: usize,
+    /// Memory used to look up regions and permissions (for coloring)
+    memory_map: Option<&'a Memory>,
+    /// Bookmarks, marked in a gutter before rows that contain one
+    bookmarks: Option<&'a BookmarkManager>,
 }
 
 impl<'a> MemoryView<'a> {
@@ -29,6 +36,8 @@ impl<'a> MemoryView<'a> {
             focused: false,
             theme,
             bytes_per_row: 16,
+            memory_map: None,
+            bookmarks: None,
         }
     }
 
@@ -41,20 +50,70 @@ impl<'a> MemoryView<'a> {
         self.bytes_per_row = count;
         self
     }
+
+    /// Color bytes by the region they belong to in `memory`
+    pub fn memory_map(mut self, memory: &'a Memory) -> Self {
+        self.memory_map = Some(memory);
+        self
+    }
+
+    /// Mark rows holding a bookmark in a gutter before the address
+    pub fn bookmarks(mut self, bookmarks: &'a BookmarkManager) -> Self {
+        self.bookmarks = Some(bookmarks);
+        self
+    }
+
+    /// Style for a hex byte at the given address
+    fn byte_style(&self, address: u32) -> Style {
+        let Some(memory) = self.memory_map else {
+            return self.theme.hex_byte();
+        };
+
+        if !memory.is_readable(address) {
+            return self.theme.unreadable();
+        }
+
+        match memory.get_region(address) {
+            Some(region) => self.theme.region(&region.name),
+            None => self.theme.hex_byte(),
+        }
+    }
+
+    /// Build the block title, with a region legend when coloring is enabled
+    fn title(&self) -> Line<'a> {
+        let mut spans = vec![Span::raw(" Memory ")];
+
+        if let Some(memory) = self.memory_map {
+            let mut names: Vec<&str> = Vec::new();
+            for region in memory.regions() {
+                if !names.contains(&region.name.as_str()) {
+                    names.push(&region.name);
+                }
+            }
+
+            if !names.is_empty() {
+                spans.push(Span::raw("["));
+                for (i, name) in names.iter().enumerate() {
+                    if i > 0 {
+                        spans.push(Span::raw(" "));
+                    }
+                    spans.push(Span::styled(name.to_string(), self.theme.region(name)));
+                }
+                if memory.enforces_permissions() {
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::styled("no-read", self.theme.unreadable()));
+                }
+                spans.push(Span::raw("] "));
+            }
+        }
+
+        Line::from(spans)
+    }
 }
 
 impl<'a> Widget for MemoryView<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let border_style = if self.focused {
-            self.theme.border_focused()
-        } else {
-            self.theme.border_style()
-        };
-
-        let block = Block::default()
-            .title(" Memory ")
-            .borders(Borders::ALL)
-            .border_style(border_style);
+        let block = self.theme.panel_block(self.title(), self.focused);
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -77,6 +136,17 @@ impl<'a> Widget for MemoryView<'a> {
 
             let mut spans = Vec::new();
 
+            // Bookmark gutter
+            if let Some(bookmarks) = self.bookmarks {
+                match bookmarks.first_in(addr, self.bytes_per_row as u32) {
+                    Some(bookmark) => spans.push(Span::styled(
+                        self.theme.bookmark_marker(),
+                        self.theme.bookmark(bookmark.category),
+                    )),
+                    None => spans.push(Span::raw(" ")),
+                }
+            }
+
             // Address
             spans.push(Span::styled(
                 format!("{:08X}: ", addr),
@@ -85,7 +155,8 @@ impl<'a> Widget for MemoryView<'a> {
 
             // Hex bytes
             for (i, byte) in row_data.iter().enumerate() {
-                spans.push(Span::styled(format!("{:02X}", byte), self.theme.hex_byte()));
+                let style = self.byte_style(addr.wrapping_add(i as u32));
+                spans.push(Span::styled(format!("{:02X}", byte), style));
                 if i < row_data.len() - 1 {
                     spans.push(Span::raw(" "));
                 }
@@ -99,13 +170,22 @@ impl<'a> Widget for MemoryView<'a> {
             spans.push(Span::raw("  "));
 
             // ASCII representation
-            for byte in row_data {
+            for (i, byte) in row_data.iter().enumerate() {
                 let ch = if *byte >= 0x20 && *byte < 0x7F {
                     *byte as char
                 } else {
                     '.'
                 };
-                spans.push(Span::styled(ch.to_string(), self.theme.ascii()));
+                let readable = self
+                    .memory_map
+                    .map(|m| m.is_readable(addr.wrapping_add(i as u32)))
+                    .unwrap_or(true);
+                let style = if readable {
+                    self.theme.ascii()
+                } else {
+                    self.theme.unreadable()
+                };
+                spans.push(Span::styled(ch.to_string(), style));
             }
 
             let line = Line::from(spans);
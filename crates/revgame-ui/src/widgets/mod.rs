@@ -1,13 +1,19 @@
 mod disasm_view;
-mod register_view;
 mod memory_view;
+mod register_bits;
+mod register_timeline;
+mod register_view;
+mod rewind_effect;
 mod stack_view;
+mod timeline_view;
 mod tutorial_overlay;
-mod rewind_effect;
 
 pub use disasm_view::DisasmView;
-pub use register_view::RegisterView;
 pub use memory_view::MemoryView;
-pub use stack_view::StackView;
-pub use tutorial_overlay::{TutorialOverlay, DebuggerLayout};
+pub use register_bits::RegisterBits;
+pub use register_timeline::RegisterTimeline;
+pub use register_view::RegisterView;
 pub use rewind_effect::{RewindEffect, RewindOverlay};
+pub use stack_view::StackView;
+pub use timeline_view::TimelineView;
+pub use tutorial_overlay::{DebuggerLayout, TutorialOverlay};
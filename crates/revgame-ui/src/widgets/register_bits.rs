@@ -0,0 +1,191 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use crate::Theme;
+
+/// Width of all 32 bits in nibble groups on one line
+const FULL_WIDTH: u16 = 39;
+
+/// One register as 32 bits in nibble groups, labelled with the byte and
+/// word sub-registers they make up, for lessons on masking and shifting
+pub struct RegisterBits<'a> {
+    /// Register name ("EAX")
+    name: &'a str,
+    value: u32,
+    /// Value before the last change, to mark the bits it flipped
+    previous: Option<u32>,
+    /// Theme
+    theme: &'a Theme,
+}
+
+/// Names of the 16-bit, high-byte and low-byte parts of a 32-bit register
+fn sub_registers(name: &str) -> (String, Option<String>, Option<String>) {
+    let word = name.strip_prefix('E').unwrap_or(name).to_string();
+    match word.strip_suffix('X') {
+        Some(letter) => (
+            word.clone(),
+            Some(format!("{}H", letter)),
+            Some(format!("{}L", letter)),
+        ),
+        None => (word, None, None),
+    }
+}
+
+impl<'a> RegisterBits<'a> {
+    pub fn new(name: &'a str, value: u32, theme: &'a Theme) -> Self {
+        Self {
+            name,
+            value,
+            previous: None,
+            theme,
+        }
+    }
+
+    /// Mark the bits that differ from `previous`
+    pub fn previous(mut self, previous: Option<u32>) -> Self {
+        self.previous = previous;
+        self
+    }
+
+    /// Bits `high` down to `low`, a space between nibbles
+    fn bits(&self, high: u32, low: u32) -> Vec<Span<'static>> {
+        let flipped = self.previous.map_or(0, |previous| previous ^ self.value);
+        let mut spans = Vec::new();
+        for bit in (low..=high).rev() {
+            let style = if flipped >> bit & 1 == 1 {
+                self.theme.changed()
+            } else if self.value >> bit & 1 == 1 {
+                self.theme.highlight()
+            } else {
+                self.theme.muted_style()
+            };
+            spans.push(Span::styled(
+                if self.value >> bit & 1 == 1 { "1" } else { "0" },
+                style,
+            ));
+            if bit != low && bit % 4 == 0 {
+                spans.push(Span::raw(" "));
+            }
+        }
+        spans
+    }
+
+    /// `label` centred in a bracket `width` columns wide
+    fn bracket(&self, label: &str, width: usize) -> String {
+        let (left, right, fill) = if self.theme.ascii {
+            ('\\', '/', '_')
+        } else {
+            ('└', '┘', '─')
+        };
+        let inner = width.saturating_sub(2);
+        let text = format!(" {} ", label);
+        let before = inner.saturating_sub(text.len()) / 2;
+        let after = inner.saturating_sub(text.len() + before);
+        format!(
+            "{}{}{}{}{}",
+            left,
+            fill.to_string().repeat(before),
+            text,
+            fill.to_string().repeat(after),
+            right
+        )
+    }
+
+    fn value_line(&self, name: &str, value: u32, digits: usize) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("{:<3}= ", name), self.theme.register_name()),
+            Span::styled(
+                format!("0x{:0width$X}", value, width = digits),
+                self.theme.register_value(),
+            ),
+            Span::styled(format!(" {}", value), self.theme.muted_style()),
+        ])
+    }
+}
+
+impl<'a> Widget for RegisterBits<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (word, high, low) = sub_registers(self.name);
+        let mut lines = vec![Line::from(vec![
+            Span::styled(format!("{} ", self.name), self.theme.register_name()),
+            Span::styled(
+                format!("bits, {} set (t: timeline)", self.value.count_ones()),
+                self.theme.muted_style(),
+            ),
+        ])];
+
+        if area.width >= FULL_WIDTH {
+            let mut bits = self.bits(31, 16);
+            bits.push(Span::raw(" "));
+            bits.extend(self.bits(15, 0));
+            lines.push(Line::from(bits));
+
+            let mut labels = self.bracket("high word", 19) + " ";
+            match (&high, &low) {
+                (Some(high), Some(low)) => {
+                    labels += &self.bracket(high, 9);
+                    labels += " ";
+                    labels += &self.bracket(low, 9);
+                }
+                _ => labels += &self.bracket(&word, 19),
+            }
+            lines.push(Line::from(Span::styled(labels, self.theme.muted_style())));
+        } else {
+            // Too narrow for one line: the high word above the low word
+            let mut upper = vec![Span::styled("31 ", self.theme.muted_style())];
+            upper.extend(self.bits(31, 16));
+            let mut lower = vec![Span::styled("15 ", self.theme.muted_style())];
+            lower.extend(self.bits(15, 0));
+            lines.push(Line::from(upper));
+            lines.push(Line::from(lower));
+        }
+
+        lines.push(self.value_line(&word, self.value & 0xFFFF, 4));
+        if let Some(ref high) = high {
+            lines.push(self.value_line(high, self.value >> 8 & 0xFF, 2));
+        }
+        if let Some(ref low) = low {
+            lines.push(self.value_line(low, self.value & 0xFF, 2));
+        }
+
+        for (i, line) in lines.iter().enumerate() {
+            if i as u16 >= area.height {
+                break;
+            }
+            buf.set_line(area.x, area.y + i as u16, line, area.width);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span]) -> String {
+        spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_bits_group_nibbles_and_name_sub_registers() {
+        let theme = Theme::default();
+        let bits = RegisterBits::new("EAX", 0x1337, &theme);
+        assert_eq!(text(&bits.bits(15, 0)), "0001 0011 0011 0111");
+        assert_eq!(text(&bits.bits(7, 0)), "0011 0111");
+        assert_eq!(bits.bracket("AH", 9), "└─ AH ──┘");
+
+        assert_eq!(
+            sub_registers("EDX"),
+            (
+                "DX".to_string(),
+                Some("DH".to_string()),
+                Some("DL".to_string())
+            )
+        );
+        assert_eq!(sub_registers("ESI"), ("SI".to_string(), None, None));
+        assert_eq!(sub_registers("EIP"), ("IP".to_string(), None, None));
+    }
+}
@@ -0,0 +1,148 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use revgame_core::debugger::RegisterSample;
+
+use crate::Theme;
+
+/// Bar levels for the sparkline, lowest first
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// ASCII fallback bar levels
+const ASCII_BARS: [char; 8] = ['_', '.', ',', '-', '=', '+', '*', '#'];
+
+/// How one register evolved over recent instructions: a sparkline of its
+/// values followed by the instructions that changed it, newest first
+pub struct RegisterTimeline<'a> {
+    /// Register name for the header
+    name: &'a str,
+    /// Samples, oldest first
+    samples: &'a [RegisterSample],
+    /// Theme
+    theme: &'a Theme,
+}
+
+impl<'a> RegisterTimeline<'a> {
+    pub fn new(name: &'a str, samples: &'a [RegisterSample], theme: &'a Theme) -> Self {
+        Self {
+            name,
+            samples,
+            theme,
+        }
+    }
+
+    /// One bar per sample, scaled between the smallest and largest value
+    fn sparkline(&self, width: usize) -> String {
+        let bars = if self.theme.ascii { &ASCII_BARS } else { &BARS };
+        let samples = &self.samples[self.samples.len().saturating_sub(width)..];
+
+        let min = samples.iter().map(|s| s.value).min().unwrap_or(0);
+        let max = samples.iter().map(|s| s.value).max().unwrap_or(0);
+        let range = u64::from(max - min);
+
+        samples
+            .iter()
+            .map(|sample| {
+                // A flat line sits on the lowest bar
+                let level = (u64::from(sample.value - min) * (bars.len() as u64 - 1))
+                    .checked_div(range)
+                    .unwrap_or(0);
+                bars[level as usize]
+            })
+            .collect()
+    }
+
+    /// Samples where the value differs from the one before, newest first
+    fn changes(&self) -> Vec<&RegisterSample> {
+        self.samples
+            .windows(2)
+            .filter(|pair| pair[0].value != pair[1].value)
+            .map(|pair| &pair[1])
+            .rev()
+            .collect()
+    }
+}
+
+impl<'a> Widget for RegisterTimeline<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 {
+            return;
+        }
+
+        let header = Line::from(vec![
+            Span::styled(format!("{} ", self.name), self.theme.register_name()),
+            Span::styled(
+                format!("over last {} steps", self.samples.len()),
+                self.theme.muted_style(),
+            ),
+        ]);
+        buf.set_line(area.x, area.y, &header, area.width);
+
+        if self.samples.is_empty() {
+            if area.height > 1 {
+                let line = Line::from(Span::styled(
+                    "Step to record values",
+                    self.theme.muted_style(),
+                ));
+                buf.set_line(area.x, area.y + 1, &line, area.width);
+            }
+            return;
+        }
+
+        if area.height > 1 {
+            let line = Line::from(Span::styled(
+                self.sparkline(area.width as usize),
+                self.theme.highlight(),
+            ));
+            buf.set_line(area.x, area.y + 1, &line, area.width);
+        }
+
+        for (i, sample) in self.changes().iter().enumerate() {
+            let y = area.y + 2 + i as u16;
+            if y >= area.y + area.height {
+                break;
+            }
+
+            let line = Line::from(vec![
+                Span::styled(format!("0x{:08X} ", sample.value), self.theme.changed()),
+                Span::styled(sample.instruction_text.clone(), self.theme.normal()),
+            ]);
+            buf.set_line(area.x, y, &line, area.width);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(value: u32) -> RegisterSample {
+        RegisterSample {
+            eip: 0x1000,
+            instruction_text: "nop".to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_sparkline_scales_between_extremes() {
+        let theme = Theme::default();
+        let samples = [sample(3), sample(3), sample(2), sample(0)];
+        let timeline = RegisterTimeline::new("ECX", &samples, &theme);
+
+        assert_eq!(timeline.sparkline(10), "██▅▁");
+        assert_eq!(timeline.sparkline(2), "█▁");
+        assert_eq!(
+            timeline
+                .changes()
+                .iter()
+                .map(|s| s.value)
+                .collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+    }
+}
@@ -3,14 +3,18 @@ use std::collections::HashSet;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
+    style::Modifier,
     text::{Line, Span},
-    widgets::{Block, Borders, Widget},
+    widgets::Widget,
 };
 
-use revgame_core::emulator::{CpuState, Eflags};
+use revgame_core::debugger::RegisterSample;
+use revgame_core::emulator::CpuState;
 
 use crate::Theme;
 
+use super::{RegisterBits, RegisterTimeline};
+
 /// Register view widget
 pub struct RegisterView<'a> {
     /// CPU state to display
@@ -19,6 +23,14 @@ pub struct RegisterView<'a> {
     changed: &'a HashSet<String>,
     /// Whether this panel is focused
     focused: bool,
+    /// Whether changed registers are mid-flash (just after a step)
+    flash: bool,
+    /// Row of the register whose timeline is shown
+    selected: usize,
+    /// Recent values of the selected register, oldest first
+    timeline: &'a [RegisterSample],
+    /// Show the selected register's bits instead of its timeline
+    bits: bool,
     /// Theme
     theme: &'a Theme,
 }
@@ -29,6 +41,10 @@ impl<'a> RegisterView<'a> {
             cpu,
             changed,
             focused: false,
+            flash: false,
+            selected: 0,
+            timeline: &[],
+            bits: false,
             theme,
         }
     }
@@ -37,20 +53,29 @@ impl<'a> RegisterView<'a> {
         self.focused = focused;
         self
     }
+
+    pub fn flash(mut self, flash: bool) -> Self {
+        self.flash = flash;
+        self
+    }
+
+    /// Show the timeline of the register on row `selected`
+    pub fn timeline(mut self, selected: usize, timeline: &'a [RegisterSample]) -> Self {
+        self.selected = selected;
+        self.timeline = timeline;
+        self
+    }
+
+    /// Break the selected register down into bits instead
+    pub fn bits(mut self, bits: bool) -> Self {
+        self.bits = bits;
+        self
+    }
 }
 
 impl<'a> Widget for RegisterView<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let border_style = if self.focused {
-            self.theme.border_focused()
-        } else {
-            self.theme.border_style()
-        };
-
-        let block = Block::default()
-            .title(" Registers ")
-            .borders(Borders::ALL)
-            .border_style(border_style);
+        let block = self.theme.panel_block(" Registers ", self.focused);
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -68,21 +93,30 @@ impl<'a> Widget for RegisterView<'a> {
         ];
 
         for (i, (name, value)) in registers.iter().enumerate() {
-            if i >= inner.height as usize - 1 {
+            if i >= (inner.height as usize).saturating_sub(1) {
                 break;
             }
 
             let y = inner.y + i as u16;
             let is_changed = self.changed.contains(*name);
 
-            let value_style = if is_changed {
+            let value_style = if is_changed && self.flash {
+                self.theme.changed().add_modifier(Modifier::REVERSED)
+            } else if is_changed {
                 self.theme.changed()
             } else {
                 self.theme.register_value()
             };
 
+            // Mark the timeline register while the panel is focused
+            let name_style = if self.focused && i == self.selected {
+                self.theme.selected()
+            } else {
+                self.theme.register_name()
+            };
+
             let line = Line::from(vec![
-                Span::styled(format!("{}: ", name), self.theme.register_name()),
+                Span::styled(format!("{}: ", name), name_style),
                 Span::styled(format!("0x{:08X}", value), value_style),
             ]);
 
@@ -90,7 +124,10 @@ impl<'a> Widget for RegisterView<'a> {
         }
 
         // Display flags on the last line
-        let flags_y = inner.y + registers.len().min(inner.height as usize - 1) as u16;
+        let flags_y = inner.y
+            + registers
+                .len()
+                .min((inner.height as usize).saturating_sub(1)) as u16;
         if flags_y < inner.y + inner.height {
             let flags_line = Line::from(vec![
                 Span::styled("FLAGS: ", self.theme.register_name()),
@@ -98,5 +135,28 @@ impl<'a> Widget for RegisterView<'a> {
             ]);
             buf.set_line(inner.x, flags_y, &flags_line, inner.width);
         }
+
+        // Timeline of the selected register in the space left below the flags
+        let timeline_y = flags_y + 2;
+        if timeline_y < inner.y + inner.height {
+            let area = Rect {
+                y: timeline_y,
+                height: inner.y + inner.height - timeline_y,
+                ..inner
+            };
+            let (name, value) = registers[self.selected.min(registers.len() - 1)];
+            if self.bits {
+                // Mark the bits the last step flipped
+                let previous = match self.timeline {
+                    [.., before, last] if last.value == value => Some(before.value),
+                    _ => None,
+                };
+                RegisterBits::new(name, value, self.theme)
+                    .previous(previous)
+                    .render(area, buf);
+            } else {
+                RegisterTimeline::new(name, self.timeline, self.theme).render(area, buf);
+            }
+        }
     }
 }
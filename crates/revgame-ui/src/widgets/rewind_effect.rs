@@ -1,4 +1,6 @@
 /// VHS-style rewind effect overlay
+use std::time::Duration;
+
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -6,54 +8,48 @@ use ratatui::{
     widgets::Widget,
 };
 
+use crate::animation::Animation;
+
+/// Length of one animation frame (noise patterns change once per frame)
+const FRAME_TIME: Duration = Duration::from_millis(100);
+
 /// State of the rewind animation
 #[derive(Debug, Clone)]
 pub struct RewindEffect {
-    /// Frame counter for animation
-    frame: u32,
-    /// Duration of effect in frames
-    max_frames: u32,
-    /// Whether effect is active
-    active: bool,
+    /// Time-based animation driving the effect
+    animation: Animation,
 }
 
 impl RewindEffect {
     pub fn new() -> Self {
         Self {
-            frame: 0,
-            max_frames: 15,
-            active: false,
+            animation: Animation::new(FRAME_TIME * 15),
         }
     }
 
     /// Start the rewind effect
     pub fn trigger(&mut self) {
-        self.frame = 0;
-        self.active = true;
+        self.animation.start();
     }
 
-    /// Advance the animation by one frame
-    pub fn tick(&mut self) {
-        if self.active {
-            self.frame += 1;
-            if self.frame >= self.max_frames {
-                self.active = false;
-                self.frame = 0;
-            }
-        }
+    /// Advance the animation by elapsed time
+    pub fn update(&mut self, dt: Duration) {
+        self.animation.update(dt);
     }
 
     /// Check if effect is currently active
     pub fn is_active(&self) -> bool {
-        self.active
+        self.animation.is_active()
+    }
+
+    /// Current frame number (for noise patterns)
+    fn frame(&self) -> u32 {
+        (self.animation.elapsed().as_millis() / FRAME_TIME.as_millis()) as u32
     }
 
     /// Get current progress (0.0 to 1.0)
     fn progress(&self) -> f32 {
-        if !self.active {
-            return 0.0;
-        }
-        self.frame as f32 / self.max_frames as f32
+        self.animation.progress()
     }
 }
 
@@ -73,36 +69,18 @@ impl<'a> RewindOverlay<'a> {
         Self { effect }
     }
 
-    /// Calculate scanline pattern
-    fn scanline_char(&self, y: u16) -> char {
-        // Alternating scanline pattern
-        if y % 2 == 0 {
-            '▀'
-        } else {
-            '▄'
-        }
-    }
-
     /// Calculate color based on rewind progress
     fn rewind_color(&self, progress: f32) -> Color {
         // Start bright blue, fade to dark
         let intensity = ((1.0 - progress) * 200.0) as u8;
-        Color::Rgb(
-            intensity / 4,
-            intensity / 3,
-            intensity.saturating_add(55),
-        )
+        Color::Rgb(intensity / 4, intensity / 3, intensity.saturating_add(55))
     }
 
     /// Calculate noise/tracking lines
     fn should_draw_tracking_line(&self, y: u16, progress: f32) -> bool {
         // Moving horizontal tracking lines
         let line_pos = ((progress * 100.0) as u16 * 3) % 100;
-        let dist = if y > line_pos {
-            y - line_pos
-        } else {
-            line_pos - y
-        };
+        let dist = y.abs_diff(line_pos);
         dist < 2
     }
 }
@@ -123,6 +101,7 @@ impl<'a> Widget for RewindOverlay<'a> {
         };
 
         let base_color = self.rewind_color(progress);
+        let frame = self.effect.frame();
 
         // Draw effect across entire area
         for y in area.y..area.y + area.height {
@@ -132,10 +111,10 @@ impl<'a> Widget for RewindOverlay<'a> {
                 }
 
                 let cell = &mut buf[(x, y)];
-                let frame_u16 = self.effect.frame as u16;
+                let frame_u16 = frame as u16;
 
                 // Apply scanline effect
-                if (y + frame_u16 / 2) % 3 == 0 {
+                if (y + frame_u16 / 2).is_multiple_of(3) {
                     let scanline_intensity = (intensity * 128.0) as u8;
                     let scanline_color = Color::Rgb(
                         scanline_intensity / 4,
@@ -148,17 +127,13 @@ impl<'a> Widget for RewindOverlay<'a> {
                 // Add tracking lines
                 if self.should_draw_tracking_line(y, progress) {
                     cell.set_char('─');
-                    cell.set_style(
-                        Style::default()
-                            .fg(base_color)
-                            .bg(Color::Rgb(10, 10, 30)),
-                    );
+                    cell.set_style(Style::default().fg(base_color).bg(Color::Rgb(10, 10, 30)));
                 }
 
                 // Add noise/static in certain areas
-                if (x + y + frame_u16) % 7 == 0 && intensity > 0.3 {
+                if (x + y + frame_u16).is_multiple_of(7) && intensity > 0.3 {
                     let chars = ['░', '▒', '▓', '█'];
-                    let char_idx = ((x as u32 * y as u32 + self.effect.frame) % chars.len() as u32) as usize;
+                    let char_idx = ((x as u32 * y as u32 + frame) % chars.len() as u32) as usize;
                     cell.set_char(chars[char_idx]);
                     let noise_intensity = (intensity * 100.0) as u8;
                     cell.set_fg(Color::Rgb(
@@ -169,7 +144,7 @@ impl<'a> Widget for RewindOverlay<'a> {
                 }
 
                 // Horizontal smearing effect (VHS tracking)
-                if (y + frame_u16) % 5 == 0 && (x as f32 / area.width as f32) < progress {
+                if (y + frame_u16).is_multiple_of(5) && (x as f32 / area.width as f32) < progress {
                     cell.set_char('▌');
                     cell.set_fg(base_color);
                 }
@@ -211,11 +186,11 @@ mod tests {
 
         effect.trigger();
         assert!(effect.is_active());
-        assert_eq!(effect.frame, 0);
+        assert_eq!(effect.frame(), 0);
 
         // Advance to completion
-        for _ in 0..effect.max_frames {
-            effect.tick();
+        for _ in 0..15 {
+            effect.update(FRAME_TIME);
         }
 
         assert!(!effect.is_active());
@@ -228,10 +203,11 @@ mod tests {
 
         assert_eq!(effect.progress(), 0.0);
 
-        effect.frame = effect.max_frames / 2;
-        assert!((effect.progress() - 0.5).abs() < 0.01);
+        effect.update(FRAME_TIME * 7);
+        assert_eq!(effect.frame(), 7);
+        assert!((effect.progress() - 7.0 / 15.0).abs() < 0.01);
 
-        effect.frame = effect.max_frames - 1;
+        effect.update(FRAME_TIME * 7);
         assert!(effect.progress() > 0.9);
     }
 }
@@ -2,9 +2,11 @@ use ratatui::{
     buffer::Buffer,
     layout::Rect,
     text::{Line, Span},
-    widgets::{Block, Borders, Widget},
+    widgets::Widget,
 };
 
+use revgame_core::debugger::{Frame, FrameTracker};
+
 use crate::Theme;
 
 /// Stack view widget
@@ -15,6 +17,12 @@ pub struct StackView<'a> {
     esp: u32,
     /// Whether this panel is focused
     focused: bool,
+    /// Call frames to delimit
+    frames: Option<&'a FrameTracker>,
+    /// Frame closed by the last RET, shown above ESP while it collapses
+    collapsing: Option<&'a Frame>,
+    /// Number of slots from ESP to label as arguments of a pending CALL
+    call_args: usize,
     /// Theme
     theme: &'a Theme,
 }
@@ -25,6 +33,9 @@ impl<'a> StackView<'a> {
             entries,
             esp,
             focused: false,
+            frames: None,
+            collapsing: None,
+            call_args: 0,
             theme,
         }
     }
@@ -33,20 +44,63 @@ impl<'a> StackView<'a> {
         self.focused = focused;
         self
     }
+
+    /// Mark the slots of each call frame, colored by nesting depth
+    pub fn frames(mut self, frames: &'a FrameTracker) -> Self {
+        self.frames = Some(frames);
+        self
+    }
+
+    /// Mark the slots of a frame that just returned
+    pub fn collapsing(mut self, frame: &'a Frame) -> Self {
+        self.collapsing = Some(frame);
+        self
+    }
+
+    /// Label the top `count` slots as the arguments a CALL would receive
+    pub fn call_args(mut self, count: usize) -> Self {
+        self.call_args = count;
+        self
+    }
+
+    /// Frame bar and role label for a slot
+    fn frame_annotation(&self, addr: u32) -> Option<(Span<'static>, Span<'static>)> {
+        let index = addr.wrapping_sub(self.esp) / 4;
+        if addr >= self.esp && (index as usize) < self.call_args {
+            let style = self.theme.changed();
+            return Some((
+                Span::styled(self.theme.frame_marker(), style),
+                Span::styled(format!(" arg {}?", index + 1), style),
+            ));
+        }
+
+        if let Some(frame) = self.collapsing {
+            if addr < self.esp && addr >= frame.lowest && addr <= frame.return_slot {
+                let style = self.theme.muted_style();
+                return Some((
+                    Span::styled(self.theme.frame_marker(), style),
+                    Span::styled(" returned", style),
+                ));
+            }
+        }
+
+        let (depth, role) = self.frames?.classify(addr, self.esp)?;
+        let style = self.theme.frame(depth);
+        Some((
+            Span::styled(self.theme.frame_marker(), style),
+            Span::styled(format!(" {}", role.label()), style),
+        ))
+    }
 }
 
 impl<'a> Widget for StackView<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let border_style = if self.focused {
-            self.theme.border_focused()
+        let title = if self.call_args > 0 {
+            " Stack (cdecl args) "
         } else {
-            self.theme.border_style()
+            " Stack "
         };
-
-        let block = Block::default()
-            .title(" Stack ")
-            .borders(Borders::ALL)
-            .border_style(border_style);
+        let block = self.theme.panel_block(title, self.focused);
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -63,13 +117,15 @@ impl<'a> Widget for StackView<'a> {
             }
 
             let y = inner.y + i as u16;
-            let offset = addr.wrapping_sub(self.esp);
+            let offset = addr.wrapping_sub(self.esp) as i32;
             let is_top = *addr == self.esp;
 
             let offset_str = if offset == 0 {
                 "ESP".to_string()
-            } else {
+            } else if offset > 0 {
                 format!("+{:02X}", offset)
+            } else {
+                format!("-{:02X}", offset.unsigned_abs())
             };
 
             let value_style = if is_top {
@@ -78,10 +134,20 @@ impl<'a> Widget for StackView<'a> {
                 self.theme.register_value()
             };
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::styled(format!("{}: ", offset_str), self.theme.address()),
                 Span::styled(format!("0x{:08X}", value), value_style),
-            ]);
+            ];
+            if self.frames.is_some() || self.call_args > 0 {
+                match self.frame_annotation(*addr) {
+                    Some((bar, label)) => {
+                        spans.insert(0, bar);
+                        spans.push(label);
+                    }
+                    None => spans.insert(0, Span::raw(" ")),
+                }
+            }
+            let line = Line::from(spans);
 
             buf.set_line(inner.x, y, &line, inner.width);
         }
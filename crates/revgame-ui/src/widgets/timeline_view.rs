@@ -0,0 +1,96 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use revgame_core::debugger::Timeline;
+
+use crate::Theme;
+
+/// Execution timeline under the disassembly: a track of the instructions
+/// run since the program started, with marks for breakpoint hits, patches
+/// and snapshots and a cursor at the current instruction
+pub struct TimelineView<'a> {
+    timeline: &'a Timeline,
+    /// Instructions executed since the program started
+    position: u64,
+    theme: &'a Theme,
+}
+
+impl<'a> TimelineView<'a> {
+    pub fn new(timeline: &'a Timeline, position: u64, theme: &'a Theme) -> Self {
+        Self {
+            timeline,
+            position,
+            theme,
+        }
+    }
+
+    /// Column of the track showing `position`
+    fn column(position: u64, width: u16, length: u64) -> u16 {
+        if length == 0 || width < 2 {
+            return 0;
+        }
+        (position.min(length) * u64::from(width - 1) / length) as u16
+    }
+
+    /// Position a click on `column` of a `width`-wide track goes to
+    pub fn position_at(column: u16, width: u16, length: u64) -> u64 {
+        if width < 2 {
+            return 0;
+        }
+        let column = u64::from(column.min(width - 1));
+        let last = u64::from(width - 1);
+        (column * length + last / 2) / last
+    }
+}
+
+impl<'a> Widget for TimelineView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let length = self.timeline.length();
+        let cursor = Self::column(self.position, area.width, length);
+
+        // Track: run so far in the accent color, the rest muted
+        let track = self.theme.timeline_track();
+        for x in 0..area.width {
+            let style = if x <= cursor {
+                self.theme.highlight()
+            } else {
+                self.theme.muted_style()
+            };
+            buf.set_string(area.x + x, area.y, track, style);
+        }
+        for marker in self.timeline.markers() {
+            let x = Self::column(marker.position, area.width, length);
+            buf.set_string(
+                area.x + x,
+                area.y,
+                self.theme.timeline_marker(marker.mark),
+                self.theme.timeline_mark(marker.mark),
+            );
+        }
+        buf.set_string(
+            area.x + cursor,
+            area.y,
+            self.theme.timeline_cursor(),
+            self.theme.highlight(),
+        );
+
+        if area.height > 1 {
+            let label = Line::from(vec![
+                Span::styled(
+                    format!("instr {} / {}", self.position, length),
+                    self.theme.normal(),
+                ),
+                Span::styled("   [ ] step  { } marks", self.theme.muted_style()),
+            ]);
+            buf.set_line(area.x, area.y + 1, &label, area.width);
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::{Line, Span, Text},
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
 };
 
+use revgame_core::reference::Glossary;
+
 use crate::tutorial::{ArrowDirection, HighlightTarget, TutorialStep};
 use crate::Theme;
 
@@ -18,6 +20,8 @@ pub struct DebuggerLayout {
     pub stack: Rect,
     pub command: Rect,
     pub status: Rect,
+    /// Execution timeline under the disassembly (empty when hidden)
+    pub timeline: Rect,
 }
 
 /// Tutorial overlay widget
@@ -75,7 +79,7 @@ impl<'a> TutorialOverlay<'a> {
 
     /// Draw a highlight border around a rect
     fn draw_highlight(&self, area: Rect, buf: &mut Buffer) {
-        let highlight_color = Color::Rgb(249, 226, 175); // Yellow
+        let highlight_color = self.theme.warning;
 
         // Top border
         for x in area.x..area.x + area.width {
@@ -118,7 +122,7 @@ impl<'a> TutorialOverlay<'a> {
 
     /// Draw an arrow pointing to a rect
     fn draw_arrow(&self, target: Rect, direction: ArrowDirection, buf: &mut Buffer) {
-        let arrow_color = Color::Rgb(166, 227, 161); // Green
+        let arrow_color = self.theme.success;
 
         match direction {
             ArrowDirection::Right => {
@@ -133,7 +137,11 @@ impl<'a> TutorialOverlay<'a> {
                         if x < buf.area().width && x < target.x {
                             let cell = &mut buf[(x, arrow_y)];
                             cell.set_char(*ch);
-                            cell.set_style(Style::default().fg(arrow_color).add_modifier(Modifier::BOLD));
+                            cell.set_style(
+                                Style::default()
+                                    .fg(arrow_color)
+                                    .add_modifier(Modifier::BOLD),
+                            );
                         }
                     }
                 }
@@ -150,7 +158,11 @@ impl<'a> TutorialOverlay<'a> {
                         if x < buf.area().width {
                             let cell = &mut buf[(x, arrow_y)];
                             cell.set_char(*ch);
-                            cell.set_style(Style::default().fg(arrow_color).add_modifier(Modifier::BOLD));
+                            cell.set_style(
+                                Style::default()
+                                    .fg(arrow_color)
+                                    .add_modifier(Modifier::BOLD),
+                            );
                         }
                     }
                 }
@@ -167,7 +179,11 @@ impl<'a> TutorialOverlay<'a> {
                         if y < buf.area().height && y < target.y {
                             let cell = &mut buf[(arrow_x, y)];
                             cell.set_char(*ch);
-                            cell.set_style(Style::default().fg(arrow_color).add_modifier(Modifier::BOLD));
+                            cell.set_style(
+                                Style::default()
+                                    .fg(arrow_color)
+                                    .add_modifier(Modifier::BOLD),
+                            );
                         }
                     }
                 }
@@ -184,7 +200,11 @@ impl<'a> TutorialOverlay<'a> {
                         if y < buf.area().height {
                             let cell = &mut buf[(arrow_x, y)];
                             cell.set_char(*ch);
-                            cell.set_style(Style::default().fg(arrow_color).add_modifier(Modifier::BOLD));
+                            cell.set_style(
+                                Style::default()
+                                    .fg(arrow_color)
+                                    .add_modifier(Modifier::BOLD),
+                            );
                         }
                     }
                 }
@@ -198,10 +218,12 @@ impl<'a> TutorialOverlay<'a> {
         let text_lines = self.step.text.lines().count() as u16;
         let title_line = 1u16;
         let hint_line = if self.step.hint.is_some() { 2 } else { 0 };
+        let concept_line = if self.step.concept.is_some() { 2 } else { 0 };
         let progress_line = 1u16;
         let padding = 4u16; // borders + margins
 
-        let height = (text_lines + title_line + hint_line + progress_line + padding).min(area.height - 4);
+        let height = (text_lines + title_line + hint_line + concept_line + progress_line + padding)
+            .min(area.height - 4);
         let width = 50.min(area.width - 10);
 
         // Position based on highlight target
@@ -277,17 +299,14 @@ impl<'a> Widget for TutorialOverlay<'a> {
         lines.push(Line::from(Span::styled(
             &self.step.title,
             Style::default()
-                .fg(Color::Rgb(137, 180, 250)) // Blue
+                .fg(self.theme.accent)
                 .add_modifier(Modifier::BOLD),
         )));
         lines.push(Line::from(""));
 
         // Main text
         for text_line in self.step.text.lines() {
-            lines.push(Line::from(Span::styled(
-                text_line,
-                Style::default().fg(Color::Rgb(205, 214, 244)), // Light text
-            )));
+            lines.push(Line::from(Span::styled(text_line, self.theme.normal())));
         }
 
         // Hint if present
@@ -296,16 +315,32 @@ impl<'a> Widget for TutorialOverlay<'a> {
             lines.push(Line::from(Span::styled(
                 format!("→ {}", hint),
                 Style::default()
-                    .fg(Color::Rgb(166, 227, 161)) // Green
+                    .fg(self.theme.success)
                     .add_modifier(Modifier::BOLD),
             )));
         }
 
+        // Link to the concept behind this step
+        if let Some(concept) = self
+            .step
+            .concept
+            .and_then(|id| Glossary::new().lookup(id).cloned())
+        {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("Press g to read about {}", concept.title),
+                self.theme.muted_style(),
+            )));
+        }
+
         // Progress indicator
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            format!("Step {}/{} • Press ESC to skip tutorial", self.progress.0, self.progress.1),
-            Style::default().fg(Color::Rgb(127, 132, 156)), // Muted
+            format!(
+                "Step {}/{} • Press ESC to skip tutorial",
+                self.progress.0, self.progress.1
+            ),
+            self.theme.muted_style(),
         )));
 
         // Create the dialog
@@ -313,8 +348,8 @@ impl<'a> Widget for TutorialOverlay<'a> {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Rgb(137, 180, 250)))
-                    .style(Style::default().bg(Color::Rgb(30, 30, 46))),
+                    .border_style(self.theme.border_focused())
+                    .style(Style::default().bg(self.theme.bg)),
             )
             .wrap(Wrap { trim: false });
 
@@ -0,0 +1,372 @@
+//! Fuzz-style tests: seeded mutations of the bundled puzzles go through
+//! `App::load_puzzle`, random and deeply nested script conditions through
+//! the puzzle script parser, and random command lines through
+//! `App::process_command` and `MemorySearch::parse_hex_pattern`. Any panic
+//! fails the test with the input that caused it; errors are fine.
+//!
+//! Set REVGAME_FUZZ_CASES to run more inputs than the default, and
+//! REVGAME_FUZZ_SEED to explore a different stream.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use revgame_core::{debugger::MemorySearch, emulator::Prng, game::SaveManager};
+use revgame_ui::App;
+
+/// Inputs per test unless REVGAME_FUZZ_CASES says otherwise
+const DEFAULT_CASES: usize = 1_000;
+
+const PUZZLES: [&str; 8] = [
+    include_str!("../../../puzzles/01-basics/001-first-patch.toml"),
+    include_str!("../../../puzzles/02-control-flow/001-double-check.toml"),
+    include_str!("../../../puzzles/03-crackmes/002-xor-decrypt.toml"),
+    include_str!("../../../puzzles/03-crackmes/004-stdin-serial.toml"),
+    include_str!("../../../puzzles/03-crackmes/001-simple-serial.toml"),
+    include_str!("../../../puzzles/06-integrity/002-timing-check.toml"),
+    include_str!("../../../puzzles/07-obfuscation/003-flattened.toml"),
+    include_str!("../../../puzzles/08-packers/001-xor-stub.toml"),
+];
+
+/// Fragments likely to upset a parser
+const TOKENS: [&str; 24] = [
+    "0",
+    "-1",
+    "0x",
+    "0xFFFFFFFF",
+    "0x100000000",
+    "4294967296",
+    "99999999999999999999",
+    "\"",
+    "\"\"\"",
+    "[",
+    "]",
+    "[[",
+    "=",
+    ",",
+    "\n",
+    "#",
+    "$",
+    "é",
+    "\u{0}",
+    "🦀",
+    " ",
+    "0xFFFFFFF0",
+    "eax",
+    "90 90",
+];
+
+/// Pieces of script conditions, brackets and prefix operators included
+const CONDITION_PARTS: [&str; 18] = [
+    "(",
+    ")",
+    "- ",
+    "!",
+    "byte(",
+    "dword(",
+    "expr(\"eax\")",
+    "reg(\"eax\")",
+    "flag(\"zf\")",
+    "0x10",
+    "1",
+    " + ",
+    " * ",
+    " == ",
+    " && ",
+    " || ",
+    " << ",
+    " / ",
+];
+
+/// Pieces that nest when repeated, as a hostile puzzle might
+const NESTING_PARTS: [&str; 6] = ["(", "- ", "!", "byte(", "1 + ", "if true { "];
+
+/// Commands to fuzz; `export` and `quit` are left out because they write
+/// files where they're told to or end the session
+const COMMANDS: [&str; 60] = [
+    "help",
+    "step",
+    "s",
+    "run",
+    "r",
+    "reset",
+    "undo",
+    "redo",
+    "bp",
+    "patch",
+    "patch!",
+    "hint",
+    "devices",
+    "seed",
+    "cycles",
+    "check",
+    "snapshot",
+    "seek",
+    "explain",
+    "diff",
+    "objectives",
+    "assist",
+    "decode",
+    "entropy",
+    "learning",
+    "copy",
+    "share",
+    "duel",
+    "compare",
+    "tab",
+    "constants",
+    "ascii",
+    "follow",
+    "alias",
+    "unalias",
+    "search",
+    "find",
+    "reveal",
+    "calc",
+    "predict",
+    "pin",
+    "unpin",
+    "watch",
+    "input",
+    "encoding",
+    "pseudo",
+    "learn",
+    "disasm",
+    "set",
+    "script",
+    "console",
+    "plugins",
+    "panel",
+    "goal",
+    "online",
+    "yank",
+    "snap",
+    "h",
+    "u",
+    "?",
+];
+
+/// Arguments for the commands above
+const ARGS: [&str; 36] = [
+    "0x1000",
+    "0x2000",
+    "1000",
+    "0",
+    "-1",
+    "eax",
+    "esp+4",
+    "[eax]",
+    "[[esp]]",
+    "90",
+    "90 90",
+    "ff",
+    "zz",
+    "0xFFFFFFFF",
+    "0xFFFFFFFE",
+    "4294967296",
+    "1+",
+    "(",
+    "*0",
+    "/0",
+    "%0",
+    "1/0",
+    "on",
+    "off",
+    "all",
+    "new",
+    "close",
+    "next",
+    "pass",
+    "xor",
+    "base64",
+    "$sel",
+    "$eip",
+    "=",
+    "é",
+    "🦀",
+];
+
+fn cases() -> usize {
+    std::env::var("REVGAME_FUZZ_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(DEFAULT_CASES)
+}
+
+fn seed(offset: u32) -> u32 {
+    let seed = std::env::var("REVGAME_FUZZ_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(0xF022);
+    seed ^ offset
+}
+
+/// Keep logs and crash reports, which bypass the save manager, out of the
+/// real save directory
+fn sandbox_home() {
+    let home = std::env::temp_dir().join(format!("revgame-fuzz-{}", std::process::id()));
+    std::fs::create_dir_all(&home).unwrap();
+    std::env::set_var("HOME", &home);
+    std::env::set_var("APPDATA", &home);
+    std::env::remove_var("XDG_DATA_HOME");
+    std::env::remove_var("REVGAME_PORTABLE");
+}
+
+fn pick<'a>(rng: &mut Prng, items: &[&'a str]) -> &'a str {
+    items[rng.next_u32() as usize % items.len()]
+}
+
+/// Run `f`, failing with `input` if it panics
+fn no_panic(what: &str, input: &str, f: impl FnOnce()) {
+    if catch_unwind(AssertUnwindSafe(f)).is_err() {
+        panic!("{} panicked on input:\n{:?}", what, input);
+    }
+}
+
+/// A char boundary at or before `at`
+fn boundary(text: &str, mut at: usize) -> usize {
+    at = at.min(text.len());
+    while !text.is_char_boundary(at) {
+        at -= 1;
+    }
+    at
+}
+
+/// `text` with a few random edits: cut, duplicated or overwritten spans,
+/// inserted tokens, and numbers swapped for extreme ones
+fn mutate(rng: &mut Prng, text: &str) -> String {
+    let mut text = text.to_string();
+    for _ in 0..1 + rng.next_u32() % 4 {
+        let len = text.len().max(1);
+        let start = boundary(&text, rng.next_u32() as usize % len);
+        let end = boundary(&text, start + rng.next_u32() as usize % 16);
+        match rng.next_u32() % 5 {
+            0 => text.replace_range(start..end, ""),
+            1 => {
+                let span = text[start..end].to_string();
+                text.insert_str(end, &span);
+            }
+            2 => text.replace_range(start..end, pick(rng, &TOKENS)),
+            3 => text.insert_str(start, pick(rng, &TOKENS)),
+            _ => {
+                // The next number after `start`
+                let Some(offset) = text[start..].find(|c: char| c.is_ascii_digit()) else {
+                    continue;
+                };
+                let from = start + offset;
+                let to = text[from..]
+                    .find(|c: char| !c.is_ascii_alphanumeric())
+                    .map_or(text.len(), |len| from + len);
+                text.replace_range(from..to, pick(rng, &TOKENS[..7]));
+            }
+        }
+    }
+    text
+}
+
+#[test]
+fn load_puzzle_never_panics() {
+    sandbox_home();
+    let mut rng = Prng::new(seed(1));
+    for _ in 0..cases() {
+        let original = pick(&mut rng, &PUZZLES);
+        let toml = mutate(&mut rng, original);
+        no_panic("load_puzzle", &toml, || {
+            let mut app = App::with_save_manager(SaveManager::in_memory());
+            if app.load_puzzle(&toml).is_ok() {
+                app.begin_puzzle();
+                for _ in 0..3 {
+                    app.step();
+                }
+            }
+        });
+    }
+}
+
+#[test]
+fn process_command_never_panics() {
+    sandbox_home();
+    let mut rng = Prng::new(seed(2));
+    let mut app = App::with_save_manager(SaveManager::in_memory());
+    let mut puzzle = 0;
+    for case in 0..cases() {
+        // A fresh puzzle now and then, so one stuck state doesn't hide the rest
+        if case % 50 == 0 {
+            app = App::with_save_manager(SaveManager::in_memory());
+            app.load_puzzle(PUZZLES[puzzle % PUZZLES.len()]).unwrap();
+            app.begin_puzzle();
+            puzzle += 1;
+        }
+        let mut line = pick(&mut rng, &COMMANDS).to_string();
+        for _ in 0..rng.next_u32() % 4 {
+            line.push(' ');
+            line.push_str(&match rng.next_u32() % 4 {
+                0 => pick(&mut rng, &TOKENS).to_string(),
+                1 => format!("0x{:X}", rng.next_u32()),
+                _ => pick(&mut rng, &ARGS).to_string(),
+            });
+        }
+        no_panic("process_command", &line, || app.process_command(&line));
+    }
+}
+
+#[test]
+fn script_conditions_never_panic() {
+    // The stdin crackme has a script; its pass statement is swapped out
+    let original = PUZZLES[3];
+    assert!(original.contains(r#"if reg("eax") == 1 { pass(); }"#));
+    let mut rng = Prng::new(seed(4));
+    for case in 0..cases() {
+        // Every tenth statement nests far deeper than the stack could take
+        // unchecked
+        let statement = if case % 10 == 0 {
+            let depth = 10_000 + (rng.next_u32() % 10_000) as usize;
+            match pick(&mut rng, &NESTING_PARTS) {
+                "if true { " => {
+                    format!("{}pass();{}", "if true { ".repeat(depth), "}".repeat(depth))
+                }
+                part => {
+                    let close = if part.ends_with('(') { ")" } else { "" };
+                    format!(
+                        "if {}1{} {{ pass(); }}",
+                        part.repeat(depth),
+                        close.repeat(depth)
+                    )
+                }
+            }
+        } else {
+            let condition: String = (0..1 + rng.next_u32() % 12)
+                .map(|_| pick(&mut rng, &CONDITION_PARTS))
+                .collect();
+            format!("if {} {{ pass(); }}", condition)
+        };
+        let toml = original.replace(r#"if reg("eax") == 1 { pass(); }"#, &statement);
+        no_panic("puzzle script", &statement, || {
+            let mut app = App::with_save_manager(SaveManager::in_memory());
+            let loaded = app.load_puzzle(&toml);
+            if case % 10 == 0 {
+                assert!(
+                    loaded.is_err_and(|e| e.contains("exceeds maximum complexity")),
+                    "{:.40}... loaded",
+                    statement
+                );
+            } else if loaded.is_ok() {
+                app.begin_puzzle();
+                app.run();
+            }
+        });
+    }
+}
+
+#[test]
+fn parse_hex_pattern_never_panics() {
+    let mut rng = Prng::new(seed(3));
+    for _ in 0..cases() {
+        let text: String = (0..rng.next_u32() % 6)
+            .map(|_| pick(&mut rng, &ARGS))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let text = mutate(&mut rng, &text);
+        no_panic("parse_hex_pattern", &text, || {
+            MemorySearch::parse_hex_pattern(&text).ok();
+        });
+    }
+}
@@ -0,0 +1,135 @@
+//! Replay regression tests: every bundled puzzle has a reference solution
+//! in `tests/replays/<puzzle id>.toml`. Each one is played through the
+//! headless app, checking the instructions run and patches made after every
+//! command against the recorded ones, and that the puzzle ends solved. A
+//! change to the emulator that breaks a puzzle fails here first.
+//!
+//! After a deliberate change to how puzzles run, record the new counts with
+//!
+//! ```text
+//! UPDATE_REPLAYS=1 cargo test -p revgame-ui --test replays
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use revgame_core::game::{Replay, SaveManager, CAMPAIGN_FILE};
+use revgame_ui::{app::Screen, App};
+
+fn puzzles_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../puzzles")
+}
+
+fn replays_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/replays")
+}
+
+/// Every puzzle file under `dir`, in name order, skipping the campaign file
+fn puzzle_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(puzzle_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "toml")
+            && !path.ends_with(CAMPAIGN_FILE)
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Play the reference replay for the puzzle at `path`, giving the replay
+/// with the counts this run produced
+fn play(path: &Path) -> Result<Replay, String> {
+    // Saves stay in memory, out of the player's save directory
+    let mut app = App::with_save_manager(SaveManager::in_memory());
+    app.pin(1, 0);
+    app.load_puzzle_file(path)?;
+    app.begin_puzzle();
+    let id = app.puzzle.as_ref().unwrap().metadata.id.clone();
+
+    let replay_path = replays_dir().join(format!("{}.toml", id));
+    let source = std::fs::read_to_string(&replay_path)
+        .map_err(|_| format!("{}: no reference replay at {}", id, replay_path.display()))?;
+    let mut replay = Replay::parse(&source)?;
+    if replay.puzzle != id {
+        return Err(format!("{}: replay is for {}", id, replay.puzzle));
+    }
+
+    let update = std::env::var_os("UPDATE_REPLAYS").is_some();
+    for event in &mut replay.events {
+        app.process_command(&event.command);
+        if let Some(ref message) = app.message {
+            if message.is_error {
+                return Err(format!(
+                    "{}: `{}` failed: {}",
+                    id, event.command, message.text
+                ));
+            }
+        }
+        let debugger = app.debugger.as_ref().unwrap();
+        let actual = (debugger.total_instructions, debugger.undo_count());
+        let expected = (
+            event.instructions.unwrap_or_default(),
+            event.patches.unwrap_or_default(),
+        );
+        if update {
+            (event.instructions, event.patches) = (Some(actual.0), Some(actual.1));
+        } else if actual != expected {
+            return Err(format!(
+                "{}: after `{}`, {} instructions and {} patches, expected {} and {}",
+                id, event.command, actual.0, actual.1, expected.0, expected.1
+            ));
+        }
+    }
+
+    if !matches!(app.screen, Screen::PuzzleComplete { .. }) {
+        return Err(format!("{}: not solved at the end of the replay", id));
+    }
+    if update {
+        std::fs::write(&replay_path, replay.to_toml()?).map_err(|e| e.to_string())?;
+    }
+    Ok(replay)
+}
+
+#[test]
+fn bundled_puzzles_solve_with_reference_replays() {
+    let files = puzzle_files(&puzzles_dir());
+    assert!(
+        !files.is_empty(),
+        "no puzzles in {}",
+        puzzles_dir().display()
+    );
+
+    let failures: Vec<String> = files.iter().filter_map(|path| play(path).err()).collect();
+    assert!(
+        failures.is_empty(),
+        "{} of {} puzzles failed:\n{}",
+        failures.len(),
+        files.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn every_replay_has_a_puzzle() {
+    let mut ids: Vec<String> = puzzle_files(&puzzles_dir())
+        .iter()
+        .map(|path| {
+            revgame_core::puzzle::load_puzzle_from_file(path).map(|puzzle| puzzle.metadata.id)
+        })
+        .collect::<Result<_, _>>()
+        .unwrap();
+    ids.sort();
+    for entry in std::fs::read_dir(replays_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        let id = path.file_stem().unwrap().to_string_lossy().to_string();
+        assert!(
+            ids.binary_search(&id).is_ok(),
+            "{} has no puzzle",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Snapshot tests: each screen rendered off-screen and compared, as plain
+//! text, with the copy under `tests/snapshots`. After a deliberate UI
+//! change, regenerate them with
+//!
+//! ```text
+//! UPDATE_SNAPSHOTS=1 cargo test -p revgame-ui --test screens
+//! ```
+//!
+//! and review the diff before committing.
+
+use std::path::PathBuf;
+
+use ratatui::{backend::TestBackend, Terminal};
+use revgame_core::game::SaveManager;
+use revgame_ui::{
+    app::{App, Screen},
+    export::buffer_to_text,
+    screens::{render_screen, AsciiTableState},
+};
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 40;
+
+const PUZZLE: &str = include_str!("../../../puzzles/01-basics/001-first-patch.toml");
+
+/// A fresh app with the seed and clock pinned, saving only in memory
+fn app() -> App {
+    let mut app = App::with_save_manager(SaveManager::in_memory());
+    app.pin(0x5EED, 1_700_000_000_000);
+    app
+}
+
+/// A pinned app with the first puzzle open in the debugger
+fn puzzle_app() -> App {
+    let mut app = app();
+    app.load_puzzle(PUZZLE).unwrap();
+    app.begin_puzzle();
+    app.screen = Screen::Debugger;
+    app
+}
+
+fn render(app: &App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(|frame| render_screen(frame, app)).unwrap();
+    buffer_to_text(terminal.backend().buffer())
+}
+
+/// Compare what `app` shows with snapshot `name`, or rewrite the snapshot
+/// when UPDATE_SNAPSHOTS is set
+fn assert_snapshot(name: &str, app: &App) {
+    let actual = render(app);
+    assert_eq!(
+        actual,
+        render(app),
+        "{} renders differently each time",
+        name
+    );
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.txt", name));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "No snapshot {}; run with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    assert!(
+        actual == expected,
+        "{} doesn't match its snapshot; rerun with UPDATE_SNAPSHOTS=1 if the change is intended\n\
+         --- expected\n{}\n--- actual\n{}",
+        name,
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn main_menu() {
+    assert_snapshot("main_menu", &app());
+}
+
+#[test]
+fn debugger() {
+    let mut app = puzzle_app();
+    app.step();
+    app.step();
+    assert_snapshot("debugger", &app);
+}
+
+#[test]
+fn briefing() {
+    let mut app = puzzle_app();
+    app.screen = Screen::Briefing;
+    assert_snapshot("briefing", &app);
+}
+
+#[test]
+fn reference() {
+    let mut app = app();
+    app.screen = Screen::Reference;
+    assert_snapshot("reference", &app);
+}
+
+#[test]
+fn achievements() {
+    let mut app = app();
+    app.screen = Screen::Achievements;
+    assert_snapshot("achievements", &app);
+}
+
+#[test]
+fn settings() {
+    let mut app = app();
+    app.screen = Screen::Settings;
+    assert_snapshot("settings", &app);
+}
+
+#[test]
+fn ascii_only() {
+    let mut app = puzzle_app();
+    app.ascii_terminal = true;
+    app.apply_settings();
+    app.step();
+    assert_snapshot("debugger_ascii", &app);
+
+    for screen in [
+        Screen::MainMenu,
+        Screen::Achievements,
+        Screen::Reference,
+        Screen::Settings,
+    ] {
+        app.screen = screen;
+        let text = render(&app);
+        assert!(
+            text.is_ascii(),
+            "{:?} isn't all ASCII:\n{}",
+            app.screen,
+            text
+        );
+    }
+}
+
+#[test]
+fn puzzle_complete() {
+    let mut app = puzzle_app();
+    app.screen = Screen::PuzzleComplete {
+        message: "EAX = 1, well done".to_string(),
+    };
+    assert_snapshot("puzzle_complete", &app);
+}
+
+#[test]
+fn goto_dialog() {
+    let mut app = puzzle_app();
+    app.goto_dialog_open = true;
+    app.goto_state.input = "0x1004".to_string();
+    assert_snapshot("goto_dialog", &app);
+}
+
+#[test]
+fn hard_reset_dialog() {
+    let mut app = puzzle_app();
+    app.hard_reset_confirm_open = true;
+    assert_snapshot("hard_reset_dialog", &app);
+}
+
+#[test]
+fn ascii_table() {
+    let mut app = puzzle_app();
+    app.ascii_table = Some(AsciiTableState::new());
+    assert_snapshot("ascii_table", &app);
+}
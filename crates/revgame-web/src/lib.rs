@@ -13,7 +13,14 @@ pub fn start() {
 
     log::info!("RevGame web version starting...");
 
-    // TODO: Initialize Ratzilla and start the TUI app
+    // TODO: Initialize Ratzilla and start the TUI app. Saves go to
+    // `MemoryBackend::shared()` and are lost with the page until something
+    // writes them to IndexedDB. Touch events go
+    // through `revgame_ui::touch::GestureRecognizer` into `handle_gesture`,
+    // and the first one turns on `App::touch_controls`. A `#puzzle=...`
+    // location hash goes to `App::load_shared_puzzle` instead of the main
+    // menu, or to `App::start_demo` with a `?demo` query, calling
+    // `App::advance_demo` every frame.
 }
 
 #[wasm_bindgen]